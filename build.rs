@@ -3,14 +3,23 @@ use std::fs::File;
 use std::io;
 use std::io::Write;
 use std::path::Path;
+use std::thread;
+
+use mago_interner::ThreadedInterner;
+use mago_names::Names;
+use mago_reflector::stubs::StubReflection;
+use mago_source::SourceManager;
 
 pub fn main() -> io::Result<()> {
     println!("cargo:rustc-env=TARGET={}", std::env::var("TARGET").unwrap());
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=stubs");
     // Determine the stubs directory and output path
     let stubs_dir = Path::new("stubs");
 
     let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR environment variable not set");
     let output_file = Path::new(&out_dir).join("stubs_map.rs");
+    let reflections_file = Path::new(&out_dir).join("stubs.bin");
 
     // Ensure the stubs directory exists
     if !stubs_dir.exists() {
@@ -21,15 +30,17 @@ pub fn main() -> io::Result<()> {
     let mut stubs_map = Vec::new();
     collect_files(stubs_dir, stubs_dir, &mut stubs_map)?;
 
+    stubs_map.retain(|(simplified_path, _)| !simplified_path.ends_with(".phpstorm.meta.php"));
+    stubs_map.sort();
+
+    // Reflect the stubs, so that they do not need to be parsed at runtime
+    fs::write(reflections_file, reflect_stubs(&stubs_map)?)?;
+
     // Prepare the map content
     let map_content = stubs_map
         .into_iter()
-        .filter_map(|(simplified_path, include_path)| {
-            if simplified_path.ends_with(".phpstorm.meta.php") {
-                None
-            } else {
-                Some(format!(r##"    (r#"{simplified_path}"#, include_str!("{include_path}"))"##))
-            }
+        .map(|(simplified_path, include_path)| {
+            format!(r##"    (r#"{simplified_path}"#, include_str!("{include_path}"))"##)
         })
         .collect::<Vec<_>>();
     let count = map_content.len();
@@ -47,6 +58,49 @@ pub fn main() -> io::Result<()> {
     Ok(())
 }
 
+/// Reflects the given stubs for each version of PHP, under the names they are loaded with, and encodes the
+/// reflections in the format read by `mago_reflector::stubs::decode`.
+fn reflect_stubs(stubs: &[(String, String)]) -> io::Result<Vec<u8>> {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let mut sources = vec![];
+    for (name, path) in stubs {
+        sources.push(manager.insert_content(name.clone(), fs::read_to_string(path)?, false));
+    }
+
+    let threads = thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+    let chunk_size = sources.len().div_ceil(threads).max(1);
+
+    let mut reflections: Vec<StubReflection> = vec![];
+    thread::scope(|scope| {
+        let tasks = sources
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let (interner, manager) = (&interner, &manager);
+
+                scope.spawn(move || {
+                    let mut reflections = vec![];
+                    for source_id in chunk {
+                        let source = manager.load(source_id).expect("stubs are loaded from memory");
+                        let (program, _) = mago_parser::parse_source(interner, &source);
+                        let names = Names::resolve(interner, &program);
+
+                        reflections.extend(mago_reflector::stubs::reflect(interner, &source, &program, &names));
+                    }
+
+                    reflections
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for task in tasks {
+            reflections.extend(task.join().expect("failed to reflect the stubs"));
+        }
+    });
+
+    mago_reflector::stubs::encode(&interner, &reflections).map_err(io::Error::other)
+}
+
 fn collect_files(root: &Path, dir: &Path, stubs_map: &mut Vec<(String, String)>) -> io::Result<()> {
     let file_separator = if cfg!(target_os = "windows") { "\\" } else { "/" };
 
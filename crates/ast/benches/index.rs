@@ -0,0 +1,40 @@
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+use mago_ast::index::NodeIndex;
+use mago_interner::ThreadedInterner;
+use mago_parser::parse_source;
+use mago_source::SourceManager;
+
+/// The largest of the vendored PHP stubs, of about 300KB, mostly made of documented classes and functions.
+const CONTENT: &str = include_str!("../../../stubs/intl/intl.php");
+
+fn bench_node_index(c: &mut Criterion) {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source = manager.load(&manager.insert_content("intl.php".to_string(), CONTENT.to_string(), true)).unwrap();
+    let (program, _) = parse_source(&interner, &source);
+
+    let index = NodeIndex::new(&program);
+    let middle = CONTENT[CONTENT.len() / 2..].find("public function").unwrap() + CONTENT.len() / 2 + 20;
+    let end = CONTENT.rfind("function").unwrap() + 10;
+    let node = index.at_offset(middle).unwrap();
+
+    let mut group = c.benchmark_group("index");
+    group.bench_function("new", |b| b.iter(|| black_box(NodeIndex::new(black_box(&program)))));
+    group.bench_function("at_offset/middle", |b| b.iter(|| black_box(index.at_offset(black_box(middle)))));
+    group.bench_function("at_offset/end", |b| b.iter(|| black_box(index.at_offset(black_box(end)))));
+    group.bench_function("enclosing_class_like", |b| b.iter(|| black_box(index.enclosing_class_like(black_box(node)))));
+    group.bench_function("get", |b| b.iter(|| black_box(index.get(&program, black_box(node)))));
+    group.bench_function("id_of", |b| {
+        let node = index.get(&program, node);
+
+        b.iter(|| black_box(index.id_of(black_box(node))))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_node_index);
+criterion_main!(benches);
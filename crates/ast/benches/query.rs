@@ -0,0 +1,58 @@
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+use mago_ast::query::enclosing_class_like;
+use mago_ast::query::enclosing_function;
+use mago_ast::query::node_at_offset;
+use mago_interner::ThreadedInterner;
+use mago_parser::parse_source;
+use mago_source::SourceManager;
+
+/// Generates a source of about 10,000 lines, made of classes with methods containing a few statements.
+fn generate_source() -> String {
+    let mut content = String::from("<?php\n\nnamespace App;\n\n");
+    for class in 0..100 {
+        content.push_str(&format!("final class Service{class}\n{{\n"));
+        for method in 0..10 {
+            content.push_str(&format!("    public function method{method}(int $value): int\n    {{\n"));
+            content.push_str("        $result = $value * 2;\n");
+            content.push_str("        if ($result > 10) {\n");
+            content
+                .push_str("            $result = array_sum(array_map(fn($item) => $item + 1, [$result, $value]));\n");
+            content.push_str("        }\n\n");
+            content.push_str("        return $result;\n    }\n\n");
+        }
+
+        content.push_str("}\n\n");
+    }
+
+    content
+}
+
+fn bench_node_at_offset(c: &mut Criterion) {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let content = generate_source();
+    let source = manager.load(&manager.insert_content("query.php".to_string(), content.clone(), true)).unwrap();
+    let (program, _) = parse_source(&interner, &source);
+
+    let middle = content[content.len() / 2..].find("$item + 1").unwrap() + content.len() / 2;
+    let end = content.rfind("return").unwrap();
+
+    let mut group = c.benchmark_group("query");
+    group
+        .bench_function("node_at_offset/middle", |b| b.iter(|| black_box(node_at_offset(&program, black_box(middle)))));
+    group.bench_function("node_at_offset/end", |b| b.iter(|| black_box(node_at_offset(&program, black_box(end)))));
+    group.bench_function("enclosing_function", |b| {
+        b.iter(|| black_box(enclosing_function(&program, black_box(middle))))
+    });
+    group.bench_function("enclosing_class_like", |b| {
+        b.iter(|| black_box(enclosing_class_like(&program, black_box(middle))))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_node_at_offset);
+criterion_main!(benches);
@@ -61,7 +61,9 @@ impl HasSpan for MatchArm {
 
 impl HasSpan for MatchExpressionArm {
     fn span(&self) -> Span {
-        Span::between(self.conditions.span(self.arrow.start), self.expression.span())
+        let start = self.conditions.first().map_or(self.arrow, |condition| condition.span());
+
+        Span::between(start, self.expression.span())
     }
 }
 
@@ -7,16 +7,22 @@ use mago_span::Span;
 use crate::ast::keyword::Keyword;
 use crate::ast::terminator::Terminator;
 
+/// Represents a `__halt_compiler();` statement, along with the data following it.
+///
+/// Nothing after the terminator is parsed as PHP code, the remaining bytes of the file are the data segment,
+/// which can be read at runtime from the offset given by the `__COMPILER_HALT_OFFSET__` constant.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 pub struct HaltCompiler {
     pub halt_compiler: Keyword,
     pub left_parenthesis: Span,
     pub right_parenthesis: Span,
     pub terminator: Terminator,
+    /// The span of the data following the terminator, up to the end of the file, which may be empty.
+    pub data: Span,
 }
 
 impl HasSpan for HaltCompiler {
     fn span(&self) -> Span {
-        self.halt_compiler.span().join(self.terminator.span())
+        self.halt_compiler.span().join(self.data)
     }
 }
@@ -37,13 +37,17 @@ pub struct LiteralString {
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 pub struct LiteralInteger {
     pub span: Span,
+    /// The literal as written in the source code, such as `0x1F` or `1_000`.
     pub raw: StringIdentifier,
+    /// The value of the literal, or `None` if it does not fit in a 64-bit signed integer, in which case
+    /// PHP evaluates it as a float.
     pub value: Option<u64>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 pub struct LiteralFloat {
     pub span: Span,
+    /// The literal as written in the source code, such as `1.5e3` or `1_000.5`.
     pub raw: StringIdentifier,
     pub value: OrderedFloat<f64>,
 }
@@ -26,6 +26,8 @@ pub enum Modifier {
     Public(Keyword),
     Protected(Keyword),
     Private(Keyword),
+    PublicSet(Keyword),
+    ProtectedSet(Keyword),
     PrivateSet(Keyword),
 }
 
@@ -39,16 +41,15 @@ impl Modifier {
             Modifier::Public(k) => k,
             Modifier::Protected(k) => k,
             Modifier::Private(k) => k,
+            Modifier::PublicSet(k) => k,
+            Modifier::ProtectedSet(k) => k,
             Modifier::PrivateSet(k) => k,
         }
     }
 
     /// Returns `true` if the modifier is a visibility modifier.
     pub fn is_visibility(&self) -> bool {
-        matches!(
-            self,
-            Modifier::Public(..) | Modifier::Protected(..) | Modifier::Private(..) | Modifier::PrivateSet(..)
-        )
+        self.is_read_visibility() || self.is_write_visibility()
     }
 
     /// Returns `true` if the modifier is a read visibility modifier.
@@ -58,7 +59,7 @@ impl Modifier {
 
     /// Returns `true` if the modifier is a write visibility modifier.
     pub fn is_write_visibility(&self) -> bool {
-        matches!(self, Modifier::PrivateSet(..))
+        matches!(self, Modifier::PublicSet(..) | Modifier::ProtectedSet(..) | Modifier::PrivateSet(..))
     }
 
     pub fn as_str<'a>(&self, interner: &'a ThreadedInterner) -> &'a str {
@@ -70,6 +71,8 @@ impl Modifier {
             Modifier::Public(k) => interner.lookup(&k.value),
             Modifier::Protected(k) => interner.lookup(&k.value),
             Modifier::Private(k) => interner.lookup(&k.value),
+            Modifier::PublicSet(k) => interner.lookup(&k.value),
+            Modifier::ProtectedSet(k) => interner.lookup(&k.value),
             Modifier::PrivateSet(k) => interner.lookup(&k.value),
         }
     }
@@ -85,6 +88,8 @@ impl HasSpan for Modifier {
             | Modifier::Public(value)
             | Modifier::Protected(value)
             | Modifier::Private(value)
+            | Modifier::PublicSet(value)
+            | Modifier::ProtectedSet(value)
             | Modifier::PrivateSet(value) => value.span(),
         }
     }
@@ -132,21 +137,15 @@ impl Sequence<Modifier> {
     }
 
     pub fn get_first_visibility(&self) -> Option<&Modifier> {
-        self.iter().find(|modifier| {
-            matches!(
-                modifier,
-                Modifier::Public(..) | Modifier::Protected(..) | Modifier::Private(..) | Modifier::PrivateSet(..)
-            )
-        })
+        self.iter().find(|modifier| modifier.is_visibility())
     }
 
     pub fn get_first_read_visibility(&self) -> Option<&Modifier> {
-        self.iter()
-            .find(|modifier| matches!(modifier, Modifier::Public(..) | Modifier::Protected(..) | Modifier::Private(..)))
+        self.iter().find(|modifier| modifier.is_read_visibility())
     }
 
     pub fn get_first_write_visibility(&self) -> Option<&Modifier> {
-        self.iter().find(|modifier| matches!(modifier, Modifier::PrivateSet(..)))
+        self.iter().find(|modifier| modifier.is_write_visibility())
     }
 
     /// Returns `true` if the sequence contains a visibility modifier for reading or writing.
@@ -181,6 +180,22 @@ impl Sequence<Modifier> {
         self.iter().any(|modifier| matches!(modifier, Modifier::Private(..)))
     }
 
+    pub fn get_public_set(&self) -> Option<&Modifier> {
+        self.iter().find(|modifier| matches!(modifier, Modifier::PublicSet(..)))
+    }
+
+    pub fn contains_public_set(&self) -> bool {
+        self.iter().any(|modifier| matches!(modifier, Modifier::PublicSet(..)))
+    }
+
+    pub fn get_protected_set(&self) -> Option<&Modifier> {
+        self.iter().find(|modifier| matches!(modifier, Modifier::ProtectedSet(..)))
+    }
+
+    pub fn contains_protected_set(&self) -> bool {
+        self.iter().any(|modifier| matches!(modifier, Modifier::ProtectedSet(..)))
+    }
+
     pub fn get_private_set(&self) -> Option<&Modifier> {
         self.iter().find(|modifier| matches!(modifier, Modifier::PrivateSet(..)))
     }
@@ -80,6 +80,8 @@ pub enum Statement {
     HaltCompiler(HaltCompiler),
     Unset(Box<Unset>),
     Noop(Span),
+    /// Source code that could not be parsed, skipped while recovering from a parse error.
+    Error(Span),
 }
 
 impl HasSpan for ExpressionStatement {
@@ -123,6 +125,7 @@ impl HasSpan for Statement {
             Statement::Unset(statement) => statement.span(),
             Statement::HaltCompiler(statement) => statement.span(),
             Statement::Noop(span) => *span,
+            Statement::Error(span) => *span,
         }
     }
 }
@@ -148,7 +148,7 @@ impl Hint {
 
     /// Returns `true` if the type can be intersected with another type.
     pub fn is_intersectable(&self) -> bool {
-        matches!(self, Self::Identifier(_) | Self::Parenthesized(_) | Self::Intersection(_))
+        matches!(self, Self::Identifier(_) | Self::Intersection(_))
     }
 
     /// Returns `true` if the type can be unioned with another type.
@@ -161,8 +161,11 @@ impl Hint {
     }
 
     /// Returns `true` if the type can be wrapped in parentheses.
+    ///
+    /// Only intersection types can be wrapped in parentheses, as members of a union type in disjunctive
+    /// normal form, such as `(A&B)|null`.
     pub fn is_parenthesizable(&self) -> bool {
-        matches!(self, Self::Union(_) | Self::Intersection(_))
+        matches!(self, Self::Intersection(_))
     }
 
     /// Returns `true` if the type is a scalar type.
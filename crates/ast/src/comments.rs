@@ -0,0 +1,166 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::ThreadedInterner;
+use mago_span::Span;
+
+use crate::trivia::Trivia;
+use crate::trivia::TriviaKind;
+
+/// Represents the comments of a program, indexed by the positions of the code surrounding them.
+///
+/// Comments are attached to the code on either side of the run of trivia they belong to:
+///
+/// - The comments on the same line as the code preceding them, up to the first docblock, are its trailing comments.
+/// - The remaining comments are the leading comments of the code following them.
+///
+/// i.e. in `$a = 1; // one` followed by `/** two */ function foo() {}` on the next line, `// one` is a trailing
+/// comment of `$a = 1;`, and `/** two */` is a leading comment, and the docblock, of the function.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommentMap {
+    comments: Vec<Trivia>,
+    /// The range of indices of the leading comments of the code starting at the given offset.
+    leading: HashMap<usize, (usize, usize)>,
+    /// The range of indices of the trailing comments of the code ending at the given offset.
+    trailing: HashMap<usize, (usize, usize)>,
+    /// The index of the docblock immediately preceding the code starting at the given offset.
+    docblocks: HashMap<usize, usize>,
+}
+
+impl CommentMap {
+    /// Creates a comment map from the given trivia, in the order they appear in the source code.
+    pub fn new(interner: &ThreadedInterner, trivia: &[Trivia]) -> Self {
+        let mut map = Self::default();
+
+        let mut index = 0;
+        while index < trivia.len() {
+            // A run of trivia is not interrupted by any token.
+            let mut end = index + 1;
+            while end < trivia.len() && trivia[end - 1].span.end.offset == trivia[end].span.start.offset {
+                end += 1;
+            }
+
+            let run = &trivia[index..end];
+            let first = map.comments.len();
+            let mut split = None;
+            for trivia in run {
+                match trivia.kind {
+                    TriviaKind::WhiteSpace => {
+                        if split.is_none() && interner.lookup(&trivia.value).contains('\n') {
+                            split = Some(map.comments.len());
+                        }
+                    }
+                    kind => {
+                        // A docblock documents the code following it, even on the same line as the code before it.
+                        if split.is_none() && kind == TriviaKind::DocBlockComment {
+                            split = Some(map.comments.len());
+                        }
+
+                        map.comments.push(*trivia);
+                    }
+                }
+            }
+
+            let last = map.comments.len();
+            let split = split.unwrap_or(last);
+            if split > first {
+                map.trailing.insert(run[0].span.start.offset, (first, split));
+            }
+
+            if last > split {
+                let offset = run[run.len() - 1].span.end.offset;
+
+                map.leading.insert(offset, (split, last));
+                if map.comments[last - 1].kind == TriviaKind::DocBlockComment {
+                    map.docblocks.insert(offset, last - 1);
+                }
+            }
+
+            index = end;
+        }
+
+        map
+    }
+
+    /// Returns all the comments, in the order they appear in the source code.
+    pub fn all(&self) -> &[Trivia] {
+        &self.comments
+    }
+
+    pub fn len(&self) -> usize {
+        self.comments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.comments.is_empty()
+    }
+
+    /// Returns the comments preceding the given span, that are not on the same line as the code before them.
+    pub fn comments_before(&self, span: Span) -> &[Trivia] {
+        match self.leading.get(&span.start.offset) {
+            Some(&(start, end)) => &self.comments[start..end],
+            None => &[],
+        }
+    }
+
+    /// Returns the comments following the given span on the same line.
+    pub fn comments_after(&self, span: Span) -> &[Trivia] {
+        match self.trailing.get(&span.end.offset) {
+            Some(&(start, end)) => &self.comments[start..end],
+            None => &[],
+        }
+    }
+
+    /// Returns the comments contained in the given span.
+    pub fn comments_within(&self, span: Span) -> &[Trivia] {
+        let start = self.comments.partition_point(|comment| comment.span.start.offset < span.start.offset);
+        let end = self.comments.partition_point(|comment| comment.span.end.offset <= span.end.offset);
+
+        &self.comments[start..end.max(start)]
+    }
+
+    /// Returns the docblock immediately preceding the given span, if any.
+    ///
+    /// i.e. `/** @return void */` for the span of the function in `/** @return void */ function foo(): void {}`.
+    pub fn docblock(&self, span: Span) -> Option<&Trivia> {
+        self.docblocks.get(&span.start.offset).map(|index| &self.comments[*index])
+    }
+
+    /// Returns whether the given span is immediately preceded by a docblock.
+    pub fn has_docblock(&self, span: Span) -> bool {
+        self.docblocks.contains_key(&span.start.offset)
+    }
+}
+
+// The indices are derived from the comments, so only the comments are compared, and hashed.
+
+impl PartialEq for CommentMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.comments == other.comments
+    }
+}
+
+impl Eq for CommentMap {}
+
+impl Hash for CommentMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.comments.hash(state);
+    }
+}
+
+impl PartialOrd for CommentMap {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CommentMap {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.comments.cmp(&other.comments)
+    }
+}
@@ -0,0 +1,391 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::ThreadedInterner;
+use mago_span::HasSpan;
+use mago_span::Span;
+
+use crate::node::Node;
+use crate::node::NodeKind;
+use crate::Program;
+use crate::Trivia;
+
+/// The options of a structural comparison of two programs.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct CompareOptions {
+    /// Whether the docblocks of the nodes are left out of the comparison, as the other comments are.
+    pub ignore_docblocks: bool,
+    /// Whether the statements of the program, and of its namespaces, may appear in any order.
+    pub ignore_top_level_order: bool,
+    /// The maximum number of differences to return.
+    pub limit: usize,
+}
+
+/// A difference between two programs, as found by [`structural_diff`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct StructuralDifference {
+    /// The kinds of the nodes leading to the difference, from the program, such as
+    /// `Program > Statement[2] > Function > Block`.
+    ///
+    /// The position of a node is only given when its parent has several children of the same kind.
+    pub path: String,
+    /// The span of the differing nodes in the first program, or the empty span where they would be.
+    pub left: Span,
+    /// The span of the differing nodes in the second program, or the empty span where they would be.
+    pub right: Span,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self { ignore_docblocks: false, ignore_top_level_order: false, limit: 10 }
+    }
+}
+
+impl std::fmt::Display for StructuralDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}..{} and {}..{})",
+            self.path, self.left.start.offset, self.left.end.offset, self.right.start.offset, self.right.end.offset
+        )
+    }
+}
+
+/// Returns whether the given programs are the same, regardless of their spans and trivia.
+///
+/// Names, literals, and the structure of the programs are compared, along with their docblocks, while
+/// whitespace, comments, and the case of keywords are not. Both programs are expected to be parsed with the
+/// given interner.
+///
+/// See [`structural_diff`] to find out how the programs differ, or to compare them with other options.
+pub fn structurally_equal(a: &Program, b: &Program, interner: &ThreadedInterner) -> bool {
+    structural_diff(a, b, interner, CompareOptions { limit: 1, ..CompareOptions::default() }).is_empty()
+}
+
+/// Returns the first differences between the given programs, regardless of their spans and trivia, in source
+/// order, and up to the limit of the given options.
+///
+/// Differences are reported for the innermost nodes that differ, such as a literal, or the statements added
+/// to a block. See [`structurally_equal`] for what is compared.
+pub fn structural_diff(
+    a: &Program,
+    b: &Program,
+    interner: &ThreadedInterner,
+    options: CompareOptions,
+) -> Vec<StructuralDifference> {
+    structural_node_diff((a, Node::Program(a)), (b, Node::Program(b)), interner, options)
+}
+
+/// Returns the first differences between the given nodes of the given programs, as [`structural_diff`] does
+/// for whole programs.
+///
+/// The paths of the differences start at the given nodes, whose docblocks are compared too.
+pub fn structural_node_diff<'a>(
+    (a_program, a): (&'a Program, Node<'a>),
+    (b_program, b): (&'a Program, Node<'a>),
+    interner: &'a ThreadedInterner,
+    options: CompareOptions,
+) -> Vec<StructuralDifference> {
+    let mut comparison =
+        Comparison { interner, options, left: a_program, right: b_program, path: vec![], differences: vec![] };
+
+    comparison.path.push((a.kind(), None));
+    comparison.compare(a, b, None);
+
+    comparison.differences
+}
+
+struct Comparison<'a> {
+    interner: &'a ThreadedInterner,
+    options: CompareOptions,
+    left: &'a Program,
+    right: &'a Program,
+    /// The kind, and position among the siblings of the same kind, of the nodes leading to the current nodes.
+    path: Vec<(NodeKind, Option<usize>)>,
+    differences: Vec<StructuralDifference>,
+}
+
+impl<'a> Comparison<'a> {
+    fn compare(&mut self, a: Node<'a>, b: Node<'a>, parents: Option<(Node<'a>, Node<'a>)>) {
+        if self.differences.len() >= self.options.limit {
+            return;
+        }
+
+        let (a_children, b_children) = (a.children(), b.children());
+        if a.kind() != b.kind() || self.label(a, a_children.is_empty()) != self.label(b, b_children.is_empty()) {
+            self.report(a.span(), b.span());
+
+            return;
+        }
+
+        if !self.options.ignore_docblocks {
+            let a_docblock = docblock(self.left, a, parents.map(|(a, _)| a));
+            let b_docblock = docblock(self.right, b, parents.map(|(_, b)| b));
+
+            if a_docblock.map(|docblock| docblock.value) != b_docblock.map(|docblock| docblock.value) {
+                let span_of = |docblock: Option<&Trivia>, node: Node<'_>| {
+                    docblock.map_or(Span::new(node.span().start, node.span().start), |docblock| docblock.span)
+                };
+
+                self.report(span_of(a_docblock, a), span_of(b_docblock, b));
+            }
+        }
+
+        let is_top_level = match a {
+            Node::Program(_) | Node::NamespaceImplicitBody(_) => true,
+            Node::Block(_) => parents.is_some_and(|(parent, _)| parent.kind() == NodeKind::NamespaceBody),
+            _ => false,
+        };
+
+        if is_top_level && self.options.ignore_top_level_order {
+            self.compare_unordered_children((a, &a_children), (b, &b_children));
+        } else {
+            self.compare_children((a, &a_children), (b, &b_children));
+        }
+    }
+
+    /// Compares the children of the given nodes, in order.
+    ///
+    /// When the nodes do not have the same number of children, the children they have in common at the start
+    /// and at the end are compared, and the remaining ones are reported as a single difference.
+    fn compare_children(&mut self, (a, a_children): (Node<'a>, &[Node<'a>]), (b, b_children): (Node<'a>, &[Node<'a>])) {
+        let a_segments = segments(a_children);
+        if a_children.len() == b_children.len() {
+            for (index, (a_child, b_child)) in a_children.iter().zip(b_children.iter()).enumerate() {
+                self.compare_child(a_segments[index], (a, *a_child), (b, *b_child));
+            }
+
+            return;
+        }
+
+        let shortest = a_children.len().min(b_children.len());
+        let prefix =
+            (0..shortest).take_while(|&index| self.is_equal((a, a_children[index]), (b, b_children[index]))).count();
+        let suffix = (0..shortest - prefix)
+            .take_while(|&index| {
+                self.is_equal(
+                    (a, a_children[a_children.len() - 1 - index]),
+                    (b, b_children[b_children.len() - 1 - index]),
+                )
+            })
+            .count();
+
+        let segment = match a_segments.get(prefix) {
+            Some(segment) if prefix < a_children.len() - suffix => *segment,
+            _ => segments(b_children)[prefix],
+        };
+
+        self.path.push(segment);
+        self.report(gap(a, a_children, prefix, suffix), gap(b, b_children, prefix, suffix));
+        self.path.pop();
+    }
+
+    /// Compares the children of the given nodes, regardless of their order.
+    ///
+    /// Children without an equal counterpart are compared with each other, in order, and the remaining ones
+    /// are reported as differences, with the empty span at the end of the other node.
+    fn compare_unordered_children(
+        &mut self,
+        (a, a_children): (Node<'a>, &[Node<'a>]),
+        (b, b_children): (Node<'a>, &[Node<'a>]),
+    ) {
+        let mut matched = vec![false; b_children.len()];
+        let mut a_unmatched = vec![];
+        for (index, a_child) in a_children.iter().enumerate() {
+            let counterpart = (0..b_children.len())
+                .find(|&candidate| !matched[candidate] && self.is_equal((a, *a_child), (b, b_children[candidate])));
+
+            match counterpart {
+                Some(candidate) => matched[candidate] = true,
+                None => a_unmatched.push(index),
+            }
+        }
+
+        let b_unmatched: Vec<usize> = (0..b_children.len()).filter(|&index| !matched[index]).collect();
+
+        let (a_segments, b_segments) = (segments(a_children), segments(b_children));
+        for (&a_index, &b_index) in a_unmatched.iter().zip(b_unmatched.iter()) {
+            self.compare_child(a_segments[a_index], (a, a_children[a_index]), (b, b_children[b_index]));
+        }
+
+        let (a_end, b_end) = (a.span().end, b.span().end);
+        for &index in a_unmatched.iter().skip(b_unmatched.len()) {
+            self.path.push(a_segments[index]);
+            self.report(a_children[index].span(), Span::new(b_end, b_end));
+            self.path.pop();
+        }
+
+        for &index in b_unmatched.iter().skip(a_unmatched.len()) {
+            self.path.push(b_segments[index]);
+            self.report(Span::new(a_end, a_end), b_children[index].span());
+            self.path.pop();
+        }
+    }
+
+    fn compare_child(
+        &mut self,
+        segment: (NodeKind, Option<usize>),
+        (a, a_child): (Node<'a>, Node<'a>),
+        (b, b_child): (Node<'a>, Node<'a>),
+    ) {
+        self.path.push(segment);
+        self.compare(a_child, b_child, Some((a, b)));
+        self.path.pop();
+    }
+
+    /// Returns whether the given children of the given nodes are the same.
+    fn is_equal(&self, (a, a_child): (Node<'a>, Node<'a>), (b, b_child): (Node<'a>, Node<'a>)) -> bool {
+        let mut comparison = Comparison {
+            interner: self.interner,
+            options: CompareOptions { limit: 1, ..self.options },
+            left: self.left,
+            right: self.right,
+            path: vec![],
+            differences: vec![],
+        };
+
+        comparison.compare(a_child, b_child, Some((a, b)));
+        comparison.differences.is_empty()
+    }
+
+    /// Returns the data of the given node that is not part of its children, such as the value of a literal.
+    fn label(&self, node: Node<'_>, is_leaf: bool) -> String {
+        let flags = |flags: &[Option<Span>]| flags.iter().map(|flag| if flag.is_some() { '1' } else { '0' }).collect();
+
+        match node {
+            Node::Keyword(keyword) => self.interner.lookup(&keyword.value).to_ascii_lowercase(),
+            Node::LiteralString(string) => literal_string_contents(self.interner.lookup(&string.value)).to_string(),
+            Node::DocumentString(document) => format!("{:?}", document.kind),
+            Node::Function(function) => flags(&[function.ampersand]),
+            Node::Closure(closure) => flags(&[closure.ampersand]),
+            Node::ClosureUseClauseVariable(variable) => flags(&[variable.ampersand]),
+            Node::ArrowFunction(arrow_function) => flags(&[arrow_function.ampersand]),
+            Node::Method(method) => flags(&[method.ampersand]),
+            Node::PropertyHook(hook) => flags(&[hook.ampersand]),
+            Node::FunctionLikeParameter(parameter) => flags(&[parameter.ampersand, parameter.ellipsis]),
+            Node::PositionalArgument(argument) => flags(&[argument.ellipsis]),
+            Node::NamedArgument(argument) => flags(&[argument.ellipsis]),
+            _ if is_leaf => {
+                let mut value = serde_json::to_value(node).unwrap_or_default();
+                remove_positions(&mut value);
+
+                value.to_string()
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn report(&mut self, left: Span, right: Span) {
+        if self.differences.len() >= self.options.limit {
+            return;
+        }
+
+        let path = self
+            .path
+            .iter()
+            .map(|(kind, position)| match position {
+                Some(position) => format!("{}[{}]", kind, position),
+                None => kind.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" > ");
+
+        self.differences.push(StructuralDifference { path, left, right });
+    }
+}
+
+/// Returns the kind of the given nodes, along with their position among the nodes of the same kind, if
+/// there are several of them.
+fn segments(nodes: &[Node<'_>]) -> Vec<(NodeKind, Option<usize>)> {
+    let mut counts: Vec<(NodeKind, usize)> = vec![];
+    let positions: Vec<(NodeKind, usize)> = nodes
+        .iter()
+        .map(|node| {
+            let kind = node.kind();
+            let position = match counts.iter_mut().find(|(counted, _)| *counted == kind) {
+                Some((_, count)) => {
+                    *count += 1;
+
+                    *count - 1
+                }
+                None => {
+                    counts.push((kind, 1));
+
+                    0
+                }
+            };
+
+            (kind, position)
+        })
+        .collect();
+
+    positions
+        .into_iter()
+        .map(|(kind, position)| {
+            let several = counts.iter().any(|(counted, count)| *counted == kind && *count > 1);
+
+            (kind, if several { Some(position) } else { None })
+        })
+        .collect()
+}
+
+/// Returns the span of the children of the given node that are neither in the given prefix nor in the given
+/// suffix, or the empty span between them if there are none.
+fn gap(node: Node<'_>, children: &[Node<'_>], prefix: usize, suffix: usize) -> Span {
+    let middle = &children[prefix..children.len() - suffix];
+    if let (Some(first), Some(last)) = (middle.first(), middle.last()) {
+        return Span::between(first.span(), last.span());
+    }
+
+    let start = match prefix {
+        0 => node.span().start,
+        _ => children[prefix - 1].span().end,
+    };
+
+    let end = match children.get(prefix) {
+        Some(next) => next.span().start,
+        None => node.span().end,
+    };
+
+    Span::new(start, end)
+}
+
+/// Returns the docblock of the given node, unless it starts where its parent does, in which case the docblock
+/// belongs to the parent.
+fn docblock<'a>(program: &'a Program, node: Node<'_>, parent: Option<Node<'_>>) -> Option<&'a Trivia> {
+    let span = node.span();
+    if parent.is_some_and(|parent| parent.span().start.offset == span.start.offset) {
+        return None;
+    }
+
+    program.comments.docblock(span)
+}
+
+/// Returns the contents of the given literal string, without its quotes, unless they change its meaning.
+///
+/// i.e. `'foo'` and `"foo"` are the same string, while `'$foo'` and `"$foo"` are not.
+fn literal_string_contents(raw: &str) -> &str {
+    match raw.as_bytes() {
+        [b'\'', .., b'\''] | [b'"', .., b'"'] => {
+            let contents = &raw[1..raw.len() - 1];
+            if contents.contains(['\\', '$', '\'', '"']) {
+                raw
+            } else {
+                contents
+            }
+        }
+        _ => raw,
+    }
+}
+
+/// Removes the positions, and therefore the spans, from the given serialized node.
+fn remove_positions(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(object) if object.contains_key("source") && object.contains_key("offset") => {
+            *value = serde_json::Value::Null;
+        }
+        serde_json::Value::Object(object) => object.values_mut().for_each(remove_positions),
+        serde_json::Value::Array(array) => array.iter_mut().for_each(remove_positions),
+        _ => {}
+    }
+}
@@ -0,0 +1,254 @@
+use mago_source::SourceIdentifier;
+use mago_span::HasSpan;
+use mago_span::Position;
+use mago_span::Span;
+
+use crate::node::Node;
+use crate::node::NodeKind;
+use crate::Program;
+
+/// The identifier of a node in a [`NodeIndex`], which is its position in a pre-order traversal of the program.
+///
+/// The program is the first node, and the descendants of a node directly follow it, in source order.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct NodeId(u32);
+
+/// An index of the nodes of a program, to find the parent, the enclosing scope, or the siblings of a node, or
+/// the node at an offset, without traversing the program again.
+///
+/// The index does not borrow the program it is built from: nodes are referred to by their [`NodeId`], and
+/// retrieved from the program with [`NodeIndex::get`].
+#[derive(Debug, Clone)]
+pub struct NodeIndex {
+    source: SourceIdentifier,
+    kinds: Vec<NodeKind>,
+    /// The start and end offsets of every node.
+    spans: Vec<(u32, u32)>,
+    parents: Vec<Option<NodeId>>,
+    /// The identifier following the last descendant of every node.
+    ends: Vec<u32>,
+    /// The position of every node among the children of its parent.
+    positions: Vec<u32>,
+}
+
+impl NodeId {
+    /// Returns the position of the node in a pre-order traversal of the program.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl NodeIndex {
+    /// Creates an index of the nodes of the given program.
+    pub fn new(program: &Program) -> Self {
+        let mut index = Self {
+            source: program.source,
+            kinds: vec![],
+            spans: vec![],
+            parents: vec![],
+            ends: vec![],
+            positions: vec![],
+        };
+
+        index.add(Node::Program(program), None, 0);
+
+        index.kinds.shrink_to_fit();
+        index.spans.shrink_to_fit();
+        index.parents.shrink_to_fit();
+        index.ends.shrink_to_fit();
+        index.positions.shrink_to_fit();
+
+        index
+    }
+
+    fn add(&mut self, node: Node<'_>, parent: Option<NodeId>, position: usize) {
+        let id = NodeId(self.kinds.len() as u32);
+        let span = node.span();
+
+        self.kinds.push(node.kind());
+        self.spans.push((span.start.offset as u32, span.end.offset as u32));
+        self.parents.push(parent);
+        self.ends.push(0);
+        self.positions.push(position as u32);
+
+        for (position, child) in node.children().into_iter().enumerate() {
+            self.add(child, Some(id), position);
+        }
+
+        self.ends[id.index()] = self.kinds.len() as u32;
+    }
+
+    /// Returns the number of nodes in the index.
+    pub fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// Returns the identifier of the program.
+    pub const fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    /// Returns the node with the given identifier, from the program this index was built from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given program is not the one this index was built from.
+    pub fn get<'a>(&self, program: &'a Program, id: NodeId) -> Node<'a> {
+        let mut positions = vec![];
+        let mut current = id;
+        while let Some(parent) = self.parent(current) {
+            positions.push(self.positions[current.index()] as usize);
+            current = parent;
+        }
+
+        let mut node = Node::Program(program);
+        for position in positions.into_iter().rev() {
+            node = node.children()[position];
+        }
+
+        node
+    }
+
+    /// Returns the identifier of the given node, which must belong to the program this index was built from.
+    ///
+    /// Nodes are found by their kind and span, so a node wrapping another node of the same kind, and with the
+    /// same span, is not told apart from it.
+    pub fn id_of(&self, node: Node<'_>) -> Option<NodeId> {
+        let (kind, span) = (node.kind(), node.span());
+
+        // The nodes with the same span as a node are its first child, the first child of that child, and so on.
+        let mut id = self.with_span(span)?;
+        while self.kinds[id.index()] != kind {
+            let child = id.0 + 1;
+            if child >= self.ends[id.index()] || self.spans[child as usize] != self.spans[id.index()] {
+                return None;
+            }
+
+            id = NodeId(child);
+        }
+
+        Some(id)
+    }
+
+    /// Returns the outermost node with the given span, if any.
+    pub fn with_span(&self, span: Span) -> Option<NodeId> {
+        let (start, end) = (span.start.offset as u32, span.end.offset as u32);
+
+        // Nodes are in source order, so the nodes starting at the same offset follow each other.
+        let first = self.spans.partition_point(|&(node_start, _)| node_start < start);
+
+        self.spans[first..]
+            .iter()
+            .take_while(|&&(node_start, _)| node_start == start)
+            .position(|&(_, node_end)| node_end == end)
+            .map(|position| NodeId((first + position) as u32))
+    }
+
+    /// Returns the innermost node containing the given offset, as [`crate::query::node_at_offset`] does.
+    pub fn at_offset(&self, offset: usize) -> Option<NodeId> {
+        let contains = |id: NodeId| {
+            let (start, end) = self.spans[id.index()];
+
+            start as usize <= offset && offset <= end as usize
+        };
+
+        if self.is_empty() || !contains(self.root()) {
+            return None;
+        }
+
+        // Nodes are in source order, so the innermost node containing the offset, among the nodes starting before
+        // it, is the last node starting before it, or one of its ancestors.
+        let before = self.spans.partition_point(|&(start, _)| (start as usize) < offset);
+        let mut id = if before == 0 { self.root() } else { NodeId(before as u32 - 1) };
+        while !contains(id) {
+            id = self.parent(id)?;
+        }
+
+        // The node to the left of the offset is preferred, so a node starting at the offset is only entered if no
+        // other child contains it. The nodes starting at the offset are the first node starting after the other
+        // children, and its first descendants.
+        if before < self.len()
+            && id.0 <= before as u32
+            && (before as u32) < self.ends[id.index()]
+            && self.spans[before].0 as usize == offset
+        {
+            id = NodeId(before as u32);
+            while id.0 + 1 < self.ends[id.index()] && self.spans[id.index() + 1].0 as usize == offset {
+                id = NodeId(id.0 + 1);
+            }
+        }
+
+        Some(id)
+    }
+
+    /// Returns the kind of the given node.
+    pub fn kind(&self, id: NodeId) -> NodeKind {
+        self.kinds[id.index()]
+    }
+
+    /// Returns the span of the given node.
+    pub fn span(&self, id: NodeId) -> Span {
+        let (start, end) = self.spans[id.index()];
+
+        Span::new(Position::new(self.source, start as usize), Position::new(self.source, end as usize))
+    }
+
+    /// Returns the parent of the given node, or `None` for the program.
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.parents[id.index()]
+    }
+
+    /// Returns the ancestors of the given node, from its parent up to the program.
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), |&ancestor| self.parent(ancestor))
+    }
+
+    /// Returns the children of the given node, in source order.
+    pub fn children(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let end = self.ends[id.index()];
+
+        std::iter::successors(Some(id.0 + 1).filter(|&child| child < end), move |&child| {
+            Some(self.ends[child as usize]).filter(|&sibling| sibling < end)
+        })
+        .map(NodeId)
+    }
+
+    /// Returns the other children of the parent of the given node, in source order.
+    pub fn siblings(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.parent(id).into_iter().flat_map(|parent| self.children(parent)).filter(move |&sibling| sibling != id)
+    }
+
+    /// Returns whether the first given node is an ancestor of the second one.
+    pub fn is_ancestor_of(&self, ancestor: NodeId, id: NodeId) -> bool {
+        ancestor.0 < id.0 && id.0 < self.ends[ancestor.index()]
+    }
+
+    /// Returns the innermost function-like containing the given node, not including the node itself.
+    ///
+    /// i.e. a function, a method, a closure, an arrow function, or a property hook.
+    pub fn enclosing_function(&self, id: NodeId) -> Option<NodeId> {
+        self.ancestors(id).find(|&ancestor| self.kind(ancestor).is_function_like())
+    }
+
+    /// Returns the innermost class-like containing the given node, not including the node itself.
+    ///
+    /// i.e. a class, an interface, a trait, an enum, or an anonymous class.
+    pub fn enclosing_class_like(&self, id: NodeId) -> Option<NodeId> {
+        self.ancestors(id).find(|&ancestor| self.kind(ancestor).is_class_like())
+    }
+
+    /// Returns the innermost function-like or class-like containing the given node, not including the node itself,
+    /// which is the scope that variables, or `$this` and `self`, refer to.
+    pub fn enclosing_scope(&self, id: NodeId) -> Option<NodeId> {
+        self.ancestors(id).find(|&ancestor| {
+            let kind = self.kind(ancestor);
+
+            kind.is_function_like() || kind.is_class_like()
+        })
+    }
+}
@@ -9,13 +9,18 @@ use mago_span::Position;
 use mago_span::Span;
 
 pub use crate::ast::*;
+pub use crate::comments::CommentMap;
 pub use crate::node::Node;
 pub use crate::sequence::Sequence;
 pub use crate::trivia::Trivia;
 pub use crate::trivia::TriviaKind;
 
 pub mod ast;
+pub mod comments;
+pub mod compare;
+pub mod index;
 pub mod node;
+pub mod query;
 pub mod sequence;
 pub mod trivia;
 
@@ -23,6 +28,7 @@ pub mod trivia;
 pub struct Program {
     pub source: SourceIdentifier,
     pub trivia: Sequence<Trivia>,
+    pub comments: CommentMap,
     pub statements: Sequence<Statement>,
 }
 
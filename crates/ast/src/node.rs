@@ -232,6 +232,20 @@ pub enum NodeKind {
     Variable,
 }
 
+impl NodeKind {
+    /// Returns whether this is the kind of a function, a method, a closure, an arrow function, or a property hook.
+    #[inline]
+    pub const fn is_function_like(&self) -> bool {
+        matches!(self, Self::Function | Self::Method | Self::Closure | Self::ArrowFunction | Self::PropertyHook)
+    }
+
+    /// Returns whether this is the kind of a class, an interface, a trait, an enum, or an anonymous class.
+    #[inline]
+    pub const fn is_class_like(&self) -> bool {
+        matches!(self, Self::Class | Self::Interface | Self::Trait | Self::Enum | Self::AnonymousClass)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, PartialOrd, Ord, Display)]
 #[serde(tag = "type", content = "value")]
 pub enum Node<'a> {
@@ -455,6 +469,10 @@ pub enum Node<'a> {
 }
 
 impl<'a> Node<'a> {
+    /// Returns the results of the given function for this node, and all of its descendants, in post-order.
+    ///
+    /// i.e. the results of the descendants of a node precede its own, unlike [`Node::traverse`], which visits
+    /// a node before its descendants.
     #[inline]
     pub fn filter_map<F, T>(&self, f: F) -> Vec<T>
     where
@@ -480,6 +498,40 @@ impl<'a> Node<'a> {
         result
     }
 
+    /// Returns the descendants of this node, not including the node itself, in source order.
+    ///
+    /// i.e. the descendants of `$a + 1` are the expression `$a`, its variable, the expression `1`, and its literal.
+    #[inline]
+    pub fn descendants(&self) -> Vec<Node<'a>> {
+        let mut descendants = vec![];
+        for child in self.children() {
+            child.traverse(&mut |node| {
+                descendants.push(node);
+
+                true
+            });
+        }
+
+        descendants
+    }
+
+    /// Calls the given function for this node, and its descendants, in source order.
+    ///
+    /// The children of a node are only visited if the function returns `true` for it, which allows skipping
+    /// whole subtrees, such as the bodies of nested functions. To only visit some of the children of a node,
+    /// traverse them separately, and return `false`.
+    #[inline]
+    pub fn traverse<F>(&self, f: &mut F)
+    where
+        F: FnMut(Node<'a>) -> bool,
+    {
+        if f(*self) {
+            for child in self.children() {
+                child.traverse(f);
+            }
+        }
+    }
+
     #[inline]
     pub const fn is_declaration(&self) -> bool {
         matches!(
@@ -1614,6 +1666,8 @@ impl<'a> Node<'a> {
                 Modifier::Public(node) => Node::Keyword(node),
                 Modifier::Static(node) => Node::Keyword(node),
                 Modifier::Readonly(node) => Node::Keyword(node),
+                Modifier::PublicSet(node) => Node::Keyword(node),
+                Modifier::ProtectedSet(node) => Node::Keyword(node),
                 Modifier::PrivateSet(node) => Node::Keyword(node),
             }],
             Node::Namespace(node) => {
@@ -1913,7 +1967,7 @@ impl<'a> Node<'a> {
                 Statement::Static(node) => vec![Node::Static(node)],
                 Statement::HaltCompiler(node) => vec![Node::HaltCompiler(node)],
                 Statement::Unset(node) => vec![Node::Unset(node)],
-                Statement::Noop(_) => vec![],
+                Statement::Noop(_) | Statement::Error(_) => vec![],
             },
             Node::ExpressionStatement(node) => {
                 vec![Node::Expression(&node.expression), Node::Terminator(&node.terminator)]
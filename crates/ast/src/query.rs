@@ -0,0 +1,44 @@
+use mago_span::HasSpan;
+
+use crate::node::Node;
+use crate::Program;
+
+/// Returns the path from the program down to the innermost node containing the given offset.
+///
+/// A node contains the offsets from its start to its end, both included, so that the node ending right
+/// before a cursor is found. When the offset is between two nodes, such as in `foo|()`, the node to the
+/// left of it is preferred, as editors do.
+///
+/// An empty vector is returned if the offset is outside of the program.
+pub fn node_at_offset(program: &Program, offset: usize) -> Vec<Node<'_>> {
+    let contains = |node: &Node<'_>| {
+        let span = node.span();
+
+        span.start.offset <= offset && offset <= span.end.offset
+    };
+
+    let mut path = vec![];
+    let mut current = Some(Node::Program(program)).filter(contains);
+    while let Some(node) = current {
+        path.push(node);
+
+        // Children are in source order, so the first child containing the offset is the leftmost one.
+        current = node.children().into_iter().find(contains);
+    }
+
+    path
+}
+
+/// Returns the innermost function-like containing the given offset, if any.
+///
+/// i.e. a function, a method, a closure, an arrow function, or a property hook.
+pub fn enclosing_function(program: &Program, offset: usize) -> Option<Node<'_>> {
+    node_at_offset(program, offset).into_iter().rev().find(|node| node.kind().is_function_like())
+}
+
+/// Returns the innermost class-like containing the given offset, if any.
+///
+/// i.e. a class, an interface, a trait, an enum, or an anonymous class.
+pub fn enclosing_class_like(program: &Program, offset: usize) -> Option<Node<'_>> {
+    node_at_offset(program, offset).into_iter().rev().find(|node| node.kind().is_class_like())
+}
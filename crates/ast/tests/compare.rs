@@ -0,0 +1,109 @@
+use mago_ast::compare::structural_diff;
+use mago_ast::compare::structurally_equal;
+use mago_ast::compare::CompareOptions;
+use mago_ast::compare::StructuralDifference;
+use mago_ast::Program;
+use mago_interner::ThreadedInterner;
+use mago_parser::parse_source;
+use mago_source::SourceManager;
+
+fn parse(interner: &ThreadedInterner, code: &str) -> Program {
+    let manager = SourceManager::new(interner.clone());
+    let source = manager.load(&manager.insert_content("compare.php".to_string(), code.to_string(), true)).unwrap();
+
+    let (program, errors) = parse_source(interner, &source);
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+
+    program
+}
+
+fn diff(a: &str, b: &str, options: CompareOptions) -> Vec<StructuralDifference> {
+    let interner = ThreadedInterner::new();
+
+    structural_diff(&parse(&interner, a), &parse(&interner, b), &interner, options)
+}
+
+fn equal(a: &str, b: &str) -> bool {
+    let interner = ThreadedInterner::new();
+
+    structurally_equal(&parse(&interner, a), &parse(&interner, b), &interner)
+}
+
+#[test]
+fn test_whitespace_comments_and_keyword_case_are_ignored() {
+    assert!(equal(
+        "<?php function foo($a) { return $a + 1; }",
+        "<?php\n\n// foo\nFUNCTION foo( $a )\n{\n    return $a+1;\n}\n"
+    ));
+    assert!(equal("<?php echo 'foo';", "<?php echo \"foo\";"));
+
+    assert!(!equal("<?php echo '$foo';", "<?php echo \"$foo\";"));
+    assert!(!equal("<?php function foo() {}", "<?php function Foo() {}"));
+    assert!(!equal("<?php function foo() {}", "<?php function &foo() {}"));
+    assert!(!equal("<?php $a + 1;", "<?php $a - 1;"));
+}
+
+#[test]
+fn test_differences_are_reported_for_the_innermost_nodes() {
+    let a = "<?php foo(); function bar() { return 1; }";
+    let b = "<?php foo(); function bar() { return 2; }";
+    let differences = diff(a, b, CompareOptions::default());
+
+    assert_eq!(1, differences.len());
+    assert_eq!(
+        "Program > Statement[2] > Function > Block > Statement > Return > Expression > Literal > LiteralInteger",
+        differences[0].path
+    );
+    assert_eq!(a.find('1').unwrap(), differences[0].left.start.offset);
+    assert_eq!(b.find('2').unwrap(), differences[0].right.start.offset);
+}
+
+#[test]
+fn test_added_nodes_are_reported_with_an_empty_span() {
+    let a = "<?php foo(); baz();";
+    let b = "<?php foo(); bar(); baz();";
+    let differences = diff(a, b, CompareOptions::default());
+
+    assert_eq!(1, differences.len());
+    assert_eq!("Program > Statement[2]", differences[0].path);
+    assert_eq!(a.find(';').unwrap() + 1, differences[0].left.start.offset);
+    assert_eq!(a.find(" baz").unwrap() + 1, differences[0].left.end.offset);
+    assert_eq!(&b[differences[0].right.start.offset..differences[0].right.end.offset], "bar();");
+}
+
+#[test]
+fn test_docblocks_are_compared_unless_ignored() {
+    let a = "<?php /** @return int */ function foo() {}";
+    let b = "<?php /** @return string */ function foo() {}";
+
+    let differences = diff(a, b, CompareOptions::default());
+    assert_eq!(1, differences.len());
+    assert_eq!(&a[differences[0].left.start.offset..differences[0].left.end.offset], "/** @return int */");
+
+    assert!(diff(a, b, CompareOptions { ignore_docblocks: true, ..CompareOptions::default() }).is_empty());
+    assert!(diff(a, "<?php function foo() {}", CompareOptions { ignore_docblocks: true, ..CompareOptions::default() })
+        .is_empty());
+}
+
+#[test]
+fn test_top_level_order_is_ignored_if_requested() {
+    let a = "<?php namespace Foo; function foo() {} function bar() { baz(); qux(); }";
+    let b = "<?php namespace Foo; function bar() { baz(); qux(); } function foo() {}";
+    let options = CompareOptions { ignore_top_level_order: true, ..CompareOptions::default() };
+
+    assert!(!diff(a, b, CompareOptions::default()).is_empty());
+    assert!(diff(a, b, options).is_empty());
+
+    // The order of the statements of functions still matters.
+    let c = "<?php namespace Foo; function bar() { qux(); baz(); } function foo() {}";
+    assert_eq!(2, diff(a, c, options).len());
+}
+
+#[test]
+fn test_differences_are_limited() {
+    let a = "<?php $a = 1; $b = 2; $c = 3;";
+    let b = "<?php $a = 4; $b = 5; $c = 6;";
+
+    assert_eq!(3, diff(a, b, CompareOptions::default()).len());
+    assert_eq!(2, diff(a, b, CompareOptions { limit: 2, ..CompareOptions::default() }).len());
+}
@@ -0,0 +1,173 @@
+use mago_ast::index::NodeId;
+use mago_ast::index::NodeIndex;
+use mago_ast::node::NodeKind;
+use mago_ast::query::node_at_offset;
+use mago_ast::Node;
+use mago_ast::Program;
+use mago_interner::ThreadedInterner;
+use mago_parser::parse_source;
+use mago_source::SourceManager;
+use mago_span::HasSpan;
+
+const CODE: &str = r#"<?php
+
+namespace App;
+
+use Foo\Bar;
+
+#[Attribute]
+final class Service extends Bar implements \Countable
+{
+    public const int LIMIT = 10;
+
+    public function __construct(private readonly array $items = []) {}
+
+    public function count(): int
+    {
+        $total = 0;
+        foreach ($this->items as $key => $item) {
+            $total += array_sum(array_map(fn($value) => $value * 2, [$item, $key]));
+        }
+
+        return $total > self::LIMIT ? $total : (function () use ($total) { return -$total; })();
+    }
+}
+
+function helper(int|string ...$values): ?string
+{
+    return match (count($values)) {
+        0 => null,
+        default => "{$values[0]} and more",
+    };
+}
+"#;
+
+fn parse(code: &str) -> Program {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source = manager.load(&manager.insert_content("index.php".to_string(), code.to_string(), true)).unwrap();
+
+    let (program, errors) = parse_source(&interner, &source);
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+
+    program
+}
+
+/// Returns the identifiers of the nodes of the index, found by following the children of the program.
+fn pre_order(index: &NodeIndex) -> Vec<NodeId> {
+    fn visit(index: &NodeIndex, id: NodeId, ids: &mut Vec<NodeId>) {
+        ids.push(id);
+        for child in index.children(id) {
+            visit(index, child, ids);
+        }
+    }
+
+    let mut ids = vec![];
+    visit(index, index.root(), &mut ids);
+
+    ids
+}
+
+#[test]
+fn test_nodes_are_indexed_in_pre_order() {
+    let program = parse(CODE);
+    let index = NodeIndex::new(&program);
+
+    let mut nodes = vec![Node::Program(&program)];
+    nodes.extend(Node::Program(&program).descendants());
+
+    let ids = pre_order(&index);
+    assert_eq!(nodes.len(), index.len());
+    assert_eq!(nodes.len(), ids.len());
+
+    for (position, (node, id)) in nodes.into_iter().zip(ids).enumerate() {
+        assert_eq!(position, id.index());
+        assert_eq!(node.kind(), index.kind(id));
+        assert_eq!(node.span(), index.span(id));
+        assert_eq!(node, index.get(&program, id));
+
+        // A node wrapping another node of the same kind, and with the same span, is found instead of it.
+        let found = index.id_of(node).unwrap();
+        assert!(found == id || (index.is_ancestor_of(found, id) && index.kind(found) == node.kind()));
+    }
+}
+
+#[test]
+fn test_parents_and_children_agree() {
+    let program = parse(CODE);
+    let index = NodeIndex::new(&program);
+
+    assert_eq!(None, index.parent(index.root()));
+    for id in pre_order(&index) {
+        for child in index.children(id) {
+            assert_eq!(Some(id), index.parent(child));
+            assert!(index.is_ancestor_of(id, child));
+            assert!(!index.is_ancestor_of(child, id));
+        }
+
+        assert_eq!(index.ancestors(id).last(), (id != index.root()).then(|| index.root()));
+    }
+}
+
+#[test]
+fn test_nodes_at_every_offset_are_the_ones_of_the_query() {
+    let program = parse(CODE);
+    let index = NodeIndex::new(&program);
+
+    for offset in 0..=CODE.len() + 1 {
+        let expected = node_at_offset(&program, offset).last().copied();
+        let actual = index.at_offset(offset).map(|id| index.get(&program, id));
+
+        assert_eq!(expected, actual, "the nodes at offset {} differ", offset);
+    }
+}
+
+#[test]
+fn test_enclosing_scopes() {
+    let program = parse(CODE);
+    let index = NodeIndex::new(&program);
+
+    let value = index.at_offset(CODE.find("$value * 2").unwrap() + 1).unwrap();
+    assert_eq!(NodeKind::DirectVariable, index.kind(value));
+
+    let arrow_function = index.enclosing_function(value).unwrap();
+    assert_eq!(NodeKind::ArrowFunction, index.kind(arrow_function));
+    assert_eq!(Some(arrow_function), index.enclosing_scope(value));
+
+    let method = index.enclosing_function(arrow_function).unwrap();
+    assert_eq!(NodeKind::Method, index.kind(method));
+
+    let class = index.enclosing_class_like(value).unwrap();
+    assert_eq!(NodeKind::Class, index.kind(class));
+    assert_eq!(Some(class), index.enclosing_scope(method));
+    assert_eq!(None, index.enclosing_class_like(class));
+
+    let Node::Class(class) = index.get(&program, class) else {
+        panic!("the enclosing class-like is a class");
+    };
+
+    assert_eq!(class.span(), index.span(index.id_of(Node::Class(class)).unwrap()));
+}
+
+#[test]
+fn test_siblings() {
+    let program = parse(CODE);
+    let index = NodeIndex::new(&program);
+
+    let class = index
+        .at_offset(CODE.find("class Service").unwrap())
+        .and_then(|id| std::iter::once(id).chain(index.ancestors(id)).find(|&id| index.kind(id) == NodeKind::Class));
+
+    let members: Vec<NodeKind> = index
+        .children(class.unwrap())
+        .filter(|&child| index.kind(child) == NodeKind::ClassLikeMember)
+        .flat_map(|member| index.children(member))
+        .map(|member| index.kind(member))
+        .collect();
+    assert_eq!(vec![NodeKind::ClassLikeConstant, NodeKind::Method, NodeKind::Method], members);
+
+    let name = index.at_offset(CODE.find("Service").unwrap()).unwrap();
+    let siblings: Vec<NodeId> = index.siblings(name).collect();
+    assert!(!siblings.contains(&name));
+    assert_eq!(index.children(index.parent(name).unwrap()).count() - 1, siblings.len());
+}
@@ -0,0 +1,53 @@
+use mago_ast::node::NodeKind;
+use mago_ast::Node;
+use mago_ast::Program;
+use mago_interner::ThreadedInterner;
+use mago_parser::parse_source;
+use mago_source::SourceManager;
+
+const CODE: &str = "<?php function foo() { return 1 + bar(2); }";
+
+fn parse(code: &str) -> Program {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source = manager.load(&manager.insert_content("node.php".to_string(), code.to_string(), true)).unwrap();
+
+    let (program, errors) = parse_source(&interner, &source);
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+
+    program
+}
+
+fn expression_kinds(node: &Node<'_>) -> Vec<NodeKind> {
+    node.filter_map(|node| match node {
+        Node::Binary(_) | Node::Call(_) | Node::LiteralInteger(_) => Some(node.kind()),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_filter_map_visits_descendants_before_their_node() {
+    let program = parse(CODE);
+
+    assert_eq!(
+        expression_kinds(&Node::Program(&program)),
+        vec![NodeKind::LiteralInteger, NodeKind::LiteralInteger, NodeKind::Call, NodeKind::Binary]
+    );
+}
+
+#[test]
+fn test_traverse_visits_nodes_before_their_descendants() {
+    let program = parse(CODE);
+
+    let mut kinds = vec![];
+    Node::Program(&program).traverse(&mut |node| {
+        if matches!(node, Node::Binary(_) | Node::Call(_) | Node::LiteralInteger(_)) {
+            kinds.push(node.kind());
+        }
+
+        // The arguments of calls are skipped.
+        !matches!(node, Node::Call(_))
+    });
+
+    assert_eq!(kinds, vec![NodeKind::Binary, NodeKind::LiteralInteger, NodeKind::Call]);
+}
@@ -0,0 +1,67 @@
+use mago_ast::node::NodeKind;
+use mago_ast::query::enclosing_class_like;
+use mago_ast::query::enclosing_function;
+use mago_ast::query::node_at_offset;
+use mago_ast::Node;
+use mago_ast::Program;
+use mago_interner::ThreadedInterner;
+use mago_parser::parse_source;
+use mago_source::SourceManager;
+
+const CODE: &str = "<?php class Foo { public function bar() { return baz(fn() => 1); } }";
+
+fn parse(code: &str) -> Program {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source = manager.load(&manager.insert_content("query.php".to_string(), code.to_string(), true)).unwrap();
+
+    let (program, errors) = parse_source(&interner, &source);
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+
+    program
+}
+
+fn innermost_kind(program: &Program, offset: usize) -> Option<NodeKind> {
+    node_at_offset(program, offset).last().map(Node::kind)
+}
+
+#[test]
+fn test_path_starts_at_the_program() {
+    let program = parse(CODE);
+    let path = node_at_offset(&program, CODE.find("baz").unwrap());
+
+    assert_eq!(Some(NodeKind::Program), path.first().map(Node::kind));
+    assert_eq!(Some(NodeKind::LocalIdentifier), path.last().map(Node::kind));
+    assert!(path.iter().any(|node| node.kind() == NodeKind::Method));
+}
+
+#[test]
+fn test_boundaries_prefer_the_node_to_the_left() {
+    let program = parse(CODE);
+
+    // Between `baz` and `(`.
+    assert_eq!(Some(NodeKind::LocalIdentifier), innermost_kind(&program, CODE.find("(fn").unwrap()));
+    // Right after the `;` of the return statement.
+    assert_eq!(Some(NodeKind::Terminator), innermost_kind(&program, CODE.find("; }").unwrap() + 1));
+}
+
+#[test]
+fn test_offsets_outside_of_the_program() {
+    let program = parse(CODE);
+
+    assert!(node_at_offset(&program, CODE.len() + 1).is_empty());
+}
+
+#[test]
+fn test_enclosing_function_and_class_like() {
+    let program = parse(CODE);
+
+    let offset = CODE.find("1)").unwrap();
+    assert_eq!(Some(NodeKind::ArrowFunction), enclosing_function(&program, offset).map(|node| node.kind()));
+    assert_eq!(Some(NodeKind::Class), enclosing_class_like(&program, offset).map(|node| node.kind()));
+
+    let offset = CODE.find("return").unwrap();
+    assert_eq!(Some(NodeKind::Method), enclosing_function(&program, offset).map(|node| node.kind()));
+
+    assert_eq!(None, enclosing_function(&program, CODE.find("class").unwrap()));
+}
@@ -0,0 +1,63 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::schema::Autoload;
+
+/// The packages installed by composer, as listed in the `vendor/composer/installed.json` file.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct InstalledPackages {
+    /// The installed packages, including the development dependencies.
+    #[serde(default)]
+    pub packages: Vec<InstalledPackage>,
+
+    /// Whether the development dependencies were installed.
+    #[serde(default)]
+    pub dev: bool,
+
+    /// The names of the installed packages which are only development dependencies.
+    #[serde(rename = "dev-package-names", default)]
+    pub dev_package_names: Vec<String>,
+}
+
+impl InstalledPackages {
+    /// Determines whether the given installed package is only a development dependency.
+    pub fn is_dev_package(&self, package: &InstalledPackage) -> bool {
+        self.dev_package_names.iter().any(|name| name.eq_ignore_ascii_case(&package.name))
+    }
+}
+
+/// A package installed by composer.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct InstalledPackage {
+    /// The name of the package, including its vendor name, e.g. `symfony/console`.
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// The path to the directory the package is installed in, relative to the `vendor/composer` directory.
+    ///
+    /// This is only recorded since composer 2, the packages being installed in `vendor/<name>` before.
+    #[serde(rename = "install-path", default, skip_serializing_if = "Option::is_none")]
+    pub install_path: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autoload: Option<Autoload>,
+}
+
+/// The layouts of the `installed.json` file: an object since composer 2, and a list of packages before.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum InstalledFile {
+    Packages(InstalledPackages),
+    List(Vec<InstalledPackage>),
+}
+
+impl From<InstalledFile> for InstalledPackages {
+    fn from(file: InstalledFile) -> Self {
+        match file {
+            InstalledFile::Packages(packages) => packages,
+            InstalledFile::List(packages) => InstalledPackages { packages, ..Default::default() },
+        }
+    }
+}
@@ -3,8 +3,10 @@ use std::str::FromStr;
 use serde_json::from_str;
 use serde_json::Error;
 
+pub use crate::installed::*;
 pub use crate::schema::*;
 
+pub mod installed;
 pub mod schema;
 
 impl FromStr for ComposerPackage {
@@ -14,3 +16,11 @@ impl FromStr for ComposerPackage {
         from_str(s)
     }
 }
+
+impl FromStr for InstalledPackages {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_str::<InstalledFile>(s).map(InstalledPackages::from)
+    }
+}
@@ -193,7 +193,8 @@ impl TagKind {
     /// If the tag does not have a vendor, `None` is returned.
     pub fn get_vendor(&self) -> Option<TagVendor> {
         match self {
-            Self::PsalmConsistentConstructor
+            Self::PsalmTemplate
+            | Self::PsalmConsistentConstructor
             | Self::PsalmConsistentTemplates
             | Self::PsalmParamOut
             | Self::PsalmVar
@@ -267,6 +268,7 @@ impl TagKind {
     ///  `None` is returned.
     pub fn get_non_vendored_variant(&self) -> Option<TagKind> {
         match self {
+            Self::PsalmTemplate => Some(Self::Template),
             Self::PsalmParamOut => Some(Self::ParamOut),
             Self::PsalmVar => Some(Self::Var),
             Self::PsalmParam => Some(Self::Param),
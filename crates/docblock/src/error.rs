@@ -140,3 +140,90 @@ impl ParseError {
         }
     }
 }
+
+/// An error in a type written in a docblock, such as `array<int` or `Foo::`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TypeError {
+    UnexpectedEnd,
+    UnexpectedCharacter(char),
+    UnterminatedString,
+    InvalidInteger(String),
+}
+
+impl std::error::Error for TypeError {}
+
+impl std::fmt::Display for TypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeError::UnexpectedEnd => write!(f, "Unexpected end of type."),
+            TypeError::UnexpectedCharacter(character) => write!(f, "Unexpected character `{}` in type.", character),
+            TypeError::UnterminatedString => write!(f, "Unterminated string literal in type."),
+            TypeError::InvalidInteger(integer) => write!(f, "Invalid integer literal `{}` in type.", integer),
+        }
+    }
+}
+
+/// An error in a tag of a docblock, such as a `@param` tag without a variable name.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TagError {
+    MissingType(Span),
+    InvalidType(Span, TypeError),
+    MissingVariable(Span),
+    MissingTemplateName(Span),
+}
+
+impl HasSpan for TagError {
+    fn span(&self) -> Span {
+        match self {
+            TagError::MissingType(span) => *span,
+            TagError::InvalidType(span, _) => *span,
+            TagError::MissingVariable(span) => *span,
+            TagError::MissingTemplateName(span) => *span,
+        }
+    }
+}
+
+impl std::error::Error for TagError {}
+
+impl std::fmt::Display for TagError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagError::MissingType(_) => {
+                write!(f, "Missing type in docblock tag.")
+            }
+            TagError::InvalidType(_, error) => {
+                write!(f, "Invalid type in docblock tag: {}", error)
+            }
+            TagError::MissingVariable(_) => {
+                write!(f, "Missing variable name in docblock tag.")
+            }
+            TagError::MissingTemplateName(_) => {
+                write!(f, "Missing template name in docblock tag.")
+            }
+        }
+    }
+}
+
+impl TagError {
+    pub fn note(&self) -> &'static str {
+        match self {
+            TagError::MissingType(_) => "The tag is expected to start with a type, such as `int` or `array<string>`.",
+            TagError::InvalidType(_, _) => "The type of the tag could not be parsed, so the tag is ignored.",
+            TagError::MissingVariable(_) => {
+                "The tag is expected to name a parameter, such as `$value`, after its type."
+            }
+            TagError::MissingTemplateName(_) => {
+                "The tag is expected to start with the name of the template, such as `T`."
+            }
+        }
+    }
+
+    pub fn help(&self) -> &'static str {
+        match self {
+            TagError::MissingType(_) => "Add a type to the tag, such as `@param int $value`.",
+            TagError::InvalidType(_, _) => "Correct the syntax of the type, or remove the tag.",
+            TagError::MissingVariable(_) => "Add the name of the parameter to the tag, such as `@param int $value`.",
+            TagError::MissingTemplateName(_) => "Add a name to the tag, such as `@template T of object`.",
+        }
+    }
+}
@@ -10,6 +10,8 @@ mod internal;
 
 pub mod document;
 pub mod error;
+pub mod tag;
+pub mod r#type;
 
 #[inline]
 pub fn parse_trivia(interner: &ThreadedInterner, trivia: &Trivia) -> Result<Document, ParseError> {
@@ -0,0 +1,368 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::StringIdentifier;
+use mago_interner::ThreadedInterner;
+use mago_span::Span;
+
+use crate::document::Document;
+use crate::document::Element;
+use crate::document::Tag;
+use crate::document::TagKind;
+use crate::error::TagError;
+use crate::r#type::parse_leading_type;
+use crate::r#type::Type;
+
+/// A `@param` tag, such as `@param array<string> $names The names.`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct ParamTag {
+    pub span: Span,
+    pub r#type: Type,
+    /// The name of the parameter, including its `$`.
+    pub variable: StringIdentifier,
+    pub is_variadic: bool,
+    pub is_by_reference: bool,
+    pub description: StringIdentifier,
+}
+
+/// A `@return` tag, such as `@return list<int> The identifiers.`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct ReturnTag {
+    pub span: Span,
+    pub r#type: Type,
+    pub description: StringIdentifier,
+}
+
+/// A `@var` tag, such as `@var non-empty-string` or `@var Foo $foo`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct VarTag {
+    pub span: Span,
+    pub r#type: Type,
+    /// The name of the variable, including its `$`, if any.
+    pub variable: Option<StringIdentifier>,
+    pub description: StringIdentifier,
+}
+
+/// A `@template` tag, or one of its variants, such as `@template-covariant T of object`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct TemplateTag {
+    pub span: Span,
+    pub name: StringIdentifier,
+    /// The type the template is bound to, following `of` or `as`.
+    pub constraint: Option<Type>,
+    pub variance: Variance,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum Variance {
+    Invariant,
+    Covariant,
+    Contravariant,
+}
+
+/// A `@deprecated` tag, such as `@deprecated Use `bar()` instead.`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct DeprecatedTag {
+    pub span: Span,
+    pub description: StringIdentifier,
+}
+
+/// The tags of a docblock which describe the types, templates, and status of the element it documents.
+///
+/// The tags prefixed by a vendor, such as `@psalm-param`, take precedence over the ones without a prefix,
+/// such as `@param`, as they usually describe the element more precisely.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct Documentation {
+    pub params: Vec<ParamTag>,
+    pub r#return: Option<ReturnTag>,
+    pub vars: Vec<VarTag>,
+    pub templates: Vec<TemplateTag>,
+    pub deprecated: Option<DeprecatedTag>,
+    /// The span of the `@internal` tag, if any.
+    pub internal: Option<Span>,
+}
+
+impl Documentation {
+    /// Extracts the documentation from the tags of the given document.
+    ///
+    /// # Returns
+    ///
+    /// The documentation, along with the errors of the tags which could not be parsed, which are left out.
+    pub fn from_document(interner: &ThreadedInterner, document: &Document) -> (Self, Vec<TagError>) {
+        let mut documentation = Documentation::default();
+        let mut errors = vec![];
+
+        let mut vendored = Documentation::default();
+        for element in document.elements.iter() {
+            let Element::Tag(tag) = element else {
+                continue;
+            };
+
+            let (target, kind) = match tag.kind.get_non_vendored_variant() {
+                Some(kind) => (&mut vendored, kind),
+                None => (&mut documentation, tag.kind),
+            };
+
+            let result = match kind {
+                TagKind::Param => parse_param_tag(interner, tag).map(|param| target.params.push(param)),
+                TagKind::Return => parse_return_tag(interner, tag).map(|r#return| target.r#return = Some(r#return)),
+                TagKind::Var => parse_var_tag(interner, tag).map(|var| target.vars.push(var)),
+                TagKind::Template | TagKind::TemplateCovariant | TagKind::TemplateContravariant => {
+                    parse_template_tag(interner, tag).map(|template| target.templates.push(template))
+                }
+                TagKind::Deprecated => {
+                    target.deprecated.get_or_insert(DeprecatedTag { span: tag.span, description: tag.description });
+
+                    Ok(())
+                }
+                TagKind::Internal => {
+                    target.internal.get_or_insert(tag.span);
+
+                    Ok(())
+                }
+                _ => Ok(()),
+            };
+
+            if let Err(error) = result {
+                errors.push(error);
+            }
+        }
+
+        merge(&mut documentation.params, vendored.params, |param| Some(param.variable));
+        merge(&mut documentation.vars, vendored.vars, |var| var.variable);
+        merge(&mut documentation.templates, vendored.templates, |template| Some(template.name));
+        documentation.r#return = vendored.r#return.or(documentation.r#return);
+        documentation.deprecated = documentation.deprecated.or(vendored.deprecated);
+        documentation.internal = documentation.internal.or(vendored.internal);
+
+        (documentation, errors)
+    }
+
+    /// Returns the `@param` tag of the parameter with the given name, including its `$`.
+    pub fn get_param(&self, variable: &StringIdentifier) -> Option<&ParamTag> {
+        self.params.iter().find(|param| param.variable == *variable)
+    }
+}
+
+/// Parses a `@param` tag, such as `@param int ...$values The values.`.
+pub fn parse_param_tag(interner: &ThreadedInterner, tag: &Tag) -> Result<ParamTag, TagError> {
+    let (r#type, rest) = parse_tag_type(interner, tag)?;
+
+    let rest = rest.trim_start();
+    let (is_by_reference, rest) = strip(rest, "&");
+    let (is_variadic, rest) = strip(rest.trim_start(), "...");
+    let Some((variable, rest)) = split_variable(rest) else {
+        return Err(TagError::MissingVariable(tag.span));
+    };
+
+    Ok(ParamTag {
+        span: tag.span,
+        r#type,
+        variable: interner.intern(variable),
+        is_variadic,
+        is_by_reference,
+        description: interner.intern(rest.trim()),
+    })
+}
+
+/// Parses a `@return` tag, such as `@return list<int> The identifiers.`.
+pub fn parse_return_tag(interner: &ThreadedInterner, tag: &Tag) -> Result<ReturnTag, TagError> {
+    let (r#type, rest) = parse_tag_type(interner, tag)?;
+
+    Ok(ReturnTag { span: tag.span, r#type, description: interner.intern(rest.trim()) })
+}
+
+/// Parses a `@var` tag, such as `@var Foo $foo The foo.`.
+pub fn parse_var_tag(interner: &ThreadedInterner, tag: &Tag) -> Result<VarTag, TagError> {
+    let (r#type, rest) = parse_tag_type(interner, tag)?;
+
+    let (variable, rest) = match split_variable(rest.trim_start()) {
+        Some((variable, rest)) => (Some(interner.intern(variable)), rest),
+        None => (None, rest),
+    };
+
+    Ok(VarTag { span: tag.span, r#type, variable, description: interner.intern(rest.trim()) })
+}
+
+/// Parses a `@template` tag, or one of its variants, such as `@template T of object`.
+pub fn parse_template_tag(interner: &ThreadedInterner, tag: &Tag) -> Result<TemplateTag, TagError> {
+    let variance = match tag.kind.get_non_vendored_variant().unwrap_or(tag.kind) {
+        TagKind::TemplateCovariant => Variance::Covariant,
+        TagKind::TemplateContravariant => Variance::Contravariant,
+        _ => Variance::Invariant,
+    };
+
+    let description = interner.lookup(&tag.description).trim_start();
+    let end = description.find(|character: char| character.is_whitespace()).unwrap_or(description.len());
+    let name = &description[..end];
+    if name.is_empty() || !name.chars().all(|character| character.is_alphanumeric() || character == '_') {
+        return Err(TagError::MissingTemplateName(tag.span));
+    }
+
+    let rest = description[end..].trim_start();
+    let constraint = match rest.strip_prefix("of ").or_else(|| rest.strip_prefix("as ")) {
+        Some(rest) => match parse_leading_type(interner, rest.trim_start()) {
+            Ok((constraint, _)) => Some(constraint),
+            Err(error) => return Err(TagError::InvalidType(tag.span, error)),
+        },
+        None => None,
+    };
+
+    Ok(TemplateTag { span: tag.span, name: interner.intern(name), constraint, variance })
+}
+
+/// Parses the type at the start of the description of the given tag.
+fn parse_tag_type<'i>(interner: &'i ThreadedInterner, tag: &Tag) -> Result<(Type, &'i str), TagError> {
+    let description = interner.lookup(&tag.description).trim_start();
+    if description.is_empty() || description.starts_with('$') {
+        return Err(TagError::MissingType(tag.span));
+    }
+
+    parse_leading_type(interner, description).map_err(|error| TagError::InvalidType(tag.span, error))
+}
+
+/// Splits the variable name at the start of the given string, such as `$foo` in `$foo The foo.`.
+fn split_variable(input: &str) -> Option<(&str, &str)> {
+    let name = input.strip_prefix('$')?;
+    let end = name.find(|character: char| !character.is_alphanumeric() && character != '_').unwrap_or(name.len());
+    if end == 0 {
+        return None;
+    }
+
+    Some(input.split_at(end + 1))
+}
+
+fn strip<'i>(input: &'i str, prefix: &str) -> (bool, &'i str) {
+    match input.strip_prefix(prefix) {
+        Some(rest) => (true, rest),
+        None => (false, input),
+    }
+}
+
+/// Merges the vendored tags into the given tags, replacing the ones with the same key.
+fn merge<T, K: PartialEq>(tags: &mut Vec<T>, vendored: Vec<T>, key: impl Fn(&T) -> K) {
+    for tag in vendored {
+        match tags.iter().position(|existing| key(existing) == key(&tag)) {
+            Some(position) => tags[position] = tag,
+            None => tags.push(tag),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mago_span::Position;
+
+    use crate::error::TypeError;
+    use crate::parse_phpdoc_with_span;
+
+    fn documentation(interner: &ThreadedInterner, phpdoc: &str) -> (Documentation, Vec<TagError>) {
+        let span = Span::new(Position::dummy(0), Position::dummy(phpdoc.len()));
+        let document = parse_phpdoc_with_span(interner, phpdoc, span).expect("Failed to parse PHPDoc");
+
+        Documentation::from_document(interner, &document)
+    }
+
+    #[test]
+    fn test_extract_documentation() {
+        let interner = ThreadedInterner::new();
+        let (documentation, errors) = documentation(
+            &interner,
+            r#"/**
+             * @template-covariant T of object
+             * @param array<T> $values The values.
+             * @param int &...$counts
+             * @return list<T>
+             * @deprecated Use `bar()` instead.
+             * @internal
+             */"#,
+        );
+
+        assert!(errors.is_empty());
+        assert_eq!(documentation.templates.len(), 1);
+        assert_eq!(documentation.templates[0].variance, Variance::Covariant);
+        assert!(documentation.templates[0].constraint.is_some());
+
+        assert_eq!(documentation.params.len(), 2);
+        assert_eq!(interner.lookup(&documentation.params[0].description), "The values.");
+        assert!(documentation.params[1].is_by_reference);
+        assert!(documentation.params[1].is_variadic);
+        assert!(documentation.get_param(&interner.intern("$counts")).is_some());
+
+        assert!(documentation.r#return.is_some());
+        assert_eq!(
+            documentation.deprecated.map(|deprecated| interner.lookup(&deprecated.description)),
+            Some("Use `bar()` instead.")
+        );
+        assert!(documentation.internal.is_some());
+    }
+
+    #[test]
+    fn test_vendored_tags_take_precedence() {
+        let interner = ThreadedInterner::new();
+        let (documentation, errors) = documentation(
+            &interner,
+            r#"/**
+             * @psalm-param list<string> $names
+             * @param array $names
+             * @param int $count
+             * @return array
+             * @phpstan-return list<string>
+             */"#,
+        );
+
+        assert!(errors.is_empty());
+        assert_eq!(documentation.params.len(), 2);
+        assert_eq!(documentation.params[0].variable, interner.intern("$names"));
+        assert!(
+            matches!(&documentation.params[0].r#type, Type::Reference(reference) if interner.lookup(&reference.name) == "list")
+        );
+        assert_eq!(documentation.params[1].variable, interner.intern("$count"));
+        assert!(matches!(
+            &documentation.r#return,
+            Some(ReturnTag { r#type: Type::Reference(reference), .. }) if interner.lookup(&reference.name) == "list"
+        ));
+    }
+
+    #[test]
+    fn test_multi_line_types() {
+        let interner = ThreadedInterner::new();
+        let (documentation, errors) = documentation(
+            &interner,
+            r#"/**
+             * @param array{
+             *   id: int,
+             *   name: string,
+             * } $user
+             */"#,
+        );
+
+        assert!(errors.is_empty());
+        assert_eq!(documentation.params[0].variable, interner.intern("$user"));
+    }
+
+    #[test]
+    fn test_malformed_tags() {
+        let interner = ThreadedInterner::new();
+        let (documentation, errors) = documentation(
+            &interner,
+            r#"/**
+             * @param $value
+             * @param int
+             * @return array<int
+             * @template
+             * @var string $valid
+             */"#,
+        );
+
+        assert_eq!(documentation.vars.len(), 1);
+        assert!(documentation.params.is_empty());
+        assert!(documentation.r#return.is_none());
+        assert!(matches!(errors[0], TagError::MissingType(_)));
+        assert!(matches!(errors[1], TagError::MissingVariable(_)));
+        assert!(matches!(errors[2], TagError::InvalidType(_, TypeError::UnexpectedEnd)));
+        assert!(matches!(errors[3], TagError::MissingTemplateName(_)));
+    }
+}
@@ -0,0 +1,755 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::StringIdentifier;
+use mago_interner::ThreadedInterner;
+
+use crate::error::TypeError;
+
+/// A type written in a docblock, such as `array<int, string>`, `?Foo`, or `list{int, string}`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum Type {
+    /// A keyword, such as `int` or `non-empty-string`, or a class-like name, optionally followed by
+    /// generic arguments, such as `array<int, string>` or `Collection<User>`.
+    Reference(ReferenceType),
+
+    /// A nullable type, such as `?int`.
+    Nullable(Box<Type>),
+
+    /// A union of types, such as `int|string`.
+    Union(Vec<Type>),
+
+    /// An intersection of types, such as `Countable&Traversable`.
+    Intersection(Vec<Type>),
+
+    /// A list of values of the given type, such as `int[]`.
+    Array(Box<Type>),
+
+    /// An array or object shape, such as `array{id: int, name?: string}`.
+    Shape(ShapeType),
+
+    /// A callable, or closure, signature, such as `callable(int, string): bool`.
+    Callable(CallableType),
+
+    /// A literal value, such as `'foo'`, `42`, or `1.5`.
+    Literal(LiteralType),
+
+    /// A class-like constant, or a pattern of class-like constants, such as `Foo::BAR` or `Foo::BAR_*`.
+    ClassConstant(ClassConstantType),
+
+    /// A variable, such as `$this`.
+    Variable(StringIdentifier),
+
+    /// A conditional type, such as `($value is string ? int : float)`.
+    Conditional(ConditionalType),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct ReferenceType {
+    /// The name, as written, e.g. `int`, `Foo`, or `\App\Foo`.
+    pub name: StringIdentifier,
+    pub arguments: Vec<Type>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct ShapeType {
+    /// The name of the shape, e.g. `array`, `list`, or `object`.
+    pub name: StringIdentifier,
+    pub entries: Vec<ShapeEntry>,
+    /// Whether the shape only contains the listed entries, i.e. it does not end with `...`.
+    pub is_sealed: bool,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct ShapeEntry {
+    pub key: Option<ShapeKey>,
+    pub is_optional: bool,
+    pub value: Type,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum ShapeKey {
+    String(StringIdentifier),
+    Integer(i64),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct CallableType {
+    /// The name of the callable, e.g. `callable`, `Closure`, or `pure-callable`.
+    pub name: StringIdentifier,
+    pub parameters: Vec<CallableParameter>,
+    pub return_type: Option<Box<Type>>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct CallableParameter {
+    pub r#type: Option<Type>,
+    pub is_by_reference: bool,
+    pub is_variadic: bool,
+    pub is_optional: bool,
+    pub variable: Option<StringIdentifier>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum LiteralType {
+    /// A string literal, without its quotes.
+    String(StringIdentifier),
+    Integer(i64),
+    /// A float literal, as written.
+    Float(StringIdentifier),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct ClassConstantType {
+    pub class: StringIdentifier,
+    /// The name of the constant, which may contain `*` wildcards.
+    pub constant: StringIdentifier,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct ConditionalType {
+    /// The checked type, usually a parameter, e.g. `$value`, or a template, e.g. `T`.
+    pub subject: Box<Type>,
+    pub is_negated: bool,
+    pub target: Box<Type>,
+    pub then: Box<Type>,
+    pub otherwise: Box<Type>,
+}
+
+/// Parses the given string as a single type, such as `array<int, string>`.
+pub fn parse_type(interner: &ThreadedInterner, input: &str) -> Result<Type, TypeError> {
+    let (r#type, rest) = parse_leading_type(interner, input.trim())?;
+
+    match rest.trim_start().chars().next() {
+        Some(character) => Err(TypeError::UnexpectedCharacter(character)),
+        None => Ok(r#type),
+    }
+}
+
+/// Parses the type at the start of the given string, such as `array<int, string>` in
+/// `array<int, string> $values The values.`, returning it along with the rest of the string.
+///
+/// The type ends at the first whitespace which is not within brackets, unless it is followed by `|`.
+pub fn parse_leading_type<'i>(interner: &ThreadedInterner, input: &'i str) -> Result<(Type, &'i str), TypeError> {
+    let mut parser = TypeParser { interner, input, offset: 0, depth: 0 };
+    let r#type = parser.parse_type()?;
+
+    let rest = &input[parser.offset..];
+    match rest.chars().next() {
+        Some(character) if !character.is_whitespace() => Err(TypeError::UnexpectedCharacter(character)),
+        _ => Ok((r#type, rest)),
+    }
+}
+
+struct TypeParser<'i> {
+    interner: &'i ThreadedInterner,
+    input: &'i str,
+    offset: usize,
+    /// The number of brackets enclosing the current position, within which whitespace is insignificant.
+    depth: usize,
+}
+
+impl<'i> TypeParser<'i> {
+    fn parse_type(&mut self) -> Result<Type, TypeError> {
+        let r#type = self.parse_union()?;
+        if self.depth == 0 {
+            return Ok(r#type);
+        }
+
+        self.skip_whitespace();
+        if !self.eat_keyword("is") {
+            return Ok(r#type);
+        }
+
+        let is_negated = self.eat_keyword("not");
+        let target = self.parse_union()?;
+        self.expect(b'?')?;
+        let then = self.parse_type()?;
+        self.expect(b':')?;
+        let otherwise = self.parse_type()?;
+
+        Ok(Type::Conditional(ConditionalType {
+            subject: Box::new(r#type),
+            is_negated,
+            target: Box::new(target),
+            then: Box::new(then),
+            otherwise: Box::new(otherwise),
+        }))
+    }
+
+    fn parse_union(&mut self) -> Result<Type, TypeError> {
+        let first = self.parse_intersection()?;
+        if !self.eat_separator(b'|') {
+            return Ok(first);
+        }
+
+        let mut types = vec![first];
+        loop {
+            types.push(self.parse_intersection()?);
+
+            if !self.eat_separator(b'|') {
+                break;
+            }
+        }
+
+        Ok(Type::Union(types))
+    }
+
+    fn parse_intersection(&mut self) -> Result<Type, TypeError> {
+        let first = self.parse_postfix()?;
+        if !self.eat_intersection_separator() {
+            return Ok(first);
+        }
+
+        let mut types = vec![first];
+        loop {
+            types.push(self.parse_postfix()?);
+
+            if !self.eat_intersection_separator() {
+                break;
+            }
+        }
+
+        Ok(Type::Intersection(types))
+    }
+
+    fn parse_postfix(&mut self) -> Result<Type, TypeError> {
+        let mut r#type = self.parse_primary()?;
+        while self.rest().starts_with("[]") {
+            self.offset += 2;
+            r#type = Type::Array(Box::new(r#type));
+        }
+
+        Ok(r#type)
+    }
+
+    fn parse_primary(&mut self) -> Result<Type, TypeError> {
+        self.skip_whitespace();
+
+        let Some(byte) = self.peek() else {
+            return Err(TypeError::UnexpectedEnd);
+        };
+
+        match byte {
+            b'?' => {
+                self.offset += 1;
+
+                Ok(Type::Nullable(Box::new(self.parse_postfix()?)))
+            }
+            b'(' => {
+                self.offset += 1;
+                self.depth += 1;
+                let r#type = self.parse_type()?;
+                self.expect(b')')?;
+                self.depth -= 1;
+
+                Ok(r#type)
+            }
+            b'\'' | b'"' => Ok(Type::Literal(LiteralType::String(self.parse_string()?))),
+            b'-' | b'0'..=b'9' => self.parse_number(),
+            b'$' => {
+                self.offset += 1;
+                let name = self.read_while(is_identifier_byte);
+                if name.is_empty() {
+                    return Err(self.unexpected());
+                }
+
+                Ok(Type::Variable(self.interner.intern(format!("${}", name))))
+            }
+            byte if is_identifier_start(byte) => self.parse_named(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_named(&mut self) -> Result<Type, TypeError> {
+        let name = self.read_name();
+
+        if self.rest().starts_with("::") {
+            self.offset += 2;
+            let constant = self.read_while(|byte| is_identifier_byte(byte) || byte == b'*');
+            if constant.is_empty() {
+                return Err(self.unexpected());
+            }
+
+            return Ok(Type::ClassConstant(ClassConstantType {
+                class: self.interner.intern(name),
+                constant: self.interner.intern(constant),
+            }));
+        }
+
+        match self.peek() {
+            Some(b'<') => {
+                self.offset += 1;
+                let arguments = self.parse_list(b'>', Self::parse_type)?;
+
+                Ok(Type::Reference(ReferenceType { name: self.interner.intern(name), arguments }))
+            }
+            Some(b'{') if is_shape_name(name) => {
+                self.offset += 1;
+
+                self.parse_shape(name)
+            }
+            Some(b'(') if is_callable_name(name) => {
+                self.offset += 1;
+
+                self.parse_callable(name)
+            }
+            _ => Ok(Type::Reference(ReferenceType { name: self.interner.intern(name), arguments: vec![] })),
+        }
+    }
+
+    fn parse_shape(&mut self, name: &str) -> Result<Type, TypeError> {
+        self.depth += 1;
+
+        let mut entries = vec![];
+        let mut is_sealed = true;
+        loop {
+            self.skip_whitespace();
+            if self.eat(b'}') {
+                break;
+            }
+
+            if self.rest().starts_with("...") {
+                self.offset += 3;
+                is_sealed = false;
+                // The types of the unlisted entries, e.g. `...<int, string>`, are not kept.
+                if self.eat(b'<') {
+                    self.parse_list(b'>', Self::parse_type)?;
+                }
+
+                self.expect(b'}')?;
+                break;
+            }
+
+            let (key, is_optional) = self.parse_shape_key()?;
+            let value = self.parse_type()?;
+            entries.push(ShapeEntry { key, is_optional, value });
+
+            self.skip_whitespace();
+            if !self.eat(b',') {
+                self.expect(b'}')?;
+                break;
+            }
+        }
+
+        self.depth -= 1;
+
+        Ok(Type::Shape(ShapeType { name: self.interner.intern(name), entries, is_sealed }))
+    }
+
+    /// Parses the key of a shape entry, along with its optionality, such as `name?:` in `array{name?: string}`,
+    /// leaving the position unchanged if the entry has no key.
+    fn parse_shape_key(&mut self) -> Result<(Option<ShapeKey>, bool), TypeError> {
+        let start = self.offset;
+        let key = match self.peek() {
+            Some(b'\'' | b'"') => Some(ShapeKey::String(self.parse_string()?)),
+            Some(b'-' | b'0'..=b'9') => {
+                let digits = self.read_while(|byte| byte.is_ascii_digit() || byte == b'-');
+
+                digits.parse().ok().map(ShapeKey::Integer)
+            }
+            Some(byte) if is_identifier_start(byte) => {
+                let name = self.read_while(|byte| is_identifier_byte(byte) || byte == b'-');
+
+                Some(ShapeKey::String(self.interner.intern(name)))
+            }
+            _ => None,
+        };
+
+        if let Some(key) = key {
+            self.skip_whitespace();
+            let is_optional = self.eat(b'?');
+            self.skip_whitespace();
+            if self.peek() == Some(b':') && !self.rest().starts_with("::") {
+                self.offset += 1;
+
+                return Ok((Some(key), is_optional));
+            }
+        }
+
+        self.offset = start;
+
+        Ok((None, false))
+    }
+
+    fn parse_callable(&mut self, name: &str) -> Result<Type, TypeError> {
+        let parameters = self.parse_list(b')', Self::parse_callable_parameter)?;
+
+        let return_type = if self.eat(b':') {
+            self.skip_whitespace_in_signature();
+
+            Some(Box::new(self.parse_postfix()?))
+        } else {
+            None
+        };
+
+        Ok(Type::Callable(CallableType { name: self.interner.intern(name), parameters, return_type }))
+    }
+
+    fn parse_callable_parameter(&mut self) -> Result<CallableParameter, TypeError> {
+        self.skip_whitespace();
+        let r#type = match self.peek() {
+            Some(b'$' | b'&' | b'.') => None,
+            _ => Some(self.parse_type()?),
+        };
+
+        self.skip_whitespace();
+        let is_by_reference = self.eat(b'&');
+        self.skip_whitespace();
+        let is_variadic = self.rest().starts_with("...");
+        if is_variadic {
+            self.offset += 3;
+        }
+
+        self.skip_whitespace();
+        let variable = if self.eat(b'$') {
+            Some(self.interner.intern(format!("${}", self.read_while(is_identifier_byte))))
+        } else {
+            None
+        };
+
+        self.skip_whitespace();
+        let is_optional = self.eat(b'=');
+
+        if r#type.is_none() && variable.is_none() {
+            return Err(self.unexpected());
+        }
+
+        Ok(CallableParameter { r#type, is_by_reference, is_variadic, is_optional, variable })
+    }
+
+    /// Parses a list of comma-separated items, up to the given closing bracket, which is consumed.
+    fn parse_list<T>(
+        &mut self,
+        closing: u8,
+        parse: impl Fn(&mut Self) -> Result<T, TypeError>,
+    ) -> Result<Vec<T>, TypeError> {
+        self.depth += 1;
+
+        let mut items = vec![];
+        loop {
+            self.skip_whitespace();
+            if self.eat(closing) {
+                break;
+            }
+
+            items.push(parse(self)?);
+
+            self.skip_whitespace();
+            if !self.eat(b',') {
+                self.expect(closing)?;
+                break;
+            }
+        }
+
+        self.depth -= 1;
+
+        Ok(items)
+    }
+
+    fn parse_string(&mut self) -> Result<StringIdentifier, TypeError> {
+        let Some(quote) = self.peek() else {
+            return Err(TypeError::UnexpectedEnd);
+        };
+
+        let start = self.offset + 1;
+        let mut offset = start;
+        let bytes = self.input.as_bytes();
+        while offset < bytes.len() {
+            match bytes[offset] {
+                b'\\' => offset += 2,
+                byte if byte == quote => {
+                    self.offset = offset + 1;
+
+                    return Ok(self.interner.intern(&self.input[start..offset]));
+                }
+                _ => offset += 1,
+            }
+        }
+
+        Err(TypeError::UnterminatedString)
+    }
+
+    fn parse_number(&mut self) -> Result<Type, TypeError> {
+        let start = self.offset;
+        self.eat(b'-');
+        let integer = self.read_while(|byte| byte.is_ascii_digit() || byte == b'_');
+        if integer.is_empty() {
+            return Err(self.unexpected());
+        }
+
+        if self.peek() == Some(b'.') {
+            self.offset += 1;
+            self.read_while(|byte| byte.is_ascii_digit() || byte == b'_');
+
+            return Ok(Type::Literal(LiteralType::Float(self.interner.intern(&self.input[start..self.offset]))));
+        }
+
+        let literal = self.input[start..self.offset].replace('_', "");
+        match literal.parse() {
+            Ok(value) => Ok(Type::Literal(LiteralType::Integer(value))),
+            Err(_) => Err(TypeError::InvalidInteger(literal)),
+        }
+    }
+
+    /// Reads a name, such as `int`, `non-empty-string`, or `\App\Foo`.
+    fn read_name(&mut self) -> &'i str {
+        let bytes = self.input.as_bytes();
+        let start = self.offset;
+        let mut offset = start;
+        while offset < bytes.len() {
+            let byte = bytes[offset];
+            // Hyphens are only part of keywords, such as `class-string`, and are always followed by a letter.
+            let is_hyphen = byte == b'-' && bytes.get(offset + 1).is_some_and(|next| next.is_ascii_alphabetic());
+            if !is_identifier_byte(byte) && byte != b'\\' && !is_hyphen {
+                break;
+            }
+
+            offset += 1;
+        }
+
+        self.offset = offset;
+
+        &self.input[start..offset]
+    }
+
+    fn read_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'i str {
+        let start = self.offset;
+        while self.peek().is_some_and(&predicate) {
+            self.offset += 1;
+        }
+
+        &self.input[start..self.offset]
+    }
+
+    /// Consumes the given separator, along with the surrounding whitespace.
+    ///
+    /// Outside of brackets, the separator may only be preceded by whitespace if it is followed by a type,
+    /// e.g. `int | string`.
+    fn eat_separator(&mut self, separator: u8) -> bool {
+        let start = self.offset;
+        self.offset += self.rest().len() - self.rest().trim_start().len();
+        if self.eat(separator) {
+            self.offset += self.rest().len() - self.rest().trim_start().len();
+
+            return true;
+        }
+
+        self.offset = start;
+
+        false
+    }
+
+    /// Consumes an `&` separating the types of an intersection, and not marking a parameter passed by
+    /// reference, e.g. `int &$value`.
+    fn eat_intersection_separator(&mut self) -> bool {
+        let start = self.offset;
+        if self.depth > 0 {
+            self.skip_whitespace();
+        }
+
+        if self.eat(b'&') {
+            let rest = self.rest().trim_start();
+            if !rest.starts_with('$') && !rest.starts_with("...") && !rest.is_empty() {
+                self.skip_whitespace();
+
+                return true;
+            }
+        }
+
+        self.offset = start;
+
+        false
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let rest = self.rest();
+        if rest.starts_with(keyword) && rest[keyword.len()..].starts_with(char::is_whitespace) {
+            self.offset += keyword.len();
+            self.skip_whitespace();
+
+            return true;
+        }
+
+        false
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.offset += 1;
+
+            return true;
+        }
+
+        false
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), TypeError> {
+        self.skip_whitespace();
+
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn unexpected(&self) -> TypeError {
+        match self.rest().chars().next() {
+            Some(character) => TypeError::UnexpectedCharacter(character),
+            None => TypeError::UnexpectedEnd,
+        }
+    }
+
+    /// Skips the whitespace, if the current position is within brackets.
+    fn skip_whitespace(&mut self) {
+        if self.depth > 0 {
+            self.offset += self.rest().len() - self.rest().trim_start().len();
+        }
+    }
+
+    /// Skips the whitespace between the `:` and the return type of a callable signature, which is allowed
+    /// outside of brackets as well, e.g. `callable(int): bool`.
+    fn skip_whitespace_in_signature(&mut self) {
+        self.offset += self.rest().len() - self.rest().trim_start().len();
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.offset).copied()
+    }
+
+    fn rest(&self) -> &'i str {
+        &self.input[self.offset..]
+    }
+}
+
+fn is_identifier_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte == b'\\' || byte >= 0x80
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+fn is_shape_name(name: &str) -> bool {
+    ["array", "non-empty-array", "list", "non-empty-list", "object"]
+        .iter()
+        .any(|shape| shape.eq_ignore_ascii_case(name))
+}
+
+fn is_callable_name(name: &str) -> bool {
+    let name = name.strip_prefix('\\').unwrap_or(name);
+
+    ["callable", "pure-callable", "closure", "pure-closure"].iter().any(|callable| callable.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(interner: &ThreadedInterner, name: &str, arguments: Vec<Type>) -> Type {
+        Type::Reference(ReferenceType { name: interner.intern(name), arguments })
+    }
+
+    #[test]
+    fn test_parse_generic_and_union_types() {
+        let interner = ThreadedInterner::new();
+
+        assert_eq!(
+            parse_type(&interner, "array<int, non-empty-string>|null").unwrap(),
+            Type::Union(vec![
+                reference(
+                    &interner,
+                    "array",
+                    vec![reference(&interner, "int", vec![]), reference(&interner, "non-empty-string", vec![])]
+                ),
+                reference(&interner, "null", vec![]),
+            ])
+        );
+
+        assert_eq!(
+            parse_type(&interner, "?\\App\\Foo[]").unwrap(),
+            Type::Nullable(Box::new(Type::Array(Box::new(reference(&interner, "\\App\\Foo", vec![])))))
+        );
+
+        assert_eq!(
+            parse_type(&interner, "Countable&Traversable").unwrap(),
+            Type::Intersection(vec![
+                reference(&interner, "Countable", vec![]),
+                reference(&interner, "Traversable", vec![])
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_shapes_and_callables() {
+        let interner = ThreadedInterner::new();
+
+        let Type::Shape(shape) = parse_type(&interner, "array{id: int, 'name'?: string, ...}").unwrap() else {
+            panic!("expected a shape");
+        };
+
+        assert!(!shape.is_sealed);
+        assert_eq!(shape.entries.len(), 2);
+        assert_eq!(shape.entries[0].key, Some(ShapeKey::String(interner.intern("id"))));
+        assert!(shape.entries[1].is_optional);
+
+        let Type::Callable(callable) = parse_type(&interner, "callable(int, string ...$rest): bool").unwrap() else {
+            panic!("expected a callable");
+        };
+
+        assert_eq!(callable.parameters.len(), 2);
+        assert!(callable.parameters[1].is_variadic);
+        assert_eq!(callable.return_type, Some(Box::new(reference(&interner, "bool", vec![]))));
+    }
+
+    #[test]
+    fn test_parse_literals_constants_and_conditionals() {
+        let interner = ThreadedInterner::new();
+
+        assert_eq!(
+            parse_type(&interner, "'foo'|-1|1.5|Foo::BAR_*").unwrap(),
+            Type::Union(vec![
+                Type::Literal(LiteralType::String(interner.intern("foo"))),
+                Type::Literal(LiteralType::Integer(-1)),
+                Type::Literal(LiteralType::Float(interner.intern("1.5"))),
+                Type::ClassConstant(ClassConstantType {
+                    class: interner.intern("Foo"),
+                    constant: interner.intern("BAR_*")
+                }),
+            ])
+        );
+
+        let Type::Conditional(conditional) = parse_type(&interner, "($value is not string ? int : float)").unwrap()
+        else {
+            panic!("expected a conditional type");
+        };
+
+        assert!(conditional.is_negated);
+        assert_eq!(*conditional.subject, Type::Variable(interner.intern("$value")));
+    }
+
+    #[test]
+    fn test_parse_leading_type() {
+        let interner = ThreadedInterner::new();
+
+        let (r#type, rest) = parse_leading_type(&interner, "array<int, string> $values The values.").unwrap();
+        assert!(matches!(r#type, Type::Reference(_)));
+        assert_eq!(rest, " $values The values.");
+
+        let (r#type, rest) = parse_leading_type(&interner, "int | string").unwrap();
+        assert!(matches!(r#type, Type::Union(_)));
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn test_invalid_types() {
+        let interner = ThreadedInterner::new();
+
+        assert_eq!(parse_type(&interner, "array<int").unwrap_err(), TypeError::UnexpectedEnd);
+        assert_eq!(parse_type(&interner, "array{int").unwrap_err(), TypeError::UnexpectedEnd);
+        assert_eq!(parse_type(&interner, "Foo::").unwrap_err(), TypeError::UnexpectedEnd);
+        assert_eq!(parse_type(&interner, "int[x]").unwrap_err(), TypeError::UnexpectedCharacter('['));
+        assert_eq!(parse_type(&interner, "'foo").unwrap_err(), TypeError::UnterminatedString);
+    }
+}
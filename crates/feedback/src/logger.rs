@@ -14,12 +14,14 @@ use crate::progress::GLOBAL_PROGRESS_MANAGER;
 ///
 /// * `directive` - A logging directive that controls the log level and filtering rules.
 /// * `env_var` - The environment variable used to override log filtering rules.
-pub fn initialize_logger(directive: impl Into<Directive>, env_var: impl Into<String>) {
+/// * `colors` - Whether log messages are written with ANSI colors.
+pub fn initialize_logger(directive: impl Into<Directive>, env_var: impl Into<String>, colors: bool) {
     fmt()
         .with_env_filter(
             EnvFilter::builder().with_default_directive(directive.into()).with_env_var(env_var.into()).from_env_lossy(),
         )
         .with_writer(LoggerWriter::stderr)
+        .with_ansi(colors)
         .with_target(cfg!(debug_assertions))
         .without_time()
         .compact()
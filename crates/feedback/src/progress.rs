@@ -13,14 +13,15 @@ pub static GLOBAL_PROGRESS_MANAGER: LazyLock<MultiProgress> = LazyLock::new(Mult
 ///
 /// * `length` - The total length of the progress bar, representing the total units of work.
 /// * `theme` - The theme of the progress bar.
+/// * `colors` - Whether the progress bar is drawn with colors, or with the uncolored template.
 ///
 /// # Returns
 ///
 /// A `ProgressBar` that is styled and ready to use.
-pub fn create_progress_bar(length: usize, prefix: &'static str, theme: ProgressBarTheme) -> ProgressBar {
+pub fn create_progress_bar(length: usize, prefix: &'static str, theme: ProgressBarTheme, colors: bool) -> ProgressBar {
     let pb = GLOBAL_PROGRESS_MANAGER.add(ProgressBar::new(length as u64));
     pb.set_style(
-        ProgressStyle::with_template(theme.template())
+        ProgressStyle::with_template(if colors { theme.template() } else { PLAIN_TEMPLATE })
             .unwrap()
             .progress_chars(theme.progress_chars())
             .tick_chars(theme.tick_chars()),
@@ -31,6 +32,10 @@ pub fn create_progress_bar(length: usize, prefix: &'static str, theme: ProgressB
     pb
 }
 
+/// The template used for progress bars when colors are disabled, shared by all themes.
+const PLAIN_TEMPLATE: &str =
+    "{spinner} {prefix:<16}▕{wide_bar}▏{pos:>6}/{len}▕  {percent:>3}%▕  ETA: {eta_precise}▕  Elapsed: {elapsed_precise}";
+
 /// Removes the specified progress bar from the global multi-progress manager.
 ///
 /// # Arguments
@@ -252,6 +252,33 @@ impl FixOperation {
             FixOperation::Delete { safety_classification, .. } => *safety_classification,
         }
     }
+
+    /// Returns the range of the original content affected by this operation.
+    ///
+    /// The range of an insertion is empty, and starts at the offset of the insertion.
+    pub fn get_range(&self) -> Range<usize> {
+        match self {
+            FixOperation::Insert { offset, .. } => *offset..*offset,
+            FixOperation::Replace { range, .. } => range.clone(),
+            FixOperation::Delete { range, .. } => range.clone(),
+        }
+    }
+
+    /// Determines whether this operation overlaps with the given operation.
+    ///
+    /// Two ranges overlap if they share at least one byte, and an insertion overlaps with a range if it
+    /// is strictly inside of it. Two insertions overlap if they are made at the same offset, as the order
+    /// in which their texts should appear is ambiguous.
+    pub fn overlaps(&self, other: &FixOperation) -> bool {
+        let (a, b) = (self.get_range(), other.get_range());
+
+        match (a.is_empty(), b.is_empty()) {
+            (true, true) => a.start == b.start,
+            (true, false) => b.start < a.start && a.start < b.end,
+            (false, true) => a.start < b.start && b.start < a.end,
+            (false, false) => a.start < b.end && b.start < a.end,
+        }
+    }
 }
 
 impl FixPlan {
@@ -387,6 +414,18 @@ impl FixPlan {
             .unwrap_or(SafetyClassification::Safe)
     }
 
+    /// Returns the highest (least safe) safety classification of all the operations in the plan.
+    ///
+    /// This is the classification a user must opt into for every operation of the plan to be applied.
+    ///
+    /// # Returns
+    ///
+    /// The maximum `SafetyClassification` of all operations, or `None` if the plan is empty.
+    #[inline]
+    pub fn get_maximum_safety_classification(&self) -> Option<SafetyClassification> {
+        self.operations.iter().map(|op| op.get_safety_classification()).max()
+    }
+
     #[inline]
     pub fn to_minimum_safety_classification(&self, safety: SafetyClassification) -> Self {
         let min_safety = self.get_minimum_safety_classification();
@@ -399,6 +438,24 @@ impl FixPlan {
         }
     }
 
+    /// Returns the smallest range of the original content containing all the operations in the plan.
+    ///
+    /// Returns `None` if the plan is empty.
+    pub fn get_range(&self) -> Option<Range<usize>> {
+        let start = self.operations.iter().map(|op| op.get_range().start).min()?;
+        let end = self.operations.iter().map(|op| op.get_range().end).max()?;
+
+        Some(start..end)
+    }
+
+    /// Determines whether any operation of this plan overlaps with an operation of the given plan.
+    ///
+    /// Plans that overlap can not be safely applied together, as one of them may modify, or remove, the
+    /// content the other one relies on.
+    pub fn conflicts_with(&self, other: &FixPlan) -> bool {
+        self.operations.iter().any(|op| other.operations.iter().any(|other_op| op.overlaps(other_op)))
+    }
+
     /// Determines whether the plan is empty.
     pub fn is_empty(&self) -> bool {
         self.operations.is_empty()
@@ -591,6 +648,35 @@ impl FromIterator<FixPlan> for FixPlan {
         FixPlan { operations }
     }
 }
+/// Selects the plans that can be applied together to the same content.
+///
+/// The plans are considered in the order of the content they affect, and every plan that conflicts
+/// with a previously selected plan is skipped.
+///
+/// # Arguments
+///
+/// * `plans` - The plans to apply to the same content.
+///
+/// # Returns
+///
+/// The indices of the selected plans, in the order of the content they affect, along with the index of every
+/// skipped plan paired with the index of the selected plan it conflicts with.
+pub fn resolve_conflicts(plans: &[FixPlan]) -> (Vec<usize>, Vec<(usize, usize)>) {
+    let mut order: Vec<usize> = (0..plans.len()).filter(|index| !plans[*index].is_empty()).collect();
+    order.sort_by_key(|index| plans[*index].get_range().map(|range| (range.start, range.end)));
+
+    let mut selected: Vec<usize> = Vec::with_capacity(order.len());
+    let mut skipped = Vec::new();
+    for index in order {
+        match selected.iter().find(|selected| plans[**selected].conflicts_with(&plans[index])) {
+            Some(conflicting) => skipped.push((index, *conflicting)),
+            None => selected.push(index),
+        }
+    }
+
+    (selected, skipped)
+}
+
 fn fix_overlapping_operations(operations: &mut Vec<FixOperation>) {
     let mut filtered_operations = Vec::new();
 
@@ -660,6 +746,63 @@ mod tests {
 
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_maximum_safety_classification() {
+        let mut fix = FixPlan::new();
+        assert_eq!(fix.get_maximum_safety_classification(), None);
+
+        fix.delete(0..1, SafetyClassification::Safe);
+        assert_eq!(fix.get_maximum_safety_classification(), Some(SafetyClassification::Safe));
+
+        fix.insert(2, "a", SafetyClassification::Unsafe);
+        fix.replace(3..4, "b", SafetyClassification::PotentiallyUnsafe);
+        assert_eq!(fix.get_maximum_safety_classification(), Some(SafetyClassification::Unsafe));
+    }
+
+    #[test]
+    fn test_operations_overlap() {
+        let insert = |offset| FixOperation::Insert {
+            offset,
+            text: "a".into(),
+            safety_classification: SafetyClassification::Safe,
+        };
+        let delete = |range| FixOperation::Delete { range, safety_classification: SafetyClassification::Safe };
+
+        assert!(delete(0..4).overlaps(&delete(3..6)));
+        assert!(!delete(0..3).overlaps(&delete(3..6)));
+        assert!(insert(2).overlaps(&delete(0..4)));
+        assert!(!insert(4).overlaps(&delete(0..4)));
+        assert!(!delete(0..4).overlaps(&insert(0)));
+        assert!(insert(1).overlaps(&insert(1)));
+        assert!(!insert(1).overlaps(&insert(2)));
+    }
+
+    #[test]
+    fn test_resolve_conflicts() {
+        let plan = |ranges: &[(usize, usize)]| {
+            let mut plan = FixPlan::new();
+            for (start, end) in ranges {
+                plan.delete(*start..*end, SafetyClassification::Safe);
+            }
+
+            plan
+        };
+
+        // The quotes of a string, the whole string, a separate statement, and the parentheses around the string.
+        let plans = vec![
+            plan(&[(10, 11), (14, 15)]),
+            plan(&[(10, 15)]),
+            plan(&[(20, 25)]),
+            plan(&[(9, 10), (15, 16)]),
+            FixPlan::new(),
+        ];
+
+        let (selected, skipped) = resolve_conflicts(&plans);
+
+        assert_eq!(selected, vec![3, 0, 2]);
+        assert_eq!(skipped, vec![(1, 0)]);
+    }
+
     #[test]
     fn test_operations() {
         let content = "$a = ($b) + ($c);";
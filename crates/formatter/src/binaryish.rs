@@ -1,6 +1,24 @@
+use mago_ast::Binary;
 use mago_ast::BinaryOperator;
+use mago_ast::Expression;
+use mago_span::HasSpan;
+use mago_span::Span;
 use mago_token::GetPrecedence;
 
+/// Returns whether the operand with the given span is parenthesized in the source.
+pub fn is_parenthesized_operand(binary: &Binary, operand: Span) -> bool {
+    [&binary.lhs, &binary.rhs].into_iter().any(|expression| {
+        let mut expression: &Expression = expression;
+        let mut is_parenthesized = false;
+        while let Expression::Parenthesized(parenthesized) = expression {
+            expression = &parenthesized.expression;
+            is_parenthesized = true;
+        }
+
+        is_parenthesized && expression.span() == operand
+    })
+}
+
 pub fn should_flatten<'a>(operator: &'a BinaryOperator, parent_op: &'a BinaryOperator) -> bool {
     let self_precedence = operator.precedence();
     let parent_precedence = parent_op.precedence();
@@ -268,6 +268,11 @@ impl<'a> Formatter<'a> {
         let lines = content.lines().collect::<Vec<_>>();
         let mut contents = vec![];
 
+        // The indentation of the line on which the comment starts, which is replaced by the current indentation.
+        let line_start = self.source_text[..comment.start].rfind('\n').map_or(0, |index| index + 1);
+        let indentation = &self.source_text[line_start..comment.start];
+        let indentation = &indentation[..indentation.len() - indentation.trim_start().len()];
+
         // Process each line according to the specified rules
         let mut processed_lines = Vec::with_capacity(lines.len());
         for (i, line) in lines.iter().enumerate() {
@@ -282,8 +287,8 @@ impl<'a> Formatter<'a> {
                 let rest = line.trim_start();
                 Cow::Owned(format!(" {}", rest))
             } else {
-                // Line does not have '*' after whitespaces, leave it as is
-                Cow::Borrowed(*line)
+                // Line does not have '*' after whitespaces, keep its indentation relative to the comment
+                Cow::Borrowed(line.strip_prefix(indentation).unwrap_or(line))
             };
 
             processed_lines.push(processed_line);
@@ -42,6 +42,10 @@ pub struct Line {
     pub hard: bool,
     pub soft: bool,
     pub literal: bool,
+    /// The line ending to print instead of the configured one, to keep the original line endings of strings.
+    pub ending: Option<&'static str>,
+    /// Whether to keep the whitespace at the end of the line before the line break, as it is part of a string.
+    pub keep_trailing_whitespace: bool,
 }
 
 impl Line {
@@ -68,6 +72,16 @@ impl Line {
     pub fn literal_line_without_break_parent() -> Self {
         Self { hard: true, literal: true, ..Self::default() }
     }
+
+    /// Print the given line ending, instead of the configured one, when breaking.
+    pub fn with_ending(self, ending: Option<&'static str>) -> Self {
+        Self { ending, ..self }
+    }
+
+    /// Keep the whitespace before the line break, instead of trimming it.
+    pub fn keeping_trailing_whitespace(self) -> Self {
+        Self { keep_trailing_whitespace: true, ..self }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, PartialOrd, Ord)]
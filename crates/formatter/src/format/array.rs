@@ -7,6 +7,8 @@ use crate::document::IfBreak;
 use crate::document::Line;
 use crate::format::misc;
 use crate::format::Format;
+use crate::settings::ArraySyntax;
+use crate::settings::CasingStyle;
 use crate::Formatter;
 
 use super::misc::is_string_word_type;
@@ -47,16 +49,27 @@ impl<'a> ArrayLike<'a> {
         }
     }
 
-    #[inline]
-    const fn uses_parenthesis(&self) -> bool {
-        matches!(self, Self::List(_) | Self::LegacyArray(_))
+    /// Returns whether the array-like is printed with the `array(...)` or `list(...)` syntax.
+    fn uses_parenthesis(&self, f: &Formatter<'a>) -> bool {
+        match self {
+            Self::List(_) => true,
+            Self::LegacyArray(_) => f.settings.array_syntax != ArraySyntax::Short,
+            Self::Array(array) => f.settings.array_syntax == ArraySyntax::Long && !is_destructuring(f, array.span()),
+        }
     }
 
     fn prefix(&self, f: &mut Formatter<'a>) -> Option<Document<'a>> {
+        if !self.uses_parenthesis(f) {
+            return None;
+        }
+
         match self {
             Self::List(list) => Some(list.list.format(f)),
             Self::LegacyArray(array) => Some(array.array.format(f)),
-            _ => None,
+            Self::Array(_) => Some(Document::String(match f.settings.keyword_case {
+                CasingStyle::Lowercase => "array",
+                CasingStyle::Uppercase => "ARRAY",
+            })),
         }
     }
 
@@ -80,13 +93,14 @@ impl HasSpan for ArrayLike<'_> {
 }
 
 pub(super) fn print_array_like<'a>(f: &mut Formatter<'a>, array_like: ArrayLike<'a>) -> Document<'a> {
+    let uses_parenthesis = array_like.uses_parenthesis(f);
     let left_delimiter = if let Some(prefix) = array_like.prefix(f) {
-        Document::Array(vec![prefix, Document::String(if array_like.uses_parenthesis() { "(" } else { "[" })])
+        Document::Array(vec![prefix, Document::String("(")])
     } else {
-        Document::String(if array_like.uses_parenthesis() { "(" } else { "[" })
+        Document::String(if uses_parenthesis { "(" } else { "[" })
     };
 
-    let right_delimiter = Document::String(if array_like.uses_parenthesis() { ")" } else { "]" });
+    let right_delimiter = Document::String(if uses_parenthesis { ")" } else { "]" });
 
     if array_like.is_empty() {
         return Document::Group(Group::new(vec![
@@ -126,7 +140,7 @@ pub(super) fn print_array_like<'a>(f: &mut Formatter<'a>, array_like: ArrayLike<
         indent_parts
     }));
 
-    if f.settings.trailing_comma {
+    if f.settings.trailing_comma.for_arrays() {
         parts.push(Document::IfBreak(IfBreak::then(Document::String(","))));
     }
 
@@ -148,6 +162,30 @@ pub(super) fn print_array_like<'a>(f: &mut Formatter<'a>, array_like: ArrayLike<
     Document::Group(Group::new(parts).with_break(should_break))
 }
 
+/// Returns whether the array with the given span is the target of a destructuring assignment, or is
+/// nested in one, in which case it can not be written with the `array(...)` syntax.
+fn is_destructuring(f: &Formatter<'_>, span: Span) -> bool {
+    let mut n = 1;
+    loop {
+        match f.nth_parent_kind(n) {
+            Some(
+                Node::Expression(_)
+                | Node::Array(_)
+                | Node::ArrayElement(_)
+                | Node::KeyValueArrayElement(_)
+                | Node::ValueArrayElement(_),
+            ) => n += 1,
+            Some(Node::AssignmentOperation(assignment)) => {
+                let target = assignment.lhs.span();
+
+                return target.start.offset <= span.start.offset && span.end.offset <= target.end.offset;
+            }
+            Some(Node::ForeachTarget(_) | Node::ForeachValueTarget(_) | Node::ForeachKeyValueTarget(_)) => return true,
+            _ => return false,
+        }
+    }
+}
+
 fn inline_single_element<'a>(f: &mut Formatter<'a>, array_like: &ArrayLike<'a>) -> Option<Document<'a>> {
     if array_like.len() != 1 {
         return None;
@@ -7,6 +7,7 @@ use crate::document::Group;
 use crate::document::IndentIfBreak;
 use crate::document::Line;
 use crate::format::binaryish::should_inline_logical_or_coalesce_expression;
+use crate::format::call::is_breaking_method_call_chain;
 use crate::format::Format;
 use crate::Formatter;
 
@@ -55,6 +56,26 @@ enum Layout {
     Fluid,
 }
 
+/// Prints the operator of an assignment, such as `=`, or `??=`.
+pub(super) fn print_assignment_operator<'a>(operator: &AssignmentOperator) -> Document<'a> {
+    Document::String(match operator {
+        AssignmentOperator::Assign(_) => "=",
+        AssignmentOperator::Addition(_) => "+=",
+        AssignmentOperator::Subtraction(_) => "-=",
+        AssignmentOperator::Multiplication(_) => "*=",
+        AssignmentOperator::Division(_) => "/=",
+        AssignmentOperator::Modulo(_) => "%=",
+        AssignmentOperator::Exponentiation(_) => "**=",
+        AssignmentOperator::Concat(_) => ".=",
+        AssignmentOperator::BitwiseAnd(_) => "&=",
+        AssignmentOperator::BitwiseOr(_) => "|=",
+        AssignmentOperator::BitwiseXor(_) => "^=",
+        AssignmentOperator::LeftShift(_) => "<<=",
+        AssignmentOperator::RightShift(_) => ">>=",
+        AssignmentOperator::Coalesce(_) => "??=",
+    })
+}
+
 pub(super) fn print_assignment<'a>(
     f: &mut Formatter<'a>,
     assignment_node: AssignmentLikeNode<'a>,
@@ -269,10 +290,8 @@ fn should_break_after_operator<'a>(f: &Formatter<'a>, rhs_expression: &'a Expres
             return conditional.condition.is_binary()
                 && !should_inline_logical_or_coalesce_expression(&conditional.condition);
         }
-        Expression::AnonymousClass(anonymous_class) => {
-            if !anonymous_class.attributes.is_empty() {
-                return true;
-            }
+        Expression::AnonymousClass(anonymous_class) if !anonymous_class.attributes.is_empty() => {
+            return true;
         }
         _ => {}
     }
@@ -355,7 +374,12 @@ fn is_poorly_breakable_member_or_call_chain<'a>(f: &Formatter<'a>, rhs_expressio
         }
     }
 
-    if !is_chain_expression || !is_identifier_or_variable || call_argument_lists.is_empty() {
+    // Chains that are always broken start on the same line as the operator.
+    if !is_chain_expression
+        || !is_identifier_or_variable
+        || call_argument_lists.is_empty()
+        || is_breaking_method_call_chain(f, rhs_expression)
+    {
         return false;
     }
 
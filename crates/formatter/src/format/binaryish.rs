@@ -1,14 +1,28 @@
 use mago_ast::*;
 use mago_span::HasSpan;
 
+use crate::binaryish::is_parenthesized_operand;
+use crate::binaryish::should_flatten;
 use crate::document::Document;
 use crate::document::Group;
 use crate::document::IfBreak;
 use crate::document::IndentIfBreak;
 use crate::document::Line;
 use crate::format::Format;
+use crate::settings::OperatorPosition;
 use crate::Formatter;
 
+/// Prints the operator of a binary operation, such as `+`, or `instanceof`.
+pub(super) fn print_binary_operator<'a>(f: &mut Formatter<'a>, operator: &'a BinaryOperator) -> Document<'a> {
+    match operator {
+        BinaryOperator::Instanceof(keyword) => keyword.format(f),
+        BinaryOperator::LowAnd(keyword) => keyword.format(f),
+        BinaryOperator::LowOr(keyword) => keyword.format(f),
+        BinaryOperator::LowXor(keyword) => keyword.format(f),
+        _ => Document::String(operator.as_str(f.interner)),
+    }
+}
+
 pub(super) fn print_binaryish_expression<'a>(
     f: &mut Formatter<'a>,
     left: &'a Expression,
@@ -27,14 +41,60 @@ pub(super) fn print_binaryish_expression<'a>(
         _ => false,
     };
 
-    let lhs = left.format(f);
-    let operator = match operator {
-        BinaryOperator::Instanceof(keyword) => keyword.format(f),
-        BinaryOperator::LowAnd(keyword) => keyword.format(f),
-        BinaryOperator::LowOr(keyword) => keyword.format(f),
-        BinaryOperator::LowXor(keyword) => keyword.format(f),
-        _ => Document::String(operator.as_str(f.interner)),
+    // An operation that is an operand of an operation of the same precedence is printed as part of its
+    // parent's group, so that the whole chain breaks at every operator, or not at all.
+    let is_flattened = match parent_node {
+        Some(Node::Binary(o)) => {
+            should_flatten(operator, &o.operator) && !is_parenthesized_operand(o, f.current_node().span())
+        }
+        _ => false,
     };
+
+    // The condition of a control structure is already indented inside of its parentheses, and so are
+    // the operations flattened into it.
+    let mut root = 0;
+    while let (Some(Node::Binary(node)), Some(Node::Binary(parent))) =
+        (f.nth_parent_kind(root), f.nth_parent_kind(root + 2))
+    {
+        if !should_flatten(&node.operator, &parent.operator) || is_parenthesized_operand(parent, node.span()) {
+            break;
+        }
+
+        root += 2;
+    }
+
+    let is_inside_parentheses = matches!(
+        f.nth_parent_kind(root + 2),
+        Some(
+            Node::If(_)
+                | Node::IfStatementBodyElseIfClause(_)
+                | Node::IfColonDelimitedBodyElseIfClause(_)
+                | Node::DoWhile(_)
+                | Node::While(_)
+                | Node::Match(_)
+        )
+    );
+
+    // An assigned operation is broken after the assignment operator, and indented there already.
+    let is_assigned = matches!(
+        f.nth_parent_kind(root + 2),
+        Some(
+            Node::AssignmentOperation(_)
+                | Node::PropertyConcreteItem(_)
+                | Node::ClassLikeConstantItem(_)
+                | Node::ConstantItem(_)
+                | Node::EnumCaseBackedItem(_)
+        )
+    ) && match f.nth_parent_kind(root + 1) {
+        Some(Node::Expression(expression)) => !should_inline_logical_or_coalesce_expression(expression),
+        _ => false,
+    };
+
+    // The body of an arrow function is indented after the `=>` already.
+    let is_arrow_function_body = matches!(f.nth_parent_kind(root + 2), Some(Node::ArrowFunction(_)));
+
+    let lhs = left.format(f);
+    let operator = print_binary_operator(f, operator);
     let rhs = right.format(f);
 
     let must_break = f.settings.preserve_multiline_binary_operations
@@ -46,42 +106,35 @@ pub(super) fn print_binaryish_expression<'a>(
         Document::empty()
     };
 
+    let line = if must_break {
+        Document::Line(Line::hardline())
+    } else {
+        Document::IfBreak(IfBreak::new(Document::Line(Line::hardline()), spaces.clone()))
+    };
+
+    // With leading operators, the line break goes before the operator, and the operator is followed by
+    // the spaces instead.
+    let (mut parts, continuation) = match f.settings.operator_position {
+        OperatorPosition::Trailing => (vec![lhs, spaces, operator], vec![line, rhs]),
+        OperatorPosition::Leading => (vec![lhs], vec![line, operator, spaces, rhs]),
+    };
+
+    if is_rhs_of_binaryish || is_inside_parentheses || is_assigned || is_arrow_function_body {
+        parts.extend(continuation);
+    } else if must_break {
+        parts.push(Document::Indent(continuation));
+    } else {
+        parts.push(Document::IndentIfBreak(IndentIfBreak::new(continuation)));
+    }
+
     if must_break {
-        if is_rhs_of_binaryish {
-            Document::Group(Group::new(vec![
-                lhs,
-                spaces,
-                operator,
-                Document::Line(Line::hardline()),
-                rhs,
-                Document::BreakParent,
-            ]))
-        } else {
-            Document::Group(Group::new(vec![
-                lhs,
-                spaces,
-                operator,
-                Document::Indent(vec![Document::Line(Line::hardline()), rhs]),
-            ]))
-        }
-    } else if is_rhs_of_binaryish {
-        Document::Group(Group::new(vec![
-            lhs,
-            spaces.clone(),
-            operator,
-            Document::IfBreak(IfBreak::new(Document::Line(Line::hardline()), spaces)),
-            rhs,
-        ]))
+        parts.push(Document::BreakParent);
+    }
+
+    if is_flattened || is_inside_parentheses {
+        Document::Array(parts)
     } else {
-        Document::Group(Group::new(vec![
-            lhs,
-            spaces.clone(),
-            operator,
-            Document::IndentIfBreak(IndentIfBreak::new(vec![
-                Document::IfBreak(IfBreak::new(Document::Line(Line::hardline()), spaces)),
-                rhs,
-            ])),
-        ]))
+        Document::Group(Group::new(parts))
     }
 }
 
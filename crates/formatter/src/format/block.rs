@@ -9,12 +9,21 @@ use crate::format::statement;
 use crate::format::Format;
 use crate::Formatter;
 
+/// Returns the number of blank lines to keep after the given node, as in the source, up to the
+/// maximum number of consecutive blank lines allowed inside of bodies.
+pub(super) fn kept_blank_lines<'a, T: HasSpan>(f: &Formatter<'a>, node: &'a T, _next: &'a T) -> usize {
+    f.blank_lines_after(node.span()).min(f.settings.max_consecutive_blank_lines.unwrap_or(1))
+}
+
+/// Prints the given nodes in a block, separated by the number of blank lines returned by `blank_lines`
+/// for every pair of consecutive nodes.
 pub(super) fn print_block_of_nodes<'a, T: Format<'a> + HasSpan>(
     f: &mut Formatter<'a>,
     left_brace: &Span,
     nodes: &'a Sequence<T>,
     right_brace: &Span,
     inline_empty: bool,
+    blank_lines: fn(&Formatter<'a>, &'a T, &'a T) -> usize,
 ) -> Document<'a> {
     let length = nodes.len();
     let mut contents = vec![Document::String("{"), {
@@ -22,15 +31,31 @@ pub(super) fn print_block_of_nodes<'a, T: Format<'a> + HasSpan>(
             Document::empty()
         } else {
             let mut formatted = vec![Document::Line(Line::hardline())];
-            for (i, item) in nodes.iter().enumerate() {
-                formatted.push(item.format(f));
+            let nodes = nodes.as_slice();
+            let mut i = 0;
+            while i < length {
+                // A region left unformatted by `@mago-fmt-ignore-start` is printed as a single node.
+                let last = match f.ignored_region(nodes, i) {
+                    Some(last) => {
+                        formatted.push(f.print_verbatim_region(nodes[i].span(), nodes[last].span()));
+
+                        last
+                    }
+                    None => {
+                        formatted.push(nodes[i].format(f));
 
-                if i < (length - 1) {
+                        i
+                    }
+                };
+
+                if last < (length - 1) {
                     formatted.push(Document::Line(Line::hardline()));
-                    if f.is_next_line_empty(item.span()) {
+                    for _ in 0..blank_lines(f, &nodes[last], &nodes[last + 1]) {
                         formatted.push(Document::Line(Line::hardline()));
                     }
                 }
+
+                i = last + 1;
             }
 
             Document::Indent(formatted)
@@ -45,9 +45,23 @@ pub(super) fn collect_method_call_chain(expr: &Expression) -> Option<MethodChain
     }
 }
 
+/// Returns whether the given expression is a method call chain that is always broken into multiple lines,
+/// as it has more calls than the `method_chain_breaking_threshold` setting allows.
+pub(super) fn is_breaking_method_call_chain(f: &Formatter<'_>, expression: &Expression) -> bool {
+    collect_method_call_chain(expression)
+        .is_some_and(|method_chain| method_chain.calls.len() > f.settings.method_chain_breaking_threshold)
+}
+
+/// Prints a method call chain, with one call per line when the chain is broken.
+///
+/// Chains with more calls than the `method_chain_breaking_threshold` setting allows are always broken,
+/// while shorter chains are only broken when they do not fit within the print width.
 pub(super) fn print_method_call_chain<'a>(method_chain: &MethodChain<'a>, f: &mut Formatter<'a>) -> Document<'a> {
+    let should_break = method_chain.calls.len() > f.settings.method_chain_breaking_threshold;
+    let line = if should_break { Line::hardline() } else { Line::softline() };
+
     let base_document = method_chain.base.format(f);
-    let mut parts = if base_needs_parerns(method_chain.base) {
+    let mut parts = if base_needs_parerns(f, method_chain.base) {
         vec![Document::String("("), base_document, Document::String(")")]
     } else {
         vec![base_document]
@@ -74,7 +88,7 @@ pub(super) fn print_method_call_chain<'a>(method_chain: &MethodChain<'a>, f: &mu
 
     // Now handle the remaining method calls
     for chain_link in calls_iter {
-        let mut contents = vec![Document::Line(Line::hardline())];
+        let mut contents = vec![Document::Line(line)];
         contents.extend(match chain_link {
             CallLikeNode::Call(Call::Method(c)) => vec![Document::String("->"), c.method.format(f)],
             CallLikeNode::Call(Call::NullSafeMethod(c)) => vec![Document::String("?->"), c.method.format(f)],
@@ -86,41 +100,36 @@ pub(super) fn print_method_call_chain<'a>(method_chain: &MethodChain<'a>, f: &mu
         parts.push(Document::Indent(contents));
     }
 
-    parts.push(Document::BreakParent);
+    if should_break {
+        parts.push(Document::BreakParent);
+    }
 
     // Wrap everything in a group to manage line breaking
     Document::Group(Group::new(parts))
 }
 
-fn base_needs_parerns(base: &Expression) -> bool {
+fn base_needs_parerns(f: &Formatter<'_>, base: &Expression) -> bool {
     if let Expression::Parenthesized(parenthesized) = base {
-        return base_needs_parerns(&parenthesized.expression);
+        // `new` expressions are kept parenthesized if they are written so, see `parenthesize_new_in_chain`.
+        if let Expression::Instantiation(_) | Expression::AnonymousClass(_) = parenthesized.expression.as_ref() {
+            return true;
+        }
+
+        return base_needs_parerns(f, &parenthesized.expression);
     }
 
     match base {
         Expression::Instantiation(instantiation) => {
-            if instantiation.arguments.is_none() {
-                // parentheses are required if the instantiation has no arguments
-                // e.g. `new Foo->baz()` should be `(new Foo)->baz()`
-                true
-            } else {
-                // parentheses are not required if the instantiation has arguments
-                // e.g. `new Foo()->baz()`.
-                //
-                // but this is only allowed in PHP 8.4, so for now, we add
-                // parentheses to be safe, in the future, we can add an option
-                // to remove them.
-                //
-                // TODO(azjezz): we should add an option to remove parentheses.
-                true
-            }
+            // parentheses are required if the instantiation has no arguments, e.g. `new Foo->baz()`
+            // should be `(new Foo)->baz()`, while `new Foo()->baz()` is only allowed in PHP 8.4.
+            instantiation.arguments.is_none() || f.settings.parenthesize_new_in_chain
         }
+        Expression::AnonymousClass(_) => f.settings.parenthesize_new_in_chain,
         Expression::Binary(_)
         | Expression::UnaryPrefix(_)
         | Expression::UnaryPostfix(_)
         | Expression::AssignmentOperation(_)
         | Expression::Conditional(_)
-        | Expression::AnonymousClass(_)
         | Expression::Closure(_)
         | Expression::ArrowFunction(_)
         | Expression::Match(_)
@@ -4,6 +4,7 @@ use mago_span::*;
 use crate::comment::CommentFlags;
 use crate::document::*;
 use crate::format::call_node::CallLikeNode;
+use crate::format::misc::should_hug_arrow_function_body;
 use crate::format::misc::should_hug_expression;
 use crate::utils::will_break;
 use crate::Format;
@@ -75,7 +76,7 @@ pub(super) fn print_argument_list<'a>(f: &mut Formatter<'a>, argument_list: &'a
         parts.push(Document::Indent(vec![
             Document::Line(Line::default()),
             Document::Array(get_printed_arguments(f, 0)),
-            if f.settings.trailing_comma { Document::String(",") } else { Document::empty() },
+            if f.settings.trailing_comma.for_arguments() { Document::String(",") } else { Document::empty() },
         ]));
 
         parts.push(Document::Line(Line::default()));
@@ -183,7 +184,7 @@ pub(super) fn print_argument_list<'a>(f: &mut Formatter<'a>, argument_list: &'a
 
     printed_arguments.insert(0, Document::Line(Line::softline()));
     contents.push(Document::Indent(printed_arguments));
-    if f.settings.trailing_comma {
+    if f.settings.trailing_comma.for_arguments() {
         contents.push(Document::IfBreak(IfBreak::then(Document::String(","))));
     }
     contents.push(Document::Line(Line::softline()));
@@ -209,7 +210,7 @@ fn should_inline_single_breaking_argument<'a>(f: &Formatter<'a>, argument_list:
 
 /// * Reference <https://github.com/prettier/prettier/blob/3.3.3/src/language-js/print/call-arguments.js#L247-L272>
 fn should_expand_first_arg<'a>(f: &Formatter<'a>, argument_list: &'a ArgumentList) -> bool {
-    if argument_list.arguments.len() != 2 {
+    if argument_list.arguments.len() != 2 || has_named_arguments(argument_list) {
         return false;
     }
 
@@ -239,11 +240,18 @@ fn should_expand_first_arg<'a>(f: &Formatter<'a>, argument_list: &'a ArgumentLis
 /// * Reference <https://github.com/prettier/prettier/blob/52829385bcc4d785e58ae2602c0b098a643523c9/src/language-js/print/call-arguments.js#L234-L258>
 fn should_expand_last_arg<'a>(f: &Formatter<'a>, argument_list: &'a ArgumentList) -> bool {
     let Some(last_argument) = argument_list.arguments.last() else { return false };
-    if f.has_comment(last_argument.span(), CommentFlags::Leading | CommentFlags::Trailing) {
+    if has_named_arguments(argument_list)
+        || f.has_comment(last_argument.span(), CommentFlags::Leading | CommentFlags::Trailing)
+    {
         return false;
     }
 
     let last_argument_value = last_argument.value();
+    if f.settings.prefer_arrow_fn_single_expression
+        && matches!(last_argument_value, Expression::ArrowFunction(arrow_function) if !should_hug_arrow_function_body(&arrow_function.expression))
+    {
+        return false;
+    }
 
     let penultimate_argument = if argument_list.arguments.len() >= 2 {
         argument_list.arguments.get(argument_list.arguments.len() - 2)
@@ -271,6 +279,13 @@ fn should_expand_last_arg<'a>(f: &Formatter<'a>, argument_list: &'a ArgumentList
             || !matches!(last_argument_value, Expression::Closure(c) if c.use_clause.is_none()))
 }
 
+/// Returns whether the argument list has several arguments, some of which are named, in which case
+/// every argument is printed on its own line when the list breaks, instead of hugging the first or last one.
+fn has_named_arguments(argument_list: &ArgumentList) -> bool {
+    argument_list.arguments.len() > 1
+        && argument_list.arguments.iter().any(|argument| matches!(argument, Argument::Named(_)))
+}
+
 fn is_hopefully_short_call_argument(mut node: &Expression) -> bool {
     loop {
         node = match node {
@@ -384,17 +399,13 @@ fn is_simple_call_argument<'a>(node: &'a Expression, depth: usize) -> bool {
         Expression::ArrayAccess(array_access) => {
             is_simple_call_argument(&array_access.array, depth) && is_simple_call_argument(&array_access.index, depth)
         }
-        Expression::Instantiation(instantiation) => {
-            if is_simple_call_argument(&instantiation.class, depth) {
-                match &instantiation.arguments {
-                    Some(argument_list) => {
-                        argument_list.arguments.len() <= depth
-                            && argument_list.arguments.iter().map(|a| a.value()).all(is_child_simple)
-                    }
-                    None => true,
+        Expression::Instantiation(instantiation) if is_simple_call_argument(&instantiation.class, depth) => {
+            match &instantiation.arguments {
+                Some(argument_list) => {
+                    argument_list.arguments.len() <= depth
+                        && argument_list.arguments.iter().map(|a| a.value()).all(is_child_simple)
                 }
-            } else {
-                false
+                None => true,
             }
         }
         _ => false,
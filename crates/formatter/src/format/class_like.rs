@@ -1,4 +1,5 @@
 use mago_ast::*;
+use mago_span::HasSpan;
 use mago_span::Span;
 
 use crate::document::Document;
@@ -18,5 +19,35 @@ pub fn print_class_like_body<'a>(
         BraceStyle::NextLine => false,
     };
 
-    print_block_of_nodes(f, left_brace, class_like_members, right_brace, inline_empty)
+    print_block_of_nodes(f, left_brace, class_like_members, right_brace, inline_empty, blank_lines_between_members)
+}
+
+/// Returns the number of blank lines to print between the given class-like members.
+///
+/// Consecutive properties, constants, enum cases, or trait uses that each fit on a single line are kept
+/// together when the source has no blank line, nor comment, between them.
+fn blank_lines_between_members<'a>(f: &Formatter<'a>, member: &'a ClassLikeMember, next: &'a ClassLikeMember) -> usize {
+    let is_groupable = matches!(
+        (member, next),
+        (ClassLikeMember::Property(_), ClassLikeMember::Property(_))
+            | (ClassLikeMember::Constant(_), ClassLikeMember::Constant(_))
+            | (ClassLikeMember::EnumCase(_), ClassLikeMember::EnumCase(_))
+            | (ClassLikeMember::TraitUse(_), ClassLikeMember::TraitUse(_))
+    );
+
+    if is_groupable {
+        let (span, next_span) = (member.span(), next.span());
+        let is_single_line = |span: Span| !f.source_slice(span).contains('\n');
+        let between = f.source_slice(Span::new(span.end, next_span.start));
+
+        if is_single_line(span)
+            && is_single_line(next_span)
+            && between.trim().is_empty()
+            && between.matches('\n').count() == 1
+        {
+            return 0;
+        }
+    }
+
+    f.settings.blank_lines_between_class_members
 }
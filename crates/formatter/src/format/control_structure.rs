@@ -4,6 +4,7 @@ use mago_span::HasSpan;
 use crate::document::Document;
 use crate::document::Group;
 use crate::document::Line;
+use crate::format::block::kept_blank_lines;
 use crate::format::block::print_block_of_nodes;
 use crate::format::misc;
 use crate::format::misc::print_colon_delimited_body;
@@ -292,7 +293,7 @@ impl<'a> Format<'a> for SwitchColonDelimitedBody {
 impl<'a> Format<'a> for SwitchBraceDelimitedBody {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, SwitchBraceDelimitedBody, {
-            print_block_of_nodes(f, &self.left_brace, &self.cases, &self.right_brace, false)
+            print_block_of_nodes(f, &self.left_brace, &self.cases, &self.right_brace, false, kept_blank_lines)
         })
     }
 }
@@ -359,9 +360,7 @@ impl<'a> Format<'a> for While {
             Document::Array(vec![
                 self.r#while.format(f),
                 Document::space(),
-                Document::String("("),
-                self.condition.format(f),
-                Document::String(")"),
+                misc::print_condition(f, &self.condition),
                 self.body.format(f),
             ])
         })
@@ -0,0 +1,97 @@
+use mago_ast::*;
+use mago_span::HasSpan;
+
+use crate::comment::CommentFlags;
+use crate::document::*;
+use crate::format::Format;
+use crate::settings::CasingStyle;
+use crate::settings::EchoTag;
+use crate::Formatter;
+
+/// Returns the index of the last statement of the block echoing a single expression that starts with
+/// the opening tag at the given index, if the block is to be printed with the configured `echo_tag`.
+///
+/// Such a block is either `<?php echo $foo; ?>`, or `<?= $foo ?>`, with or without a semicolon.
+pub(super) fn echo_block_end(f: &Formatter<'_>, stmts: &[Statement], index: usize) -> Option<usize> {
+    if f.settings.echo_tag == EchoTag::Preserve {
+        return None;
+    }
+
+    let terminator = match (&stmts[index], stmts.get(index + 1)?) {
+        (
+            Statement::OpeningTag(OpeningTag::Full(_) | OpeningTag::Short(_)),
+            Statement::Echo(Echo { values, terminator, .. }),
+        ) if values.len() == 1 => terminator,
+        (Statement::OpeningTag(OpeningTag::Echo(_)), Statement::Expression(statement)) => &statement.terminator,
+        _ => return None,
+    };
+
+    let end = match terminator {
+        Terminator::ClosingTag(_) => index + 1,
+        Terminator::Semicolon(_) if matches!(stmts.get(index + 2), Some(Statement::ClosingTag(_))) => index + 2,
+        _ => return None,
+    };
+
+    let span = stmts[index].span().join(stmts[end].span());
+    if f.has_comment(span, CommentFlags::Dangling) || stmts[index..=end].iter().any(|stmt| f.is_ignored(stmt.span())) {
+        return None;
+    }
+
+    Some(end)
+}
+
+/// Prints the given block echoing a single expression, with the configured `echo_tag`.
+pub(super) fn print_echo_block<'a>(f: &mut Formatter<'a>, stmts: &'a [Statement]) -> Document<'a> {
+    f.scripting_mode = true;
+
+    let statement = &stmts[1];
+    f.enter_node(Node::Statement(statement));
+    let (expression, terminator) = match statement {
+        Statement::Echo(echo) => {
+            f.enter_node(Node::Echo(echo));
+
+            (&echo.values.as_slice()[0], &echo.terminator)
+        }
+        Statement::Expression(statement) => {
+            f.enter_node(Node::ExpressionStatement(statement));
+
+            (&statement.expression, &statement.terminator)
+        }
+        _ => unreachable!("only blocks echoing a single expression are converted"),
+    };
+
+    let value = expression.format(f);
+    let closing_tag = match terminator {
+        Terminator::ClosingTag(tag) => tag.format(f),
+        _ => Document::empty(),
+    };
+
+    f.leave_node();
+    f.leave_node();
+
+    let closing_tag = match stmts.get(2) {
+        Some(statement) => statement.format(f),
+        None => closing_tag,
+    };
+
+    let mut parts = match f.settings.echo_tag {
+        EchoTag::Long => vec![
+            Document::String(match f.settings.keyword_case {
+                CasingStyle::Lowercase => "<?php echo",
+                CasingStyle::Uppercase => "<?PHP ECHO",
+            }),
+            Document::space(),
+            value,
+            Document::String(";"),
+        ],
+        _ => vec![Document::String("<?="), Document::space(), value],
+    };
+
+    // The closing tag is omitted at the end of the file.
+    if !matches!(closing_tag, Document::String("")) {
+        parts.push(Document::space());
+        parts.push(closing_tag);
+    }
+
+    Document::Group(Group::new(parts))
+}
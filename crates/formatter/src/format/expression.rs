@@ -6,6 +6,7 @@ use crate::document::Line;
 use crate::format::array::print_array_like;
 use crate::format::array::ArrayLike;
 use crate::format::assignment::print_assignment;
+use crate::format::assignment::print_assignment_operator;
 use crate::format::assignment::AssignmentLikeNode;
 use crate::format::binaryish;
 use crate::format::call::collect_method_call_chain;
@@ -18,6 +19,7 @@ use crate::format::misc;
 use crate::format::misc::print_attribute_list_sequence;
 use crate::format::misc::print_condition;
 use crate::format::misc::print_modifiers;
+use crate::format::misc::should_hug_arrow_function_body;
 use crate::format::string::print_string;
 use crate::format::Group;
 use crate::format::IfBreak;
@@ -59,20 +61,10 @@ impl<'a> Format<'a> for Expression {
                 Expression::Construct(construct) => construct.format(f),
                 Expression::Throw(t) => t.format(f),
                 Expression::Clone(c) => c.format(f),
-                Expression::Call(c) => {
-                    if let Some(method_chain) = collect_method_call_chain(self) {
-                        let chain_length = method_chain.calls.len();
-                        if chain_length >= f.settings.method_chain_break_threshold {
-                            // Chain is longer than threshold; format with line breaks
-                            print_method_call_chain(&method_chain, f)
-                        } else {
-                            // Regular formatting
-                            c.format(f)
-                        }
-                    } else {
-                        c.format(f)
-                    }
-                }
+                Expression::Call(c) => match collect_method_call_chain(self) {
+                    Some(method_chain) if method_chain.calls.len() > 1 => print_method_call_chain(&method_chain, f),
+                    _ => c.format(f),
+                },
                 Expression::Access(a) => a.format(f),
                 Expression::ConstantAccess(a) => a.format(f),
                 Expression::ClosureCreation(c) => c.format(f),
@@ -301,7 +293,7 @@ impl<'a> Format<'a> for IssetConstruct {
                 let mut values =
                     Document::join(self.values.iter().map(|v| v.format(f)).collect(), Separator::CommaLine);
 
-                if f.settings.trailing_comma {
+                if f.settings.trailing_comma.for_arguments() {
                     values.push(Document::IfBreak(IfBreak::then(Document::String(","))));
                 }
 
@@ -465,22 +457,7 @@ impl<'a> Format<'a> for Assignment {
         wrap!(f, self, AssignmentOperation, {
             let lhs = self.lhs.format(f);
 
-            let operator = match self.operator {
-                AssignmentOperator::Assign(_) => Document::String("="),
-                AssignmentOperator::Addition(_) => Document::String("+="),
-                AssignmentOperator::Subtraction(_) => Document::String("-="),
-                AssignmentOperator::Multiplication(_) => Document::String("*="),
-                AssignmentOperator::Division(_) => Document::String("/="),
-                AssignmentOperator::Modulo(_) => Document::String("%="),
-                AssignmentOperator::Exponentiation(_) => Document::String("**="),
-                AssignmentOperator::Concat(_) => Document::String(".="),
-                AssignmentOperator::BitwiseAnd(_) => Document::String("&="),
-                AssignmentOperator::BitwiseOr(_) => Document::String("|="),
-                AssignmentOperator::BitwiseXor(_) => Document::String("^="),
-                AssignmentOperator::LeftShift(_) => Document::String("<<="),
-                AssignmentOperator::RightShift(_) => Document::String(">>="),
-                AssignmentOperator::Coalesce(_) => Document::String("??="),
-            };
+            let operator = print_assignment_operator(&self.operator);
 
             print_assignment(f, AssignmentLikeNode::AssignmentOperation(self), lhs, operator, &self.rhs)
         })
@@ -516,7 +493,7 @@ impl<'a> Format<'a> for ClosureUseClause {
 
             let mut inner_conent = Document::join(variables, Separator::CommaLine);
             inner_conent.insert(0, Document::Line(Line::softline()));
-            if f.settings.trailing_comma {
+            if f.settings.trailing_comma.for_arguments() {
                 inner_conent.push(Document::IfBreak(IfBreak::then(Document::String(","))));
             }
 
@@ -530,7 +507,9 @@ impl<'a> Format<'a> for ClosureUseClause {
 
             contents.push(Document::String(")"));
 
-            Document::Group(Group::new(contents))
+            // The variables are part of the signature group of the closure, so that they break along with
+            // its parameters.
+            Document::Array(contents)
         })
     }
 }
@@ -538,12 +517,7 @@ impl<'a> Format<'a> for ClosureUseClause {
 impl<'a> Format<'a> for Closure {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, Closure, {
-            let mut attributes = vec![];
-            for attribute_list in self.attributes.iter() {
-                attributes.push(attribute_list.format(f));
-                attributes.push(Document::Line(Line::hardline()));
-                attributes.push(Document::BreakParent);
-            }
+            let attributes = print_attribute_list_sequence(f, &self.attributes, true);
 
             let mut signature = vec![];
             if let Some(s) = &self.r#static {
@@ -574,7 +548,7 @@ impl<'a> Format<'a> for Closure {
             let signature_document = Document::Group(Group::new(signature).with_id(signature_id));
 
             Document::Group(Group::new(vec![
-                Document::Group(Group::new(attributes)),
+                attributes.unwrap_or_else(Document::empty),
                 signature_document,
                 Document::Group(Group::new(vec![
                     match f.settings.closure_brace_style {
@@ -615,8 +589,27 @@ impl<'a> Format<'a> for ArrowFunction {
                 contents.push(h.format(f));
             }
 
-            contents.push(Document::String(" => "));
-            contents.push(self.expression.format(f));
+            contents.push(Document::String(" =>"));
+            if should_hug_arrow_function_body(&self.expression) {
+                contents.push(Document::space());
+                contents.push(self.expression.format(f));
+            } else {
+                // The body of an arrow function that is hugged as the last argument of a call is followed by
+                // the closing parenthesis of the call on its own line once it breaks.
+                let is_last_argument = f.argument_state.expand_last_argument
+                    && matches!(f.grandparent_node(), Some(Node::PositionalArgument(_) | Node::NamedArgument(_)));
+
+                let mut body = vec![Document::Indent(vec![Document::Line(Line::default()), self.expression.format(f)])];
+                if is_last_argument {
+                    if f.settings.trailing_comma.for_arguments() {
+                        body.push(Document::IfBreak(IfBreak::then(Document::String(","))));
+                    }
+
+                    body.push(Document::Line(Line::softline()));
+                }
+
+                contents.push(Document::Group(Group::new(body)));
+            }
 
             if let Some(attributes) = attributes {
                 Document::Group(Group::new(vec![attributes, Document::Group(Group::new(contents))]))
@@ -784,7 +777,7 @@ impl<'a> Format<'a> for MatchExpressionArm {
                 if i != (len - 1) {
                     contents.push(Document::String(","));
                     contents.push(Document::IfBreak(IfBreak::new(Document::Line(Line::default()), Document::space())));
-                } else if f.settings.trailing_comma {
+                } else if f.settings.trailing_comma.for_arrays() {
                     contents.push(Document::IfBreak(IfBreak::then(Document::String(","))));
                 }
             }
@@ -822,7 +815,7 @@ impl<'a> Format<'a> for Match {
                 let mut inner_contents =
                     Document::join(self.arms.iter().map(|arm| arm.format(f)).collect::<Vec<_>>(), Separator::CommaLine);
 
-                if f.settings.trailing_comma {
+                if f.settings.trailing_comma.for_arrays() {
                     inner_contents.push(Document::IfBreak(IfBreak::then(Document::String(","))));
                 }
 
@@ -837,7 +830,7 @@ impl<'a> Format<'a> for Match {
 
             contents.push(Document::String("}"));
 
-            Document::Group(Group::new(contents).with_break(true))
+            Document::Group(Group::new(contents))
         })
     }
 }
@@ -901,38 +894,44 @@ impl<'a> Format<'a> for DocumentString {
                 }
             }
 
-            let indent = match self.indentation {
-                DocumentIndentation::None => 0,
-                DocumentIndentation::Whitespace(n) => n,
-                DocumentIndentation::Tab(n) => n,
-                DocumentIndentation::Mixed(t, w) => t + w,
-            };
-
-            contents.push(Document::Line(Line::hardline()));
-            for part in self.parts.iter() {
-                let formatted = match part {
-                    StringPart::Literal(l) => {
-                        let content = f.lookup(&l.value);
-                        let mut part_contents = vec![];
-                        for line in Formatter::split_lines(content) {
-                            let line = Formatter::skip_leading_whitespace_up_to(line, indent);
-
-                            part_contents.push(Document::String(line));
-                        }
-
-                        part_contents = Document::join(part_contents, Separator::Hardline);
+            if f.detached {
+                contents.extend(print_detached_document_body(f, self));
+            } else {
+                let opening = &f.source_text[self.open.start.offset..self.open.end.offset];
+                let mut ending = Formatter::split_lines_with_endings(opening).last().and_then(|(_, ending)| *ending);
+
+                // The body is printed as it is in the source, interpolated expressions included, so that the
+                // value of the string never changes.
+                let body = &f.source_text[self.open.end.offset..self.close.start.offset];
+                let closing = &f.source_text[self.close.start.offset..self.close.end.offset];
+                let closing_indentation = &closing[..closing.len() - label.len()];
+
+                if f.settings.normalize_heredoc_indent {
+                    // The indentation of the closing marker is removed from every line of the body, so the
+                    // lines are moved along with the closing marker to the current indentation.
+                    for (line, line_ending) in Formatter::split_lines_with_endings(body) {
+                        let line = Formatter::skip_leading_whitespace_up_to(line, closing_indentation.len());
+                        let line_break =
+                            if line.is_empty() { Line::literal_line_without_break_parent() } else { Line::hardline() };
+
+                        contents.push(Document::Line(line_break.with_ending(ending).keeping_trailing_whitespace()));
+                        contents.push(Document::String(line));
+                        ending = line_ending;
+                    }
 
-                        // if ends with a newline, add a newline
-                        if content.ends_with('\n') {
-                            part_contents.push(Document::Line(Line::hardline()));
+                    contents.push(Document::Line(Line::hardline().with_ending(ending).keeping_trailing_whitespace()));
+                } else {
+                    contents.push(Document::Line(Line::literal_line_without_break_parent().with_ending(ending)));
+                    for (line, ending) in Formatter::split_lines_with_endings(body) {
+                        contents.push(Document::String(line));
+                        if ending.is_some() {
+                            contents
+                                .push(Document::Line(Line::literal_line_without_break_parent().with_ending(ending)));
                         }
-
-                        Document::Array(part_contents)
                     }
-                    _ => part.format(f),
-                };
 
-                contents.push(formatted);
+                    contents.push(Document::String(closing_indentation));
+                }
             }
 
             contents.push(Document::String(label));
@@ -942,6 +941,37 @@ impl<'a> Format<'a> for DocumentString {
     }
 }
 
+/// Prints the body, and the indentation of the closing marker, of a heredoc, or a nowdoc, from its parts,
+/// for when the source is not available.
+fn print_detached_document_body<'a>(f: &mut Formatter<'a>, document: &'a DocumentString) -> Vec<Document<'a>> {
+    let mut contents = vec![Document::Line(Line::literal_line_without_break_parent())];
+    for part in document.parts.iter() {
+        let StringPart::Literal(literal) = part else {
+            contents.push(part.format(f));
+
+            continue;
+        };
+
+        for (line, ending) in Formatter::split_lines_with_endings(f.lookup(&literal.value)) {
+            contents.push(Document::String(line));
+            if ending.is_some() {
+                contents.push(Document::Line(Line::literal_line_without_break_parent().with_ending(ending)));
+            }
+        }
+    }
+
+    let (spaces, tabs) = match document.indentation {
+        DocumentIndentation::None => (0, 0),
+        DocumentIndentation::Whitespace(spaces) => (spaces, 0),
+        DocumentIndentation::Tab(tabs) => (0, tabs),
+        DocumentIndentation::Mixed(spaces, tabs) => (spaces, tabs),
+    };
+
+    contents.push(Document::String(f.as_str(format!("{}{}", " ".repeat(spaces), "\t".repeat(tabs)))));
+
+    contents
+}
+
 impl<'a> Format<'a> for InterpolatedString {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, InterpolatedString, {
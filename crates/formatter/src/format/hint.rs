@@ -0,0 +1,165 @@
+use mago_ast::*;
+use mago_span::HasSpan;
+
+use crate::document::*;
+use crate::format::Format;
+use crate::settings::CasingStyle;
+use crate::settings::NullableTypeStyle;
+use crate::Formatter;
+
+/// A member of a union type, printed along with what it is sorted by.
+struct Member<'a> {
+    is_null: bool,
+    name: String,
+    document: Document<'a>,
+}
+
+/// Prints a nullable type, such as `?Foo`, as `Foo|null` if `nullable_type_style` is `union_null`.
+///
+/// A nullable type nested inside of another type, or wrapping a type that cannot be nullable with `?`,
+/// is not valid PHP, and is always printed as a union, so that it is valid once it is reformatted.
+pub(super) fn print_nullable_hint<'a>(f: &mut Formatter<'a>, nullable_hint: &'a NullableHint) -> Document<'a> {
+    let is_nested = matches!(f.parent_node(), Node::Hint(_))
+        || matches!(
+            nullable_hint.hint.as_ref(),
+            Hint::Nullable(_) | Hint::Union(_) | Hint::Intersection(_) | Hint::Parenthesized(_)
+        );
+
+    if !is_nested && f.settings.nullable_type_style != NullableTypeStyle::UnionNull {
+        return Document::Group(Group::new(vec![Document::String("?"), spacing(f), nullable_hint.hint.format(f)]));
+    }
+
+    let mut members = vec![];
+    collect_union_members(&nullable_hint.hint, &mut members);
+
+    let mut members: Vec<Member<'a>> = members.into_iter().map(|hint| print_member(f, hint)).collect();
+    members.push(Member { is_null: true, name: "null".to_string(), document: null_keyword(f) });
+
+    print_union_members(f, members)
+}
+
+/// Prints a parenthesized type, such as `(A&B)`.
+pub(super) fn print_parenthesized_hint<'a>(
+    f: &mut Formatter<'a>,
+    parenthesized_hint: &'a ParenthesizedHint,
+) -> Document<'a> {
+    let spacing = spacing(f);
+
+    Document::Group(Group::new(vec![
+        Document::String("("),
+        spacing.clone(),
+        parenthesized_hint.hint.format(f),
+        spacing,
+        Document::String(")"),
+    ]))
+}
+
+/// Prints an intersection type, such as `A&B`.
+pub(super) fn print_intersection_hint<'a>(
+    f: &mut Formatter<'a>,
+    intersection_hint: &'a IntersectionHint,
+) -> Document<'a> {
+    let spacing = spacing(f);
+
+    Document::Group(Group::new(vec![
+        intersection_hint.left.format(f),
+        spacing.clone(),
+        Document::String("&"),
+        spacing,
+        intersection_hint.right.format(f),
+    ]))
+}
+
+/// Prints a union type, such as `Foo|null`, as `?Foo` if `nullable_type_style` is `question_mark`,
+/// and it is the union of `null` and a single type that can be nullable with `?`.
+pub(super) fn print_union_hint<'a>(f: &mut Formatter<'a>, union_hint: &'a UnionHint) -> Document<'a> {
+    let mut members = vec![];
+    collect_union_members(&union_hint.left, &mut members);
+    collect_union_members(&union_hint.right, &mut members);
+
+    if f.settings.nullable_type_style == NullableTypeStyle::QuestionMark && !matches!(f.parent_node(), Node::Hint(_)) {
+        if let [first, second] = members.as_slice() {
+            let hint = match (first, second) {
+                (Hint::Null(_), hint) | (hint, Hint::Null(_)) => Some(*hint),
+                _ => None,
+            };
+
+            if let Some(hint) = hint.filter(|hint| can_be_nullable(hint)) {
+                return Document::Group(Group::new(vec![Document::String("?"), spacing(f), hint.format(f)]));
+            }
+        }
+    }
+
+    let members = members.into_iter().map(|hint| print_member(f, hint)).collect();
+
+    print_union_members(f, members)
+}
+
+/// Collects the members of the given type, in their original order, if it is a union type.
+fn collect_union_members<'a>(hint: &'a Hint, members: &mut Vec<&'a Hint>) {
+    match hint {
+        Hint::Union(union_hint) => {
+            collect_union_members(&union_hint.left, members);
+            collect_union_members(&union_hint.right, members);
+        }
+        _ => members.push(hint),
+    }
+}
+
+fn print_member<'a>(f: &mut Formatter<'a>, hint: &'a Hint) -> Member<'a> {
+    let span = hint.span();
+    let name = f.source_slice(span).trim_start_matches('\\').to_lowercase();
+
+    Member { is_null: matches!(hint, Hint::Null(_)), name, document: hint.format(f) }
+}
+
+/// Prints the members of a union type, sorted if `sort_union_types` is enabled.
+///
+/// The members are printed before they are sorted, so that their comments are printed in order.
+fn print_union_members<'a>(f: &Formatter<'a>, mut members: Vec<Member<'a>>) -> Document<'a> {
+    if f.settings.sort_union_types {
+        members.sort_by(|a, b| (a.is_null, &a.name).cmp(&(b.is_null, &b.name)));
+    }
+
+    let spacing = spacing(f);
+    let mut parts = vec![];
+    for (index, member) in members.into_iter().enumerate() {
+        if index > 0 {
+            parts.extend([spacing.clone(), Document::String("|"), spacing.clone()]);
+        }
+
+        parts.push(member.document);
+    }
+
+    Document::Group(Group::new(parts))
+}
+
+/// Returns whether the given type can be made nullable with `?`.
+fn can_be_nullable(hint: &Hint) -> bool {
+    !matches!(
+        hint,
+        Hint::Nullable(_)
+            | Hint::Union(_)
+            | Hint::Intersection(_)
+            | Hint::Parenthesized(_)
+            | Hint::Null(_)
+            | Hint::Mixed(_)
+            | Hint::Void(_)
+            | Hint::Never(_)
+    )
+}
+
+fn null_keyword<'a>(f: &Formatter<'a>) -> Document<'a> {
+    match f.settings.keyword_case {
+        CasingStyle::Lowercase => Document::String("null"),
+        CasingStyle::Uppercase => Document::String("NULL"),
+    }
+}
+
+fn spacing<'a>(f: &Formatter<'a>) -> Document<'a> {
+    if f.settings.type_spacing > 0 {
+        Document::String(f.as_str(" ".repeat(f.settings.type_spacing)))
+    } else {
+        Document::empty()
+    }
+}
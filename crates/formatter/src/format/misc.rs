@@ -13,7 +13,7 @@ use crate::settings::BraceStyle;
 use crate::Formatter;
 
 pub(super) fn has_new_line_in_range(text: &str, start: usize, end: usize) -> bool {
-    text[start..end].contains('\n')
+    text.get(start..end).is_some_and(|text| text.contains('\n'))
 }
 
 /// Determines whether an expression can be "hugged" within brackets without line breaks.
@@ -80,6 +80,21 @@ pub(super) fn should_hug_expression<'a>(f: &Formatter<'a>, expression: &'a Expre
     )
 }
 
+/// Returns whether the body of an arrow function is kept on the line of the `=>` when it breaks,
+/// instead of being moved to the next line.
+pub(super) fn should_hug_arrow_function_body(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Array(_)
+            | Expression::LegacyArray(_)
+            | Expression::List(_)
+            | Expression::Closure(_)
+            | Expression::ArrowFunction(_)
+            | Expression::AnonymousClass(_)
+            | Expression::Match(_)
+    )
+}
+
 pub(super) fn is_string_word_type(node: &Expression) -> bool {
     match node {
         Expression::Static(_) | Expression::Parent(_) | Expression::Self_(_) => true,
@@ -168,6 +183,51 @@ pub(super) fn print_modifiers<'a>(f: &mut Formatter<'a>, modifiers: &'a Sequence
     Document::join(printed_modifiers, Separator::Space)
 }
 
+/// Prints every attribute list of the given sequence.
+///
+/// When `separate_attribute_groups` is enabled, lists of several attributes are split into one list
+/// per attribute, unless they contain comments.
+pub(super) fn print_attribute_lists<'a>(
+    f: &mut Formatter<'a>,
+    attribute_lists: &'a Sequence<AttributeList>,
+) -> Vec<Document<'a>> {
+    let mut lists = vec![];
+    for attribute_list in attribute_lists.iter() {
+        if !f.settings.separate_attribute_groups
+            || attribute_list.attributes.len() < 2
+            || f.has_comment(attribute_list.span(), CommentFlags::Dangling)
+        {
+            lists.push(attribute_list.format(f));
+
+            continue;
+        }
+
+        f.enter_node(Node::AttributeList(attribute_list));
+        let leading = f.print_leading_comments(attribute_list.span());
+        let mut separated = vec![];
+        for attribute in attribute_list.attributes.iter() {
+            separated.push(Document::Group(Group::new(vec![
+                Document::String("#["),
+                attribute.format(f),
+                Document::String("]"),
+            ])));
+        }
+
+        let trailing = f.print_trailing_comments(attribute_list.span());
+        f.leave_node();
+
+        let last = separated.len() - 1;
+        for (index, list) in separated.into_iter().enumerate() {
+            let leading = if index == 0 { leading.clone() } else { None };
+            let trailing = if index == last { trailing.clone() } else { None };
+
+            lists.push(f.print_comments(leading, list, trailing));
+        }
+    }
+
+    lists
+}
+
 pub(super) fn print_attribute_list_sequence<'a>(
     f: &mut Formatter<'a>,
     attribute_lists: &'a Sequence<AttributeList>,
@@ -177,17 +237,18 @@ pub(super) fn print_attribute_list_sequence<'a>(
         return None;
     }
 
-    let mut lists = vec![];
-    let mut has_new_line = false;
-    for attribute_list in attribute_lists.iter() {
-        lists.push(attribute_list.format(f));
+    let lists = print_attribute_lists(f, attribute_lists);
+    let has_new_line = attribute_lists.last().is_some_and(|attribute_list| f.is_next_line_empty(attribute_list.span()));
 
-        has_new_line = f.is_next_line_empty(attribute_list.span());
-    }
+    // attribute lists of parameters, closures, and arrow functions are kept on the same line when they fit
+    if can_inline && !has_new_line {
+        let mut contents = vec![];
+        for attribute_list in lists {
+            contents.push(attribute_list);
+            contents.push(Document::Line(Line::default()));
+        }
 
-    // if there is a single attribute list, we can inline it
-    if can_inline && !has_new_line && lists.len() == 1 {
-        return Some(Document::Group(Group::new(vec![lists.remove(0), Document::Line(Line::default())])));
+        return Some(Document::Group(Group::new(contents)));
     }
 
     let mut contents = vec![];
@@ -256,7 +317,11 @@ pub(super) fn adjust_clause<'a>(
 
     if has_trailing_segment {
         if is_block {
-            Document::Array(vec![clause, Document::space()])
+            // The trailing segment, e.g. `else`, is cuddled with the closing brace, unless braces are on their own line.
+            match f.settings.control_brace_style {
+                BraceStyle::SameLine => Document::Array(vec![clause, Document::space()]),
+                BraceStyle::NextLine => Document::Array(vec![clause, Document::Line(Line::hardline())]),
+            }
         } else {
             Document::Indent(vec![Document::BreakParent, clause, Document::Line(Line::hardline())])
         }
@@ -266,6 +331,18 @@ pub(super) fn adjust_clause<'a>(
 }
 
 pub(super) fn print_condition<'a>(f: &mut Formatter<'a>, condition: &'a Expression) -> Document<'a> {
+    // Binary operations are broken inside of the parentheses, with one operand per line.
+    if let Expression::Binary(_) = condition {
+        let line = if f.settings.control_space_parens { Line::default() } else { Line::softline() };
+
+        return Document::Group(Group::new(vec![
+            Document::String("("),
+            Document::Indent(vec![Document::Line(line), condition.format(f)]),
+            Document::Line(line),
+            Document::String(")"),
+        ]));
+    }
+
     Document::Group(Group::new(vec![
         Document::String("("),
         if f.settings.control_space_parens { Document::space() } else { Document::empty() },
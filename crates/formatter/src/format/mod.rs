@@ -6,11 +6,17 @@ use crate::comment::CommentFlags;
 use crate::document::*;
 use crate::format::assignment::print_assignment;
 use crate::format::assignment::AssignmentLikeNode;
+use crate::format::block::kept_blank_lines;
 use crate::format::block::print_block_of_nodes;
 use crate::format::call_node::print_call_like_node;
 use crate::format::call_node::CallLikeNode;
 use crate::format::class_like::print_class_like_body;
+use crate::format::hint::print_intersection_hint;
+use crate::format::hint::print_nullable_hint;
+use crate::format::hint::print_parenthesized_hint;
+use crate::format::hint::print_union_hint;
 use crate::format::misc::print_attribute_list_sequence;
+use crate::format::misc::print_attribute_lists;
 use crate::format::misc::print_colon_delimited_body;
 use crate::format::misc::print_modifiers;
 use crate::format::parameters::print_function_like_parameters;
@@ -28,11 +34,15 @@ pub mod call_arguments;
 pub mod call_node;
 pub mod class_like;
 pub mod control_structure;
+pub mod echo_tag;
 pub mod expression;
+pub mod hint;
 pub mod misc;
+pub mod node;
 pub mod parameters;
 pub mod statement;
 pub mod string;
+pub mod uses;
 
 pub trait Format<'a> {
     #[must_use]
@@ -86,40 +96,47 @@ impl<'a> Format<'a> for Program {
 
 impl<'a> Format<'a> for Statement {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
+        let ignored = f.is_ignored(self.span());
+
         wrap!(f, self, Statement, {
-            match self {
-                Statement::OpeningTag(t) => t.format(f),
-                Statement::ClosingTag(t) => t.format(f),
-                Statement::Inline(i) => i.format(f),
-                Statement::Namespace(n) => n.format(f),
-                Statement::Use(u) => u.format(f),
-                Statement::Class(c) => c.format(f),
-                Statement::Interface(i) => i.format(f),
-                Statement::Trait(t) => t.format(f),
-                Statement::Enum(e) => e.format(f),
-                Statement::Block(b) => b.format(f),
-                Statement::Constant(c) => c.format(f),
-                Statement::Function(u) => u.format(f),
-                Statement::Declare(d) => d.format(f),
-                Statement::Goto(g) => g.format(f),
-                Statement::Label(l) => l.format(f),
-                Statement::Try(t) => t.format(f),
-                Statement::Foreach(o) => o.format(f),
-                Statement::For(o) => o.format(f),
-                Statement::While(w) => w.format(f),
-                Statement::DoWhile(d) => d.format(f),
-                Statement::Continue(c) => c.format(f),
-                Statement::Break(b) => b.format(f),
-                Statement::Switch(s) => s.format(f),
-                Statement::If(i) => i.format(f),
-                Statement::Return(r) => r.format(f),
-                Statement::Expression(e) => e.format(f),
-                Statement::Echo(e) => e.format(f),
-                Statement::Global(g) => g.format(f),
-                Statement::Static(s) => s.format(f),
-                Statement::HaltCompiler(h) => h.format(f),
-                Statement::Unset(u) => u.format(f),
-                Statement::Noop(_) => Document::String(";"),
+            if ignored {
+                f.print_verbatim(self.span())
+            } else {
+                match self {
+                    Statement::OpeningTag(t) => t.format(f),
+                    Statement::ClosingTag(t) => t.format(f),
+                    Statement::Inline(i) => i.format(f),
+                    Statement::Namespace(n) => n.format(f),
+                    Statement::Use(u) => u.format(f),
+                    Statement::Class(c) => c.format(f),
+                    Statement::Interface(i) => i.format(f),
+                    Statement::Trait(t) => t.format(f),
+                    Statement::Enum(e) => e.format(f),
+                    Statement::Block(b) => b.format(f),
+                    Statement::Constant(c) => c.format(f),
+                    Statement::Function(u) => u.format(f),
+                    Statement::Declare(d) => d.format(f),
+                    Statement::Goto(g) => g.format(f),
+                    Statement::Label(l) => l.format(f),
+                    Statement::Try(t) => t.format(f),
+                    Statement::Foreach(o) => o.format(f),
+                    Statement::For(o) => o.format(f),
+                    Statement::While(w) => w.format(f),
+                    Statement::DoWhile(d) => d.format(f),
+                    Statement::Continue(c) => c.format(f),
+                    Statement::Break(b) => b.format(f),
+                    Statement::Switch(s) => s.format(f),
+                    Statement::If(i) => i.format(f),
+                    Statement::Return(r) => r.format(f),
+                    Statement::Expression(e) => e.format(f),
+                    Statement::Echo(e) => e.format(f),
+                    Statement::Global(g) => g.format(f),
+                    Statement::Static(s) => s.format(f),
+                    Statement::HaltCompiler(h) => h.format(f),
+                    Statement::Unset(u) => u.format(f),
+                    Statement::Noop(_) => Document::String(";"),
+                    Statement::Error(span) => f.print_verbatim(*span),
+                }
             }
         })
     }
@@ -173,7 +190,7 @@ impl<'a> Format<'a> for ClosingTag {
         wrap!(f, self, ClosingTag, {
             let last_index = self.span.end.offset;
             // todo: put this behind a setting
-            if f.skip_spaces_and_new_lines(Some(last_index), false).is_none() {
+            if !f.detached && f.skip_spaces_and_new_lines(Some(last_index), false).is_none() {
                 f.scripting_mode = true;
 
                 Document::empty()
@@ -265,8 +282,11 @@ impl<'a> Format<'a> for Namespace {
             match &self.body {
                 NamespaceBody::Implicit(namespace_implicit_body) => {
                     parts.push(namespace_implicit_body.terminator.format(f));
-                    parts.push(Document::Line(Line::hardline()));
-                    parts.push(Document::Line(Line::hardline()));
+                    // An empty namespace is followed by the blank lines of the enclosing statements.
+                    if !namespace_implicit_body.statements.is_empty() {
+                        parts.push(Document::Line(Line::hardline()));
+                        parts.push(Document::Line(Line::hardline()));
+                    }
 
                     parts.extend(print_statement_sequence(f, &namespace_implicit_body.statements));
                 }
@@ -317,18 +337,24 @@ impl<'a> Format<'a> for Use {
             Document::Group(Group::new(vec![
                 self.r#use.format(f),
                 Document::space(),
-                match &self.items {
-                    UseItems::Sequence(s) => s.format(f),
-                    UseItems::TypedSequence(s) => s.format(f),
-                    UseItems::TypedList(t) => t.format(f),
-                    UseItems::MixedList(m) => m.format(f),
-                },
+                self.items.format(f),
                 self.terminator.format(f),
             ]))
         })
     }
 }
 
+impl<'a> Format<'a> for UseItems {
+    fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
+        match self {
+            UseItems::Sequence(s) => s.format(f),
+            UseItems::TypedSequence(s) => s.format(f),
+            UseItems::TypedList(t) => t.format(f),
+            UseItems::MixedList(m) => m.format(f),
+        }
+    }
+}
+
 impl<'a> Format<'a> for UseItem {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, UseItem, {
@@ -499,7 +525,7 @@ impl<'a> Format<'a> for TraitUseAbstractSpecification {
 impl<'a> Format<'a> for TraitUseConcreteSpecification {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, TraitUseConcreteSpecification, {
-            print_block_of_nodes(f, &self.left_brace, &self.adaptations, &self.right_brace, false)
+            print_block_of_nodes(f, &self.left_brace, &self.adaptations, &self.right_brace, true, kept_blank_lines)
         })
     }
 }
@@ -646,8 +672,8 @@ impl<'a> Format<'a> for EnumCase {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, EnumCase, {
             let mut parts = vec![];
-            for attribute_list in self.attributes.iter() {
-                parts.push(attribute_list.format(f));
+            for attribute_list in print_attribute_lists(f, &self.attributes) {
+                parts.push(attribute_list);
                 parts.push(Document::Line(Line::hardline()));
             }
 
@@ -830,8 +856,8 @@ impl<'a> Format<'a> for Method {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, Method, {
             let mut attributes = vec![];
-            for attribute_list in self.attributes.iter() {
-                attributes.push(attribute_list.format(f));
+            for attribute_list in print_attribute_lists(f, &self.attributes) {
+                attributes.push(attribute_list);
                 attributes.push(Document::Line(Line::hardline()));
             }
 
@@ -913,7 +939,11 @@ impl<'a> Format<'a> for Terminator {
         wrap!(f, self, Terminator, {
             match self {
                 Terminator::Semicolon(_) | Terminator::TagPair(_, _) => Document::String(";"),
-                Terminator::ClosingTag(t) => t.format(f),
+                // The closing tag is omitted at the end of the file.
+                Terminator::ClosingTag(t) => match t.format(f) {
+                    Document::String("") => Document::empty(),
+                    tag => Document::Array(vec![Document::space(), tag]),
+                },
             }
         })
     }
@@ -959,13 +989,19 @@ impl<'a> Format<'a> for Implements {
 
 impl<'a> Format<'a> for ClassLikeMember {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
+        let ignored = f.is_ignored(self.span());
+
         wrap!(f, self, ClassLikeMember, {
-            match self {
-                ClassLikeMember::TraitUse(m) => m.format(f),
-                ClassLikeMember::Constant(m) => m.format(f),
-                ClassLikeMember::Property(m) => m.format(f),
-                ClassLikeMember::EnumCase(m) => m.format(f),
-                ClassLikeMember::Method(m) => m.format(f),
+            if ignored {
+                f.print_verbatim(self.span())
+            } else {
+                match self {
+                    ClassLikeMember::TraitUse(m) => m.format(f),
+                    ClassLikeMember::Constant(m) => m.format(f),
+                    ClassLikeMember::Property(m) => m.format(f),
+                    ClassLikeMember::EnumCase(m) => m.format(f),
+                    ClassLikeMember::Method(m) => m.format(f),
+                }
             }
         })
     }
@@ -975,8 +1011,8 @@ impl<'a> Format<'a> for Interface {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, Interface, {
             let mut attributes = vec![];
-            for attribute_list in self.attributes.iter() {
-                attributes.push(attribute_list.format(f));
+            for attribute_list in print_attribute_lists(f, &self.attributes) {
+                attributes.push(attribute_list);
                 attributes.push(Document::Line(Line::hardline()));
             }
 
@@ -1067,8 +1103,8 @@ impl<'a> Format<'a> for Trait {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, Trait, {
             let mut attributes = vec![];
-            for attribute_list in self.attributes.iter() {
-                attributes.push(attribute_list.format(f));
+            for attribute_list in print_attribute_lists(f, &self.attributes) {
+                attributes.push(attribute_list);
                 attributes.push(Document::Line(Line::hardline()));
             }
 
@@ -1096,8 +1132,8 @@ impl<'a> Format<'a> for Enum {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, Enum, {
             let mut attributes = vec![];
-            for attribute_list in self.attributes.iter() {
-                attributes.push(attribute_list.format(f));
+            for attribute_list in print_attribute_lists(f, &self.attributes) {
+                attributes.push(attribute_list);
                 attributes.push(Document::Line(Line::hardline()));
             }
 
@@ -1233,123 +1269,10 @@ impl<'a> Format<'a> for Hint {
 
             match self {
                 Hint::Identifier(identifier) => identifier.format(f),
-                Hint::Parenthesized(parenthesized_hint) => {
-                    let spacing = if f.settings.type_spacing > 0 {
-                        Document::String(f.as_str(" ".repeat(f.settings.type_spacing)))
-                    } else {
-                        Document::empty()
-                    };
-
-                    Document::Group(Group::new(vec![
-                        Document::String("("),
-                        spacing.clone(),
-                        parenthesized_hint.hint.format(f),
-                        spacing,
-                        Document::String(")"),
-                    ]))
-                }
-                Hint::Nullable(nullable_hint) => {
-                    let spacing = if f.settings.type_spacing > 0 {
-                        Document::String(f.as_str(" ".repeat(f.settings.type_spacing)))
-                    } else {
-                        Document::empty()
-                    };
-
-                    // If the nullable type is nested inside another type hint,
-                    // we cannot use `?` syntax.
-                    let force_long_syntax = matches!(f.parent_node(), Node::Hint(_))
-                        || (matches!(
-                            nullable_hint.hint.as_ref(),
-                            Hint::Nullable(_) | Hint::Union(_) | Hint::Intersection(_) | Hint::Parenthesized(_)
-                        ));
-
-                    if force_long_syntax {
-                        return Document::Group(Group::new(vec![
-                            k("null"),
-                            spacing.clone(),
-                            Document::String("|"),
-                            spacing,
-                            nullable_hint.hint.format(f),
-                        ]));
-                    }
-
-                    match f.settings.null_type_hint {
-                        NullTypeHint::NullPipe => Document::Group(Group::new(vec![
-                            k("null"),
-                            spacing.clone(),
-                            Document::String("|"),
-                            spacing,
-                            nullable_hint.hint.format(f),
-                        ])),
-                        NullTypeHint::Question => Document::Group(Group::new(vec![
-                            Document::String("?"),
-                            spacing,
-                            nullable_hint.hint.format(f),
-                        ])),
-                    }
-                }
-                Hint::Union(union_hint) => {
-                    let spacing = if f.settings.type_spacing > 0 {
-                        Document::String(f.as_str(" ".repeat(f.settings.type_spacing)))
-                    } else {
-                        Document::empty()
-                    };
-
-                    let force_long_syntax = matches!(f.parent_node(), Node::Hint(_))
-                        || matches!(
-                            union_hint.left.as_ref(),
-                            Hint::Nullable(_) | Hint::Union(_) | Hint::Intersection(_) | Hint::Parenthesized(_)
-                        )
-                        || matches!(
-                            union_hint.right.as_ref(),
-                            Hint::Nullable(_) | Hint::Union(_) | Hint::Intersection(_) | Hint::Parenthesized(_)
-                        );
-
-                    if !force_long_syntax {
-                        if let Hint::Null(_) = union_hint.left.as_ref() {
-                            if f.settings.null_type_hint.is_question() {
-                                return Document::Group(Group::new(vec![
-                                    Document::String("?"),
-                                    spacing,
-                                    union_hint.right.format(f),
-                                ]));
-                            }
-                        }
-
-                        if let Hint::Null(_) = union_hint.right.as_ref() {
-                            if f.settings.null_type_hint.is_question() {
-                                return Document::Group(Group::new(vec![
-                                    Document::String("?"),
-                                    spacing,
-                                    union_hint.left.format(f),
-                                ]));
-                            }
-                        }
-                    }
-
-                    Document::Group(Group::new(vec![
-                        union_hint.left.format(f),
-                        spacing.clone(),
-                        Document::String("|"),
-                        spacing,
-                        union_hint.right.format(f),
-                    ]))
-                }
-                Hint::Intersection(intersection_hint) => {
-                    let spacing = if f.settings.type_spacing > 0 {
-                        Document::String(f.as_str(" ".repeat(f.settings.type_spacing)))
-                    } else {
-                        Document::empty()
-                    };
-
-                    Document::Group(Group::new(vec![
-                        intersection_hint.left.format(f),
-                        spacing.clone(),
-                        Document::String("&"),
-                        spacing,
-                        intersection_hint.right.format(f),
-                    ]))
-                }
+                Hint::Parenthesized(parenthesized_hint) => print_parenthesized_hint(f, parenthesized_hint),
+                Hint::Nullable(nullable_hint) => print_nullable_hint(f, nullable_hint),
+                Hint::Union(union_hint) => print_union_hint(f, union_hint),
+                Hint::Intersection(intersection_hint) => print_intersection_hint(f, intersection_hint),
                 Hint::Null(_) => k("null"),
                 Hint::True(_) => k("true"),
                 Hint::False(_) => k("false"),
@@ -1383,6 +1306,8 @@ impl<'a> Format<'a> for Modifier {
                 Modifier::Public(keyword) => keyword.format(f),
                 Modifier::Protected(keyword) => keyword.format(f),
                 Modifier::Private(keyword) => keyword.format(f),
+                Modifier::PublicSet(keyword) => keyword.format(f),
+                Modifier::ProtectedSet(keyword) => keyword.format(f),
                 Modifier::PrivateSet(keyword) => keyword.format(f),
             }
         })
@@ -1424,10 +1349,7 @@ impl<'a> Format<'a> for AttributeList {
                     && f.has_comment(self.hash_left_bracket.join(self.right_bracket), CommentFlags::all())
             };
 
-            let should_break = self.attributes.len() > 3
-                || has_comments_before_first()
-                || has_comments_after_last()
-                || is_empty_with_comments();
+            let should_break = has_comments_before_first() || has_comments_after_last() || is_empty_with_comments();
 
             let mut contents = vec![Document::String("#[")];
             let mut attributes = vec![];
@@ -1435,10 +1357,12 @@ impl<'a> Format<'a> for AttributeList {
                 attributes.push(Document::Group(Group::new(vec![attribute.format(f)])));
             }
 
-            if should_break {
+            // a list of several attributes breaks with one attribute per line when it does not fit,
+            // while a single attribute only breaks its own arguments.
+            if should_break || attributes.len() > 1 {
                 let mut inner_conent = Document::join(attributes, Separator::CommaLine);
                 inner_conent.insert(0, Document::Line(Line::softline()));
-                if f.settings.trailing_comma {
+                if f.settings.trailing_comma.for_arrays() {
                     inner_conent.push(Document::IfBreak(IfBreak::then(Document::String(","))));
                 }
 
@@ -1450,13 +1374,7 @@ impl<'a> Format<'a> for AttributeList {
                     contents.push(Document::Line(Line::softline()));
                 }
             } else {
-                for (i, attribute) in attributes.into_iter().enumerate() {
-                    if i != 0 {
-                        contents.push(Document::String(", "));
-                    }
-
-                    contents.push(attribute);
-                }
+                contents.extend(attributes);
             }
 
             contents.push(Document::String("]"));
@@ -1634,8 +1552,8 @@ impl<'a> Format<'a> for Function {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, Function, {
             let mut attributes = vec![];
-            for attribute_list in self.attributes.iter() {
-                attributes.push(attribute_list.format(f));
+            for attribute_list in print_attribute_lists(f, &self.attributes) {
+                attributes.push(attribute_list);
                 attributes.push(Document::Line(Line::hardline()));
             }
 
@@ -1679,15 +1597,20 @@ impl<'a> Format<'a> for Function {
 impl<'a> Format<'a> for Try {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, Try, {
-            let mut parts = vec![self.r#try.format(f), Document::space(), self.block.format(f)];
+            let separator = match f.settings.control_brace_style {
+                BraceStyle::SameLine => Document::space(),
+                BraceStyle::NextLine => Document::Line(Line::hardline()),
+            };
+
+            let mut parts = vec![self.r#try.format(f), separator.clone(), self.block.format(f)];
 
             for clause in self.catch_clauses.iter() {
-                parts.push(Document::space());
+                parts.push(separator.clone());
                 parts.push(clause.format(f));
             }
 
             if let Some(clause) = &self.finally_clause {
-                parts.push(Document::space());
+                parts.push(separator);
                 parts.push(clause.format(f));
             }
 
@@ -1711,7 +1634,10 @@ impl<'a> Format<'a> for TryCatchClause {
                 Document::String("("),
                 Document::Group(Group::new(context)),
                 Document::String(")"),
-                Document::space(),
+                match f.settings.control_brace_style {
+                    BraceStyle::SameLine => Document::space(),
+                    BraceStyle::NextLine => Document::Line(Line::hardline()),
+                },
                 self.block.format(f),
             ]))
         })
@@ -1721,7 +1647,14 @@ impl<'a> Format<'a> for TryCatchClause {
 impl<'a> Format<'a> for TryFinallyClause {
     fn format(&'a self, f: &mut Formatter<'a>) -> Document<'a> {
         wrap!(f, self, TryFinallyClause, {
-            Document::Group(Group::new(vec![self.finally.format(f), Document::space(), self.block.format(f)]))
+            Document::Group(Group::new(vec![
+                self.finally.format(f),
+                match f.settings.control_brace_style {
+                    BraceStyle::SameLine => Document::space(),
+                    BraceStyle::NextLine => Document::Line(Line::hardline()),
+                },
+                self.block.format(f),
+            ]))
         })
     }
 }
@@ -1816,7 +1749,7 @@ impl<'a> Format<'a> for Unset {
                 let mut values =
                     Document::join(self.values.iter().map(|v| v.format(f)).collect(), Separator::CommaLine);
 
-                if f.settings.trailing_comma {
+                if f.settings.trailing_comma.for_arguments() {
                     values.push(Document::IfBreak(IfBreak::then(Document::String(","))));
                 }
 
@@ -1858,11 +1791,19 @@ impl<'a> Format<'a> for HaltCompiler {
         f.scripting_mode = false;
 
         wrap!(f, self, HaltCompiler, {
+            // Unlike other closing tags, a closing tag terminator is kept at the end of the file, as the
+            // statement would be missing its terminator otherwise.
+            let terminator = match &self.terminator {
+                Terminator::Semicolon(_) => Document::String(";"),
+                _ => Document::String(" ?>"),
+            };
+
             Document::Group(Group::new(vec![
                 self.halt_compiler.format(f),
                 Document::String("("),
                 Document::String(")"),
-                self.terminator.format(f),
+                terminator,
+                Document::String(f.source_slice(self.data)),
             ]))
         })
     }
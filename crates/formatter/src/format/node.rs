@@ -0,0 +1,269 @@
+use mago_ast::*;
+
+use crate::document::Document;
+use crate::document::Group;
+use crate::document::Line;
+use crate::format::assignment::print_assignment_operator;
+use crate::format::binaryish::print_binary_operator;
+use crate::format::hint::print_intersection_hint;
+use crate::format::hint::print_nullable_hint;
+use crate::format::hint::print_parenthesized_hint;
+use crate::format::hint::print_union_hint;
+use crate::format::statement::print_statement_sequence;
+use crate::format::Format;
+use crate::Formatter;
+
+/// Formats any node, including the ones that are otherwise only formatted as part of their parent,
+/// such as the operator of a binary operation.
+pub(crate) fn format_node<'a>(f: &mut Formatter<'a>, node: Node<'a>) -> Document<'a> {
+    match node {
+        Node::Program(node) => node.format(f),
+        Node::Access(node) => node.format(f),
+        Node::ConstantAccess(node) => node.format(f),
+        Node::ClassConstantAccess(node) => node.format(f),
+        Node::NullSafePropertyAccess(node) => node.format(f),
+        Node::PropertyAccess(node) => node.format(f),
+        Node::StaticPropertyAccess(node) => node.format(f),
+        Node::Argument(node) => node.format(f),
+        Node::ArgumentList(node) => node.format(f),
+        Node::NamedArgument(node) => node.format(f),
+        Node::PositionalArgument(node) => node.format(f),
+        Node::Array(node) => node.format(f),
+        Node::ArrayAccess(node) => node.format(f),
+        Node::ArrayAppend(node) => node.format(f),
+        Node::ArrayElement(node) => node.format(f),
+        Node::KeyValueArrayElement(node) => node.format(f),
+        Node::LegacyArray(node) => node.format(f),
+        Node::List(node) => node.format(f),
+        Node::MissingArrayElement(node) => node.format(f),
+        Node::ValueArrayElement(node) => node.format(f),
+        Node::VariadicArrayElement(node) => node.format(f),
+        Node::Attribute(node) => node.format(f),
+        Node::AttributeList(node) => node.format(f),
+        Node::Block(node) => node.format(f),
+        Node::Call(node) => node.format(f),
+        Node::ClassLikeConstant(node) => node.format(f),
+        Node::ClassLikeConstantItem(node) => node.format(f),
+        Node::EnumCase(node) => node.format(f),
+        Node::EnumCaseBackedItem(node) => node.format(f),
+        Node::EnumCaseItem(node) => node.format(f),
+        Node::EnumCaseUnitItem(node) => node.format(f),
+        Node::Extends(node) => node.format(f),
+        Node::Implements(node) => node.format(f),
+        Node::ClassLikeConstantSelector(node) => node.format(f),
+        Node::ClassLikeMember(node) => node.format(f),
+        Node::ClassLikeMemberExpressionSelector(node) => node.format(f),
+        Node::ClassLikeMemberSelector(node) => node.format(f),
+        Node::Method(node) => node.format(f),
+        Node::MethodAbstractBody(node) => node.format(f),
+        Node::MethodBody(node) => node.format(f),
+        Node::HookedProperty(node) => node.format(f),
+        Node::PlainProperty(node) => node.format(f),
+        Node::Property(node) => node.format(f),
+        Node::PropertyAbstractItem(node) => node.format(f),
+        Node::PropertyConcreteItem(node) => node.format(f),
+        Node::PropertyHook(node) => node.format(f),
+        Node::PropertyHookAbstractBody(node) => node.format(f),
+        Node::PropertyHookBody(node) => node.format(f),
+        Node::PropertyHookConcreteBody(node) => node.format(f),
+        Node::PropertyHookConcreteExpressionBody(node) => node.format(f),
+        Node::PropertyHookList(node) => node.format(f),
+        Node::PropertyItem(node) => node.format(f),
+        Node::TraitUse(node) => node.format(f),
+        Node::TraitUseAbsoluteMethodReference(node) => node.format(f),
+        Node::TraitUseAbstractSpecification(node) => node.format(f),
+        Node::TraitUseAdaptation(node) => node.format(f),
+        Node::TraitUseAliasAdaptation(node) => node.format(f),
+        Node::TraitUseConcreteSpecification(node) => node.format(f),
+        Node::TraitUseMethodReference(node) => node.format(f),
+        Node::TraitUsePrecedenceAdaptation(node) => node.format(f),
+        Node::TraitUseSpecification(node) => node.format(f),
+        Node::AnonymousClass(node) => node.format(f),
+        Node::Class(node) => node.format(f),
+        Node::Enum(node) => node.format(f),
+        Node::EnumBackingTypeHint(node) => node.format(f),
+        Node::Interface(node) => node.format(f),
+        Node::Trait(node) => node.format(f),
+        Node::Clone(node) => node.format(f),
+        Node::ClosureCreation(node) => node.format(f),
+        Node::FunctionClosureCreation(node) => node.format(f),
+        Node::MethodClosureCreation(node) => node.format(f),
+        Node::StaticMethodClosureCreation(node) => node.format(f),
+        Node::Constant(node) => node.format(f),
+        Node::ConstantItem(node) => node.format(f),
+        Node::Construct(node) => node.format(f),
+        Node::DieConstruct(node) => node.format(f),
+        Node::EmptyConstruct(node) => node.format(f),
+        Node::EvalConstruct(node) => node.format(f),
+        Node::ExitConstruct(node) => node.format(f),
+        Node::IncludeConstruct(node) => node.format(f),
+        Node::IncludeOnceConstruct(node) => node.format(f),
+        Node::IssetConstruct(node) => node.format(f),
+        Node::PrintConstruct(node) => node.format(f),
+        Node::RequireConstruct(node) => node.format(f),
+        Node::RequireOnceConstruct(node) => node.format(f),
+        Node::If(node) => node.format(f),
+        Node::IfBody(node) => node.format(f),
+        Node::IfColonDelimitedBody(node) => node.format(f),
+        Node::IfColonDelimitedBodyElseClause(node) => node.format(f),
+        Node::IfColonDelimitedBodyElseIfClause(node) => node.format(f),
+        Node::IfStatementBody(node) => node.format(f),
+        Node::IfStatementBodyElseClause(node) => node.format(f),
+        Node::IfStatementBodyElseIfClause(node) => node.format(f),
+        Node::Match(node) => node.format(f),
+        Node::MatchArm(node) => node.format(f),
+        Node::MatchDefaultArm(node) => node.format(f),
+        Node::MatchExpressionArm(node) => node.format(f),
+        Node::Switch(node) => node.format(f),
+        Node::SwitchBody(node) => node.format(f),
+        Node::SwitchBraceDelimitedBody(node) => node.format(f),
+        Node::SwitchCase(node) => node.format(f),
+        Node::SwitchCaseSeparator(node) => node.format(f),
+        Node::SwitchColonDelimitedBody(node) => node.format(f),
+        Node::SwitchDefaultCase(node) => node.format(f),
+        Node::SwitchExpressionCase(node) => node.format(f),
+        Node::Declare(node) => node.format(f),
+        Node::DeclareBody(node) => node.format(f),
+        Node::DeclareColonDelimitedBody(node) => node.format(f),
+        Node::DeclareItem(node) => node.format(f),
+        Node::Echo(node) => node.format(f),
+        Node::Expression(node) => node.format(f),
+        Node::Binary(node) => node.format(f),
+        Node::UnaryPrefix(node) => node.format(f),
+        Node::UnaryPrefixOperator(node) => node.format(f),
+        Node::UnaryPostfix(node) => node.format(f),
+        Node::UnaryPostfixOperator(node) => node.format(f),
+        Node::ArrowFunction(node) => node.format(f),
+        Node::Closure(node) => node.format(f),
+        Node::ClosureUseClause(node) => node.format(f),
+        Node::ClosureUseClauseVariable(node) => node.format(f),
+        Node::Function(node) => node.format(f),
+        Node::FunctionLikeParameter(node) => node.format(f),
+        Node::FunctionLikeParameterDefaultValue(node) => node.format(f),
+        Node::FunctionLikeParameterList(node) => node.format(f),
+        Node::FunctionLikeReturnTypeHint(node) => node.format(f),
+        Node::Global(node) => node.format(f),
+        Node::Goto(node) => node.format(f),
+        Node::Label(node) => node.format(f),
+        Node::HaltCompiler(node) => node.format(f),
+        Node::FullyQualifiedIdentifier(node) => node.format(f),
+        Node::Identifier(node) => node.format(f),
+        Node::LocalIdentifier(node) => node.format(f),
+        Node::QualifiedIdentifier(node) => node.format(f),
+        Node::Inline(node) => node.format(f),
+        Node::Instantiation(node) => node.format(f),
+        Node::Keyword(node) => node.format(f),
+        Node::Literal(node) => node.format(f),
+        Node::LiteralFloat(node) => node.format(f),
+        Node::LiteralInteger(node) => node.format(f),
+        Node::LiteralString(node) => node.format(f),
+        Node::MagicConstant(node) => node.format(f),
+        Node::Modifier(node) => node.format(f),
+        Node::Namespace(node) => node.format(f),
+        Node::AssignmentOperation(node) => node.format(f),
+        Node::Conditional(node) => node.format(f),
+        Node::DoWhile(node) => node.format(f),
+        Node::Foreach(node) => node.format(f),
+        Node::ForeachBody(node) => node.format(f),
+        Node::ForeachColonDelimitedBody(node) => node.format(f),
+        Node::ForeachKeyValueTarget(node) => node.format(f),
+        Node::ForeachTarget(node) => node.format(f),
+        Node::ForeachValueTarget(node) => node.format(f),
+        Node::For(node) => node.format(f),
+        Node::ForBody(node) => node.format(f),
+        Node::ForColonDelimitedBody(node) => node.format(f),
+        Node::While(node) => node.format(f),
+        Node::WhileBody(node) => node.format(f),
+        Node::WhileColonDelimitedBody(node) => node.format(f),
+        Node::Break(node) => node.format(f),
+        Node::Continue(node) => node.format(f),
+        Node::Return(node) => node.format(f),
+        Node::Static(node) => node.format(f),
+        Node::StaticAbstractItem(node) => node.format(f),
+        Node::StaticConcreteItem(node) => node.format(f),
+        Node::StaticItem(node) => node.format(f),
+        Node::Try(node) => node.format(f),
+        Node::TryCatchClause(node) => node.format(f),
+        Node::TryFinallyClause(node) => node.format(f),
+        Node::MaybeTypedUseItem(node) => node.format(f),
+        Node::MixedUseItemList(node) => node.format(f),
+        Node::TypedUseItemList(node) => node.format(f),
+        Node::TypedUseItemSequence(node) => node.format(f),
+        Node::Use(node) => node.format(f),
+        Node::UseItem(node) => node.format(f),
+        Node::UseItemAlias(node) => node.format(f),
+        Node::UseItemSequence(node) => node.format(f),
+        Node::UseType(node) => node.format(f),
+        Node::Yield(node) => node.format(f),
+        Node::YieldFrom(node) => node.format(f),
+        Node::YieldPair(node) => node.format(f),
+        Node::YieldValue(node) => node.format(f),
+        Node::Statement(node) => node.format(f),
+        Node::ExpressionStatement(node) => node.format(f),
+        Node::BracedExpressionStringPart(node) => node.format(f),
+        Node::DocumentString(node) => node.format(f),
+        Node::InterpolatedString(node) => node.format(f),
+        Node::LiteralStringPart(node) => node.format(f),
+        Node::ShellExecuteString(node) => node.format(f),
+        Node::CompositeString(node) => node.format(f),
+        Node::StringPart(node) => node.format(f),
+        Node::ClosingTag(node) => node.format(f),
+        Node::EchoOpeningTag(node) => node.format(f),
+        Node::FullOpeningTag(node) => node.format(f),
+        Node::OpeningTag(node) => node.format(f),
+        Node::ShortOpeningTag(node) => node.format(f),
+        Node::Terminator(node) => node.format(f),
+        Node::Throw(node) => node.format(f),
+        Node::Hint(node) => node.format(f),
+        Node::Unset(node) => node.format(f),
+        Node::DirectVariable(node) => node.format(f),
+        Node::IndirectVariable(node) => node.format(f),
+        Node::NestedVariable(node) => node.format(f),
+        Node::Variable(node) => node.format(f),
+        Node::FunctionCall(call) => {
+            Document::Group(Group::new(vec![call.function.format(f), call.arguments.format(f)]))
+        }
+        Node::MethodCall(call) => Document::Group(Group::new(vec![
+            call.object.format(f),
+            Document::String("->"),
+            call.method.format(f),
+            call.arguments.format(f),
+        ])),
+        Node::NullSafeMethodCall(call) => Document::Group(Group::new(vec![
+            call.object.format(f),
+            Document::String("?->"),
+            call.method.format(f),
+            call.arguments.format(f),
+        ])),
+        Node::StaticMethodCall(call) => Document::Group(Group::new(vec![
+            call.class.format(f),
+            Document::String("::"),
+            call.method.format(f),
+            call.arguments.format(f),
+        ])),
+        Node::BinaryOperator(operator) => print_binary_operator(f, operator),
+        Node::AssignmentOperator(operator) => print_assignment_operator(operator),
+        Node::Parenthesized(parenthesized) => Document::Group(Group::new(vec![
+            Document::String("("),
+            parenthesized.expression.format(f),
+            Document::String(")"),
+        ])),
+        Node::NamespaceBody(NamespaceBody::BraceDelimited(block)) => block.format(f),
+        Node::NamespaceBody(NamespaceBody::Implicit(body)) | Node::NamespaceImplicitBody(body) => {
+            let mut parts = vec![body.terminator.format(f)];
+            if !body.statements.is_empty() {
+                parts.push(Document::Line(Line::hardline()));
+                parts.push(Document::Line(Line::hardline()));
+            }
+
+            parts.extend(print_statement_sequence(f, &body.statements));
+
+            Document::Array(parts)
+        }
+        Node::UseItems(items) => items.format(f),
+        Node::IntersectionHint(hint) => print_intersection_hint(f, hint),
+        Node::NullableHint(hint) => print_nullable_hint(f, hint),
+        Node::ParenthesizedHint(hint) => print_parenthesized_hint(f, hint),
+        Node::UnionHint(hint) => print_union_hint(f, hint),
+    }
+}
@@ -82,7 +82,9 @@ pub(super) fn print_function_like_parameters<'a>(
         contents.extend(printed);
         parts.push(Document::Indent(contents));
 
-        if f.settings.trailing_comma {
+        // A trailing comma is not allowed after a variadic parameter.
+        let is_variadic = parameter_list.parameters.last().is_some_and(|parameter| parameter.ellipsis.is_some());
+        if f.settings.trailing_comma.for_arguments() && !is_variadic {
             parts.push(Document::IfBreak(IfBreak::then(Document::String(","))));
         }
     }
@@ -91,13 +93,17 @@ pub(super) fn print_function_like_parameters<'a>(
         f.print_dangling_comments(parameter_list.left_parenthesis.join(parameter_list.right_parenthesis), true)
     {
         parts.push(comments);
-    } else {
+    } else if !parameter_list.parameters.is_empty() {
         parts.push(Document::Line(Line::softline()));
     }
 
     parts.push(Document::String(")"));
 
-    if f.argument_state.expand_first_argument {
+    // The parameters of a closure with a `use` clause are part of its signature group, so that they break
+    // along with the variables of the clause.
+    let is_closure_with_use_clause = matches!(f.parent_node(), Node::Closure(closure) if closure.use_clause.is_some());
+
+    if f.argument_state.expand_first_argument || (is_closure_with_use_clause && !should_break) {
         Document::Array(parts)
     } else {
         Document::Group(Group::new(parts).with_break(should_break))
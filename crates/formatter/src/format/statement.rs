@@ -3,6 +3,10 @@ use mago_span::HasSpan;
 
 use crate::document::Document;
 use crate::document::Line;
+use crate::format::echo_tag::echo_block_end;
+use crate::format::echo_tag::print_echo_block;
+use crate::format::uses::print_use_statements;
+use crate::format::uses::use_statements_end;
 use crate::format::Format;
 use crate::Formatter;
 
@@ -11,30 +15,101 @@ pub fn print_statement_sequence<'a>(f: &mut Formatter<'a>, stmts: &'a Sequence<S
 
     let mut should_include_new_line = true;
     let last_non_noop_index = stmts.iter().rposition(|stmt| !matches!(stmt, Statement::Noop(_)));
-    for (i, stmt) in stmts.iter().enumerate() {
-        if matches!(stmt, Statement::ClosingTag(_)) {
-            // stop including new lines after closing tags
-            should_include_new_line = false;
+    let stmts = stmts.as_slice();
+    let mut i = 0;
+    while i < stmts.len() {
+        // A region left unformatted by `@mago-fmt-ignore-start` is printed as a single statement.
+        let region_end = f.ignored_region(stmts, i);
+        // A run of `use` statements at the top level is printed as a whole, so that it can be sorted.
+        let uses_end = if region_end.is_none() && is_top_level(f) { use_statements_end(f, stmts, i) } else { None };
+        // A block echoing a single expression is printed as a whole, so that its opening tag can be converted.
+        let echo_end = if region_end.is_none() && uses_end.is_none() { echo_block_end(f, stmts, i) } else { None };
+        let last = region_end.or(uses_end).or(echo_end).unwrap_or(i);
+
+        for stmt in &stmts[i..=last] {
+            if matches!(stmt, Statement::ClosingTag(_)) {
+                // stop including new lines after closing tags
+                should_include_new_line = false;
+            }
+
+            if matches!(stmt, Statement::OpeningTag(_)) {
+                // start including new lines after opening tags
+                should_include_new_line = true;
+            }
+        }
+
+        match (region_end, uses_end, echo_end) {
+            (Some(last), _, _) => parts.push(f.print_verbatim_region(stmts[i].span(), stmts[last].span())),
+            (None, Some(last), _) => parts.push(print_use_statements(f, &stmts[i..=last])),
+            (None, None, Some(last)) => parts.push(print_echo_block(f, &stmts[i..=last])),
+            (None, None, None) => parts.push(stmts[i].format(f)),
         }
 
-        if matches!(stmt, Statement::OpeningTag(_)) {
-            // start including new lines after opening tags
-            should_include_new_line = true;
+        // The empty statements on the same line, such as the `;` of `function foo() {};`, terminate the statement,
+        // so they are printed along with it, and the blank lines are counted from the end of the last one.
+        let mut last = last;
+        while let Some(noop @ Statement::Noop(span)) = stmts.get(last + 1) {
+            if f.source_text[stmts[last].span().end.offset..span.start.offset].contains('\n') {
+                break;
+            }
+
+            parts.push(noop.format(f));
+            last += 1;
         }
 
-        parts.push(stmt.format(f));
+        // Inline HTML is printed exactly as it is, so nothing is printed between it and the tags around it.
+        let is_next_to_inline =
+            matches!(stmts[last], Statement::Inline(_)) || matches!(stmts.get(last + 1), Some(Statement::Inline(_)));
 
-        if should_include_new_line {
+        if should_include_new_line && !is_next_to_inline {
             if let Some(index) = last_non_noop_index {
-                if i != index {
-                    parts.push(Document::Line(Line::hardline()));
-                    if f.is_next_line_empty(stmt.span()) {
+                if last < index {
+                    if matches!(stmts[last], Statement::OpeningTag(OpeningTag::Echo(_))) {
+                        parts.push(Document::space());
+                    } else {
                         parts.push(Document::Line(Line::hardline()));
+                        for _ in 0..blank_lines_after(f, stmts, last) {
+                            parts.push(Document::Line(Line::hardline()));
+                        }
                     }
                 }
             }
         }
+
+        i = last + 1;
     }
 
     parts
 }
+
+/// Returns the number of blank lines to print after the statement at the given index.
+fn blank_lines_after<'a>(f: &Formatter<'a>, stmts: &'a [Statement], index: usize) -> usize {
+    let is_top_level = is_top_level(f);
+
+    if index == 0
+        && is_top_level
+        && matches!(stmts[0], Statement::OpeningTag(OpeningTag::Full(_) | OpeningTag::Short(_)))
+        && !stmts.iter().any(|stmt| matches!(stmt, Statement::Inline(_)))
+    {
+        return f.settings.blank_lines_after_opening_tag;
+    }
+
+    if let (Some(Statement::Return(_)), Some(blank_lines)) =
+        (stmts.get(index + 1), f.settings.blank_lines_before_return)
+    {
+        return blank_lines;
+    }
+
+    let max = f.settings.max_consecutive_blank_lines.unwrap_or(if is_top_level { 2 } else { 1 });
+
+    f.blank_lines_after(stmts[index].span()).min(max)
+}
+
+/// Returns whether the statements being printed are at the top level of a file, or of a namespace.
+fn is_top_level(f: &Formatter<'_>) -> bool {
+    match f.current_node() {
+        Node::Program(_) | Node::Namespace(_) => true,
+        Node::Block(_) => matches!(f.parent_node(), Node::Namespace(_)),
+        _ => false,
+    }
+}
@@ -1,74 +1,130 @@
 use mago_ast::LiteralStringKind;
 use mago_interner::StringIdentifier;
 
+use crate::settings::StringQuotes;
 use crate::Formatter;
 
-fn get_preferred_quote(raw: &str, enclosing_quote: char, prefer_single_quote: bool) -> char {
-    let (preferred_quote_char, alternate_quote_char) = if prefer_single_quote { ('\'', '"') } else { ('"', '\'') };
+fn get_preferred_quote(raw: &str, preferred_quote: char) -> char {
+    let alternate_quote = if preferred_quote == '\'' { '"' } else { '\'' };
 
     let mut preferred_quote_count = 0;
     let mut alternate_quote_count = 0;
 
     for character in raw.chars() {
-        if character == preferred_quote_char {
+        if character == preferred_quote {
             preferred_quote_count += 1;
-        } else if character == alternate_quote_char {
+        } else if character == alternate_quote {
             alternate_quote_count += 1;
-        } else if character == '\\' && !matches!(raw.chars().next(), Some(c) if c == enclosing_quote) {
-            // If the string contains a backslash followed by the other quote character, we should
-            // prefer the existing quote character.
-            return enclosing_quote;
         }
     }
 
     if preferred_quote_count > alternate_quote_count {
-        alternate_quote_char
+        alternate_quote
     } else {
-        preferred_quote_char
+        preferred_quote
     }
 }
 
-fn make_string(raw_text: &str, enclosing_quote: char) -> String {
-    let other_quote = if enclosing_quote == '"' { '\'' } else { '"' };
-    let mut result = String::new();
-    result.push(enclosing_quote);
-
-    let mut chars = raw_text.chars().peekable();
-    while let Some(c) = chars.next() {
-        match c {
-            '\\' => {
-                if let Some(&next_char) = chars.peek() {
-                    if next_char != other_quote {
-                        result.push('\\');
-                    }
-                    result.push(next_char);
-                    chars.next();
-                } else {
+/// Returns whether the given character starts an escape sequence in double-quoted strings.
+///
+/// `\x` and `\u` are only escape sequences when followed by hexadecimal digits, or by `{`, but
+/// they are treated as such regardless, to be on the safe side.
+fn is_double_quoted_escape(character: char) -> bool {
+    matches!(character, 'n' | 't' | 'v' | 'e' | 'f' | 'r' | 'x' | 'u' | '$' | '"' | '\\' | '0'..='7')
+}
+
+/// Converts the raw content of a double-quoted string into the raw content of a single-quoted string
+/// with the same value, or returns `None` if it contains escape sequences that single-quoted strings
+/// do not support, such as `\n`.
+fn double_to_single_quoted(raw: &str) -> Option<String> {
+    let mut result = String::with_capacity(raw.len());
+
+    let mut chars = raw.chars().peekable();
+    while let Some(character) = chars.next() {
+        match character {
+            '\\' => match chars.next() {
+                Some('\\') => result.push_str("\\\\"),
+                Some(escaped @ ('"' | '$')) => result.push(escaped),
+                Some(escaped) if is_double_quoted_escape(escaped) => return None,
+                // A backslash that does not start an escape sequence is kept as is.
+                Some('\'') => result.push_str("\\\\\\'"),
+                Some(other) => {
                     result.push('\\');
+                    result.push(other);
                 }
+                None => result.push_str("\\\\"),
+            },
+            '\'' => result.push_str("\\'"),
+            _ => result.push(character),
+        }
+    }
+
+    Some(result)
+}
+
+/// Converts the raw content of a single-quoted string into the raw content of a double-quoted string
+/// with the same value, or returns `None` if its content would be interpolated, or escaped, in a
+/// double-quoted string, such as `$name` or `\n`.
+fn single_to_double_quoted(raw: &str) -> Option<String> {
+    let mut result = String::with_capacity(raw.len());
+
+    let mut chars = raw.chars().peekable();
+    while let Some(character) = chars.next() {
+        match character {
+            '\\' => match chars.peek().copied() {
+                Some('\\') => {
+                    chars.next();
+                    result.push_str("\\\\");
+                }
+                Some('\'') => {
+                    chars.next();
+                    result.push('\'');
+                }
+                Some(next) if is_double_quoted_escape(next) || next == '{' => return None,
+                Some(_) => result.push('\\'),
+                None => result.push_str("\\\\"),
+            },
+            '$' if chars
+                .peek()
+                .is_some_and(|&next| matches!(next, '{' | '_' | 'a'..='z' | 'A'..='Z') || !next.is_ascii()) =>
+            {
+                return None;
             }
-            _ if c == enclosing_quote => {
-                result.push('\\');
-                result.push(c);
-            }
-            _ => result.push(c),
+            '{' if chars.peek() == Some(&'$') => return None,
+            '"' => result.push_str("\\\""),
+            _ => result.push(character),
         }
     }
 
-    result.push(enclosing_quote);
-    result
+    Some(result)
 }
 
 pub(super) fn print_string<'a>(f: &Formatter<'a>, kind: &LiteralStringKind, value: &StringIdentifier) -> &'a str {
     let text = f.lookup(value);
 
-    let quote = unsafe { text.chars().next().unwrap_unchecked() };
+    let preferred_quote = match f.settings.string_quotes {
+        StringQuotes::Single => '\'',
+        StringQuotes::Double => '"',
+        StringQuotes::Preserve => return text,
+    };
+
+    let (Some(quote @ ('\'' | '"')), true) = (text.chars().next(), text.len() >= 2) else {
+        return text;
+    };
+
     let raw_text = &text[1..text.len() - 1];
-    let enclosing_quote = get_preferred_quote(raw_text, quote, f.settings.single_quote);
+    let converted = match (kind, get_preferred_quote(raw_text, preferred_quote)) {
+        (LiteralStringKind::SingleQuoted, '"') if quote == '\'' => single_to_double_quoted(raw_text),
+        (LiteralStringKind::DoubleQuoted, '\'') if quote == '"' => double_to_single_quoted(raw_text),
+        _ => None,
+    };
 
-    match kind {
-        LiteralStringKind::SingleQuoted if enclosing_quote == '\'' => text,
-        LiteralStringKind::DoubleQuoted if enclosing_quote == '"' => text,
-        _ => f.as_str(make_string(raw_text, enclosing_quote)),
+    match converted {
+        Some(converted) => {
+            let enclosing_quote = if quote == '\'' { '"' } else { '\'' };
+
+            f.as_str(format!("{}{}{}", enclosing_quote, converted, enclosing_quote))
+        }
+        None => text,
     }
 }
@@ -0,0 +1,302 @@
+use mago_ast::sequence::TokenSeparatedSequence;
+use mago_ast::*;
+use mago_span::HasSpan;
+
+use crate::comment::CommentFlags;
+use crate::document::*;
+use crate::format::Format;
+use crate::settings::UseGroup;
+use crate::Formatter;
+
+/// A `use` statement, printed on its own line, along with what it is sorted by.
+struct Import<'a> {
+    group: UseGroup,
+    /// The fully qualified name of the first name imported by the statement.
+    name: String,
+    document: Document<'a>,
+    /// The number of blank lines after the statement in the source, kept when statements are not sorted.
+    blank_lines: usize,
+}
+
+/// Returns the index of the last statement of the run of `use` statements starting at the given index,
+/// if the run is to be sorted, or expanded.
+pub(super) fn use_statements_end(f: &Formatter<'_>, stmts: &[Statement], index: usize) -> Option<usize> {
+    if !f.settings.sort_uses && !f.settings.expand_use_groups {
+        return None;
+    }
+
+    let length = (index..stmts.len())
+        .take_while(|&i| {
+            matches!(&stmts[i], Statement::Use(r#use) if matches!(r#use.terminator, Terminator::Semicolon(_)))
+                && !f.is_ignored(stmts[i].span())
+                && f.ignored_region(stmts, i).is_none()
+        })
+        .count();
+
+    if length == 0 {
+        None
+    } else {
+        Some(index + length - 1)
+    }
+}
+
+/// Prints the given `use` statements, one per line, sorted and grouped by kind if `sort_uses` is enabled.
+///
+/// Each statement is printed along with its leading and trailing comments, so that comments are moved
+/// along with the statement they belong to.
+pub(super) fn print_use_statements<'a>(f: &mut Formatter<'a>, stmts: &'a [Statement]) -> Document<'a> {
+    let max_blank_lines = f.settings.max_consecutive_blank_lines.unwrap_or(2);
+
+    let mut imports = vec![];
+    for statement in stmts {
+        let mut printed = print_use_statement(f, statement);
+        if let Some(last) = printed.last_mut() {
+            last.blank_lines = f.blank_lines_after(statement.span()).min(max_blank_lines);
+        }
+
+        imports.extend(printed);
+    }
+
+    if f.settings.sort_uses {
+        let groups = f.settings.use_groups;
+
+        imports.sort_by_cached_key(|import| {
+            (groups.iter().position(|group| *group == import.group), sort_key(&import.name))
+        });
+    }
+
+    let mut parts = vec![];
+    let mut previous: Option<(UseGroup, usize)> = None;
+    for import in imports {
+        if let Some((group, blank_lines)) = previous {
+            parts.push(Document::Line(Line::hardline()));
+
+            let blank_lines = if !f.settings.sort_uses {
+                blank_lines
+            } else if f.settings.separate_use_groups && group != import.group {
+                1
+            } else {
+                0
+            };
+
+            for _ in 0..blank_lines {
+                parts.push(Document::Line(Line::hardline()));
+            }
+        }
+
+        previous = Some((import.group, import.blank_lines));
+        parts.push(import.document);
+    }
+
+    Document::Array(parts)
+}
+
+fn print_use_statement<'a>(f: &mut Formatter<'a>, statement: &'a Statement) -> Vec<Import<'a>> {
+    let Statement::Use(r#use) = statement else {
+        unreachable!("only `use` statements are sorted");
+    };
+
+    let is_empty_list = match &r#use.items {
+        UseItems::TypedList(list) => list.items.is_empty(),
+        UseItems::MixedList(list) => list.items.is_empty(),
+        _ => false,
+    };
+
+    // Statements containing comments, or importing nothing, are printed, and sorted, as a whole.
+    if is_empty_list || f.has_comment(r#use.span(), CommentFlags::Dangling) {
+        let (group, name) = first_import(f, r#use);
+
+        return vec![Import { group, name, document: statement.format(f), blank_lines: 0 }];
+    }
+
+    f.enter_node(Node::Statement(statement));
+    let leading = f.print_leading_comments(statement.span());
+    // The statement contains no comments, so the trailing comments are the next ones, and are printed
+    // first, as they would otherwise be printed before the semicolon, as trailing comments of the last name.
+    let trailing = f.print_trailing_comments(statement.span());
+    f.enter_node(Node::Use(r#use));
+
+    let keyword = r#use.r#use.format(f);
+    let mut imports = match &r#use.items {
+        UseItems::Sequence(sequence) => vec![print_use_item_sequence(f, keyword, None, &sequence.items)],
+        UseItems::TypedSequence(sequence) => {
+            vec![print_use_item_sequence(f, keyword, Some(&sequence.r#type), &sequence.items)]
+        }
+        UseItems::TypedList(list) => print_use_item_list(
+            f,
+            keyword,
+            Some(&list.r#type),
+            &list.namespace,
+            list.items.iter().map(|item| (None, item)).collect(),
+        ),
+        UseItems::MixedList(list) => print_use_item_list(
+            f,
+            keyword,
+            None,
+            &list.namespace,
+            list.items.iter().map(|item| (item.r#type.as_ref(), &item.item)).collect(),
+        ),
+    };
+
+    f.leave_node();
+    f.leave_node();
+
+    if let Some(first) = imports.first_mut() {
+        first.document = f.print_comments(leading, std::mem::replace(&mut first.document, Document::empty()), None);
+    }
+
+    if let Some(last) = imports.last_mut() {
+        last.document = f.print_comments(None, std::mem::replace(&mut last.document, Document::empty()), trailing);
+    }
+
+    imports
+}
+
+/// Prints a `use` statement importing a comma-separated sequence of names, such as `use Foo, Bar;`.
+fn print_use_item_sequence<'a>(
+    f: &mut Formatter<'a>,
+    keyword: Document<'a>,
+    r#type: Option<&'a UseType>,
+    items: &'a TokenSeparatedSequence<UseItem>,
+) -> Import<'a> {
+    let mut names: Vec<(String, Document<'a>)> =
+        items.iter().map(|item| (name_of(f, &item.name).to_string(), item.format(f))).collect();
+
+    if f.settings.sort_uses {
+        names.sort_by_cached_key(|(name, _)| sort_key(name));
+    }
+
+    let mut parts = vec![keyword, Document::space()];
+    if let Some(r#type) = r#type {
+        parts.push(r#type.format(f));
+        parts.push(Document::space());
+    }
+
+    let name = names[0].0.clone();
+    parts.push(Document::Group(Group::new(vec![
+        Document::Indent(Document::join(names.into_iter().map(|(_, item)| item).collect(), Separator::CommaLine)),
+        Document::Line(Line::softline()),
+    ])));
+    parts.push(Document::String(";"));
+
+    Import { group: group_of(r#type), name, document: Document::Group(Group::new(parts)), blank_lines: 0 }
+}
+
+/// Prints a grouped `use` statement, such as `use Foo\{Bar, Baz};`, or one statement per name
+/// if `expand_use_groups` is enabled.
+fn print_use_item_list<'a>(
+    f: &mut Formatter<'a>,
+    keyword: Document<'a>,
+    list_type: Option<&'a UseType>,
+    namespace: &'a Identifier,
+    items: Vec<(Option<&'a UseType>, &'a UseItem)>,
+) -> Vec<Import<'a>> {
+    let prefix = name_of(f, namespace);
+    let namespace = namespace.format(f);
+
+    let mut names: Vec<(String, Option<&'a UseType>, Document<'a>)> = items
+        .into_iter()
+        .map(|(r#type, item)| (format!("{}\\{}", prefix, name_of(f, &item.name)), r#type, item.format(f)))
+        .collect();
+
+    if f.settings.sort_uses {
+        names.sort_by_cached_key(|(name, _, _)| sort_key(name));
+    }
+
+    if f.settings.expand_use_groups {
+        return names
+            .into_iter()
+            .map(|(name, r#type, item)| {
+                let mut parts = vec![keyword.clone(), Document::space()];
+                if let Some(r#type) = list_type.or(r#type) {
+                    parts.push(r#type.format(f));
+                    parts.push(Document::space());
+                }
+
+                parts.extend([namespace.clone(), Document::String("\\"), item, Document::String(";")]);
+
+                Import {
+                    group: group_of(list_type.or(r#type)),
+                    name,
+                    document: Document::Group(Group::new(parts)),
+                    blank_lines: 0,
+                }
+            })
+            .collect();
+    }
+
+    let mut parts = vec![keyword, Document::space()];
+    if let Some(r#type) = list_type {
+        parts.push(r#type.format(f));
+        parts.push(Document::space());
+    }
+
+    let name = names[0].0.clone();
+    let items = names
+        .into_iter()
+        .map(|(_, r#type, item)| match r#type {
+            Some(r#type) => Document::Group(Group::new(vec![r#type.format(f), Document::space(), item])),
+            None => item,
+        })
+        .collect();
+
+    let mut items = Document::join(items, Separator::CommaLine);
+    items.insert(0, Document::Line(Line::softline()));
+
+    parts.extend([namespace, Document::String("\\"), Document::String("{")]);
+    parts.push(Document::Indent(items));
+    parts.push(Document::Line(Line::softline()));
+    parts.push(Document::String("}"));
+    parts.push(Document::String(";"));
+
+    vec![Import { group: group_of(list_type), name, document: Document::Group(Group::new(parts)), blank_lines: 0 }]
+}
+
+/// Returns the group, and the first fully qualified name, of a `use` statement printed as a whole.
+fn first_import(f: &Formatter<'_>, r#use: &Use) -> (UseGroup, String) {
+    let (r#type, mut names): (Option<&UseType>, Vec<String>) = match &r#use.items {
+        UseItems::Sequence(sequence) => {
+            (None, sequence.items.iter().map(|item| name_of(f, &item.name).to_string()).collect())
+        }
+        UseItems::TypedSequence(sequence) => {
+            (Some(&sequence.r#type), sequence.items.iter().map(|item| name_of(f, &item.name).to_string()).collect())
+        }
+        UseItems::TypedList(list) => (
+            Some(&list.r#type),
+            list.items
+                .iter()
+                .map(|item| format!("{}\\{}", name_of(f, &list.namespace), name_of(f, &item.name)))
+                .collect(),
+        ),
+        UseItems::MixedList(list) => (
+            None,
+            list.items
+                .iter()
+                .map(|item| format!("{}\\{}", name_of(f, &list.namespace), name_of(f, &item.item.name)))
+                .collect(),
+        ),
+    };
+
+    if f.settings.sort_uses {
+        names.sort_by_cached_key(|name| sort_key(name));
+    }
+
+    (group_of(r#type), names.into_iter().next().unwrap_or_default())
+}
+
+fn name_of<'a>(f: &Formatter<'a>, identifier: &Identifier) -> &'a str {
+    f.lookup(&identifier.value()).trim_start_matches('\\')
+}
+
+fn group_of(r#type: Option<&UseType>) -> UseGroup {
+    match r#type {
+        None => UseGroup::Classes,
+        Some(UseType::Function(_)) => UseGroup::Functions,
+        Some(UseType::Const(_)) => UseGroup::Constants,
+    }
+}
+
+/// Sorts names alphabetically, ignoring case, and then by case, so that the order is deterministic.
+fn sort_key(name: &str) -> (String, String) {
+    (name.to_lowercase(), name.to_string())
+}
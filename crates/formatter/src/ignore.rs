@@ -0,0 +1,169 @@
+use mago_ast::Program;
+use mago_ast::Statement;
+use mago_ast::Trivia;
+use mago_span::HasSpan;
+use mago_span::Span;
+
+use crate::document::Document;
+use crate::document::Line;
+use crate::Formatter;
+
+/// A formatting directive, given as the first word of a comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Directive {
+    /// `@mago-fmt-ignore`: leave the next statement, or class-like member, unformatted.
+    Ignore,
+    /// `@mago-fmt-ignore-start`: leave the following statements, or class-like members, unformatted.
+    Start,
+    /// `@mago-fmt-ignore-end`: format the following statements, or class-like members, again.
+    End,
+    /// `@mago-fmt-ignore-file`: leave the whole file unformatted.
+    File,
+}
+
+impl Directive {
+    pub(crate) fn parse(comment: &str) -> Option<Self> {
+        let content = if let Some(content) = comment.strip_prefix("/*") {
+            content.strip_suffix("*/").unwrap_or(content).trim_start_matches('*')
+        } else {
+            comment.strip_prefix("//").or_else(|| comment.strip_prefix('#'))?
+        };
+
+        match content.split_whitespace().next()? {
+            "@mago-fmt-ignore" => Some(Self::Ignore),
+            "@mago-fmt-ignore-start" => Some(Self::Start),
+            "@mago-fmt-ignore-end" => Some(Self::End),
+            "@mago-fmt-ignore-file" => Some(Self::File),
+            _ => None,
+        }
+    }
+
+    fn of(source_text: &str, trivia: &Trivia) -> Option<Self> {
+        if !trivia.kind.is_comment() {
+            return None;
+        }
+
+        Self::parse(&source_text[trivia.span.start.offset..trivia.span.end.offset])
+    }
+}
+
+/// Returns whether the program has a `@mago-fmt-ignore-file` comment before its first statement,
+/// not counting opening tags, inline HTML, and declare statements.
+pub(crate) fn is_ignored_file(source_text: &str, program: &Program) -> bool {
+    let first_statement = program
+        .statements
+        .iter()
+        .find(|statement| !matches!(statement, Statement::OpeningTag(_) | Statement::Inline(_) | Statement::Declare(_)))
+        .map_or(usize::MAX, |statement| statement.span().start.offset);
+
+    program
+        .comments
+        .all()
+        .iter()
+        .take_while(|trivia| trivia.span.end.offset <= first_statement)
+        .any(|trivia| Directive::of(source_text, trivia) == Some(Directive::File))
+}
+
+/// Returns the offset of the last `@mago-fmt-ignore-start` comment that is not followed by a
+/// `@mago-fmt-ignore-end` comment, if any.
+pub(crate) fn find_unclosed_start(source_text: &str, program: &Program) -> Option<usize> {
+    let mut start = None;
+    for trivia in program.comments.all() {
+        match Directive::of(source_text, trivia) {
+            Some(Directive::Start) => start = start.or(Some(trivia.span.start.offset)),
+            Some(Directive::End) => start = None,
+            _ => {}
+        }
+    }
+
+    start
+}
+
+impl<'a> Formatter<'a> {
+    /// Returns whether the node with the given span is preceded by a `@mago-fmt-ignore` comment.
+    pub(crate) fn is_ignored(&self, span: Span) -> bool {
+        let mut last = None;
+        for comment in self.comments.clone() {
+            if comment.span.end.offset > span.start.offset {
+                break;
+            }
+
+            last = Some(comment);
+        }
+
+        last.is_some_and(|comment| Directive::of(self.source_text, &comment) == Some(Directive::Ignore))
+    }
+
+    /// Returns the index of the last node of the unformatted region starting at the node at the given index.
+    ///
+    /// A region starts at a node preceded by a `@mago-fmt-ignore-start` comment, and ends at the last node
+    /// before the next `@mago-fmt-ignore-end` comment, or at the end of the nodes. After an unclosed
+    /// `@mago-fmt-ignore-start` comment, every node is part of a region.
+    pub(crate) fn ignored_region<T: HasSpan>(&self, nodes: &[T], index: usize) -> Option<usize> {
+        let start = nodes[index].span().start.offset;
+        if self.ignored_from.is_some_and(|offset| offset <= start) {
+            return Some(nodes.len() - 1);
+        }
+
+        let mut comments = self.comments.clone();
+        let mut is_region = false;
+        while let Some(comment) = comments.next_if(|comment| comment.span.end.offset <= start) {
+            match Directive::of(self.source_text, &comment) {
+                Some(Directive::Start) => is_region = true,
+                Some(Directive::End) => is_region = false,
+                _ => {}
+            }
+        }
+
+        if !is_region {
+            return None;
+        }
+
+        let end = comments
+            .find(|comment| Directive::of(self.source_text, comment) == Some(Directive::End))
+            .map_or(usize::MAX, |comment| comment.span.start.offset);
+
+        Some(nodes.iter().rposition(|node| node.span().end.offset <= end).unwrap_or(index).max(index))
+    }
+
+    /// Prints the nodes from the first span to the last span as they are in the source, along with
+    /// their leading and trailing comments.
+    pub(crate) fn print_verbatim_region(&mut self, first: Span, last: Span) -> Document<'a> {
+        let leading = self.print_leading_comments(first);
+        let document = self.print_verbatim(first.join(last));
+        let trailing = self.print_trailing_comments(last);
+
+        self.print_comments(leading, document, trailing)
+    }
+
+    /// Prints the given span as it is in the source, along with the comments it contains, and the
+    /// comments following it on the same line.
+    ///
+    /// Lines after the first are printed without indentation, so that they keep their original one.
+    pub(crate) fn print_verbatim(&mut self, span: Span) -> Document<'a> {
+        let mut end = span.end.offset;
+        while let Some(comment) = self.comments.next_if(|comment| {
+            comment.span.end.offset <= end
+                || self.source_text[end..comment.span.start.offset].bytes().all(|c| c == b' ' || c == b'\t')
+        }) {
+            end = end.max(comment.span.end.offset);
+        }
+
+        let text = &self.source_text[span.start.offset..end];
+        let lines = Formatter::split_lines_with_endings(text);
+        if lines.len() <= 1 {
+            return Document::String(text);
+        }
+
+        // The original line endings are kept, along with the rest of the text.
+        let mut parts = vec![Document::BreakParent];
+        for (line, ending) in lines {
+            parts.push(Document::String(line));
+            if ending.is_some() {
+                parts.push(Document::Line(Line::literal_line_without_break_parent().with_ending(ending)));
+            }
+        }
+
+        Document::Array(parts)
+    }
+}
@@ -1,12 +1,19 @@
+use std::iter::Copied;
 use std::iter::Peekable;
-use std::vec::IntoIter;
+use std::ops::Range;
+use std::slice::Iter;
 
+use mago_ast::CommentMap;
 use mago_ast::Node;
 use mago_ast::Program;
+use mago_ast::Sequence;
 use mago_ast::Trivia;
 use mago_interner::StringIdentifier;
 use mago_interner::ThreadedInterner;
+use mago_parser::settings::ParserSettings;
 use mago_source::Source;
+use mago_source::SourceIdentifier;
+use mago_source::SourceManager;
 use mago_span::Span;
 
 use crate::document::group::GroupIdentifier;
@@ -22,9 +29,11 @@ mod binaryish;
 mod comment;
 mod document;
 mod format;
+mod ignore;
 mod macros;
 mod parens;
 mod printer;
+mod range;
 mod utils;
 
 pub fn format<'a>(
@@ -33,6 +42,64 @@ pub fn format<'a>(
     source: &'a Source,
     program: &'a Program,
 ) -> String {
+    let formatted = format_once(settings, interner, source, program);
+
+    #[cfg(feature = "debug-idempotency")]
+    {
+        // `<?` can only be an opening tag of the formatted code if there is one in the program.
+        let short_open_tags = Node::Program(program)
+            .filter_map(|node| matches!(node, Node::ShortOpeningTag(_)).then_some(()))
+            .first()
+            .is_some();
+
+        debug_assert_eq!(
+            reformat(settings, ParserSettings { short_open_tags }, interner, source, &formatted).as_ref(),
+            Some(&formatted),
+            "formatting '{}' is not idempotent",
+            interner.lookup(&source.identifier.0)
+        );
+    }
+
+    formatted
+}
+
+/// Formats the given already formatted code of the source again.
+///
+/// Formatting is idempotent when the result is the same as the given code. Returns `None` if the
+/// formatted code cannot be parsed.
+pub fn reformat(
+    settings: FormatSettings,
+    parser_settings: ParserSettings,
+    interner: &ThreadedInterner,
+    source: &Source,
+    formatted: &str,
+) -> Option<String> {
+    let manager = SourceManager::new(interner.clone());
+    let name = interner.lookup(&source.identifier.0).to_string();
+    let source = manager.load(&manager.insert_content(name, formatted.to_string(), source.identifier.1)).ok()?;
+
+    let (program, errors) = mago_parser::parse_source_with_settings(interner, &source, parser_settings);
+    if !errors.is_empty() {
+        return None;
+    }
+
+    Some(format_once(settings, interner, &source, &program))
+}
+
+fn format_once<'a>(
+    settings: FormatSettings,
+    interner: &'a ThreadedInterner,
+    source: &'a Source,
+    program: &'a Program,
+) -> String {
+    let source_text = interner.lookup(&source.content);
+    if ignore::is_ignored_file(source_text, program) {
+        return source_text.to_string();
+    }
+
+    let end_of_line = settings.end_of_line.resolve(source_text);
+    let settings = FormatSettings { end_of_line, ..settings };
+
     let mut formatter = Formatter::new(interner, source, settings);
     let document = formatter.format(program);
 
@@ -41,6 +108,73 @@ pub fn format<'a>(
     printer.build()
 }
 
+/// Formats the statements, and class-like members, covered by the given byte range of the source,
+/// leaving the rest of the source untouched.
+///
+/// The range is widened to the boundaries of the innermost statements, or class-like members, it
+/// covers, and the formatted code is re-indented to match its context. The source is returned
+/// unchanged if the range does not cover any statement.
+pub fn format_range(
+    settings: FormatSettings,
+    interner: &ThreadedInterner,
+    source: &Source,
+    program: &Program,
+    range: Range<usize>,
+) -> String {
+    range::format_range(settings, interner, source, program, range)
+}
+
+/// Prints the given node as PHP code, with the default settings, without the source it was parsed from.
+///
+/// This is meant for nodes built, or modified, programmatically, whose spans do not have to point to any
+/// source: the keywords and identifiers are printed from their values, and the punctuation is invented.
+/// As the source is not available, comments are not printed, blank lines are not preserved, and the data
+/// following `__halt_compiler();` is left out.
+///
+/// Nodes other than programs are printed as PHP code, without an opening tag.
+pub fn print_node(interner: &ThreadedInterner, node: Node<'_>) -> String {
+    print_node_with_settings(FormatSettings::default(), interner, node)
+}
+
+/// Prints the given node as PHP code, with the given settings, without the source it was parsed from.
+///
+/// See [`print_node`].
+pub fn print_node_with_settings(settings: FormatSettings, interner: &ThreadedInterner, node: Node<'_>) -> String {
+    let source = Source {
+        identifier: SourceIdentifier::dummy(),
+        path: None,
+        content: StringIdentifier::empty(),
+        size: 0,
+        lines: vec![0],
+    };
+
+    let settings = FormatSettings { end_of_line: settings.end_of_line.resolve(""), ..settings };
+
+    // The formatter expects a program at the root of the nodes it prints.
+    let program = Program {
+        source: SourceIdentifier::dummy(),
+        trivia: Sequence::empty(),
+        comments: CommentMap::default(),
+        statements: Sequence::empty(),
+    };
+
+    let mut formatter = Formatter::new(interner, &source, settings);
+    formatter.detached = true;
+
+    let document = if let Node::Program(program) = node {
+        program.format(&mut formatter)
+    } else {
+        formatter.scripting_mode = true;
+        formatter.enter_node(Node::Program(&program));
+        let document = format::node::format_node(&mut formatter, node);
+        formatter.leave_node();
+
+        document
+    };
+
+    Printer::new(document, &source, settings).build()
+}
+
 struct ArgumentState {
     expand_first_argument: bool,
     expand_last_argument: bool,
@@ -52,10 +186,13 @@ pub struct Formatter<'a> {
     source_text: &'a str,
     settings: FormatSettings,
     stack: Vec<Node<'a>>,
-    comments: Peekable<IntoIter<Trivia>>,
+    comments: Peekable<Copied<Iter<'a, Trivia>>>,
     scripting_mode: bool,
+    ignored_from: Option<usize>,
     id_builder: GroupIdentifierBuilder,
     argument_state: ArgumentState,
+    /// Whether the nodes are printed without the source they were parsed from, as with [`print_node`].
+    detached: bool,
 }
 
 impl<'a> Formatter<'a> {
@@ -66,16 +203,26 @@ impl<'a> Formatter<'a> {
             source_text: interner.lookup(&source.content),
             settings,
             stack: vec![],
-            comments: vec![].into_iter().peekable(),
+            comments: [].iter().copied().peekable(),
             scripting_mode: false,
+            ignored_from: None,
             id_builder: GroupIdentifierBuilder::new(),
             argument_state: ArgumentState { expand_first_argument: false, expand_last_argument: false },
+            detached: false,
         }
     }
 
     pub fn format(&mut self, program: &'a Program) -> Document<'a> {
-        self.comments =
-            program.trivia.iter().filter(|t| t.kind.is_comment()).copied().collect::<Vec<_>>().into_iter().peekable();
+        self.comments = program.comments.all().iter().copied().peekable();
+
+        self.ignored_from = ignore::find_unclosed_start(self.source_text, program);
+        if let Some(offset) = self.ignored_from {
+            tracing::warn!(
+                "`@mago-fmt-ignore-start` on line {} of '{}' is never closed, the rest of the file is left unformatted.",
+                self.source.line_number(offset) + 1,
+                self.interner.lookup(&self.source.identifier.0)
+            );
+        }
 
         program.format(self)
     }
@@ -92,6 +239,12 @@ impl<'a> Formatter<'a> {
         self.interner.interned_str(string)
     }
 
+    /// Returns the source text of the given span, or an empty string if it is not part of the source,
+    /// such as when the nodes are detached from their source.
+    pub(crate) fn source_slice(&self, span: Span) -> &'a str {
+        self.source_text.get(span.start.offset..span.end.offset).unwrap_or("")
+    }
+
     pub(crate) fn enter_node(&mut self, node: Node<'a>) {
         self.stack.push(node);
     }
@@ -119,7 +272,10 @@ impl<'a> Formatter<'a> {
     }
 
     fn is_previous_line_empty(&self, start_index: usize) -> bool {
-        let idx = start_index - 1;
+        let Some(idx) = start_index.checked_sub(1) else {
+            return false;
+        };
+
         let idx = self.skip_spaces(Some(idx), true);
         let idx = self.skip_newline(idx, true);
         let idx = self.skip_spaces(idx, true);
@@ -146,9 +302,38 @@ impl<'a> Formatter<'a> {
         idx.is_some_and(|idx| self.has_newline(idx, /* backwards */ false))
     }
 
+    /// Returns the number of blank lines following the line on which the given span ends.
+    pub(crate) fn blank_lines_after(&self, span: Span) -> usize {
+        let mut old_idx = None;
+        let mut idx = Some(span.end.offset);
+        while idx != old_idx {
+            old_idx = idx;
+            idx = self.skip_to_line_end(idx);
+            idx = self.skip_inline_comment(idx);
+            idx = self.skip_spaces(idx, /* backwards */ false);
+        }
+
+        idx = self.skip_trailing_comment(idx);
+        let Some(mut index) = self.skip_newline(idx, /* backwards */ false).filter(|index| Some(*index) != idx) else {
+            return 0;
+        };
+
+        let mut count = 0;
+        loop {
+            let line_end = self.skip_spaces(Some(index), /* backwards */ false);
+            match self.skip_newline(line_end, /* backwards */ false) {
+                Some(next) if Some(next) != line_end => {
+                    count += 1;
+                    index = next;
+                }
+                _ => return count,
+            }
+        }
+    }
+
     pub(crate) fn skip_trailing_comment(&self, start_index: Option<usize>) -> Option<usize> {
         let start_index = start_index?;
-        let mut bytes = self.source_text[start_index..].bytes();
+        let mut bytes = self.source_text.get(start_index..)?.bytes();
 
         match bytes.next()? {
             b'/' => {
@@ -158,7 +343,8 @@ impl<'a> Formatter<'a> {
                 }
             }
             b'#' => {
-                if let Some(b'#') = bytes.next() {
+                // `#[` starts an attribute, not a comment.
+                if let Some(b'[') = bytes.next() {
                     return Some(start_index);
                 }
             }
@@ -196,14 +382,14 @@ impl<'a> Formatter<'a> {
         let start_index = start_index?;
         let mut index = start_index;
         if backwards {
-            for c in self.source_text[..=start_index].bytes().rev() {
+            for c in self.source_text.get(..=start_index)?.bytes().rev() {
                 if !f(c) {
                     return Some(index);
                 }
                 index -= 1;
             }
         } else {
-            for c in self.source_text[start_index..].bytes() {
+            for c in self.source_text.get(start_index..)?.bytes() {
                 if !f(c) {
                     return Some(index);
                 }
@@ -218,20 +404,30 @@ impl<'a> Formatter<'a> {
     pub(crate) fn skip_newline(&self, start_index: Option<usize>, backwards: bool) -> Option<usize> {
         let start_index = start_index?;
         let c = if backwards {
-            self.source_text[..=start_index].bytes().next_back()
+            self.source_text.get(..=start_index)?.bytes().next_back()
         } else {
-            self.source_text[start_index..].bytes().next()
+            self.source_text.get(start_index..)?.bytes().next()
         }?;
 
         if matches!(c, b'\n') {
-            return Some(if backwards { start_index - 1 } else { start_index + 1 });
+            if backwards {
+                let is_crlf = start_index > 0 && self.source_text.as_bytes()[start_index - 1] == b'\r';
+
+                return Some(if is_crlf { start_index.saturating_sub(2) } else { start_index.saturating_sub(1) });
+            }
+
+            return Some(start_index + 1);
+        }
+
+        if !backwards && c == b'\r' && self.source_text.as_bytes().get(start_index + 1) == Some(&b'\n') {
+            return Some(start_index + 2);
         }
 
         Some(start_index)
     }
 
     pub(crate) fn has_newline(&self, start_index: usize, backwards: bool) -> bool {
-        if (backwards && start_index == 0) || (!backwards && start_index == self.source_text.len()) {
+        if (backwards && start_index == 0) || (!backwards && start_index >= self.source_text.len()) {
             return false;
         }
         let start_index = if backwards { start_index - 1 } else { start_index };
@@ -240,32 +436,32 @@ impl<'a> Formatter<'a> {
         idx != idx2
     }
 
-    pub(crate) fn split_lines(slice: &'a str) -> Vec<&'a str> {
+    /// Splits the given slice into lines, along with the line ending of each line, if any.
+    pub(crate) fn split_lines_with_endings(slice: &'a str) -> Vec<(&'a str, Option<&'static str>)> {
         let bytes = slice.as_bytes();
         let mut lines = Vec::new();
 
         let mut start = 0;
         let mut i = 0;
         while i < bytes.len() {
-            match bytes[i] {
-                b'\n' => {
-                    lines.push(&slice[start..i]);
-                    start = i + 1;
-                }
-                b'\r' => {
-                    lines.push(&slice[start..i]);
-                    start = i + 1;
-                    if i + 1 < bytes.len() && bytes[i + 1] == b'\n' {
-                        i += 1;
-                    }
-                }
-                _ => {}
+            let ending = match bytes[i] {
+                b'\n' => Some("\n"),
+                b'\r' if bytes.get(i + 1) == Some(&b'\n') => Some("\r\n"),
+                b'\r' => Some("\r"),
+                _ => None,
+            };
+
+            if let Some(ending) = ending {
+                lines.push((&slice[start..i], Some(ending)));
+                i += ending.len();
+                start = i;
+            } else {
+                i += 1;
             }
-            i += 1;
         }
 
         if start < bytes.len() {
-            lines.push(&slice[start..]);
+            lines.push((&slice[start..], None));
         }
 
         lines
@@ -2,6 +2,7 @@ use mago_ast::*;
 use mago_span::HasSpan;
 use mago_token::GetPrecedence;
 
+use crate::binaryish::is_parenthesized_operand;
 use crate::binaryish::should_flatten;
 use crate::document::Document;
 use crate::document::Group;
@@ -51,6 +52,10 @@ impl<'a> Formatter<'a> {
         };
 
         let parent_operator = match self.nth_parent_kind(2) {
+            Some(Node::Binary(e)) if is_parenthesized_operand(e, node.span()) => {
+                // Parentheses around operands are kept as they are, as they make the precedence explicit.
+                return true;
+            }
             Some(Node::Binary(e)) => {
                 if let BinaryOperator::NullCoalesce(_) = e.operator {
                     // Add parentheses if parent is a coalesce operator,
@@ -185,29 +190,22 @@ impl<'a> Formatter<'a> {
         }
 
         if let Node::Call(call) = self.parent_node() {
+            let object = match call {
+                Call::Function(function_call) => &function_call.function,
+                Call::Method(method_call) => &method_call.object,
+                Call::NullSafeMethod(null_safe_method_call) => &null_safe_method_call.object,
+                Call::StaticMethod(static_method_call) => &static_method_call.class,
+            };
+
+            if let Some(needs_parenthesis) = self.new_expression_needs_parenthesis(expression, object) {
+                return needs_parenthesis;
+            }
+
             if let Call::Function(_) = call {
                 return self.function_callee_expression_need_parenthesis(expression);
             }
 
-            if let Expression::Instantiation(new) = expression {
-                if new.arguments.is_none() {
-                    // parentheses are required if the instantiation has no arguments
-                    // e.g. `new Foo->baz()` should be `(new Foo)->baz()`
-                    return true;
-                }
-
-                // parentheses are not required if the instantiation has arguments
-                // e.g. `new Foo()->baz()`.
-                //
-                // but this is only allowed in PHP 8.4, so for now, we add
-                // parentheses to be safe, in the future, we can add an option
-                // to remove them.
-                //
-                // TODO(azjezz): we should add an option to remove parentheses.
-                return true;
-            } else {
-                return self.callee_expression_need_parenthesis(expression, false);
-            }
+            return self.callee_expression_need_parenthesis(expression, false);
         }
 
         if let Node::Instantiation(_) = self.parent_node() {
@@ -215,6 +213,10 @@ impl<'a> Formatter<'a> {
         }
 
         if let Node::ArrayAccess(access) = self.parent_node() {
+            if let Some(needs_parenthesis) = self.new_expression_needs_parenthesis(expression, &access.array) {
+                return needs_parenthesis && expression.span().end.offset <= access.left_bracket.start.offset;
+            }
+
             return if expression.span().end.offset < access.left_bracket.start.offset {
                 self.callee_expression_need_parenthesis(expression, false)
             } else {
@@ -223,15 +225,23 @@ impl<'a> Formatter<'a> {
         }
 
         if let Some(Node::Access(access)) = self.grandparent_node() {
-            let offset = match access {
-                Access::Property(property_access) => property_access.arrow.start.offset,
+            let (object, offset) = match access {
+                Access::Property(property_access) => (&property_access.object, property_access.arrow.start.offset),
                 Access::NullSafeProperty(null_safe_property_access) => {
-                    null_safe_property_access.question_mark_arrow.start.offset
+                    (&null_safe_property_access.object, null_safe_property_access.question_mark_arrow.start.offset)
+                }
+                Access::StaticProperty(static_property_access) => {
+                    (&static_property_access.class, static_property_access.double_colon.start.offset)
+                }
+                Access::ClassConstant(class_constant_access) => {
+                    (&class_constant_access.class, class_constant_access.double_colon.start.offset)
                 }
-                Access::StaticProperty(static_property_access) => static_property_access.double_colon.start.offset,
-                Access::ClassConstant(class_constant_access) => class_constant_access.double_colon.start.offset,
             };
 
+            if let Some(needs_parenthesis) = self.new_expression_needs_parenthesis(expression, object) {
+                return needs_parenthesis && expression.span().end.offset <= offset;
+            }
+
             return if expression.span().end.offset < offset {
                 self.callee_expression_need_parenthesis(expression, false)
             } else {
@@ -242,6 +252,22 @@ impl<'a> Formatter<'a> {
         false
     }
 
+    /// Returns whether the given expression, used as `object` in a member access, or a call, needs parentheses,
+    /// or `None` if it is not a `new` expression.
+    ///
+    /// Parentheses are always required if the class is instantiated without arguments, as `new Foo->bar()`
+    /// means `new (Foo->bar)()`. Otherwise, they are only required before PHP 8.4, so they are added if
+    /// `parenthesize_new_in_chain` is enabled, and kept as they are written if it is not.
+    fn new_expression_needs_parenthesis(&self, expression: &'a Expression, object: &'a Expression) -> Option<bool> {
+        match expression {
+            Expression::Instantiation(instantiation) if instantiation.arguments.is_none() => Some(true),
+            Expression::Instantiation(_) | Expression::AnonymousClass(_) => {
+                Some(self.settings.parenthesize_new_in_chain || matches!(object, Expression::Parenthesized(_)))
+            }
+            _ => None,
+        }
+    }
+
     const fn callee_expression_need_parenthesis(&self, expression: &'a Expression, instantiation: bool) -> bool {
         if instantiation && matches!(expression, Expression::Call(_)) {
             return true;
@@ -187,8 +187,9 @@ impl<'a> Printer<'a> {
             return;
         }
 
+        let new_line = line.ending.unwrap_or(self.new_line);
         if line.literal {
-            self.out.extend(self.new_line.as_bytes());
+            self.out.extend(new_line.as_bytes());
             if !indent.root {
                 self.position = 0;
             }
@@ -196,8 +197,11 @@ impl<'a> Printer<'a> {
             return;
         }
 
-        self.trim();
-        self.out.extend(self.new_line.as_bytes());
+        if !line.keep_trailing_whitespace {
+            self.trim();
+        }
+
+        self.out.extend(new_line.as_bytes());
         self.position = self.indent(indent.length);
     }
 
@@ -307,10 +311,13 @@ impl<'a> Printer<'a> {
         };
     }
 
+    /// Prints the indentation of the given level, returning its width in columns.
+    ///
+    /// Tabs are counted as `tab_width` columns, so that the line width is the same with tabs and spaces.
     fn indent(&mut self, size: usize) -> usize {
         if self.settings.use_tabs {
             self.out.extend("\t".repeat(size).as_bytes());
-            size
+            self.settings.tab_width * size
         } else {
             let count = self.settings.tab_width * size;
             self.out.extend(" ".repeat(count).as_bytes());
@@ -361,8 +368,8 @@ impl<'a> Printer<'a> {
                 }
                 Document::Group(group) => {
                     let mode = if group.should_break { Mode::Break } else { mode };
-                    if group.expanded_states.is_some() && mode.is_break() {
-                        queue.push_front((mode, group.expanded_states.as_ref().unwrap().last().unwrap()));
+                    if let (Some(expanded_states), true) = (&group.expanded_states, mode.is_break()) {
+                        queue.push_front((mode, expanded_states.last().unwrap()));
                     } else {
                         for d in group.contents.iter().rev() {
                             queue.push_front((mode, d));
@@ -0,0 +1,314 @@
+use std::ops::Range;
+
+use mago_ast::node::NodeKind;
+use mago_ast::Node;
+use mago_ast::Program;
+use mago_interner::ThreadedInterner;
+use mago_lexer::input::Input;
+use mago_source::Source;
+use mago_span::HasSpan;
+
+use crate::settings::FormatSettings;
+
+/// A run of consecutive sibling statements, or class-like members, covered by a range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Selection {
+    /// The indices of the children leading from the program to the parent of the selected nodes.
+    path: Vec<usize>,
+    /// The index of the first selected child.
+    first: usize,
+    /// The index of the last selected child.
+    last: usize,
+}
+
+/// The selected nodes of a program, along with their parent and the kinds of the nodes leading to them.
+struct Resolved<'a> {
+    kinds: Vec<NodeKind>,
+    parent: Node<'a>,
+    nodes: Vec<Node<'a>>,
+}
+
+pub(crate) fn format_range(
+    settings: FormatSettings,
+    interner: &ThreadedInterner,
+    source: &Source,
+    program: &Program,
+    range: Range<usize>,
+) -> String {
+    let original = interner.lookup(&source.content);
+
+    let Some(selection) = select(original, Node::Program(program), &range, &mut vec![]) else {
+        return original.to_string();
+    };
+
+    let formatted = crate::format(settings, interner, source, program);
+    let (formatted_program, errors) = mago_parser::parse(interner, Input::new(source.identifier, formatted.as_bytes()));
+    if !errors.is_empty() {
+        return original.to_string();
+    }
+
+    // The formatter keeps the statements and members of the program, but it may change the expressions
+    // containing them, e.g. by adding parentheses around a closure, in which case only the top-level
+    // statements are known to match.
+    let mut resolved = None;
+    for selection in [Some(selection), select_top_level(program, &range)].into_iter().flatten() {
+        let original_nodes = resolve(Node::Program(program), &selection);
+        let formatted_nodes = resolve(Node::Program(&formatted_program), &selection);
+
+        if let (Some(original_nodes), Some(formatted_nodes)) = (original_nodes, formatted_nodes) {
+            if original_nodes.kinds == formatted_nodes.kinds
+                && original_nodes.nodes.iter().map(Node::kind).eq(formatted_nodes.nodes.iter().map(Node::kind))
+            {
+                resolved = Some((original_nodes, formatted_nodes));
+
+                break;
+            }
+        }
+    }
+
+    let Some((original_nodes, formatted_nodes)) = resolved else {
+        return original.to_string();
+    };
+
+    let original_span = span_of(&original_nodes.nodes);
+    let formatted_span = span_of(&formatted_nodes.nodes);
+
+    let mut protected = vec![];
+    for node in &formatted_nodes.nodes {
+        collect_verbatim_spans(*node, &mut protected);
+    }
+
+    // The selected nodes are indented at the depth the formatter puts them at within their parent, using the
+    // indentation of the parent in the original source, so that their first line is re-indented as well.
+    let from = indentation(&formatted, formatted_span.start);
+    let parent_from = indentation(&formatted, formatted_nodes.parent.span().start.offset);
+    let parent_to = indentation(original, original_nodes.parent.span().start.offset);
+    let depth = from.strip_prefix(parent_from).unwrap_or_default();
+    let to = format!("{}{}", parent_to, convert_indentation(&settings, depth, parent_from, parent_to));
+
+    let region = reindent(&formatted, formatted_span.clone(), from, &to, &protected);
+
+    // The selected nodes may follow other code on their line, e.g. `{ $a = 1;`, whose indentation is kept.
+    let start = if starts_line(original, original_span.start) {
+        original_span.start - indentation(original, original_span.start).len()
+    } else {
+        original_span.start
+    };
+
+    let mut result = String::with_capacity(original.len());
+    result.push_str(&original[..start]);
+    if start != original_span.start {
+        result.push_str(&to);
+    }
+
+    result.push_str(&region);
+    result.push_str(&original[original_span.end..]);
+
+    result
+}
+
+/// Selects the innermost run of statements, or class-like members, covering the given range.
+///
+/// A single covered node is only narrowed down to its own statements or members when the range
+/// covers nothing but whitespace outside of them, so that partially covered statements are formatted whole.
+fn select(text: &str, node: Node<'_>, range: &Range<usize>, path: &mut Vec<usize>) -> Option<Selection> {
+    let children = node.children();
+    let covered: Vec<usize> = children
+        .iter()
+        .enumerate()
+        .filter(|(_, child)| is_unit(child) && overlaps(child, range))
+        .map(|(index, _)| index)
+        .collect();
+
+    match covered.as_slice() {
+        [] => {
+            for (index, child) in children.iter().enumerate() {
+                if !overlaps(child, range) {
+                    continue;
+                }
+
+                path.push(index);
+                let selection = select(text, *child, range, path);
+                path.pop();
+
+                if selection.is_some() {
+                    return selection;
+                }
+            }
+
+            None
+        }
+        [index] => {
+            path.push(*index);
+            let nested = select(text, children[*index], range, path);
+            path.pop();
+
+            if let Some(nested) = nested {
+                let nodes = resolve(
+                    children[*index],
+                    &Selection { path: nested.path[path.len() + 1..].to_vec(), ..nested.clone() },
+                );
+
+                if nodes.is_some_and(|nodes| only_whitespace_outside(text, range, span_of(&nodes.nodes))) {
+                    return Some(nested);
+                }
+            }
+
+            Some(Selection { path: path.clone(), first: *index, last: *index })
+        }
+        [first, .., last] => Some(Selection { path: path.clone(), first: *first, last: *last }),
+    }
+}
+
+/// Selects the top-level statements covering the given range.
+fn select_top_level(program: &Program, range: &Range<usize>) -> Option<Selection> {
+    let statements = Node::Program(program).children();
+    let first = statements.iter().position(|statement| overlaps(statement, range))?;
+    let last = statements.iter().rposition(|statement| overlaps(statement, range))?;
+
+    Some(Selection { path: vec![], first, last })
+}
+
+/// Returns the nodes selected in the given root, or `None` if the selection does not exist in it.
+fn resolve<'a>(root: Node<'a>, selection: &Selection) -> Option<Resolved<'a>> {
+    let mut kinds = vec![root.kind()];
+    let mut parent = root;
+    for index in &selection.path {
+        parent = *parent.children().get(*index)?;
+        kinds.push(parent.kind());
+    }
+
+    let nodes = parent.children().get(selection.first..=selection.last)?.to_vec();
+
+    Some(Resolved { kinds, parent, nodes })
+}
+
+fn is_unit(node: &Node<'_>) -> bool {
+    matches!(node.kind(), NodeKind::Statement | NodeKind::ClassLikeMember)
+}
+
+fn overlaps(node: &Node<'_>, range: &Range<usize>) -> bool {
+    let span = node.span();
+
+    if range.is_empty() {
+        span.start.offset < range.start && range.start < span.end.offset
+    } else {
+        span.start.offset < range.end && range.start < span.end.offset
+    }
+}
+
+fn span_of(nodes: &[Node<'_>]) -> Range<usize> {
+    match (nodes.first(), nodes.last()) {
+        (Some(first), Some(last)) => first.span().start.offset..last.span().end.offset,
+        _ => 0..0,
+    }
+}
+
+fn only_whitespace_outside(text: &str, range: &Range<usize>, span: Range<usize>) -> bool {
+    let before = if range.start < span.start { &text[range.start..span.start] } else { "" };
+    let after = if span.end < range.end { &text[span.end..range.end.min(text.len())] } else { "" };
+
+    before.trim().is_empty() && after.trim().is_empty()
+}
+
+/// Collects the spans of the nodes whose content must not be re-indented, such as strings and inline HTML.
+fn collect_verbatim_spans(node: Node<'_>, spans: &mut Vec<Range<usize>>) {
+    if matches!(node.kind(), NodeKind::LiteralString | NodeKind::CompositeString | NodeKind::Inline) {
+        let span = node.span();
+
+        spans.push(span.start.offset..span.end.offset);
+
+        return;
+    }
+
+    for child in node.children() {
+        collect_verbatim_spans(child, spans);
+    }
+}
+
+/// Returns whether only whitespace precedes the given offset on its line.
+fn starts_line(text: &str, offset: usize) -> bool {
+    let line_start = text[..offset].rfind(['\n', '\r']).map_or(0, |index| index + 1);
+
+    text[line_start..offset].trim_start_matches([' ', '\t']).is_empty()
+}
+
+/// Returns the leading whitespace of the line containing the given offset.
+fn indentation(text: &str, offset: usize) -> &str {
+    let line_start = text[..offset].rfind(['\n', '\r']).map_or(0, |index| index + 1);
+    let line = &text[line_start..];
+
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Converts the given indentation, relative to a parent, from the indentation of the formatter to the one of the
+/// original source, whose unit is inferred from the indentation of the parent in both.
+///
+/// The indentation is kept as is if the unit of the original source cannot be inferred, e.g. for a top-level
+/// parent, or if it is not made of whole units.
+fn convert_indentation(settings: &FormatSettings, indentation: &str, parent_from: &str, parent_to: &str) -> String {
+    let unit = if settings.use_tabs { "\t".to_string() } else { " ".repeat(settings.tab_width) };
+    if unit.is_empty() || indentation.len() % unit.len() != 0 || parent_from.len() % unit.len() != 0 {
+        return indentation.to_string();
+    }
+
+    let parent_depth = parent_from.len() / unit.len();
+    if parent_depth == 0 || parent_to.is_empty() || parent_to.len() % parent_depth != 0 {
+        return indentation.to_string();
+    }
+
+    parent_to[..parent_to.len() / parent_depth].repeat(indentation.len() / unit.len())
+}
+
+/// Returns the given region of the text, with the indentation of every line after the first
+/// replaced, except for the lines starting inside the protected spans.
+fn reindent(text: &str, region: Range<usize>, from: &str, to: &str, protected: &[Range<usize>]) -> String {
+    if from == to {
+        return text[region].to_string();
+    }
+
+    let mut result = String::with_capacity(region.len());
+    let mut line_start = region.start;
+    let bytes = text.as_bytes();
+    for index in region.clone() {
+        let is_line_end = match bytes[index] {
+            b'\n' => true,
+            b'\r' => bytes.get(index + 1) != Some(&b'\n'),
+            _ => false,
+        };
+
+        if is_line_end {
+            push_line(&mut result, text, line_start..index + 1, region.start, from, to, protected);
+            line_start = index + 1;
+        }
+    }
+
+    push_line(&mut result, text, line_start..region.end, region.start, from, to, protected);
+
+    result
+}
+
+fn push_line(
+    result: &mut String,
+    text: &str,
+    line: Range<usize>,
+    region_start: usize,
+    from: &str,
+    to: &str,
+    protected: &[Range<usize>],
+) {
+    let is_protected = protected.iter().any(|span| span.start < line.start && line.start < span.end);
+    let content = &text[line.clone()];
+
+    match content.strip_prefix(from) {
+        Some(rest) if line.start != region_start && !is_protected => {
+            // Empty lines are kept without indentation.
+            if !rest.trim().is_empty() {
+                result.push_str(to);
+            }
+
+            result.push_str(rest);
+        }
+        _ => result.push_str(content),
+    }
+}
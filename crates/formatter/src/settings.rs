@@ -26,33 +26,43 @@ pub struct FormatSettings {
 
     /// Specify which end-of-line characters to use.
     ///
-    /// Default: "lf"
+    /// When set to "auto", the most common line ending of the source is used.
+    ///
+    /// Default: "auto"
     #[serde(default)]
     pub end_of_line: EndOfLine,
 
-    /// Use single quotes instead of double quotes for strings.
+    /// Specify which quotes to use for strings.
     ///
-    /// The formatter will automatically determine whether to use single or double quotes based on the content of the string,
-    /// with a preference for single quotes if this option is enabled.
+    /// Strings are only converted to the preferred quotes when they contain more of the other quotes, and when
+    /// their content does not change, e.g. double-quoted strings with escape sequences such as `\n` are kept.
     ///
-    /// If the string contains more single quotes than double quotes, the formatter will use double quotes.
-    /// If the string contains more double quotes than single quotes, the formatter will use single quotes.
+    /// Default: "single"
+    #[serde(default)]
+    pub string_quotes: StringQuotes,
+
+    /// Whether to re-indent heredoc and nowdoc strings to the indentation of the code containing them.
     ///
-    /// If the string contains an equal number of single and double quotes, the formatter will use single quotes
-    /// if this option is enabled, and double quotes otherwise.
+    /// The closing marker is moved to the current indentation, and every line of the body is moved
+    /// along with it, so that the value of the string does not change. When disabled, heredoc and
+    /// nowdoc strings are printed exactly as they are in the source.
     ///
-    /// Default: true
-    #[serde(default = "default_true")]
-    pub single_quote: bool,
+    /// Default: false
+    #[serde(default = "default_false")]
+    pub normalize_heredoc_indent: bool,
 
-    /// Enable or disable trailing commas in multi-line syntactic structures.
+    /// Specify where to add trailing commas in multi-line syntactic structures.
     ///
-    /// When enabled, the formatter will add a trailing comma to the last element in a multi-line list, array,
-    /// parameter list, argument list, and other syntactic structures.
+    /// When set to "all", the formatter will add a trailing comma to the last element in every multi-line list,
+    /// including argument lists, which requires PHP 7.3, and parameter lists and closure `use` clauses, which
+    /// require PHP 8.0. When set to "multiline", trailing commas are only added to multi-line arrays, attribute
+    /// lists, and match expressions. When set to "never", trailing commas are removed.
     ///
-    /// Default: true
-    #[serde(default = "default_true")]
-    pub trailing_comma: bool,
+    /// For compatibility, `true` and `false` are accepted as "all" and "never".
+    ///
+    /// Default: "all"
+    #[serde(default)]
+    pub trailing_comma: TrailingComma,
 
     /// Add spaces around the `=` in declare statements.
     ///
@@ -98,9 +108,50 @@ pub struct FormatSettings {
     #[serde(default)]
     pub keyword_case: CasingStyle,
 
-    /// Blank line after the opening PHP tag.
+    /// Which syntax to use for array literals.
+    ///
+    /// `list()` destructuring is never converted, and neither are short arrays used for destructuring,
+    /// such as `[$a, $b] = $pair;`, as `array()` can not be used there.
+    ///
+    /// Note: the short array syntax requires PHP 5.4 or later.
+    ///
+    /// Example:
+    ///
+    /// ```php
+    /// // short
+    /// $foo = [1, 2, 3];
+    ///
+    /// // long
+    /// $foo = array(1, 2, 3);
+    /// ```
+    ///
+    /// Default: preserve
+    #[serde(default)]
+    pub array_syntax: ArraySyntax,
+
+    /// Which opening tag to use for blocks that only echo a single expression.
+    ///
+    /// Blocks containing comments are never converted.
+    ///
+    /// Note: `<?=` is always available as of PHP 5.4, regardless of the `short_open_tag` ini setting.
+    ///
+    /// Example:
+    ///
+    /// ```php
+    /// // short
+    /// <p><?= $foo ?></p>
+    ///
+    /// // long
+    /// <p><?php echo $foo; ?></p>
+    /// ```
+    ///
+    /// Default: preserve
+    #[serde(default)]
+    pub echo_tag: EchoTag,
+
+    /// The number of blank lines after the opening PHP tag.
     ///
-    /// When enabled, the formatter will add a blank line after the opening PHP tag.
+    /// Only applies to files that start with an opening tag, and contain no inline HTML.
     ///
     /// Example:
     ///
@@ -110,9 +161,35 @@ pub struct FormatSettings {
     /// echo 'Hello, world!';
     /// ```
     ///
-    /// Default: true
-    #[serde(default = "default_true")]
-    pub blank_line_after_open_tag: bool,
+    /// Default: 1
+    #[serde(default = "default_one")]
+    pub blank_lines_after_opening_tag: usize,
+
+    /// The number of blank lines between class-like members.
+    ///
+    /// Consecutive properties, constants, enum cases, and trait uses that each fit on a single line, and
+    /// are not separated by a blank line or a comment, are kept together as a group.
+    ///
+    /// Default: 1
+    #[serde(default = "default_one")]
+    pub blank_lines_between_class_members: usize,
+
+    /// The number of blank lines before a `return` statement that follows another statement.
+    ///
+    /// When not set, the blank lines before `return` statements are kept, as for any other statement.
+    ///
+    /// Default: not set
+    #[serde(default)]
+    pub blank_lines_before_return: Option<usize>,
+
+    /// The maximum number of consecutive blank lines to keep.
+    ///
+    /// When not set, at most 2 consecutive blank lines are kept at the top level of a file, or of a namespace,
+    /// and at most 1 inside of bodies. Blank lines added by the other blank line settings are not limited.
+    ///
+    /// Default: not set
+    #[serde(default)]
+    pub max_consecutive_blank_lines: Option<usize>,
 
     /// In a control structure expression, is there a space after the opening parenthesis
     ///  and a space before the closing parenthesis?
@@ -322,6 +399,29 @@ pub struct FormatSettings {
     #[serde(default = "default_true")]
     pub space_before_arrow_function_params: bool,
 
+    /// Whether to move an arrow function that is the last argument of a call onto its own line when it
+    /// does not fit, so that it is kept as a single expression, instead of breaking after its `=>`.
+    ///
+    /// This only affects the layout of arrow functions, closures are never converted to arrow functions.
+    ///
+    /// Example:
+    ///
+    /// ```php
+    /// // false
+    /// $names = $users->map(fn($user) =>
+    ///     $user->getProfile()->getDisplayName(),
+    /// );
+    ///
+    /// // true
+    /// $names = $users->map(
+    ///     fn($user) => $user->getProfile()->getDisplayName(),
+    /// );
+    /// ```
+    ///
+    /// Default: false
+    #[serde(default = "default_false")]
+    pub prefer_arrow_fn_single_expression: bool,
+
     /// Whether to put the `static` keyword before the visibility keyword.
     ///
     /// Example:
@@ -342,25 +442,37 @@ pub struct FormatSettings {
     #[serde(default = "default_false")]
     pub static_before_visibility: bool,
 
-    /// Which style to use for null type hints.
+    /// How to write nullable types, in parameter, property, and return types.
+    ///
+    /// `?Foo` is written as `Foo|null` with `union_null`, and `Foo|null` is written as `?Foo` with
+    /// `question_mark`. Unions of more than two types, and types that cannot be nullable with `?`,
+    /// such as `(A&B)|null`, are always written as unions.
     ///
     /// Example:
     ///
     /// ```php
+    /// // question_mark
     /// function foo(?string $bar) {
     ///   return $bar;
     /// }
     ///
-    /// // or
-    ///
-    /// function foo(null|string $bar) {
+    /// // union_null
+    /// function foo(string|null $bar) {
     ///   return $bar;
     /// }
     /// ```
     ///
-    /// Default: NullPipe
-    #[serde(default)]
-    pub null_type_hint: NullTypeHint,
+    /// Default: preserve
+    #[serde(default, alias = "null_type_hint")]
+    pub nullable_type_style: NullableTypeStyle,
+
+    /// Whether to sort the members of union types alphabetically, ignoring case, with `null` last.
+    ///
+    /// The members of union types are kept in their original order otherwise.
+    ///
+    /// Default: false
+    #[serde(default = "default_false")]
+    pub sort_union_types: bool,
 
     /// How many spaces to add around binary operators.
     ///
@@ -402,13 +514,24 @@ pub struct FormatSettings {
     #[serde(default = "default_type_spacing")]
     pub type_spacing: usize,
 
-    /// The minimum length of a method call chain that triggers line-breaking formatting.
+    /// The maximum number of calls in a method call chain that is kept on a single line.
     ///
-    /// When the number of chained method calls exceeds this threshold, the formatter will break the chain into multiple lines.
+    /// When the number of chained method calls exceeds this threshold, the formatter will break the chain into multiple
+    /// lines, with one call per line. Shorter chains are only broken when they do not fit within the print width.
     ///
-    /// Default: 4
-    #[serde(default = "default_method_chain_break_threshold")]
-    pub method_chain_break_threshold: usize,
+    /// Default: 3
+    #[serde(default = "default_method_chain_breaking_threshold")]
+    pub method_chain_breaking_threshold: usize,
+
+    /// Whether to wrap `new` expressions in parentheses when a member is accessed, or a method is called, on them.
+    ///
+    /// Accessing a member of a `new` expression without parentheses, such as `new Foo()->bar()`, requires PHP 8.4
+    /// or later. When disabled, the parentheses are kept as they are written, and are only added when required,
+    /// such as in `(new Foo)->bar()`.
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub parenthesize_new_in_chain: bool,
 
     /// Whether to break a parameter list into multiple lines if it contains one or more promoted property even if it fits into a single line.
     ///
@@ -461,6 +584,26 @@ pub struct FormatSettings {
     #[serde(default = "default_true")]
     pub preserve_multiline_binary_operations: bool,
 
+    /// Where to place binary operators when a binary operation is broken into multiple lines.
+    ///
+    /// Applies to logical, comparison, null coalescing, and concatenation operators, among others.
+    ///
+    /// Example:
+    ///
+    /// ```php
+    /// // trailing
+    /// $foo = $first_condition &&
+    ///     $second_condition;
+    ///
+    /// // leading
+    /// $foo = $first_condition
+    ///     && $second_condition;
+    /// ```
+    ///
+    /// Default: trailing
+    #[serde(default)]
+    pub operator_position: OperatorPosition,
+
     /// How to format broken method/property chains.
     ///
     /// When breaking a method or property chain, this option determines whether the
@@ -482,6 +625,64 @@ pub struct FormatSettings {
     /// Default: NextLine
     #[serde(default)]
     pub method_chain_breaking_style: MethodChainBreakingStyle,
+
+    /// Whether to sort the `use` statements at the top of a file, or of a namespace.
+    ///
+    /// Imports are sorted alphabetically, ignoring case, by their fully qualified name, and grouped
+    /// by kind in the order given by `use_groups`. The names inside of grouped `use` statements,
+    /// such as `use Foo\{Bar, Baz};`, are sorted too.
+    ///
+    /// Comments on the line of a `use` statement, or on the lines before it, are moved along with it.
+    ///
+    /// Default: false
+    #[serde(default = "default_false")]
+    pub sort_uses: bool,
+
+    /// The order of the groups of sorted `use` statements.
+    ///
+    /// Default: [Classes, Functions, Constants]
+    #[serde(default = "default_use_groups")]
+    pub use_groups: [UseGroup; 3],
+
+    /// Whether to separate the groups of sorted `use` statements with a blank line.
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub separate_use_groups: bool,
+
+    /// Whether to expand grouped `use` statements into one statement per name.
+    ///
+    /// Example:
+    ///
+    /// ```php
+    /// use Foo\{Bar, Baz};
+    ///
+    /// // becomes
+    ///
+    /// use Foo\Bar;
+    /// use Foo\Baz;
+    /// ```
+    ///
+    /// Default: false
+    #[serde(default = "default_false")]
+    pub expand_use_groups: bool,
+
+    /// Whether to split attribute lists containing several attributes into one list per attribute.
+    ///
+    /// Example:
+    ///
+    /// ```php
+    /// #[Foo, Bar]
+    ///
+    /// // becomes
+    ///
+    /// #[Foo]
+    /// #[Bar]
+    /// ```
+    ///
+    /// Default: false
+    #[serde(default = "default_false")]
+    pub separate_attribute_groups: bool,
 }
 
 impl Default for FormatSettings {
@@ -492,11 +693,17 @@ impl Default for FormatSettings {
             tab_width: default_tab_width(),
             use_tabs: false,
             end_of_line: EndOfLine::default(),
-            single_quote: true,
-            trailing_comma: true,
+            string_quotes: StringQuotes::default(),
+            normalize_heredoc_indent: false,
+            trailing_comma: TrailingComma::default(),
             space_around_declare_equals: false,
             keyword_case: CasingStyle::default(),
-            blank_line_after_open_tag: true,
+            array_syntax: ArraySyntax::default(),
+            echo_tag: EchoTag::default(),
+            blank_lines_after_opening_tag: default_one(),
+            blank_lines_between_class_members: default_one(),
+            blank_lines_before_return: None,
+            max_consecutive_blank_lines: None,
             control_space_parens: false,
             closure_brace_style: BraceStyle::SameLine,
             function_brace_style: BraceStyle::NextLine,
@@ -506,15 +713,24 @@ impl Default for FormatSettings {
             space_before_closure_params: true,
             space_after_closure_use: true,
             space_before_arrow_function_params: false,
+            prefer_arrow_fn_single_expression: false,
             static_before_visibility: false,
-            null_type_hint: NullTypeHint::default(),
+            nullable_type_style: NullableTypeStyle::default(),
+            sort_union_types: false,
             binary_op_spacing: default_binary_op_spacing(),
             type_spacing: default_type_spacing(),
-            method_chain_break_threshold: default_method_chain_break_threshold(),
+            method_chain_breaking_threshold: default_method_chain_breaking_threshold(),
+            parenthesize_new_in_chain: true,
             break_promoted_properties_list: true,
             space_concatenation: true,
             preserve_multiline_binary_operations: true,
+            operator_position: OperatorPosition::default(),
             method_chain_breaking_style: MethodChainBreakingStyle::NextLine,
+            sort_uses: false,
+            use_groups: default_use_groups(),
+            separate_use_groups: true,
+            expand_use_groups: false,
+            separate_attribute_groups: false,
         }
     }
 }
@@ -533,6 +749,27 @@ pub enum EndOfLine {
     Cr,
 }
 
+/// Specifies which quotes to use for strings.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum StringQuotes {
+    #[default]
+    #[serde(alias = "single")]
+    Single,
+    #[serde(alias = "double")]
+    Double,
+    #[serde(alias = "preserve")]
+    Preserve,
+}
+
+/// Specifies where to add trailing commas.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, PartialOrd, Ord)]
+pub enum TrailingComma {
+    #[default]
+    All,
+    Multiline,
+    Never,
+}
+
 /// Specifies the style of line endings.
 #[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 pub enum CasingStyle {
@@ -543,12 +780,40 @@ pub enum CasingStyle {
     Uppercase,
 }
 
+/// Specifies the syntax of array literals.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum ArraySyntax {
+    /// `[1, 2, 3]`
+    #[serde(alias = "short")]
+    Short,
+    /// `array(1, 2, 3)`
+    #[serde(alias = "long")]
+    Long,
+    #[default]
+    #[serde(alias = "preserve")]
+    Preserve,
+}
+
+/// Specifies the opening tag of blocks echoing a single expression.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum EchoTag {
+    /// `<?= $foo ?>`
+    #[serde(alias = "short")]
+    Short,
+    /// `<?php echo $foo; ?>`
+    #[serde(alias = "long")]
+    Long,
+    #[default]
+    #[serde(alias = "preserve")]
+    Preserve,
+}
+
 /// Specifies the style of line endings.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 pub enum BraceStyle {
-    #[serde(alias = "same")]
+    #[serde(alias = "same", alias = "same_line")]
     SameLine,
-    #[serde(alias = "next")]
+    #[serde(alias = "next", alias = "next_line")]
     NextLine,
 }
 
@@ -561,6 +826,43 @@ pub enum MethodChainBreakingStyle {
     NextLine,
 }
 
+/// Specifies where binary operators are placed when a binary operation is broken.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum OperatorPosition {
+    /// At the start of the continuation line.
+    #[serde(alias = "leading")]
+    Leading,
+    /// At the end of the broken line.
+    #[default]
+    #[serde(alias = "trailing")]
+    Trailing,
+}
+
+/// Specifies a kind of imported names.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum UseGroup {
+    #[serde(alias = "classes", alias = "class")]
+    Classes,
+    #[serde(alias = "functions", alias = "function")]
+    Functions,
+    #[serde(alias = "constants", alias = "constant", alias = "const")]
+    Constants,
+}
+
+impl UseGroup {
+    /// Returns the order of the given groups, followed by the missing groups in their default order.
+    pub fn order(groups: &[UseGroup]) -> [UseGroup; 3] {
+        let mut order = Vec::with_capacity(3);
+        for group in groups.iter().chain(default_use_groups().iter()) {
+            if !order.contains(group) {
+                order.push(*group);
+            }
+        }
+
+        [order[0], order[1], order[2]]
+    }
+}
+
 impl BraceStyle {
     pub fn same_line() -> Self {
         Self::SameLine
@@ -584,6 +886,34 @@ impl MethodChainBreakingStyle {
 }
 
 impl EndOfLine {
+    /// Resolves `Auto` into the most common line ending of the given text, or `Lf` if there is none.
+    ///
+    /// Ties are resolved in favor of `Lf`, then `Crlf`.
+    pub fn resolve(self, text: &str) -> Self {
+        if self != Self::Auto {
+            return self;
+        }
+
+        let (mut lf, mut crlf, mut cr) = (0, 0, 0);
+        let mut bytes = text.bytes().peekable();
+        while let Some(byte) = bytes.next() {
+            match byte {
+                b'\n' => lf += 1,
+                b'\r' if bytes.next_if_eq(&b'\n').is_some() => crlf += 1,
+                b'\r' => cr += 1,
+                _ => {}
+            }
+        }
+
+        if cr > lf && cr > crlf {
+            Self::Cr
+        } else if crlf > lf {
+            Self::Crlf
+        } else {
+            Self::Lf
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Crlf => "\r\n",
@@ -607,22 +937,71 @@ impl FromStr for EndOfLine {
     }
 }
 
-/// Specifies null type hint style.
-#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
-pub enum NullTypeHint {
-    #[default]
-    #[serde(alias = "null_pipe", alias = "pipe", alias = "long", alias = "|")]
-    NullPipe,
-    #[serde(alias = "question", alias = "short", alias = "?")]
-    Question,
+impl TrailingComma {
+    /// Whether to add trailing commas to multi-line arrays, attribute lists, and match expressions.
+    #[inline]
+    pub fn for_arrays(&self) -> bool {
+        *self != Self::Never
+    }
+
+    /// Whether to add trailing commas to multi-line argument lists, parameter lists, and closure `use` clauses.
+    #[inline]
+    pub fn for_arguments(&self) -> bool {
+        *self == Self::All
+    }
 }
 
-impl NullTypeHint {
-    pub fn is_question(&self) -> bool {
-        *self == Self::Question
+impl FromStr for TrailingComma {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "all" | "true" => Ok(Self::All),
+            "multiline" => Ok(Self::Multiline),
+            "never" | "false" => Ok(Self::Never),
+            _ => Err(()),
+        }
     }
 }
 
+impl<'de> Deserialize<'de> for TrailingComma {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = TrailingComma;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("one of \"all\", \"multiline\", or \"never\", or a boolean")
+            }
+
+            fn visit_bool<E: serde::de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(if value { TrailingComma::All } else { TrailingComma::Never })
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(|_| E::unknown_variant(value, &["all", "multiline", "never"]))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// Specifies how nullable types are written.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum NullableTypeStyle {
+    /// `?Foo`
+    #[serde(alias = "question_mark", alias = "question", alias = "short", alias = "?")]
+    QuestionMark,
+    /// `Foo|null`
+    #[serde(alias = "union_null", alias = "null_pipe", alias = "pipe", alias = "long", alias = "|")]
+    UnionNull,
+    #[default]
+    #[serde(alias = "preserve")]
+    Preserve,
+}
+
 fn default_print_width() -> usize {
     120
 }
@@ -639,8 +1018,16 @@ fn default_type_spacing() -> usize {
     0
 }
 
-fn default_method_chain_break_threshold() -> usize {
-    4
+fn default_one() -> usize {
+    1
+}
+
+fn default_method_chain_breaking_threshold() -> usize {
+    3
+}
+
+fn default_use_groups() -> [UseGroup; 3] {
+    [UseGroup::Classes, UseGroup::Functions, UseGroup::Constants]
 }
 
 fn default_false() -> bool {
@@ -0,0 +1,77 @@
+<?php
+
+declare(strict_types=1); // strict
+
+namespace App; # namespace
+
+// imports
+use Foo\Bar; // bar
+use Foo\{Baz /* baz */, Qux};
+
+/**
+ * A documented function.
+ *
+ * @param int $a
+ */
+function foo(/* no type */ $a, int $b /* typed */): int // returns int
+{
+    // leading
+    $c = $a + /* plus */ $b; // trailing
+
+    /* before return */
+    return $c;
+    // dangling at the end of the body
+}
+
+/** @var list<int> $list */
+$list = [
+    1, // one
+    2,
+    /* three */ 3,
+    // nothing after
+];
+
+#[Attribute] // attribute
+final class Foo extends Bar /* parent */ implements Baz
+{
+    // constants
+    public const int A = 1; // a
+
+    /**
+     * The property.
+     */
+    private ?string $property = null;
+
+    public function __construct(
+        /** promoted */
+        private readonly int $value, // value
+    ) {}
+
+    public function method(): void
+    {
+        if ($this->value > 1) { // condition
+            echo 'yes';
+        } else {
+            // empty
+        }
+
+        $result = $this->first() // first
+            ->second() /* second */
+            ->third();
+
+        match ($result) {
+            1 => 'a', // a
+            /* b */ 2 => 'b',
+            default => 'c',
+        };
+    }
+
+    // dangling member comment
+}
+
+$closure = function () use ($list) /* use */ {
+    # hash comment
+};
+
+foo(1, /* argument */ 2);
+/* the end */
@@ -0,0 +1,23 @@
+<?php
+
+function render(array $items, string $title): string
+{
+    $html = <<<HTML
+    <div class="list" data-title="{$title}">
+      <h1>{$items['title']} &amp; "more"</h1>   
+
+    	<p>{$this->escape("it's")}</p>
+       ${legacy}
+    </div>
+    HTML;
+
+    $sql = <<<'SQL'
+    SELECT "id", 'name'
+      FROM users   
+     WHERE id = $id
+    SQL;
+
+    return sprintf(<<<EOT
+        Hello, %s!
+        EOT, $title) . $html . $sql;
+}
@@ -0,0 +1,29 @@
+<?php
+
+abstract class User
+{
+    public string $name {
+        get => strtoupper($this->name);
+        set {
+            $this->name = $value;
+        }
+    }
+
+    public string $fullName {
+        get {
+            return $this->first . ' ' . $this->last;
+        }
+    }
+
+    public string $email { set(string $value) => strtolower($value); }
+
+    public array $items { &get => $this->items; }
+
+    abstract public string $id { get; }
+
+    public string $slug = 'default' {
+        final set(string $value) {
+            $this->slug = strtolower($value);
+        }
+    }
+}
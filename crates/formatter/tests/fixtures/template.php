@@ -0,0 +1,22 @@
+<?php
+$title = 'Hello';
+?>
+<!DOCTYPE html>
+<html>
+  <head>
+    <title><?= $title ?></title>   
+  </head>
+	<body>
+<?php
+foreach ($items as $item):
+    ?>
+      <li class="<?= $item['class'] ?>">{{ $item->name }}</li>
+<?php
+endforeach;
+?>
+
+    @if ($user)
+        <p>Hello, {{ $user->name }}</p>
+    @endif
+  </body>
+</html>
@@ -0,0 +1,29 @@
+<?php
+
+class Example
+{
+    public ?Foo $nullable;
+    public null|Foo $unionNull;
+    public Foo|null $unionNullLast;
+    public (A&B)|null $dnf;
+    public int|string|null $union;
+
+    public function __construct(
+        private ?Foo $nullable,
+        protected readonly Foo|null $union,
+        public (A&B)|C|null $dnf,
+    ) {
+    }
+
+    public function parameters(?Foo $nullable, null|Foo $union, Countable&Traversable $intersection): void
+    {
+    }
+
+    public function nullable(): ?static
+    {
+    }
+
+    public function union(): null|false|string
+    {
+    }
+}
@@ -0,0 +1,95 @@
+use indoc::indoc;
+
+use mago_formatter::settings::ArraySyntax;
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+const CODE: &str = indoc! {r#"
+    <?php
+
+    function foo($a = array(1, array('x' => 2)), $b = []) {}
+
+    $c = array(
+        // first
+        1,
+        2, // second
+    );
+    list($d, $e) = array(1, 2);
+    [$f, [$g, $h]] = [3, [4, 5]];
+    ['k' => $i] = ['k' => 6];
+    foreach ($items as $key => [$j, $k]) {}
+"#};
+
+#[test]
+pub fn test_array_syntax_preserve_keeps_both_syntaxes() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        function foo($a = array(1, array('x' => 2)), $b = [])
+        {
+        }
+
+        $c = array(
+            // first
+            1,
+            2, // second
+        );
+        list($d, $e) = array(1, 2);
+        [$f, [$g, $h]] = [3, [4, 5]];
+        ['k' => $i] = ['k' => 6];
+        foreach ($items as $key => [$j, $k]) {
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_array_syntax_short_converts_long_arrays() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        function foo($a = [1, ['x' => 2]], $b = [])
+        {
+        }
+
+        $c = [
+            // first
+            1,
+            2, // second
+        ];
+        list($d, $e) = [1, 2];
+        [$f, [$g, $h]] = [3, [4, 5]];
+        ['k' => $i] = ['k' => 6];
+        foreach ($items as $key => [$j, $k]) {
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings { array_syntax: ArraySyntax::Short, ..Default::default() })
+}
+
+#[test]
+pub fn test_array_syntax_long_converts_short_arrays_except_destructuring() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        function foo($a = array(1, array('x' => 2)), $b = array())
+        {
+        }
+
+        $c = array(
+            // first
+            1,
+            2, // second
+        );
+        list($d, $e) = array(1, 2);
+        [$f, [$g, $h]] = array(3, array(4, 5));
+        ['k' => $i] = array('k' => 6);
+        foreach ($items as $key => [$j, $k]) {
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings { array_syntax: ArraySyntax::Long, ..Default::default() })
+}
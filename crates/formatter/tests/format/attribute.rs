@@ -0,0 +1,132 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+const CODE: &str = indoc! {r#"
+    <?php
+
+    #[Attribute(Attribute::TARGET_CLASS)] #[Another]
+    final class Foo {
+        #[Inject, Named('foo')] public $a;
+        #[Route('/path', methods: ['GET', 'POST'], name: 'some_route_name_that_is_long', requirements: ['id' => '\d+'], priority: 10)]
+        public function bar(#[SensitiveParameter] $password, #[Foo, Bar] int $x) {
+            $c = #[Pure] function () {};
+            $d = #[Pure] fn() => 1;
+        }
+        public function __construct(#[Inject] private readonly Service $service, #[Autowire(service: 'some.very.long.service.identifier')] private readonly Other $other) {}
+        #[VeryLongAttributeNameNumberOne('argument one'), VeryLongAttributeNameNumberTwo('argument two'), Three('argument three')]
+        public const X = 1;
+    }
+"#};
+
+#[test]
+pub fn test_attribute_placement() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        #[Attribute(Attribute::TARGET_CLASS)]
+        #[Another]
+        final class Foo
+        {
+            #[Inject, Named('foo')]
+            public $a;
+
+            #[Route(
+                '/path',
+                methods: ['GET', 'POST'],
+                name: 'some_route_name_that_is_long',
+                requirements: ['id' => '\d+'],
+                priority: 10,
+            )]
+            public function bar(#[SensitiveParameter] $password, #[Foo, Bar] int $x)
+            {
+                $c = #[Pure] function () {};
+                $d = #[Pure] fn() => 1;
+            }
+
+            public function __construct(
+                #[Inject] private readonly Service $service,
+                #[Autowire(service: 'some.very.long.service.identifier')] private readonly Other $other,
+            ) {
+            }
+
+            #[
+                VeryLongAttributeNameNumberOne('argument one'),
+                VeryLongAttributeNameNumberTwo('argument two'),
+                Three('argument three'),
+            ]
+            public const X = 1;
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_separate_attribute_groups() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        #[Attribute(Attribute::TARGET_CLASS)]
+        #[Another]
+        final class Foo
+        {
+            #[Inject]
+            #[Named('foo')]
+            public $a;
+
+            #[Route(
+                '/path',
+                methods: ['GET', 'POST'],
+                name: 'some_route_name_that_is_long',
+                requirements: ['id' => '\d+'],
+                priority: 10,
+            )]
+            public function bar(#[SensitiveParameter] $password, #[Foo] #[Bar] int $x)
+            {
+                $c = #[Pure] function () {};
+                $d = #[Pure] fn() => 1;
+            }
+
+            public function __construct(
+                #[Inject] private readonly Service $service,
+                #[Autowire(service: 'some.very.long.service.identifier')] private readonly Other $other,
+            ) {
+            }
+
+            #[VeryLongAttributeNameNumberOne('argument one')]
+            #[VeryLongAttributeNameNumberTwo('argument two')]
+            #[Three('argument three')]
+            public const X = 1;
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings { separate_attribute_groups: true, ..Default::default() })
+}
+
+#[test]
+pub fn test_separate_attribute_groups_keeps_comments() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        // Leading.
+        #[A, B(1)] // Trailing.
+        final class Foo {}
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        // Leading.
+        #[A]
+        #[B(1)] // Trailing.
+        final class Foo
+        {
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings { separate_attribute_groups: true, ..Default::default() })
+}
@@ -0,0 +1,276 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+const CLASS: &str = indoc! {r#"
+    <?php
+
+    final class Foo
+    {
+        use A;
+        use B;
+        public const A = 1;
+        public const B = 2;
+        public $a;
+        public $b;
+        /** Documented. */
+        public $c;
+        #[Attribute]
+        public $d;
+
+
+
+        public function foo() { return 1; }
+        public function bar() { return 2; }
+    }
+"#};
+
+#[test]
+pub fn test_consecutive_one_liner_members_stay_grouped() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        final class Foo
+        {
+            use A;
+            use B;
+
+            public const A = 1;
+            public const B = 2;
+
+            public $a;
+            public $b;
+
+            /** Documented. */
+            public $c;
+
+            #[Attribute]
+            public $d;
+
+            public function foo()
+            {
+                return 1;
+            }
+
+            public function bar()
+            {
+                return 2;
+            }
+        }
+    "#};
+
+    test_format(CLASS, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_blank_lines_between_class_members() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        final class Foo
+        {
+            use A;
+            use B;
+
+
+            public const A = 1;
+            public const B = 2;
+
+
+            public $a;
+            public $b;
+
+
+            /** Documented. */
+            public $c;
+
+
+            #[Attribute]
+            public $d;
+
+
+            public function foo()
+            {
+                return 1;
+            }
+
+
+            public function bar()
+            {
+                return 2;
+            }
+        }
+    "#};
+
+    test_format(CLASS, expected, FormatSettings { blank_lines_between_class_members: 2, ..Default::default() })
+}
+
+#[test]
+pub fn test_no_blank_lines_between_class_members() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        final class Foo
+        {
+            use A;
+            use B;
+            public const A = 1;
+            public const B = 2;
+            public $a;
+            public $b;
+            /** Documented. */
+            public $c;
+            #[Attribute]
+            public $d;
+            public function foo()
+            {
+                return 1;
+            }
+            public function bar()
+            {
+                return 2;
+            }
+        }
+    "#};
+
+    test_format(CLASS, expected, FormatSettings { blank_lines_between_class_members: 0, ..Default::default() })
+}
+
+#[test]
+pub fn test_blank_lines_after_opening_tag() -> Result<(), SourceError> {
+    let code = "<?php\n\n\n$a = 1;\n";
+
+    test_format(code, "<?php\n$a = 1;\n", FormatSettings { blank_lines_after_opening_tag: 0, ..Default::default() })?;
+    test_format(code, "<?php\n\n$a = 1;\n", FormatSettings::default())?;
+    test_format("<?php $a = 1;\n", "<?php\n\n$a = 1;\n", FormatSettings::default())
+}
+
+const FUNCTION: &str = indoc! {r#"
+    <?php
+
+    $a = 1;
+
+
+
+
+    $b = 2;
+    function foo() {
+        $a = 1;
+
+
+
+        $b = 2;
+        return $a;
+    }
+    function bar() {
+        $a = 1;
+
+        return $a;
+    }
+"#};
+
+#[test]
+pub fn test_consecutive_blank_lines_are_collapsed() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        $a = 1;
+
+
+        $b = 2;
+        function foo()
+        {
+            $a = 1;
+
+            $b = 2;
+            return $a;
+        }
+        function bar()
+        {
+            $a = 1;
+
+            return $a;
+        }
+    "#};
+
+    test_format(FUNCTION, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_max_consecutive_blank_lines() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        $a = 1;
+        $b = 2;
+        function foo()
+        {
+            $a = 1;
+            $b = 2;
+            return $a;
+        }
+        function bar()
+        {
+            $a = 1;
+            return $a;
+        }
+    "#};
+
+    test_format(FUNCTION, expected, FormatSettings { max_consecutive_blank_lines: Some(0), ..Default::default() })
+}
+
+#[test]
+pub fn test_blank_lines_before_return() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        $a = 1;
+
+
+        $b = 2;
+        function foo()
+        {
+            $a = 1;
+
+            $b = 2;
+
+            return $a;
+        }
+        function bar()
+        {
+            $a = 1;
+
+            return $a;
+        }
+    "#};
+
+    test_format(FUNCTION, expected, FormatSettings { blank_lines_before_return: Some(1), ..Default::default() })
+}
+
+#[test]
+pub fn test_no_blank_lines_before_return() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        function foo() {
+            $a = 1;
+
+            return $a;
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+            $a = 1;
+            return $a;
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings { blank_lines_before_return: Some(0), ..Default::default() })
+}
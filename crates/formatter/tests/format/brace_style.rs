@@ -0,0 +1,196 @@
+use indoc::indoc;
+
+use mago_formatter::settings::BraceStyle;
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+const CODE: &str = indoc! {r#"
+    <?php
+
+    if ($a) { foo(); } elseif ($b) { bar(); } else { baz(); }
+    do { foo(); } while ($a);
+    try { foo(); } catch (Exception $e) { bar(); } finally { baz(); }
+    $x = match ($value) { 1 => 'the first value', 2 => 'the second value', 3 => 'the third value', default => 'another value' };
+    class Foo { public function bar() { $c = function () { return 1; }; $d = fn() => 1; $e = new class { public $a; }; } }
+    function foo() { return 1; }
+"#};
+
+#[test]
+pub fn test_default_brace_style_follows_psr12() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        if ($a) {
+            foo();
+        } elseif ($b) {
+            bar();
+        } else {
+            baz();
+        }
+        do {
+            foo();
+        } while ($a);
+        try {
+            foo();
+        } catch (Exception $e) {
+            bar();
+        } finally {
+            baz();
+        }
+        $x = match ($value) {
+            1 => 'the first value',
+            2 => 'the second value',
+            3 => 'the third value',
+            default => 'another value',
+        };
+        class Foo
+        {
+            public function bar()
+            {
+                $c = function () {
+                    return 1;
+                };
+                $d = fn() => 1;
+                $e = new class {
+                    public $a;
+                };
+            }
+        }
+        function foo()
+        {
+            return 1;
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_next_line_control_brace_style() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        if ($a)
+        {
+            foo();
+        }
+        elseif ($b)
+        {
+            bar();
+        }
+        else
+        {
+            baz();
+        }
+        do
+        {
+            foo();
+        }
+        while ($a);
+        try
+        {
+            foo();
+        }
+        catch (Exception $e)
+        {
+            bar();
+        }
+        finally
+        {
+            baz();
+        }
+        $x = match ($value)
+        {
+            1 => 'the first value',
+            2 => 'the second value',
+            3 => 'the third value',
+            default => 'another value',
+        };
+        class Foo
+        {
+            public function bar()
+            {
+                $c = function () {
+                    return 1;
+                };
+                $d = fn() => 1;
+                $e = new class {
+                    public $a;
+                };
+            }
+        }
+        function foo()
+        {
+            return 1;
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings { control_brace_style: BraceStyle::NextLine, ..Default::default() })
+}
+
+#[test]
+pub fn test_same_line_declaration_brace_style() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        class Foo { public function bar() { $c = function () { return 1; }; $d = fn() => 1; $e = new class { public $a; }; } }
+        function foo() { return 1; }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        class Foo {
+            public function bar() {
+                $c = function () {
+                    return 1;
+                };
+                $d = fn() => 1;
+                $e = new class {
+                    public $a;
+                };
+            }
+        }
+        function foo() {
+            return 1;
+        }
+    "#};
+
+    let settings = FormatSettings {
+        classlike_brace_style: BraceStyle::SameLine,
+        function_brace_style: BraceStyle::SameLine,
+        method_brace_style: BraceStyle::SameLine,
+        ..Default::default()
+    };
+
+    test_format(code, expected, settings)
+}
+
+#[test]
+pub fn test_next_line_closure_brace_style() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $c = function () { return 1; };
+        $d = fn() => 1;
+        $e = new class { public $a; };
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $c = function ()
+        {
+            return 1;
+        };
+        $d = fn() => 1;
+        $e = new class
+        {
+            public $a;
+        };
+    "#};
+
+    test_format(code, expected, FormatSettings { closure_brace_style: BraceStyle::NextLine, ..Default::default() })
+}
@@ -0,0 +1,128 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+#[test]
+pub fn test_use_clause_breaks_along_with_parameters() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $short = function ($item) use ($multiplier) { return $item * $multiplier; };
+        $long = function (SomeVeryLongClassName $someVeryLongParameterName, AnotherClassName $another) use ($someCapturedVariable, $anotherCapturedVariable): int { return 1; };
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $short = function ($item) use ($multiplier) {
+            return $item * $multiplier;
+        };
+        $long = function (
+            SomeVeryLongClassName $someVeryLongParameterName,
+            AnotherClassName $another,
+        ) use (
+            $someCapturedVariable,
+            $anotherCapturedVariable,
+        ): int {
+            return 1;
+        };
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_closure_hugs_the_call_as_last_argument() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $result = $collection->map(function ($item) use ($multiplier) { return $item * $multiplier; });
+        usort($items, function ($a, $b) { return $a <=> $b; });
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $result = $collection->map(function ($item) use ($multiplier) {
+            return $item * $multiplier;
+        });
+        usort($items, function ($a, $b) {
+            return $a <=> $b;
+        });
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_arrow_function_body_breaks_after_arrow() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $fn = fn(SomeVeryLongClassName $someVeryLongParameterName) => $someVeryLongParameterName->someMethodCall($argumentOne, $argumentTwo);
+        $result = $collection->map(fn($item) => $item->someVeryLongMethodName($firstArgumentValue, $secondArgumentValue, $third));
+        $rows = $collection->map(fn($item) => ['identifier' => $item->getIdentifier(), 'name' => $item->getName(), 'email' => $item->getEmail()]);
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $fn = fn(SomeVeryLongClassName $someVeryLongParameterName) =>
+            $someVeryLongParameterName->someMethodCall($argumentOne, $argumentTwo);
+        $result = $collection->map(fn($item) =>
+            $item->someVeryLongMethodName($firstArgumentValue, $secondArgumentValue, $third),
+        );
+        $rows = $collection->map(fn($item) => [
+            'identifier' => $item->getIdentifier(),
+            'name' => $item->getName(),
+            'email' => $item->getEmail(),
+        ]);
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_prefer_arrow_fn_single_expression() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $result = $collection->map(fn($item) => $item->someVeryLongMethodName($firstArgumentValue, $secondArgumentValue, $third));
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $result = $collection->map(
+            fn($item) => $item->someVeryLongMethodName($firstArgumentValue, $secondArgumentValue, $third),
+        );
+    "#};
+
+    test_format(code, expected, FormatSettings { prefer_arrow_fn_single_expression: true, ..Default::default() })
+}
+
+#[test]
+pub fn test_nested_closures() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = function () { return function () use ($x) { return $items->map(function ($y) use ($x) { return $x + $y; }); }; };
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $a = function () {
+            return function () use ($x) {
+                return $items->map(function ($y) use ($x) {
+                    return $x + $y;
+                });
+            };
+        };
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
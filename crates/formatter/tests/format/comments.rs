@@ -0,0 +1,57 @@
+use mago_formatter::settings::FormatSettings;
+use mago_formatter::settings::UseGroup;
+use mago_source::error::SourceError;
+
+use crate::test_comments_are_preserved;
+
+/// Returns the code of every fixture, along with its name.
+pub(crate) fn fixtures() -> Vec<(String, String)> {
+    let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let mut fixtures: Vec<(String, String)> = std::fs::read_dir(directory)
+        .expect("the fixtures directory can be read")
+        .map(|entry| entry.expect("the fixtures directory can be read").path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "php"))
+        .map(|path| {
+            let code = std::fs::read_to_string(&path).expect("the fixture can be read");
+
+            (path.display().to_string(), code)
+        })
+        .collect();
+
+    fixtures.sort();
+    fixtures
+}
+
+#[test]
+pub fn test_comments_of_fixtures_are_preserved() -> Result<(), SourceError> {
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures were found");
+
+    for (name, code) in fixtures {
+        println!("checking the comments of `{}`", name);
+
+        test_comments_are_preserved(&code, FormatSettings::default())?;
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn test_comments_of_fixtures_are_preserved_when_uses_are_sorted() -> Result<(), SourceError> {
+    let settings = FormatSettings {
+        sort_uses: true,
+        expand_use_groups: true,
+        use_groups: [UseGroup::Constants, UseGroup::Functions, UseGroup::Classes],
+        print_width: 40,
+        ..Default::default()
+    };
+
+    for (name, code) in fixtures() {
+        println!("checking the comments of `{}`", name);
+
+        test_comments_are_preserved(&code, settings)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use pretty_assertions::assert_eq;
+
+use mago_ast::compare::structural_diff;
+use mago_ast::compare::CompareOptions;
+use mago_ast::*;
+use mago_interner::ThreadedInterner;
+use mago_parser::parse_source;
+use mago_source::error::SourceError;
+use mago_source::SourceManager;
+use mago_span::Position;
+use mago_span::Span;
+
+use crate::format::comments::fixtures;
+
+#[test]
+pub fn test_fixtures_are_printed_without_their_source() -> Result<(), SourceError> {
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures were found");
+
+    for (name, code) in fixtures {
+        println!("printing `{}` without its source", name);
+
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.load(&manager.insert_content(name.clone(), code.clone(), true))?;
+        let (program, errors) = parse_source(&interner, &source);
+        assert!(errors.is_empty(), "`{}` cannot be parsed: {:?}", name, errors);
+
+        let printed = mago_formatter::print_node(&interner, Node::Program(&program));
+
+        let printed_source =
+            manager.load(&manager.insert_content(format!("{}.printed", name), printed.clone(), true))?;
+        let (printed_program, errors) = parse_source(&interner, &printed_source);
+        assert!(errors.is_empty(), "the printed `{}` cannot be parsed: {:?}\n{}", name, errors, printed);
+
+        // Comments, including docblocks, are not printed without the source.
+        let options = CompareOptions { ignore_docblocks: true, ..CompareOptions::default() };
+        let differences = structural_diff(&program, &printed_program, &interner, options);
+
+        assert!(differences.is_empty(), "the printed `{}` differs: {:?}\n{}", name, differences, printed);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn test_nodes_of_a_program_are_printed_on_their_own() -> Result<(), SourceError> {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source = manager.load(&manager.insert_content(
+        "code.php".to_string(),
+        "<?php\n\nfunction foo(int $a) { return $a+1; }".to_string(),
+        true,
+    ))?;
+    let (program, _) = parse_source(&interner, &source);
+
+    let Some(Statement::Function(function)) = program.statements.iter().find(|s| matches!(s, Statement::Function(_)))
+    else {
+        panic!("the program contains a function");
+    };
+
+    let Some(Statement::Return(r#return)) = function.body.statements.get(0) else {
+        panic!("the function starts with a return statement");
+    };
+
+    assert_eq!("return $a + 1;", mago_formatter::print_node(&interner, Node::Return(r#return)));
+    assert_eq!(
+        "function foo(int $a)\n{\n    return $a + 1;\n}",
+        mago_formatter::print_node(&interner, Node::Function(function)).trim_end()
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn test_constructed_nodes_are_printed() {
+    let interner = ThreadedInterner::new();
+    let span = Span::new(Position::dummy(0), Position::dummy(0));
+    let integer = |value: u64| {
+        Expression::Literal(Literal::Integer(LiteralInteger {
+            span,
+            raw: interner.intern(value.to_string()),
+            value: Some(value),
+        }))
+    };
+
+    let binary = Binary {
+        lhs: Box::new(integer(1)),
+        operator: BinaryOperator::Addition(span),
+        rhs: Box::new(Expression::Binary(Binary {
+            lhs: Box::new(integer(2)),
+            operator: BinaryOperator::Multiplication(span),
+            rhs: Box::new(integer(3)),
+        })),
+    };
+
+    assert_eq!("1 + (2 * 3)", mago_formatter::print_node(&interner, Node::Binary(&binary)));
+}
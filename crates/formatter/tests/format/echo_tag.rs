@@ -0,0 +1,62 @@
+use indoc::indoc;
+
+use mago_formatter::settings::EchoTag;
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+#[test]
+pub fn test_echo_tag_short_converts_single_echo_blocks() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <a><?php echo $a; ?></a>
+        <b><?php echo $b ?></b>
+        <c><?php echo $user->getName() . ' ' . ($x ? 'a' : "b"); ?></c>
+        <d><?= $d ?></d>
+    "#};
+
+    let expected = indoc! {r#"
+        <a><?= $a ?></a>
+        <b><?= $b ?></b>
+        <c><?= $user->getName() . ' ' . ($x ? 'a' : 'b') ?></c>
+        <d><?= $d ?></d>
+    "#};
+
+    test_format(code, expected, FormatSettings { echo_tag: EchoTag::Short, ..Default::default() })
+}
+
+#[test]
+pub fn test_echo_tag_long_converts_short_echo_tags() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <a><?= $a ?></a>
+        <b><?= $b; ?></b>
+        <c><?php echo $c; ?></c>
+    "#};
+
+    let expected = indoc! {r#"
+        <a><?php echo $a; ?></a>
+        <b><?php echo $b; ?></b>
+        <c><?php echo $c; ?></c>
+    "#};
+
+    test_format(code, expected, FormatSettings { echo_tag: EchoTag::Long, ..Default::default() })
+}
+
+#[test]
+pub fn test_echo_tag_keeps_blocks_with_comments_or_several_values() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <a><?php /* name */ echo $a; ?></a>
+        <b><?php echo $b, $c; ?></b>
+    "#};
+
+    let expected = indoc! {r#"
+        <a><?php /* name */
+        echo $a;
+        ?></a>
+        <b><?php
+        echo $b, $c;
+        ?></b>
+    "#};
+
+    test_format(code, expected, FormatSettings { echo_tag: EchoTag::Short, ..Default::default() })
+}
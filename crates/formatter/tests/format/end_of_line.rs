@@ -0,0 +1,61 @@
+use mago_formatter::settings::EndOfLine;
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+#[test]
+pub fn test_auto_end_of_line_keeps_crlf() -> Result<(), SourceError> {
+    let code = "<?php\r\n\r\n$a  =  1;\r\n$b = [1,\r\n2];\r\n";
+    let expected = "<?php\r\n\r\n$a = 1;\r\n$b = [1, 2];\r\n";
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_auto_end_of_line_defaults_to_lf() -> Result<(), SourceError> {
+    test_format("<?php $a  =  1;", "<?php\n\n$a = 1;\n", FormatSettings::default())
+}
+
+#[test]
+pub fn test_explicit_end_of_line_is_used() -> Result<(), SourceError> {
+    let code = "<?php\r\n\r\n$a  =  1;\r\n";
+    let expected = "<?php\n\n$a = 1;\n";
+
+    test_format(code, expected, FormatSettings { end_of_line: EndOfLine::Lf, ..Default::default() })
+}
+
+/// Mixes line endings in the code, a literal string, a heredoc, and inline HTML.
+const MIXED_CODE: &str =
+    "<p>a</p>\n<p>b</p>\r\n<?php\r\n$a  =  1;\n$b = 'x\ny\r\nz';\r\n$c = <<<EOT\n  foo\r\n  bar\nEOT;\r\n$d = 2;\r\n";
+
+#[test]
+pub fn test_auto_end_of_line_uses_dominant_line_ending() -> Result<(), SourceError> {
+    let expected = "<p>a</p>\n<p>b</p>\r\n<?php\r\n$a = 1;\r\n$b = 'x\ny\r\nz';\r\n$c = <<<EOT\n  foo\r\n  bar\nEOT;\r\n$d = 2;\r\n";
+
+    test_format(MIXED_CODE, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_auto_end_of_line_prefers_lf_on_tie() -> Result<(), SourceError> {
+    let code = "<?php\r\n$a  =  1;\n$b  =  2;\r\n$c  =  3;\n";
+    let expected = "<?php\n\n$a = 1;\n$b = 2;\n$c = 3;\n";
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_lf_end_of_line_keeps_line_endings_of_strings() -> Result<(), SourceError> {
+    let expected =
+        "<p>a</p>\n<p>b</p>\r\n<?php\n$a = 1;\n$b = 'x\ny\r\nz';\n$c = <<<EOT\n  foo\r\n  bar\nEOT;\n$d = 2;\n";
+
+    test_format(MIXED_CODE, expected, FormatSettings { end_of_line: EndOfLine::Lf, ..Default::default() })
+}
+
+#[test]
+pub fn test_crlf_end_of_line_keeps_line_endings_of_strings() -> Result<(), SourceError> {
+    let code = "<?php\n$a  =  1;\n$b = 'x\ny';\n$c = <<<'EOT'\nfoo\nbar\nEOT;\n$d = 2;";
+    let expected = "<?php\r\n\r\n$a = 1;\r\n$b = 'x\ny';\r\n$c = <<<'EOT'\nfoo\nbar\nEOT;\r\n$d = 2;\r\n";
+
+    test_format(code, expected, FormatSettings { end_of_line: EndOfLine::Crlf, ..Default::default() })
+}
@@ -0,0 +1,124 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_idempotency;
+
+#[test]
+pub fn test_long_chained_calls_are_idempotent() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $result = $this->createQueryBuilder('user')->select('user.id', 'user.name')->where('user.active = :active')->setParameter('active', true)->orderBy('user.name', 'ASC')->getQuery()->getResult();
+        $value = Foo::create()->withBar($bar)->withBaz(fn($baz) => $baz->qux()->quux())->build();
+        $short = $a->b()->c();
+    "#};
+
+    test_idempotency(code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_heredocs_are_idempotent() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        function foo(): string
+        {
+            $html = <<<HTML
+                <div class="{$class}">
+                    {$content}
+                </div>
+                HTML;
+
+            $sql = <<<'SQL'
+            SELECT *
+              FROM users
+            SQL;
+
+            return sprintf(<<<EOT
+            Hello, %s!
+            EOT, $name) . $html . $sql;
+        }
+    "#};
+
+    test_idempotency(code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_match_expressions_are_idempotent() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = match ($value) { 1, 2 => 'low', 3 => 'medium', default => 'high' };
+        $b = match (true) {
+            $value instanceof VeryLongClassNameForTesting && $value->isEnabled() => $value->getVeryLongPropertyName(),
+            default => throw new InvalidArgumentException('The value is not supported by this very long match arm.'),
+        };
+        $c = fn($x) => match ($x) { default => $x };
+    "#};
+
+    test_idempotency(code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_nested_arrays_and_comments_are_idempotent() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $config = [
+            'database' => ['host' => 'localhost', 'port' => 3306, 'options' => [PDO::ATTR_ERRMODE => PDO::ERRMODE_EXCEPTION]], // trailing
+            /* leading */ 'cache' => ['driver' => 'redis'],
+        ];
+
+        // A comment before a call.
+        foo($a, /* inline */ $b, function () use ($config) {
+            return $config['database']['host'] ?? 'localhost' ?: 'fallback';
+        });
+    "#};
+
+    test_idempotency(code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_statement_terminators_are_idempotent() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        function foo() {};
+
+        $callback = function () {
+            return 1;
+        };
+
+
+        $values = [
+            'a' => 1,
+        ];
+        function bar() {};
+    "#};
+
+    test_idempotency(code, FormatSettings::default())?;
+    test_idempotency(code, FormatSettings { max_consecutive_blank_lines: Some(2), ..FormatSettings::default() })
+}
+
+#[test]
+pub fn test_docblocks_and_attributed_parameters_are_idempotent() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        class Foo
+        {
+            /**
+              Not starting with an asterisk.
+                 Indented further.
+             */
+            public function bar(
+                #[SensitiveParameter] string $password,
+                #[SensitiveParameter] string $secret,
+            ): void {}
+        }
+    "#};
+
+    test_idempotency(code, FormatSettings::default())
+}
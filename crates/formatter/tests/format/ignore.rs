@@ -0,0 +1,142 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+use crate::test_idempotency;
+
+#[test]
+pub fn test_ignore_leaves_the_next_statement_unformatted() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a  =  1;
+        // @mago-fmt-ignore
+        $matrix = [
+            1, 0,   0,
+            0, 1,   0, // identity
+        ];   // aligned
+        $b  =  2;
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $a = 1;
+        // @mago-fmt-ignore
+        $matrix = [
+            1, 0,   0,
+            0, 1,   0, // identity
+        ];   // aligned
+        $b = 2;
+    "#};
+
+    test_format(code, expected, FormatSettings::default())?;
+    test_idempotency(code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_ignore_leaves_the_next_class_member_unformatted() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        class Foo {
+          /* @mago-fmt-ignore */
+          const   X = [1,   2];
+          const   Y = 1;
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        class Foo
+        {
+            /* @mago-fmt-ignore */
+            const   X = [1,   2];
+            const Y = 1;
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_ignore_region_leaves_statements_unformatted() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        function foo() {
+            $a  =  1;
+            // @mago-fmt-ignore-start
+            $bb   = 2;
+            $ccc  = <<<SQL
+                SELECT *
+                  FROM foo
+              SQL;
+            // @mago-fmt-ignore-end
+            $d  =  4;
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+            $a = 1;
+            // @mago-fmt-ignore-start
+            $bb   = 2;
+            $ccc  = <<<SQL
+                SELECT *
+                  FROM foo
+              SQL;
+            // @mago-fmt-ignore-end
+            $d = 4;
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings::default())?;
+    test_idempotency(code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_unclosed_ignore_region_leaves_the_rest_of_the_file_unformatted() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a  =  1;
+        // @mago-fmt-ignore-start
+        $b  =  2;
+
+        $c  =  3;
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $a = 1;
+        // @mago-fmt-ignore-start
+        $b  =  2;
+
+        $c  =  3;
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_ignore_file_leaves_the_file_unformatted() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        declare(strict_types=1);
+
+        // @mago-fmt-ignore-file
+
+        $a  =  1;
+    "#};
+
+    test_format(code, code, FormatSettings::default())
+}
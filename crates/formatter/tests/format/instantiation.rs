@@ -0,0 +1,107 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+#[test]
+pub fn test_new_in_chain_is_parenthesized() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = new Foo()->bar();
+        $b = new Foo()?->bar;
+        $c = new Foo()::BAR;
+        $d = new Foo()::$bar;
+        $e = new Foo()::bar();
+        $f = new Foo()[0];
+        $g = new Foo()();
+        $h = new Foo()->baz;
+        $i = new class {}->bar();
+        $j = (new Foo)->bar();
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $a = (new Foo())->bar();
+        $b = (new Foo())?->bar;
+        $c = (new Foo())::BAR;
+        $d = (new Foo())::$bar;
+        $e = (new Foo())::bar();
+        $f = (new Foo())[0];
+        $g = (new Foo())();
+        $h = (new Foo())->baz;
+        $i = (new class {
+        })->bar();
+        $j = (new Foo)->bar();
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_new_in_chain_is_preserved() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = new Foo()->bar();
+        $b = new Foo()?->bar;
+        $c = new Foo()::BAR;
+        $d = new Foo()::$bar;
+        $e = new Foo()::bar();
+        $f = new Foo()[0];
+        $g = new Foo()();
+        $h = (new Foo())->baz;
+        $i = (new Foo)->bar();
+        $j = new Foo()
+            ->a()
+            ->b()
+            ->c()
+            ->d();
+        $k = (new Foo())
+            ->a()
+            ->b()
+            ->c()
+            ->d();
+    "#};
+
+    test_format(code, code, FormatSettings { parenthesize_new_in_chain: false, ..Default::default() })
+}
+
+#[test]
+pub fn test_new_in_chain_precedence_with_instanceof_and_unary_operators() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = new Foo() instanceof Foo;
+        $b = !new Foo()->bar();
+        $c = -new Foo()->bar;
+        $d = new Foo()->bar() instanceof Bar;
+        $e = !new Foo() instanceof Foo;
+    "#};
+
+    let parenthesized = indoc! {r#"
+        <?php
+
+        $a = new Foo() instanceof Foo;
+        $b = !(new Foo())->bar();
+        $c = -(new Foo())->bar;
+        $d = (new Foo())->bar() instanceof Bar;
+        $e = !(new Foo() instanceof Foo);
+    "#};
+
+    let preserved = indoc! {r#"
+        <?php
+
+        $a = new Foo() instanceof Foo;
+        $b = !new Foo()->bar();
+        $c = -new Foo()->bar;
+        $d = new Foo()->bar() instanceof Bar;
+        $e = !(new Foo() instanceof Foo);
+    "#};
+
+    test_format(code, parenthesized, FormatSettings::default())?;
+    test_format(code, preserved, FormatSettings { parenthesize_new_in_chain: false, ..Default::default() })
+}
@@ -0,0 +1,89 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+const CODE: &str = indoc! {r#"
+    <?php
+
+    function foo() {
+        $result = call_some_function($first_argument, $second_argument, $third_argument);
+        $total = $first_value + $second_value + $third_value + $fourth_value + $fifth_value;
+    }
+"#};
+
+#[test]
+pub fn test_print_width_of_120_keeps_lines_unbroken() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+            $result = call_some_function($first_argument, $second_argument, $third_argument);
+            $total = $first_value + $second_value + $third_value + $fourth_value + $fifth_value;
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings { print_width: 120, ..Default::default() })
+}
+
+#[test]
+pub fn test_print_width_of_80_breaks_long_lines() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+            $result = call_some_function(
+                $first_argument,
+                $second_argument,
+                $third_argument,
+            );
+            $total =
+                $first_value +
+                $second_value +
+                $third_value +
+                $fourth_value +
+                $fifth_value;
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings { print_width: 80, ..Default::default() })
+}
+
+#[test]
+pub fn test_use_tabs_indents_with_tabs() -> Result<(), SourceError> {
+    let code = "<?php\n\nfunction foo() {\nif ($a) {\n$b = 1;\n}\n}\n";
+    let expected = "<?php\n\nfunction foo()\n{\n\tif ($a) {\n\t\t$b = 1;\n\t}\n}\n";
+
+    test_format(code, expected, FormatSettings { use_tabs: true, ..Default::default() })
+}
+
+#[test]
+pub fn test_tab_width_sets_the_number_of_spaces() -> Result<(), SourceError> {
+    let code = "<?php\n\nfunction foo() {\nif ($a) {\n$b = 1;\n}\n}\n";
+    let expected = "<?php\n\nfunction foo()\n{\n  if ($a) {\n    $b = 1;\n  }\n}\n";
+
+    test_format(code, expected, FormatSettings { tab_width: 2, ..Default::default() })
+}
+
+#[test]
+pub fn test_tabs_count_as_tab_width_columns() -> Result<(), SourceError> {
+    // The call fits in exactly 59 columns when indented with 4 spaces, and so must it with a tab.
+    let code = indoc! {r#"
+        <?php
+
+        function foo() {
+            $result = call_some_function($first_argument, $second);
+        }
+    "#};
+
+    let expected = "<?php\n\nfunction foo()\n{\n\t$result = call_some_function($first_argument, $second);\n}\n";
+    test_format(code, expected, FormatSettings { print_width: 59, use_tabs: true, ..Default::default() })?;
+
+    let expected =
+        "<?php\n\nfunction foo()\n{\n\t$result = call_some_function(\n\t\t$first_argument,\n\t\t$second,\n\t);\n}\n";
+    test_format(code, expected, FormatSettings { print_width: 58, use_tabs: true, ..Default::default() })
+}
@@ -0,0 +1,95 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_formatter::settings::TrailingComma;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+#[test]
+pub fn test_match_fitting_print_width_stays_on_one_line() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $x = match ($a) {
+            1, 2 => 'one or two',
+            default => 'other',
+        };
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $x = match ($a) { 1, 2 => 'one or two', default => 'other' };
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_match_breaks_one_arm_per_line() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $y = match ($condition) { SomeEnum::FirstCase, SomeEnum::SecondCase => $this->handleFirstAndSecond($value), SomeEnum::ThirdCase => $this->handleThird($value), default => throw new InvalidArgumentException('Unexpected') };
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $y = match ($condition) {
+            SomeEnum::FirstCase, SomeEnum::SecondCase => $this->handleFirstAndSecond($value),
+            SomeEnum::ThirdCase => $this->handleThird($value),
+            default => throw new InvalidArgumentException('Unexpected'),
+        };
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+const NESTED: &str = indoc! {r#"
+    <?php
+
+    $handler = fn($event) => match ($event->type) { 'created' => match ($event->source) { 'api' => $this->onApiCreated($event), 'web' => $this->onWebCreated($event), default => null }, 'deleted' => $this->onDeleted($event), default => null };
+    $short = fn($v) => match ($v) { 1 => 'a', default => 'b' };
+"#};
+
+#[test]
+pub fn test_nested_match_in_arrow_function() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        $handler = fn($event) => match ($event->type) {
+            'created' => match ($event->source) {
+                'api' => $this->onApiCreated($event),
+                'web' => $this->onWebCreated($event),
+                default => null,
+            },
+            'deleted' => $this->onDeleted($event),
+            default => null,
+        };
+        $short = fn($v) => match ($v) { 1 => 'a', default => 'b' };
+    "#};
+
+    test_format(NESTED, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_nested_match_in_arrow_function_without_trailing_commas() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        $handler = fn($event) => match ($event->type) {
+            'created' => match ($event->source) {
+                'api' => $this->onApiCreated($event),
+                'web' => $this->onWebCreated($event),
+                default => null
+            },
+            'deleted' => $this->onDeleted($event),
+            default => null
+        };
+        $short = fn($v) => match ($v) { 1 => 'a', default => 'b' };
+    "#};
+
+    test_format(NESTED, expected, FormatSettings { trailing_comma: TrailingComma::Never, ..Default::default() })
+}
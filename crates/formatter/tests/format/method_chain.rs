@@ -0,0 +1,101 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_formatter::settings::MethodChainBreakingStyle;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+use crate::test_idempotency;
+
+const CODE: &str = indoc! {r#"
+    <?php
+
+    $query->select('id', 'name')->from('users')->where('active', true)->orderBy('name');
+    $short = $query->select('id')->from('users');
+    $result = $repository->findAllMatchingCriteria($someLongCriteria, $anotherArgument)->filterByStatus($status)->toArrayList();
+    $city = $user?->getProfile()?->getAddress()?->getCity()?->getName();
+    $names = $collection->filter(function ($item) { return $item->isActive(); })->map(fn ($item) => $item->name)->values()->all();
+"#};
+
+#[test]
+pub fn test_method_chains_are_broken_one_call_per_line() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        $query
+            ->select('id', 'name')
+            ->from('users')
+            ->where('active', true)
+            ->orderBy('name');
+        $short = $query->select('id')->from('users');
+        $result = $repository
+            ->findAllMatchingCriteria($someLongCriteria, $anotherArgument)
+            ->filterByStatus($status)
+            ->toArrayList();
+        $city = $user
+            ?->getProfile()
+            ?->getAddress()
+            ?->getCity()
+            ?->getName();
+        $names = $collection
+            ->filter(function ($item) {
+                return $item->isActive();
+            })
+            ->map(fn($item) => $item->name)
+            ->values()
+            ->all();
+    "#};
+
+    test_format(CODE, expected, FormatSettings::default())?;
+    test_idempotency(CODE, FormatSettings::default())
+}
+
+#[test]
+pub fn test_method_chains_keep_first_call_on_same_line() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $query->select('id', 'name')->from('users')->where('active', true)->orderBy('name');
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $query->select('id', 'name')
+            ->from('users')
+            ->where('active', true)
+            ->orderBy('name');
+    "#};
+
+    let settings =
+        FormatSettings { method_chain_breaking_style: MethodChainBreakingStyle::SameLine, ..Default::default() };
+
+    test_format(code, expected, settings)
+}
+
+#[test]
+pub fn test_method_chain_breaking_threshold() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $query->select('id', 'name')->from('users')->where('active', true)->orderBy('name');
+        $short = $query->select('id')->from('users');
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $query
+            ->select('id', 'name')
+            ->from('users')
+            ->where('active', true)
+            ->orderBy('name');
+        $short = $query
+            ->select('id')
+            ->from('users');
+    "#};
+
+    test_format(code, expected, FormatSettings { method_chain_breaking_threshold: 1, ..Default::default() })?;
+
+    test_format(code, code, FormatSettings { method_chain_breaking_threshold: 4, ..Default::default() })
+}
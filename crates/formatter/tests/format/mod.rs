@@ -1,2 +1,27 @@
+pub mod array_syntax;
+pub mod attribute;
+pub mod blank_lines;
+pub mod brace_style;
+pub mod closure;
+pub mod comments;
+pub mod detached;
+pub mod echo_tag;
+pub mod end_of_line;
 pub mod expression;
+pub mod idempotency;
+pub mod ignore;
+pub mod instantiation;
+pub mod layout;
+pub mod match_expression;
+pub mod method_chain;
+pub mod modifiers;
+pub mod named_arguments;
+pub mod operator_position;
+pub mod property_hooks;
+pub mod range;
 pub mod string;
+pub mod trailing_comma;
+pub mod trait_use;
+pub mod types;
+pub mod uses;
+pub mod verbatim;
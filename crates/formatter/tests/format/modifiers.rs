@@ -0,0 +1,65 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+#[test]
+pub fn test_asymmetric_visibility_modifiers_are_preserved() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        final class Counter
+        {
+            public private(set) int $count = 0;
+            public protected(set) readonly string $name;
+            protected private(set) ?Foo $foo = null;
+            private(set) string $implicit = '';
+
+            public function __construct(
+                public private(set) string $first,
+                protected protected(set) readonly string $second,
+                public(set) string $third,
+            ) {
+            }
+        }
+    "#};
+
+    test_format(code, code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_asymmetric_visibility_modifiers_are_printed_in_canonical_order() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        final class Counter
+        {
+            private(set) public int $count = 0;
+            readonly protected(set) public string $name;
+
+            public function __construct(
+                readonly private(set) public string $first,
+            ) {
+            }
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        final class Counter
+        {
+            public private(set) int $count = 0;
+            public protected(set) readonly string $name;
+
+            public function __construct(
+                public private(set) readonly string $first,
+            ) {
+            }
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
@@ -0,0 +1,84 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+#[test]
+pub fn test_named_arguments_fitting_print_width_stay_on_one_line() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $r = foo(1, name: $value, other: $anotherValue);
+    "#};
+
+    test_format(code, code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_named_arguments_break_one_per_line_in_order() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $r = foo($positional, name: $value, other: $anotherValue, third: $this->someMethodCall($withArguments), fourth: 'string');
+        $r = foo(first: 1, second: ['a' => 1, 'b' => 2, 'c' => 3, 'd' => 4, 'e' => 5, 'f' => 6, 'g' => 7, 'h' => 8, 'i' => 9, 'j' => 10]);
+        $r = foo(first: function () { return 1; }, second: 1);
+        $r = foo(1, function () { return 1; });
+        $r = foo(name: function () { return 1; });
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $r = foo(
+            $positional,
+            name: $value,
+            other: $anotherValue,
+            third: $this->someMethodCall($withArguments),
+            fourth: 'string',
+        );
+        $r = foo(
+            first: 1,
+            second: ['a' => 1, 'b' => 2, 'c' => 3, 'd' => 4, 'e' => 5, 'f' => 6, 'g' => 7, 'h' => 8, 'i' => 9, 'j' => 10],
+        );
+        $r = foo(
+            first: function () {
+                return 1;
+            },
+            second: 1,
+        );
+        $r = foo(1, function () {
+            return 1;
+        });
+        $r = foo(name: function () {
+            return 1;
+        });
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_named_arguments_with_nested_match_in_arrow_function() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $r = $collection->map(callback: fn($item) => match ($item->status) { Status::Active => $item->activate(), Status::Inactive, Status::Suspended => $item->deactivate(), default => null }, preserveKeys: true);
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $r = $collection->map(
+            callback: fn($item) => match ($item->status) {
+                Status::Active => $item->activate(),
+                Status::Inactive, Status::Suspended => $item->deactivate(),
+                default => null,
+            },
+            preserveKeys: true,
+        );
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
@@ -0,0 +1,111 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_formatter::settings::OperatorPosition;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+const CODE: &str = indoc! {r#"
+    <?php
+
+    if (($someCondition && $anotherCondition) || ($yetAnotherCondition && $theLastConditionInTheChain) || $e) {
+        $message = 'The quick brown fox ' . $jumpsOver . ' the lazy dog, and then ' . $someMoreText . ' until the end';
+    }
+"#};
+
+#[test]
+pub fn test_trailing_operators_end_the_broken_lines() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        if (
+            ($someCondition && $anotherCondition) ||
+            ($yetAnotherCondition && $theLastConditionInTheChain) ||
+            $e
+        ) {
+            $message =
+                'The quick brown fox ' .
+                $jumpsOver .
+                ' the lazy dog, and then ' .
+                $someMoreText .
+                ' until the end';
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings { print_width: 80, ..Default::default() })
+}
+
+#[test]
+pub fn test_leading_operators_start_the_continuation_lines() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        if (
+            ($someCondition && $anotherCondition)
+            || ($yetAnotherCondition && $theLastConditionInTheChain)
+            || $e
+        ) {
+            $message =
+                'The quick brown fox '
+                . $jumpsOver
+                . ' the lazy dog, and then '
+                . $someMoreText
+                . ' until the end';
+        }
+    "#};
+
+    test_format(
+        CODE,
+        expected,
+        FormatSettings { print_width: 80, operator_position: OperatorPosition::Leading, ..Default::default() },
+    )
+}
+
+#[test]
+pub fn test_mixed_precedence_operations_keep_their_parentheses() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = $b && $c || $d;
+        $a = ($b && $c) || $d;
+        $a = $b && ($c || $d);
+        $a = $b + ($c * $d);
+        $a = $b ?? $c ?? $d;
+        $a = $b ? ($c ? 1 : 2) : ($d ? ($e ? 3 : 4) : 5);
+    "#};
+
+    test_format(code, code, FormatSettings { operator_position: OperatorPosition::Leading, ..Default::default() })
+}
+
+#[test]
+pub fn test_leading_operators_with_null_coalescing_and_comparisons() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $value = $someVeryLongVariableName ?? $anotherVeryLongVariableName ?? $yetAnotherVeryLongVariableName ?? 'default';
+        while ($someVeryLongVariableNameForComparison === $anotherVeryLongVariableNameForComparisonPurposes) {
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $value =
+            $someVeryLongVariableName
+            ?? $anotherVeryLongVariableName
+            ?? $yetAnotherVeryLongVariableName
+            ?? 'default';
+        while (
+            $someVeryLongVariableNameForComparison
+            === $anotherVeryLongVariableNameForComparisonPurposes
+        ) {
+        }
+    "#};
+
+    test_format(
+        code,
+        expected,
+        FormatSettings { print_width: 100, operator_position: OperatorPosition::Leading, ..Default::default() },
+    )
+}
@@ -0,0 +1,46 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+/// Hooked properties with short and block hook bodies, by-reference, abstract, and final hooks.
+const PROPERTY_HOOKS: &str = include_str!("../fixtures/property_hooks.php");
+
+#[test]
+pub fn test_property_hooks_are_preserved() -> Result<(), SourceError> {
+    test_format(PROPERTY_HOOKS, PROPERTY_HOOKS, FormatSettings::default())
+}
+
+#[test]
+pub fn test_property_hooks_are_normalized() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        final class User
+        {
+            public string $name { get=>strtoupper($this->name); set { $this->name = $value; } }
+
+            public string $email   {   set ( string $value )   =>   strtolower($value) ;  }
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        final class User
+        {
+            public string $name {
+                get => strtoupper($this->name);
+                set {
+                    $this->name = $value;
+                }
+            }
+
+            public string $email { set(string $value) => strtolower($value); }
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
@@ -0,0 +1,215 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format_range;
+
+fn range_of(code: &str, needle: &str) -> std::ops::Range<usize> {
+    let start = code.find(needle).expect("needle not found");
+
+    start..start + needle.len()
+}
+
+#[test]
+pub fn test_format_range_only_formats_covered_statements() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a  =  1;
+        $b  =  2;
+        $c  =  3;
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $a  =  1;
+        $b = 2;
+        $c  =  3;
+    "#};
+
+    test_format_range(code, range_of(code, "=  2"), expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_format_range_widens_to_partially_covered_statements() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a  =  1;
+        if ($a)   {
+            $b  =  2;
+        }
+        $c  =  3;
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $a  =  1;
+        if ($a) {
+            $b = 2;
+        }
+        $c  =  3;
+    "#};
+
+    test_format_range(code, range_of(code, "($a)   {\n    $b"), expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_format_range_matches_the_indentation_of_the_context() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        class Foo
+        {
+          public function bar()
+          {
+            $a  =  [1,
+            2];
+            $b  =  2;
+          }
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        class Foo
+        {
+          public function bar()
+          {
+            $a = [1, 2];
+            $b  =  2;
+          }
+        }
+    "#};
+
+    test_format_range(code, range_of(code, "[1,\n    2];"), expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_format_range_reindents_a_misindented_first_line() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        class Foo
+        {
+            public function bar()
+            {
+                $a  =  1;
+          $b  =  2;
+            }
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        class Foo
+        {
+            public function bar()
+            {
+                $a  =  1;
+                $b = 2;
+            }
+        }
+    "#};
+
+    test_format_range(code, range_of(code, "$b"), expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_format_range_keeps_strings_verbatim() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+          $a  =  'a
+            b';
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+            $a = 'a
+            b';
+        }
+    "#};
+
+    test_format_range(code, range_of(code, "$a  ="), expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_format_range_without_statements_is_unchanged() -> Result<(), SourceError> {
+    let code = "<?php\n\n$a  =  1;\n\n\n$b  =  2;\n";
+
+    test_format_range(code, 17..18, code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_format_range_nested_two_blocks_deep() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+          if ($a) {
+            if ($b) {
+              $c  =  1;
+              $d  =  2;
+            }
+          }
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+          if ($a) {
+            if ($b) {
+              $c = 1;
+              $d = 2;
+            }
+          }
+        }
+    "#};
+
+    test_format_range(code, range_of(code, "1;\n      $d"), expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_format_range_nested_two_blocks_deep_after_other_code_on_the_line() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+            if ($a) {
+                if ($b) { $c  =  1;
+                $d  =  2; }
+            }
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+            if ($a) {
+                if ($b) { $c = 1;
+                    $d = 2; }
+            }
+        }
+    "#};
+
+    test_format_range(code, range_of(code, "1;\n        $d"), expected, FormatSettings::default())
+}
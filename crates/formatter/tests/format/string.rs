@@ -1,6 +1,7 @@
 use indoc::indoc;
 
 use mago_formatter::settings::FormatSettings;
+use mago_formatter::settings::StringQuotes;
 use mago_source::error::SourceError;
 
 use crate::test_format;
@@ -27,13 +28,13 @@ pub fn test_single_quote() -> Result<(), SourceError> {
         $b = 'Hello, world!';
         $c = "Hello, 'world'!";
         $d = 'Hello, "world"!';
-        $e = "Hello, \"world\"!";
-        $f = 'Hello, \'world\'!';
+        $e = 'Hello, "world"!';
+        $f = "Hello, 'world'!";
         $g = "Hello, 'world'!";
         $h = 'Hello, \"world\"!';
     "#};
 
-    test_format(code, expected, FormatSettings { single_quote: true, ..Default::default() })
+    test_format(code, expected, FormatSettings { string_quotes: StringQuotes::Single, ..Default::default() })
 }
 
 #[test]
@@ -58,11 +59,123 @@ pub fn test_double_quote() -> Result<(), SourceError> {
         $b = "Hello, world!";
         $c = "Hello, 'world'!";
         $d = 'Hello, "world"!';
-        $e = "Hello, \"world\"!";
-        $f = 'Hello, \'world\'!';
+        $e = 'Hello, "world"!';
+        $f = "Hello, 'world'!";
         $g = "Hello, 'world'!";
         $h = 'Hello, \"world\"!';
     "#};
 
-    test_format(code, expected, FormatSettings { single_quote: false, ..Default::default() })
+    test_format(code, expected, FormatSettings { string_quotes: StringQuotes::Double, ..Default::default() })
+}
+
+#[test]
+pub fn test_preserve_quotes() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = "Hello, world!";
+        $b = 'Hello, world!';
+        $c = "Hello, \"world\"!";
+        $d = 'Hello, \'world\'!';
+    "#};
+
+    test_format(code, code, FormatSettings { string_quotes: StringQuotes::Preserve, ..Default::default() })
+}
+
+#[test]
+pub fn test_single_quotes_keep_escape_sequences_and_interpolation() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = "line\n";
+        $b = "tab\t";
+        $c = "null\0";
+        $d = "hex\x41";
+        $e = "unicode\u{1F600}";
+        $f = "escape\e";
+        $g = "Hello, $name!";
+        $h = "Hello, {$user->name}!";
+        $i = "Hello, ${name}!";
+    "#};
+
+    test_format(code, code, FormatSettings { string_quotes: StringQuotes::Single, ..Default::default() })
+}
+
+#[test]
+pub fn test_single_quotes_unescape_characters() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = "price: \$5";
+        $b = "\$name";
+        $c = "back\\slash";
+        $d = "C:\Windows\System32";
+        $e = "it's";
+        $f = "it's \"quoted\" twice";
+        $g = "\' and \"x\" \"y\"";
+        $h = "$";
+        $i = "{}";
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $a = 'price: $5';
+        $b = '$name';
+        $c = 'back\\slash';
+        $d = 'C:\Windows\System32';
+        $e = "it's";
+        $f = 'it\'s "quoted" twice';
+        $g = '\\\' and "x" "y"';
+        $h = '$';
+        $i = '{}';
+    "#};
+
+    test_format(code, expected, FormatSettings { string_quotes: StringQuotes::Single, ..Default::default() })
+}
+
+#[test]
+pub fn test_double_quotes_keep_characters_with_meaning() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = '$name';
+        $b = '{$name}';
+        $c = '${name}';
+        $d = 'line\n';
+        $e = 'C:\temp';
+        $f = 'price: \$5';
+        $g = 'say \"hi\"';
+    "#};
+
+    test_format(code, code, FormatSettings { string_quotes: StringQuotes::Double, ..Default::default() })
+}
+
+#[test]
+pub fn test_double_quotes_escape_characters() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        $a = 'price: $5';
+        $b = '$';
+        $c = 'back\\slash';
+        $d = 'C:\Windows\System32';
+        $e = 'it\'s';
+        $f = 'say "hi" it\'s \'me\'';
+        $g = '{ $ }';
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        $a = "price: $5";
+        $b = "$";
+        $c = "back\\slash";
+        $d = "C:\Windows\System32";
+        $e = "it's";
+        $f = "say \"hi\" it's 'me'";
+        $g = "{ $ }";
+    "#};
+
+    test_format(code, expected, FormatSettings { string_quotes: StringQuotes::Double, ..Default::default() })
 }
@@ -0,0 +1,176 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_formatter::settings::TrailingComma;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+const CODE: &str = indoc! {r#"
+    <?php
+
+    $array = ['first' => ['nested_one', 'nested_two', 'nested_three', 'nested_four', 'nested_five'], 'second' => 2,];
+    $result = some_function_with_long_name($first_argument, $second_argument, $third_argument, $fourth);
+    function variadic(string $first_parameter, string $second_parameter, string ...$remaining_parameters) {}
+    function regular(string $first_parameter, string $second_parameter, string $third_parameter,) {}
+    $closure = function () use ($first_variable, $second_variable, $third_variable, $fourth_variable) {};
+    $value = match ($subject) { 'first_condition' => 'first_result', 'second_condition' => 'second_result', };
+    $short = [1, 2, 3,];
+"#};
+
+#[test]
+pub fn test_trailing_comma_all() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        $array = [
+            'first' => [
+                'nested_one',
+                'nested_two',
+                'nested_three',
+                'nested_four',
+                'nested_five',
+            ],
+            'second' => 2,
+        ];
+        $result = some_function_with_long_name(
+            $first_argument,
+            $second_argument,
+            $third_argument,
+            $fourth,
+        );
+        function variadic(
+            string $first_parameter,
+            string $second_parameter,
+            string ...$remaining_parameters
+        ) {
+        }
+        function regular(
+            string $first_parameter,
+            string $second_parameter,
+            string $third_parameter,
+        ) {
+        }
+        $closure = function () use (
+            $first_variable,
+            $second_variable,
+            $third_variable,
+            $fourth_variable,
+        ) {};
+        $value = match ($subject) {
+            'first_condition' => 'first_result',
+            'second_condition' => 'second_result',
+        };
+        $short = [1, 2, 3];
+    "#};
+
+    test_format(
+        CODE,
+        expected,
+        FormatSettings { print_width: 80, trailing_comma: TrailingComma::All, ..Default::default() },
+    )
+}
+
+#[test]
+pub fn test_trailing_comma_multiline() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        $array = [
+            'first' => [
+                'nested_one',
+                'nested_two',
+                'nested_three',
+                'nested_four',
+                'nested_five',
+            ],
+            'second' => 2,
+        ];
+        $result = some_function_with_long_name(
+            $first_argument,
+            $second_argument,
+            $third_argument,
+            $fourth
+        );
+        function variadic(
+            string $first_parameter,
+            string $second_parameter,
+            string ...$remaining_parameters
+        ) {
+        }
+        function regular(
+            string $first_parameter,
+            string $second_parameter,
+            string $third_parameter
+        ) {
+        }
+        $closure = function () use (
+            $first_variable,
+            $second_variable,
+            $third_variable,
+            $fourth_variable
+        ) {};
+        $value = match ($subject) {
+            'first_condition' => 'first_result',
+            'second_condition' => 'second_result',
+        };
+        $short = [1, 2, 3];
+    "#};
+
+    let settings = FormatSettings { print_width: 80, trailing_comma: TrailingComma::Multiline, ..Default::default() };
+
+    test_format(CODE, expected, settings)
+}
+
+#[test]
+pub fn test_trailing_comma_never() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        $array = [
+            'first' => [
+                'nested_one',
+                'nested_two',
+                'nested_three',
+                'nested_four',
+                'nested_five'
+            ],
+            'second' => 2
+        ];
+        $result = some_function_with_long_name(
+            $first_argument,
+            $second_argument,
+            $third_argument,
+            $fourth
+        );
+        function variadic(
+            string $first_parameter,
+            string $second_parameter,
+            string ...$remaining_parameters
+        ) {
+        }
+        function regular(
+            string $first_parameter,
+            string $second_parameter,
+            string $third_parameter
+        ) {
+        }
+        $closure = function () use (
+            $first_variable,
+            $second_variable,
+            $third_variable,
+            $fourth_variable
+        ) {};
+        $value = match ($subject) {
+            'first_condition' => 'first_result',
+            'second_condition' => 'second_result'
+        };
+        $short = [1, 2, 3];
+    "#};
+
+    test_format(
+        CODE,
+        expected,
+        FormatSettings { print_width: 80, trailing_comma: TrailingComma::Never, ..Default::default() },
+    )
+}
@@ -0,0 +1,52 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+#[test]
+pub fn test_trait_use_adaptations() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        final class A {
+            use Foo, Bar { Foo::baz   insteadof   Bar ; Bar::baz as   protected   qux; quux as private; }
+            use Foo {
+                // Keep `corge` public.
+                corge as public;
+
+
+                grault as garply;
+            }
+
+            use Bar {}
+            use Baz;
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        final class A
+        {
+            use Foo, Bar {
+                Foo::baz insteadof Bar;
+                Bar::baz as protected qux;
+                quux as private;
+            }
+
+            use Foo {
+                // Keep `corge` public.
+                corge as public;
+
+                grault as garply;
+            }
+
+            use Bar {}
+            use Baz;
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
@@ -0,0 +1,225 @@
+use indoc::indoc;
+
+use mago_formatter::settings::*;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+/// Nullable, union, intersection, and DNF types in property, promoted property, parameter, and return types.
+const TYPES: &str = include_str!("../fixtures/types.php");
+
+#[test]
+pub fn test_types_are_preserved_by_default() -> Result<(), SourceError> {
+    test_format(TYPES, TYPES, FormatSettings::default())
+}
+
+#[test]
+pub fn test_spaces_around_type_operators_are_removed() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        function foo(A | B $a, A & B $b, ( A & B ) | null $c): int | null
+        {
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        function foo(A|B $a, A&B $b, (A&B)|null $c): int|null
+        {
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_nullable_types_are_written_with_question_mark() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        class Example
+        {
+            public ?Foo $nullable;
+            public ?Foo $unionNull;
+            public ?Foo $unionNullLast;
+            public (A&B)|null $dnf;
+            public int|string|null $union;
+
+            public function __construct(
+                private ?Foo $nullable,
+                protected readonly ?Foo $union,
+                public (A&B)|C|null $dnf,
+            ) {
+            }
+
+            public function parameters(?Foo $nullable, ?Foo $union, Countable&Traversable $intersection): void
+            {
+            }
+
+            public function nullable(): ?static
+            {
+            }
+
+            public function union(): null|false|string
+            {
+            }
+        }
+    "#};
+
+    test_format(
+        TYPES,
+        expected,
+        FormatSettings { nullable_type_style: NullableTypeStyle::QuestionMark, ..Default::default() },
+    )
+}
+
+#[test]
+pub fn test_nullable_types_are_written_as_unions_with_null() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        class Example
+        {
+            public Foo|null $nullable;
+            public null|Foo $unionNull;
+            public Foo|null $unionNullLast;
+            public (A&B)|null $dnf;
+            public int|string|null $union;
+
+            public function __construct(
+                private Foo|null $nullable,
+                protected readonly Foo|null $union,
+                public (A&B)|C|null $dnf,
+            ) {
+            }
+
+            public function parameters(Foo|null $nullable, null|Foo $union, Countable&Traversable $intersection): void
+            {
+            }
+
+            public function nullable(): static|null
+            {
+            }
+
+            public function union(): null|false|string
+            {
+            }
+        }
+    "#};
+
+    test_format(
+        TYPES,
+        expected,
+        FormatSettings { nullable_type_style: NullableTypeStyle::UnionNull, ..Default::default() },
+    )
+}
+
+#[test]
+pub fn test_union_types_are_sorted_with_null_last() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        class Example
+        {
+            public ?Foo $nullable;
+            public Foo|null $unionNull;
+            public Foo|null $unionNullLast;
+            public (A&B)|null $dnf;
+            public int|string|null $union;
+
+            public function __construct(
+                private ?Foo $nullable,
+                protected readonly Foo|null $union,
+                public (A&B)|C|null $dnf,
+            ) {
+            }
+
+            public function parameters(?Foo $nullable, Foo|null $union, Countable&Traversable $intersection): void
+            {
+            }
+
+            public function nullable(): ?static
+            {
+            }
+
+            public function union(): false|string|null
+            {
+            }
+        }
+    "#};
+
+    test_format(TYPES, expected, FormatSettings { sort_union_types: true, ..Default::default() })
+}
+
+#[test]
+pub fn test_typed_class_constants_are_preserved() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        final class Foo
+        {
+            const int FOO = 1;
+            public const ?string BAR = null;
+            protected const int|string BAZ = 1, QUX = 'qux';
+            final public const (A&B)|null DNF = null;
+        }
+    "#};
+
+    test_format(code, code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_typed_class_constants_are_normalized() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        final class Foo
+        {
+            public const ?string BAR = null;
+            public const string | int BAZ = 1;
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        final class Foo
+        {
+            public const string|null BAR = null;
+            public const int|string BAZ = 1;
+        }
+    "#};
+
+    test_format(
+        code,
+        expected,
+        FormatSettings {
+            nullable_type_style: NullableTypeStyle::UnionNull,
+            sort_union_types: true,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+pub fn test_dnf_types_are_spaced_with_type_spacing() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        function foo((Countable&Traversable)|array $a): (A&B)|(C&D)|null
+        {
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        function foo(( Countable & Traversable ) | array $a): ( A & B ) | ( C & D ) | null
+        {
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings { type_spacing: 1, ..Default::default() })
+}
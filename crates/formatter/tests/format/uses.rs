@@ -0,0 +1,170 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_formatter::settings::UseGroup;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+
+const CODE: &str = indoc! {r#"
+    <?php
+
+    namespace App;
+
+    use Zed\Thing;
+    use function Foo\zeta;
+    // About alpha.
+    use Alpha\Beta; // Trailing.
+    use const Foo\BAR;
+    use Foo\{Qux, Bar as Baz, function quux};
+
+    use App\Models\User, App\Models\Account;
+    use function Foo\alpha;
+    use apple\Pie;
+
+    final class A {}
+"#};
+
+#[test]
+pub fn test_sort_uses() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        namespace App;
+
+        // About alpha.
+        use Alpha\Beta; // Trailing.
+        use App\Models\Account, App\Models\User;
+        use apple\Pie;
+        use Foo\{Bar as Baz, function quux, Qux};
+        use Zed\Thing;
+
+        use function Foo\alpha;
+        use function Foo\zeta;
+
+        use const Foo\BAR;
+
+        final class A
+        {
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings { sort_uses: true, ..Default::default() })
+}
+
+#[test]
+pub fn test_sort_uses_with_groups_order() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        namespace App;
+
+        use const Foo\BAR;
+        use function Foo\alpha;
+        use function Foo\zeta;
+        // About alpha.
+        use Alpha\Beta; // Trailing.
+        use App\Models\Account, App\Models\User;
+        use apple\Pie;
+        use Foo\{Bar as Baz, function quux, Qux};
+        use Zed\Thing;
+
+        final class A
+        {
+        }
+    "#};
+
+    let settings = FormatSettings {
+        sort_uses: true,
+        use_groups: UseGroup::order(&[UseGroup::Constants, UseGroup::Functions]),
+        separate_use_groups: false,
+        ..Default::default()
+    };
+
+    test_format(CODE, expected, settings)
+}
+
+#[test]
+pub fn test_sort_and_expand_uses() -> Result<(), SourceError> {
+    let expected = indoc! {r#"
+        <?php
+
+        namespace App;
+
+        // About alpha.
+        use Alpha\Beta; // Trailing.
+        use App\Models\Account, App\Models\User;
+        use apple\Pie;
+        use Foo\Bar as Baz;
+        use Foo\Qux;
+        use Zed\Thing;
+
+        use function Foo\alpha;
+        use function Foo\quux;
+        use function Foo\zeta;
+
+        use const Foo\BAR;
+
+        final class A
+        {
+        }
+    "#};
+
+    test_format(CODE, expected, FormatSettings { sort_uses: true, expand_use_groups: true, ..Default::default() })
+}
+
+#[test]
+pub fn test_expand_uses_keeps_order() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        use Foo\{Qux, Bar};
+
+        use function Foo\{b, a};
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        use Foo\Qux;
+        use Foo\Bar;
+
+        use function Foo\b;
+        use function Foo\a;
+    "#};
+
+    test_format(code, expected, FormatSettings { expand_use_groups: true, ..Default::default() })
+}
+
+#[test]
+pub fn test_sort_uses_keeps_duplicates_and_comments_inside_statements() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+
+        namespace App {
+            use Foo\{
+                Qux, // Qux.
+                Bar,
+            };
+            use Baz;
+            use baz;
+            use Baz;
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        namespace App {
+            use Baz;
+            use Baz;
+            use baz;
+            use Foo\{
+                Qux, // Qux.
+                Bar
+            };
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings { sort_uses: true, ..Default::default() })
+}
@@ -0,0 +1,142 @@
+use indoc::indoc;
+
+use mago_formatter::settings::FormatSettings;
+use mago_source::error::SourceError;
+
+use crate::test_format;
+use crate::test_idempotency;
+
+/// Heredocs and nowdocs with interpolation, nested quotes, trailing whitespace, and mixed indentation.
+const HEREDOC: &str = include_str!("../fixtures/heredoc.php");
+
+/// A template mixing inline HTML, Blade-like directives, and PHP tags.
+const TEMPLATE: &str = include_str!("../fixtures/template.php");
+
+/// A PHAR stub, followed by control characters, PHP tags, and unterminated literals after `__halt_compiler();`.
+const HALT_COMPILER: &str = include_str!("../fixtures/halt_compiler.php");
+
+#[test]
+pub fn test_heredoc_bodies_are_kept_byte_for_byte() -> Result<(), SourceError> {
+    test_format(HEREDOC, HEREDOC, FormatSettings::default())
+}
+
+#[test]
+pub fn test_inline_html_is_kept_byte_for_byte() -> Result<(), SourceError> {
+    test_format(TEMPLATE, TEMPLATE, FormatSettings::default())
+}
+
+#[test]
+pub fn test_halt_compiler_data_is_kept_byte_for_byte() -> Result<(), SourceError> {
+    test_format(HALT_COMPILER, HALT_COMPILER, FormatSettings::default())
+}
+
+#[test]
+pub fn test_halt_compiler_closing_tag_is_kept() -> Result<(), SourceError> {
+    let code = "<?php\nfoo();\n__HALT_COMPILER()?>";
+    let expected = "<?php\n\nfoo();\n__halt_compiler() ?>";
+
+    test_format(code, expected, FormatSettings::default())?;
+    test_idempotency(code, FormatSettings::default())
+}
+
+#[test]
+pub fn test_nothing_is_printed_between_tags_and_inline_html() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php $title = "Hello"; ?>
+        <title><?= $title ?></title>
+        <?php foreach ($items as $item): ?>
+            <li><?= $item ?></li>
+        <?php endforeach; ?>
+        <p>end</p>
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+        $title = 'Hello';
+        ?>
+        <title><?= $title ?></title>
+        <?php
+        foreach ($items as $item):
+            ?>
+            <li><?= $item ?></li>
+        <?php
+        endforeach;
+        ?>
+        <p>end</p>
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_statements_containing_heredocs_are_indented() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+        function foo() {
+        if ($a) {
+        $x = <<<EOT
+          Hello {$name["first"]}
+
+            indented
+          EOT;
+        $y = foo(<<<'SQL'
+        SELECT *
+        SQL, 1);
+        }
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+            if ($a) {
+                $x = <<<EOT
+          Hello {$name["first"]}
+
+            indented
+          EOT;
+                $y = foo(<<<'SQL'
+        SELECT *
+        SQL, 1);
+            }
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings::default())
+}
+
+#[test]
+pub fn test_normalize_heredoc_indent_moves_the_body_along_with_the_closing_marker() -> Result<(), SourceError> {
+    let code = indoc! {r#"
+        <?php
+        function foo() {
+        $x = <<<EOT
+          Hello {$name["first"]}
+
+            indented
+          EOT;
+        }
+    "#};
+
+    let expected = indoc! {r#"
+        <?php
+
+        function foo()
+        {
+            $x = <<<EOT
+            Hello {$name["first"]}
+
+              indented
+            EOT;
+        }
+    "#};
+
+    test_format(code, expected, FormatSettings { normalize_heredoc_indent: true, ..Default::default() })
+}
+
+#[test]
+pub fn test_normalized_heredocs_are_idempotent() -> Result<(), SourceError> {
+    test_idempotency(HEREDOC, FormatSettings { normalize_heredoc_indent: true, ..Default::default() })
+}
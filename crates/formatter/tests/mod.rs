@@ -1,6 +1,7 @@
 use mago_formatter::settings::FormatSettings;
 use mago_interner::ThreadedInterner;
 use mago_parser::parse_source;
+use mago_parser::settings::ParserSettings;
 use mago_source::error::SourceError;
 use mago_source::SourceManager;
 
@@ -12,9 +13,9 @@ pub fn test_format(code: impl AsRef<str>, expected: &str, settings: FormatSettin
     let manager = SourceManager::new(interner.clone());
     let source_id = manager.insert_content("code.php".to_string(), code.as_ref().to_string(), true);
     let source = manager.load(&source_id)?;
-    let (program, error) = parse_source(&interner, &source);
+    let (program, errors) = parse_source(&interner, &source);
 
-    pretty_assertions::assert_eq!(error, None, "Error parsing code");
+    pretty_assertions::assert_eq!(errors, vec![], "Error parsing code");
 
     let formatted = mago_formatter::format(settings, &interner, &source, &program);
 
@@ -22,3 +23,77 @@ pub fn test_format(code: impl AsRef<str>, expected: &str, settings: FormatSettin
 
     Ok(())
 }
+
+pub fn test_format_range(
+    code: impl AsRef<str>,
+    range: std::ops::Range<usize>,
+    expected: &str,
+    settings: FormatSettings,
+) -> Result<(), SourceError> {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source_id = manager.insert_content("code.php".to_string(), code.as_ref().to_string(), true);
+    let source = manager.load(&source_id)?;
+    let (program, errors) = parse_source(&interner, &source);
+
+    pretty_assertions::assert_eq!(errors, vec![], "Error parsing code");
+
+    let formatted = mago_formatter::format_range(settings, &interner, &source, &program, range);
+
+    pretty_assertions::assert_eq!(expected, formatted, "Formatted code does not match expected");
+
+    Ok(())
+}
+
+pub fn test_idempotency(code: impl AsRef<str>, settings: FormatSettings) -> Result<(), SourceError> {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source_id = manager.insert_content("code.php".to_string(), code.as_ref().to_string(), true);
+    let source = manager.load(&source_id)?;
+    let (program, errors) = parse_source(&interner, &source);
+
+    pretty_assertions::assert_eq!(errors, vec![], "Error parsing code");
+
+    let formatted = mago_formatter::format(settings, &interner, &source, &program);
+    let reformatted = mago_formatter::reformat(settings, ParserSettings::default(), &interner, &source, &formatted);
+
+    pretty_assertions::assert_eq!(Some(formatted), reformatted, "Formatting is not idempotent");
+
+    Ok(())
+}
+
+/// Asserts that formatting the given code keeps every one of its comments, comparing the comments of the
+/// formatted code with the original ones, ignoring the indentation of their lines, and their order.
+pub fn test_comments_are_preserved(code: impl AsRef<str>, settings: FormatSettings) -> Result<(), SourceError> {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source_id = manager.insert_content("code.php".to_string(), code.as_ref().to_string(), true);
+    let source = manager.load(&source_id)?;
+    let (program, errors) = parse_source(&interner, &source);
+
+    pretty_assertions::assert_eq!(errors, vec![], "Error parsing code");
+
+    let formatted = mago_formatter::format(settings, &interner, &source, &program);
+
+    let formatted_id = manager.insert_content("formatted.php".to_string(), formatted, true);
+    let formatted_source = manager.load(&formatted_id)?;
+    let (formatted_program, errors) = parse_source(&interner, &formatted_source);
+
+    pretty_assertions::assert_eq!(errors, vec![], "Error parsing formatted code");
+
+    let comments_of = |program: &mago_ast::Program| {
+        let mut comments: Vec<String> = program
+            .comments
+            .all()
+            .iter()
+            .map(|comment| interner.lookup(&comment.value).lines().map(str::trim).collect::<Vec<_>>().join("\n"))
+            .collect();
+
+        comments.sort();
+        comments
+    };
+
+    pretty_assertions::assert_eq!(comments_of(&program), comments_of(&formatted_program), "Comments are not preserved");
+
+    Ok(())
+}
@@ -1,14 +1,40 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 
 use lasso::Key;
 use lasso::Rodeo;
 use lasso::ThreadedRodeo;
+use serde::de;
 use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
+use serde::Serializer;
+
+thread_local! {
+    /// The interner used to serialize identifiers as the strings they represent, or to deserialize them
+    /// from these strings, if any.
+    static RESOLVING_INTERNER: RefCell<Option<ThreadedInterner>> = const { RefCell::new(None) };
+
+    /// The table of strings used to serialize identifiers as indices in it, or to deserialize them, if any.
+    static STRING_TABLE: RefCell<Option<StringTable>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug)]
+enum StringTable {
+    /// The interner of the serialized identifiers, the index of each of them, and the strings they represent.
+    Serializing(ThreadedInterner, HashMap<StringIdentifier, usize>, Vec<String>),
+    /// The identifiers of the strings of the table, in the interner deserializing them.
+    Deserializing(Vec<StringIdentifier>),
+}
 
 /// An string identifier that is used to represent an interned string.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+///
+/// Identifiers are serialized as numbers, unless serialized within [`ThreadedInterner::with_resolved_serialization`],
+/// or [`ThreadedInterner::with_string_table_serialization`], and deserialized from numbers, unless deserialized
+/// within [`ThreadedInterner::with_resolved_deserialization`], or [`ThreadedInterner::with_string_table_deserialization`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct StringIdentifier(pub(crate) usize);
 
 impl StringIdentifier {
@@ -39,6 +65,66 @@ impl StringIdentifier {
     }
 }
 
+impl Serialize for StringIdentifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RESOLVING_INTERNER.with_borrow(|interner| match interner {
+            Some(interner) => serializer.serialize_str(interner.lookup(self)),
+            None => {
+                let index = STRING_TABLE.with_borrow_mut(|table| match table {
+                    Some(StringTable::Serializing(interner, indices, strings)) => {
+                        Some(*indices.entry(*self).or_insert_with(|| {
+                            strings.push(interner.lookup(self).to_owned());
+
+                            strings.len() - 1
+                        }))
+                    }
+                    _ => None,
+                });
+
+                serializer.serialize_newtype_struct("StringIdentifier", &index.unwrap_or(self.0))
+            }
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for StringIdentifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if let Some(interner) = RESOLVING_INTERNER.with_borrow(Option::clone) {
+            let string = std::borrow::Cow::<str>::deserialize(deserializer)?;
+
+            return Ok(interner.intern(string));
+        }
+
+        struct StringIdentifierVisitor;
+
+        impl<'de> de::Visitor<'de> for StringIdentifierVisitor {
+            type Value = usize;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a string identifier")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                usize::try_from(value).map_err(E::custom)
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+                usize::deserialize(deserializer)
+            }
+        }
+
+        let value = deserializer.deserialize_newtype_struct("StringIdentifier", StringIdentifierVisitor)?;
+
+        STRING_TABLE.with_borrow(|table| match table {
+            Some(StringTable::Deserializing(identifiers)) => identifiers
+                .get(value)
+                .copied()
+                .ok_or_else(|| de::Error::custom(format!("the string table has no string at index {}", value))),
+            _ => Ok(StringIdentifier(value)),
+        })
+    }
+}
+
 unsafe impl Key for StringIdentifier {
     fn into_usize(self) -> usize {
         self.0 - 1
@@ -280,6 +366,89 @@ impl ThreadedInterner {
     pub fn all(&self) -> HashSet<(StringIdentifier, &str)> {
         self.rodeo.iter().collect()
     }
+
+    /// Calls the given function, serializing the identifiers of this interner as the strings they
+    /// represent, instead of numbers, on the current thread.
+    ///
+    /// This is meant for output read by humans, or by external tools, such as a JSON dump of an AST;
+    /// the resolved strings are deserialized back into identifiers within
+    /// [`ThreadedInterner::with_resolved_deserialization`].
+    pub fn with_resolved_serialization<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _restore =
+            RestoreResolvingInterner(RESOLVING_INTERNER.with_borrow_mut(|interner| interner.replace(self.clone())));
+
+        f()
+    }
+
+    /// Calls the given function, deserializing identifiers from the strings they represent, as serialized
+    /// within [`ThreadedInterner::with_resolved_serialization`], on the current thread.
+    ///
+    /// The strings are interned into this interner.
+    pub fn with_resolved_deserialization<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _restore =
+            RestoreResolvingInterner(RESOLVING_INTERNER.with_borrow_mut(|interner| interner.replace(self.clone())));
+
+        f()
+    }
+
+    /// Calls the given function, serializing the identifiers of this interner as indices in a table of the
+    /// strings they represent, on the current thread.
+    ///
+    /// This is meant for compact data read by another interner, or another process, each string being
+    /// written once, no matter how many identifiers represent it.
+    ///
+    /// # Returns
+    ///
+    /// The result of the function, along with the table of strings, which is needed to deserialize the
+    /// identifiers within [`ThreadedInterner::with_string_table_deserialization`].
+    pub fn with_string_table_serialization<T>(&self, f: impl FnOnce() -> T) -> (T, Vec<String>) {
+        let previous = STRING_TABLE
+            .with_borrow_mut(|table| table.replace(StringTable::Serializing(self.clone(), HashMap::new(), vec![])));
+        let _restore = RestoreStringTable(previous);
+
+        let result = f();
+
+        let strings = match STRING_TABLE.with_borrow_mut(Option::take) {
+            Some(StringTable::Serializing(_, _, strings)) => strings,
+            _ => vec![],
+        };
+
+        (result, strings)
+    }
+
+    /// Calls the given function, deserializing the identifiers serialized within
+    /// [`ThreadedInterner::with_string_table_serialization`], on the current thread.
+    ///
+    /// The strings of the given table are interned into this interner.
+    pub fn with_string_table_deserialization<T>(&self, strings: &[impl AsRef<str>], f: impl FnOnce() -> T) -> T {
+        let identifiers = strings.iter().map(|string| self.intern(string.as_ref())).collect();
+        let previous = STRING_TABLE.with_borrow_mut(|table| table.replace(StringTable::Deserializing(identifiers)));
+        let _restore = RestoreStringTable(previous);
+
+        f()
+    }
+}
+
+/// Restores the previous resolving interner of the current thread when dropped.
+struct RestoreResolvingInterner(Option<ThreadedInterner>);
+
+impl Drop for RestoreResolvingInterner {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+
+        RESOLVING_INTERNER.with_borrow_mut(|interner| *interner = previous);
+    }
+}
+
+/// Restores the previous table of strings of the current thread when dropped.
+struct RestoreStringTable(Option<StringTable>);
+
+impl Drop for RestoreStringTable {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+
+        STRING_TABLE.with_borrow_mut(|table| *table = previous);
+    }
 }
 
 impl std::fmt::Display for StringIdentifier {
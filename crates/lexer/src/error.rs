@@ -3,7 +3,6 @@ use serde::Serialize;
 
 use mago_reporting::Annotation;
 use mago_reporting::Issue;
-use mago_span::HasPosition;
 use mago_span::HasSpan;
 use mago_span::Position;
 use mago_span::Span;
@@ -13,6 +12,20 @@ pub enum SyntaxError {
     UnexpectedToken(u8, Position),
     UnrecognizedToken(u8, Position),
     UnexpectedEndOfFile(Position),
+    InvalidNumericLiteral(NumericLiteralError, Span),
+}
+
+/// The reason a numeric literal is invalid.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum NumericLiteralError {
+    /// A `_` separator that is not placed between two digits, such as in `1__0`, `1_`, or `0x_1`.
+    MisplacedSeparator,
+    /// A digit that is not valid in the base of the literal, such as `9` in the legacy octal literal `09`.
+    ///
+    /// The digit, and the base, are included.
+    InvalidDigit(u8, u8),
+    /// A base prefix that is not followed by any digit, such as `0x`.
+    MissingDigits,
 }
 
 impl HasSpan for SyntaxError {
@@ -21,6 +34,7 @@ impl HasSpan for SyntaxError {
             Self::UnexpectedToken(_, p) => *p,
             Self::UnrecognizedToken(_, p) => *p,
             Self::UnexpectedEndOfFile(p) => *p,
+            Self::InvalidNumericLiteral(_, span) => return *span,
         };
 
         Span::new(position, Position { offset: position.offset + 1, ..position })
@@ -33,6 +47,22 @@ impl std::fmt::Display for SyntaxError {
             Self::UnexpectedToken(token, _) => &format!("Unexpected token `{}` (0x{:02X})", *token as char, token),
             Self::UnrecognizedToken(token, _) => &format!("Unrecognised token `{}` (0x{:02X})", *token as char, token),
             Self::UnexpectedEndOfFile(_) => "Unexpected end of file",
+            Self::InvalidNumericLiteral(NumericLiteralError::MisplacedSeparator, _) => {
+                "Invalid numeric literal: `_` separators must be placed between two digits"
+            }
+            Self::InvalidNumericLiteral(NumericLiteralError::InvalidDigit(digit, base), _) => {
+                let base = match base {
+                    2 => "binary",
+                    8 => "octal",
+                    16 => "hexadecimal",
+                    _ => "decimal",
+                };
+
+                &format!("Invalid numeric literal: `{}` is not a valid {} digit", *digit as char, base)
+            }
+            Self::InvalidNumericLiteral(NumericLiteralError::MissingDigits, _) => {
+                "Invalid numeric literal: expected digits after the base prefix"
+            }
         };
 
         write!(f, "{}", message)
@@ -43,8 +73,7 @@ impl std::error::Error for SyntaxError {}
 
 impl From<SyntaxError> for Issue {
     fn from(error: SyntaxError) -> Issue {
-        let position = error.position();
-        let span = Span::new(position, Position { offset: position.offset + 1, ..position });
+        let span = error.span();
 
         Issue::error(error.to_string()).with_annotation(Annotation::primary(span).with_message("Syntax error."))
     }
@@ -1,4 +1,3 @@
 pub(crate) mod consts;
 pub(crate) mod macros;
 pub(crate) mod mode;
-pub(crate) mod utils;
@@ -2,30 +2,26 @@ use std::fmt::Debug;
 use std::mem;
 
 use mago_interner::ThreadedInterner;
+use mago_source::Source;
 use mago_span::Position;
 use mago_span::Span;
 use mago_token::DocumentKind;
 use mago_token::Token;
 use mago_token::TokenKind;
 
+use crate::error::NumericLiteralError;
 use crate::error::SyntaxError;
 use crate::input::Input;
-use crate::internal::macros::float_exponent;
-use crate::internal::macros::float_separator;
 use crate::internal::macros::number_separator;
-use crate::internal::macros::number_sign;
 use crate::internal::macros::part_of_identifier;
 use crate::internal::macros::start_of_binary_number;
-use crate::internal::macros::start_of_float_number;
 use crate::internal::macros::start_of_hexadecimal_number;
 use crate::internal::macros::start_of_identifier;
 use crate::internal::macros::start_of_number;
 use crate::internal::macros::start_of_octal_number;
-use crate::internal::macros::start_of_octal_or_float_number;
 use crate::internal::mode::HaltStage;
 use crate::internal::mode::Interpolation;
 use crate::internal::mode::LexerMode;
-use crate::internal::utils::NumberKind;
 
 pub mod error;
 pub mod input;
@@ -49,6 +45,9 @@ pub struct Lexer<'a, 'i> {
     input: Input<'a>,
     mode: LexerMode<'a>,
     interpolating: bool,
+    short_open_tags: bool,
+    /// The errors recovered from while tokenizing, such as invalid numeric literals, see [`Lexer::take_errors`].
+    errors: Vec<SyntaxError>,
 }
 
 impl<'a, 'i> Lexer<'a, 'i> {
@@ -63,7 +62,7 @@ impl<'a, 'i> Lexer<'a, 'i> {
     ///
     /// A new `Lexer` instance that reads from the provided byte slice.
     pub fn new(interner: &'i ThreadedInterner, input: Input<'a>) -> Lexer<'a, 'i> {
-        Lexer { interner, input, mode: LexerMode::Inline, interpolating: false }
+        Lexer { interner, input, mode: LexerMode::Inline, interpolating: false, short_open_tags: false, errors: vec![] }
     }
 
     /// Creates a new `Lexer` instance for parsing a script block.
@@ -77,7 +76,32 @@ impl<'a, 'i> Lexer<'a, 'i> {
     ///
     /// A new `Lexer` instance that reads from the provided byte slice.
     pub fn scripting(interner: &'i ThreadedInterner, input: Input<'a>) -> Lexer<'a, 'i> {
-        Lexer { interner, input, mode: LexerMode::Script, interpolating: false }
+        Lexer { interner, input, mode: LexerMode::Script, interpolating: false, short_open_tags: false, errors: vec![] }
+    }
+
+    /// Sets whether `<?` is an opening tag, as with the `short_open_tag` ini setting of PHP.
+    ///
+    /// Short open tags are disabled by default, `<?` is then part of the inline text, while `<?php` and `<?=`
+    /// are always opening tags.
+    ///
+    /// # Parameters
+    ///
+    /// - `short_open_tags`: Whether `<?` is an opening tag.
+    ///
+    /// # Returns
+    ///
+    /// The `Lexer` instance, with short open tags enabled or disabled.
+    pub fn with_short_open_tags(mut self, short_open_tags: bool) -> Lexer<'a, 'i> {
+        self.short_open_tags = short_open_tags;
+        self
+    }
+
+    /// Takes the errors recovered from since the last call, which did not stop the lexer.
+    ///
+    /// An invalid numeric literal, such as `1__0`, is reported here, while the literal is still returned
+    /// as a token, so that the code following it can be tokenized.
+    pub fn take_errors(&mut self) -> Vec<SyntaxError> {
+        std::mem::take(&mut self.errors)
     }
 
     /// Check if the lexer has reached the end of the input.
@@ -155,7 +179,7 @@ impl<'a, 'i> Lexer<'a, 'i> {
         match self.mode {
             LexerMode::Inline => {
                 let start = self.input.position();
-                if self.input.is_at(b"<?", false) {
+                if is_opening_tag(self.input.read(5), self.short_open_tags) {
                     let (kind, buffer) = if self.input.is_at(b"<?php", true) {
                         (TokenKind::OpenTag, self.input.consume(5))
                     } else if self.input.is_at(b"<?=", false) {
@@ -178,20 +202,26 @@ impl<'a, 'i> Lexer<'a, 'i> {
 
                     self.token(TokenKind::InlineShebang, buffer, start, end)
                 } else {
-                    let buffer = self.input.consume_until(b"<?", false);
+                    let mut length = 1;
+                    while !self.input.peek(length, 1).is_empty()
+                        && !is_opening_tag(self.input.peek(length, 5), self.short_open_tags)
+                    {
+                        length += 1;
+                    }
+
+                    let buffer = self.input.consume(length);
                     let end = self.input.position();
 
                     self.token(TokenKind::InlineText, buffer, start, end)
                 }
             }
             LexerMode::Script => {
+                let start = self.input.position();
                 let whitespaces = self.input.consume_whitespaces();
                 if !whitespaces.is_empty() {
-                    let start = self.input.position();
-                    let buffer = whitespaces;
                     let end = self.input.position();
 
-                    return self.token(TokenKind::Whitespace, buffer, start, end);
+                    return self.token(TokenKind::Whitespace, whitespaces, start, end);
                 }
 
                 let mut document_label: &[u8] = &[];
@@ -425,10 +455,16 @@ impl<'a, 'i> Lexer<'a, 'i> {
                                     ended_with_slash = true;
                                     break;
                                 }
-                                // special case for `private(set)`
-                                [b'(', ..] if length == 7 => {
-                                    if self.input.is_at(b"private(set)", true) {
-                                        break 'identifier (TokenKind::PrivateSet, 7 + 5);
+                                // special case for `public(set)`, `protected(set)`, and `private(set)`
+                                [b'(', ..] if matches!(length, 6 | 7 | 9) => {
+                                    for (value, kind) in [
+                                        (b"public(set)".as_slice(), TokenKind::PublicSet),
+                                        (b"private(set)".as_slice(), TokenKind::PrivateSet),
+                                        (b"protected(set)".as_slice(), TokenKind::ProtectedSet),
+                                    ] {
+                                        if value.len() == length + 5 && self.input.is_at(value, true) {
+                                            break 'identifier (kind, value.len());
+                                        }
                                     }
 
                                     break;
@@ -492,72 +528,18 @@ impl<'a, 'i> Lexer<'a, 'i> {
                             (TokenKind::Identifier, length)
                         }
                     }
-                    [b'.', start_of_number!(), ..] => {
-                        let mut length = read_digits_of_base(&self.input, 2, 10);
-                        if let float_exponent!() = self.input.peek(length, 1) {
-                            length += 1;
-                            if let number_sign!() = self.input.peek(length, 1) {
-                                length += 1;
-                            }
-
-                            length = read_digits_of_base(&self.input, length, 10);
-                        }
-
-                        (TokenKind::LiteralFloat, length)
-                    }
-                    [start_of_number!(), ..] => 'number: {
-                        let mut length = 1;
+                    [start_of_number!(), ..] | [b'.', start_of_number!(), ..] => {
+                        match read_number(&self.input, self.interpolating) {
+                            Ok(number) => number,
+                            Err((error, start, end)) => {
+                                let position = self.input.position();
+                                let span = Span::new(position.forward(start), position.forward(end));
 
-                        let (base, kind): (u8, NumberKind) = match self.input.read(2) {
-                            start_of_binary_number!() => {
-                                length += 1;
-
-                                (2, NumberKind::Integer)
-                            }
-                            start_of_octal_number!() => {
-                                length += 1;
-
-                                (8, NumberKind::Integer)
-                            }
-                            start_of_hexadecimal_number!() => {
-                                length += 1;
+                                self.errors.push(SyntaxError::InvalidNumericLiteral(error, span));
 
-                                (16, NumberKind::Integer)
+                                read_invalid_number(&self.input, end)
                             }
-                            start_of_octal_or_float_number!() => (10, NumberKind::OctalOrFloat),
-                            start_of_float_number!() => (10, NumberKind::Float),
-                            _ => (10, NumberKind::IntegerOrFloat),
-                        };
-
-                        if kind != NumberKind::Float {
-                            length = read_digits_of_base(&self.input, length, base);
-
-                            if kind == NumberKind::Integer {
-                                break 'number (TokenKind::LiteralInteger, length);
-                            }
-                        }
-
-                        let is_float = matches!(self.input.peek(length, 3), float_separator!());
-
-                        if !is_float {
-                            break 'number (TokenKind::LiteralInteger, length);
                         }
-
-                        if let [b'.'] = self.input.peek(length, 1) {
-                            length += 1;
-                            length = read_digits_of_base(&self.input, length, 10);
-                        }
-
-                        if let float_exponent!() = self.input.peek(length, 1) {
-                            length += 1;
-                            if let number_sign!() = self.input.peek(length, 1) {
-                                length += 1;
-                            }
-
-                            length = read_digits_of_base(&self.input, length, 10);
-                        }
-
-                        (TokenKind::LiteralFloat, length)
                     }
                     [b'.', ..] => (TokenKind::Dot, 1),
                     [unknown_byte, ..] => {
@@ -907,10 +889,7 @@ impl<'a, 'i> Lexer<'a, 'i> {
 
                             self.token(TokenKind::CloseTag, buffer, start, end)
                         } else {
-                            return Some(Err(SyntaxError::UnexpectedToken(
-                                self.input.read(1)[0],
-                                self.input.position(),
-                            )));
+                            Some(Err(SyntaxError::UnexpectedToken(self.input.read(1)[0], self.input.position())))
                         }
                     }
                     _ => unreachable!(),
@@ -951,6 +930,56 @@ impl<'a, 'i> Lexer<'a, 'i> {
     }
 }
 
+/// Tokenizes the given source, returning every token, including whitespace, comments, and inline text.
+///
+/// The spans of the tokens cover the content of the source without gaps or overlaps, so concatenating the
+/// text of the tokens reproduces the content byte for byte.
+///
+/// # Parameters
+///
+/// - `interner`: The interner to use for string interning.
+/// - `source`: The source to tokenize.
+/// - `short_open_tags`: Whether `<?` is an opening tag, see [`Lexer::with_short_open_tags`].
+///
+/// # Returns
+///
+/// The tokens of the source, and the syntax errors found while tokenizing it. The last error may have stopped
+/// the lexer, in which case the tokens cover the content up to the position of that error.
+pub fn lex_lossless(
+    interner: &ThreadedInterner,
+    source: &Source,
+    short_open_tags: bool,
+) -> (Vec<Token>, Vec<SyntaxError>) {
+    let content = interner.lookup(&source.content);
+    let input = Input::new(source.identifier, content.as_bytes());
+    let mut lexer = Lexer::new(interner, input).with_short_open_tags(short_open_tags);
+
+    let mut tokens = vec![];
+    while let Some(result) = lexer.advance() {
+        match result {
+            Ok(token) => tokens.push(token),
+            Err(error) => {
+                let mut errors = lexer.take_errors();
+                errors.push(error);
+
+                return (tokens, errors);
+            }
+        }
+    }
+
+    (tokens, lexer.take_errors())
+}
+
+/// Returns whether the given bytes start with an opening tag, which `<?` only is when short open tags are enabled.
+#[inline]
+fn is_opening_tag(bytes: &[u8], short_open_tags: bool) -> bool {
+    match bytes {
+        [b'<', b'?', b'=', ..] => true,
+        [b'<', b'?', rest @ ..] => short_open_tags || rest.eq_ignore_ascii_case(b"php"),
+        _ => false,
+    }
+}
+
 fn matches_start_of_heredoc_document(input: &Input) -> bool {
     let mut length = 3;
     let mut whitespaces = 0;
@@ -1200,26 +1229,111 @@ fn read_literal_string(input: &Input, quote: &u8) -> (TokenKind, usize) {
     }
 }
 
-fn read_digits_of_base(input: &Input, offset: usize, base: u8) -> usize {
-    if 16 == base {
-        read_digits_with(input, offset, u8::is_ascii_hexdigit)
-    } else {
-        let max = b'0' + base;
-        let range = b'0'..max;
+/// Reads the numeric literal at the start of the input, returning its kind and length.
+///
+/// Literals are read as far as they look numeric, so that an invalid literal, such as `1__0`, `0x`, or the
+/// legacy octal `09`, is reported as a whole, along with the offsets of the offending bytes.
+///
+/// While interpolating, a literal such as `09` is accepted, as it is a valid string offset in `"$a[09]"`.
+fn read_number(input: &Input, interpolating: bool) -> Result<(TokenKind, usize), (NumericLiteralError, usize, usize)> {
+    let base = match input.read(2) {
+        start_of_binary_number!() => 2,
+        start_of_octal_number!() => 8,
+        start_of_hexadecimal_number!() => 16,
+        _ => 10,
+    };
+
+    if base != 10 {
+        let length = read_digits_of_base(input, 2, base)?;
+        if length == 2 {
+            return Err((NumericLiteralError::MissingDigits, 0, 2));
+        }
 
-        read_digits_with(input, offset, |byte| range.contains(byte))
+        return Ok((TokenKind::LiteralInteger, length));
+    }
+
+    let mut length = read_digits_of_base(input, 0, 10)?;
+    let mut is_float = false;
+    if let [b'.'] = input.peek(length, 1) {
+        is_float = true;
+        length = read_digits_of_base(input, length + 1, 10)?;
+    }
+
+    let exponent = match input.peek(length, 3) {
+        [b'e' | b'E', b'-' | b'+', b'0'..=b'9'] => 2,
+        [b'e' | b'E', b'0'..=b'9', ..] => 1,
+        _ => 0,
+    };
+
+    if exponent > 0 {
+        is_float = true;
+        length = read_digits_of_base(input, length + exponent, 10)?;
+    }
+
+    if is_float {
+        return Ok((TokenKind::LiteralFloat, length));
+    }
+
+    if interpolating {
+        return Ok((TokenKind::LiteralInteger, length));
+    }
+
+    // An integer starting with `0` is a legacy octal literal, such as `0755`.
+    if let [b'0', digits @ ..] = input.peek(0, length) {
+        if let Some(index) = digits.iter().position(|digit| matches!(digit, b'8' | b'9')) {
+            return Err((NumericLiteralError::InvalidDigit(digits[index], 8), index + 1, index + 2));
+        }
+    }
+
+    Ok((TokenKind::LiteralInteger, length))
+}
+
+/// Reads the rest of an invalid numeric literal, whose error ends at the given offset, returning its kind and length.
+///
+/// The digits, and separators, following the error are part of the literal, so that it is tokenized as a whole.
+fn read_invalid_number(input: &Input, from: usize) -> (TokenKind, usize) {
+    let prefixed =
+        matches!(input.read(2), start_of_binary_number!() | start_of_octal_number!() | start_of_hexadecimal_number!());
+    let hexadecimal = matches!(input.read(2), start_of_hexadecimal_number!());
+
+    let mut length = from;
+    while let [byte] = input.peek(length, 1) {
+        if !(byte.is_ascii_digit() || *byte == b'_' || (hexadecimal && byte.is_ascii_hexdigit())) {
+            break;
+        }
+
+        length += 1;
+    }
+
+    if !prefixed && input.peek(0, length).iter().any(|byte| matches!(byte, b'.' | b'e' | b'E')) {
+        (TokenKind::LiteralFloat, length)
+    } else {
+        (TokenKind::LiteralInteger, length)
     }
 }
 
-fn read_digits_with<F: Fn(&u8) -> bool>(input: &Input, offset: usize, is_digit: F) -> usize {
+/// Reads the digits, and separators, starting at the given offset, returning the offset following them.
+///
+/// Any decimal digit is read, so that a digit that is not valid in the given base is reported rather than
+/// left for the next token.
+fn read_digits_of_base(input: &Input, offset: usize, base: u8) -> Result<usize, (NumericLiteralError, usize, usize)> {
+    let is_digit = |byte: &u8| if base == 16 { byte.is_ascii_hexdigit() } else { byte.is_ascii_digit() };
+
     let mut length = offset;
     loop {
         match input.peek(length, 2) {
-            [b, ..] if is_digit(b) => {
+            [digit, ..] if is_digit(digit) => {
+                if base < 10 && digit - b'0' >= base {
+                    return Err((NumericLiteralError::InvalidDigit(*digit, base), length, length + 1));
+                }
+
                 length += 1;
             }
-            [number_separator!(), b] if is_digit(b) => {
-                length += 2;
+            [number_separator!(), following] if length > offset && is_digit(following) => {
+                length += 1;
+            }
+            [number_separator!(), ..] => {
+                return Err((NumericLiteralError::MisplacedSeparator, length, length + 1));
             }
             _ => {
                 break;
@@ -1227,7 +1341,7 @@ fn read_digits_with<F: Fn(&u8) -> bool>(input: &Input, offset: usize, is_digit:
         }
     }
 
-    length
+    Ok(length)
 }
 
 fn read_until_end_of_variable_interpolation(input: &Input, from: usize) -> usize {
@@ -0,0 +1,21 @@
+#!/usr/bin/env php
+<?php
+
+declare(strict_types=1);
+
+/**
+ * A docblock.
+ *
+ * @return void
+ */
+function foo(): void // A single line comment.
+{
+    # A hash comment.
+    /* A multi-line
+       comment. */
+    $a = 0x1F_FF + 0b1010 + 0o17 + 017 + 1_000.5e-3 + .5;
+    $b = $a?->b ?? $a <=> 1 ** 2 <<= 3;
+    $c = (int) $a . (string)$b;
+    #[Attribute] fn() => yield from [];
+}
+__halt_compiler(); data that is not code <?php echo 'still data';
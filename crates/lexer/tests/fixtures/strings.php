@@ -0,0 +1,16 @@
+<?php
+
+$name = 'World';
+$greeting = "Hello, $name! {$user->name} ${legacy} $items[0] $items[key] $object->property \$escaped \u{1F418}";
+$command = `ls -la $directory`;
+
+$heredoc = <<<EOT
+    Hello, $name.
+      {$user->profile['name']} and {$items[1 + 2]}
+    EOT;
+
+$nowdoc = <<<'EOT'
+No $interpolation {$here}.
+EOT;
+
+$multibyte = 'é 世界 😀';
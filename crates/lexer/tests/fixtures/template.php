@@ -0,0 +1,9 @@
+<!DOCTYPE html>
+<html>
+<body>
+    <?php foreach ($items as $item): ?>
+        <li class="<?= $item->class ?>"><?php echo htmlspecialchars($item->name); ?></li>
+    <?php endforeach ?>
+    <? echo 'not a tag while short open tags are disabled'; ?>
+</body>
+</html>
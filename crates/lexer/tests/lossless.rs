@@ -0,0 +1,55 @@
+use mago_interner::ThreadedInterner;
+use mago_lexer::lex_lossless;
+use mago_source::SourceManager;
+
+#[test]
+fn test_tokens_cover_the_input_byte_for_byte() {
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let mut paths = std::fs::read_dir(&fixtures).unwrap().map(|entry| entry.unwrap().path()).collect::<Vec<_>>();
+    paths.sort();
+
+    assert!(!paths.is_empty(), "no fixtures found in {}", fixtures.display());
+
+    for path in paths {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let content = std::fs::read_to_string(&path).unwrap();
+        let source_id = manager.insert_content(path.display().to_string(), content.clone(), true);
+        let source = manager.load(&source_id).unwrap();
+
+        let (tokens, errors) = lex_lossless(&interner, &source, false);
+        assert!(errors.is_empty(), "unexpected syntax errors in {}: {:?}", path.display(), errors);
+
+        let mut offset = 0;
+        let mut reproduced = String::new();
+        for token in tokens.iter() {
+            let text = interner.lookup(&token.value);
+
+            assert_eq!(offset, token.span.start.offset, "gap or overlap before {:?} in {}", token, path.display());
+            assert_eq!(&content[token.span.to_range()], text, "text of {:?} in {}", token, path.display());
+
+            offset = token.span.end.offset;
+            reproduced.push_str(text);
+        }
+
+        assert_eq!(content.len(), offset, "tokens do not reach the end of {}", path.display());
+        assert_eq!(content, reproduced, "tokens do not reproduce {}", path.display());
+    }
+}
+
+#[test]
+fn test_tokens_preceding_a_syntax_error_are_returned() {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let content = "<?php $a = 1__0; /* unterminated";
+    let source_id = manager.insert_content("code.php".to_string(), content.to_string(), true);
+    let source = manager.load(&source_id).unwrap();
+
+    let (tokens, errors) = lex_lossless(&interner, &source, false);
+
+    // The invalid numeric literal is recovered from, while the unterminated comment stops the lexer.
+    assert_eq!(2, errors.len());
+    assert_eq!(0, tokens[0].span.start.offset);
+    assert_eq!("<?php $a = 1__0; ", &content[..tokens.last().unwrap().span.end.offset]);
+}
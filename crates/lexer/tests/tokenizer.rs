@@ -6,6 +6,7 @@ use mago_source::SourceIdentifier;
 use mago_token::DocumentKind;
 use mago_token::TokenKind;
 
+use mago_lexer::error::NumericLiteralError;
 use mago_lexer::error::SyntaxError;
 use mago_lexer::Lexer;
 
@@ -368,6 +369,35 @@ fn test_keywords() -> Result<(), SyntaxError> {
     })
 }
 
+#[test]
+fn test_set_visibility_modifiers() -> Result<(), SyntaxError> {
+    let code = b"<?php public(set) PROTECTED(set) private(set) public (set) private(get)";
+    let expected = vec![
+        TokenKind::OpenTag,
+        TokenKind::Whitespace,
+        TokenKind::PublicSet,
+        TokenKind::Whitespace,
+        TokenKind::ProtectedSet,
+        TokenKind::Whitespace,
+        TokenKind::PrivateSet,
+        TokenKind::Whitespace,
+        TokenKind::Public,
+        TokenKind::Whitespace,
+        TokenKind::LeftParenthesis,
+        TokenKind::Identifier,
+        TokenKind::RightParenthesis,
+        TokenKind::Whitespace,
+        TokenKind::Private,
+        TokenKind::LeftParenthesis,
+        TokenKind::Identifier,
+        TokenKind::RightParenthesis,
+    ];
+
+    test_lexer(code, expected).map_err(|err| {
+        panic!("unexpected error: {}", err);
+    })
+}
+
 #[test]
 fn test_halt() -> Result<(), SyntaxError> {
     let code = b"hello <?= echo + __halt_compiler ( ) ;  echo 'unreachable';";
@@ -394,6 +424,80 @@ fn test_halt() -> Result<(), SyntaxError> {
     })
 }
 
+#[test]
+fn test_halt_with_binary_data() -> Result<(), SyntaxError> {
+    let code = "<?php __halt_compiler() ?>\x00\x01\x7f<?php echo 'not code'; ?>\n\u{fffd}é".as_bytes();
+    let expected = vec![
+        TokenKind::OpenTag,
+        TokenKind::Whitespace,
+        TokenKind::HaltCompiler,
+        TokenKind::LeftParenthesis,
+        TokenKind::RightParenthesis,
+        TokenKind::Whitespace,
+        TokenKind::CloseTag,
+        TokenKind::InlineText,
+    ];
+
+    test_lexer(code, expected).map_err(|err| {
+        panic!("unexpected error: {}", err);
+    })
+}
+
+#[test]
+fn test_short_open_tags_disabled() -> Result<(), SyntaxError> {
+    let code = b"<p><? echo 1; ?></p><?= 2 ?><?PHP echo 3;";
+    let expected = vec![
+        TokenKind::InlineText,
+        TokenKind::EchoTag,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Whitespace,
+        TokenKind::CloseTag,
+        TokenKind::OpenTag,
+        TokenKind::Whitespace,
+        TokenKind::Echo,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Semicolon,
+    ];
+
+    test_lexer(code, expected).map_err(|err| {
+        panic!("unexpected error: {}", err);
+    })
+}
+
+#[test]
+fn test_short_open_tags_enabled() -> Result<(), SyntaxError> {
+    let code = b"<p><? echo 1; ?></p><?= 2 ?><?php echo 3;";
+    let expected = vec![
+        TokenKind::InlineText,
+        TokenKind::ShortOpenTag,
+        TokenKind::Whitespace,
+        TokenKind::Echo,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Semicolon,
+        TokenKind::Whitespace,
+        TokenKind::CloseTag,
+        TokenKind::InlineText,
+        TokenKind::EchoTag,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Whitespace,
+        TokenKind::CloseTag,
+        TokenKind::OpenTag,
+        TokenKind::Whitespace,
+        TokenKind::Echo,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Semicolon,
+    ];
+
+    test_lexer_with_short_open_tags(code, true, expected).map_err(|err| {
+        panic!("unexpected error: {}", err);
+    })
+}
+
 #[test]
 fn test_identifiers() -> Result<(), SyntaxError> {
     let code = b"hello <?php FooBar Foo\\Bar Foo\\\\Bar::class;";
@@ -464,6 +568,138 @@ fn test_numbers() -> Result<(), SyntaxError> {
     })
 }
 
+#[test]
+fn test_numbers_with_separators() -> Result<(), SyntaxError> {
+    let code = b"<?php 1_000 1_000.5_5 1e1_0 0x1F_FF 0b1_0 0o1_7 0_7 08.5 .5_5;";
+    let expected = vec![
+        TokenKind::OpenTag,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Whitespace,
+        TokenKind::LiteralFloat,
+        TokenKind::Whitespace,
+        TokenKind::LiteralFloat,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Whitespace,
+        TokenKind::LiteralInteger,
+        TokenKind::Whitespace,
+        TokenKind::LiteralFloat,
+        TokenKind::Whitespace,
+        TokenKind::LiteralFloat,
+        TokenKind::Semicolon,
+    ];
+
+    test_lexer(code, expected).map_err(|err| {
+        panic!("unexpected error: {}", err);
+    })
+}
+
+#[test]
+fn test_invalid_numbers() {
+    let cases: [(&str, NumericLiteralError, std::ops::Range<usize>); 9] = [
+        ("1__0", NumericLiteralError::MisplacedSeparator, 1..2),
+        ("1_", NumericLiteralError::MisplacedSeparator, 1..2),
+        ("1._5", NumericLiteralError::MisplacedSeparator, 2..3),
+        ("0x_1", NumericLiteralError::MisplacedSeparator, 2..3),
+        ("0x", NumericLiteralError::MissingDigits, 0..2),
+        ("0b", NumericLiteralError::MissingDigits, 0..2),
+        ("09", NumericLiteralError::InvalidDigit(b'9', 8), 1..2),
+        ("0b102", NumericLiteralError::InvalidDigit(b'2', 2), 4..5),
+        ("0o78", NumericLiteralError::InvalidDigit(b'8', 8), 3..4),
+    ];
+
+    for (literal, expected_error, expected_range) in cases {
+        match lex_number(literal) {
+            Err(SyntaxError::InvalidNumericLiteral(error, span)) => {
+                assert_eq!(expected_error, error, "error of `{}`", literal);
+                assert_eq!(expected_range, span.start.offset - 6..span.end.offset - 6, "span of `{}`", literal);
+            }
+            result => panic!("expected `{}` to be invalid, got {:?}", literal, result),
+        }
+    }
+}
+
+#[test]
+fn test_invalid_numbers_are_tokenized_as_a_whole() -> Result<(), SyntaxError> {
+    let interner = ThreadedInterner::new();
+    let code = b"<?php 1__0; 0x_f; 1.2__3; 0b102;";
+    let input = Input::new(SourceIdentifier::dummy(), code);
+    let mut lexer = Lexer::new(&interner, input);
+
+    let mut literals = vec![];
+    while let Some(result) = lexer.advance() {
+        let token = result?;
+        if matches!(token.kind, TokenKind::LiteralInteger | TokenKind::LiteralFloat) {
+            literals.push((token.kind, interner.lookup(&token.value).to_string()));
+        }
+    }
+
+    assert_eq!(
+        vec![
+            (TokenKind::LiteralInteger, "1__0".to_string()),
+            (TokenKind::LiteralInteger, "0x_f".to_string()),
+            (TokenKind::LiteralFloat, "1.2__3".to_string()),
+            (TokenKind::LiteralInteger, "0b102".to_string()),
+        ],
+        literals
+    );
+    assert_eq!(4, lexer.take_errors().len());
+
+    Ok(())
+}
+
+/// Checks every integer literal made of a base prefix, and up to five digits and separators, against the
+/// rules of PHP: separators go between two digits, a prefix is followed by at least one digit, and digits
+/// are valid in the base of the literal, which is octal for legacy literals starting with `0`.
+#[test]
+fn test_generated_integer_literals() {
+    let prefixes = [("", 10), ("0x", 16), ("0b", 2), ("0o", 8)];
+
+    for (prefix, base) in prefixes {
+        // Letters are only part of hexadecimal literals.
+        let alphabet: &[char] = if base == 16 { &['0', '1', '9', 'a', 'F', '_'] } else { &['0', '1', '7', '9', '_'] };
+
+        let mut bodies = vec![String::new()];
+        for _ in 0..5 {
+            let longer: Vec<String> = bodies
+                .iter()
+                .filter(|body| body.len() == bodies.last().unwrap().len())
+                .flat_map(|body| alphabet.iter().map(move |character| format!("{}{}", body, character)))
+                .collect();
+
+            bodies.extend(longer);
+        }
+
+        for body in bodies {
+            // A decimal literal starts with a digit.
+            if base == 10 && !body.starts_with(|c: char| c.is_ascii_digit()) {
+                continue;
+            }
+
+            let literal = format!("{}{}", prefix, body);
+            let digit_base = if base == 10 && body.len() > 1 && body.starts_with('0') { 8 } else { base };
+            let is_valid = !body.is_empty()
+                && !body.starts_with('_')
+                && !body.ends_with('_')
+                && !body.contains("__")
+                && body.chars().filter(|c| *c != '_').all(|c| c.to_digit(16).is_some_and(|d| d < digit_base));
+
+            match lex_number(&literal) {
+                Ok(kinds) if is_valid => {
+                    assert_eq!(vec![TokenKind::LiteralInteger], kinds, "tokens of `{}`", literal);
+                }
+                Err(SyntaxError::InvalidNumericLiteral(..)) if !is_valid => {}
+                result => panic!("unexpected result for `{}`: {:?}", literal, result),
+            }
+        }
+    }
+}
+
 #[test]
 fn test_emojis() -> Result<(), SyntaxError> {
     let code = "hello <?php final readonly class 🐘 { const 🦀 = 🐱 + 🦊; }".as_bytes();
@@ -895,9 +1131,17 @@ fn test_escape_in_string() -> Result<(), SyntaxError> {
 }
 
 fn test_lexer(code: &[u8], expected_kinds: Vec<TokenKind>) -> Result<(), SyntaxError> {
+    test_lexer_with_short_open_tags(code, false, expected_kinds)
+}
+
+fn test_lexer_with_short_open_tags(
+    code: &[u8],
+    short_open_tags: bool,
+    expected_kinds: Vec<TokenKind>,
+) -> Result<(), SyntaxError> {
     let interner = ThreadedInterner::new();
     let input = Input::new(SourceIdentifier::dummy(), code);
-    let mut lexer = Lexer::new(&interner, input);
+    let mut lexer = Lexer::new(&interner, input).with_short_open_tags(short_open_tags);
 
     let mut tokens = Vec::new();
     let mut error = None;
@@ -929,6 +1173,25 @@ fn test_lexer(code: &[u8], expected_kinds: Vec<TokenKind>) -> Result<(), SyntaxE
     Ok(())
 }
 
+/// Returns the kinds of the tokens of the given literal, when placed after an opening tag.
+fn lex_number(literal: &str) -> Result<Vec<TokenKind>, SyntaxError> {
+    let interner = ThreadedInterner::new();
+    let code = format!("<?php {}", literal);
+    let input = Input::new(SourceIdentifier::dummy(), code.as_bytes());
+    let mut lexer = Lexer::new(&interner, input);
+
+    let mut kinds = vec![];
+    while let Some(result) = lexer.advance() {
+        kinds.push(result?.kind);
+    }
+
+    if let Some(error) = lexer.take_errors().into_iter().next() {
+        return Err(error);
+    }
+
+    Ok(kinds.split_off(2))
+}
+
 pub const KEYWORD_TYPES: [(&[u8], TokenKind); 84] = [
     (b"eval", TokenKind::Eval),
     (b"die", TokenKind::Die),
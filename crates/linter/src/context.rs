@@ -5,6 +5,8 @@ use mago_ast::Identifier;
 use mago_fixer::FixPlan;
 use mago_interner::StringIdentifier;
 use mago_interner::ThreadedInterner;
+use mago_php_version::PHPVersion;
+use mago_reflection::reference::ReferenceIndex;
 use mago_reflection::CodebaseReflection;
 use mago_reporting::Issue;
 use mago_reporting::IssueCollection;
@@ -16,22 +18,32 @@ use crate::rule::ConfiguredRule;
 
 #[derive(Debug)]
 pub struct Context<'a> {
+    pub php_version: PHPVersion,
     pub interner: &'a ThreadedInterner,
     pub codebase: &'a CodebaseReflection,
+    pub references: Option<&'a ReferenceIndex>,
     pub semantics: &'a Semantics,
     pub issues: IssueCollection,
 }
 
 impl<'a> Context<'a> {
-    pub fn new(interner: &'a ThreadedInterner, codebase: &'a CodebaseReflection, semantics: &'a Semantics) -> Self {
-        Self { interner, codebase, semantics, issues: IssueCollection::default() }
+    pub fn new(
+        php_version: PHPVersion,
+        interner: &'a ThreadedInterner,
+        codebase: &'a CodebaseReflection,
+        references: Option<&'a ReferenceIndex>,
+        semantics: &'a Semantics,
+    ) -> Self {
+        Self { php_version, interner, codebase, references, semantics, issues: IssueCollection::default() }
     }
 
     pub fn for_rule<'b>(&'b mut self, rule: &'b ConfiguredRule) -> LintContext<'b> {
         LintContext {
             rule,
+            php_version: self.php_version,
             interner: self.interner,
             codebase: self.codebase,
+            references: self.references,
             semantics: self.semantics,
             issues: &mut self.issues,
         }
@@ -45,8 +57,12 @@ impl<'a> Context<'a> {
 #[derive(Debug)]
 pub struct LintContext<'a> {
     pub rule: &'a ConfiguredRule,
+    /// The targeted version of PHP.
+    pub php_version: PHPVersion,
     pub interner: &'a ThreadedInterner,
     pub codebase: &'a CodebaseReflection,
+    /// The index of the references to the symbols of the codebase, if an enabled rule requires it.
+    pub references: Option<&'a ReferenceIndex>,
     pub semantics: &'a Semantics,
     pub issues: &'a mut IssueCollection,
 }
@@ -270,6 +286,16 @@ impl LintContext<'_> {
     }
 
     pub fn report(&mut self, issue: Issue) {
+        let issue = match issue.code {
+            Some(_) => issue,
+            None => issue.with_code(format!("{}/{}", self.rule.plugin, self.rule.rule.get_name())),
+        };
+
+        let issue = match (&issue.help_uri, self.rule.rule.get_help_uri()) {
+            (None, Some(help_uri)) => issue.with_help_uri(help_uri),
+            _ => issue,
+        };
+
         self.issues.push(issue);
     }
 
@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::sync::RwLock;
 
 use mago_interner::ThreadedInterner;
+use mago_reflection::reference::ReferenceIndex;
 use mago_reflection::CodebaseReflection;
 use mago_reporting::IssueCollection;
 use mago_semantics::Semantics;
@@ -19,11 +20,14 @@ pub mod plugin;
 pub mod rule;
 pub mod settings;
 
+pub mod suppression;
+
 #[derive(Debug, Clone)]
 pub struct Linter {
     settings: Settings,
     interner: ThreadedInterner,
     codebase: Arc<CodebaseReflection>,
+    references: Option<Arc<ReferenceIndex>>,
     rules: Arc<RwLock<Vec<ConfiguredRule>>>,
 }
 
@@ -36,13 +40,60 @@ impl Linter {
     ///
     /// - `settings`: The settings to use for the linter.
     /// - `interner`: The interner to use for the linter, usually the same one used by the parser, and the semantics.
-    /// - `codebase`: The codebase reflection to use for the linter.
+    /// - `codebase`: The codebase reflection to use for the linter, which can be shared with other linters.
     ///
     /// # Returns
     ///
     /// A new linter.
-    pub fn new(settings: Settings, interner: ThreadedInterner, codebase: CodebaseReflection) -> Self {
-        Self { settings, interner, codebase: Arc::new(codebase), rules: Arc::new(RwLock::new(Vec::new())) }
+    pub fn new(settings: Settings, interner: ThreadedInterner, codebase: impl Into<Arc<CodebaseReflection>>) -> Self {
+        Self {
+            settings,
+            interner,
+            codebase: codebase.into(),
+            references: None,
+            rules: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Sets the index of the references to the symbols of the codebase, available to the rules requiring it.
+    ///
+    /// # Parameters
+    ///
+    /// - `references`: The index of the references, built from all the sources of the codebase.
+    ///
+    /// # Returns
+    ///
+    /// The linter, with the given index.
+    pub fn with_references(mut self, references: Arc<ReferenceIndex>) -> Self {
+        self.references = Some(references);
+
+        self
+    }
+
+    /// Returns the codebase reflection used by the linter.
+    pub fn codebase(&self) -> &Arc<CodebaseReflection> {
+        &self.codebase
+    }
+
+    /// Returns the full names (`plugin/rule`) of the enabled rules.
+    pub fn enabled_rules(&self) -> Vec<String> {
+        let rules = self.rules.read().expect("Unable to read rules: poisoned lock");
+
+        rules
+            .iter()
+            .map(|configured_rule| format!("{}/{}", configured_rule.plugin, configured_rule.rule.get_name()))
+            .collect()
+    }
+
+    /// Checks if any of the enabled rules requires the index of the references to the symbols of the codebase.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the index should be built, and set with [`Linter::with_references`], before linting.
+    pub fn requires_references(&self) -> bool {
+        let rules = self.rules.read().expect("Unable to read rules: poisoned lock");
+
+        rules.iter().any(|configured_rule| configured_rule.rule.requires_references())
     }
 
     /// Creates a new linter with all plugins enabled.
@@ -54,12 +105,16 @@ impl Linter {
     ///
     /// - `settings`: The settings to use for the linter.
     /// - `interner`: The interner to use for the linter, usually the same one used by the parser, and the semantics.
-    /// - `codebase`: The codebase reflection to use for the linter.
+    /// - `codebase`: The codebase reflection to use for the linter, which can be shared with other linters.
     ///
     /// # Returns
     ///
     /// A new linter with all plugins enabled.
-    pub fn with_all_plugins(settings: Settings, interner: ThreadedInterner, codebase: CodebaseReflection) -> Self {
+    pub fn with_all_plugins(
+        settings: Settings,
+        interner: ThreadedInterner,
+        codebase: impl Into<Arc<CodebaseReflection>>,
+    ) -> Self {
         let mut linter = Self::new(settings, interner, codebase);
 
         crate::foreach_plugin!(|plugin| linter.add_plugin(plugin));
@@ -127,6 +182,17 @@ impl Linter {
             return;
         }
 
+        if let Some(version) = rule.get_minimum_php_version() {
+            if !self.settings.php_version.is_supported(version) {
+                tracing::debug!(
+                    "Rule `{full_name}` requires PHP {version}, but PHP {} is targeted. Skipping.",
+                    self.settings.php_version
+                );
+
+                return;
+            }
+        }
+
         let level = match settings.level {
             Some(level) => level,
             None => match rule.get_default_level() {
@@ -153,6 +219,9 @@ impl Linter {
     ///
     /// This method will lint the given semantics and return a collection of issues.
     ///
+    /// The issues are not filtered by the suppression comments of the source, so that the comments apply to the
+    /// issues found by other analyses as well, see [`suppression::apply_suppressions`].
+    ///
     /// # Parameters
     ///
     /// - `semantics`: The semantics to lint.
@@ -165,7 +234,13 @@ impl Linter {
 
         tracing::debug!("Linting source `{}`...", source_name);
 
-        let mut context = Context::new(&self.interner, &self.codebase, semantics);
+        let mut context = Context::new(
+            self.settings.php_version,
+            &self.interner,
+            &self.codebase,
+            self.references.as_deref(),
+            semantics,
+        );
 
         let configured_rules = self.rules.read().expect("Unable to read rules: poisoned lock");
 
@@ -1,5 +1,7 @@
 use crate::plugin::analysis::rules::inheritance::InheritanceRule;
 use crate::plugin::analysis::rules::instantiation::InstantiationRule;
+use crate::plugin::analysis::rules::integer_literal_overflow::IntegerLiteralOverflowRule;
+use crate::plugin::analysis::rules::short_open_tag::ShortOpenTagRule;
 use crate::plugin::analysis::rules::undefined_constant::UndefinedConstantRule;
 use crate::plugin::analysis::rules::undefined_function::UndefinedFunctionRule;
 
@@ -24,6 +26,8 @@ impl Plugin for AnalysisPlugin {
         vec![
             Box::new(InheritanceRule),
             Box::new(InstantiationRule),
+            Box::new(IntegerLiteralOverflowRule),
+            Box::new(ShortOpenTagRule),
             Box::new(UndefinedConstantRule),
             Box::new(UndefinedFunctionRule),
         ]
@@ -15,6 +15,10 @@ impl Rule for InheritanceRule {
         "inheritance"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports classes, and interfaces, extending or implementing symbols that do not exist."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
@@ -27,6 +31,14 @@ impl InheritanceRule {
         let other_fqcn = context.lookup(other_name_identifier).to_string();
 
         let Some(other) = context.codebase.get_class(context.interner, other_name_identifier) else {
+            // Extending an interface, a trait, or an enum is reported by the inheritance checks of the codebase.
+            if context.codebase.interface_exists(context.interner, other_name_identifier)
+                || context.codebase.trait_exists(context.interner, other_name_identifier)
+                || context.codebase.enum_exists(context.interner, other_name_identifier)
+            {
+                return;
+            }
+
             let issue = Issue::error(format!("Extended class `{}` does not exist.", other_name))
                 .with_annotation(
                     Annotation::primary(other_identifier.span())
@@ -41,17 +53,6 @@ impl InheritanceRule {
 
         let this_name = this.name.get_key(context.interner);
 
-        if other.is_final {
-            let issue = Issue::error(format!("Cannot extend final class `{}` from `{}`.", other_name, this_name))
-                .with_annotation(
-                    Annotation::primary(other_identifier.span())
-                        .with_message(format!("Class `{}` is final.", other_fqcn)),
-                )
-                .with_help(format!("Ensure the class `{}` is not final or remove the `extends` clause.", other_fqcn));
-
-            context.report(issue);
-        }
-
         if this.is_readonly && !other.is_readonly {
             let issue = Issue::error(format!(
                 "Cannot extend non-readonly class `{}` from readonly class `{}`.",
@@ -106,6 +107,14 @@ impl InheritanceRule {
         let other_fqcn = context.lookup(other_name_identifier);
 
         let Some(other) = context.codebase.get_interface(context.interner, other_name_identifier) else {
+            // Extending a class, a trait, or an enum is reported by the inheritance checks of the codebase.
+            if context.codebase.class_exists(context.interner, other_name_identifier)
+                || context.codebase.trait_exists(context.interner, other_name_identifier)
+                || context.codebase.enum_exists(context.interner, other_name_identifier)
+            {
+                return;
+            }
+
             let issue = Issue::error(format!("Extended interface `{}` does not exist.", other_name))
                 .with_annotation(
                     Annotation::primary(other_identifier.span())
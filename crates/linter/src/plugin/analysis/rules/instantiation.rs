@@ -14,6 +14,10 @@ impl Rule for InstantiationRule {
         "instantiation"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports instantiations of classes that do not exist."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
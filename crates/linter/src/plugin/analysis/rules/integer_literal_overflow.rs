@@ -0,0 +1,51 @@
+use mago_ast::*;
+use mago_reporting::*;
+use mago_span::HasSpan;
+use mago_walker::Walker;
+
+use crate::context::LintContext;
+use crate::rule::Rule;
+
+/// Reports the integer literals that do not fit in a 64-bit signed integer, which PHP silently evaluates as
+/// floats, losing precision.
+#[derive(Clone, Debug)]
+pub struct IntegerLiteralOverflowRule;
+
+impl Rule for IntegerLiteralOverflowRule {
+    fn get_name(&self) -> &'static str {
+        "integer-literal-overflow"
+    }
+
+    fn get_description(&self) -> &'static str {
+        "Reports integer literals that overflow a 64-bit integer, and are evaluated as floats."
+    }
+
+    fn get_default_level(&self) -> Option<Level> {
+        Some(Level::Warning)
+    }
+}
+
+impl<'a> Walker<LintContext<'a>> for IntegerLiteralOverflowRule {
+    fn walk_in_literal_integer(&self, literal_integer: &LiteralInteger, context: &mut LintContext<'a>) {
+        if literal_integer.value.is_some() {
+            return;
+        }
+
+        let raw = context.lookup(&literal_integer.raw).to_string();
+        let issue = Issue::new(
+            context.level(),
+            format!("Integer literal `{}` overflows a 64-bit integer, and is evaluated as a float.", raw),
+        )
+        .with_annotation(
+            Annotation::primary(literal_integer.span()).with_message("This literal loses precision as a float."),
+        )
+        .with_note(
+            "Integers larger than `PHP_INT_MAX` are converted to floats, which cannot represent every integer exactly.",
+        )
+        .with_help(
+            "Use a float literal to make the conversion explicit, or a string with an arbitrary precision library.",
+        );
+
+        context.report(issue);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod inheritance;
 pub mod instantiation;
+pub mod integer_literal_overflow;
+pub mod short_open_tag;
 pub mod undefined_constant;
 pub mod undefined_function;
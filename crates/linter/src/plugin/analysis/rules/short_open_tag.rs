@@ -0,0 +1,57 @@
+use mago_ast::*;
+use mago_reporting::*;
+use mago_span::HasSpan;
+use mago_walker::Walker;
+
+use crate::context::LintContext;
+use crate::rule::Rule;
+
+/// Reports the `<?` in inline text, which is a short open tag when the `short_open_tag` ini setting of PHP is
+/// enabled, so the code following it is likely meant to be executed, but is output as text instead.
+///
+/// `<?` is only part of inline text while short open tags are disabled, and `<?xml` declarations are ignored.
+#[derive(Clone, Debug)]
+pub struct ShortOpenTagRule;
+
+impl Rule for ShortOpenTagRule {
+    fn get_name(&self) -> &'static str {
+        "short-open-tag"
+    }
+
+    fn get_description(&self) -> &'static str {
+        "Reports possible short open tags while short open tags are disabled."
+    }
+
+    fn get_default_level(&self) -> Option<Level> {
+        Some(Level::Warning)
+    }
+}
+
+impl<'a> Walker<LintContext<'a>> for ShortOpenTagRule {
+    fn walk_in_inline<'ast>(&self, inline: &'ast Inline, context: &mut LintContext<'a>) {
+        if inline.kind != InlineKind::Text {
+            return;
+        }
+
+        let text = context.interner.lookup(&inline.value);
+        for (index, _) in text.match_indices("<?") {
+            let following = text.get(index + 2..index + 5).unwrap_or_default();
+            if following.eq_ignore_ascii_case("xml") {
+                continue;
+            }
+
+            let span = inline.span().subspan(index, index + 2);
+            let issue = Issue::new(
+                context.level(),
+                "Possible short open tag found while `short_open_tags` is disabled.",
+            )
+            .with_annotation(Annotation::primary(span).with_message("This `<?` is output as text."))
+            .with_note("The code following a short open tag is not executed when short open tags are disabled.")
+            .with_help(
+                "Replace `<?` with `<?php`, or enable the `short_open_tags` option of the source configuration.",
+            );
+
+            context.report(issue);
+        }
+    }
+}
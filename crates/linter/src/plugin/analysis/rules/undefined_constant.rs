@@ -14,6 +14,10 @@ impl Rule for UndefinedConstantRule {
         "undefined-constant"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports uses of constants that are not defined."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
@@ -23,6 +27,11 @@ impl<'a> Walker<LintContext<'a>> for UndefinedConstantRule {
     fn walk_in_constant_access(&self, constant_access: &ConstantAccess, context: &mut LintContext<'a>) {
         let identifier = &constant_access.name;
         let constant_name = context.resolve_constant_name(identifier);
+        // The offset of the data following `__halt_compiler` is defined by PHP in the file using it.
+        if constant_name == "__COMPILER_HALT_OFFSET__" {
+            return;
+        }
+
         let constant_name_id = context.interner.intern(constant_name);
         if context.codebase.constant_exists(context.interner, &constant_name_id) {
             return;
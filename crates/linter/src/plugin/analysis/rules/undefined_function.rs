@@ -14,6 +14,10 @@ impl Rule for UndefinedFunctionRule {
         "undefined-function"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports calls to functions that are not defined."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
@@ -15,6 +15,10 @@ impl Rule for CombineConsecutiveIssetsRule {
         "combine-consecutive-issets"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Suggests combining consecutive `isset` calls into a single call."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
@@ -15,6 +15,10 @@ impl Rule for DisallowedFunctionsRule {
         "disallowed-functions"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports calls to the functions disallowed by the configuration."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
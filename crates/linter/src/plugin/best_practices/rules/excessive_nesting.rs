@@ -18,6 +18,10 @@ impl Rule for ExcessiveNesting {
         "excessive-nesting"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports blocks nested deeper than the configured threshold."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
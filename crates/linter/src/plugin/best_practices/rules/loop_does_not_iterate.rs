@@ -14,6 +14,10 @@ impl Rule for LoopDoesNotIterateRule {
         "loop-does-not-iterate"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports loops whose body always ends the loop, so that they never iterate more than once."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
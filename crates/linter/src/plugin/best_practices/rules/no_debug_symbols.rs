@@ -71,6 +71,10 @@ impl Rule for NoDebugSymbolsRule {
         "no-debug-symbols"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports calls to debug functions, such as `var_dump`, that should not be left in code."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
     }
@@ -15,6 +15,10 @@ impl Rule for NoEmptyLoopRule {
         "no-empty-loop"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports loops with an empty body."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
     }
@@ -14,6 +14,10 @@ impl Rule for NoGotoRule {
         "no-goto"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports uses of `goto` statements, and of their labels."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
     }
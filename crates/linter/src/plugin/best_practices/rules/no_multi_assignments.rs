@@ -14,6 +14,10 @@ impl Rule for NoMultiAssignmentsRule {
         "no-multi-assignments"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports multiple assignments in a single statement, such as `$a = $b = 1`."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
@@ -21,6 +21,10 @@ impl Rule for NoUnusedParameterRule {
         "no-unused-parameter"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports parameters of functions, methods, and closures that are never used."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
     }
@@ -15,6 +15,10 @@ impl Rule for UseWhileInsteadOfForRule {
         "use-while-instead-of-for"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Suggests using a `while` loop instead of a `for` loop with only a condition."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
     }
@@ -16,6 +16,10 @@ impl Rule for DocblockSyntaxRule {
         "docblock-syntax"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports docblocks that cannot be parsed."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         None
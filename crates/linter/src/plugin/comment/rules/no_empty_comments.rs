@@ -16,6 +16,10 @@ impl Rule for NoEmptyCommentsRule {
         "no-empty-comments"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports comments without any content."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
@@ -16,6 +16,10 @@ impl Rule for NoShellStyleRule {
         "no-shell-style"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports shell-style comments, starting with `#`."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Level::Warning.into()
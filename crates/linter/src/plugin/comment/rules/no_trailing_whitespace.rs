@@ -18,6 +18,10 @@ impl Rule for NoTrailingWhitespaceRule {
         "no-trailing-whitespace"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports trailing whitespace in comments."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Level::Note.into()
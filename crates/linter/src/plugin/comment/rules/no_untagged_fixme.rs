@@ -21,6 +21,10 @@ impl Rule for NoUntaggedFixmeRule {
         "no-untagged-fixme"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports `FIXME` comments that are not tagged with a user, or an issue."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Level::Warning.into()
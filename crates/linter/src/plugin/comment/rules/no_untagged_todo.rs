@@ -21,6 +21,10 @@ impl Rule for NoUntaggedTodoRule {
         "no-untagged-todo"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports `TODO` comments that are not tagged with a user, or an issue."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Level::Warning.into()
@@ -15,6 +15,10 @@ impl Rule for ArraySyntaxRule {
         "array-syntax"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces a consistent syntax for arrays, either the short `[..]` syntax, or the long `array(..)` syntax."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
     }
@@ -15,6 +15,10 @@ impl Rule for LowercaseHintRule {
         "lowercase-hint"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces lowercase type hints for the built-in types, such as `int`, and `string`."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
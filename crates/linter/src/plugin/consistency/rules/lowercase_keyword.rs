@@ -15,6 +15,10 @@ impl Rule for LowercaseKeywordRule {
         "lowercase-keyword"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces lowercase keywords."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -3,4 +3,5 @@ pub mod lowercase_hint;
 pub mod lowercase_keyword;
 pub mod no_function_aliases;
 pub mod no_tag_pair_terminator;
+pub mod ordered_class_elements;
 pub mod require_block_statement_body;
@@ -101,6 +101,10 @@ impl Rule for NoFunctionAliasesRule {
         "no-function-aliases"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports calls to aliases of built-in functions, such as `sizeof` for `count`."
+    }
+
     #[inline(always)]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
@@ -15,6 +15,10 @@ impl Rule for NoTagPairTerminatorRule {
         "no-tag-pair-terminator"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Suggests terminating statements with a semicolon rather than a closing tag."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
     }
@@ -0,0 +1,252 @@
+use mago_ast::*;
+use mago_fixer::SafetyClassification;
+use mago_reporting::*;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_walker::Walker;
+
+use crate::context::LintContext;
+use crate::rule::Rule;
+
+/// The order of the members of class-likes, used when the `order` option is not set.
+///
+/// A slot is either a kind of member, or a kind of member followed by a visibility, such as `methods:public`.
+const DEFAULT_ORDER: [&str; 8] = [
+    "traits",
+    "cases",
+    "constants",
+    "properties",
+    "constructor",
+    "methods:public",
+    "methods:protected",
+    "methods:private",
+];
+
+#[derive(Clone, Debug)]
+pub struct OrderedClassElementsRule;
+
+impl Rule for OrderedClassElementsRule {
+    fn get_name(&self) -> &'static str {
+        "ordered-class-elements"
+    }
+
+    fn get_description(&self) -> &'static str {
+        "Enforces the configured order of the members of classes, interfaces, traits, and enums."
+    }
+
+    fn get_default_level(&self) -> Option<Level> {
+        None
+    }
+}
+
+impl<'a> Walker<LintContext<'a>> for OrderedClassElementsRule {
+    fn walk_in_class<'ast>(&self, class: &'ast Class, context: &mut LintContext<'a>) {
+        let name = context.lookup(&class.name.value).to_string();
+
+        check_members(context, &format!("class `{}`", name), class.left_brace, &class.members, class.right_brace);
+    }
+
+    fn walk_in_interface<'ast>(&self, interface: &'ast Interface, context: &mut LintContext<'a>) {
+        let name = context.lookup(&interface.name.value).to_string();
+
+        check_members(
+            context,
+            &format!("interface `{}`", name),
+            interface.left_brace,
+            &interface.members,
+            interface.right_brace,
+        );
+    }
+
+    fn walk_in_trait<'ast>(&self, r#trait: &'ast Trait, context: &mut LintContext<'a>) {
+        let name = context.lookup(&r#trait.name.value).to_string();
+
+        check_members(context, &format!("trait `{}`", name), r#trait.left_brace, &r#trait.members, r#trait.right_brace);
+    }
+
+    fn walk_in_enum<'ast>(&self, r#enum: &'ast Enum, context: &mut LintContext<'a>) {
+        let name = context.lookup(&r#enum.name.value).to_string();
+
+        check_members(context, &format!("enum `{}`", name), r#enum.left_brace, &r#enum.members, r#enum.right_brace);
+    }
+
+    fn walk_in_anonymous_class<'ast>(&self, anonymous_class: &'ast AnonymousClass, context: &mut LintContext<'a>) {
+        check_members(
+            context,
+            "anonymous class",
+            anonymous_class.left_brace,
+            &anonymous_class.members,
+            anonymous_class.right_brace,
+        );
+    }
+}
+
+fn check_members(
+    context: &mut LintContext<'_>,
+    class_like: &str,
+    left_brace: Span,
+    members: &Sequence<ClassLikeMember>,
+    right_brace: Span,
+) {
+    let order: Vec<String> = match context.option("order").and_then(|o| o.as_array()) {
+        Some(order) => order.iter().filter_map(|slot| slot.as_str()).map(|slot| slot.to_lowercase()).collect(),
+        None => DEFAULT_ORDER.iter().map(|slot| slot.to_string()).collect(),
+    };
+
+    let ranks: Vec<usize> = members
+        .iter()
+        .map(|member| {
+            slots_of(context, member)
+                .iter()
+                .find_map(|slot| order.iter().position(|s| s == slot))
+                .unwrap_or(order.len())
+        })
+        .collect();
+
+    // The first member that belongs before one of the members preceding it.
+    let Some(misplaced) = (1..ranks.len()).find(|&i| ranks[..i].iter().any(|&rank| rank > ranks[i])) else {
+        return;
+    };
+
+    let source_text = context.interner.lookup(&context.semantics.source.content);
+    let comments: Vec<Span> = context
+        .semantics
+        .program
+        .comments
+        .comments_within(Span::between(left_brace, right_brace))
+        .iter()
+        .map(|trivia| trivia.span)
+        .collect();
+
+    // Members that the formatter is told to leave alone are not moved either.
+    if comments.iter().any(|span| is_ignore_directive(&source_text[span.start.offset..span.end.offset])) {
+        return;
+    }
+
+    let chunks = member_chunks(source_text, left_brace, members, &comments);
+
+    let mut sorted: Vec<usize> = (0..chunks.len()).collect();
+    sorted.sort_by_key(|&i| ranks[i]);
+
+    // The text between the members, such as blank lines, is kept in place, and only the members are moved.
+    let mut replacement = String::new();
+    for (position, &index) in sorted.iter().enumerate() {
+        if position > 0 {
+            replacement.push_str(&source_text[chunks[position - 1].end..chunks[position].start]);
+        }
+
+        replacement.push_str(&source_text[chunks[index].clone()]);
+    }
+
+    let member = members.get(misplaced).unwrap();
+    let issue = Issue::new(context.level(), format!("The members of {} are not in the configured order.", class_like))
+        .with_annotation(
+            Annotation::primary(member.span())
+                .with_message(format!("This {} is placed after members that belong after it.", kind_of(member))),
+        )
+        .with_note(format!("Members are expected in the following order: {}.", order.join(", ")))
+        .with_help("Reorder the members of the class-like, or change the `order` option of this rule.");
+
+    let range = chunks[0].start..chunks[chunks.len() - 1].end;
+    context.report_with_fix(issue, |plan| {
+        plan.replace(range, replacement, SafetyClassification::PotentiallyUnsafe);
+    });
+}
+
+/// Returns the slots the given member belongs to, from the most specific to the least specific.
+fn slots_of(context: &LintContext<'_>, member: &ClassLikeMember) -> Vec<String> {
+    match member {
+        ClassLikeMember::TraitUse(_) => vec!["traits".to_string()],
+        ClassLikeMember::EnumCase(_) => vec!["cases".to_string()],
+        ClassLikeMember::Constant(constant) => {
+            let visibility = visibility_of(&constant.modifiers);
+
+            vec![format!("constants:{}", visibility), "constants".to_string()]
+        }
+        ClassLikeMember::Property(property) => {
+            let visibility = visibility_of(property.modifiers());
+
+            vec![format!("properties:{}", visibility), "properties".to_string()]
+        }
+        ClassLikeMember::Method(method) => {
+            let visibility = visibility_of(&method.modifiers);
+            let mut slots = vec![format!("methods:{}", visibility), "methods".to_string()];
+            if context.lookup(&method.name.value).eq_ignore_ascii_case("__construct") {
+                slots.insert(0, "constructor".to_string());
+            }
+
+            slots
+        }
+    }
+}
+
+fn visibility_of(modifiers: &Sequence<Modifier>) -> &'static str {
+    match modifiers.get_first_read_visibility() {
+        Some(Modifier::Protected(_)) => "protected",
+        Some(Modifier::Private(_)) => "private",
+        _ => "public",
+    }
+}
+
+fn kind_of(member: &ClassLikeMember) -> &'static str {
+    match member {
+        ClassLikeMember::TraitUse(_) => "trait use",
+        ClassLikeMember::EnumCase(_) => "enum case",
+        ClassLikeMember::Constant(_) => "constant",
+        ClassLikeMember::Property(_) => "property",
+        ClassLikeMember::Method(_) => "method",
+    }
+}
+
+/// Returns the ranges of the source text covered by the given members, along with their comments.
+///
+/// The comments on the lines before a member, such as its docblock, and the comments following it on
+/// the same line, are moved along with it.
+fn member_chunks(
+    source_text: &str,
+    left_brace: Span,
+    members: &Sequence<ClassLikeMember>,
+    comments: &[Span],
+) -> Vec<std::ops::Range<usize>> {
+    let is_same_line = |from: usize, to: usize| !source_text[from..to].contains('\n');
+
+    let mut chunks: Vec<std::ops::Range<usize>> = vec![];
+    for (index, member) in members.iter().enumerate() {
+        let span = member.span();
+        let previous_end = chunks.last().map_or(left_brace.end.offset, |chunk| chunk.end);
+        let next_start = members.get(index + 1).map_or(usize::MAX, |next| next.span().start.offset);
+
+        let start = comments
+            .iter()
+            .filter(|comment| {
+                comment.start.offset >= previous_end
+                    && comment.end.offset <= span.start.offset
+                    && !is_same_line(previous_end, comment.start.offset)
+            })
+            .map(|comment| comment.start.offset)
+            .min()
+            .unwrap_or(span.start.offset);
+
+        let end = comments
+            .iter()
+            .filter(|comment| {
+                comment.start.offset >= span.end.offset
+                    && comment.end.offset <= next_start
+                    && is_same_line(span.end.offset, comment.start.offset)
+            })
+            .map(|comment| comment.end.offset)
+            .max()
+            .unwrap_or(span.end.offset);
+
+        chunks.push(start..end);
+    }
+
+    chunks
+}
+
+fn is_ignore_directive(comment: &str) -> bool {
+    comment
+        .trim_start_matches(['/', '*', '#'])
+        .trim_start_matches(|c: char| c.is_whitespace() || c == '*')
+        .starts_with("@mago-fmt-ignore")
+}
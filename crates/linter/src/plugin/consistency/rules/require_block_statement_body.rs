@@ -40,6 +40,10 @@ impl Rule for RequireBlockStatementBodyRule {
         "require-block-statement-body"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Requires the bodies of loops to be enclosed in a block."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
     }
@@ -0,0 +1,19 @@
+use crate::plugin::dead_code::rules::unused_symbol::UnusedSymbolRule;
+
+use crate::plugin::Plugin;
+use crate::rule::Rule;
+
+pub mod rules;
+
+#[derive(Debug)]
+pub struct DeadCodePlugin;
+
+impl Plugin for DeadCodePlugin {
+    fn get_name(&self) -> &'static str {
+        "dead-code"
+    }
+
+    fn get_rules(&self) -> Vec<Box<dyn Rule>> {
+        vec![Box::new(UnusedSymbolRule)]
+    }
+}
@@ -0,0 +1 @@
+pub mod unused_symbol;
@@ -0,0 +1,305 @@
+use ahash::HashSet;
+
+use mago_ast::Program;
+use mago_interner::StringIdentifier;
+use mago_reflection::class_like::ClassLikeReflection;
+use mago_reflection::identifier::FunctionLikeName;
+use mago_reflection::query::NamePattern;
+use mago_reflection::query::Symbol;
+use mago_reflection::reference::ReferenceIndex;
+use mago_reflection::reference::ReferencedSymbol;
+use mago_reporting::*;
+use mago_span::Span;
+use mago_walker::Walker;
+
+use crate::context::LintContext;
+use crate::rule::Rule;
+
+/// Reports the class-likes, methods, functions, constants, and class constants declared in the
+/// user-defined sources, but never referenced anywhere in the codebase.
+///
+/// The following symbols are never reported:
+///
+/// - the symbols matching one of the `entry_points` patterns, such as `App\Controller\*`,
+/// - the symbols with attributes, which are usually used by a framework through reflection,
+/// - the magic methods, and the methods overriding, or implementing, the method of an ancestor,
+/// - the symbols declared in a source containing dynamic usages, such as `new $class()`, which may refer to them.
+#[derive(Clone, Debug)]
+pub struct UnusedSymbolRule;
+
+impl Rule for UnusedSymbolRule {
+    fn get_name(&self) -> &'static str {
+        "unused-symbol"
+    }
+
+    fn get_description(&self) -> &'static str {
+        "Reports classes, functions, constants, and members that are never used in the codebase."
+    }
+
+    fn get_default_level(&self) -> Option<Level> {
+        Some(Level::Warning)
+    }
+
+    fn requires_references(&self) -> bool {
+        true
+    }
+
+    fn lint(&self, program: &Program, context: &mut LintContext<'_>) {
+        if !program.source.is_user_defined() {
+            return;
+        }
+
+        let Some(references) = context.references else {
+            return;
+        };
+
+        // Any symbol of a source containing dynamic usages may be referenced by them.
+        if references.has_dynamic_usages(&program.source) {
+            return;
+        }
+
+        let entry_points = match context.option("entry_points").and_then(|value| value.as_array()) {
+            Some(patterns) => patterns
+                .iter()
+                .filter_map(|pattern| pattern.as_str())
+                .map(|pattern| NamePattern::new(pattern, true))
+                .collect(),
+            None => vec![],
+        };
+
+        let mut checker = Checker { context, references, entry_points, unused: vec![] };
+        checker.check_source(program);
+
+        let Checker { context, mut unused, .. } = checker;
+        unused.sort_by_key(|(span, _, _)| span.start.offset);
+
+        for (span, kind, name) in unused {
+            let issue = Issue::new(context.level(), format!("Unused {} `{}`.", kind, name))
+                .with_annotation(
+                    Annotation::primary(span).with_message(format!("`{}` is declared here, but never referenced.", name)),
+                )
+                .with_note(format!("No reference to this {} was found in the scanned codebase.", kind))
+                .with_help(format!(
+                    "Remove the {}, or add it to the `entry_points` option of this rule if it is used from outside of the codebase.",
+                    kind
+                ));
+
+            context.report(issue);
+        }
+    }
+}
+
+impl<'a> Walker<LintContext<'a>> for UnusedSymbolRule {}
+
+struct Checker<'c, 'a> {
+    context: &'c mut LintContext<'a>,
+    references: &'a ReferenceIndex,
+    entry_points: Vec<NamePattern>,
+    unused: Vec<(Span, &'static str, String)>,
+}
+
+impl Checker<'_, '_> {
+    fn check_source(&mut self, program: &Program) {
+        let codebase = self.context.codebase;
+        let interner = self.context.interner;
+
+        for class_like in codebase.class_like_reflections.values() {
+            if class_like.is_anonymous || class_like.span.start.source != program.source {
+                continue;
+            }
+
+            let Some(name) = class_like.name.inner() else {
+                continue;
+            };
+
+            let symbol = Symbol::ClassLike(class_like);
+            if !self.is_exempt(&symbol, !class_like.attribute_reflections.is_empty()) {
+                let referenced = ReferencedSymbol::class_like(interner, &name.value);
+                if !self.references.is_referenced_outside(&referenced, &class_like.span) {
+                    // The members of an unused class-like are not reported on their own.
+                    self.report(&symbol, name.span);
+
+                    continue;
+                }
+            }
+
+            self.check_members(class_like);
+        }
+
+        for function in codebase.function_like_reflections.values() {
+            let FunctionLikeName::Function(name) = function.name else {
+                continue;
+            };
+
+            if function.span.start.source != program.source {
+                continue;
+            }
+
+            let symbol = Symbol::Function(function);
+            if self.is_exempt(&symbol, !function.attribute_reflections.is_empty()) {
+                continue;
+            }
+
+            let referenced = ReferencedSymbol::function(interner, &name.value);
+            if !self.references.is_referenced_outside(&referenced, &function.span) {
+                self.report(&symbol, name.span);
+            }
+        }
+
+        for constant in codebase.constant_reflections.values() {
+            if constant.item_span.start.source != program.source {
+                continue;
+            }
+
+            let symbol = Symbol::Constant(constant);
+            if self.is_exempt(&symbol, false) {
+                continue;
+            }
+
+            let referenced = ReferencedSymbol::constant(interner, &constant.name.value);
+            if !self.references.is_referenced_outside(&referenced, &constant.item_span) {
+                self.report(&symbol, constant.name.span);
+            }
+        }
+    }
+
+    /// Checks the methods, and the constants declared by the given class-like.
+    ///
+    /// A member is referenced when it is referenced through the class-like, any of its ancestors, or any of
+    /// its descendants, as the same member may be accessed through any of them.
+    fn check_members(&mut self, class_like: &ClassLikeReflection) {
+        let codebase = self.context.codebase;
+        let interner = self.context.interner;
+
+        let ancestors: Vec<StringIdentifier> = class_like
+            .inheritance
+            .all_extended_classes
+            .iter()
+            .chain(class_like.inheritance.all_implemented_interfaces.iter())
+            .chain(class_like.inheritance.all_extended_interfaces.iter())
+            .map(|ancestor| ancestor.value)
+            .chain(class_like.used_traits.iter().copied())
+            .collect();
+
+        // The members of a class-like with unknown ancestors may implement theirs.
+        if ancestors.iter().any(|ancestor| codebase.get_named_class_like(interner, ancestor).is_none()) {
+            return;
+        }
+
+        let family = family(self.context, class_like, &ancestors);
+
+        for method in class_like.methods.members.values() {
+            let FunctionLikeName::Method(declaring_class_like, name) = method.name else {
+                continue;
+            };
+
+            if declaring_class_like != class_like.name
+                || method.is_overriding
+                || interner.lookup(&name.value).starts_with("__")
+            {
+                continue;
+            }
+
+            let symbol = Symbol::Method(class_like, method);
+            if self.is_exempt(&symbol, !method.attribute_reflections.is_empty()) {
+                continue;
+            }
+
+            let referenced = std::iter::once(ReferencedSymbol::any_method(interner, &name.value))
+                .chain(family.iter().map(|member| ReferencedSymbol::method(interner, member, &name.value)))
+                .any(|referenced| self.references.is_referenced_outside(&referenced, &method.span));
+
+            if !referenced {
+                self.report(&symbol, name.span);
+            }
+        }
+
+        for constant in class_like.constants.values() {
+            if constant.name.class_like != class_like.name {
+                continue;
+            }
+
+            let member = constant.name.member.value;
+            let overrides = ancestors.iter().filter_map(|ancestor| codebase.get_named_class_like(interner, ancestor));
+            if overrides.into_iter().any(|ancestor| ancestor.has_constant(&member)) {
+                continue;
+            }
+
+            let symbol = Symbol::ClassLikeConstant(class_like, constant);
+            if self.is_exempt(&symbol, !constant.attribute_reflections.is_empty()) {
+                continue;
+            }
+
+            let referenced = family
+                .iter()
+                .map(|member_of| ReferencedSymbol::class_like_constant(interner, member_of, &member))
+                .any(|referenced| self.references.is_referenced_outside(&referenced, &constant.item_span));
+
+            if !referenced {
+                self.report(&symbol, constant.name.member.span);
+            }
+        }
+    }
+
+    fn is_exempt(&self, symbol: &Symbol<'_>, has_attributes: bool) -> bool {
+        if has_attributes {
+            return true;
+        }
+
+        if self.entry_points.is_empty() {
+            return false;
+        }
+
+        let name = symbol.name(self.context.interner);
+
+        self.entry_points.iter().any(|pattern| pattern.matches(&name))
+    }
+
+    fn report(&mut self, symbol: &Symbol<'_>, span: Span) {
+        self.unused.push((span, symbol.kind().as_str(), symbol.name(self.context.interner)));
+    }
+}
+
+/// Returns the names of the given class-like, of its ancestors, and of the user-defined class-likes descending
+/// from it, or using it.
+fn family(
+    context: &LintContext<'_>,
+    class_like: &ClassLikeReflection,
+    ancestors: &[StringIdentifier],
+) -> Vec<StringIdentifier> {
+    let interner = context.interner;
+    let Some(name) = class_like.name.inner().map(|name| name.value) else {
+        return vec![];
+    };
+
+    let lowered = interner.lowered(&name);
+    let mut family: Vec<StringIdentifier> = vec![name];
+    family.extend(ancestors.iter().copied());
+
+    let mut seen = HashSet::default();
+    for descendant in context.codebase.class_like_reflections.values() {
+        if !descendant.span.start.source.is_user_defined() {
+            continue;
+        }
+
+        let Some(descendant_name) = descendant.name.inner().map(|name| name.value) else {
+            continue;
+        };
+
+        let descends = descendant
+            .inheritance
+            .all_extended_classes
+            .iter()
+            .chain(descendant.inheritance.all_implemented_interfaces.iter())
+            .chain(descendant.inheritance.all_extended_interfaces.iter())
+            .map(|ancestor| ancestor.value)
+            .chain(descendant.used_traits.iter().copied())
+            .any(|ancestor| interner.lowered(&ancestor) == lowered);
+
+        if descends && seen.insert(descendant_name) {
+            family.push(descendant_name);
+        }
+    }
+
+    family
+}
@@ -14,6 +14,10 @@ impl Rule for OptionalParameterBeforeRequiredRule {
         "optional-parameter-before-required"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports optional parameters declared before required ones, which is deprecated since PHP 8.0."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
@@ -15,6 +15,10 @@ impl Rule for ReturnByReferenceFromVoidFunctionRule {
         "return-by-reference-from-void-function"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports void functions, and methods, returning by reference, which is deprecated."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
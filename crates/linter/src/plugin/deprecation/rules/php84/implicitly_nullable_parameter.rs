@@ -15,6 +15,10 @@ impl Rule for ImplicitlyNullableParameterRule {
         "implicitly-nullable-parameter"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports parameters that are implicitly nullable through a `null` default value, which is deprecated since PHP 8.4."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
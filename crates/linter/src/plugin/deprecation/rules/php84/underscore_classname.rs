@@ -14,6 +14,10 @@ impl Rule for UnderscoreClassNameRule {
         "underscore-class-name"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports classes named `_`, which is deprecated since PHP 8.4."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
@@ -22,6 +22,10 @@ impl Rule for NoRequestAllRule {
         "no-request-all"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports uses of `$request->all()`, and `Request::all()`, in Laravel applications."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
@@ -1,5 +1,6 @@
 use mago_ast::*;
 use mago_fixer::SafetyClassification;
+use mago_php_version::PHPVersion;
 use mago_reporting::*;
 use mago_span::HasSpan;
 use mago_walker::Walker;
@@ -18,9 +19,17 @@ impl Rule for StrContainsRule {
         "str-contains"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Suggests using `str_contains` instead of comparing the result of `strpos` with `false`."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
+
+    fn get_minimum_php_version(&self) -> Option<PHPVersion> {
+        Some(PHPVersion::PHP80)
+    }
 }
 
 impl<'a> Walker<LintContext<'a>> for StrContainsRule {
@@ -1,5 +1,6 @@
 use mago_ast::*;
 use mago_fixer::SafetyClassification;
+use mago_php_version::PHPVersion;
 use mago_reporting::*;
 use mago_span::HasSpan;
 use mago_walker::Walker;
@@ -18,9 +19,17 @@ impl Rule for StrStartsWithRule {
         "str-starts-with"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Suggests using `str_starts_with` instead of comparing the result of `strpos` with `0`."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
+
+    fn get_minimum_php_version(&self) -> Option<PHPVersion> {
+        Some(PHPVersion::PHP80)
+    }
 }
 
 impl<'a> Walker<LintContext<'a>> for StrStartsWithRule {
@@ -1,5 +1,6 @@
 use mago_ast::*;
 use mago_fixer::SafetyClassification;
+use mago_php_version::PHPVersion;
 use mago_reporting::*;
 use mago_span::HasSpan;
 use mago_walker::Walker;
@@ -15,9 +16,17 @@ impl Rule for ExplicitOctalNotationRule {
         "explicit-octal-notation"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Suggests using the explicit `0o` prefix for octal integer literals."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
+
+    fn get_minimum_php_version(&self) -> Option<PHPVersion> {
+        Some(PHPVersion::PHP81)
+    }
 }
 
 impl<'a> Walker<LintContext<'a>> for ExplicitOctalNotationRule {
@@ -34,6 +43,11 @@ impl<'a> Walker<LintContext<'a>> for ExplicitOctalNotationRule {
             return;
         }
 
+        // A string offset in an interpolation, such as `"$a[09]"`, is not an octal literal.
+        if !literal_text.bytes().all(|c| matches!(c, b'0'..=b'7' | b'_')) {
+            return;
+        }
+
         let issue = Issue::new(context.level(), "Use explicit octal numeral notation.")
             .with_annotation(
                 Annotation::primary(literal_integer.span())
@@ -44,7 +58,7 @@ impl<'a> Walker<LintContext<'a>> for ExplicitOctalNotationRule {
             .with_link("https://www.php.net/manual/en/migration81.new-features.php#migration81.new-features.core.octal-literal-prefix")
         ;
 
-        let replacement = format!("0o{}", &literal_text[1..]);
+        let replacement = format!("0o{}", literal_text[1..].trim_start_matches('_'));
 
         context.report_with_fix(issue, |plan| {
             plan.replace(literal_integer.span().to_range(), replacement, SafetyClassification::Safe);
@@ -1,5 +1,6 @@
 use mago_ast::*;
 use mago_fixer::SafetyClassification;
+use mago_php_version::PHPVersion;
 use mago_reporting::*;
 use mago_span::HasSpan;
 use mago_walker::Walker;
@@ -15,9 +16,17 @@ impl Rule for ReadonlyClassPromotionRule {
         "readonly-class-promotion"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Suggests declaring a class readonly when all its properties are readonly."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
+
+    fn get_minimum_php_version(&self) -> Option<PHPVersion> {
+        Some(PHPVersion::PHP82)
+    }
 }
 
 impl<'a> Walker<LintContext<'a>> for ReadonlyClassPromotionRule {
@@ -6,6 +6,7 @@ pub mod analysis;
 pub mod best_practices;
 pub mod comment;
 pub mod consistency;
+pub mod dead_code;
 pub mod deprecation;
 pub mod laravel;
 pub mod migration;
@@ -22,6 +23,7 @@ macro_rules! foreach_plugin {
         $do($crate::plugin::best_practices::BestPracticesPlugin);
         $do($crate::plugin::comment::CommentPlugin);
         $do($crate::plugin::consistency::ConsistencyPlugin);
+        $do($crate::plugin::dead_code::DeadCodePlugin);
         $do($crate::plugin::deprecation::DeprecationPlugin);
         $do($crate::plugin::laravel::LaravelPlugin);
         $do($crate::plugin::migration::MigrationPlugin);
@@ -14,6 +14,10 @@ impl Rule for ClassRule {
         "class"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces class case names, such as `UserProfile`, for classes."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
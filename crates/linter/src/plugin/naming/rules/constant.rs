@@ -14,6 +14,10 @@ impl Rule for ConstantRule {
         "constant"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces constant case names, such as `MAX_SIZE`, for constants."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
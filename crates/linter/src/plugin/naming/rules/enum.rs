@@ -14,6 +14,10 @@ impl Rule for EnumRule {
         "enum"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces class case names for enums."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -14,6 +14,10 @@ impl Rule for FunctionRule {
         "function"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces the configured case for the names of functions."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
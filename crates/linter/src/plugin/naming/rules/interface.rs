@@ -14,6 +14,10 @@ impl Rule for InterfaceRule {
         "interface"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces class case names for interfaces."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -14,6 +14,10 @@ impl Rule for TraitRule {
         "trait"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces class case names for traits."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -23,6 +23,10 @@ impl Rule for AssertionsStyleRule {
         "assertions-style"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Enforces a consistent style for calling PHPUnit assertions."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
@@ -20,6 +20,10 @@ impl Rule for StrictAssertionsRule {
         "strict-assertions"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Suggests using strict PHPUnit assertions, such as `assertSame` instead of `assertEquals`."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
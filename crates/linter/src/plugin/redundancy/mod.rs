@@ -1,6 +1,7 @@
 use crate::plugin::redundancy::rules::redundant_block::RedundantBlockRule;
 use crate::plugin::redundancy::rules::redundant_closing_tag::RedudnantClosingTagRule;
 use crate::plugin::redundancy::rules::redundant_continue::RedundantContinueRule;
+use crate::plugin::redundancy::rules::redundant_expression_statement::RedundantExpressionStatementRule;
 use crate::plugin::redundancy::rules::redundant_final_method_modifier::RedundantFinalMethodModifierRule;
 use crate::plugin::redundancy::rules::redundant_if_statement::RedundantIfStatementRule;
 use crate::plugin::redundancy::rules::redundant_label::RedundantLabelRule;
@@ -37,6 +38,7 @@ impl Plugin for RedundancyPlugin {
             Box::new(RedundantFinalMethodModifierRule),
             Box::new(RedundantLabelRule),
             Box::new(RedundantIfStatementRule),
+            Box::new(RedundantExpressionStatementRule),
         ]
     }
 }
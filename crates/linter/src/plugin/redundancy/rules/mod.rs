@@ -1,6 +1,7 @@
 pub mod redundant_block;
 pub mod redundant_closing_tag;
 pub mod redundant_continue;
+pub mod redundant_expression_statement;
 pub mod redundant_final_method_modifier;
 pub mod redundant_if_statement;
 pub mod redundant_label;
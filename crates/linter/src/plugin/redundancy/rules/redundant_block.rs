@@ -30,6 +30,10 @@ impl Rule for RedundantBlockRule {
         "redundant-block"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports blocks that only group statements, without being the body of a statement."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -71,6 +71,10 @@ impl Rule for RedudnantClosingTagRule {
         "redundant-closing-tag"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports closing tags at the end of files."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -15,6 +15,10 @@ impl Rule for RedundantContinueRule {
         "redundant-continue"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports `continue` statements at the end of loop bodies."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
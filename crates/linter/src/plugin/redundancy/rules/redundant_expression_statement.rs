@@ -0,0 +1,311 @@
+use mago_ast::*;
+use mago_names::ResolutionKind;
+use mago_reporting::*;
+use mago_span::HasSpan;
+use mago_walker::Walker;
+
+use crate::context::LintContext;
+use crate::rule::Rule;
+
+/// The built-in functions which do not have side effects, nor modify their arguments, so calling them is
+/// useless unless their result is used.
+const PURE_FUNCTIONS: [&str; 55] = [
+    "abs",
+    "array_combine",
+    "array_diff",
+    "array_diff_assoc",
+    "array_diff_key",
+    "array_fill",
+    "array_fill_keys",
+    "array_flip",
+    "array_intersect",
+    "array_intersect_key",
+    "array_is_list",
+    "array_key_exists",
+    "array_key_first",
+    "array_key_last",
+    "array_keys",
+    "array_merge",
+    "array_merge_recursive",
+    "array_pad",
+    "array_replace",
+    "array_reverse",
+    "array_slice",
+    "array_unique",
+    "array_values",
+    "ceil",
+    "count",
+    "explode",
+    "floor",
+    "implode",
+    "in_array",
+    "intdiv",
+    "is_array",
+    "is_bool",
+    "is_float",
+    "is_int",
+    "is_null",
+    "is_numeric",
+    "is_string",
+    "lcfirst",
+    "ltrim",
+    "max",
+    "min",
+    "round",
+    "rtrim",
+    "sprintf",
+    "str_contains",
+    "str_ends_with",
+    "str_pad",
+    "str_repeat",
+    "str_replace",
+    "str_starts_with",
+    "strlen",
+    "strtolower",
+    "strtoupper",
+    "substr",
+    "trim",
+];
+
+#[derive(Clone, Debug)]
+pub struct RedundantExpressionStatementRule;
+
+impl RedundantExpressionStatementRule {
+    /// Determines whether evaluating the expression has no side effects.
+    ///
+    /// This is conservative: the calls, the assignments, the accesses to properties and array elements, which
+    /// may call magic methods, and the expressions whose errors are suppressed with `@` are never pure.
+    fn is_pure(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Literal(_)
+            | Expression::MagicConstant(_)
+            | Expression::ConstantAccess(_)
+            | Expression::Identifier(_)
+            | Expression::Closure(_)
+            | Expression::ArrowFunction(_) => true,
+            Expression::Variable(variable) => self.is_pure_variable(variable),
+            Expression::Parenthesized(parenthesized) => self.is_pure(&parenthesized.expression),
+            Expression::Binary(binary) => self.is_pure(&binary.lhs) && self.is_pure(&binary.rhs),
+            Expression::UnaryPrefix(unary) => match unary.operator {
+                UnaryPrefixOperator::BitwiseNot(_)
+                | UnaryPrefixOperator::Not(_)
+                | UnaryPrefixOperator::Plus(_)
+                | UnaryPrefixOperator::Negation(_)
+                | UnaryPrefixOperator::ArrayCast(_, _)
+                | UnaryPrefixOperator::BoolCast(_, _)
+                | UnaryPrefixOperator::BooleanCast(_, _)
+                | UnaryPrefixOperator::DoubleCast(_, _)
+                | UnaryPrefixOperator::RealCast(_, _)
+                | UnaryPrefixOperator::FloatCast(_, _)
+                | UnaryPrefixOperator::IntCast(_, _)
+                | UnaryPrefixOperator::IntegerCast(_, _) => self.is_pure(&unary.operand),
+                _ => false,
+            },
+            Expression::Conditional(conditional) => {
+                self.is_pure(&conditional.condition)
+                    && conditional.then.as_ref().is_none_or(|then| self.is_pure(then))
+                    && self.is_pure(&conditional.r#else)
+            }
+            Expression::Array(array) => self.are_pure_elements(array.elements.as_slice()),
+            Expression::LegacyArray(array) => self.are_pure_elements(array.elements.as_slice()),
+            Expression::Access(access) => match access.as_ref() {
+                Access::ClassConstant(access) => {
+                    matches!(access.constant, ClassLikeConstantSelector::Identifier(_)) && self.is_pure(&access.class)
+                }
+                _ => false,
+            },
+            Expression::Self_(_) | Expression::Static(_) | Expression::Parent(_) => true,
+            _ => false,
+        }
+    }
+
+    fn is_pure_variable(&self, variable: &Variable) -> bool {
+        match variable {
+            Variable::Direct(_) => true,
+            Variable::Indirect(variable) => self.is_pure(&variable.expression),
+            Variable::Nested(variable) => self.is_pure_variable(&variable.variable),
+        }
+    }
+
+    fn are_pure_elements(&self, elements: &[ArrayElement]) -> bool {
+        elements.iter().all(|element| match element {
+            ArrayElement::KeyValue(element) => self.is_pure(&element.key) && self.is_pure(&element.value),
+            ArrayElement::Value(element) => self.is_pure(&element.value),
+            // Unpacking a `Traversable` iterates it, which may run any code.
+            ArrayElement::Variadic(_) => false,
+            ArrayElement::Missing(_) => true,
+        })
+    }
+
+    /// Returns the name of the pure built-in function called, if the arguments of the call are pure too.
+    fn get_pure_function_name<'c>(&self, call: &FunctionCall, context: &'c LintContext<'_>) -> Option<&'c str> {
+        let Expression::Identifier(identifier) = call.function.as_ref() else {
+            return None;
+        };
+
+        let name = match context.semantics.names.resolved(identifier)? {
+            // A function declared in the namespace takes precedence over the built-in one.
+            (name, ResolutionKind::GlobalFallback) if context.codebase.function_exists(context.interner, &name) => {
+                return None;
+            }
+            (_, ResolutionKind::GlobalFallback) => context.lookup(&identifier.value()),
+            (name, _) => context.lookup(&name),
+        };
+
+        if !PURE_FUNCTIONS.iter().any(|function| function.eq_ignore_ascii_case(name)) {
+            return None;
+        }
+
+        // The function may be redefined by the user, e.g. in the global namespace, when it is not loaded.
+        let name_id = context.interner.intern(name);
+        if let Some(function) = context.codebase.get_function(context.interner, &name_id) {
+            if function.span.start.source.is_user_defined() {
+                return None;
+            }
+        }
+
+        let arguments_are_pure = call.arguments.arguments.iter().all(|argument| match argument {
+            Argument::Positional(PositionalArgument { ellipsis: None, value })
+            | Argument::Named(NamedArgument { ellipsis: None, value, .. }) => self.is_pure(value),
+            _ => false,
+        });
+
+        arguments_are_pure.then_some(name)
+    }
+
+    /// Determines whether the statement follows an echo tag, i.e. `<?= $value ?>`, which outputs its value.
+    fn is_echoed(&self, statement: &ExpressionStatement, context: &LintContext<'_>) -> bool {
+        let content = context.interner.lookup(&context.semantics.source.content);
+
+        content[..statement.span().start.offset].trim_end().ends_with("<?=")
+    }
+
+    /// Determines whether the statement is the first one of the program, which may be a literal intentionally,
+    /// such as a string documenting the file.
+    fn is_first_statement(&self, statement: &ExpressionStatement, context: &LintContext<'_>) -> bool {
+        context
+            .semantics
+            .program
+            .statements
+            .iter()
+            .find(|statement| !matches!(statement, Statement::OpeningTag(_) | Statement::Inline(_)))
+            .is_some_and(|first| first.span() == statement.span())
+    }
+}
+
+impl Rule for RedundantExpressionStatementRule {
+    fn get_name(&self) -> &'static str {
+        "redundant-expression-statement"
+    }
+
+    fn get_description(&self) -> &'static str {
+        "Reports expression statements without effect, such as calls to pure functions, or variables assigned to themselves."
+    }
+
+    fn get_default_level(&self) -> Option<Level> {
+        Some(Level::Warning)
+    }
+}
+
+impl<'a> Walker<LintContext<'a>> for RedundantExpressionStatementRule {
+    fn walk_in_statement_expression<'ast>(
+        &self,
+        statement_expression: &'ast ExpressionStatement,
+        context: &mut LintContext<'a>,
+    ) {
+        if self.is_echoed(statement_expression, context) {
+            return;
+        }
+
+        let expression = &statement_expression.expression;
+
+        let issue = match expression {
+            Expression::AssignmentOperation(Assignment { lhs, operator: AssignmentOperator::Assign(_), rhs }) => {
+                let (
+                    Expression::Variable(Variable::Direct(DirectVariable { name: lhs, .. })),
+                    Expression::Variable(Variable::Direct(DirectVariable { name: rhs, .. })),
+                ) = (lhs.as_ref(), rhs.as_ref())
+                else {
+                    return;
+                };
+
+                if lhs != rhs {
+                    return;
+                }
+
+                Issue::new(context.level(), format!("The variable `{}` is assigned to itself.", context.lookup(lhs)))
+                    .with_annotation(
+                        Annotation::primary(expression.span()).with_message("This assignment has no effect."),
+                    )
+                    .with_help("Did you mean to assign a different value? Otherwise, remove the assignment.")
+            }
+            Expression::Call(Call::Function(call)) => {
+                let Some(name) = self.get_pure_function_name(call, context) else {
+                    return;
+                };
+
+                Issue::new(context.level(), format!("The result of the call to `{}` is not used.", name))
+                    .with_annotation(
+                        Annotation::primary(expression.span())
+                            .with_message(format!("`{}` has no side effects, so this call has no effect.", name)),
+                    )
+                    .with_help(format!("Did you mean to assign the result, e.g. `$result = {}(...)`?", name))
+            }
+            _ if !self.is_pure(expression) => {
+                return;
+            }
+            Expression::Binary(Binary { operator, .. }) if is_comparison(operator) => {
+                let help = match operator {
+                    BinaryOperator::Equal(_) => "Did you mean `=` instead of `==`?",
+                    BinaryOperator::Identical(_) => "Did you mean `=` instead of `===`?",
+                    _ => "Did you mean to use this comparison in a condition? Otherwise, remove it.",
+                };
+
+                Issue::new(context.level(), "The result of this comparison is not used.")
+                    .with_annotation(
+                        Annotation::primary(expression.span()).with_message("This comparison has no effect."),
+                    )
+                    .with_help(help)
+            }
+            Expression::Variable(variable) => Issue::new(context.level(), "This variable statement has no effect.")
+                .with_annotation(
+                    Annotation::primary(variable.span()).with_message("The variable is evaluated, but not used."),
+                )
+                .with_help("Did you mean to assign, return, or echo it? Otherwise, remove the statement."),
+            Expression::Literal(literal) => {
+                if self.is_first_statement(statement_expression, context) {
+                    return;
+                }
+
+                Issue::new(context.level(), "This literal statement has no effect.")
+                    .with_annotation(
+                        Annotation::primary(literal.span()).with_message("The literal is evaluated, but not used."),
+                    )
+                    .with_help("Did you mean to echo, or return it? Otherwise, remove the statement.")
+            }
+            _ => Issue::new(context.level(), "This expression statement has no effect.")
+                .with_annotation(
+                    Annotation::primary(expression.span()).with_message("The expression is evaluated, but not used."),
+                )
+                .with_help("Did you mean to assign, or return, its value? Otherwise, remove the statement."),
+        };
+
+        context.report(issue);
+    }
+}
+
+fn is_comparison(operator: &BinaryOperator) -> bool {
+    matches!(
+        operator,
+        BinaryOperator::Equal(_)
+            | BinaryOperator::NotEqual(_)
+            | BinaryOperator::Identical(_)
+            | BinaryOperator::NotIdentical(_)
+            | BinaryOperator::AngledNotEqual(_)
+            | BinaryOperator::LessThan(_)
+            | BinaryOperator::LessThanOrEqual(_)
+            | BinaryOperator::GreaterThan(_)
+            | BinaryOperator::GreaterThanOrEqual(_)
+            | BinaryOperator::Spaceship(_)
+    )
+}
@@ -40,29 +40,32 @@ impl Rule for RedundantFinalMethodModifierRule {
         "redundant-final-method-modifier"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports `final` modifiers on methods of final classes, and enums."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
 }
 
 impl<'a> Walker<LintContext<'a>> for RedundantFinalMethodModifierRule {
-    fn walk_in_class<'ast>(&self, class: &'ast Class, context: &mut LintContext<'a>) {
-        if !class.modifiers.contains_final() {
+    fn walk_in_method<'ast>(&self, method: &'ast Method, context: &mut LintContext<'a>) {
+        if method.modifiers.get_final().is_none() {
             return;
         }
 
-        for member in class.members.iter() {
-            if let ClassLikeMember::Method(method) = member {
-                self.report(method, context, false);
-            }
-        }
-    }
+        let index = context.semantics.node_index();
+        let Some(class_like) = index.id_of(Node::Method(method)).and_then(|id| index.enclosing_class_like(id)) else {
+            return;
+        };
 
-    fn walk_in_enum<'ast>(&self, r#enum: &'ast Enum, context: &mut LintContext<'a>) {
-        for member in r#enum.members.iter() {
-            if let ClassLikeMember::Method(method) = member {
-                self.report(method, context, true);
-            }
-        }
+        let in_enum = match context.semantics.node(class_like) {
+            Node::Class(class) if class.modifiers.contains_final() => false,
+            Node::Enum(_) => true,
+            _ => return,
+        };
+
+        self.report(method, context, in_enum);
     }
 }
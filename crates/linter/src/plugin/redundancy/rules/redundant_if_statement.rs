@@ -15,6 +15,10 @@ impl Rule for RedundantIfStatementRule {
         "redundant-if-statement"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports `if` statements whose condition always evaluates to the same value."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -15,6 +15,10 @@ impl Rule for RedundantLabelRule {
         "redundant-label"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports `goto` labels that are never jumped to."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
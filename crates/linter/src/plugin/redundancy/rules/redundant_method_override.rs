@@ -16,6 +16,10 @@ impl Rule for RedundantMethodOverrideRule {
         "redundant-method-override"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports methods that only call the method they override, with the same arguments."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -25,6 +25,10 @@ impl Rule for RedundantNoopRule {
         "redundant-noop"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports empty statements, consisting of a single `;`."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -31,6 +31,10 @@ impl Rule for RedundantParenthesesRule {
         "redundant-parentheses"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports parentheses that do not change the meaning of the expression they surround."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -16,6 +16,10 @@ impl Rule for RedundantStringConcatRule {
         "redundant-string-concat"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports concatenations of string literals that can be written as a single literal."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Help)
     }
@@ -43,7 +47,7 @@ impl<'a> Walker<LintContext<'a>> for RedundantStringConcatRule {
                 return;
             }
 
-            let dangerous = matches!(context.interner.lookup(&right.value)[1..].as_bytes(), [b'{', ..]);
+            let dangerous = matches!(&context.interner.lookup(&right.value).as_bytes()[1..], [b'{', ..]);
             if dangerous {
                 // $a = "\u" . "{1F418}";
                 // $b = "\u{1F418}";
@@ -15,9 +15,16 @@ impl Rule for NoErrorControlOperatorRule {
         "no-error-control-operator"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports uses of the error control operator `@`."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
+    fn get_help_uri(&self) -> Option<&'static str> {
+        Some("https://www.php.net/manual/en/language.operators.errorcontrol.php")
+    }
 }
 
 impl<'a> Walker<LintContext<'a>> for NoErrorControlOperatorRule {
@@ -14,9 +14,16 @@ impl Rule for NoEvalRule {
         "no-eval"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports uses of the `eval` construct."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
+    fn get_help_uri(&self) -> Option<&'static str> {
+        Some("https://www.php.net/manual/en/function.eval.php")
+    }
 }
 
 impl<'a> Walker<LintContext<'a>> for NoEvalRule {
@@ -40,6 +40,10 @@ impl Rule for NoFFIRule {
         "no-ffi"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports uses of the FFI extension."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
@@ -16,9 +16,16 @@ impl Rule for NoGlobalRule {
         "no-global"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports uses of the `global` keyword."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
+    fn get_help_uri(&self) -> Option<&'static str> {
+        Some("https://www.php.net/manual/en/language.variables.scope.php")
+    }
 }
 
 impl<'a> Walker<LintContext<'a>> for NoGlobalRule {
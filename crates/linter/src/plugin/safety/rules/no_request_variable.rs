@@ -15,6 +15,10 @@ impl Rule for NoRequestVariableRule {
         "no-request-variable"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports uses of the `$_REQUEST` variable."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
@@ -14,6 +14,10 @@ impl Rule for NoShellExecuteStringRule {
         "no-shell-execute-string"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports shell execute strings with interpolations."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
@@ -16,6 +16,10 @@ impl Rule for NoUnsafeFinallyRule {
         "no-unsafe-finally"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports control flow statements, such as `return`, in `finally` blocks."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
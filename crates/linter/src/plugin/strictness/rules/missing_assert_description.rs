@@ -15,6 +15,10 @@ impl Rule for MissingAssertDescriptionRule {
         "missing-assert-description"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Requires a description as the second argument of `assert` calls."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
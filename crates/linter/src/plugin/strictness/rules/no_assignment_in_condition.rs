@@ -31,6 +31,10 @@ impl Rule for NoAssignmentInConditionRule {
         "no-assignment-in-condition"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Reports assignments in the conditions of `if`, and loop, statements."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
@@ -1,4 +1,5 @@
 use mago_ast::ast::*;
+use mago_php_version::PHPVersion;
 use mago_reporting::*;
 use mago_span::*;
 use mago_walker::Walker;
@@ -15,10 +16,19 @@ impl Rule for RequireConstantTypeRule {
         "require-constant-type"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Requires class constants to declare their type."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
+
+    #[inline]
+    fn get_minimum_php_version(&self) -> Option<PHPVersion> {
+        Some(PHPVersion::PHP83)
+    }
 }
 
 impl<'a> Walker<LintContext<'a>> for RequireConstantTypeRule {
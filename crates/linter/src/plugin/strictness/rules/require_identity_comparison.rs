@@ -15,6 +15,10 @@ impl Rule for RequireIdentityComparisonRule {
         "require-identity-comparison"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Requires identity comparisons, `===` and `!==`, instead of equality comparisons."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
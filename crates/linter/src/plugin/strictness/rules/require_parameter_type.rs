@@ -16,6 +16,10 @@ impl Rule for RequireParameterTypeRule {
         "require-parameter-type"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Requires parameters to declare their type."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
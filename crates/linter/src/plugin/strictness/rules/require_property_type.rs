@@ -1,4 +1,5 @@
 use mago_ast::ast::*;
+use mago_php_version::PHPVersion;
 use mago_reflection::class_like::ClassLikeReflection;
 use mago_reporting::*;
 use mago_span::HasSpan;
@@ -16,10 +17,19 @@ impl Rule for RequirePropertyTypeRule {
         "require-property-type"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Requires properties to declare their type."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
     }
+
+    #[inline]
+    fn get_minimum_php_version(&self) -> Option<PHPVersion> {
+        Some(PHPVersion::PHP74)
+    }
 }
 
 impl RequirePropertyTypeRule {
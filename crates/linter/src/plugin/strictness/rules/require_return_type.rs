@@ -15,6 +15,10 @@ impl Rule for RequireReturnTypeRule {
         "require-return-type"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Requires functions, and methods, to declare their return type."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
@@ -18,6 +18,10 @@ impl Rule for RequireStrictTypesRule {
         "require-strict-types"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Requires files to declare `strict_types=1`."
+    }
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Warning)
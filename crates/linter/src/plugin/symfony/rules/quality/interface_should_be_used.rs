@@ -15,6 +15,10 @@ impl Rule for InterfaceShouldBeUsed {
         "interface-should-be-used"
     }
 
+    fn get_description(&self) -> &'static str {
+        "Suggests type hinting the Symfony interfaces instead of their implementations."
+    }
+
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Note)
     }
@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 use mago_ast::Program;
+use mago_php_version::PHPVersion;
 use mago_reporting::Level;
 use mago_walker::Walker;
 
@@ -31,11 +32,40 @@ pub trait Rule: for<'a> Walker<LintContext<'a>> + Send + Sync + Debug {
     /// This name is used in configurations to enable or disable the rule.
     fn get_name(&self) -> &'static str;
 
+    /// Returns a short description of what this rule checks, e.g. to describe it in reports.
+    fn get_description(&self) -> &'static str;
+
     #[inline]
     fn get_default_level(&self) -> Option<Level> {
         Some(Level::Error)
     }
 
+    /// Returns the oldest version of PHP this rule applies to, if any.
+    ///
+    /// Rules suggesting features introduced in a version of PHP, such as `str_contains` in PHP 8.0, return
+    /// that version, so that they are skipped when an older version is targeted.
+    #[inline]
+    fn get_minimum_php_version(&self) -> Option<PHPVersion> {
+        None
+    }
+
+    /// Returns a URI pointing to the documentation of this rule, if any.
+    ///
+    /// When provided, the URI is attached to every issue reported by this rule.
+    #[inline]
+    fn get_help_uri(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns whether this rule needs the index of the references to the symbols of the codebase.
+    ///
+    /// The index is only built when an enabled rule needs it, as it costs memory, and is then available
+    /// to every rule through [`LintContext::references`].
+    #[inline]
+    fn requires_references(&self) -> bool {
+        false
+    }
+
     /// Lint the entire program for this rule.
     ///
     /// This method is called to apply the rule to the whole [`Program`] AST.
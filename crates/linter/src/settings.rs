@@ -3,10 +3,13 @@ use serde::Deserialize;
 use serde::Serialize;
 use toml::value::Value;
 
+use mago_php_version::PHPVersion;
 use mago_reporting::Level;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Settings {
+    /// The targeted version of PHP, which rules can use to only suggest features that it supports.
+    pub php_version: PHPVersion,
     pub level: Option<Level>,
     pub default_plugins: bool,
     pub plugins: Vec<String>,
@@ -22,7 +25,13 @@ pub struct RuleSettings {
 
 impl Settings {
     pub fn new() -> Self {
-        Self { level: Some(Level::Error), default_plugins: true, plugins: Vec::new(), rules: HashMap::default() }
+        Self {
+            php_version: PHPVersion::LATEST,
+            level: Some(Level::Error),
+            default_plugins: true,
+            plugins: Vec::new(),
+            rules: HashMap::default(),
+        }
     }
 
     pub fn is_enabled(&self) -> bool {
@@ -38,6 +47,11 @@ impl Settings {
         self
     }
 
+    pub fn with_php_version(mut self, php_version: PHPVersion) -> Self {
+        self.php_version = php_version;
+        self
+    }
+
     pub fn with_level(mut self, level: Level) -> Self {
         self.level = Some(level);
         self
@@ -0,0 +1,384 @@
+use std::sync::LazyLock;
+
+use ahash::HashSet;
+
+use mago_ast::node::NodeKind;
+use mago_ast::Node;
+use mago_ast::Statement;
+use mago_interner::ThreadedInterner;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_semantics::Semantics;
+use mago_source::Source;
+use mago_span::HasSpan;
+use mago_span::Span;
+
+use crate::plugin::Plugin;
+
+/// The pragma used to expect, and therefore suppress, issues of the given rules.
+const EXPECT_PRAGMA: &str = "@mago-expect";
+
+/// The pragma used to ignore all issues, or the issues of the given rules, in a file.
+const IGNORE_FILE_PRAGMA: &str = "@mago-ignore-file";
+
+/// The pragma used to disable the given rules until they are enabled again.
+const DISABLE_PRAGMA: &str = "@mago-disable";
+
+/// The pragma used to enable rules previously disabled with `@mago-disable`.
+const ENABLE_PRAGMA: &str = "@mago-enable";
+
+/// The code of the issue reported for suppressions that do not match any issue.
+pub const UNUSED_SUPPRESSION_CODE: &str = "unused-suppression";
+
+/// The code of the issue reported for `@mago-disable` comments without a matching `@mago-enable`.
+const UNBALANCED_SUPPRESSION_CODE: &str = "unbalanced-suppression";
+
+/// The full names (`plugin/rule`) of all the rules provided by the linter plugins, whether enabled or not.
+static LINTER_RULES: LazyLock<Vec<String>> = LazyLock::new(|| {
+    let mut rules = vec![];
+
+    crate::foreach_plugin!(|plugin| {
+        let name = Plugin::get_name(&plugin);
+
+        for rule in Plugin::get_rules(&plugin) {
+            rules.push(format!("{}/{}", name, rule.get_name()));
+        }
+    });
+
+    rules
+});
+
+/// A `@mago-expect` comment, suppressing the issues of the given rules in a statement, or on a single line.
+#[derive(Debug)]
+struct Expectation {
+    /// The span of the comment containing the pragma.
+    span: Span,
+    /// The offset at which the range of the suppressed issues starts.
+    start: usize,
+    /// The offset at which the range of the suppressed issues ends.
+    end: usize,
+    /// The expected rules, and whether they matched an issue.
+    rules: Vec<(String, bool)>,
+}
+
+/// A range of the source in which the issues of a rule are suppressed.
+#[derive(Debug)]
+struct DisabledRange {
+    rule: String,
+    start: usize,
+    end: usize,
+}
+
+/// Removes the issues suppressed by comments in the source of the given semantics.
+///
+/// The given issues may come from the linter, as well as from the semantics, or the reflection of the
+/// codebase: any issue with a code, whose primary annotation is in the source, can be suppressed.
+///
+/// Expectations (`@mago-expect`) are applied first, so that their unused suppression warnings can
+/// themselves be ignored by `@mago-ignore-file`, `@mago-disable`, and `@mago-enable` comments.
+///
+/// The given enabled rules are the full names of the linter rules run on the source: the expectations
+/// of the other linter rules are not reported as unused, as these rules could not report any issue.
+pub fn apply_suppressions(
+    interner: &ThreadedInterner,
+    semantics: &Semantics,
+    issues: IssueCollection,
+    enabled_rules: &[String],
+) -> IssueCollection {
+    let issues = apply_expectations(interner, semantics, issues, enabled_rules);
+
+    apply_disabled_ranges(interner, semantics, issues)
+}
+
+/// Removes the issues that are expected by a `@mago-expect` comment.
+///
+/// A comment on the same line as the code suppresses issues on that line, while a comment on its
+/// own line suppresses issues in the statement, or class-like member, starting on the next line that
+/// is not a comment, or on that line alone if no statement starts there. Rules can be referenced either
+/// by their full name (`plugin/rule`) or by their name alone.
+///
+/// Every expected rule that does not match any issue is reported as an `unused-suppression` warning,
+/// unless it is a linter rule which is not enabled.
+fn apply_expectations(
+    interner: &ThreadedInterner,
+    semantics: &Semantics,
+    issues: IssueCollection,
+    enabled_rules: &[String],
+) -> IssueCollection {
+    let mut expectations = collect_expectations(interner, semantics);
+    if expectations.is_empty() {
+        return issues;
+    }
+
+    let source = &semantics.source;
+    let mut remaining = IssueCollection::new();
+    for issue in issues {
+        let offset = issue
+            .annotations
+            .iter()
+            .find(|annotation| annotation.is_primary())
+            .filter(|annotation| annotation.span.start.source == source.identifier)
+            .map(|annotation| annotation.span.start.offset);
+
+        let (Some(offset), Some(code)) = (offset, issue.code.as_deref()) else {
+            remaining.push(issue);
+
+            continue;
+        };
+
+        let mut suppressed = false;
+        for expectation in
+            expectations.iter_mut().filter(|expectation| expectation.start <= offset && offset < expectation.end)
+        {
+            for (rule, used) in expectation.rules.iter_mut() {
+                if matches_rule(code, rule) {
+                    *used = true;
+                    suppressed = true;
+                }
+            }
+        }
+
+        if !suppressed {
+            remaining.push(issue);
+        }
+    }
+
+    for expectation in expectations {
+        for (rule, _) in expectation.rules.into_iter().filter(|(rule, used)| !used && has_run(rule, enabled_rules)) {
+            remaining.push(
+                Issue::warning(format!("Unused suppression of rule `{}`.", rule))
+                    .with_code(UNUSED_SUPPRESSION_CODE)
+                    .with_annotation(
+                        Annotation::primary(expectation.span)
+                            .with_message("this suppression does not match any issue."),
+                    )
+                    .with_help(format!("Remove `{}` from the suppression, or remove the comment entirely.", rule)),
+            );
+        }
+    }
+
+    remaining
+}
+
+fn collect_expectations(interner: &ThreadedInterner, semantics: &Semantics) -> Vec<Expectation> {
+    let source = &semantics.source;
+    let content = interner.lookup(&source.content);
+
+    // Lines that only contain comments are skipped when looking for the line a comment applies to,
+    // so that multiple suppression comments can be stacked above the same line.
+    let mut comment_lines = HashSet::default();
+    let mut pragmas = vec![];
+    for trivia in semantics.program.comments.all().iter() {
+        let start_line = source.line_number(trivia.span.start.offset);
+        let end_line = source.line_number(trivia.span.end.offset);
+        let line_start = source.lines.get(start_line).copied().unwrap_or(0);
+        let line_end = source.lines.get(end_line + 1).copied().unwrap_or(content.len());
+        let leading = content.get(line_start..trivia.span.start.offset).is_some_and(|prefix| prefix.trim().is_empty());
+        let trailing = content.get(trivia.span.end.offset..line_end).is_some_and(|suffix| suffix.trim().is_empty());
+        let own_line = leading && trailing;
+
+        if own_line {
+            comment_lines.extend(start_line..=end_line);
+        }
+
+        let text = interner.lookup(&trivia.value);
+        if let Some(rules) = parse_pragma(text, EXPECT_PRAGMA).filter(|rules| !rules.is_empty()) {
+            // A comment followed by code applies to the line of that code, otherwise to the line it starts on.
+            let line = if leading { end_line } else { start_line };
+
+            pragmas.push((trivia.span, own_line, line, rules));
+        }
+    }
+
+    pragmas
+        .into_iter()
+        .map(|(span, own_line, line, rules)| {
+            let (start, end) = if own_line {
+                let mut line = line + 1;
+                while comment_lines.contains(&line) {
+                    line += 1;
+                }
+
+                match statement_starting_on(Node::Program(&semantics.program), source, line) {
+                    Some(statement) => (statement.start.offset, statement.end.offset),
+                    None => line_range(source, content, line),
+                }
+            } else {
+                line_range(source, content, line)
+            };
+
+            Expectation { span, start, end, rules: rules.into_iter().map(|rule| (rule, false)).collect() }
+        })
+        .collect()
+}
+
+/// Returns the span of the outermost statement, or class-like member, starting on the given line.
+fn statement_starting_on(node: Node<'_>, source: &Source, line: usize) -> Option<Span> {
+    for child in node.children() {
+        let span = child.span();
+        if source.line_number(span.end.offset) < line {
+            continue;
+        }
+
+        if source.line_number(span.start.offset) > line {
+            break;
+        }
+
+        if matches!(child.kind(), NodeKind::Statement | NodeKind::ClassLikeMember)
+            && source.line_number(span.start.offset) == line
+        {
+            return Some(span);
+        }
+
+        if let Some(span) = statement_starting_on(child, source, line) {
+            return Some(span);
+        }
+    }
+
+    None
+}
+
+/// Returns the range of offsets covered by the given line, including its line terminator.
+fn line_range(source: &Source, content: &str, line: usize) -> (usize, usize) {
+    let start = source.lines.get(line).copied().unwrap_or(content.len());
+    let end = source.lines.get(line + 1).copied().unwrap_or(content.len());
+
+    (start, end)
+}
+
+/// Removes the issues of files ignored with `@mago-ignore-file`, and of ranges disabled with `@mago-disable`.
+///
+/// `@mago-ignore-file` is only honored in the comments preceding the first statement of the file, and
+/// ignores every rule when no rule is listed. A `@mago-disable` comment without a matching
+/// `@mago-enable` comment disables the rule until the end of the file, and is reported as a note.
+fn apply_disabled_ranges(
+    interner: &ThreadedInterner,
+    semantics: &Semantics,
+    issues: IssueCollection,
+) -> IssueCollection {
+    // Comments placed before the first statement, ignoring the opening tag and `declare` statements.
+    let leading_end = semantics
+        .program
+        .statements
+        .iter()
+        .find(|statement| !matches!(statement, Statement::OpeningTag(_) | Statement::Inline(_) | Statement::Declare(_)))
+        .map(|statement| statement.span().start.offset)
+        .unwrap_or(usize::MAX);
+
+    let mut ranges: Vec<DisabledRange> = vec![];
+    let mut ignore_file: Option<Vec<String>> = None;
+    let mut open: Vec<(String, Span)> = vec![];
+    for trivia in semantics.program.comments.all().iter() {
+        let text = interner.lookup(&trivia.value);
+
+        if trivia.span.end.offset <= leading_end {
+            if let Some(rules) = parse_pragma(text, IGNORE_FILE_PRAGMA) {
+                ignore_file.get_or_insert_with(Vec::new).extend(if rules.is_empty() {
+                    vec!["*".to_string()]
+                } else {
+                    rules
+                });
+            }
+        }
+
+        if let Some(rules) = parse_pragma(text, DISABLE_PRAGMA) {
+            for rule in rules {
+                if !open.iter().any(|(open_rule, _)| open_rule == &rule) {
+                    open.push((rule, trivia.span));
+                }
+            }
+        } else if let Some(rules) = parse_pragma(text, ENABLE_PRAGMA) {
+            for rule in rules {
+                if let Some(index) = open.iter().position(|(open_rule, _)| open_rule == &rule) {
+                    let (rule, span) = open.remove(index);
+
+                    ranges.push(DisabledRange { rule, start: span.start.offset, end: trivia.span.end.offset });
+                }
+            }
+        }
+    }
+
+    let mut unbalanced = vec![];
+    for (rule, span) in open {
+        ranges.push(DisabledRange { rule: rule.clone(), start: span.start.offset, end: usize::MAX });
+        unbalanced.push((rule, span));
+    }
+
+    if ranges.is_empty() && ignore_file.is_none() {
+        return issues;
+    }
+
+    let source = semantics.source.identifier;
+    let mut remaining = IssueCollection::from(issues.into_iter().filter(|issue| {
+        let Some(code) = issue.code.as_deref() else {
+            return true;
+        };
+
+        if let Some(rules) = &ignore_file {
+            if rules.iter().any(|rule| rule == "*" || matches_rule(code, rule)) {
+                return false;
+            }
+        }
+
+        let Some(offset) = issue
+            .annotations
+            .iter()
+            .find(|annotation| annotation.is_primary())
+            .filter(|annotation| annotation.span.start.source == source)
+            .map(|annotation| annotation.span.start.offset)
+        else {
+            return true;
+        };
+
+        !ranges.iter().any(|range| offset >= range.start && offset < range.end && matches_rule(code, &range.rule))
+    }));
+
+    for (rule, span) in unbalanced {
+        remaining.push(
+            Issue::note(format!("Rule `{}` is disabled until the end of the file.", rule))
+                .with_code(UNBALANCED_SUPPRESSION_CODE)
+                .with_annotation(Annotation::primary(span).with_message("this `@mago-disable` is never re-enabled."))
+                .with_help(format!(
+                    "Add a `{} {}` comment where the rule should be enabled again.",
+                    ENABLE_PRAGMA, rule
+                )),
+        );
+    }
+
+    remaining
+}
+
+/// Parses the comma-separated list of rules following the given pragma in a comment.
+///
+/// Returns `None` if the comment does not contain the pragma, and an empty list if no rule is listed.
+fn parse_pragma(comment: &str, pragma: &str) -> Option<Vec<String>> {
+    let (_, rest) = comment.split_once(pragma)?;
+    // Make sure the pragma is not the prefix of another one, e.g. `@mago-ignore` and `@mago-ignore-file`.
+    if rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+
+    // Only the rest of the line is considered, so that the pragma may be followed by a description in doc-blocks.
+    let rest = rest.lines().next().unwrap_or("").trim_end().trim_end_matches("*/");
+
+    Some(
+        rest.split(',')
+            .filter_map(|part| part.split_whitespace().next())
+            .map(|rule| rule.to_ascii_lowercase())
+            .collect(),
+    )
+}
+
+/// Checks whether the given rule was run, that is whether it is an enabled linter rule, or is not a
+/// linter rule at all, in which case it belongs to an analysis which always runs.
+fn has_run(rule: &str, enabled_rules: &[String]) -> bool {
+    enabled_rules.iter().any(|enabled| matches_rule(enabled, rule))
+        || !LINTER_RULES.iter().any(|linter_rule| matches_rule(linter_rule, rule))
+}
+
+/// Returns whether the given issue code refers to the given rule, which may omit the plugin name.
+fn matches_rule(code: &str, rule: &str) -> bool {
+    code.eq_ignore_ascii_case(rule)
+        || code.rsplit_once('/').is_some_and(|(_, name)| !rule.contains('/') && name.eq_ignore_ascii_case(rule))
+}
@@ -0,0 +1,20 @@
+<?php
+
+final class Invoice
+{
+    private function total(): int
+    {
+        return 0;
+    }
+
+    /** The number of the invoice. */
+    public string $number = '';
+
+    public function __construct()
+    {
+    }
+
+    use Timestamps; // Adds the timestamps.
+
+    public const PREFIX = 'INV';
+}
@@ -0,0 +1,41 @@
+<?php
+
+final class Invoice
+{
+    use Timestamps;
+
+    public const PREFIX = 'INV';
+
+    public string $number = '';
+
+    public function __construct()
+    {
+    }
+
+    public function send(): void
+    {
+    }
+
+    protected function format(): string
+    {
+        return '';
+    }
+
+    private function total(): int
+    {
+        return 0;
+    }
+}
+
+enum Status
+{
+    use Labels;
+
+    case Draft;
+    case Sent;
+
+    public function label(): string
+    {
+        return '';
+    }
+}
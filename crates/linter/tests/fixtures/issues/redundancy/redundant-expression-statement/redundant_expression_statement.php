@@ -0,0 +1,12 @@
+<?php
+
+function compute(int $a, array $values): void
+{
+    $a == 1;
+    $a;
+    'a string';
+    42;
+    $a = $a;
+    array_merge($values, [1]);
+    strtolower('A');
+}
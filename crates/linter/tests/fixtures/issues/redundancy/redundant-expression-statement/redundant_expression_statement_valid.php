@@ -0,0 +1,16 @@
+<?php
+
+'first statement of the file';
+
+function compute(int $a, array $values): array
+{
+    $a = 1;
+    $a++;
+    @$values[0];
+    array_push($values, $a);
+    array_merge($values, [next($values)]);
+    $values = array_merge($values, [1]);
+    echo $a;
+
+    return $values;
+}
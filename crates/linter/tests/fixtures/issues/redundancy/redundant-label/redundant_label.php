@@ -0,0 +1,15 @@
+<?php
+
+declare(strict_types=1);
+
+function retry(): void
+{
+    attempt:
+    unused:
+
+    if (!try_once()) {
+        goto attempt;
+    }
+
+    nested:
+}
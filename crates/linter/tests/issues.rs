@@ -0,0 +1,126 @@
+use std::path::Path;
+
+use mago_fixer::FixPlan;
+use mago_interner::ThreadedInterner;
+use mago_linter::settings::RuleSettings;
+use mago_linter::settings::Settings;
+use mago_linter::Linter;
+use mago_parser::settings::ParserSettings;
+use mago_php_version::PHPVersion;
+use mago_reporting::Level;
+use mago_semantics::Semantics;
+use mago_source::line_index::ColumnEncoding;
+use mago_source::SourceManager;
+
+/// Renders the issues the given rule reports in the given code as text, one issue per block, with 1-based
+/// lines and columns, followed by the code with the suggested fixes applied, if any.
+fn render_issues(rule: &str, code: &str) -> String {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source_id = manager.insert_content("code.php".to_string(), code.to_string(), true);
+    let source = manager.load(&source_id).expect("the source can be loaded");
+    let semantics = Semantics::build(&interner, PHPVersion::LATEST, ParserSettings::default(), source);
+    assert!(semantics.parse_errors.is_empty(), "the fixture cannot be parsed: {:?}", semantics.parse_errors);
+
+    let plugin = rule.split_once('/').map(|(plugin, _)| plugin).expect("the rule has a plugin");
+    // The rule is given a level, so that the rules which are disabled by default are run as well.
+    let settings = Settings::new()
+        .with_default_plugins(false)
+        .with_plugins(vec![plugin.to_string()])
+        .with_rule(rule, RuleSettings::from_level(Some(Level::Warning)));
+    let codebase = mago_reflector::reflect(&interner, &semantics.source, &semantics.program, &semantics.names);
+    let linter = Linter::with_all_plugins(settings, interner.clone(), codebase);
+
+    let location = |offset: usize| {
+        let position = manager.line_column(&source_id, offset, ColumnEncoding::Utf8CodePoints).unwrap();
+
+        format!("{}:{}", position.line + 1, position.column + 1)
+    };
+
+    let mut output = String::new();
+    let mut fix = FixPlan::new();
+    for issue in linter.lint(&semantics).iter().filter(|issue| issue.code.as_deref() == Some(rule)) {
+        for (_, plan) in issue.suggestions.iter() {
+            fix.merge(plan.clone());
+        }
+
+        output.push_str(&format!("{}: {}\n", issue.level.to_string().to_lowercase(), issue.message));
+        for annotation in issue.annotations.iter() {
+            output.push_str(&format!(
+                "  {} {}-{}: {}\n",
+                if annotation.is_primary() { "primary" } else { "secondary" },
+                location(annotation.span.start.offset),
+                location(annotation.span.end.offset),
+                annotation.message.as_deref().unwrap_or_default(),
+            ));
+        }
+
+        if let Some(help) = issue.help.as_ref() {
+            output.push_str(&format!("  help: {}\n", help));
+        }
+
+        if let Some(help_uri) = issue.help_uri.as_ref() {
+            output.push_str(&format!("  docs: {}\n", help_uri));
+        }
+    }
+
+    if !fix.is_empty() {
+        output.push_str("fixed:\n");
+        output.push_str(&fix.execute(code).get_fixed());
+    }
+
+    output
+}
+
+/// Compares the issues of every file in the `fixtures/issues/<plugin>/<rule>` directories with the ones expected
+/// in the `.txt` file next to it, which is written instead if the `UPDATE_SNAPSHOTS` environment variable is set.
+#[test]
+pub fn test_issues_of_fixtures() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/issues");
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+
+    let read_dir = |path: &Path| {
+        let mut paths: Vec<_> = std::fs::read_dir(path)
+            .expect("the fixtures directory can be read")
+            .map(|entry| entry.expect("the fixtures directory can be read").path())
+            .collect();
+
+        paths.sort();
+        paths
+    };
+
+    let mut fixtures = vec![];
+    for plugin in read_dir(&directory) {
+        for rule in read_dir(&plugin) {
+            let name = format!(
+                "{}/{}",
+                plugin.file_name().unwrap().to_string_lossy(),
+                rule.file_name().unwrap().to_string_lossy()
+            );
+
+            for path in read_dir(&rule) {
+                if path.extension().is_some_and(|extension| extension == "php") {
+                    fixtures.push((name.clone(), path));
+                }
+            }
+        }
+    }
+
+    assert!(!fixtures.is_empty(), "no fixtures were found");
+
+    for (rule, path) in fixtures {
+        let code = std::fs::read_to_string(&path).expect("the fixture can be read");
+        let rendered = render_issues(&rule, &code);
+        let snapshot = path.with_extension("txt");
+
+        if update {
+            std::fs::write(&snapshot, &rendered).expect("the snapshot can be written");
+
+            continue;
+        }
+
+        let expected = std::fs::read_to_string(&snapshot).unwrap_or_default();
+
+        pretty_assertions::assert_eq!(expected, rendered, "the issues of `{}` have changed", path.display());
+    }
+}
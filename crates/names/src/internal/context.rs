@@ -10,6 +10,9 @@ use strum::Display;
 use mago_ast::ast::*;
 use mago_interner::StringIdentifier;
 use mago_interner::ThreadedInterner;
+use mago_span::Span;
+
+use crate::ResolutionKind;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord, Display)]
 #[serde(tag = "type", content = "value")]
@@ -22,9 +25,9 @@ pub enum NameKind {
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
 pub struct NameResolutionContext {
     namespace_name: String,
-    default_aliases: HashMap<String, String, RandomState>,
-    function_aliases: HashMap<String, String, RandomState>,
-    constant_aliases: HashMap<String, String, RandomState>,
+    default_aliases: HashMap<String, (String, Span), RandomState>,
+    function_aliases: HashMap<String, (String, Span), RandomState>,
+    constant_aliases: HashMap<String, (String, Span), RandomState>,
 }
 
 #[derive(Debug)]
@@ -98,7 +101,13 @@ impl<'a> NameContext<'a> {
             self.name_resolution_contexts.last().map(|last_context| last_context.namespace_name.clone());
     }
 
-    pub fn add_name(&mut self, kind: NameKind, name_id: StringIdentifier, alias_id: Option<StringIdentifier>) {
+    pub fn add_name(
+        &mut self,
+        kind: NameKind,
+        name_id: StringIdentifier,
+        alias_id: Option<StringIdentifier>,
+        use_item: Span,
+    ) {
         let name = self.interner.lookup(&name_id);
 
         let alias = match alias_id {
@@ -118,40 +127,53 @@ impl<'a> NameContext<'a> {
             .expect("expected there to be at least one resolution context in the context");
 
         match kind {
-            NameKind::Default => context.default_aliases.insert(alias, name.to_owned()),
-            NameKind::Function => context.function_aliases.insert(alias, name.to_owned()),
-            NameKind::Constant => context.constant_aliases.insert(alias, name.to_owned()),
+            NameKind::Default => context.default_aliases.insert(alias, (name.to_owned(), use_item)),
+            NameKind::Function => context.function_aliases.insert(alias, (name.to_owned(), use_item)),
+            NameKind::Constant => context.constant_aliases.insert(alias, (name.to_owned(), use_item)),
         };
     }
 
-    pub fn resolve_name(&mut self, kind: NameKind, name_id: StringIdentifier) -> (StringIdentifier, bool) {
+    pub fn resolve_name(&mut self, kind: NameKind, name_id: StringIdentifier) -> (StringIdentifier, ResolutionKind) {
         let name = self.interner.lookup(&name_id);
 
         if let Some(stripped) = name.strip_prefix('\\') {
-            return (self.interner.intern(stripped), true);
+            return (self.interner.intern(stripped), ResolutionKind::FullyQualified);
         }
 
-        if let Some(alias) = self.resolve_alias(kind, name) {
-            return (alias, true);
+        if let Some((alias, use_item)) = self.resolve_alias(kind, name) {
+            return match use_item {
+                Some(use_item) => (alias, ResolutionKind::Imported(use_item)),
+                None => (alias, ResolutionKind::SameNamespace),
+            };
         }
 
         match self.get_namespace_name() {
             Some(namespace_name) => {
                 if namespace_name.is_empty() {
-                    return (name_id, false);
+                    return (name_id, ResolutionKind::SameNamespace);
                 }
 
+                // Unqualified functions and constants fall back to the global ones, if the namespaced ones
+                // are not defined at runtime.
+                let resolution = if kind != NameKind::Default && !name.contains('\\') {
+                    ResolutionKind::GlobalFallback
+                } else {
+                    ResolutionKind::SameNamespace
+                };
+
                 let mut resolved = namespace_name.clone();
                 resolved.push('\\');
                 resolved.push_str(name);
 
-                (self.interner.intern(resolved), false)
+                (self.interner.intern(resolved), resolution)
             }
-            None => (name_id, false),
+            None => (name_id, ResolutionKind::SameNamespace),
         }
     }
 
-    fn resolve_alias(&mut self, kind: NameKind, name: &str) -> Option<StringIdentifier> {
+    /// Resolves the name using the aliases of the current context, or relative to the current namespace if it
+    /// is prefixed with `namespace\`, returning the span of the `use` item it was imported by, if any.
+    fn resolve_alias(&mut self, kind: NameKind, name: &str) -> Option<(StringIdentifier, Option<Span>)> {
         let context = self
             .name_resolution_contexts
             .last()
@@ -170,20 +192,20 @@ impl<'a> NameContext<'a> {
                     resolved.push('\\');
                     resolved.push_str(&suffix);
 
-                    return Some(self.interner.intern(resolved));
+                    return Some((self.interner.intern(resolved), None));
                 }
 
-                return Some(self.interner.intern(suffix));
+                return Some((self.interner.intern(suffix), None));
             } else {
                 context.default_aliases.get(first_part_lower.as_str())
             };
 
-            if let Some(alias) = alias {
+            if let Some((alias, use_item)) = alias {
                 let mut resolved = alias.clone();
                 resolved.push('\\');
                 resolved.push_str(&suffix);
 
-                return Some(self.interner.intern(resolved));
+                return Some((self.interner.intern(resolved), Some(*use_item)));
             }
         } else {
             let alias = match kind {
@@ -192,8 +214,8 @@ impl<'a> NameContext<'a> {
                 NameKind::Constant => context.constant_aliases.get(first_part_lower.as_str()),
             };
 
-            if let Some(resolved) = alias {
-                return Some(self.interner.intern(resolved));
+            if let Some((resolved, use_item)) = alias {
+                return Some((self.interner.intern(resolved), Some(*use_item)));
             }
         }
 
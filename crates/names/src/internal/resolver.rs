@@ -7,6 +7,7 @@ use mago_walker::MutWalker;
 use crate::internal::context::NameContext;
 use crate::internal::context::NameKind;
 use crate::Names;
+use crate::ResolutionKind;
 
 #[derive(Debug, Clone)]
 pub struct NameResolver {
@@ -15,7 +16,7 @@ pub struct NameResolver {
 
 impl NameResolver {
     pub fn new() -> Self {
-        NameResolver { resolved_names: Names { names: HashMap::default() } }
+        NameResolver { resolved_names: Names { names: HashMap::default(), usages: HashMap::default() } }
     }
 }
 
@@ -36,7 +37,8 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
                     let name = use_item.name.value();
                     let alias = use_item.alias.as_ref().map(|alias| alias.identifier.value);
 
-                    context.add_name(NameKind::Default, name, alias);
+                    self.resolved_names.insert_import(use_item.span());
+                    context.add_name(NameKind::Default, name, alias, use_item.span());
                 }
             }
             UseItems::TypedSequence(typed_use_item_sequence) => {
@@ -49,7 +51,8 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
                     let name = use_item.name.value();
                     let alias = use_item.alias.as_ref().map(|alias| alias.identifier.value);
 
-                    context.add_name(name_kind, name, alias);
+                    self.resolved_names.insert_import(use_item.span());
+                    context.add_name(name_kind, name, alias, use_item.span());
                 }
             }
             UseItems::TypedList(typed_use_item_list) => {
@@ -70,7 +73,8 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
 
                     let namespaced_id = context.interner.intern(&namespaced);
 
-                    context.add_name(name_kind, namespaced_id, alias);
+                    self.resolved_names.insert_import(use_item.span());
+                    context.add_name(name_kind, namespaced_id, alias, use_item.span());
                 }
             }
             UseItems::MixedList(mixed_use_item_list) => {
@@ -92,7 +96,8 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
 
                     let namespaced_id = context.interner.intern(&namespaced);
 
-                    context.add_name(kind, namespaced_id, alias);
+                    self.resolved_names.insert_import(use_item.item.span());
+                    context.add_name(kind, namespaced_id, alias, use_item.item.span());
                 }
             }
         };
@@ -102,83 +107,105 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
         for item in constant.items.iter() {
             let name = context.get_namespaced_identifier(&item.name);
 
-            self.resolved_names.insert_at(item.name.span().start, name, false);
+            self.resolved_names.insert_at(item.name.span().start, name, ResolutionKind::Declaration);
         }
     }
 
     fn walk_in_function<'ast>(&mut self, function: &'ast Function, context: &mut NameContext<'a>) {
         let name = context.get_namespaced_identifier(&function.name);
 
-        self.resolved_names.insert_at(function.name.span().start, name, false);
+        self.resolved_names.insert_at(function.name.span().start, name, ResolutionKind::Declaration);
     }
 
     fn walk_in_class<'ast>(&mut self, class: &'ast Class, context: &mut NameContext<'a>) {
         let classlike = context.get_namespaced_identifier(&class.name);
 
-        self.resolved_names.insert_at(class.name.span().start, classlike, false);
+        self.resolved_names.insert_at(class.name.span().start, classlike, ResolutionKind::Declaration);
     }
 
     fn walk_in_interface<'ast>(&mut self, interface: &'ast Interface, context: &mut NameContext<'a>) {
         let classlike = context.get_namespaced_identifier(&interface.name);
 
-        self.resolved_names.insert_at(interface.name.span().start, classlike, false);
+        self.resolved_names.insert_at(interface.name.span().start, classlike, ResolutionKind::Declaration);
     }
 
     fn walk_in_trait<'ast>(&mut self, r#trait: &'ast Trait, context: &mut NameContext<'a>) {
         let classlike = context.get_namespaced_identifier(&r#trait.name);
 
-        self.resolved_names.insert_at(r#trait.name.span().start, classlike, false);
+        self.resolved_names.insert_at(r#trait.name.span().start, classlike, ResolutionKind::Declaration);
     }
 
     fn walk_in_enum<'ast>(&mut self, r#enum: &'ast Enum, context: &mut NameContext<'a>) {
         let classlike = context.get_namespaced_identifier(&r#enum.name);
 
-        self.resolved_names.insert_at(r#enum.name.span().start, classlike, false);
+        self.resolved_names.insert_at(r#enum.name.span().start, classlike, ResolutionKind::Declaration);
     }
 
     fn walk_in_trait_use<'ast>(&mut self, trait_use: &'ast TraitUse, context: &mut NameContext<'a>) {
         for trait_name in trait_use.trait_names.iter() {
-            let (trait_classlike, imported) = context.resolve_name(NameKind::Default, trait_name.value());
+            let (trait_classlike, resolution) = context.resolve_name(NameKind::Default, trait_name.value());
 
-            self.resolved_names.insert_at(trait_name.span().start, trait_classlike, imported);
+            self.resolved_names.insert_at(trait_name.span().start, trait_classlike, resolution);
         }
     }
 
+    fn walk_in_trait_use_precedence_adaptation<'ast>(
+        &mut self,
+        adaptation: &'ast TraitUsePrecedenceAdaptation,
+        context: &mut NameContext<'a>,
+    ) {
+        for trait_name in adaptation.trait_names.iter() {
+            let (trait_classlike, resolution) = context.resolve_name(NameKind::Default, trait_name.value());
+
+            self.resolved_names.insert_at(trait_name.span().start, trait_classlike, resolution);
+        }
+    }
+
+    fn walk_in_trait_use_absolute_method_reference<'ast>(
+        &mut self,
+        reference: &'ast TraitUseAbsoluteMethodReference,
+        context: &mut NameContext<'a>,
+    ) {
+        let (trait_classlike, resolution) = context.resolve_name(NameKind::Default, reference.trait_name.value());
+
+        self.resolved_names.insert_at(reference.trait_name.span().start, trait_classlike, resolution);
+    }
+
     fn walk_in_extends<'ast>(&mut self, extends: &'ast Extends, context: &mut NameContext<'a>) {
         for parent in extends.types.iter() {
-            let (parent_classlike, imported) = context.resolve_name(NameKind::Default, parent.value());
+            let (parent_classlike, resolution) = context.resolve_name(NameKind::Default, parent.value());
 
-            self.resolved_names.insert_at(parent.span().start, parent_classlike, imported);
+            self.resolved_names.insert_at(parent.span().start, parent_classlike, resolution);
         }
     }
 
     fn walk_in_implements<'ast>(&mut self, implements: &'ast Implements, context: &mut NameContext<'a>) {
         for parent in implements.types.iter() {
-            let (parent_classlike, imported) = context.resolve_name(NameKind::Default, parent.value());
+            let (parent_classlike, resolution) = context.resolve_name(NameKind::Default, parent.value());
 
-            self.resolved_names.insert_at(parent.span().start, parent_classlike, imported);
+            self.resolved_names.insert_at(parent.span().start, parent_classlike, resolution);
         }
     }
 
     fn walk_in_hint<'ast>(&mut self, hint: &'ast Hint, context: &mut NameContext<'a>) {
         if let Hint::Identifier(identifier) = hint {
-            let (name, imported) = context.resolve_name(NameKind::Default, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Default, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
     fn walk_in_attribute<'ast>(&mut self, attribute: &'ast Attribute, context: &mut NameContext<'a>) {
-        let (name, imported) = context.resolve_name(NameKind::Default, attribute.name.value());
+        let (name, resolution) = context.resolve_name(NameKind::Default, attribute.name.value());
 
-        self.resolved_names.insert_at(attribute.name.span().start, name, imported);
+        self.resolved_names.insert_at(attribute.name.span().start, name, resolution);
     }
 
     fn walk_in_function_call<'ast>(&mut self, function_call: &'ast FunctionCall, context: &mut NameContext<'a>) {
         if let Expression::Identifier(identifier) = function_call.function.as_ref() {
-            let (name, imported) = context.resolve_name(NameKind::Function, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Function, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
@@ -189,17 +216,17 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
         context: &mut NameContext<'a>,
     ) {
         if let Expression::Identifier(identifier) = &function_closure_creation.function {
-            let (name, imported) = context.resolve_name(NameKind::Function, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Function, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
     fn walk_in_instantiation<'ast>(&mut self, instantiation: &'ast Instantiation, context: &mut NameContext<'a>) {
         if let Expression::Identifier(identifier) = &instantiation.class {
-            let (name, imported) = context.resolve_name(NameKind::Default, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Default, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
@@ -210,9 +237,9 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
         context: &mut NameContext<'a>,
     ) {
         if let Expression::Identifier(identifier) = static_method_call.class.as_ref() {
-            let (name, imported) = context.resolve_name(NameKind::Default, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Default, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
@@ -223,9 +250,9 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
         context: &mut NameContext<'a>,
     ) {
         if let Expression::Identifier(identifier) = &static_method_closure_creation.class {
-            let (name, imported) = context.resolve_name(NameKind::Default, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Default, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
@@ -236,9 +263,9 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
         context: &mut NameContext<'a>,
     ) {
         if let Expression::Identifier(identifier) = &static_property_access.class {
-            let (name, imported) = context.resolve_name(NameKind::Default, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Default, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
@@ -249,9 +276,9 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
         context: &mut NameContext<'a>,
     ) {
         if let Expression::Identifier(identifier) = &class_constant_access.class {
-            let (name, imported) = context.resolve_name(NameKind::Default, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Default, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
@@ -259,9 +286,9 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
         if let (BinaryOperator::Instanceof(_), Expression::Identifier(identifier)) =
             (binary.operator, binary.rhs.as_ref())
         {
-            let (name, imported) = context.resolve_name(NameKind::Default, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Default, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
@@ -269,9 +296,9 @@ impl<'a> MutWalker<NameContext<'a>> for NameResolver {
         let identifier = &constant_access.name;
 
         if !self.resolved_names.contains(&identifier.span().start) {
-            let (name, imported) = context.resolve_name(NameKind::Constant, identifier.value());
+            let (name, resolution) = context.resolve_name(NameKind::Constant, identifier.value());
 
-            self.resolved_names.insert_at(identifier.span().start, name, imported);
+            self.resolved_names.insert_at(identifier.span().start, name, resolution);
         }
     }
 
@@ -3,12 +3,16 @@ use std::collections::HashSet;
 use ahash::HashMap;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::Display;
 
 use mago_ast::Program;
+use mago_ast::UseItem;
 use mago_interner::StringIdentifier;
 use mago_interner::ThreadedInterner;
 use mago_span::HasPosition;
+use mago_span::HasSpan;
 use mago_span::Position;
+use mago_span::Span;
 use mago_walker::MutWalker;
 
 use crate::internal::context::NameContext;
@@ -16,13 +20,36 @@ use crate::internal::resolver::NameResolver;
 
 mod internal;
 
+/// Describes how a name was resolved to its fully qualified form.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord, Display)]
+#[serde(tag = "type", content = "value")]
+#[strum(serialize_all = "kebab-case")]
+pub enum ResolutionKind {
+    /// The name of a declaration, such as a class or a function, qualified with the current namespace.
+    Declaration,
+    /// The name was imported by the `use` item of the given span, either entirely, or by its first part.
+    Imported(Span),
+    /// The name is relative to the current namespace, e.g. an unqualified class name which is not imported,
+    /// a qualified name, or a name prefixed with `namespace\`.
+    SameNamespace,
+    /// The name of a function or a constant which is unqualified, and not imported, within a namespace.
+    ///
+    /// It resolves to the namespaced name, which PHP uses if it is defined at runtime, falling back to the
+    /// global name otherwise.
+    GlobalFallback,
+    /// The name is fully qualified, i.e. prefixed with `\`.
+    FullyQualified,
+}
+
 /// Represents a collection of resolved names in a program.
 ///
 /// This struct stores a mapping of positions (represented as byte offsets)
-/// to resolved names (represented as `StringIdentifier`s).
+/// to resolved names (represented as `StringIdentifier`s), and how they were resolved,
+/// as well as the positions of the names imported by each `use` item.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Names {
-    names: HashMap<usize, (StringIdentifier, bool)>,
+    names: HashMap<usize, (StringIdentifier, ResolutionKind)>,
+    usages: HashMap<usize, Vec<Position>>,
 }
 
 impl Names {
@@ -88,7 +115,22 @@ impl Names {
         self.names.get(&position.position().offset).map(|(name, _)| name).expect("name not found at position")
     }
 
-    /// Returns whether the name at the given position was explicitly imported.
+    /// Gets the resolved name at the given position, and how it was resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - A reference to a type that implements `HasPosition`.
+    ///
+    /// # Returns
+    ///
+    /// The `StringIdentifier` of the resolved name, and its `ResolutionKind`, or `None` if no name
+    /// is resolved at the given position.
+    pub fn resolved(&self, position: &impl HasPosition) -> Option<(StringIdentifier, ResolutionKind)> {
+        self.names.get(&position.position().offset).copied()
+    }
+
+    /// Returns whether the name at the given position was explicitly imported, or fully qualified,
+    /// so that it never falls back to the global namespace.
     ///
     /// # Arguments
     ///
@@ -96,9 +138,23 @@ impl Names {
     ///
     /// # Returns
     ///
-    /// `true` if the name was imported, `false` otherwise.
+    /// `true` if the name was imported, or fully qualified, `false` otherwise.
     pub fn is_imported(&self, position: &impl HasPosition) -> bool {
-        self.names.get(&position.position().offset).map(|(_, imported)| *imported).unwrap_or(false)
+        matches!(self.resolved(position), Some((_, ResolutionKind::Imported(_) | ResolutionKind::FullyQualified)))
+    }
+
+    /// Gets the positions of the names imported by the given `use` item.
+    ///
+    /// # Arguments
+    ///
+    /// * `use_item` - A reference to the `UseItem` of a `use` statement.
+    ///
+    /// # Returns
+    ///
+    /// The positions of the names resolved using the `use` item, in the order they appear,
+    /// which is empty if the import is unused.
+    pub fn get_usages(&self, use_item: &UseItem) -> &[Position] {
+        self.usages.get(&use_item.span().start.offset).map(Vec::as_slice).unwrap_or_default()
     }
 
     /// Inserts a resolved name at the given position.
@@ -107,16 +163,193 @@ impl Names {
     ///
     /// # Arguments
     ///
-    /// * `position` - The position (as a byte offset) where the name is resolved.
+    /// * `position` - The position where the name is resolved.
     /// * `name` - The `StringIdentifier` of the resolved name.
-    pub(crate) fn insert_at<P: Into<usize>>(&mut self, position: P, name: StringIdentifier, imported: bool) {
-        self.names.insert(position.into(), (name, imported));
+    /// * `resolution` - How the name was resolved.
+    pub(crate) fn insert_at(&mut self, position: Position, name: StringIdentifier, resolution: ResolutionKind) {
+        if let ResolutionKind::Imported(use_item) = resolution {
+            self.usages.entry(use_item.start.offset).or_default().push(position);
+        }
+
+        self.names.insert(position.offset, (name, resolution));
+    }
+
+    /// Registers the `use` item of the given span, before the names it imports are resolved.
+    ///
+    /// This method is intended for internal use within the crate.
+    pub(crate) fn insert_import(&mut self, use_item: Span) {
+        self.usages.entry(use_item.start.offset).or_default();
     }
 
     /// Returns a set of all resolved names.
     ///
     /// The set contains tuples of positions and resolved names.
-    pub fn all(&self) -> HashSet<(&usize, &(StringIdentifier, bool))> {
+    pub fn all(&self) -> HashSet<(&usize, &(StringIdentifier, ResolutionKind))> {
         HashSet::from_iter(self.names.iter())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use mago_ast::Node;
+    use mago_source::SourceManager;
+
+    use super::*;
+
+    /// Parses the given code, and returns its resolved names, along with the names and the usages of
+    /// its `use` items.
+    fn resolve(code: &'static str) -> (ThreadedInterner, Names, Vec<(String, usize)>) {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source_id = manager.insert_content("code.php".to_string(), code.to_string(), true);
+        let source = manager.load(&source_id).expect("the source can be loaded");
+        let (program, errors) = mago_parser::parse_source(&interner, &source);
+        assert!(errors.is_empty(), "the code cannot be parsed: {:?}", errors);
+
+        let names = Names::resolve(&interner, &program);
+        let usages = Node::Program(&program)
+            .descendants()
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::UseItem(use_item) => {
+                    Some((interner.lookup(&use_item.name.value()).to_string(), names.get_usages(use_item).len()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        (interner, names, usages)
+    }
+
+    /// Returns the resolved name, and how it was resolved, of the last occurrence of the given name.
+    fn resolved(interner: &ThreadedInterner, names: &Names, code: &str, name: &str) -> (String, ResolutionKind) {
+        let offset = code.rfind(name).expect("the name is in the code");
+        let (resolved, kind) = names.resolved(&Position::dummy(offset)).expect("the name is resolved");
+
+        (interner.lookup(&resolved).to_string(), kind)
+    }
+
+    #[test]
+    fn test_imports() {
+        let code = indoc! {r#"
+            <?php
+
+            namespace App\Http;
+
+            use App\Models\{User, Post as Article};
+            use function App\Support\{format_name, slugify as slug};
+            use const App\Config\{VERSION, DEBUG as IS_DEBUG};
+            use Psr\Log;
+            use Psr\Container\ContainerInterface;
+
+            final class Controller
+            {
+                public function show(User $user): Article
+                {
+                    $slug = slug(format_name($user));
+                    $logger = new Log\NullLogger();
+                    $version = VERSION;
+                    $debug = IS_DEBUG;
+
+                    return new article($slug);
+                }
+            }
+        "#};
+
+        let (interner, names, usages) = resolve(code);
+
+        for (name, expected) in [
+            ("User $user", "App\\Models\\User"),
+            ("Article\n", "App\\Models\\Post"),
+            ("article(", "App\\Models\\Post"),
+            ("slug(", "App\\Support\\slugify"),
+            ("format_name(", "App\\Support\\format_name"),
+            ("Log\\NullLogger", "Psr\\Log\\NullLogger"),
+            ("VERSION;", "App\\Config\\VERSION"),
+            ("IS_DEBUG;", "App\\Config\\DEBUG"),
+        ] {
+            let (resolved, kind) = resolved(&interner, &names, code, name);
+
+            assert_eq!(resolved, expected);
+            assert!(matches!(kind, ResolutionKind::Imported(_)), "`{}` is resolved as {:?}", name, kind);
+        }
+
+        assert_eq!(
+            usages,
+            vec![
+                ("User".to_string(), 1),
+                ("Post".to_string(), 2),
+                ("format_name".to_string(), 1),
+                ("slugify".to_string(), 1),
+                ("VERSION".to_string(), 1),
+                ("DEBUG".to_string(), 1),
+                ("Psr\\Log".to_string(), 1),
+                ("Psr\\Container\\ContainerInterface".to_string(), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_namespaced_names() {
+        let code = indoc! {r#"
+            <?php
+
+            namespace App;
+
+            final class Clock
+            {
+                public function now(): Time\Instant
+                {
+                    $now = \time();
+                    $length = strlen(PHP_EOL) + \PHP_INT_SIZE;
+                    $format = namespace\format();
+
+                    return new Time\Instant($now, new Timezone());
+                }
+            }
+        "#};
+
+        let (interner, names, _) = resolve(code);
+
+        for (name, expected, expected_kind) in [
+            ("Clock", "App\\Clock", ResolutionKind::Declaration),
+            ("Time\\Instant(", "App\\Time\\Instant", ResolutionKind::SameNamespace),
+            ("Timezone", "App\\Timezone", ResolutionKind::SameNamespace),
+            ("namespace\\format", "App\\format", ResolutionKind::SameNamespace),
+            ("strlen", "App\\strlen", ResolutionKind::GlobalFallback),
+            ("PHP_EOL", "App\\PHP_EOL", ResolutionKind::GlobalFallback),
+            ("\\time", "time", ResolutionKind::FullyQualified),
+            ("\\PHP_INT_SIZE", "PHP_INT_SIZE", ResolutionKind::FullyQualified),
+        ] {
+            assert_eq!(resolved(&interner, &names, code, name), (expected.to_string(), expected_kind));
+        }
+
+        let offset = |name: &str| Position::dummy(code.rfind(name).expect("the name is in the code"));
+
+        assert!(!names.is_imported(&offset("strlen")));
+        assert!(names.is_imported(&offset("\\time")));
+    }
+
+    #[test]
+    fn test_global_names() {
+        let code = indoc! {r#"
+            <?php
+
+            use Foo\Bar;
+            use function Foo\baz;
+
+            $bar = new BAR();
+            $length = strlen(Baz());
+        "#};
+
+        let (interner, names, usages) = resolve(code);
+
+        // In the global namespace, the unqualified names are global, and the aliases are case-insensitive.
+        assert_eq!(resolved(&interner, &names, code, "strlen"), ("strlen".to_string(), ResolutionKind::SameNamespace));
+        assert_eq!(resolved(&interner, &names, code, "BAR").0, "Foo\\Bar");
+        assert_eq!(resolved(&interner, &names, code, "Baz").0, "Foo\\baz");
+        assert_eq!(usages, vec![("Foo\\Bar".to_string(), 1), ("Foo\\baz".to_string(), 1)]);
+    }
+}
@@ -13,18 +13,45 @@ use mago_token::TokenKind;
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ParseError {
     SyntaxError(SyntaxError),
-    UnexpectedEndOfFile(Vec<TokenKind>, Position),
-    UnexpectedToken(Vec<TokenKind>, TokenKind, Span),
+    UnexpectedEndOfFile(Vec<TokenKind>, Position, Option<Box<ParseHint>>),
+    UnexpectedToken(Vec<TokenKind>, TokenKind, Span, Option<Box<ParseHint>>),
     UnclosedLiteralString(LiteralStringKind, Span),
+    UnexpectedTrailingInput(Span),
+}
+
+/// A likely cause of a parse error, used to suggest how to fix it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ParseHint {
+    /// A `;` is likely missing at the given position, at the end of the line preceding the unexpected token.
+    MissingSemicolon(Position),
+    /// The brace at the given span is never closed.
+    UnclosedBrace(Span),
+    /// The token at the given span is likely a typo of the given one, such as `->` instead of `=>`.
+    Instead(Span, TokenKind),
+    /// The unexpected token is a float starting with a `.`, which is likely meant to be a concatenation.
+    ConcatenatedNumber,
 }
 
 impl HasSpan for ParseError {
     fn span(&self) -> Span {
         match &self {
             ParseError::SyntaxError(syntax_error) => syntax_error.span(),
-            ParseError::UnexpectedEndOfFile(_, position) => Span::new(*position, *position),
-            ParseError::UnexpectedToken(_, _, span) => *span,
+            ParseError::UnexpectedEndOfFile(_, position, _) => Span::new(*position, *position),
+            ParseError::UnexpectedToken(_, _, span, _) => *span,
             ParseError::UnclosedLiteralString(_, span) => *span,
+            ParseError::UnexpectedTrailingInput(span) => *span,
+        }
+    }
+}
+
+impl ParseError {
+    /// Returns the likely cause of the error, if it is known.
+    pub fn hint(&self) -> Option<ParseHint> {
+        match self {
+            ParseError::UnexpectedEndOfFile(_, _, hint) | ParseError::UnexpectedToken(_, _, _, hint) => {
+                hint.as_deref().copied()
+            }
+            _ => None,
         }
     }
 }
@@ -35,40 +62,42 @@ impl std::fmt::Display for ParseError {
             ParseError::SyntaxError(e) => {
                 return write!(f, "{}", e);
             }
-            ParseError::UnexpectedEndOfFile(expected, _) => {
-                let expected = expected.iter().map(|kind| kind.to_string()).collect::<Vec<_>>().join("`, `");
-
-                if expected.is_empty() {
-                    "Unexpected end of file".to_string()
-                } else if expected.len() == 1 {
-                    format!("Expected `{}` before end of file", expected)
-                } else {
-                    format!("Expected one of `{}` before end of file", expected)
-                }
-            }
-            ParseError::UnexpectedToken(expected, found, _) => {
-                let expected = expected.iter().map(|kind| kind.to_string()).collect::<Vec<_>>().join("`, `");
-
-                let found = found.to_string();
-
-                if expected.is_empty() {
-                    format!("Unexpected token `{}`", found)
-                } else if expected.len() == 1 {
-                    format!("Expected `{}`, found `{}`", expected, found)
-                } else {
-                    format!("Expected one of `{}`, found `{}`", expected, found)
-                }
-            }
+            ParseError::UnexpectedEndOfFile(expected, _, _) => match describe_expected(expected) {
+                None => "Unexpected end of file".to_string(),
+                Some(expected) => format!("Expected {} before end of file", expected),
+            },
+            ParseError::UnexpectedToken(expected, found, _, _) => match describe_expected(expected) {
+                None => format!("Unexpected {}", found.description()),
+                Some(expected) => format!("Expected {}, found {}", expected, found.description()),
+            },
             ParseError::UnclosedLiteralString(kind, _) => match kind {
                 LiteralStringKind::SingleQuoted => "Unclosed single-quoted string".to_string(),
                 LiteralStringKind::DoubleQuoted => "Unclosed double-quoted string".to_string(),
             },
+            ParseError::UnexpectedTrailingInput(_) => "Unexpected input after the end of the fragment".to_string(),
         };
 
         write!(f, "{}", message)
     }
 }
 
+/// Describes the expected token kinds, such as "one of `;`, `,`, variable", or `None` if there are none.
+fn describe_expected(expected: &[TokenKind]) -> Option<String> {
+    let mut descriptions: Vec<&str> = vec![];
+    for kind in expected {
+        let description = kind.description();
+        if !descriptions.contains(&description) {
+            descriptions.push(description);
+        }
+    }
+
+    match descriptions.as_slice() {
+        [] => None,
+        [description] => Some(description.to_string()),
+        descriptions => Some(format!("one of {}", descriptions.join(", "))),
+    }
+}
+
 impl std::error::Error for ParseError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -88,6 +117,30 @@ impl From<&ParseError> for Issue {
     fn from(error: &ParseError) -> Self {
         let span = error.span();
 
-        Issue::error(error.to_string()).with_annotation(Annotation::primary(span).with_message("Invalid syntax."))
+        let issue =
+            Issue::error(error.to_string()).with_annotation(Annotation::primary(span).with_message("Invalid syntax."));
+
+        match error.hint() {
+            None => issue,
+            Some(ParseHint::MissingSemicolon(position)) => issue
+                .with_annotation(
+                    Annotation::secondary(Span::new(position, position)).with_message("A `;` may be missing here."),
+                )
+                .with_help("Statements end with a `;`, add one at the end of the previous line."),
+            Some(ParseHint::UnclosedBrace(brace)) => issue
+                .with_annotation(Annotation::secondary(brace).with_message("This brace is never closed."))
+                .with_help("Add a `}` to close the brace."),
+            Some(ParseHint::Instead(typo, kind)) if typo == span => {
+                issue.with_help(format!("Did you mean {}?", kind.description()))
+            }
+            Some(ParseHint::Instead(typo, kind)) => issue
+                .with_annotation(
+                    Annotation::secondary(typo).with_message(format!("Did you mean {}?", kind.description())),
+                )
+                .with_help(format!("Replace this token with {}.", kind.description())),
+            Some(ParseHint::ConcatenatedNumber) => issue.with_help(
+                "A `.` directly followed by a number is a float, add a space after the `.` to concatenate the number.",
+            ),
+        }
     }
 }
@@ -3,7 +3,7 @@ use mago_ast::sequence::Sequence;
 use mago_token::T;
 
 use crate::error::ParseError;
-use crate::internal::statement::parse_statement;
+use crate::internal::recovery::parse_statement_with_recovery;
 use crate::internal::token_stream::TokenStream;
 use crate::internal::utils;
 
@@ -18,7 +18,7 @@ pub fn parse_block(stream: &mut TokenStream<'_, '_>) -> Result<Block, ParseError
                     break;
                 }
 
-                statements.push(parse_statement(stream)?);
+                statements.push(parse_statement_with_recovery(stream)?);
             }
 
             Sequence::new(statements)
@@ -1,4 +1,6 @@
 use mago_ast::ast::*;
+use mago_span::HasSpan;
+use mago_span::Span;
 use mago_token::T;
 
 use crate::error::ParseError;
@@ -7,10 +9,19 @@ use crate::internal::token_stream::TokenStream;
 use crate::internal::utils;
 
 pub fn parse_halt_compiler(stream: &mut TokenStream<'_, '_>) -> Result<HaltCompiler, ParseError> {
-    Ok(HaltCompiler {
-        halt_compiler: utils::expect_one_of_keyword(stream, &[T!["__halt_compiler"]])?,
-        left_parenthesis: utils::expect_span(stream, T!["("])?,
-        right_parenthesis: utils::expect_span(stream, T![")"])?,
-        terminator: parse_terminator(stream)?,
-    })
+    let halt_compiler = utils::expect_one_of_keyword(stream, &[T!["__halt_compiler"]])?;
+    let left_parenthesis = utils::expect_span(stream, T!["("])?;
+    let right_parenthesis = utils::expect_span(stream, T![")"])?;
+    let terminator = parse_terminator(stream)?;
+
+    // The lexer stops tokenizing after the terminator, and returns the remaining bytes as a single inline text.
+    let end = terminator.span().end;
+    let data = match utils::maybe_peek(stream)? {
+        Some(token) if token.kind == T![InlineText] && token.span.start.offset == end.offset => {
+            utils::expect_any(stream)?.span
+        }
+        _ => Span::new(end, end),
+    };
+
+    Ok(HaltCompiler { halt_compiler, left_parenthesis, right_parenthesis, terminator, data })
 }
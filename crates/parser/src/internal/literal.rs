@@ -20,7 +20,7 @@ pub fn parse_literal(stream: &mut TokenStream<'_, '_>) -> Result<Literal, ParseE
         T![LiteralInteger] => Literal::Integer(LiteralInteger {
             span: token.span,
             raw: token.value,
-            value: parse_literal_integer(stream.interner().lookup(&token.value), &token.span.start),
+            value: parse_literal_integer(stream.interner().lookup(&token.value)),
         }),
         T!["true"] => Literal::True(utils::to_keyword(token)),
         T!["false"] => Literal::False(utils::to_keyword(token)),
@@ -59,16 +59,20 @@ fn parse_literal_float(value: &str, at: &Position) -> f64 {
         .unwrap_or_else(|_| panic!("failed to parse float `{}` at {}; this should never happen.", source, at))
 }
 
-fn parse_literal_integer(value: &str, at: &Position) -> Option<u64> {
+/// Parses the value of an integer literal, which is `None` if it does not fit in a 64-bit signed integer.
+fn parse_literal_integer(value: &str) -> Option<u64> {
     let source = value.replace("_", "");
 
-    Some(match source.as_bytes() {
-        [b'0', b'x' | b'X', ..] => u64::from_str_radix(&source.as_str()[2..], 16).unwrap_or_else(|_| {
-            panic!("failed to parse hex integer `{}` at `{}`; this should never happen.", source, at)
-        }),
-        [b'0', b'o' | b'O', ..] => u64::from_str_radix(&source.as_str()[2..], 8).unwrap_or_else(|_| {
-            panic!("failed to parse octal integer `{}` at `{}`; this should never happen.", source, at)
-        }),
-        _ => return source.parse::<u64>().ok(),
-    })
+    let (digits, radix) = match source.as_bytes() {
+        [b'0', b'x' | b'X', ..] => (&source[2..], 16),
+        [b'0', b'o' | b'O', ..] => (&source[2..], 8),
+        [b'0', b'b' | b'B', ..] => (&source[2..], 2),
+        // A legacy octal literal is only made of decimal digits in a string offset, such as `"$a[09]"`.
+        [b'0', digits @ ..] if !digits.is_empty() && digits.iter().all(|digit| matches!(digit, b'0'..=b'7')) => {
+            (&source[1..], 8)
+        }
+        _ => (source.as_str(), 10),
+    };
+
+    u64::from_str_radix(digits, radix).ok().filter(|value| *value <= i64::MAX as u64)
 }
@@ -4,6 +4,7 @@ use mago_token::T;
 
 use crate::error::ParseError;
 use crate::internal::expression::parse_expression;
+use crate::internal::recovery::parse_statement_with_recovery;
 use crate::internal::statement::parse_statement;
 use crate::internal::terminator::parse_terminator;
 use crate::internal::token_stream::TokenStream;
@@ -53,7 +54,7 @@ pub fn parse_foreach_colon_delimited_body(
                     break;
                 }
 
-                statements.push(parse_statement(stream)?);
+                statements.push(parse_statement_with_recovery(stream)?);
             }
 
             Sequence::new(statements)
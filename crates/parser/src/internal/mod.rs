@@ -23,6 +23,7 @@ pub(crate) mod magic_constant;
 pub(crate) mod modifier;
 pub(crate) mod namespace;
 pub(crate) mod operation;
+pub(crate) mod recovery;
 pub(crate) mod r#return;
 pub(crate) mod statement;
 pub(crate) mod r#static;
@@ -4,7 +4,7 @@ use mago_token::T;
 
 use crate::error::ParseError;
 use crate::internal::identifier::parse_identifier;
-use crate::internal::statement::parse_statement;
+use crate::internal::recovery::parse_statement_with_recovery;
 use crate::internal::terminator::parse_terminator;
 use crate::internal::token_stream::TokenStream;
 use crate::internal::utils;
@@ -39,7 +39,7 @@ pub fn parse_namespace_implicit_body(stream: &mut TokenStream<'_, '_>) -> Result
             break;
         }
 
-        statements.push(parse_statement(stream)?);
+        statements.push(parse_statement_with_recovery(stream)?);
     }
 
     Ok(NamespaceImplicitBody { terminator, statements: Sequence::new(statements) })
@@ -0,0 +1,122 @@
+use mago_ast::ast::*;
+use mago_span::Span;
+use mago_token::TokenKind;
+use mago_token::T;
+
+use crate::error::ParseError;
+use crate::internal::statement::parse_statement;
+use crate::internal::token_stream::TokenStream;
+use crate::internal::utils;
+
+/// The maximum number of errors recovered from in a single file, after which parsing stops.
+const MAX_RECOVERED_ERRORS: usize = 100;
+
+/// Parses a statement, recovering from the error if it cannot be parsed.
+///
+/// The error is recorded in the stream, and the tokens up to the next synchronization point, such as a `;`,
+/// a `}`, or a keyword starting a statement, are skipped, and returned as an error statement.
+///
+/// Errors raised by the lexer, unexpected ends of file, and errors after the first `MAX_RECOVERED_ERRORS`
+/// ones are not recovered from, and are returned as is.
+pub fn parse_statement_with_recovery(stream: &mut TokenStream<'_, '_>) -> Result<Statement, ParseError> {
+    let start = utils::peek(stream)?.span.start;
+
+    let error = match parse_statement(stream) {
+        Ok(statement) => return Ok(statement),
+        Err(error) => error,
+    };
+
+    if matches!(error, ParseError::SyntaxError(_) | ParseError::UnexpectedEndOfFile(..))
+        || stream.get_error_count() >= MAX_RECOVERED_ERRORS
+    {
+        return Err(error);
+    }
+
+    // The unexpected token may have been consumed already. A `;` ends the statement, while a `}` is put back,
+    // as it most likely closes an enclosing block.
+    let consumed = match &error {
+        ParseError::UnexpectedToken(_, kind, span, _) if span.end.offset == stream.get_position().offset => Some(*kind),
+        _ => None,
+    };
+
+    stream.record_error(error);
+
+    match consumed {
+        Some(T![";"]) => {}
+        Some(T!["}"]) if stream.step_back() => {}
+        _ => skip_to_synchronization_point(stream, start.offset)?,
+    }
+
+    // Make sure that at least one token is consumed, so that the same error is not raised again.
+    if stream.get_position().offset <= start.offset {
+        utils::expect_any(stream)?;
+    }
+
+    Ok(Statement::Error(Span::new(start, stream.get_position())))
+}
+
+/// Skips the tokens up to the next synchronization point, not counting the ones nested in brackets.
+///
+/// A `;`, or a `}` closing a brace opened while skipping, is consumed, while a `}` closing an enclosing
+/// block, or a keyword starting a statement, is not.
+fn skip_to_synchronization_point(stream: &mut TokenStream<'_, '_>, start: usize) -> Result<(), ParseError> {
+    let mut braces = 0usize;
+    let mut parentheses = 0usize;
+    while let Some(token) = utils::maybe_peek(stream)? {
+        match token.kind {
+            T![";"] if braces == 0 && parentheses == 0 => {
+                utils::expect_any(stream)?;
+
+                return Ok(());
+            }
+            // A `}` closing a brace opened by the statement that could not be parsed, such as the brace of
+            // a `match` expression, is skipped along with the statement.
+            T!["}"] if braces == 0 && stream.get_unclosed_brace().is_some_and(|brace| brace.start.offset >= start) => {}
+            T!["}"] if braces == 0 => {
+                return Ok(());
+            }
+            T!["}"] if braces == 1 && parentheses == 0 => {
+                utils::expect_any(stream)?;
+
+                return Ok(());
+            }
+            T!["{" | "${"] => {
+                braces += 1;
+            }
+            T!["}"] => {
+                braces -= 1;
+            }
+            T!["(" | "[" | "#["] => {
+                parentheses += 1;
+            }
+            T![")" | "]"] => {
+                parentheses = parentheses.saturating_sub(1);
+            }
+            kind if braces == 0
+                && parentheses == 0
+                && stream.get_position().offset > start
+                && is_synchronization_keyword(kind) =>
+            {
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        utils::expect_any(stream)?;
+    }
+
+    Ok(())
+}
+
+fn is_synchronization_keyword(kind: TokenKind) -> bool {
+    matches!(
+        kind,
+        T![InlineText | InlineShebang]
+            | T!["<?php" | "<?=" | "<?" | "?>"]
+            | T!["declare" | "namespace" | "use" | "return" | "interface" | "trait" | "enum" | "class" | "function"]
+            | T!["abstract" | "final" | "global" | "const" | "__halt_compiler" | "goto" | "echo" | "unset" | "try"]
+            | T!["if" | "elseif" | "else" | "endif" | "switch" | "case" | "default" | "endswitch"]
+            | T!["foreach" | "endforeach" | "for" | "endfor" | "while" | "endwhile" | "do" | "continue" | "break"]
+            | T!["enddeclare"]
+    )
+}
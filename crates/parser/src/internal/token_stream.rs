@@ -8,23 +8,44 @@ use mago_interner::ThreadedInterner;
 use mago_lexer::error::SyntaxError;
 use mago_lexer::Lexer;
 use mago_span::Position;
+use mago_span::Span;
 use mago_token::Token;
 use mago_token::TokenKind;
 
+use crate::error::ParseError;
+
 #[derive(Debug)]
 pub struct TokenStream<'a, 'i> {
     interner: &'i ThreadedInterner,
     lexer: Lexer<'a, 'i>,
     buffer: VecDeque<Token>,
     trivia: Vec<Token>,
+    errors: Vec<ParseError>,
     position: Position,
+    /// The last consumed token, along with the position of the stream before it was consumed, and the
+    /// brace it closed, if any.
+    last: Option<(Token, Position, Option<Span>)>,
+    /// The token consumed before the last consumed token.
+    previous: Option<Token>,
+    /// The braces, `{` and `${`, that are opened, and not yet closed, from the outermost to the innermost.
+    braces: Vec<Span>,
 }
 
 impl<'a, 'i> TokenStream<'a, 'i> {
     pub fn new(interner: &'i ThreadedInterner, lexer: Lexer<'a, 'i>) -> TokenStream<'a, 'i> {
         let position = lexer.get_position();
 
-        TokenStream { interner, lexer, buffer: VecDeque::new(), trivia: Vec::new(), position }
+        TokenStream {
+            interner,
+            lexer,
+            buffer: VecDeque::new(),
+            trivia: Vec::new(),
+            errors: Vec::new(),
+            position,
+            last: None,
+            previous: None,
+            braces: Vec::new(),
+        }
     }
 
     pub fn interner(&self) -> &'i ThreadedInterner {
@@ -43,6 +64,21 @@ impl<'a, 'i> TokenStream<'a, 'i> {
         match self.fill_buffer(1) {
             Ok(Some(_)) => {
                 if let Some(token) = self.buffer.pop_front() {
+                    let closed = match token.kind {
+                        TokenKind::LeftBrace | TokenKind::DollarLeftBrace => {
+                            self.braces.push(token.span);
+
+                            None
+                        }
+                        TokenKind::RightBrace => self.braces.pop(),
+                        _ => None,
+                    };
+
+                    if let Some((last, ..)) = self.last {
+                        self.previous = Some(last);
+                    }
+
+                    self.last = Some((token, self.position, closed));
                     self.position = token.span.end;
 
                     Some(Ok(token))
@@ -55,12 +91,73 @@ impl<'a, 'i> TokenStream<'a, 'i> {
         }
     }
 
+    /// Puts the last consumed token back into the stream, so that it is returned by the next call to `advance`.
+    ///
+    /// Only the last consumed token can be put back, and only once, so this method returns `false` if
+    /// there is no such token.
+    #[inline]
+    pub fn step_back(&mut self) -> bool {
+        let Some((token, position, closed)) = self.last.take() else {
+            return false;
+        };
+
+        match token.kind {
+            TokenKind::LeftBrace | TokenKind::DollarLeftBrace => {
+                self.braces.pop();
+            }
+            TokenKind::RightBrace => {
+                self.braces.extend(closed);
+            }
+            _ => {}
+        }
+
+        self.buffer.push_front(token);
+        self.position = position;
+
+        true
+    }
+
     /// Return the current position of the stream in the input source code.
     #[inline]
     pub const fn get_position(&self) -> Position {
         self.position
     }
 
+    /// Returns the end of the token preceding the given token, which is either the last consumed token,
+    /// or the next one.
+    #[inline]
+    pub fn get_position_before(&self, token: &Token) -> Position {
+        match &self.last {
+            Some((last, position, _)) if last.span == token.span => *position,
+            _ => self.position,
+        }
+    }
+
+    /// Returns the token preceding the given token, which is either the last consumed token, or the next one.
+    #[inline]
+    pub fn get_token_before(&self, token: &Token) -> Option<Token> {
+        match &self.last {
+            Some((last, ..)) if last.span != token.span => Some(*last),
+            _ => self.previous,
+        }
+    }
+
+    /// Returns the span of the innermost brace that is opened, and not yet closed, if any.
+    #[inline]
+    pub fn get_unclosed_brace(&self) -> Option<Span> {
+        self.braces.last().copied()
+    }
+
+    /// Returns whether the trivia collected between the given offsets contains a line break.
+    pub fn has_line_break_between(&self, from: usize, to: usize) -> bool {
+        self.trivia
+            .iter()
+            .rev()
+            .skip_while(|trivia| trivia.span.start.offset >= to)
+            .take_while(|trivia| trivia.span.start.offset >= from)
+            .any(|trivia| trivia.kind == TokenKind::Whitespace && self.interner.lookup(&trivia.value).contains('\n'))
+    }
+
     #[inline]
     pub fn has_reached_eof(&mut self) -> Result<bool, SyntaxError> {
         Ok(self.fill_buffer(1)?.is_none())
@@ -121,6 +218,24 @@ impl<'a, 'i> TokenStream<'a, 'i> {
             .collect()
     }
 
+    /// Records an error the parser has recovered from.
+    #[inline]
+    pub fn record_error(&mut self, error: ParseError) {
+        self.errors.push(error);
+    }
+
+    /// Returns the number of errors recorded so far.
+    #[inline]
+    pub fn get_error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Consumes the errors recorded so far and returns them.
+    #[inline]
+    pub fn get_errors(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.errors)
+    }
+
     /// Fills the token buffer with at least `n` tokens.
     #[inline]
     fn fill_buffer(&mut self, n: usize) -> Result<Option<usize>, SyntaxError> {
@@ -132,6 +247,8 @@ impl<'a, 'i> TokenStream<'a, 'i> {
             match self.lexer.advance() {
                 Some(result) => match result {
                     Ok(token) => {
+                        self.errors.extend(self.lexer.take_errors().into_iter().map(ParseError::from));
+
                         if token.kind.is_trivia() {
                             self.trivia.push(token);
 
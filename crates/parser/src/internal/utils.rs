@@ -2,8 +2,10 @@ use mago_ast::ast::*;
 use mago_span::Span;
 use mago_token::Token;
 use mago_token::TokenKind;
+use mago_token::T;
 
 use crate::error::ParseError;
+use crate::error::ParseHint;
 use crate::internal::token_stream::TokenStream;
 
 pub fn peek(stream: &mut TokenStream<'_, '_>) -> Result<Token, ParseError> {
@@ -111,8 +113,41 @@ pub fn to_keyword(token: Token) -> Keyword {
 
 pub fn unexpected(stream: &mut TokenStream<'_, '_>, token: Option<Token>, one_of: &[TokenKind]) -> ParseError {
     if let Some(token) = token {
-        ParseError::UnexpectedToken(one_of.to_vec(), token.kind, token.span)
+        let hint = unexpected_token_hint(stream, &token, one_of).map(Box::new);
+
+        ParseError::UnexpectedToken(one_of.to_vec(), token.kind, token.span, hint)
     } else {
-        ParseError::UnexpectedEndOfFile(one_of.to_vec(), stream.get_position())
+        let hint = stream.get_unclosed_brace().map(|brace| Box::new(ParseHint::UnclosedBrace(brace)));
+
+        ParseError::UnexpectedEndOfFile(one_of.to_vec(), stream.get_position(), hint)
+    }
+}
+
+/// Returns the likely cause of an unexpected token, if it is one of the common mistakes.
+fn unexpected_token_hint(stream: &TokenStream<'_, '_>, token: &Token, one_of: &[TokenKind]) -> Option<ParseHint> {
+    let expects = |kind: TokenKind| one_of.contains(&kind);
+
+    match token.kind {
+        T!["->" | "="] if expects(T!["=>"]) => return Some(ParseHint::Instead(token.span, T!["=>"])),
+        // A `=>` can not end an expression statement, such as `$a => 1;`, so it is likely a typo of `=`.
+        T!["=>"] if expects(T!["="]) || expects(T![";"]) => return Some(ParseHint::Instead(token.span, T!["="])),
+        T!["=>"] if expects(T!["->"]) => return Some(ParseHint::Instead(token.span, T!["->"])),
+        T![LiteralFloat] if stream.interner().lookup(&token.value).starts_with('.') => {
+            return Some(ParseHint::ConcatenatedNumber);
+        }
+        // A member name is expected after `->`, so a value, such as in `['a' -> 1]`, likely follows a typo of `=>`.
+        kind if kind.is_literal() || matches!(kind, T!["\"" | "[" | "<<<"]) => {
+            if let Some(previous) = stream.get_token_before(token).filter(|previous| previous.kind == T!["->"]) {
+                return Some(ParseHint::Instead(previous.span, T!["=>"]));
+            }
+        }
+        _ => {}
+    }
+
+    let previous = stream.get_position_before(token);
+    if expects(T![";"]) && stream.has_line_break_between(previous.offset, token.span.start.offset) {
+        return Some(ParseHint::MissingSemicolon(previous));
     }
+
+    None
 }
@@ -1,55 +1,147 @@
 use mago_ast::sequence::Sequence;
+use mago_ast::CommentMap;
+use mago_ast::Expression;
 use mago_ast::Program;
+use mago_ast::Statement;
 use mago_interner::ThreadedInterner;
 use mago_lexer::input::Input;
 use mago_lexer::Lexer;
 use mago_source::Source;
+use mago_source::SourceIdentifier;
+use mago_span::Span;
 
 use crate::error::ParseError;
-use crate::internal::statement::parse_statement;
+use crate::internal::recovery::parse_statement_with_recovery;
 use crate::internal::token_stream::TokenStream;
+use crate::settings::ParserSettings;
 
 pub mod error;
+pub mod settings;
 
 mod internal;
 
-pub fn parse_source(interner: &ThreadedInterner, source: &Source) -> (Program, Option<ParseError>) {
+pub fn parse_source(interner: &ThreadedInterner, source: &Source) -> (Program, Vec<ParseError>) {
+    parse_source_with_settings(interner, source, ParserSettings::default())
+}
+
+/// Parses the given source with the given settings, such as whether short open tags are enabled.
+pub fn parse_source_with_settings(
+    interner: &ThreadedInterner,
+    source: &Source,
+    settings: ParserSettings,
+) -> (Program, Vec<ParseError>) {
     let content = interner.lookup(&source.content);
-    let lexer = Lexer::new(interner, Input::new(source.identifier, content.as_bytes()));
 
-    construct(interner, lexer)
+    parse_with_settings(interner, Input::new(source.identifier, content.as_bytes()), settings)
 }
 
-pub fn parse(interner: &ThreadedInterner, input: Input<'_>) -> (Program, Option<ParseError>) {
-    let lexer = Lexer::new(interner, input);
+pub fn parse(interner: &ThreadedInterner, input: Input<'_>) -> (Program, Vec<ParseError>) {
+    parse_with_settings(interner, input, ParserSettings::default())
+}
+
+/// Parses the given input with the given settings, such as whether short open tags are enabled.
+pub fn parse_with_settings(
+    interner: &ThreadedInterner,
+    input: Input<'_>,
+    settings: ParserSettings,
+) -> (Program, Vec<ParseError>) {
+    let lexer = Lexer::new(interner, input).with_short_open_tags(settings.short_open_tags);
 
     construct(interner, lexer)
 }
 
-fn construct<'i>(interner: &'i ThreadedInterner, lexer: Lexer<'_, 'i>) -> (Program, Option<ParseError>) {
+/// Parses a fragment of PHP code containing a single expression, such as `$a ?? bar()`.
+///
+/// The fragment is not preceded by an opening tag, and its spans belong to the dummy source, see
+/// [`parse_expression_input`] to parse a fragment of a source registered in a source manager.
+pub fn parse_expression(interner: &ThreadedInterner, code: &str) -> Result<Expression, ParseError> {
+    parse_expression_input(interner, Input::new(SourceIdentifier::dummy(), code.as_bytes()))
+}
+
+/// Parses the given input, which is not preceded by an opening tag, as a single expression.
+///
+/// An error is returned if the input contains anything other than the expression, and its trivia.
+pub fn parse_expression_input(interner: &ThreadedInterner, input: Input<'_>) -> Result<Expression, ParseError> {
+    let mut stream = TokenStream::new(interner, Lexer::scripting(interner, input));
+    let expression = internal::expression::parse_expression(&mut stream)?;
+
+    expect_end_of_fragment(&mut stream)?;
+
+    Ok(expression)
+}
+
+/// Parses a fragment of PHP code containing a single statement, such as `echo $a;`.
+///
+/// The fragment is not preceded by an opening tag, and its spans belong to the dummy source, see
+/// [`parse_statement_input`] to parse a fragment of a source registered in a source manager.
+pub fn parse_statement(interner: &ThreadedInterner, code: &str) -> Result<Statement, ParseError> {
+    parse_statement_input(interner, Input::new(SourceIdentifier::dummy(), code.as_bytes()))
+}
+
+/// Parses the given input, which is not preceded by an opening tag, as a single statement.
+///
+/// An error is returned if the input contains anything other than the statement, and its trivia.
+pub fn parse_statement_input(interner: &ThreadedInterner, input: Input<'_>) -> Result<Statement, ParseError> {
+    let mut stream = TokenStream::new(interner, Lexer::scripting(interner, input));
+    let statement = internal::statement::parse_statement(&mut stream)?;
+
+    expect_end_of_fragment(&mut stream)?;
+
+    Ok(statement)
+}
+
+/// Returns an error spanning the tokens left in the stream, if any.
+fn expect_end_of_fragment(stream: &mut TokenStream<'_, '_>) -> Result<(), ParseError> {
+    let Some(first) = stream.advance().transpose()? else {
+        return Ok(());
+    };
+
+    let mut last = first;
+    while let Some(token) = stream.advance().transpose()? {
+        last = token;
+    }
+
+    Err(ParseError::UnexpectedTrailingInput(first.span.join(last.span)))
+}
+
+/// Parses the tokens produced by the given lexer into a program.
+///
+/// Errors are recovered from where possible, so the program contains every statement that could be
+/// parsed, along with error statements covering the source code that could not be, and the returned
+/// errors are in the order they were encountered.
+fn construct<'i>(interner: &'i ThreadedInterner, lexer: Lexer<'_, 'i>) -> (Program, Vec<ParseError>) {
     let mut stream = TokenStream::new(interner, lexer);
 
-    let mut error = None;
     let statements = {
         let mut statements = Vec::new();
 
         loop {
             match stream.has_reached_eof() {
-                Ok(false) => match parse_statement(&mut stream) {
-                    Ok(statement) => {
-                        statements.push(statement);
-                    }
-                    Err(parse_error) => {
-                        error = Some(parse_error);
+                Ok(false) => {
+                    let start = stream.peek().and_then(Result::ok).map(|token| token.span.start);
+
+                    match parse_statement_with_recovery(&mut stream) {
+                        Ok(statement) => {
+                            statements.push(statement);
+                        }
+                        Err(parse_error) => {
+                            stream.record_error(parse_error);
 
-                        break;
+                            // The source code consumed before the error could not be recovered from is kept
+                            // as an error statement, so that the program still covers it.
+                            if let Some(start) = start.filter(|start| stream.get_position().offset > start.offset) {
+                                statements.push(Statement::Error(Span::new(start, stream.get_position())));
+                            }
+
+                            break;
+                        }
                     }
-                },
+                }
                 Ok(true) => {
                     break;
                 }
                 Err(syntax_error) => {
-                    error = Some(ParseError::from(syntax_error));
+                    stream.record_error(ParseError::from(syntax_error));
 
                     break;
                 }
@@ -59,12 +151,11 @@ fn construct<'i>(interner: &'i ThreadedInterner, lexer: Lexer<'_, 'i>) -> (Progr
         statements
     };
 
+    let trivia = stream.get_trivia();
+    let comments = CommentMap::new(interner, trivia.as_slice());
+
     (
-        Program {
-            source: stream.get_position().source,
-            statements: Sequence::new(statements),
-            trivia: stream.get_trivia(),
-        },
-        error,
+        Program { source: stream.get_position().source, statements: Sequence::new(statements), trivia, comments },
+        stream.get_errors(),
     )
 }
@@ -0,0 +1,15 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Settings controlling how PHP source code is parsed.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ParserSettings {
+    /// Whether `<?` opens a block of PHP code, as with the `short_open_tag` ini setting of PHP.
+    ///
+    /// When disabled, the code following `<?` is inline text, as it is output by PHP without being executed,
+    /// while `<?php` and `<?=` always open a block of PHP code.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub short_open_tags: bool,
+}
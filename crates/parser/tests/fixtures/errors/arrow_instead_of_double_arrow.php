@@ -0,0 +1,5 @@
+<?php
+
+$array = [
+    "a" -> 1,
+];
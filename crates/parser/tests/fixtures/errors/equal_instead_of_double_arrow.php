@@ -0,0 +1,5 @@
+<?php
+
+$value = match ($a) {
+    1 = "one",
+};
@@ -0,0 +1,6 @@
+<?php
+
+eval("1");
+$c = 1__0;
+eval("2");
+$x = ;
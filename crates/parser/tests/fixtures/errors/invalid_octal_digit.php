@@ -0,0 +1,3 @@
+<?php
+
+$permissions = 0759;
@@ -0,0 +1,3 @@
+<?php
+
+$million = 1_000__000;
@@ -0,0 +1,3 @@
+<?php
+
+function foo(int $a string $b) {}
@@ -0,0 +1,4 @@
+<?php
+
+$a = 1
+$b = 2;
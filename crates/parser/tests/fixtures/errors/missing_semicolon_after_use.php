@@ -0,0 +1,5 @@
+<?php
+
+use Foo\Bar
+
+class Baz {}
@@ -0,0 +1,6 @@
+<?php
+
+function foo() {
+    if ($a) {
+        echo 1;
+    
@@ -0,0 +1,6 @@
+<?php
+
+class Foo {
+    public function bar() {
+        return 1;
+    }
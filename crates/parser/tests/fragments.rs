@@ -0,0 +1,51 @@
+use mago_ast::Expression;
+use mago_ast::Statement;
+use mago_interner::ThreadedInterner;
+use mago_lexer::input::Input;
+use mago_parser::error::ParseError;
+use mago_parser::parse_expression;
+use mago_parser::parse_expression_input;
+use mago_parser::parse_statement;
+use mago_source::SourceManager;
+use mago_span::HasSpan;
+
+#[test]
+pub fn test_expression_fragments_are_parsed() {
+    let interner = ThreadedInterner::new();
+
+    assert!(matches!(parse_expression(&interner, "$a ?? bar()"), Ok(Expression::Binary(_))));
+    assert!(matches!(parse_expression(&interner, "  foo(1, 2) /* trailing comment */ "), Ok(Expression::Call(_))));
+}
+
+#[test]
+pub fn test_statement_fragments_are_parsed() {
+    let interner = ThreadedInterner::new();
+
+    assert!(matches!(parse_statement(&interner, "echo $a;"), Ok(Statement::Echo(_))));
+    assert!(matches!(parse_statement(&interner, "$a = 1; // comment"), Ok(Statement::Expression(_))));
+}
+
+#[test]
+pub fn test_trailing_input_of_fragments_is_an_error() {
+    let interner = ThreadedInterner::new();
+
+    let Err(ParseError::UnexpectedTrailingInput(span)) = parse_expression(&interner, "$a + 1; $b") else {
+        panic!("the trailing input is not reported");
+    };
+
+    assert_eq!(span.start.offset..span.end.offset, 6..10);
+    assert!(matches!(parse_statement(&interner, "echo $a; echo $b;"), Err(ParseError::UnexpectedTrailingInput(_))));
+    assert!(matches!(parse_expression(&interner, "$a +"), Err(ParseError::UnexpectedEndOfFile(..))));
+}
+
+#[test]
+pub fn test_fragments_of_sources_belong_to_their_source() {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source_id = manager.insert_content("fragment.php".to_string(), "$a->b()".to_string(), true);
+
+    let expression = parse_expression_input(&interner, Input::new(source_id, b"$a->b()")).unwrap();
+
+    assert_eq!(expression.span().start.source, source_id);
+    assert_eq!(expression.span().end.offset, 7);
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+/// A version of PHP, made of a major, and a minor, version number, such as `8.1`.
+///
+/// Versions are ordered, so that the features of a version can be checked against the targeted one,
+/// e.g. `version >= PHPVersion::PHP81` for enums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PHPVersion {
+    major: u8,
+    minor: u8,
+}
+
+impl PHPVersion {
+    pub const PHP70: PHPVersion = PHPVersion::new(7, 0);
+    pub const PHP71: PHPVersion = PHPVersion::new(7, 1);
+    pub const PHP72: PHPVersion = PHPVersion::new(7, 2);
+    pub const PHP73: PHPVersion = PHPVersion::new(7, 3);
+    pub const PHP74: PHPVersion = PHPVersion::new(7, 4);
+    pub const PHP80: PHPVersion = PHPVersion::new(8, 0);
+    pub const PHP81: PHPVersion = PHPVersion::new(8, 1);
+    pub const PHP82: PHPVersion = PHPVersion::new(8, 2);
+    pub const PHP83: PHPVersion = PHPVersion::new(8, 3);
+    pub const PHP84: PHPVersion = PHPVersion::new(8, 4);
+
+    /// The oldest version of PHP that can be targeted.
+    pub const OLDEST: PHPVersion = PHPVersion::PHP70;
+
+    /// The newest version of PHP that can be targeted, used when no version is configured.
+    pub const LATEST: PHPVersion = PHPVersion::PHP84;
+
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+
+    pub const fn major(&self) -> u8 {
+        self.major
+    }
+
+    pub const fn minor(&self) -> u8 {
+        self.minor
+    }
+
+    /// Returns whether the given version is supported by this version, i.e. whether this version is
+    /// the given one, or a newer one.
+    pub fn is_supported(&self, version: PHPVersion) -> bool {
+        *self >= version
+    }
+}
+
+impl Default for PHPVersion {
+    fn default() -> Self {
+        Self::LATEST
+    }
+}
+
+impl fmt::Display for PHPVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// An error returned when a string is not a supported version of PHP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePHPVersionError(String);
+
+impl fmt::Display for ParsePHPVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a supported PHP version, expected a version from {} to {}, such as `{}`",
+            self.0,
+            PHPVersion::OLDEST,
+            PHPVersion::LATEST,
+            PHPVersion::PHP81
+        )
+    }
+}
+
+impl std::error::Error for ParsePHPVersionError {}
+
+impl FromStr for PHPVersion {
+    type Err = ParsePHPVersionError;
+
+    /// Parses a version such as `8.1`, ignoring the patch version, if any, such as in `8.1.2`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error = || ParsePHPVersionError(value.to_string());
+
+        let mut parts = value.trim().split('.');
+        let major = parts.next().and_then(|part| part.parse::<u8>().ok()).ok_or_else(error)?;
+        let minor = parts.next().and_then(|part| part.parse::<u8>().ok()).ok_or_else(error)?;
+        if parts.next().is_some_and(|patch| patch.parse::<u16>().is_err()) || parts.next().is_some() {
+            return Err(error());
+        }
+
+        let version = PHPVersion::new(major, minor);
+        if version < PHPVersion::OLDEST || version > PHPVersion::LATEST {
+            return Err(error());
+        }
+
+        Ok(version)
+    }
+}
+
+impl Serialize for PHPVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PHPVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PHPVersionVisitor;
+
+        impl de::Visitor<'_> for PHPVersionVisitor {
+            type Value = PHPVersion;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a PHP version, such as \"8.1\"")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            /// Versions written as numbers, such as `8.1` in TOML, or in environment variables, are accepted.
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                format!("{:.1}", value).parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(PHPVersionVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("8.1".parse(), Ok(PHPVersion::PHP81));
+        assert_eq!("7.4.33".parse(), Ok(PHPVersion::PHP74));
+        assert_eq!(" 8.4 ".parse(), Ok(PHPVersion::PHP84));
+
+        for invalid in ["", "8", "8.", "eight", "8.1.x", "8.1.2.3", "5.6", "9.0"] {
+            assert!(invalid.parse::<PHPVersion>().is_err(), "`{}` should not be parsed", invalid);
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(PHPVersion::PHP80 < PHPVersion::PHP81);
+        assert!(PHPVersion::PHP74 < PHPVersion::PHP80);
+        assert!(PHPVersion::PHP82.is_supported(PHPVersion::PHP81));
+        assert!(!PHPVersion::PHP80.is_supported(PHPVersion::PHP81));
+        assert_eq!(PHPVersion::default(), PHPVersion::LATEST);
+        assert_eq!(PHPVersion::PHP80.to_string(), "8.0");
+    }
+}
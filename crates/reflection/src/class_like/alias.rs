@@ -0,0 +1,20 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::StringIdentifier;
+use mago_span::Span;
+
+use crate::identifier::Name;
+
+/// Represents an alias of a class-like entity, declared with `class_alias()`, e.g. `class_alias('Foo', 'Bar')`.
+///
+/// The alias resolves to the reflection of the class-like entity it refers to once the codebase is populated.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct ClassLikeAliasReflection {
+    /// The name of the alias, e.g. `Bar`.
+    pub name: Name,
+    /// The fully qualified name of the class-like entity the alias refers to, e.g. `Foo`.
+    pub original: StringIdentifier,
+    pub is_conditionally_declared: bool,
+    pub span: Span,
+}
@@ -12,34 +12,45 @@ use crate::class_like::enum_case::EnumCaseReflection;
 use crate::class_like::inheritance::InheritanceReflection;
 use crate::class_like::member::MemeberCollection;
 use crate::class_like::property::PropertyReflection;
+use crate::class_like::trait_adaptation::TraitMethodAliasReflection;
+use crate::class_like::trait_adaptation::TraitMethodPrecedenceReflection;
+use crate::docblock::DocblockReflection;
 use crate::function_like::FunctionLikeReflection;
 use crate::identifier::ClassLikeName;
 use crate::identifier::Name;
+use crate::r#type::kind::Template;
 use crate::r#type::TypeReflection;
 
+pub mod alias;
 pub mod constant;
 pub mod enum_case;
 pub mod inheritance;
 pub mod member;
 pub mod property;
+pub mod trait_adaptation;
 
 /// Represents reflection data for a PHP class, interface, enum, or trait.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ClassLikeReflection {
     pub attribute_reflections: Vec<AttributeReflection>,
     pub name: ClassLikeName,
+    pub templates: Vec<Template>,
     pub inheritance: InheritanceReflection,
     pub constants: HashMap<StringIdentifier, ClassLikeConstantReflection>,
     pub cases: MemeberCollection<EnumCaseReflection>,
     pub properties: MemeberCollection<PropertyReflection>,
     pub methods: MemeberCollection<FunctionLikeReflection>,
     pub used_traits: HashSet<StringIdentifier>,
+    pub trait_precedences: Vec<TraitMethodPrecedenceReflection>,
+    pub trait_aliases: Vec<TraitMethodAliasReflection>,
     pub backing_type: Option<TypeReflection>,
     pub is_final: bool,
     pub is_readonly: bool,
     pub is_abstract: bool,
     pub is_anonymous: bool,
+    pub docblock: Option<DocblockReflection>,
     pub span: Span,
+    pub is_conditionally_declared: bool,
     pub is_populated: bool,
 }
 
@@ -64,6 +75,11 @@ impl ClassLikeReflection {
         matches!(self.name, ClassLikeName::AnonymousClass(_))
     }
 
+    /// Checks if this class-like entity is documented as deprecated, with a `@deprecated` tag.
+    pub fn is_deprecated(&self) -> bool {
+        self.docblock.as_ref().is_some_and(DocblockReflection::is_deprecated)
+    }
+
     /// Checks if this class-like entity extends the given class.
     pub fn extends_class(&self, class_like_identifier: &Name) -> bool {
         self.inheritance.all_extended_classes.contains(class_like_identifier)
@@ -8,6 +8,7 @@ use mago_span::Span;
 
 use crate::attribute::AttributeReflection;
 use crate::class_like::member::ClassLikeMemberVisibilityReflection;
+use crate::docblock::DocblockReflection;
 use crate::function_like::FunctionLikeReflection;
 use crate::identifier::ClassLikeMemberName;
 use crate::r#type::TypeReflection;
@@ -31,7 +32,16 @@ pub struct PropertyReflection {
     pub is_final: bool,
     pub is_promoted: bool,
     pub is_static: bool,
+    pub is_virtual: bool,
+    pub docblock: Option<DocblockReflection>,
     pub item_span: Span,
     pub definition_span: Span,
     pub is_overriding: bool,
 }
+
+impl PropertyReflection {
+    /// Checks if this property is documented as deprecated, with a `@deprecated` tag.
+    pub fn is_deprecated(&self) -> bool {
+        self.docblock.as_ref().is_some_and(DocblockReflection::is_deprecated)
+    }
+}
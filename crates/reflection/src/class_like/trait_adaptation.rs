@@ -0,0 +1,41 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::StringIdentifier;
+use mago_span::Span;
+
+use crate::class_like::member::ClassLikeMemberVisibilityReflection;
+use crate::identifier::Name;
+
+/// Represents a precedence adaptation of a trait method, such as `A::foo insteadof B;`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TraitMethodPrecedenceReflection {
+    /// The lowercased name of the trait the method is taken from.
+    pub trait_name: StringIdentifier,
+
+    /// The name of the method, as written in the adaptation.
+    pub method_name: StringIdentifier,
+
+    /// The lowercased names of the traits the method is not taken from.
+    pub excluded_traits: Vec<StringIdentifier>,
+
+    pub span: Span,
+}
+
+/// Represents an alias adaptation of a trait method, such as `B::foo as protected bar;` or `foo as private;`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TraitMethodAliasReflection {
+    /// The lowercased name of the trait the method is taken from, if it is specified.
+    pub trait_name: Option<StringIdentifier>,
+
+    /// The name of the method, as written in the adaptation.
+    pub method_name: StringIdentifier,
+
+    /// The new name of the method, if any.
+    pub alias: Option<Name>,
+
+    /// The new visibility of the method, if any.
+    pub visibility: Option<ClassLikeMemberVisibilityReflection>,
+
+    pub span: Span,
+}
@@ -12,5 +12,6 @@ pub struct ConstantReflection {
     pub type_reflection: TypeReflection,
     pub item_span: Span,
     pub definition_span: Span,
+    pub is_conditionally_declared: bool,
     pub is_populated: bool,
 }
@@ -0,0 +1,73 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::StringIdentifier;
+use mago_span::Span;
+
+use crate::r#type::TypeReflection;
+
+/// Represents the documentation of a function-like, class-like, or property declared by the tags of the
+/// docblock preceding it, such as its `@param`, `@return`, `@var`, `@deprecated`, and `@internal` tags.
+///
+/// The templates declared by the `@template` tags are reflected by the function-like, or class-like, itself.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct DocblockReflection {
+    /// The types of the parameters, as declared by the `@param` tags.
+    pub parameters: Vec<DocblockParameterReflection>,
+
+    /// The return type, as declared by the `@return` tag.
+    pub return_type_reflection: Option<TypeReflection>,
+
+    /// The type of the property, as declared by the `@var` tag.
+    pub type_reflection: Option<TypeReflection>,
+
+    /// The deprecation, as declared by the `@deprecated` tag.
+    pub deprecation: Option<DeprecationReflection>,
+
+    /// The span of the `@internal` tag, if any.
+    pub internal: Option<Span>,
+
+    /// The span of the docblock in the source code.
+    pub span: Span,
+}
+
+/// Represents a parameter documented by a `@param` tag, such as `@param list<int> $ids`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct DocblockParameterReflection {
+    /// The name of the parameter, including its `$`.
+    pub name: StringIdentifier,
+
+    /// The type of the parameter.
+    pub type_reflection: TypeReflection,
+
+    /// Indicates whether the parameter is documented as variadic, such as `@param int ...$values`.
+    pub is_variadic: bool,
+
+    /// The span of the `@param` tag in the source code.
+    pub span: Span,
+}
+
+/// Represents a `@deprecated` tag, such as `@deprecated Use `bar()` instead.`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct DeprecationReflection {
+    /// The description following the tag, if any.
+    pub message: Option<StringIdentifier>,
+
+    /// The span of the `@deprecated` tag in the source code.
+    pub span: Span,
+}
+
+impl DocblockReflection {
+    /// Returns the documented parameter with the given name, including its `$`.
+    pub fn get_parameter(&self, name: &StringIdentifier) -> Option<&DocblockParameterReflection> {
+        self.parameters.iter().find(|parameter| parameter.name == *name)
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation.is_some()
+    }
+
+    pub fn is_internal(&self) -> bool {
+        self.internal.is_some()
+    }
+}
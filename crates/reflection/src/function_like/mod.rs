@@ -5,8 +5,10 @@ use mago_span::Span;
 
 use crate::attribute::AttributeReflection;
 use crate::class_like::member::ClassLikeMemberVisibilityReflection;
+use crate::docblock::DocblockReflection;
 use crate::function_like::parameter::FunctionLikeParameterReflection;
 use crate::function_like::r#return::FunctionLikeReturnTypeReflection;
+use crate::identifier::ClassLikeName;
 use crate::identifier::FunctionLikeName;
 use crate::r#type::kind::Template;
 
@@ -28,7 +30,7 @@ pub struct FunctionLikeReflection {
     /// The unique identifier for this function or method.
     pub name: FunctionLikeName,
 
-    /// The list of templates accepted by this function or method.
+    /// The list of templates accepted by this function or method, as declared by the `@template` tags of its docblock.
     pub templates: Vec<Template>,
 
     /// The list of parameters accepted by this function or method, including their types and attributes.
@@ -75,9 +77,24 @@ pub struct FunctionLikeReflection {
     /// it depends on whether they override a parent method.
     pub is_overriding: bool,
 
+    /// The class-like within which this closure, or arrow function, is defined, if any, which `$this`, `self`,
+    /// and `static` refer to within its body.
+    ///
+    /// For methods and property hooks, this is the class-like declaring them, and for functions, it is always `None`.
+    pub scope: Option<ClassLikeName>,
+
+    /// The documentation of this function or method, if it is preceded by a docblock.
+    pub docblock: Option<DocblockReflection>,
+
     /// The span in the source code where this function or method is defined.
     pub span: Span,
 
+    /// Indicates if this function is declared in a conditional, a loop, or the body of another function-like,
+    /// such as within `if (!function_exists('foo')) { ... }`, instead of the top level of the program.
+    ///
+    /// Always `false` for methods, property hooks, closures, and arrow functions.
+    pub is_conditionally_declared: bool,
+
     /// Indicate if this function-like entity is populated.
     pub is_populated: bool,
 }
@@ -102,4 +119,9 @@ impl FunctionLikeReflection {
     pub fn is_arrow_function(&self) -> bool {
         matches!(self.name, FunctionLikeName::ArrowFunction(_))
     }
+
+    /// Checks if this function or method is documented as deprecated, with a `@deprecated` tag.
+    pub fn is_deprecated(&self) -> bool {
+        self.docblock.as_ref().is_some_and(DocblockReflection::is_deprecated)
+    }
 }
@@ -43,4 +43,7 @@ pub struct FunctionLikeParameterReflection {
 
     /// The default value of the parameter, if any, including its type and span in the source code.
     pub default: Option<FunctionLikeParameterDefaultValueReflection>,
+
+    /// The span of the parameter in the source code.
+    pub span: Span,
 }
@@ -1,8 +1,9 @@
-use std::collections::hash_map::Entry;
-
 use ahash::HashMap;
 use ahash::HashSet;
 use mago_interner::ThreadedInterner;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
 use mago_span::HasSpan;
 use serde::Deserialize;
 use serde::Serialize;
@@ -10,6 +11,7 @@ use serde::Serialize;
 use mago_interner::StringIdentifier;
 use mago_span::HasPosition;
 
+use crate::class_like::alias::ClassLikeAliasReflection;
 use crate::class_like::ClassLikeReflection;
 use crate::constant::ConstantReflection;
 use crate::function_like::FunctionLikeReflection;
@@ -21,8 +23,11 @@ pub mod assertion;
 pub mod attribute;
 pub mod class_like;
 pub mod constant;
+pub mod docblock;
 pub mod function_like;
 pub mod identifier;
+pub mod query;
+pub mod reference;
 pub mod r#type;
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
@@ -36,9 +41,15 @@ pub struct CodebaseReflection {
     pub class_like_reflections: HashMap<ClassLikeName, ClassLikeReflection>,
     pub class_like_names: HashMap<StringIdentifier, ClassLikeName>,
 
+    /// The aliases declared with `class_alias()`, keyed by their lowercase names.
+    pub class_like_aliases: HashMap<StringIdentifier, ClassLikeAliasReflection>,
+
     pub direct_classlike_descendants: HashMap<StringIdentifier, HashSet<StringIdentifier>>,
     pub all_classlike_descendants: HashMap<StringIdentifier, HashSet<StringIdentifier>>,
 
+    /// The issues found while building the reflection, such as duplicate declarations.
+    pub issues: IssueCollection,
+
     pub populated: bool,
 }
 
@@ -50,13 +61,30 @@ impl CodebaseReflection {
 
     /// Registers a new constant in the codebase.
     ///
-    /// If the constant already exists, it will not be added again.
+    /// If the constant already exists, it will not be added again, and it is reported as a duplicate,
+    /// unless either of the declarations is conditional. An unconditional declaration replaces a
+    /// conditional one, as it is the one in effect at runtime.
     ///
     /// Returns `false` if the constant already exists.
     pub fn register_constant(&mut self, interner: &ThreadedInterner, reflection: ConstantReflection) -> bool {
         let lowercase_name = lower_constant_name(interner, &reflection.name.value);
-        if self.constant_names.contains_key(&lowercase_name) {
-            return false;
+        let existing = self.constant_names.get(&lowercase_name).and_then(|name| self.constant_reflections.get(name));
+        if let Some(existing) = existing {
+            if !existing.is_conditionally_declared || reflection.is_conditionally_declared {
+                if !existing.is_conditionally_declared && !reflection.is_conditionally_declared {
+                    self.issues.push(duplicate_declaration(
+                        interner,
+                        ("constant", reflection.name),
+                        ("constant", existing.name),
+                    ));
+                }
+
+                return false;
+            }
+
+            let existing_name = existing.name;
+            self.constant_names.remove(&existing_name.value);
+            self.constant_reflections.remove(&existing_name);
         }
 
         self.constant_names.insert(lowercase_name, reflection.name);
@@ -68,80 +96,98 @@ impl CodebaseReflection {
 
     /// Registers a new function-like entity in the codebase.
     ///
-    /// If the function-like entity already exists, it will not be added again.
+    /// If a function with the same name already exists, it will not be added again, and it is reported
+    /// as a duplicate, unless either of the declarations is conditional. An unconditional declaration
+    /// replaces a conditional one, as it is the one in effect at runtime.
     ///
     /// Returns `false` if the function-like entity already exists.
     pub fn register_function_like(&mut self, interner: &ThreadedInterner, reflection: FunctionLikeReflection) -> bool {
-        let mut exists = false;
-
         if let FunctionLikeName::Function(name) = reflection.name {
             let lowercase_name = interner.lowered(&name.value);
-            if let Entry::Vacant(e) = self.function_names.entry(lowercase_name) {
-                e.insert(reflection.name);
-            } else {
-                exists = true;
+            let existing =
+                self.function_names.get(&lowercase_name).and_then(|name| self.function_like_reflections.get(name));
+
+            if let Some(existing) = existing {
+                if !existing.is_conditionally_declared || reflection.is_conditionally_declared {
+                    if let FunctionLikeName::Function(existing_name) = existing.name {
+                        if !existing.is_conditionally_declared && !reflection.is_conditionally_declared {
+                            self.issues.push(duplicate_declaration(
+                                interner,
+                                ("function", name),
+                                ("function", existing_name),
+                            ));
+                        }
+                    }
+
+                    return false;
+                }
+
+                let existing_name = existing.name;
+                self.function_like_reflections.remove(&existing_name);
             }
-        }
 
-        if !exists {
-            self.function_like_reflections.insert(reflection.name, reflection);
+            self.function_names.insert(lowercase_name, reflection.name);
         }
 
-        exists
+        self.function_like_reflections.insert(reflection.name, reflection);
+
+        true
     }
 
     /// Registers a new class-like entity (class, enum, interface, or trait) in the codebase.
     ///
-    /// If the class-like entity already exists, it will not be added again.
+    /// If a class-like entity with the same name already exists, it will not be added again, and it is reported
+    /// as a duplicate, unless either of the declarations is conditional. An unconditional declaration replaces
+    /// a conditional one, as it is the one in effect at runtime.
     ///
     /// Returns `false` if the class-like entity already exists.
     pub fn register_class_like(&mut self, interner: &ThreadedInterner, reflection: ClassLikeReflection) -> bool {
-        let mut exists = false;
-
-        match reflection.name {
-            ClassLikeName::Class(name) => {
-                let lowercase_name = interner.lowered(&name.value);
-
-                if let Entry::Vacant(e) = self.class_like_names.entry(lowercase_name) {
-                    e.insert(reflection.name);
-                } else {
-                    exists = true;
+        if let Some(declaration) = class_like_declaration(&reflection.name) {
+            let lowercase_name = interner.lowered(&declaration.1.value);
+            let existing =
+                self.class_like_names.get(&lowercase_name).and_then(|name| self.class_like_reflections.get(name));
+
+            if let Some(existing) = existing {
+                if !existing.is_conditionally_declared || reflection.is_conditionally_declared {
+                    if let Some(existing_declaration) = class_like_declaration(&existing.name) {
+                        if !existing.is_conditionally_declared && !reflection.is_conditionally_declared {
+                            self.issues.push(duplicate_declaration(interner, declaration, existing_declaration));
+                        }
+                    }
+
+                    return false;
                 }
-            }
-            ClassLikeName::Enum(name) => {
-                let lowercase_name = interner.lowered(&name.value);
-                if let Entry::Vacant(e) = self.class_like_names.entry(lowercase_name) {
-                    e.insert(reflection.name);
-                } else {
-                    exists = true;
-                }
-            }
-            ClassLikeName::Interface(name) => {
-                let lowercase_name = interner.lowered(&name.value);
 
-                if let Entry::Vacant(e) = self.class_like_names.entry(lowercase_name) {
-                    e.insert(reflection.name);
-                } else {
-                    exists = true;
-                }
+                let existing_name = existing.name;
+                self.class_like_reflections.remove(&existing_name);
             }
-            ClassLikeName::Trait(name) => {
-                let lowercase_name = interner.lowered(&name.value);
 
-                if let Entry::Vacant(e) = self.class_like_names.entry(lowercase_name) {
-                    e.insert(reflection.name);
-                } else {
-                    exists = true;
-                }
-            }
-            _ => {}
+            self.class_like_names.insert(lowercase_name, reflection.name);
         }
 
-        if !exists {
-            self.class_like_reflections.insert(reflection.name, reflection);
+        self.class_like_reflections.insert(reflection.name, reflection);
+
+        true
+    }
+
+    /// Registers a new alias of a class-like entity in the codebase.
+    ///
+    /// The alias is resolved to the class-like entity it refers to when the codebase is populated. If an alias
+    /// with the same name already exists, it will not be added again, unless the existing alias is conditional
+    /// and the new one is not.
+    ///
+    /// Returns `false` if the alias already exists.
+    pub fn register_class_like_alias(&mut self, interner: &ThreadedInterner, alias: ClassLikeAliasReflection) -> bool {
+        let lowercase_name = interner.lowered(&alias.name.value);
+        if let Some(existing) = self.class_like_aliases.get(&lowercase_name) {
+            if !existing.is_conditionally_declared || alias.is_conditionally_declared {
+                return false;
+            }
         }
 
-        exists
+        self.class_like_aliases.insert(lowercase_name, alias);
+
+        true
     }
 
     pub fn constant_exists(&self, interner: &ThreadedInterner, id: &StringIdentifier) -> bool {
@@ -347,6 +393,39 @@ impl CodebaseReflection {
     }
 }
 
+/// Returns the kind, and the name, of a named class-like entity.
+fn class_like_declaration(name: &ClassLikeName) -> Option<(&'static str, Name)> {
+    match name {
+        ClassLikeName::Class(name) => Some(("class", *name)),
+        ClassLikeName::Interface(name) => Some(("interface", *name)),
+        ClassLikeName::Enum(name) => Some(("enum", *name)),
+        ClassLikeName::Trait(name) => Some(("trait", *name)),
+        ClassLikeName::AnonymousClass(_) => None,
+    }
+}
+
+/// Creates the issue reported for the declaration of a name which is already declared.
+fn duplicate_declaration(
+    interner: &ThreadedInterner,
+    (kind, name): (&'static str, Name),
+    (first_kind, first): (&'static str, Name),
+) -> Issue {
+    let value = interner.lookup(&name.value);
+    let first_value = interner.lookup(&first.value);
+
+    Issue::error(format!("Duplicate declaration of {} `{}`.", kind, value))
+        .with_code("reflection/duplicate-declaration")
+        .with_annotation(
+            Annotation::primary(name.span).with_message(format!("The {} `{}` is declared again here.", kind, value)),
+        )
+        .with_annotation(
+            Annotation::secondary(first.span)
+                .with_message(format!("The {} `{}` is first declared here.", first_kind, first_value)),
+        )
+        .with_note("Declaring a name twice results in a fatal error at runtime, once both declarations are loaded.")
+        .with_help("Rename or remove one of the declarations, or declare them conditionally.")
+}
+
 fn lower_constant_name(interner: &ThreadedInterner, name: &StringIdentifier) -> StringIdentifier {
     let name = interner.lookup(name);
 
@@ -0,0 +1,680 @@
+//! Lookups of the symbols of a codebase, such as its class-likes, functions, constants, and their members.
+//!
+//! A query is made of a name, or of a class-like name and a member name separated by `::`, such as
+//! `App\Service\UserRepository::find`. Each name is either exact, or a pattern in which `*` matches any
+//! sequence of characters, including namespace separators, and `?` matches any single character.
+//!
+//! Names are compared case-sensitively unless the query is case-insensitive, in which case ASCII letters
+//! are compared regardless of their case, as PHP does for the names of classes, functions, and methods.
+
+use std::fmt::Display;
+
+use ahash::HashSet;
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::ThreadedInterner;
+use mago_span::HasSpan;
+use mago_span::Span;
+
+use crate::class_like::constant::ClassLikeConstantReflection;
+use crate::class_like::enum_case::EnumCaseReflection;
+use crate::class_like::property::PropertyReflection;
+use crate::class_like::ClassLikeReflection;
+use crate::constant::ConstantReflection;
+use crate::function_like::FunctionLikeReflection;
+use crate::identifier::ClassLikeName;
+use crate::identifier::FunctionLikeName;
+use crate::CodebaseReflection;
+
+/// The kind of a symbol of a codebase.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum SymbolKind {
+    Class,
+    Interface,
+    Enum,
+    Trait,
+    Function,
+    Constant,
+    Method,
+    Property,
+    ClassLikeConstant,
+    EnumCase,
+}
+
+/// A symbol of a codebase, along with the reflection of the class-like declaring it, for members.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Symbol<'a> {
+    ClassLike(&'a ClassLikeReflection),
+    Function(&'a FunctionLikeReflection),
+    Constant(&'a ConstantReflection),
+    Method(&'a ClassLikeReflection, &'a FunctionLikeReflection),
+    Property(&'a ClassLikeReflection, &'a PropertyReflection),
+    ClassLikeConstant(&'a ClassLikeReflection, &'a ClassLikeConstantReflection),
+    EnumCase(&'a ClassLikeReflection, &'a EnumCaseReflection),
+}
+
+/// A name, or a pattern of names, to look up.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct NamePattern {
+    pattern: String,
+    case_insensitive: bool,
+}
+
+/// A query of the symbols of a codebase.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SymbolQuery {
+    name: NamePattern,
+    member: Option<NamePattern>,
+    kinds: HashSet<SymbolKind>,
+    user_defined_only: bool,
+    include_anonymous: bool,
+}
+
+impl SymbolKind {
+    /// Returns the kinds of class-likes.
+    pub const fn class_likes() -> [SymbolKind; 4] {
+        [SymbolKind::Class, SymbolKind::Interface, SymbolKind::Enum, SymbolKind::Trait]
+    }
+
+    /// Returns the kinds of the members of class-likes.
+    pub const fn members() -> [SymbolKind; 4] {
+        [SymbolKind::Method, SymbolKind::Property, SymbolKind::ClassLikeConstant, SymbolKind::EnumCase]
+    }
+
+    /// Checks if symbols of this kind are members of class-likes.
+    pub const fn is_member(&self) -> bool {
+        matches!(self, SymbolKind::Method | SymbolKind::Property | SymbolKind::ClassLikeConstant | SymbolKind::EnumCase)
+    }
+
+    /// Returns the name of the kind, as used in messages.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            SymbolKind::Class => "class",
+            SymbolKind::Interface => "interface",
+            SymbolKind::Enum => "enum",
+            SymbolKind::Trait => "trait",
+            SymbolKind::Function => "function",
+            SymbolKind::Constant => "constant",
+            SymbolKind::Method => "method",
+            SymbolKind::Property => "property",
+            SymbolKind::ClassLikeConstant => "class constant",
+            SymbolKind::EnumCase => "enum case",
+        }
+    }
+}
+
+impl<'a> Symbol<'a> {
+    /// Returns the kind of the symbol.
+    pub fn kind(&self) -> SymbolKind {
+        match self {
+            Symbol::ClassLike(class_like) => class_like_kind(&class_like.name),
+            Symbol::Function(_) => SymbolKind::Function,
+            Symbol::Constant(_) => SymbolKind::Constant,
+            Symbol::Method(_, _) => SymbolKind::Method,
+            Symbol::Property(_, _) => SymbolKind::Property,
+            Symbol::ClassLikeConstant(_, _) => SymbolKind::ClassLikeConstant,
+            Symbol::EnumCase(_, _) => SymbolKind::EnumCase,
+        }
+    }
+
+    /// Returns the reflection of the class-like of the symbol, which is the symbol itself for class-likes.
+    pub fn class_like(&self) -> Option<&'a ClassLikeReflection> {
+        match self {
+            Symbol::ClassLike(class_like)
+            | Symbol::Method(class_like, _)
+            | Symbol::Property(class_like, _)
+            | Symbol::ClassLikeConstant(class_like, _)
+            | Symbol::EnumCase(class_like, _) => Some(class_like),
+            Symbol::Function(_) | Symbol::Constant(_) => None,
+        }
+    }
+
+    /// Returns the fully qualified name of the symbol, such as `App\Foo`, `App\Foo::bar`, or `App\Foo::$baz`.
+    pub fn name(&self, interner: &ThreadedInterner) -> String {
+        let member = match self {
+            Symbol::ClassLike(class_like) => return class_like.name.get_key(interner),
+            Symbol::Function(function) => return function.name.get_key(interner),
+            Symbol::Constant(constant) => return interner.lookup(&constant.name.value).to_string(),
+            Symbol::Method(_, method) => match method.name {
+                FunctionLikeName::Method(_, name) => name.value,
+                _ => return method.name.get_key(interner),
+            },
+            Symbol::Property(_, property) => property.name.member.value,
+            Symbol::ClassLikeConstant(_, constant) => constant.name.member.value,
+            Symbol::EnumCase(_, case) => case.name.member.value,
+        };
+
+        let class_like = self.class_like().map(|class_like| class_like.name.get_key(interner)).unwrap_or_default();
+
+        format!("{}::{}", class_like, interner.lookup(&member))
+    }
+
+    /// Checks if the symbol is declared in a user-defined source.
+    pub fn is_user_defined(&self) -> bool {
+        self.span().start.source.is_user_defined()
+    }
+}
+
+impl NamePattern {
+    /// Creates a pattern from the given name, ignoring its leading namespace separator, if any.
+    pub fn new(pattern: &str, case_insensitive: bool) -> Self {
+        Self { pattern: pattern.strip_prefix('\\').unwrap_or(pattern).to_string(), case_insensitive }
+    }
+
+    /// Checks if the pattern is an exact name, without wildcards.
+    pub fn is_exact(&self) -> bool {
+        !self.pattern.contains(['*', '?'])
+    }
+
+    /// Returns the pattern, without its leading namespace separator.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Checks if the given name matches the pattern, ignoring its leading namespace separator, if any.
+    pub fn matches(&self, name: &str) -> bool {
+        let name: Vec<char> = name.strip_prefix('\\').unwrap_or(name).chars().collect();
+        let pattern: Vec<char> = self.pattern.chars().collect();
+        let equals = |a: char, b: char| if self.case_insensitive { a.eq_ignore_ascii_case(&b) } else { a == b };
+
+        // The position after the last `*`, and the position in the name it was matched up to, to backtrack to.
+        let mut backtrack: Option<(usize, usize)> = None;
+        let (mut p, mut n) = (0, 0);
+        while n < name.len() {
+            match pattern.get(p) {
+                Some('*') => {
+                    backtrack = Some((p + 1, n));
+                    p += 1;
+                }
+                Some(&c) if c == '?' || equals(c, name[n]) => {
+                    p += 1;
+                    n += 1;
+                }
+                _ => match backtrack {
+                    Some((star, matched)) => {
+                        backtrack = Some((star, matched + 1));
+                        p = star;
+                        n = matched + 1;
+                    }
+                    None => return false,
+                },
+            }
+        }
+
+        pattern[p..].iter().all(|c| *c == '*')
+    }
+}
+
+impl SymbolQuery {
+    /// Creates a query from the given string, which is either a name, or a class-like name and a member name
+    /// separated by `::`, each of which may contain wildcards.
+    ///
+    /// The query looks up the symbols of all kinds, declared in any source, and compares names case-sensitively.
+    pub fn new(query: &str) -> Self {
+        let (name, member) = match query.split_once("::") {
+            Some((name, member)) => (name, Some(member)),
+            None => (query, None),
+        };
+
+        Self {
+            name: NamePattern::new(name, false),
+            member: member.map(|member| NamePattern::new(member, false)),
+            kinds: HashSet::default(),
+            user_defined_only: false,
+            include_anonymous: false,
+        }
+    }
+
+    /// Sets whether names are compared regardless of the case of their ASCII letters.
+    pub fn with_case_insensitivity(mut self, case_insensitive: bool) -> Self {
+        self.name.case_insensitive = case_insensitive;
+        if let Some(member) = &mut self.member {
+            member.case_insensitive = case_insensitive;
+        }
+
+        self
+    }
+
+    /// Restricts the query to the symbols of the given kinds; the symbols of all kinds are looked up otherwise.
+    ///
+    /// Members are only looked up by the queries with a member name, and the other symbols by those without.
+    pub fn with_kinds(mut self, kinds: impl IntoIterator<Item = SymbolKind>) -> Self {
+        self.kinds.extend(kinds);
+
+        self
+    }
+
+    /// Sets whether only the symbols declared in user-defined sources are looked up.
+    pub fn with_user_defined_only(mut self, user_defined_only: bool) -> Self {
+        self.user_defined_only = user_defined_only;
+
+        self
+    }
+
+    /// Sets whether anonymous classes, closures, and arrow functions are looked up, along with the members of
+    /// anonymous classes.
+    ///
+    /// As they have no name, they are matched against the names synthesized from their locations, such as
+    /// `anonymous-class@src/a.php:120-480`, or `closure@src/a.php:200-260`.
+    pub fn with_anonymous(mut self, include_anonymous: bool) -> Self {
+        self.include_anonymous = include_anonymous;
+
+        self
+    }
+
+    /// Looks up the symbols matching the query in the given codebase.
+    ///
+    /// Only the members declared by each class-like are looked up, not those it inherits. Anonymous classes,
+    /// closures, and arrow functions are only looked up by the queries including them.
+    ///
+    /// # Returns
+    ///
+    /// The matching symbols, sorted by their names, and then by the positions of their declarations.
+    pub fn find<'a>(&self, interner: &ThreadedInterner, codebase: &'a CodebaseReflection) -> Vec<Symbol<'a>> {
+        let mut symbols = vec![];
+        match &self.member {
+            Some(member) => {
+                for class_like in self.find_class_likes(interner, codebase) {
+                    symbols.extend(
+                        class_like_members(class_like)
+                            .filter(|symbol| self.includes(symbol.kind()))
+                            .filter(|symbol| member.matches(&member_name(interner, symbol))),
+                    );
+                }
+            }
+            None => {
+                symbols.extend(
+                    self.find_class_likes(interner, codebase)
+                        .into_iter()
+                        .filter(|class_like| self.includes(class_like_kind(&class_like.name)))
+                        .map(Symbol::ClassLike),
+                );
+
+                if self.includes(SymbolKind::Function) {
+                    symbols.extend(self.find_functions(interner, codebase).into_iter().map(Symbol::Function));
+                }
+
+                if self.includes(SymbolKind::Constant) {
+                    symbols.extend(self.find_constants(interner, codebase).into_iter().map(Symbol::Constant));
+                }
+            }
+        }
+
+        symbols.retain(|symbol| !self.user_defined_only || symbol.is_user_defined());
+        symbols.sort_by_cached_key(|symbol| {
+            let span = symbol.span();
+
+            (symbol.name(interner), interner.lookup(&span.start.source.0).to_string(), span.start.offset)
+        });
+
+        symbols
+    }
+
+    /// Checks if the query includes the symbols of the given kind.
+    fn includes(&self, kind: SymbolKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+
+    /// Returns the class-likes matching the name of the query, using the index of the names of the codebase
+    /// when the name is exact, and anonymous classes are not looked up.
+    fn find_class_likes<'a>(
+        &self,
+        interner: &ThreadedInterner,
+        codebase: &'a CodebaseReflection,
+    ) -> Vec<&'a ClassLikeReflection> {
+        let matches = |class_like: &&ClassLikeReflection| match class_like.name.inner() {
+            Some(name) => self.name.matches(interner.lookup(&name.value)),
+            None => self.include_anonymous && self.name.matches(&class_like.name.get_key(interner)),
+        };
+
+        if self.name.is_exact() && !self.include_anonymous {
+            return codebase
+                .get_named_class_like(interner, &interner.intern(self.name.as_str()))
+                .into_iter()
+                .filter(matches)
+                .collect();
+        }
+
+        codebase.class_like_reflections.values().filter(matches).collect()
+    }
+
+    /// Returns the functions matching the name of the query, using the index of the names of the codebase
+    /// when the name is exact, and closures and arrow functions are not looked up.
+    fn find_functions<'a>(
+        &self,
+        interner: &ThreadedInterner,
+        codebase: &'a CodebaseReflection,
+    ) -> Vec<&'a FunctionLikeReflection> {
+        let matches = |function: &&FunctionLikeReflection| match function.name {
+            FunctionLikeName::Function(name) => self.name.matches(interner.lookup(&name.value)),
+            FunctionLikeName::Closure(_) | FunctionLikeName::ArrowFunction(_) => {
+                self.include_anonymous && self.name.matches(&function.name.get_key(interner))
+            }
+            FunctionLikeName::Method(..) | FunctionLikeName::PropertyHook(..) => false,
+        };
+
+        if self.name.is_exact() && !self.include_anonymous {
+            return codebase
+                .get_function(interner, &interner.intern(self.name.as_str()))
+                .into_iter()
+                .filter(matches)
+                .collect();
+        }
+
+        codebase.function_like_reflections.values().filter(matches).collect()
+    }
+
+    /// Returns the constants matching the name of the query, using the index of the names of the codebase
+    /// when the name is exact, and compared case-sensitively, as the names of constants are.
+    fn find_constants<'a>(
+        &self,
+        interner: &ThreadedInterner,
+        codebase: &'a CodebaseReflection,
+    ) -> Vec<&'a ConstantReflection> {
+        let matches = |constant: &&ConstantReflection| self.name.matches(interner.lookup(&constant.name.value));
+
+        if self.name.is_exact() && !self.name.case_insensitive {
+            return codebase
+                .get_constant(interner, &interner.intern(self.name.as_str()))
+                .into_iter()
+                .filter(matches)
+                .collect();
+        }
+
+        codebase.constant_reflections.values().filter(matches).collect()
+    }
+}
+
+impl Display for SymbolKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl HasSpan for Symbol<'_> {
+    /// Returns the span of the name of the symbol.
+    fn span(&self) -> Span {
+        match self {
+            Symbol::ClassLike(class_like) => class_like.name.span(),
+            Symbol::Function(function) => function.name.span(),
+            Symbol::Constant(constant) => constant.name.span,
+            Symbol::Method(_, method) => method.name.span(),
+            Symbol::Property(_, property) => property.name.span(),
+            Symbol::ClassLikeConstant(_, constant) => constant.name.span(),
+            Symbol::EnumCase(_, case) => case.name.span(),
+        }
+    }
+}
+
+/// Returns the kind of the class-like with the given name.
+fn class_like_kind(name: &ClassLikeName) -> SymbolKind {
+    match name {
+        ClassLikeName::Interface(_) => SymbolKind::Interface,
+        ClassLikeName::Enum(_) => SymbolKind::Enum,
+        ClassLikeName::Trait(_) => SymbolKind::Trait,
+        ClassLikeName::Class(_) | ClassLikeName::AnonymousClass(_) => SymbolKind::Class,
+    }
+}
+
+/// Returns the members declared by the given class-like, excluding those it inherits.
+fn class_like_members(class_like: &ClassLikeReflection) -> impl Iterator<Item = Symbol<'_>> {
+    let methods = class_like.methods.members.values().map(|method| Symbol::Method(class_like, method));
+    let properties = class_like.properties.members.values().map(|property| Symbol::Property(class_like, property));
+    let constants = class_like
+        .constants
+        .values()
+        .filter(|constant| constant.name.class_like == class_like.name)
+        .map(|constant| Symbol::ClassLikeConstant(class_like, constant));
+    let cases = class_like.cases.members.values().map(|case| Symbol::EnumCase(class_like, case));
+
+    methods.chain(properties).chain(constants).chain(cases)
+}
+
+/// Returns the name of the given member, without the name of its class-like.
+fn member_name(interner: &ThreadedInterner, symbol: &Symbol<'_>) -> String {
+    let name = symbol.name(interner);
+
+    name.rsplit_once("::").map(|(_, member)| member.to_string()).unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mago_source::SourceIdentifier;
+    use mago_span::Position;
+
+    use crate::class_like::inheritance::InheritanceReflection;
+    use crate::class_like::member::MemeberCollection;
+    use crate::identifier::Name;
+    use crate::r#type::kind::string_kind;
+    use crate::r#type::TypeReflection;
+
+    fn name(interner: &ThreadedInterner, value: &str, user_defined: bool) -> Name {
+        let source =
+            SourceIdentifier(interner.intern(if user_defined { "src/a.php" } else { "vendor/b.php" }), user_defined);
+        let span = Span::new(Position::new(source, 0), Position::new(source, value.len()));
+
+        Name::new(interner.intern(value), span)
+    }
+
+    fn function_like(name: FunctionLikeName) -> FunctionLikeReflection {
+        FunctionLikeReflection {
+            attribute_reflections: vec![],
+            visibility_reflection: None,
+            name,
+            templates: vec![],
+            parameters: vec![],
+            return_type_reflection: None,
+            returns_by_reference: false,
+            has_yield: false,
+            has_throws: false,
+            is_anonymous: false,
+            is_static: false,
+            is_final: false,
+            is_abstract: false,
+            is_pure: false,
+            is_overriding: false,
+            scope: None,
+            docblock: None,
+            span: name.span(),
+            is_conditionally_declared: false,
+            is_populated: false,
+        }
+    }
+
+    fn class_like(name: ClassLikeName, methods: &[Name]) -> ClassLikeReflection {
+        let mut reflection = ClassLikeReflection {
+            attribute_reflections: vec![],
+            name,
+            templates: vec![],
+            inheritance: InheritanceReflection::default(),
+            constants: Default::default(),
+            cases: MemeberCollection::empty(),
+            properties: MemeberCollection::empty(),
+            methods: MemeberCollection::empty(),
+            used_traits: Default::default(),
+            trait_precedences: vec![],
+            trait_aliases: vec![],
+            backing_type: None,
+            is_final: false,
+            is_readonly: false,
+            is_abstract: false,
+            is_anonymous: false,
+            docblock: None,
+            span: name.span(),
+            is_conditionally_declared: false,
+            is_populated: false,
+        };
+
+        for method in methods {
+            reflection.methods.members.insert(method.value, function_like(FunctionLikeName::Method(name, *method)));
+        }
+
+        reflection
+    }
+
+    fn codebase(interner: &ThreadedInterner) -> CodebaseReflection {
+        let mut codebase = CodebaseReflection::new();
+        let repository = ClassLikeName::Class(name(interner, "App\\Service\\UserRepository", true));
+        let methods = [name(interner, "findAll", true), name(interner, "findById", true), name(interner, "save", true)];
+        codebase.register_class_like(interner, class_like(repository, &methods));
+        codebase.register_class_like(
+            interner,
+            class_like(ClassLikeName::Interface(name(interner, "App\\Service\\Repository", true)), &[]),
+        );
+        codebase.register_class_like(
+            interner,
+            class_like(ClassLikeName::Class(name(interner, "App\\Service\\Mailer", true)), &[]),
+        );
+        codebase.register_class_like(
+            interner,
+            class_like(ClassLikeName::Class(name(interner, "Vendor\\PostRepository", false)), &[]),
+        );
+        codebase.register_function_like(
+            interner,
+            function_like(FunctionLikeName::Function(name(interner, "array_first", true))),
+        );
+        codebase.register_function_like(
+            interner,
+            function_like(FunctionLikeName::Function(name(interner, "array_map", false))),
+        );
+        let anonymous = ClassLikeName::AnonymousClass(name(interner, "new class {}", true).span);
+        codebase.register_class_like(interner, class_like(anonymous, &[name(interner, "handle", true)]));
+        codebase.register_function_like(
+            interner,
+            function_like(FunctionLikeName::Closure(name(interner, "fn", true).span)),
+        );
+        let constant = name(interner, "App\\VERSION", true);
+        codebase.register_constant(
+            interner,
+            ConstantReflection {
+                name: constant,
+                type_reflection: TypeReflection { kind: string_kind(), inferred: true, span: constant.span },
+                item_span: constant.span,
+                definition_span: constant.span,
+                is_conditionally_declared: false,
+                is_populated: false,
+            },
+        );
+
+        codebase
+    }
+
+    fn find(interner: &ThreadedInterner, codebase: &CodebaseReflection, query: SymbolQuery) -> Vec<String> {
+        query
+            .find(interner, codebase)
+            .iter()
+            .map(|symbol| format!("{} {}", symbol.kind(), symbol.name(interner)))
+            .collect()
+    }
+
+    #[test]
+    fn test_patterns_match_names() {
+        let pattern = NamePattern::new("\\App\\*Repository", false);
+
+        assert!(!pattern.is_exact());
+        assert!(pattern.matches("App\\Service\\UserRepository"));
+        assert!(pattern.matches("\\App\\Repository"));
+        assert!(!pattern.matches("App\\Service\\UserRepositoryFactory"));
+        assert!(!pattern.matches("app\\Service\\UserRepository"));
+        assert!(NamePattern::new("app\\*repository", true).matches("App\\Service\\UserRepository"));
+        assert!(NamePattern::new("array_?ap", false).matches("array_map"));
+        assert!(!NamePattern::new("array_?ap", false).matches("array_ap"));
+        assert!(NamePattern::new("*", false).matches(""));
+        assert!(NamePattern::new("a*b*c", false).matches("aXbXbXc"));
+        assert!(NamePattern::new("strlen", false).is_exact());
+    }
+
+    #[test]
+    fn test_find_class_likes_by_pattern() {
+        let interner = ThreadedInterner::new();
+        let codebase = codebase(&interner);
+
+        assert_eq!(
+            find(&interner, &codebase, SymbolQuery::new("App\\Service\\*Repository")),
+            ["interface App\\Service\\Repository", "class App\\Service\\UserRepository"]
+        );
+        assert_eq!(
+            find(&interner, &codebase, SymbolQuery::new("*Repository").with_kinds([SymbolKind::Class])),
+            ["class App\\Service\\UserRepository", "class Vendor\\PostRepository"]
+        );
+        assert_eq!(find(&interner, &codebase, SymbolQuery::new("*Repository").with_user_defined_only(true)).len(), 2);
+    }
+
+    #[test]
+    fn test_find_exact_names() {
+        let interner = ThreadedInterner::new();
+        let codebase = codebase(&interner);
+
+        assert_eq!(
+            find(&interner, &codebase, SymbolQuery::new("\\App\\Service\\Mailer")),
+            ["class App\\Service\\Mailer"]
+        );
+        assert!(find(&interner, &codebase, SymbolQuery::new("app\\service\\mailer")).is_empty());
+        assert_eq!(
+            find(&interner, &codebase, SymbolQuery::new("app\\service\\mailer").with_case_insensitivity(true)),
+            ["class App\\Service\\Mailer"]
+        );
+        assert_eq!(find(&interner, &codebase, SymbolQuery::new("App\\VERSION")), ["constant App\\VERSION"]);
+        assert_eq!(
+            find(&interner, &codebase, SymbolQuery::new("app\\version").with_case_insensitivity(true)),
+            ["constant App\\VERSION"]
+        );
+    }
+
+    #[test]
+    fn test_find_functions() {
+        let interner = ThreadedInterner::new();
+        let codebase = codebase(&interner);
+        let query = SymbolQuery::new("array_*").with_kinds([SymbolKind::Function]);
+
+        assert_eq!(find(&interner, &codebase, query.clone()), ["function array_first", "function array_map"]);
+        assert_eq!(find(&interner, &codebase, query.with_user_defined_only(true)), ["function array_first"]);
+    }
+
+    #[test]
+    fn test_find_members() {
+        let interner = ThreadedInterner::new();
+        let codebase = codebase(&interner);
+
+        assert_eq!(
+            find(&interner, &codebase, SymbolQuery::new("*Repository::find*")),
+            ["method App\\Service\\UserRepository::findAll", "method App\\Service\\UserRepository::findById"]
+        );
+        assert_eq!(
+            find(
+                &interner,
+                &codebase,
+                SymbolQuery::new("App\\Service\\UserRepository::SAVE").with_case_insensitivity(true)
+            ),
+            ["method App\\Service\\UserRepository::save"]
+        );
+        assert!(find(&interner, &codebase, SymbolQuery::new("*::save").with_kinds([SymbolKind::Property])).is_empty());
+    }
+
+    #[test]
+    fn test_find_anonymous_symbols() {
+        let interner = ThreadedInterner::new();
+        let codebase = codebase(&interner);
+
+        assert!(find(&interner, &codebase, SymbolQuery::new("*")).iter().all(|symbol| !symbol.contains('@')));
+        assert!(find(&interner, &codebase, SymbolQuery::new("*::handle")).is_empty());
+
+        let query = SymbolQuery::new("*@src/a.php:*").with_anonymous(true);
+        assert_eq!(
+            find(&interner, &codebase, query.clone()),
+            ["class anonymous-class@src/a.php:0-12", "function closure@src/a.php:0-2"]
+        );
+        assert_eq!(
+            find(&interner, &codebase, query.with_kinds([SymbolKind::Function])),
+            ["function closure@src/a.php:0-2"]
+        );
+        assert_eq!(
+            find(&interner, &codebase, SymbolQuery::new("anonymous-class@src/a.php:0-12::handle").with_anonymous(true)),
+            ["method anonymous-class@src/a.php:0-12::handle"]
+        );
+    }
+}
@@ -0,0 +1,308 @@
+//! An index of the references to the symbols of a codebase, such as its class-likes, functions, constants,
+//! methods, and class constants, built from the resolved names of each source.
+//!
+//! Symbols are keyed by their fully qualified names, lowercased as PHP compares them: entirely for the names
+//! of class-likes, functions, and methods, and only for the namespaces of constants, and not at all for the
+//! names of class constants, and enum cases.
+//!
+//! The references which cannot be resolved without knowing the types of the expressions, such as the methods
+//! called on objects whose class is not known, are recorded by the name of the member alone, while the
+//! constructs referencing symbols dynamically, such as `new $class`, or `$function()`, are recorded as dynamic
+//! usages of the source they are found in, so that the consumers of the index can be conservative.
+
+use ahash::HashMap;
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::StringIdentifier;
+use mago_interner::ThreadedInterner;
+use mago_source::SourceIdentifier;
+use mago_span::HasSpan;
+use mago_span::Span;
+
+use crate::identifier::ClassLikeName;
+use crate::identifier::FunctionLikeName;
+use crate::lower_constant_name;
+use crate::query::Symbol;
+
+/// A symbol which is referenced, keyed by its fully qualified, and lowercased, name.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum ReferencedSymbol {
+    /// A class, an interface, an enum, or a trait.
+    ClassLike(StringIdentifier),
+    /// A function.
+    Function(StringIdentifier),
+    /// A constant, whether declared with `const`, or `define()`.
+    Constant(StringIdentifier),
+    /// A method of the given class-like.
+    Method(StringIdentifier, StringIdentifier),
+    /// A method with the given name, called on an object whose class is not known, such as `$object->bar()`.
+    AnyMethod(StringIdentifier),
+    /// A constant, or an enum case, of the given class-like.
+    ClassLikeConstant(StringIdentifier, StringIdentifier),
+}
+
+/// How a symbol is referenced.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReferenceKind {
+    /// The class-like is instantiated, such as in `new Foo()`.
+    Instantiation,
+    /// The class-like is extended, such as in `class Foo extends Bar`.
+    Extension,
+    /// The interface is implemented, such as in `class Foo implements Bar`.
+    Implementation,
+    /// The trait is used, such as in `use Bar;` within a class-like.
+    TraitUse,
+    /// The class-like is named in a type hint, such as in `function foo(Bar $bar)`.
+    TypeHint,
+    /// The class-like is named in a `catch` clause.
+    Catch,
+    /// The class-like is named on the right of `instanceof`.
+    Instanceof,
+    /// The name of the class-like is taken with `::class`.
+    ClassName,
+    /// The class-like is named in an attribute, such as in `#[Bar]`.
+    Attribute,
+    /// The class-like is named on the left of `::`, such as in `Foo::bar()`, `Foo::BAR`, or `Foo::$bar`.
+    StaticAccess,
+    /// The function, or the method, is called.
+    Call,
+    /// A closure is created from the function, or the method, such as in `foo(...)`, or `$this->bar(...)`.
+    ClosureCreation,
+    /// The constant, or the class constant, is accessed.
+    ConstantAccess,
+}
+
+/// A reference to a symbol, at the given location.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct Reference {
+    pub kind: ReferenceKind,
+    pub span: Span,
+}
+
+/// A construct which may reference any symbol, as the name of the symbol is only known at runtime.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum DynamicUsageKind {
+    /// A class-like instantiated by an expression, such as `new $class()`.
+    Instantiation,
+    /// A function called by an expression, such as `$function()`, or by a function such as `call_user_func()`.
+    FunctionCall,
+    /// A method called by an expression, such as `$object->$method()`, or `Foo::{$method}()`.
+    MethodCall,
+    /// A class-like accessed by an expression, such as `$class::bar()`, or `$class::BAR`.
+    StaticAccess,
+    /// A constant, or a class constant, accessed by an expression, such as `constant($name)`, or `Foo::{$name}`.
+    ConstantAccess,
+}
+
+/// A dynamic usage, at the given location.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct DynamicUsage {
+    pub kind: DynamicUsageKind,
+    pub span: Span,
+}
+
+/// The references to the symbols of a codebase, and the dynamic usages of each of its sources.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct ReferenceIndex {
+    pub references: HashMap<ReferencedSymbol, Vec<Reference>>,
+    pub dynamic_usages: HashMap<SourceIdentifier, Vec<DynamicUsage>>,
+}
+
+impl ReferencedSymbol {
+    /// Creates the symbol of the class-like with the given name.
+    pub fn class_like(interner: &ThreadedInterner, name: &StringIdentifier) -> Self {
+        Self::ClassLike(interner.lowered(name))
+    }
+
+    /// Creates the symbol of the function with the given name.
+    pub fn function(interner: &ThreadedInterner, name: &StringIdentifier) -> Self {
+        Self::Function(interner.lowered(name))
+    }
+
+    /// Creates the symbol of the constant with the given name.
+    pub fn constant(interner: &ThreadedInterner, name: &StringIdentifier) -> Self {
+        Self::Constant(lower_constant_name(interner, name))
+    }
+
+    /// Creates the symbol of the method with the given name, of the class-like with the given name.
+    pub fn method(interner: &ThreadedInterner, class_like: &StringIdentifier, name: &StringIdentifier) -> Self {
+        Self::Method(interner.lowered(class_like), interner.lowered(name))
+    }
+
+    /// Creates the symbol of the methods with the given name, of any class-like.
+    pub fn any_method(interner: &ThreadedInterner, name: &StringIdentifier) -> Self {
+        Self::AnyMethod(interner.lowered(name))
+    }
+
+    /// Creates the symbol of the constant, or the enum case, with the given name, of the class-like with the given name.
+    pub fn class_like_constant(
+        interner: &ThreadedInterner,
+        class_like: &StringIdentifier,
+        name: &StringIdentifier,
+    ) -> Self {
+        Self::ClassLikeConstant(interner.lowered(class_like), *name)
+    }
+
+    /// Returns the symbols under which the references to the given symbol of a codebase are recorded, if any.
+    ///
+    /// The methods are also referenced by the calls on objects whose class is not known, which are returned
+    /// after the method itself. Neither properties, nor anonymous classes and their members, are indexed.
+    pub fn of(interner: &ThreadedInterner, symbol: &Symbol<'_>) -> Vec<Self> {
+        let class_like_name = |name: &ClassLikeName| match name {
+            ClassLikeName::Class(name)
+            | ClassLikeName::Interface(name)
+            | ClassLikeName::Enum(name)
+            | ClassLikeName::Trait(name) => Some(name.value),
+            ClassLikeName::AnonymousClass(_) => None,
+        };
+
+        let class_like = symbol.class_like().and_then(|class_like| class_like_name(&class_like.name));
+
+        match (symbol, class_like) {
+            (Symbol::ClassLike(_), Some(class_like)) => vec![Self::class_like(interner, &class_like)],
+            (Symbol::Function(function), _) => match function.name {
+                FunctionLikeName::Function(name) => vec![Self::function(interner, &name.value)],
+                _ => vec![],
+            },
+            (Symbol::Constant(constant), _) => vec![Self::constant(interner, &constant.name.value)],
+            (Symbol::Method(_, method), Some(class_like)) => match method.name {
+                FunctionLikeName::Method(_, name) => {
+                    vec![Self::method(interner, &class_like, &name.value), Self::any_method(interner, &name.value)]
+                }
+                _ => vec![],
+            },
+            (Symbol::ClassLikeConstant(_, constant), Some(class_like)) => {
+                vec![Self::class_like_constant(interner, &class_like, &constant.name.member.value)]
+            }
+            (Symbol::EnumCase(_, case), Some(class_like)) => {
+                vec![Self::class_like_constant(interner, &class_like, &case.name.member.value)]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+impl ReferenceKind {
+    /// Returns the name of the kind, as used in messages.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ReferenceKind::Instantiation => "instantiation",
+            ReferenceKind::Extension => "extension",
+            ReferenceKind::Implementation => "implementation",
+            ReferenceKind::TraitUse => "trait use",
+            ReferenceKind::TypeHint => "type hint",
+            ReferenceKind::Catch => "catch",
+            ReferenceKind::Instanceof => "instanceof",
+            ReferenceKind::ClassName => "class name",
+            ReferenceKind::Attribute => "attribute",
+            ReferenceKind::StaticAccess => "static access",
+            ReferenceKind::Call => "call",
+            ReferenceKind::ClosureCreation => "closure creation",
+            ReferenceKind::ConstantAccess => "constant access",
+        }
+    }
+}
+
+impl DynamicUsageKind {
+    /// Returns the name of the kind, as used in messages.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            DynamicUsageKind::Instantiation => "dynamic instantiation",
+            DynamicUsageKind::FunctionCall => "dynamic function call",
+            DynamicUsageKind::MethodCall => "dynamic method call",
+            DynamicUsageKind::StaticAccess => "dynamic static access",
+            DynamicUsageKind::ConstantAccess => "dynamic constant access",
+        }
+    }
+}
+
+impl ReferenceIndex {
+    /// Creates a new, empty `ReferenceIndex`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a reference to the given symbol.
+    pub fn add_reference(&mut self, symbol: ReferencedSymbol, kind: ReferenceKind, span: Span) {
+        self.references.entry(symbol).or_default().push(Reference { kind, span });
+    }
+
+    /// Records a dynamic usage, in the source of the given location.
+    pub fn add_dynamic_usage(&mut self, kind: DynamicUsageKind, span: Span) {
+        self.dynamic_usages.entry(span.start.source).or_default().push(DynamicUsage { kind, span });
+    }
+
+    /// Adds the references, and the dynamic usages, recorded by the given index, such as the index of another source.
+    pub fn extend(&mut self, other: ReferenceIndex) {
+        for (symbol, references) in other.references {
+            self.references.entry(symbol).or_default().extend(references);
+        }
+
+        for (source, usages) in other.dynamic_usages {
+            self.dynamic_usages.entry(source).or_default().extend(usages);
+        }
+    }
+
+    /// Returns the references to the given symbol, sorted by their locations.
+    pub fn get_references(&self, symbol: &ReferencedSymbol) -> Vec<Reference> {
+        let mut references = self.references.get(symbol).cloned().unwrap_or_default();
+        references.sort_by_key(|reference| (reference.span.start.source, reference.span.start.offset));
+
+        references
+    }
+
+    /// Checks if the given symbol is referenced, anywhere.
+    pub fn is_referenced(&self, symbol: &ReferencedSymbol) -> bool {
+        self.references.get(symbol).is_some_and(|references| !references.is_empty())
+    }
+
+    /// Checks if the given symbol is referenced outside of the given span, such as the declaration of the symbol,
+    /// so that recursive calls, and references of a class-like to itself, are not taken into account.
+    pub fn is_referenced_outside(&self, symbol: &ReferencedSymbol, span: &impl HasSpan) -> bool {
+        let span = span.span();
+
+        self.references.get(symbol).is_some_and(|references| {
+            references.iter().any(|reference| {
+                reference.span.start.source != span.start.source
+                    || reference.span.end.offset <= span.start.offset
+                    || reference.span.start.offset >= span.end.offset
+            })
+        })
+    }
+
+    /// Returns the dynamic usages found in the given source, if any.
+    pub fn get_dynamic_usages(&self, source: &SourceIdentifier) -> &[DynamicUsage] {
+        self.dynamic_usages.get(source).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Checks if any dynamic usage is found in the given source.
+    pub fn has_dynamic_usages(&self, source: &SourceIdentifier) -> bool {
+        !self.get_dynamic_usages(source).is_empty()
+    }
+
+    /// Returns the sources in which dynamic usages are found, sorted.
+    pub fn get_sources_with_dynamic_usages(&self) -> Vec<SourceIdentifier> {
+        let mut sources = self
+            .dynamic_usages
+            .iter()
+            .filter(|(_, usages)| !usages.is_empty())
+            .map(|(source, _)| *source)
+            .collect::<Vec<_>>();
+        sources.sort();
+
+        sources
+    }
+
+    /// Returns the number of symbols which are referenced.
+    pub fn len(&self) -> usize {
+        self.references.len()
+    }
+
+    /// Returns `true` if no reference, nor dynamic usage, is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.references.is_empty() && self.dynamic_usages.is_empty()
+    }
+}
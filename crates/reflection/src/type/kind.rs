@@ -398,6 +398,18 @@ pub enum TypeKind {
 }
 
 impl Template {
+    pub fn new(name: StringIdentifier, constraints: Vec<TypeKind>) -> Self {
+        Self { name, constraints }
+    }
+
+    pub fn get_name(&self) -> &StringIdentifier {
+        &self.name
+    }
+
+    pub fn get_constraints(&self) -> &[TypeKind] {
+        &self.constraints
+    }
+
     pub fn get_key(&self, interner: &ThreadedInterner) -> String {
         let mut key = String::from(interner.lookup(&self.name));
 
@@ -699,7 +711,15 @@ impl TypeKind {
 
     pub fn get_key(&self, interner: &ThreadedInterner) -> String {
         match &self {
-            TypeKind::Union { kinds } => kinds.iter().map(|k| k.get_key(interner)).collect::<Vec<_>>().join("|"),
+            TypeKind::Union { kinds } => kinds
+                .iter()
+                .map(|k| match k {
+                    // Intersections within unions, such as `(A&B)|null`, are printed in disjunctive normal form.
+                    TypeKind::Intersection { .. } => format!("({})", k.get_key(interner)),
+                    _ => k.get_key(interner),
+                })
+                .collect::<Vec<_>>()
+                .join("|"),
             TypeKind::Intersection { kinds } => kinds.iter().map(|k| k.get_key(interner)).collect::<Vec<_>>().join("&"),
             TypeKind::Scalar(scalar_type_kind) => match &scalar_type_kind {
                 ScalarTypeKind::Bool => "bool".to_string(),
@@ -0,0 +1,759 @@
+use ahash::HashSet;
+
+use mago_interner::StringIdentifier;
+use mago_interner::ThreadedInterner;
+use mago_reflection::attribute::AttributeReflection;
+use mago_reflection::class_like::member::ClassLikeMemberVisibilityReflection;
+use mago_reflection::class_like::ClassLikeReflection;
+use mago_reflection::function_like::parameter::FunctionLikeParameterReflection;
+use mago_reflection::function_like::FunctionLikeReflection;
+use mago_reflection::identifier::ClassLikeName;
+use mago_reflection::identifier::FunctionLikeName;
+use mago_reflection::identifier::Name;
+use mago_reflection::r#type::kind::*;
+use mago_reflection::CodebaseReflection;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_span::Span;
+
+/// The attribute of the stubs marking a return type that is only enforced with a deprecation,
+/// so that implementations can be updated.
+const TENTATIVE_TYPE_ATTRIBUTE: &str = "TentativeType";
+
+/// The attribute of the stubs marking a type which depends on the targeted version of PHP.
+const LANGUAGE_LEVEL_TYPE_AWARE_ATTRIBUTE: &str = "LanguageLevelTypeAware";
+
+/// The attribute of the stubs marking an element that is only available in some versions of PHP.
+pub(crate) const ELEMENT_AVAILABLE_ATTRIBUTE: &str = "PhpStormStubsElementAvailable";
+
+/// The codes of the issues reported when checking the inheritance of the class-likes.
+const INVALID_EXTENSION_CODE: &str = "reflection/invalid-extension";
+const UNIMPLEMENTED_ABSTRACT_METHOD_CODE: &str = "reflection/unimplemented-abstract-method";
+const FINAL_METHOD_OVERRIDE_CODE: &str = "reflection/final-method-override";
+const INCOMPATIBLE_VISIBILITY_CODE: &str = "reflection/incompatible-visibility";
+const INCOMPATIBLE_PARAMETERS_CODE: &str = "reflection/incompatible-parameters";
+const INCOMPATIBLE_RETURN_TYPE_CODE: &str = "reflection/incompatible-return-type";
+
+/// The methods which the engine implements for enums, on behalf of the `UnitEnum` and `BackedEnum` interfaces.
+const ENUM_METHODS: [&str; 3] = ["cases", "from", "tryfrom"];
+
+#[inline]
+pub fn check_inheritance(
+    interner: &ThreadedInterner,
+    codebase: &CodebaseReflection,
+    class_like: &ClassLikeReflection,
+) -> IssueCollection {
+    let mut checker = Checker { interner, codebase, issues: IssueCollection::new() };
+
+    checker.check_extensions(class_like);
+    checker.check_abstract_methods(class_like);
+
+    // The methods are checked in the order of their declaration, so that the issues are reported in a stable order.
+    let mut methods = class_like.methods.members.values().collect::<Vec<_>>();
+    methods.sort_by_key(|method| method.span.start.offset);
+    for method in methods {
+        checker.check_method(class_like, method);
+    }
+
+    checker.issues
+}
+
+struct Checker<'a> {
+    interner: &'a ThreadedInterner,
+    codebase: &'a CodebaseReflection,
+    issues: IssueCollection,
+}
+
+impl<'a> Checker<'a> {
+    /// Reports the extension of a final class, or of a class-like entity which cannot be extended.
+    fn check_extensions(&mut self, class_like: &ClassLikeReflection) {
+        if let Some(parent_name) = class_like.inheritance.direct_extended_class {
+            if let Some(parent) = self.get(&parent_name.value) {
+                let issue = if !parent.is_class() {
+                    Issue::error(format!(
+                        "The {} `{}` cannot extend the {} `{}`, as classes can only extend classes.",
+                        kind(class_like),
+                        self.display(class_like),
+                        kind(parent),
+                        self.display(parent),
+                    ))
+                    .with_code(INVALID_EXTENSION_CODE)
+                    .with_help(format!(
+                        "Implement, or use, the {} `{}` instead.",
+                        kind(parent),
+                        self.display(parent)
+                    ))
+                } else if parent.is_final {
+                    Issue::error(format!(
+                        "The {} `{}` cannot extend the final class `{}`.",
+                        kind(class_like),
+                        self.display(class_like),
+                        self.display(parent),
+                    ))
+                    .with_code(INVALID_EXTENSION_CODE)
+                    .with_help(format!(
+                        "Remove the `final` modifier of the class `{}`, or do not extend it.",
+                        self.display(parent)
+                    ))
+                } else {
+                    return;
+                };
+
+                self.issues.push(
+                    issue
+                        .with_annotation(Annotation::primary(parent_name.span).with_message("Extended here."))
+                        .with_annotation(Annotation::secondary(name_span(parent)).with_message(format!(
+                            "The {} `{}` is declared here.",
+                            kind(parent),
+                            self.display(parent)
+                        ))),
+                );
+            }
+        }
+
+        if !class_like.is_interface() {
+            return;
+        }
+
+        // The interfaces extended by an interface are recorded as the interfaces it implements.
+        for parent_name in &class_like.inheritance.direct_implemented_interfaces {
+            let Some(parent) = self.get(&parent_name.value) else {
+                continue;
+            };
+
+            if parent.is_interface() {
+                continue;
+            }
+
+            self.issues.push(
+                Issue::error(format!(
+                    "The interface `{}` cannot extend the {} `{}`, as interfaces can only extend interfaces.",
+                    self.display(class_like),
+                    kind(parent),
+                    self.display(parent),
+                ))
+                .with_code(INVALID_EXTENSION_CODE)
+                .with_annotation(Annotation::primary(parent_name.span).with_message("Extended here."))
+                .with_annotation(Annotation::secondary(name_span(parent)).with_message(format!(
+                    "The {} `{}` is declared here.",
+                    kind(parent),
+                    self.display(parent)
+                )))
+                .with_help(format!(
+                    "Remove the {} `{}` from the extended interfaces.",
+                    kind(parent),
+                    self.display(parent)
+                )),
+            );
+        }
+    }
+
+    /// Reports the abstract methods, inherited from the parent classes, the used traits, and the implemented
+    /// interfaces, which a concrete class or an enum does not implement.
+    fn check_abstract_methods(&mut self, class_like: &ClassLikeReflection) {
+        let is_concrete = match class_like.name {
+            ClassLikeName::Class(_) | ClassLikeName::AnonymousClass(_) => !class_like.is_abstract,
+            ClassLikeName::Enum(_) => true,
+            ClassLikeName::Interface(_) | ClassLikeName::Trait(_) => false,
+        };
+
+        // The methods inherited from an unknown class-like entity are unknown, so they may implement the others.
+        if !is_concrete || self.has_unknown_ancestors(class_like) {
+            return;
+        }
+
+        let mut implemented = HashSet::default();
+        if class_like.is_enum() {
+            implemented.extend(ENUM_METHODS.map(|method| method.to_string()));
+        }
+
+        let mut abstract_methods = vec![];
+        for (method_name, declaring_class_like) in &class_like.methods.declaring_members {
+            let lowered_name = self.interner.lookup(method_name).to_ascii_lowercase();
+
+            match self.get_declared_method(declaring_class_like, method_name) {
+                // The abstract methods declared in the class itself are reported by the semantic analysis.
+                Some((declaring_class_like, method))
+                    if method.is_abstract
+                        && declaring_class_like.name != class_like.name
+                        && !self.has_attribute(&method.attribute_reflections, ELEMENT_AVAILABLE_ATTRIBUTE) =>
+                {
+                    abstract_methods.push((lowered_name, declaring_class_like, method));
+                }
+                _ => {
+                    implemented.insert(lowered_name);
+                }
+            }
+        }
+
+        abstract_methods.retain(|(lowered_name, _, _)| !implemented.contains(lowered_name));
+        abstract_methods.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+        abstract_methods.dedup_by(|(a, _, _), (b, _, _)| a == b);
+
+        let Some((_, first_class_like, first_method)) = abstract_methods.first() else {
+            return;
+        };
+
+        let message = if abstract_methods.len() == 1 {
+            format!(
+                "The {} `{}` does not implement the abstract method `{}`.",
+                kind(class_like),
+                self.display(class_like),
+                self.display_method(first_class_like, first_method),
+            )
+        } else {
+            format!(
+                "The {} `{}` does not implement {} abstract methods.",
+                kind(class_like),
+                self.display(class_like),
+                abstract_methods.len(),
+            )
+        };
+
+        let mut issue = Issue::error(message).with_code(UNIMPLEMENTED_ABSTRACT_METHOD_CODE).with_annotation(
+            Annotation::primary(name_span(class_like))
+                .with_message(format!("The {} is declared here.", kind(class_like))),
+        );
+
+        for (_, declaring_class_like, method) in &abstract_methods {
+            issue = issue.with_annotation(Annotation::secondary(method_name_span(method)).with_message(format!(
+                "The abstract method `{}` is declared here.",
+                self.display_method(declaring_class_like, method),
+            )));
+        }
+
+        self.issues.push(issue.with_help(if class_like.is_class() {
+            "Implement the missing methods, or declare the class `abstract`."
+        } else {
+            "Implement the missing methods."
+        }));
+    }
+
+    /// Reports the incompatibilities of a method with the methods it overrides, in the parent class
+    /// and in the implemented, or extended, interfaces.
+    fn check_method(&mut self, class_like: &ClassLikeReflection, method: &FunctionLikeReflection) {
+        let FunctionLikeName::Method(_, name) = method.name else {
+            return;
+        };
+
+        let lowered_name = self.interner.lookup(&name.value).to_ascii_lowercase();
+
+        let mut overridden_methods = vec![];
+        if let Some(parent) = class_like.inheritance.direct_extended_class.and_then(|parent| self.get(&parent.value)) {
+            overridden_methods.extend(self.find_method(parent, &lowered_name));
+        }
+
+        for interface in &class_like.inheritance.direct_implemented_interfaces {
+            if let Some(interface) = self.get(&interface.value).filter(|interface| interface.is_interface()) {
+                if let Some(overridden) = self.find_method(interface, &lowered_name) {
+                    if !overridden_methods
+                        .iter()
+                        .any(|(_, method): &(_, &FunctionLikeReflection)| method.span == overridden.1.span)
+                    {
+                        overridden_methods.push(overridden);
+                    }
+                }
+            }
+        }
+
+        for (parent, parent_method) in overridden_methods {
+            // Private methods are not inherited, so they are not overridden.
+            if parent_method.visibility_reflection.is_some_and(|visibility| visibility.is_private())
+                || self.has_attribute(&parent_method.attribute_reflections, ELEMENT_AVAILABLE_ATTRIBUTE)
+            {
+                continue;
+            }
+
+            // The methods of a final class are final as well, but extending the class is reported instead.
+            if parent_method.is_final && !parent.is_final {
+                self.issues.push(
+                    Issue::error(format!(
+                        "The method `{}` cannot override the final method `{}`.",
+                        self.display_method(class_like, method),
+                        self.display_method(parent, parent_method),
+                    ))
+                    .with_code(FINAL_METHOD_OVERRIDE_CODE)
+                    .with_annotation(Annotation::primary(name.span).with_message("Overridden here."))
+                    .with_annotation(
+                        Annotation::secondary(method_name_span(parent_method)).with_message("Declared final here."),
+                    )
+                    .with_help("Rename the method, or remove the `final` modifier of the overridden method."),
+                );
+
+                continue;
+            }
+
+            self.check_visibility(class_like, method, parent, parent_method);
+
+            // The signature of a constructor is only checked against an abstract one.
+            if lowered_name == "__construct" && !parent_method.is_abstract {
+                continue;
+            }
+
+            self.check_parameters(class_like, method, parent, parent_method);
+            self.check_return_type(class_like, method, parent, parent_method, &lowered_name);
+        }
+    }
+
+    fn check_visibility(
+        &mut self,
+        class_like: &ClassLikeReflection,
+        method: &FunctionLikeReflection,
+        parent: &ClassLikeReflection,
+        parent_method: &FunctionLikeReflection,
+    ) {
+        let (visibility, parent_visibility) = (visibility(method), visibility(parent_method));
+        if visibility >= parent_visibility {
+            return;
+        }
+
+        let (parent_visibility_name, expected) =
+            if parent_visibility == 2 { ("public", "`public`") } else { ("protected", "`protected`, or `public`") };
+
+        self.issues.push(
+            Issue::error(format!(
+                "The method `{}` must be {}, as the overridden method `{}` is `{}`.",
+                self.display_method(class_like, method),
+                expected,
+                self.display_method(parent, parent_method),
+                parent_visibility_name,
+            ))
+            .with_code(INCOMPATIBLE_VISIBILITY_CODE)
+            .with_annotation(
+                Annotation::primary(visibility_span(method)).with_message("The visibility is reduced here."),
+            )
+            .with_annotation(
+                Annotation::secondary(visibility_span(parent_method))
+                    .with_message(format!("The overridden method is `{}`.", parent_visibility_name)),
+            )
+            .with_help(format!("Make the method {}.", expected)),
+        );
+    }
+
+    fn check_parameters(
+        &mut self,
+        class_like: &ClassLikeReflection,
+        method: &FunctionLikeReflection,
+        parent: &ClassLikeReflection,
+        parent_method: &FunctionLikeReflection,
+    ) {
+        let parameters = &method.parameters;
+        let parent_parameters = &parent_method.parameters;
+        let is_variadic = parameters.last().is_some_and(|parameter| parameter.is_variadic);
+
+        // The parameters of the stubs which are only available in some versions of PHP are not counted.
+        let has_available_parameters = parent_parameters
+            .iter()
+            .any(|parameter| self.has_attribute(&parameter.attribute_reflections, ELEMENT_AVAILABLE_ATTRIBUTE));
+
+        if !has_available_parameters && !is_variadic {
+            let missing_parameter =
+                parent_parameters.iter().take_while(|parameter| !parameter.is_variadic).nth(parameters.len());
+
+            if let Some(missing_parameter) = missing_parameter {
+                self.issues.push(
+                    Issue::error(format!(
+                        "The method `{}` does not accept the parameter `{}` of the overridden method `{}`.",
+                        self.display_method(class_like, method),
+                        self.interner.lookup(&missing_parameter.name),
+                        self.display_method(parent, parent_method),
+                    ))
+                    .with_code(INCOMPATIBLE_PARAMETERS_CODE)
+                    .with_annotation(
+                        Annotation::primary(method_name_span(method))
+                            .with_message("Declared here with fewer parameters."),
+                    )
+                    .with_annotation(
+                        Annotation::secondary(missing_parameter.span)
+                            .with_message("The overridden parameter is declared here."),
+                    )
+                    .with_help("Add the missing parameters to the method."),
+                );
+
+                return;
+            }
+        }
+
+        if !has_available_parameters {
+            let added_parameter = parameters.iter().enumerate().find(|(index, parameter)| {
+                is_required(parameter) && parent_parameters.get(*index).is_none_or(|parameter| !is_required(parameter))
+            });
+
+            if let Some((_, added_parameter)) = added_parameter {
+                self.issues.push(
+                    Issue::error(format!(
+                        "The method `{}` requires the parameter `{}`, which the overridden method `{}` does not require.",
+                        self.display_method(class_like, method),
+                        self.interner.lookup(&added_parameter.name),
+                        self.display_method(parent, parent_method),
+                    )).with_code(INCOMPATIBLE_PARAMETERS_CODE)
+                    .with_annotation(
+                        Annotation::primary(added_parameter.span).with_message("This parameter is required."),
+                    )
+                    .with_annotation(
+                        Annotation::secondary(method_name_span(parent_method))
+                            .with_message("The overridden method is declared here."),
+                    )
+                    .with_help("Give the parameter a default value, or remove it."),
+                );
+
+                return;
+            }
+        }
+
+        for (index, parent_parameter) in parent_parameters.iter().enumerate() {
+            let Some(parameter) = parameters.get(index).or_else(|| parameters.last().filter(|last| last.is_variadic))
+            else {
+                break;
+            };
+
+            let Some(type_reflection) = &parameter.type_reflection else {
+                continue;
+            };
+
+            if self.has_attribute(&parent_parameter.attribute_reflections, LANGUAGE_LEVEL_TYPE_AWARE_ATTRIBUTE) {
+                continue;
+            }
+
+            let parent_kind = match &parent_parameter.type_reflection {
+                Some(parent_type_reflection) => parent_type_reflection.kind.clone(),
+                None => mixed_kind(false),
+            };
+
+            if self.is_subtype(&parent_kind, &type_reflection.kind) {
+                continue;
+            }
+
+            let parent_span = match &parent_parameter.type_reflection {
+                Some(parent_type_reflection) => parent_type_reflection.span,
+                None => parent_parameter.span,
+            };
+
+            self.issues.push(
+                Issue::error(format!(
+                    "The type of the parameter `{}` of the method `{}` is not compatible with the overridden method `{}`.",
+                    self.interner.lookup(&parameter.name),
+                    self.display_method(class_like, method),
+                    self.display_method(parent, parent_method),
+                )).with_code(INCOMPATIBLE_PARAMETERS_CODE)
+                .with_annotation(
+                    Annotation::primary(type_reflection.span)
+                        .with_message("This type does not accept all the values of the overridden parameter."),
+                )
+                .with_annotation(
+                    Annotation::secondary(parent_span).with_message("The overridden parameter is declared here."),
+                )
+                .with_note("The parameters of an overriding method must accept all the values the overridden method accepts.")
+                .with_help("Widen the type of the parameter, or remove it."),
+            );
+        }
+    }
+
+    fn check_return_type(
+        &mut self,
+        class_like: &ClassLikeReflection,
+        method: &FunctionLikeReflection,
+        parent: &ClassLikeReflection,
+        parent_method: &FunctionLikeReflection,
+        lowered_name: &str,
+    ) {
+        let Some(parent_return_type) = &parent_method.return_type_reflection else {
+            return;
+        };
+
+        if self.has_attribute(&parent_method.attribute_reflections, TENTATIVE_TYPE_ATTRIBUTE)
+            || self.has_attribute(&parent_method.attribute_reflections, LANGUAGE_LEVEL_TYPE_AWARE_ATTRIBUTE)
+        {
+            return;
+        }
+
+        let Some(return_type) = &method.return_type_reflection else {
+            // The `string` return type of `__toString` is implicit.
+            if lowered_name == "__tostring" {
+                return;
+            }
+
+            self.issues.push(
+                Issue::error(format!(
+                    "The method `{}` must declare a return type compatible with the overridden method `{}`.",
+                    self.display_method(class_like, method),
+                    self.display_method(parent, parent_method),
+                ))
+                .with_code(INCOMPATIBLE_RETURN_TYPE_CODE)
+                .with_annotation(
+                    Annotation::primary(method_name_span(method)).with_message("Declared here without a return type."),
+                )
+                .with_annotation(
+                    Annotation::secondary(parent_return_type.span)
+                        .with_message("The overridden return type is declared here."),
+                )
+                .with_help("Add a return type to the method, compatible with the overridden one."),
+            );
+
+            return;
+        };
+
+        if self.is_subtype(&return_type.type_reflection.kind, &parent_return_type.type_reflection.kind) {
+            return;
+        }
+
+        self.issues.push(
+            Issue::error(format!(
+                "The return type of the method `{}` is not compatible with the overridden method `{}`.",
+                self.display_method(class_like, method),
+                self.display_method(parent, parent_method),
+            ))
+            .with_code(INCOMPATIBLE_RETURN_TYPE_CODE)
+            .with_annotation(
+                Annotation::primary(return_type.span)
+                    .with_message("This type allows values which the overridden method does not return."),
+            )
+            .with_annotation(
+                Annotation::secondary(parent_return_type.span)
+                    .with_message("The overridden return type is declared here."),
+            )
+            .with_note("The return type of an overriding method must be a subtype of the overridden return type.")
+            .with_help("Narrow the return type of the method."),
+        );
+    }
+
+    /// Determines whether all the values of the type `sub` are values of the type `sup`, as PHP checks
+    /// the compatibility of the native type declarations.
+    ///
+    /// The class-like entities which are not in the codebase are assumed to be compatible.
+    fn is_subtype(&self, sub: &TypeKind, sup: &TypeKind) -> bool {
+        match (sub, sup) {
+            (TypeKind::Never, _) | (TypeKind::Void, TypeKind::Void) => true,
+            (TypeKind::Void, _) | (_, TypeKind::Void) => false,
+            (_, TypeKind::Mixed { .. }) => true,
+            (TypeKind::Union { kinds }, _) => kinds.iter().all(|kind| self.is_subtype(kind, sup)),
+            (_, TypeKind::Intersection { kinds }) => kinds.iter().all(|kind| self.is_subtype(sub, kind)),
+            (_, TypeKind::Union { kinds }) => {
+                kinds.iter().any(|kind| self.is_subtype(sub, kind))
+                    || matches!(sub, TypeKind::Intersection { kinds } if kinds.iter().any(|kind| self.is_subtype(kind, sup)))
+            }
+            (TypeKind::Intersection { kinds }, _) => kinds.iter().any(|kind| self.is_subtype(kind, sup)),
+            (TypeKind::Scalar(sub), TypeKind::Scalar(sup)) => sub == sup,
+            (TypeKind::Value(ValueTypeKind::True | ValueTypeKind::False), TypeKind::Scalar(ScalarTypeKind::Bool)) => {
+                true
+            }
+            (TypeKind::Value(sub), TypeKind::Value(sup)) => sub == sup,
+            (TypeKind::Array(_), TypeKind::Array(_) | TypeKind::Iterable { .. }) => true,
+            (TypeKind::Iterable { .. }, TypeKind::Iterable { .. }) => true,
+            (TypeKind::Callable(_), TypeKind::Callable(_)) => true,
+            (TypeKind::Object(sub), TypeKind::Object(sup)) => self.is_object_subtype(sub, sup),
+            (TypeKind::Object(sub), TypeKind::Iterable { .. }) => self.is_object_instance_of(sub, "Traversable"),
+            (TypeKind::Object(sub), TypeKind::Callable(_)) => self.is_object_instance_of(sub, "Closure"),
+            _ => false,
+        }
+    }
+
+    fn is_object_subtype(&self, sub: &ObjectTypeKind, sup: &ObjectTypeKind) -> bool {
+        match (sub, sup) {
+            (_, ObjectTypeKind::AnyObject) => true,
+            (ObjectTypeKind::AnyObject, _) => false,
+            (ObjectTypeKind::Static { .. }, ObjectTypeKind::Static { .. }) => true,
+            (_, ObjectTypeKind::Static { .. }) => false,
+            _ => match (self.get_object_class_like_name(sub), self.get_object_class_like_name(sup)) {
+                (Some(sub), Some(sup)) => self.is_instance_of(sub, sup),
+                _ => true,
+            },
+        }
+    }
+
+    fn is_object_instance_of(&self, object: &ObjectTypeKind, class_like_name: &str) -> bool {
+        match self.get_object_class_like_name(object) {
+            Some(name) => self.is_instance_of(name, self.interner.intern(class_like_name)),
+            None => !matches!(object, ObjectTypeKind::AnyObject),
+        }
+    }
+
+    /// Returns the name of the class-like entity of an object type, resolving `self`, `static`, and `parent`.
+    ///
+    /// Returns `None` if the class-like entity is unknown, or if the object type is declared in a trait,
+    /// as `self` then refers to the class-like entity using it.
+    fn get_object_class_like_name(&self, object: &ObjectTypeKind) -> Option<StringIdentifier> {
+        match object {
+            ObjectTypeKind::NamedObject { name, .. } => Some(*name),
+            ObjectTypeKind::Static { scope } | ObjectTypeKind::Self_ { scope } => {
+                self.get(scope).filter(|class_like| !class_like.is_trait()).map(|_| *scope)
+            }
+            ObjectTypeKind::Parent { scope } => self
+                .get(scope)
+                .filter(|class_like| !class_like.is_trait())
+                .and_then(|class_like| class_like.inheritance.direct_extended_class)
+                .map(|parent| parent.value),
+            _ => None,
+        }
+    }
+
+    /// Determines whether the instances of the class-like entity `name` are instances of `other`.
+    ///
+    /// Returns `true` when either of them, or one of the ancestors of `name`, is unknown.
+    fn is_instance_of(&self, name: StringIdentifier, other: StringIdentifier) -> bool {
+        if self.get(&other).is_none() {
+            return true;
+        }
+
+        let other_name = self.interner.lookup(&other).to_ascii_lowercase();
+        let mut visited = HashSet::default();
+        let mut queue = vec![name];
+        while let Some(name) = queue.pop() {
+            let lowered_name = self.interner.lookup(&name).to_ascii_lowercase();
+            if lowered_name == other_name {
+                return true;
+            }
+
+            if !visited.insert(lowered_name) {
+                continue;
+            }
+
+            let Some(class_like) = self.get(&name) else {
+                return true;
+            };
+
+            // The interfaces which are implicitly implemented, by enums, and by the classes declaring `__toString`.
+            match other_name.as_str() {
+                "unitenum" if class_like.is_enum() => return true,
+                "backedenum" if class_like.is_enum() && class_like.backing_type.is_some() => return true,
+                "stringable" if self.find_method(class_like, "__tostring").is_some() => return true,
+                _ => {}
+            }
+
+            queue.extend(class_like.inheritance.direct_extended_class.map(|parent| parent.value));
+            queue.extend(class_like.inheritance.direct_implemented_interfaces.iter().map(|interface| interface.value));
+            queue.extend(class_like.inheritance.direct_extended_interfaces.iter().map(|interface| interface.value));
+        }
+
+        false
+    }
+
+    /// Determines whether a parent class, or a used trait, of the class-like entity is not in the codebase.
+    fn has_unknown_ancestors(&self, class_like: &ClassLikeReflection) -> bool {
+        let mut visited = HashSet::default();
+        let mut current = class_like;
+        loop {
+            if current.used_traits.iter().any(|trait_name| self.get(trait_name).is_none()) {
+                return true;
+            }
+
+            let Some(parent_name) = current.inheritance.direct_extended_class else {
+                return false;
+            };
+
+            if !visited.insert(self.interner.lookup(&parent_name.value).to_ascii_lowercase()) {
+                return false;
+            }
+
+            match self.get(&parent_name.value) {
+                Some(parent) => current = parent,
+                None => return true,
+            }
+        }
+    }
+
+    /// Finds the method with the given lowercase name, as declared in, or inherited by, the class-like entity.
+    fn find_method(
+        &self,
+        class_like: &ClassLikeReflection,
+        lowered_name: &str,
+    ) -> Option<(&'a ClassLikeReflection, &'a FunctionLikeReflection)> {
+        class_like.methods.declaring_members.iter().find_map(|(method_name, declaring_class_like)| {
+            if self.interner.lookup(method_name).eq_ignore_ascii_case(lowered_name) {
+                self.get_declared_method(declaring_class_like, method_name)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn get_declared_method(
+        &self,
+        declaring_class_like: &ClassLikeName,
+        method_name: &StringIdentifier,
+    ) -> Option<(&'a ClassLikeReflection, &'a FunctionLikeReflection)> {
+        let declaring_class_like = self.codebase.get_class_like(*declaring_class_like)?;
+        let method = declaring_class_like.get_method(method_name)?;
+
+        Some((declaring_class_like, method))
+    }
+
+    fn get(&self, name: &StringIdentifier) -> Option<&'a ClassLikeReflection> {
+        self.codebase.get_named_class_like(self.interner, name)
+    }
+
+    fn has_attribute(&self, attributes: &[AttributeReflection], name: &str) -> bool {
+        attributes.iter().any(|attribute| {
+            let attribute_name = self.interner.lookup(&attribute.name.value);
+
+            attribute_name.rsplit('\\').next().is_some_and(|short_name| short_name.eq_ignore_ascii_case(name))
+        })
+    }
+
+    fn display(&self, class_like: &ClassLikeReflection) -> String {
+        match class_like.name.inner() {
+            Some(name) => self.interner.lookup(&name.value).to_string(),
+            None => "class@anonymous".to_string(),
+        }
+    }
+
+    fn display_method(&self, class_like: &ClassLikeReflection, method: &FunctionLikeReflection) -> String {
+        match method.name {
+            FunctionLikeName::Method(_, name) => {
+                format!("{}::{}", self.display(class_like), self.interner.lookup(&name.value))
+            }
+            _ => self.display(class_like),
+        }
+    }
+}
+
+fn kind(class_like: &ClassLikeReflection) -> &'static str {
+    match class_like.name {
+        ClassLikeName::Class(_) | ClassLikeName::AnonymousClass(_) => "class",
+        ClassLikeName::Interface(_) => "interface",
+        ClassLikeName::Enum(_) => "enum",
+        ClassLikeName::Trait(_) => "trait",
+    }
+}
+
+fn name_span(class_like: &ClassLikeReflection) -> Span {
+    match class_like.name {
+        ClassLikeName::Class(name)
+        | ClassLikeName::Interface(name)
+        | ClassLikeName::Enum(name)
+        | ClassLikeName::Trait(name) => name.span,
+        ClassLikeName::AnonymousClass(span) => span,
+    }
+}
+
+fn method_name_span(method: &FunctionLikeReflection) -> Span {
+    match method.name {
+        FunctionLikeName::Method(_, Name { span, .. }) => span,
+        _ => method.span,
+    }
+}
+
+/// Returns the rank of the visibility of a method, from `0` for private, to `2` for public.
+fn visibility(method: &FunctionLikeReflection) -> u8 {
+    match method.visibility_reflection {
+        Some(ClassLikeMemberVisibilityReflection::Private { .. }) => 0,
+        Some(ClassLikeMemberVisibilityReflection::Protected { .. }) => 1,
+        Some(ClassLikeMemberVisibilityReflection::Public { .. }) | None => 2,
+    }
+}
+
+fn visibility_span(method: &FunctionLikeReflection) -> Span {
+    match method.visibility_reflection {
+        Some(
+            ClassLikeMemberVisibilityReflection::Private { span }
+            | ClassLikeMemberVisibilityReflection::Protected { span }
+            | ClassLikeMemberVisibilityReflection::Public { span },
+        ) => span,
+        None => method_name_span(method),
+    }
+}
+
+fn is_required(parameter: &FunctionLikeParameterReflection) -> bool {
+    parameter.default.is_none() && !parameter.is_variadic
+}
@@ -1,16 +1,200 @@
+use ahash::HashMap;
+use ahash::HashSet;
+
+use mago_ast::*;
+use mago_interner::StringIdentifier;
 use mago_interner::ThreadedInterner;
 use mago_names::Names;
+use mago_php_version::PHPVersion;
+use mago_reporting::Issue;
 use mago_source::Source;
+use mago_span::HasSpan;
+use mago_span::Span;
+
+use crate::inheritance::ELEMENT_AVAILABLE_ATTRIBUTE;
 
 #[derive(Debug)]
 pub struct Context<'a> {
     pub interner: &'a ThreadedInterner,
     pub source: &'a Source,
     pub names: &'a Names,
+    pub comments: &'a CommentMap,
+    /// The issues found while reflecting the program, such as malformed docblock tags.
+    pub issues: Vec<Issue>,
+    /// The version of PHP for which the stubs are reflected, if any.
+    php_version: Option<PHPVersion>,
+    top_level: HashSet<Span>,
+    /// The namespace of the statements being reflected, and the names of the class-likes it imports, keyed by
+    /// their lowercase alias, which are used to resolve the names written in docblocks.
+    namespace: String,
+    imports: HashMap<String, String>,
 }
 
 impl<'a> Context<'a> {
-    pub fn new(interner: &'a ThreadedInterner, source: &'a Source, names: &'a Names) -> Self {
-        Self { interner, source, names }
+    pub fn new(
+        interner: &'a ThreadedInterner,
+        source: &'a Source,
+        names: &'a Names,
+        program: &'a Program,
+        php_version: Option<PHPVersion>,
+    ) -> Self {
+        let mut top_level = HashSet::default();
+        collect_top_level_spans(program.statements.iter(), &mut top_level);
+
+        Self {
+            interner,
+            source,
+            names,
+            comments: &program.comments,
+            issues: vec![],
+            php_version,
+            top_level,
+            namespace: String::new(),
+            imports: HashMap::default(),
+        }
+    }
+
+    /// Enters the namespace with the given name, which is empty for the global namespace.
+    pub fn enter_namespace(&mut self, name: &str) {
+        self.namespace = name.trim_start_matches('\\').to_owned();
+        self.imports.clear();
+    }
+
+    /// Imports the class-like with the given name, under the given alias, or the last part of its name.
+    pub fn import(&mut self, name: &str, alias: Option<&str>) {
+        let name = name.trim_start_matches('\\');
+        let alias = alias.unwrap_or_else(|| name.rsplit('\\').next().unwrap_or(name));
+
+        self.imports.insert(alias.to_ascii_lowercase(), name.to_owned());
+    }
+
+    /// Resolves the given class-like name, as written in a docblock, to its fully qualified name, using the
+    /// namespace and the imports in effect, e.g. `Foo\Bar` is resolved to `App\Foo\Bar` in the `App` namespace.
+    pub fn resolve_class_like_name(&self, name: &str) -> StringIdentifier {
+        if let Some(name) = name.strip_prefix('\\') {
+            return self.interner.intern(name);
+        }
+
+        let (first, rest) = match name.split_once('\\') {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (name, None),
+        };
+
+        if let Some(imported) = self.imports.get(&first.to_ascii_lowercase()) {
+            return match rest {
+                Some(rest) => self.interner.intern(format!("{}\\{}", imported, rest)),
+                None => self.interner.intern(imported),
+            };
+        }
+
+        if self.namespace.is_empty() {
+            self.interner.intern(name)
+        } else {
+            self.interner.intern(format!("{}\\{}", self.namespace, name))
+        }
+    }
+
+    /// Determines whether the given declaration is executed unconditionally, when the program is loaded.
+    ///
+    /// Declarations nested in a conditional, a loop, or the body of a function-like are not, e.g. a function
+    /// declared within `if (!function_exists('foo')) { ... }`.
+    pub fn is_conditionally_declared(&self, declaration: &impl HasSpan) -> bool {
+        !self.top_level.contains(&declaration.span())
+    }
+
+    /// Determines whether the given function call is a call to the global function with the given lowercase
+    /// name, e.g. `define`, whether it is written as `define(...)`, `\define(...)`, or `DEFINE(...)`.
+    pub fn is_call_to(&self, function_call: &FunctionCall, name: &str) -> bool {
+        let Expression::Identifier(identifier) = function_call.function.as_ref() else {
+            return false;
+        };
+
+        self.interner.lookup(&identifier.value()).trim_start_matches('\\').eq_ignore_ascii_case(name)
+    }
+
+    /// Determines whether the element with the given attributes is available in the version of PHP for which
+    /// the stubs are reflected, as marked by the `PhpStormStubsElementAvailable` attribute, e.g.
+    /// `#[PhpStormStubsElementAvailable(from: '8.0', to: '8.2')]`, where both bounds are inclusive.
+    ///
+    /// Every element is available when no version is targeted.
+    pub fn is_available(&self, attribute_lists: &Sequence<AttributeList>) -> bool {
+        let Some(php_version) = self.php_version else {
+            return true;
+        };
+
+        attribute_lists.iter().flat_map(|attribute_list| attribute_list.attributes.iter()).all(|attribute| {
+            let name = self.interner.lookup(self.names.get(&attribute.name));
+            if !name
+                .rsplit('\\')
+                .next()
+                .is_some_and(|short_name| short_name.eq_ignore_ascii_case(ELEMENT_AVAILABLE_ATTRIBUTE))
+            {
+                return true;
+            }
+
+            let Some(argument_list) = &attribute.arguments else {
+                return true;
+            };
+
+            let mut from = None;
+            let mut to = None;
+            for (position, argument) in argument_list.arguments.iter().enumerate() {
+                match argument {
+                    Argument::Positional(argument) if position == 0 => from = self.version(&argument.value),
+                    Argument::Positional(argument) if position == 1 => to = self.version(&argument.value),
+                    Argument::Named(argument) => match self.interner.lookup(&argument.name.value) {
+                        "from" => from = self.version(&argument.value),
+                        "to" => to = self.version(&argument.value),
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+
+            from.is_none_or(|from| php_version >= from) && to.is_none_or(|to| php_version <= to)
+        })
+    }
+
+    /// Returns the version of PHP written as a string literal, such as `'8.1'`, ignoring the patch version.
+    fn version(&self, expression: &Expression) -> Option<PHPVersion> {
+        let Expression::Literal(Literal::String(string)) = expression else {
+            return None;
+        };
+
+        let value = self.interner.lookup(&string.value).trim_matches(|c| c == '\'' || c == '"');
+        let mut parts = value.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+
+        Some(PHPVersion::new(major, minor))
+    }
+}
+
+/// Collects the spans of the statements at the top level of the program, and those of the namespaces,
+/// `declare` statements, and blocks within it, as they are all executed unconditionally.
+fn collect_top_level_spans<'ast>(statements: impl Iterator<Item = &'ast Statement>, spans: &mut HashSet<Span>) {
+    for statement in statements {
+        spans.insert(statement.span());
+
+        match statement {
+            Statement::Namespace(namespace) => {
+                collect_top_level_spans(namespace.statements().iter(), spans);
+            }
+            Statement::Block(block) => {
+                collect_top_level_spans(block.statements.iter(), spans);
+            }
+            Statement::Declare(declare) => match &declare.body {
+                DeclareBody::Statement(statement) => {
+                    collect_top_level_spans(std::iter::once(statement), spans);
+                }
+                DeclareBody::ColonDelimited(body) => {
+                    collect_top_level_spans(body.statements.iter(), spans);
+                }
+            },
+            Statement::Expression(statement) => {
+                spans.insert(statement.expression.span());
+            }
+            _ => {}
+        }
     }
 }
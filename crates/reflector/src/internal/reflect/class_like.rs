@@ -1,6 +1,8 @@
 use ahash::HashMap;
 
 use mago_ast::*;
+use mago_interner::StringIdentifier;
+use mago_reflection::class_like::alias::ClassLikeAliasReflection;
 use mago_reflection::class_like::constant::ClassLikeConstantReflection;
 use mago_reflection::class_like::enum_case::EnumCaseReflection;
 use mago_reflection::class_like::inheritance::InheritanceReflection;
@@ -8,6 +10,8 @@ use mago_reflection::class_like::member::ClassLikeMemberVisibilityReflection;
 use mago_reflection::class_like::member::MemeberCollection;
 use mago_reflection::class_like::property::PropertyDefaultValueReflection;
 use mago_reflection::class_like::property::PropertyReflection;
+use mago_reflection::class_like::trait_adaptation::TraitMethodAliasReflection;
+use mago_reflection::class_like::trait_adaptation::TraitMethodPrecedenceReflection;
 use mago_reflection::class_like::ClassLikeReflection;
 use mago_reflection::function_like::FunctionLikeReflection;
 use mago_reflection::identifier::ClassLikeMemberName;
@@ -18,6 +22,7 @@ use mago_span::*;
 
 use crate::internal::context::Context;
 use crate::internal::reflect::attribute::reflect_attributes;
+use crate::internal::reflect::docblock::reflect_docblock;
 
 use super::function_like::reflect_function_like_parameter_list;
 use super::function_like::reflect_function_like_return_type_hint;
@@ -25,9 +30,14 @@ use super::r#type::maybe_reflect_hint;
 use super::r#type::reflect_hint;
 
 pub fn reflect_class<'ast>(class: &'ast Class, context: &'ast mut Context<'_>) -> ClassLikeReflection {
+    let name = ClassLikeName::Class(Name::new(*context.names.get(&class.name), class.name.span));
+    let defined_in = context.interner.intern(name.get_key(context.interner));
+    let (docblock, templates) = reflect_docblock(class, context, None, defined_in);
+
     let mut reflection = ClassLikeReflection {
         attribute_reflections: reflect_attributes(&class.attributes, context),
-        name: ClassLikeName::Class(Name::new(*context.names.get(&class.name), class.name.span)),
+        name,
+        templates,
         inheritance: {
             let mut reflection = InheritanceReflection::default();
             if let Some(extends) = &class.extends {
@@ -60,13 +70,17 @@ pub fn reflect_class<'ast>(class: &'ast Class, context: &'ast mut Context<'_>) -
         is_readonly: class.modifiers.contains_readonly(),
         is_abstract: class.modifiers.contains_abstract(),
         span: class.span(),
+        is_conditionally_declared: context.is_conditionally_declared(class),
         constants: Default::default(),
         cases: MemeberCollection::empty(),
         properties: MemeberCollection::empty(),
         methods: MemeberCollection::empty(),
         used_traits: Default::default(),
+        trait_precedences: Default::default(),
+        trait_aliases: Default::default(),
         is_populated: false,
         is_anonymous: false,
+        docblock,
     };
 
     reflect_class_like_members(&mut reflection, &class.members, context);
@@ -78,9 +92,14 @@ pub fn reflect_anonymous_class<'ast>(
     class: &'ast AnonymousClass,
     context: &'ast mut Context<'_>,
 ) -> ClassLikeReflection {
+    let name = ClassLikeName::AnonymousClass(class.span());
+    let defined_in = context.interner.intern(name.get_key(context.interner));
+    let (docblock, templates) = reflect_docblock(class, context, None, defined_in);
+
     let mut reflection = ClassLikeReflection {
         attribute_reflections: reflect_attributes(&class.attributes, context),
-        name: ClassLikeName::AnonymousClass(class.span()),
+        name,
+        templates,
         inheritance: {
             let mut reflection = InheritanceReflection::default();
             if let Some(extends) = &class.extends {
@@ -112,13 +131,17 @@ pub fn reflect_anonymous_class<'ast>(
         is_readonly: class.modifiers.contains_readonly(),
         is_abstract: class.modifiers.contains_abstract(),
         span: class.span(),
+        is_conditionally_declared: false,
         constants: Default::default(),
         cases: MemeberCollection::empty(),
         properties: MemeberCollection::empty(),
         methods: MemeberCollection::empty(),
         used_traits: Default::default(),
+        trait_precedences: Default::default(),
+        trait_aliases: Default::default(),
         is_populated: false,
         is_anonymous: true,
+        docblock,
     };
 
     reflect_class_like_members(&mut reflection, &class.members, context);
@@ -127,9 +150,14 @@ pub fn reflect_anonymous_class<'ast>(
 }
 
 pub fn reflect_interface<'ast>(interface: &'ast Interface, context: &'ast mut Context<'_>) -> ClassLikeReflection {
+    let name = ClassLikeName::Interface(Name::new(*context.names.get(&interface.name), interface.name.span()));
+    let defined_in = context.interner.intern(name.get_key(context.interner));
+    let (docblock, templates) = reflect_docblock(interface, context, None, defined_in);
+
     let mut reflection = ClassLikeReflection {
         attribute_reflections: reflect_attributes(&interface.attributes, context),
-        name: ClassLikeName::Interface(Name::new(*context.names.get(&interface.name), interface.name.span())),
+        name,
+        templates,
         inheritance: {
             let mut reflection = InheritanceReflection::default();
 
@@ -151,13 +179,17 @@ pub fn reflect_interface<'ast>(interface: &'ast Interface, context: &'ast mut Co
         is_readonly: false,
         is_abstract: true,
         span: interface.span(),
+        is_conditionally_declared: context.is_conditionally_declared(interface),
         constants: Default::default(),
         cases: MemeberCollection::empty(),
         properties: MemeberCollection::empty(),
         methods: MemeberCollection::empty(),
         used_traits: Default::default(),
+        trait_precedences: Default::default(),
+        trait_aliases: Default::default(),
         is_populated: false,
         is_anonymous: false,
+        docblock,
     };
 
     reflect_class_like_members(&mut reflection, &interface.members, context);
@@ -166,22 +198,31 @@ pub fn reflect_interface<'ast>(interface: &'ast Interface, context: &'ast mut Co
 }
 
 pub fn reflect_trait<'ast>(r#trait: &'ast Trait, context: &'ast mut Context<'_>) -> ClassLikeReflection {
+    let name = ClassLikeName::Trait(Name::new(*context.names.get(&r#trait.name), r#trait.name.span()));
+    let defined_in = context.interner.intern(name.get_key(context.interner));
+    let (docblock, templates) = reflect_docblock(r#trait, context, None, defined_in);
+
     let mut reflection = ClassLikeReflection {
         attribute_reflections: reflect_attributes(&r#trait.attributes, context),
-        name: ClassLikeName::Trait(Name::new(*context.names.get(&r#trait.name), r#trait.name.span())),
+        name,
+        templates,
         inheritance: InheritanceReflection::default(),
         backing_type: None,
         is_final: false,
         is_readonly: false,
         is_abstract: true,
         span: r#trait.span(),
+        is_conditionally_declared: context.is_conditionally_declared(r#trait),
         constants: Default::default(),
         cases: MemeberCollection::empty(),
         properties: MemeberCollection::empty(),
         methods: MemeberCollection::empty(),
         used_traits: Default::default(),
+        trait_precedences: Default::default(),
+        trait_aliases: Default::default(),
         is_populated: false,
         is_anonymous: false,
+        docblock,
     };
 
     reflect_class_like_members(&mut reflection, &r#trait.members, context);
@@ -190,9 +231,14 @@ pub fn reflect_trait<'ast>(r#trait: &'ast Trait, context: &'ast mut Context<'_>)
 }
 
 pub fn reflect_enum<'ast>(r#enum: &'ast Enum, context: &'ast mut Context<'_>) -> ClassLikeReflection {
+    let name = ClassLikeName::Enum(Name::new(*context.names.get(&r#enum.name), r#enum.name.span()));
+    let defined_in = context.interner.intern(name.get_key(context.interner));
+    let (docblock, templates) = reflect_docblock(r#enum, context, None, defined_in);
+
     let mut reflection = ClassLikeReflection {
         attribute_reflections: reflect_attributes(&r#enum.attributes, context),
-        name: ClassLikeName::Enum(Name::new(*context.names.get(&r#enum.name), r#enum.name.span())),
+        name,
+        templates,
         inheritance: {
             let mut reflection = InheritanceReflection::default();
 
@@ -217,13 +263,17 @@ pub fn reflect_enum<'ast>(r#enum: &'ast Enum, context: &'ast mut Context<'_>) ->
         is_readonly: true,
         is_abstract: false,
         span: r#enum.span(),
+        is_conditionally_declared: context.is_conditionally_declared(r#enum),
         constants: Default::default(),
         cases: MemeberCollection::empty(),
         properties: MemeberCollection::empty(),
         methods: MemeberCollection::empty(),
         used_traits: Default::default(),
+        trait_precedences: Default::default(),
+        trait_aliases: Default::default(),
         is_populated: false,
         is_anonymous: false,
+        docblock,
     };
 
     reflect_class_like_members(&mut reflection, &r#enum.members, context);
@@ -231,12 +281,85 @@ pub fn reflect_enum<'ast>(r#enum: &'ast Enum, context: &'ast mut Context<'_>) ->
     reflection
 }
 
+pub fn reflect_class_like_alias(
+    class_alias: &FunctionCall,
+    context: &mut Context<'_>,
+) -> Option<ClassLikeAliasReflection> {
+    if !context.is_call_to(class_alias, "class_alias") {
+        return None;
+    }
+
+    let arguments = class_alias.arguments.arguments.as_slice();
+    if arguments.len() != 2 && arguments.len() != 3 {
+        return None;
+    }
+
+    let (original, _) = reflect_class_like_alias_name(arguments[0].value(), context)?;
+    let (alias, alias_span) = reflect_class_like_alias_name(arguments[1].value(), context)?;
+
+    Some(ClassLikeAliasReflection {
+        name: Name::new(alias, alias_span),
+        original,
+        is_conditionally_declared: context.is_conditionally_declared(class_alias),
+        span: class_alias.span(),
+    })
+}
+
+/// Reflects a name passed to `class_alias()`, which is either a string literal, e.g. `'App\Foo'`,
+/// or a class name constant, e.g. `Foo::class`.
+fn reflect_class_like_alias_name(
+    expression: &Expression,
+    context: &mut Context<'_>,
+) -> Option<(StringIdentifier, Span)> {
+    match expression {
+        Expression::Literal(Literal::String(string)) => {
+            let value = context.interner.lookup(&string.value);
+            let name = value[1..value.len() - 1].trim_start_matches('\\');
+            if name.is_empty() {
+                return None;
+            }
+
+            Some((context.interner.intern(name), string.span()))
+        }
+        Expression::Access(access) => {
+            let Access::ClassConstant(ClassConstantAccess {
+                class: Expression::Identifier(identifier),
+                constant: ClassLikeConstantSelector::Identifier(constant),
+                ..
+            }) = access.as_ref()
+            else {
+                return None;
+            };
+
+            if !context.interner.lookup(&constant.value).eq_ignore_ascii_case("class") {
+                return None;
+            }
+
+            Some((*context.names.get(identifier), expression.span()))
+        }
+        _ => None,
+    }
+}
+
 fn reflect_class_like_members<'ast>(
     reflection: &mut ClassLikeReflection,
     members: &'ast Sequence<ClassLikeMember>,
     context: &'ast mut Context<'_>,
 ) {
     for member in members.iter() {
+        let attributes = match &member {
+            ClassLikeMember::TraitUse(_) => None,
+            ClassLikeMember::Constant(constant) => Some(&constant.attributes),
+            ClassLikeMember::EnumCase(enum_case) => Some(&enum_case.attributes),
+            ClassLikeMember::Method(method) => Some(&method.attributes),
+            ClassLikeMember::Property(Property::Plain(property)) => Some(&property.attributes),
+            ClassLikeMember::Property(Property::Hooked(property)) => Some(&property.attributes),
+        };
+
+        if attributes.is_some_and(|attributes| !context.is_available(attributes)) {
+            continue;
+        }
+
         match &member {
             ClassLikeMember::TraitUse(trait_use) => {
                 for trait_name in trait_use.trait_names.iter() {
@@ -244,6 +367,12 @@ fn reflect_class_like_members<'ast>(
 
                     reflection.used_traits.insert(context.interner.lowered(&name.value));
                 }
+
+                if let TraitUseSpecification::Concrete(specification) = &trait_use.specification {
+                    for adaptation in specification.adaptations.iter() {
+                        reflect_trait_use_adaptation(reflection, adaptation, context);
+                    }
+                }
             }
             ClassLikeMember::Constant(class_like_constant) => {
                 let const_refs = reflect_class_like_constant(reflection, class_like_constant, context);
@@ -285,6 +414,48 @@ fn reflect_class_like_members<'ast>(
     }
 }
 
+fn reflect_trait_use_adaptation(
+    reflection: &mut ClassLikeReflection,
+    adaptation: &TraitUseAdaptation,
+    context: &mut Context<'_>,
+) {
+    let trait_name = |identifier: &Identifier| context.interner.lowered(context.names.get(identifier));
+
+    match adaptation {
+        TraitUseAdaptation::Precedence(precedence) => {
+            reflection.trait_precedences.push(TraitMethodPrecedenceReflection {
+                trait_name: trait_name(&precedence.method_reference.trait_name),
+                method_name: precedence.method_reference.method_name.value,
+                excluded_traits: precedence.trait_names.iter().map(trait_name).collect(),
+                span: precedence.span(),
+            });
+        }
+        TraitUseAdaptation::Alias(alias) => {
+            let (trait_name, method_name) = match &alias.method_reference {
+                TraitUseMethodReference::Identifier(identifier) => (None, identifier.value),
+                TraitUseMethodReference::Absolute(reference) => {
+                    (Some(trait_name(&reference.trait_name)), reference.method_name.value)
+                }
+            };
+
+            let visibility = match &alias.visibility {
+                Some(Modifier::Public(m)) => Some(ClassLikeMemberVisibilityReflection::Public { span: m.span() }),
+                Some(Modifier::Protected(m)) => Some(ClassLikeMemberVisibilityReflection::Protected { span: m.span() }),
+                Some(Modifier::Private(m)) => Some(ClassLikeMemberVisibilityReflection::Private { span: m.span() }),
+                _ => None,
+            };
+
+            reflection.trait_aliases.push(TraitMethodAliasReflection {
+                trait_name,
+                method_name,
+                alias: alias.alias.as_ref().map(|alias| Name::new(alias.value, alias.span)),
+                visibility,
+                span: alias.span(),
+            });
+        }
+    }
+}
+
 fn reflect_class_like_constant<'ast>(
     class_like: &mut ClassLikeReflection,
     constant: &'ast ClassLikeConstant,
@@ -377,14 +548,17 @@ fn reflect_class_like_method<'ast>(
         method.modifiers.get_private().map(|m| ClassLikeMemberVisibilityReflection::Private { span: m.span() })
     };
 
+    let function_like_name = FunctionLikeName::Method(class_like.name, name);
+    let defined_in = context.interner.intern(function_like_name.get_key(context.interner));
+    let (docblock, templates) = reflect_docblock(method, context, Some(class_like), defined_in);
+
     (
         name,
         FunctionLikeReflection {
             attribute_reflections: reflect_attributes(&method.attributes, context),
             visibility_reflection,
-            name: FunctionLikeName::Method(class_like.name, name),
-            // TODO: parse docblock to get the template list
-            templates: vec![],
+            name: function_like_name,
+            templates,
             parameters: reflect_function_like_parameter_list(&method.parameters, context, Some(class_like)),
             return_type_reflection: reflect_function_like_return_type_hint(
                 &method.return_type_hint,
@@ -401,7 +575,10 @@ fn reflect_class_like_method<'ast>(
             is_final: class_like.is_final || method.modifiers.contains_final(),
             is_abstract,
             is_overriding: false,
+            scope: Some(class_like.name),
+            docblock,
             span: method.span(),
+            is_conditionally_declared: false,
             is_populated: false,
         },
     )
@@ -428,12 +605,14 @@ fn reflect_class_like_property<'ast>(
                     .map(|m| ClassLikeMemberVisibilityReflection::Private { span: m.span() })
             };
 
-            // TODO(azjezz): take `(set)` modifiers into account.
-            let write_visibility_reflection = read_visibility_reflection;
+            let write_visibility_reflection =
+                reflect_write_visibility(&plain_property.modifiers).or(read_visibility_reflection);
             let type_reflection = maybe_reflect_hint(&plain_property.hint, context, Some(class_like));
             let is_readonly = class_like.is_readonly || plain_property.modifiers.contains_readonly();
             let is_final = class_like.is_final || plain_property.modifiers.contains_final();
             let is_static = plain_property.modifiers.contains_static();
+            let defined_in = context.interner.intern(class_like.name.get_key(context.interner));
+            let (docblock, _) = reflect_docblock(plain_property, context, Some(class_like), defined_in);
 
             for item in plain_property.items.iter() {
                 let (identifier, default_value_reflection) = match &item {
@@ -473,6 +652,8 @@ fn reflect_class_like_property<'ast>(
                     is_final,
                     is_promoted: false,
                     is_static,
+                    is_virtual: false,
+                    docblock: docblock.clone(),
                     item_span: item.span(),
                     definition_span: plain_property.span(),
                     is_overriding: false,
@@ -491,8 +672,10 @@ fn reflect_class_like_property<'ast>(
                     .map(|m| ClassLikeMemberVisibilityReflection::Private { span: m.span() })
             };
 
-            // TODO(azjezz): take `(set)` modifiers into account.
-            let write_visibility_reflection = read_visibility_reflection;
+            let write_visibility_reflection =
+                reflect_write_visibility(&hooked_property.modifiers).or(read_visibility_reflection);
+            let defined_in = context.interner.intern(class_like.name.get_key(context.interner));
+            let (docblock, _) = reflect_docblock(hooked_property, context, Some(class_like), defined_in);
 
             let (name, default_value_reflection) = match &hooked_property.item {
                 PropertyItem::Abstract(item) => (
@@ -547,13 +730,15 @@ fn reflect_class_like_property<'ast>(
                             },
                         };
 
+                        let defined_in = context.interner.intern(function_like_name.get_key(context.interner));
+                        let (docblock, templates) = reflect_docblock(hook, context, Some(class_like), defined_in);
+
                         map.insert(
                             hook_name.value,
                             FunctionLikeReflection {
                                 attribute_reflections: reflect_attributes(&hook.attributes, context),
                                 name: function_like_name,
-                                // TODO: parse docblock to get the template list
-                                templates: vec![],
+                                templates,
                                 parameters: match hook.parameters.as_ref() {
                                     Some(parameters) => {
                                         reflect_function_like_parameter_list(parameters, context, Some(class_like))
@@ -570,7 +755,10 @@ fn reflect_class_like_property<'ast>(
                                 is_pure: false,
                                 is_abstract: false,
                                 is_overriding: false,
+                                scope: Some(class_like.name),
+                                docblock,
                                 span: hook.span(),
+                                is_conditionally_declared: false,
                                 visibility_reflection: None,
                                 is_populated: false,
                             },
@@ -583,6 +771,8 @@ fn reflect_class_like_property<'ast>(
                 is_final: class_like.is_final || hooked_property.modifiers.contains_final(),
                 is_promoted: false,
                 is_static: false,
+                is_virtual: is_virtual_property(hooked_property, context),
+                docblock,
                 item_span: hooked_property.item.span(),
                 definition_span: hooked_property.span(),
                 is_overriding: false,
@@ -592,3 +782,52 @@ fn reflect_class_like_property<'ast>(
 
     reflections
 }
+
+/// Returns whether the given hooked property is virtual, i.e. has no backing value.
+///
+/// A hooked property is backed if one of its hooks is abstract, if it has a `set` hook with an expression
+/// body, whose result is assigned to the backing value, or if one of its hooks accesses the property itself.
+fn is_virtual_property(hooked_property: &HookedProperty, context: &Context<'_>) -> bool {
+    let property_name = context.interner.lookup(&hooked_property.item.variable().name);
+    let property_name = property_name.trim_start_matches('$');
+
+    hooked_property.hooks.hooks.iter().all(|hook| {
+        match &hook.body {
+            PropertyHookBody::Abstract(_) => return false,
+            PropertyHookBody::Concrete(PropertyHookConcreteBody::Expression(_))
+                if context.interner.lookup(&hook.name.value).eq_ignore_ascii_case("set") =>
+            {
+                return false;
+            }
+            PropertyHookBody::Concrete(_) => {}
+        }
+
+        let accesses = Node::PropertyHook(hook).filter_map(|node| {
+            let Node::PropertyAccess(access) = node else {
+                return None;
+            };
+
+            let (Expression::Variable(Variable::Direct(object)), ClassLikeMemberSelector::Identifier(property)) =
+                (&access.object, &access.property)
+            else {
+                return None;
+            };
+
+            (context.interner.lookup(&object.name) == "$this"
+                && context.interner.lookup(&property.value) == property_name)
+                .then_some(())
+        });
+
+        accesses.is_empty()
+    })
+}
+
+/// Reflects the write visibility of a property, given by a `public(set)`, `protected(set)`, or `private(set)` modifier.
+fn reflect_write_visibility(modifiers: &Sequence<Modifier>) -> Option<ClassLikeMemberVisibilityReflection> {
+    match modifiers.get_first_write_visibility()? {
+        Modifier::PublicSet(k) => Some(ClassLikeMemberVisibilityReflection::Public { span: k.span() }),
+        Modifier::ProtectedSet(k) => Some(ClassLikeMemberVisibilityReflection::Protected { span: k.span() }),
+        Modifier::PrivateSet(k) => Some(ClassLikeMemberVisibilityReflection::Private { span: k.span() }),
+        _ => None,
+    }
+}
@@ -15,6 +15,7 @@ pub fn reflect_constant(constant: &Constant, context: &mut Context<'_>) -> Vec<C
             type_reflection: mago_typing::infere(context.interner, context.source, context.names, &item.value),
             item_span: item.span(),
             definition_span: constant.span(),
+            is_conditionally_declared: context.is_conditionally_declared(constant),
             is_populated: false,
         });
     }
@@ -23,17 +24,13 @@ pub fn reflect_constant(constant: &Constant, context: &mut Context<'_>) -> Vec<C
 }
 
 pub fn reflect_defined_constant(define: &FunctionCall, context: &mut Context<'_>) -> Option<ConstantReflection> {
-    let Expression::Identifier(identifier) = define.function.as_ref() else {
-        return None;
-    };
-
-    let function_name = context.interner.lookup(&identifier.value());
-    if function_name != "define" {
+    if !context.is_call_to(define, "define") {
         return None;
     }
 
+    // The third argument, `case_insensitive`, is deprecated, and has no effect since PHP 8.0.
     let arguments = define.arguments.arguments.as_slice();
-    if arguments.len() != 2 {
+    if arguments.len() != 2 && arguments.len() != 3 {
         return None;
     }
 
@@ -43,14 +40,14 @@ pub fn reflect_defined_constant(define: &FunctionCall, context: &mut Context<'_>
 
     let name_span = name_string.span();
     let name_string = context.interner.lookup(&name_string.value);
-    let name = name_string[1..name_string.len() - 1].to_owned();
-    let name = context.interner.intern(name);
+    let name = context.interner.intern(name_string[1..name_string.len() - 1].trim_start_matches('\\'));
 
     Some(ConstantReflection {
         name: Name::new(name, name_span),
         type_reflection: mago_typing::infere(context.interner, context.source, context.names, arguments[1].value()),
         item_span: define.span(),
         definition_span: define.span(),
+        is_conditionally_declared: context.is_conditionally_declared(define),
         is_populated: false,
     })
 }
@@ -0,0 +1,387 @@
+use ordered_float::OrderedFloat;
+
+use mago_docblock::error::TagError;
+use mago_docblock::r#type::CallableType;
+use mago_docblock::r#type::LiteralType;
+use mago_docblock::r#type::ShapeKey;
+use mago_docblock::r#type::ShapeType;
+use mago_docblock::r#type::Type;
+use mago_docblock::tag::Documentation;
+use mago_interner::StringIdentifier;
+use mago_reflection::class_like::ClassLikeReflection;
+use mago_reflection::docblock::DeprecationReflection;
+use mago_reflection::docblock::DocblockParameterReflection;
+use mago_reflection::docblock::DocblockReflection;
+use mago_reflection::identifier::ClassLikeName;
+use mago_reflection::identifier::Name;
+use mago_reflection::r#type::kind::*;
+use mago_reflection::r#type::TypeReflection;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::*;
+
+use crate::internal::context::Context;
+
+/// Reflects the docblock preceding the given declaration, if any, along with the templates declared by its
+/// `@template` tags.
+///
+/// The tags which cannot be parsed are left out, and reported as issues. The docblocks which cannot be parsed
+/// at all are ignored, as they are reported by the `comment/docblock-syntax` rule.
+///
+/// # Arguments
+///
+/// - `declaration`: The declaration, whose span starts right after its docblock.
+/// - `context`: The reflection context.
+/// - `scope`: The class-like the declaration is a member of, if any, whose templates are in scope.
+/// - `defined_in`: The name of the declaration, which defines the templates declared by its docblock.
+pub fn reflect_docblock(
+    declaration: &impl HasSpan,
+    context: &mut Context<'_>,
+    scope: Option<&ClassLikeReflection>,
+    defined_in: StringIdentifier,
+) -> (Option<DocblockReflection>, Vec<Template>) {
+    let Some(trivia) = context.comments.docblock(declaration.span()) else {
+        return (None, vec![]);
+    };
+
+    let Ok(document) = mago_docblock::parse_trivia(context.interner, trivia) else {
+        return (None, vec![]);
+    };
+
+    let span = trivia.span;
+    let (documentation, errors) = Documentation::from_document(context.interner, &document);
+    for error in errors {
+        context.issues.push(tag_error_issue(&error));
+    }
+
+    let context: &Context<'_> = context;
+    let mut builder = TypeBuilder { context, scope, span, templates: vec![] };
+    if let Some(scope) = scope {
+        let scope_name = context.interner.intern(scope.name.get_key(context.interner));
+        for template in scope.templates.iter() {
+            builder.add_template(template, scope_name);
+        }
+    }
+
+    // The templates are declared in order, so that their constraints may refer to the previous ones.
+    let mut templates = vec![];
+    for tag in documentation.templates.iter() {
+        let constraints: Vec<_> = tag.constraint.iter().map(|constraint| builder.build(constraint)).collect();
+        let template = Template::new(tag.name, constraints);
+
+        builder.add_template(&template, defined_in);
+        templates.push(template);
+    }
+
+    let docblock = DocblockReflection {
+        parameters: documentation
+            .params
+            .iter()
+            .map(|param| DocblockParameterReflection {
+                name: param.variable,
+                type_reflection: builder.reflect(&param.r#type, param.span),
+                is_variadic: param.is_variadic,
+                span: param.span,
+            })
+            .collect(),
+        return_type_reflection: documentation.r#return.as_ref().map(|tag| builder.reflect(&tag.r#type, tag.span)),
+        type_reflection: documentation.vars.first().map(|tag| builder.reflect(&tag.r#type, tag.span)),
+        deprecation: documentation.deprecated.map(|tag| DeprecationReflection {
+            message: Some(tag.description).filter(|message| !context.interner.lookup(message).trim().is_empty()),
+            span: tag.span,
+        }),
+        internal: documentation.internal,
+        span,
+    };
+
+    (Some(docblock), templates)
+}
+
+fn tag_error_issue(error: &TagError) -> Issue {
+    Issue::help(error.to_string())
+        .with_code("reflection/invalid-docblock-tag")
+        .with_annotation(Annotation::primary(error.span()).with_message("This tag is ignored."))
+        .with_note(error.note())
+        .with_help(error.help())
+}
+
+/// Builds the type kinds of the types written in a docblock.
+struct TypeBuilder<'c, 'a> {
+    context: &'c Context<'a>,
+    scope: Option<&'c ClassLikeReflection>,
+    /// The span of the docblock.
+    span: Span,
+    /// The templates in scope, along with the type they are bound to, and the name of their declaration.
+    templates: Vec<(StringIdentifier, TypeKind, StringIdentifier)>,
+}
+
+impl TypeBuilder<'_, '_> {
+    fn add_template(&mut self, template: &Template, defined_in: StringIdentifier) {
+        let of = template.get_constraints().first().cloned().unwrap_or_else(|| mixed_kind(false));
+
+        self.templates.push((*template.get_name(), of, defined_in));
+    }
+
+    fn reflect(&self, r#type: &Type, span: Span) -> TypeReflection {
+        TypeReflection { kind: self.build(r#type), inferred: false, span }
+    }
+
+    fn build(&self, r#type: &Type) -> TypeKind {
+        match r#type {
+            Type::Reference(reference) => self.build_reference(reference.name, &reference.arguments),
+            Type::Nullable(inner) => match self.build(inner) {
+                TypeKind::Union { mut kinds } => {
+                    kinds.insert(0, null_kind());
+
+                    TypeKind::Union { kinds }
+                }
+                kind => union_kind(vec![null_kind(), kind]),
+            },
+            Type::Union(types) => union_kind(
+                types
+                    .iter()
+                    .flat_map(|r#type| match self.build(r#type) {
+                        TypeKind::Union { kinds } => kinds,
+                        kind => vec![kind],
+                    })
+                    .collect(),
+            ),
+            Type::Intersection(types) => intersection_kind(types.iter().map(|r#type| self.build(r#type)).collect()),
+            Type::Array(value) => array_kind(array_key_kind(), self.build(value), None),
+            Type::Shape(shape) => self.build_shape(shape),
+            Type::Callable(callable) => self.build_callable(callable),
+            Type::Literal(LiteralType::String(value)) => {
+                mago_typing::literal_string_kind(self.context.interner, *value)
+            }
+            Type::Literal(LiteralType::Integer(value)) => value_integer_kind(*value),
+            Type::Literal(LiteralType::Float(value)) => {
+                let value = self.context.interner.lookup(value).replace('_', "");
+
+                value_float_kind(OrderedFloat(value.parse().unwrap_or_default()))
+            }
+            Type::ClassConstant(constant) => {
+                let class = self.context.interner.lookup(&constant.class);
+                if self.context.interner.lookup(&constant.constant).eq_ignore_ascii_case("class") {
+                    return TypeKind::Scalar(ScalarTypeKind::ClassString(Some(self.class_like_name(class))));
+                }
+
+                // The kind of the class-like is not known from the docblock, so it is assumed to be a class.
+                let name = Name::new(self.class_like_name(class), self.span);
+
+                TypeKind::Value(ValueTypeKind::ClassLikeConstant {
+                    class_like: ClassLikeName::Class(name),
+                    constant: constant.constant,
+                })
+            }
+            Type::Variable(name) => {
+                if self.context.interner.lookup(name) == "$this" {
+                    return static_kind(self.scope_name());
+                }
+
+                variable_kind(*name)
+            }
+            Type::Conditional(conditional) => {
+                let condition = self.build(&conditional.target);
+                let (then, otherwise) = if conditional.is_negated {
+                    (&conditional.otherwise, &conditional.then)
+                } else {
+                    (&conditional.then, &conditional.otherwise)
+                };
+
+                conditional_kind(self.build(&conditional.subject), condition, self.build(then), self.build(otherwise))
+            }
+        }
+    }
+
+    fn build_reference(&self, name: StringIdentifier, arguments: &[Type]) -> TypeKind {
+        let name = self.context.interner.lookup(&name);
+        let argument = |index: usize| arguments.get(index).map(|argument| self.build(argument));
+        let key_and_value = || match arguments {
+            [key, value, ..] => (self.build(key), self.build(value)),
+            [value] => (array_key_kind(), self.build(value)),
+            [] => (array_key_kind(), mixed_kind(true)),
+        };
+
+        if let Some((_, of, defined_in)) = self
+            .templates
+            .iter()
+            .rev()
+            .find(|(template, _, _)| !name.contains('\\') && self.context.interner.lookup(template) == name)
+        {
+            return TypeKind::GenericParameter {
+                name: self.context.interner.intern(name),
+                of: Box::new(of.clone()),
+                defined_in: *defined_in,
+            };
+        }
+
+        match name.to_ascii_lowercase().as_str() {
+            "int" | "integer" => match arguments {
+                [min, max] => {
+                    let bound = |argument: &Type| match argument {
+                        Type::Literal(LiteralType::Integer(value)) => Some(*value as isize),
+                        _ => None,
+                    };
+
+                    TypeKind::Scalar(ScalarTypeKind::Integer { min: bound(min), max: bound(max) })
+                }
+                _ => integer_kind(),
+            },
+            "positive-int" => positive_integer_kind(),
+            "negative-int" => negative_integer_kind(),
+            "non-negative-int" => non_negative_integer_kind(),
+            "non-positive-int" => non_positive_integer_kind(),
+            "literal-int" => TypeKind::Scalar(ScalarTypeKind::LiteralInt),
+            "int-mask" => TypeKind::Scalar(ScalarTypeKind::IntegerMask(
+                arguments
+                    .iter()
+                    .filter_map(|argument| match argument {
+                        Type::Literal(LiteralType::Integer(value)) => Some(*value as isize),
+                        _ => None,
+                    })
+                    .collect(),
+            )),
+            "float" | "double" => float_kind(),
+            "string" | "lowercase-string" => string_kind(),
+            "non-empty-string" | "non-empty-lowercase-string" | "non-falsy-string" | "truthy-string" => {
+                non_empty_string_kind()
+            }
+            "numeric-string" => TypeKind::Scalar(ScalarTypeKind::NumericString),
+            "literal-string" | "non-empty-literal-string" => TypeKind::Scalar(ScalarTypeKind::LiteralString),
+            "callable-string" => TypeKind::Scalar(ScalarTypeKind::CallableString),
+            "class-string" | "interface-string" => {
+                TypeKind::Scalar(ScalarTypeKind::ClassString(arguments.first().and_then(|argument| match argument {
+                    Type::Reference(reference) => {
+                        Some(self.class_like_name(self.context.interner.lookup(&reference.name)))
+                    }
+                    _ => None,
+                })))
+            }
+            "trait-string" => TypeKind::Scalar(ScalarTypeKind::TraitString),
+            "enum-string" => TypeKind::Scalar(ScalarTypeKind::EnumString),
+            "bool" | "boolean" => bool_kind(),
+            "true" => true_kind(),
+            "false" => false_kind(),
+            "null" => null_kind(),
+            "array-key" => array_key_kind(),
+            "numeric" => TypeKind::Scalar(ScalarTypeKind::Numeric),
+            "scalar" => TypeKind::Scalar(ScalarTypeKind::Scalar),
+            "array" | "associative-array" => {
+                let (key, value) = key_and_value();
+
+                array_kind(key, value, None)
+            }
+            "non-empty-array" => {
+                let (key, value) = key_and_value();
+
+                non_empty_array_kind(key, value, None)
+            }
+            "list" => list_kind(argument(0).unwrap_or_else(|| mixed_kind(true)), None),
+            "non-empty-list" => non_empty_list_kind(argument(0).unwrap_or_else(|| mixed_kind(true)), None),
+            "callable-array" => TypeKind::Array(ArrayTypeKind::CallableArray),
+            "iterable" => {
+                let (key, value) = match arguments {
+                    [_, _, ..] | [_] => key_and_value(),
+                    [] => (mixed_kind(true), mixed_kind(true)),
+                };
+
+                iterable_kind(key, value)
+            }
+            "key-of" => key_of_kind(argument(0).unwrap_or_else(|| mixed_kind(false))),
+            "value-of" => value_of_kind(argument(0).unwrap_or_else(|| mixed_kind(false))),
+            "properties-of" => properties_of_kind(argument(0).unwrap_or_else(|| mixed_kind(false))),
+            "mixed" => mixed_kind(true),
+            "void" => void_kind(),
+            "never" | "never-return" | "never-returns" | "no-return" => never_kind(),
+            "resource" | "open-resource" => resource_kind(),
+            "closed-resource" => closed_resource_kind(),
+            "object" => any_object_kind(),
+            "callable" | "pure-callable" => any_callable_kind(),
+            "closure" | "\\closure" | "pure-closure" => any_closure_kind(),
+            "static" => static_kind(self.scope_name()),
+            "self" => self_kind(self.scope_name()),
+            "parent" => parent_kind(self.scope_name()),
+            _ => named_object_kind(
+                self.class_like_name(name),
+                arguments.iter().map(|argument| self.build(argument)).collect(),
+            ),
+        }
+    }
+
+    fn build_shape(&self, shape: &ShapeType) -> TypeKind {
+        let name = self.context.interner.lookup(&shape.name).to_ascii_lowercase();
+        if name == "object" {
+            return TypeKind::Object(ObjectTypeKind::TypedObject {
+                properties: shape
+                    .entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let Some(ShapeKey::String(name)) = entry.key else {
+                            return None;
+                        };
+
+                        Some(ObjectProperty { name, kind: self.build(&entry.value), optional: entry.is_optional })
+                    })
+                    .collect(),
+            });
+        }
+
+        let properties = shape
+            .entries
+            .iter()
+            .map(|entry| {
+                let kind = self.build(&entry.value);
+                match entry.key {
+                    Some(ShapeKey::String(key)) => string_shape_property(key, kind, entry.is_optional),
+                    Some(ShapeKey::Integer(key)) => integer_shape_property(key as isize, kind, entry.is_optional),
+                    None => indexed_shape_property(kind, entry.is_optional),
+                }
+            })
+            .collect();
+
+        let additional_properties = if shape.is_sealed {
+            None
+        } else if name.ends_with("list") {
+            Some((non_negative_integer_kind(), mixed_kind(true)))
+        } else {
+            Some((array_key_kind(), mixed_kind(true)))
+        };
+
+        array_shape_kind(properties, additional_properties)
+    }
+
+    fn build_callable(&self, callable: &CallableType) -> TypeKind {
+        let name = self.context.interner.lookup(&callable.name).trim_start_matches('\\').to_ascii_lowercase();
+        let pure = name.starts_with("pure-");
+        let parameters = callable
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let kind =
+                    parameter.r#type.as_ref().map(|r#type| self.build(r#type)).unwrap_or_else(|| mixed_kind(false));
+
+                callable_parameter(kind, parameter.is_optional, parameter.is_variadic)
+            })
+            .collect();
+
+        let return_kind =
+            callable.return_type.as_ref().map(|r#type| self.build(r#type)).unwrap_or_else(|| mixed_kind(false));
+
+        if name.ends_with("closure") {
+            closure_kind(pure, vec![], parameters, return_kind)
+        } else {
+            callable_kind(pure, vec![], parameters, return_kind)
+        }
+    }
+
+    fn class_like_name(&self, name: &str) -> StringIdentifier {
+        self.context.resolve_class_like_name(name)
+    }
+
+    fn scope_name(&self) -> StringIdentifier {
+        match &self.scope {
+            Some(scope) => self.context.interner.intern(scope.name.get_key(self.context.interner)),
+            None => StringIdentifier::empty(),
+        }
+    }
+}
@@ -9,6 +9,7 @@ use mago_span::*;
 
 use crate::internal::context::Context;
 use crate::internal::reflect::attribute::reflect_attributes;
+use crate::internal::reflect::docblock::reflect_docblock;
 use crate::internal::reflect::r#type::maybe_reflect_hint;
 use crate::internal::reflect::r#type::reflect_hint;
 
@@ -17,14 +18,15 @@ pub fn reflect_function<'ast>(
     context: &'ast mut Context<'_>,
     scope: Option<&ClassLikeReflection>,
 ) -> FunctionLikeReflection {
-    let name = Name::new(*context.names.get(&function.name), function.name.span);
+    let name = FunctionLikeName::Function(Name::new(*context.names.get(&function.name), function.name.span));
+    let defined_in = context.interner.intern(name.get_key(context.interner));
+    let (docblock, templates) = reflect_docblock(function, context, scope, defined_in);
 
     FunctionLikeReflection {
         attribute_reflections: reflect_attributes(&function.attributes, context),
         visibility_reflection: None,
-        name: FunctionLikeName::Function(name),
-        // TODO: parse docblock to get the template list
-        templates: vec![],
+        name,
+        templates,
         parameters: reflect_function_like_parameter_list(&function.parameters, context, scope),
         return_type_reflection: reflect_function_like_return_type_hint(&function.return_type_hint, context, scope),
         returns_by_reference: function.ampersand.is_some(),
@@ -37,7 +39,10 @@ pub fn reflect_function<'ast>(
         is_pure: false,
         is_abstract: false,
         is_overriding: false,
+        scope: None,
+        docblock,
         span: function.span(),
+        is_conditionally_declared: context.is_conditionally_declared(function),
         is_populated: false,
     }
 }
@@ -47,12 +52,15 @@ pub fn reflect_closure<'ast>(
     context: &'ast mut Context<'_>,
     scope: Option<&ClassLikeReflection>,
 ) -> FunctionLikeReflection {
+    let name = FunctionLikeName::Closure(closure.span());
+    let defined_in = context.interner.intern(name.get_key(context.interner));
+    let (docblock, templates) = reflect_docblock(closure, context, scope, defined_in);
+
     FunctionLikeReflection {
         attribute_reflections: reflect_attributes(&closure.attributes, context),
         visibility_reflection: None,
-        name: FunctionLikeName::Closure(closure.span()),
-        // TODO: parse docblock to get the template list
-        templates: vec![],
+        name,
+        templates,
         parameters: reflect_function_like_parameter_list(&closure.parameters, context, scope),
         return_type_reflection: reflect_function_like_return_type_hint(&closure.return_type_hint, context, scope),
         returns_by_reference: closure.ampersand.is_some(),
@@ -65,7 +73,10 @@ pub fn reflect_closure<'ast>(
         is_pure: false,
         is_abstract: false,
         is_overriding: false,
+        scope: scope.map(|scope| scope.name),
+        docblock,
         span: closure.span(),
+        is_conditionally_declared: false,
         is_populated: false,
     }
 }
@@ -75,12 +86,15 @@ pub fn reflect_arrow_function<'ast>(
     context: &'ast mut Context<'_>,
     scope: Option<&ClassLikeReflection>,
 ) -> FunctionLikeReflection {
+    let name = FunctionLikeName::ArrowFunction(arrow_function.span());
+    let defined_in = context.interner.intern(name.get_key(context.interner));
+    let (docblock, templates) = reflect_docblock(arrow_function, context, scope, defined_in);
+
     FunctionLikeReflection {
         attribute_reflections: reflect_attributes(&arrow_function.attributes, context),
         visibility_reflection: None,
-        name: FunctionLikeName::ArrowFunction(arrow_function.span()),
-        // TODO: parse docblock to get the template list
-        templates: vec![],
+        name,
+        templates,
         parameters: reflect_function_like_parameter_list(&arrow_function.parameters, context, scope),
         return_type_reflection: reflect_function_like_return_type_hint(
             &arrow_function.return_type_hint,
@@ -97,7 +111,10 @@ pub fn reflect_arrow_function<'ast>(
         is_pure: false,
         is_abstract: false,
         is_overriding: false,
+        scope: scope.map(|scope| scope.name),
+        docblock,
         span: arrow_function.span(),
+        is_conditionally_declared: false,
         is_populated: false,
     }
 }
@@ -109,6 +126,10 @@ pub fn reflect_function_like_parameter_list<'ast>(
 ) -> Vec<FunctionLikeParameterReflection> {
     let mut parameters = vec![];
     for parameter in parameter_list.parameters.iter() {
+        if !context.is_available(&parameter.attributes) {
+            continue;
+        }
+
         parameters.push(reflect_function_like_parameter(parameter, context, scope));
     }
 
@@ -131,6 +152,7 @@ pub fn reflect_function_like_parameter<'ast>(
             type_reflection: mago_typing::infere(context.interner, context.source, context.names, &d.value),
             span: d.span(),
         }),
+        span: parameter.span(),
     }
 }
 
@@ -21,7 +21,50 @@ impl ReflectionWalker {
 }
 
 impl<'a> MutWalker<Context<'a>> for ReflectionWalker {
+    fn walk_in_namespace(&mut self, namespace: &Namespace, context: &mut Context<'_>) {
+        let name = namespace.name.as_ref().map(|name| context.interner.lookup(&name.value())).unwrap_or_default();
+
+        context.enter_namespace(name);
+    }
+
+    fn walk_out_namespace(&mut self, _namespace: &Namespace, context: &mut Context<'_>) {
+        context.enter_namespace("");
+    }
+
+    fn walk_in_use(&mut self, r#use: &Use, context: &mut Context<'_>) {
+        // Only the class-likes are imported, as the names in docblocks never refer to functions, or constants.
+        fn import(prefix: Option<&str>, item: &UseItem, context: &mut Context<'_>) {
+            let name = context.interner.lookup(&item.name.value());
+            let alias = item.alias.as_ref().map(|alias| context.interner.lookup(&alias.identifier.value));
+            match prefix {
+                Some(prefix) => context.import(&format!("{}\\{}", prefix, name), alias),
+                None => context.import(name, alias),
+            }
+        }
+
+        match &r#use.items {
+            UseItems::Sequence(sequence) => {
+                for item in sequence.items.iter() {
+                    import(None, item, context);
+                }
+            }
+            UseItems::MixedList(list) => {
+                let prefix = context.interner.lookup(&list.namespace.value());
+                for item in list.items.iter() {
+                    if item.r#type.is_none() {
+                        import(Some(prefix), &item.item, context);
+                    }
+                }
+            }
+            UseItems::TypedSequence(_) | UseItems::TypedList(_) => {}
+        }
+    }
+
     fn walk_in_function(&mut self, function: &Function, context: &mut Context<'_>) {
+        if !context.is_available(&function.attributes) {
+            return;
+        }
+
         let reflection = reflect_function(function, context, self.scope.last());
 
         self.reflection.register_function_like(context.interner, reflection);
@@ -43,11 +86,15 @@ impl<'a> MutWalker<Context<'a>> for ReflectionWalker {
         self.scope.push(reflect_class(class, context));
     }
 
-    fn walk_out_class<'ast>(&mut self, _class: &'ast Class, context: &mut Context<'a>) {
+    fn walk_out_class<'ast>(&mut self, class: &'ast Class, context: &mut Context<'a>) {
         let Some(reflection) = self.scope.pop() else {
             return;
         };
 
+        if !context.is_available(&class.attributes) {
+            return;
+        }
+
         self.reflection.register_class_like(context.interner, reflection);
     }
 
@@ -55,11 +102,15 @@ impl<'a> MutWalker<Context<'a>> for ReflectionWalker {
         self.scope.push(reflect_trait(r#trait, context));
     }
 
-    fn walk_out_trait<'ast>(&mut self, _trait: &'ast Trait, context: &mut Context<'a>) {
+    fn walk_out_trait<'ast>(&mut self, r#trait: &'ast Trait, context: &mut Context<'a>) {
         let Some(reflection) = self.scope.pop() else {
             return;
         };
 
+        if !context.is_available(&r#trait.attributes) {
+            return;
+        }
+
         self.reflection.register_class_like(context.interner, reflection);
     }
 
@@ -67,11 +118,15 @@ impl<'a> MutWalker<Context<'a>> for ReflectionWalker {
         self.scope.push(reflect_enum(r#enum, context));
     }
 
-    fn walk_out_enum<'ast>(&mut self, _enum: &'ast Enum, context: &mut Context<'a>) {
+    fn walk_out_enum<'ast>(&mut self, r#enum: &'ast Enum, context: &mut Context<'a>) {
         let Some(reflection) = self.scope.pop() else {
             return;
         };
 
+        if !context.is_available(&r#enum.attributes) {
+            return;
+        }
+
         self.reflection.register_class_like(context.interner, reflection);
     }
 
@@ -79,11 +134,15 @@ impl<'a> MutWalker<Context<'a>> for ReflectionWalker {
         self.scope.push(reflect_interface(interface, context));
     }
 
-    fn walk_out_interface<'ast>(&mut self, _interface: &'ast Interface, context: &mut Context<'a>) {
+    fn walk_out_interface<'ast>(&mut self, interface: &'ast Interface, context: &mut Context<'a>) {
         let Some(reflection) = self.scope.pop() else {
             return;
         };
 
+        if !context.is_available(&interface.attributes) {
+            return;
+        }
+
         self.reflection.register_class_like(context.interner, reflection);
     }
 
@@ -111,5 +170,9 @@ impl<'a> MutWalker<Context<'a>> for ReflectionWalker {
         if let Some(constant_reflection) = reflect_defined_constant(function_call, context) {
             self.reflection.register_constant(context.interner, constant_reflection);
         }
+
+        if let Some(alias_reflection) = reflect_class_like_alias(function_call, context) {
+            self.reflection.register_class_like_alias(context.interner, alias_reflection);
+        }
     }
 }
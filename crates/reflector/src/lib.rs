@@ -1,15 +1,24 @@
 use mago_ast::Program;
 use mago_interner::ThreadedInterner;
 use mago_names::Names;
+use mago_php_version::PHPVersion;
+use mago_reflection::class_like::ClassLikeReflection;
+use mago_reflection::reference::ReferenceIndex;
 use mago_reflection::CodebaseReflection;
+use mago_reporting::IssueCollection;
 use mago_source::Source;
 use mago_walker::*;
 
 use crate::internal::context::Context;
 use crate::internal::walker::ReflectionWalker;
+use crate::reference::ReferenceWalker;
 
+pub mod stubs;
+
+mod inheritance;
 mod internal;
 mod populator;
+mod reference;
 
 /// Construct a codebase reflection from the given program.
 ///
@@ -22,35 +31,101 @@ mod populator;
 ///
 /// # Returns
 ///
-/// A reflection result containing the reflection of the codebase and any issues found during reflection.
+/// The reflection of the codebase, including any issues found during reflection, such as duplicate declarations.
 #[inline]
 pub fn reflect(interner: &ThreadedInterner, source: &Source, program: &Program, names: &Names) -> CodebaseReflection {
+    reflect_program(interner, source, program, names, None)
+}
+
+/// Construct a codebase reflection from the given program, as it applies to the given version of PHP.
+///
+/// Unlike [`reflect`], the declarations, members, and parameters which the stubs mark as unavailable in
+/// the given version, with the `PhpStormStubsElementAvailable` attribute, are omitted, so that the
+/// elements declared once for each range of versions are reflected as declared for this version.
+///
+/// # Arguments
+///
+/// - `interner`: The `ThreadedInterner` instance used for string interning.
+/// - `source`: The `Source` instance containing the source code of the program.
+/// - `program`: The `Program` instance to reflect.
+/// - `names`: The `Names` instance containing the names of the program.
+/// - `php_version`: The version of PHP for which the program is reflected.
+///
+/// # Returns
+///
+/// The reflection of the codebase, including any issues found during reflection, such as duplicate declarations.
+#[inline]
+pub fn reflect_for_version(
+    interner: &ThreadedInterner,
+    source: &Source,
+    program: &Program,
+    names: &Names,
+    php_version: PHPVersion,
+) -> CodebaseReflection {
+    reflect_program(interner, source, program, names, Some(php_version))
+}
+
+fn reflect_program(
+    interner: &ThreadedInterner,
+    source: &Source,
+    program: &Program,
+    names: &Names,
+    php_version: Option<PHPVersion>,
+) -> CodebaseReflection {
     let mut walker = ReflectionWalker::new();
 
-    let mut context = Context::new(interner, source, names);
+    let mut context = Context::new(interner, source, names, program, php_version);
 
     walker.walk_program(program, &mut context);
+    for issue in context.issues {
+        walker.reflection.issues.push(issue);
+    }
 
     walker.reflection
 }
 
-/// Merges two `ReflectionResult` instances.
+/// Indexes the references made by the given program to the symbols of the codebase.
+///
+/// The references are resolved through the names of the program, such as the classes it instantiates, and
+/// names in type hints, `catch` clauses, and attributes, the functions it calls, and the constants, methods,
+/// and class constants it accesses. The constructs referencing symbols dynamically, such as `new $class`,
+/// are recorded as dynamic usages of the source of the program.
+///
+/// # Parameters
+///
+/// - `interner`: The `ThreadedInterner` instance used for string interning.
+/// - `program`: The `Program` instance to index.
+/// - `names`: The `Names` instance containing the names of the program.
+///
+/// # Returns
+///
+/// The index of the references made by the program, which can be combined with the indexes of other programs.
+#[inline]
+pub fn index_references(interner: &ThreadedInterner, program: &Program, names: &Names) -> ReferenceIndex {
+    let mut walker = ReferenceWalker::default();
+    let mut context = reference::Context::new(interner, names);
+
+    walker.walk_program(program, &mut context);
+
+    walker.index
+}
+
+/// Merges two `CodebaseReflection` instances.
 ///
-/// This method combines the codebase reflections and issues from two `ReflectionResult` instances
-/// into a single `ReflectionResult`. If duplicates are found during merging (such as functions,
-/// classes, or constants with identical names), they are recorded as issues within the resulting
-/// `ReflectionResult`.
+/// This method combines the reflections and issues of two `CodebaseReflection` instances into a single
+/// `CodebaseReflection`. If duplicates are found during merging (such as functions, classes, or constants
+/// with identical names), they are recorded as issues on the declarations of `other_reflection`, unless
+/// either of the declarations is conditional.
 ///
 /// # Parameters
 ///
-/// - `left`: The first `ReflectionResult` to merge.
-/// - `right`: The second `ReflectionResult` to merge.
+/// - `interner`: The `ThreadedInterner` instance used for string interning.
+/// - `reflection`: The first `CodebaseReflection` to merge, whose declarations take precedence.
+/// - `other_reflection`: The second `CodebaseReflection` to merge.
 ///
 /// # Returns
 ///
-/// A new `ReflectionResult` containing the combined reflections and issues from both inputs.
-/// If any conflicts are found (e.g., duplicate functions, classes, or constants), they are recorded
-/// as issues in the returned result.
+/// A `CodebaseReflection` containing the combined reflections and issues from both inputs.
 #[inline]
 pub fn merge(
     interner: &ThreadedInterner,
@@ -69,6 +144,12 @@ pub fn merge(
         reflection.register_constant(interner, constant);
     }
 
+    for (_, alias) in other_reflection.class_like_aliases.into_iter() {
+        reflection.register_class_like_alias(interner, alias);
+    }
+
+    reflection.issues.extend(other_reflection.issues);
+
     reflection
 }
 
@@ -89,3 +170,28 @@ pub fn merge(
 pub fn populate(interner: &ThreadedInterner, reflection: &mut CodebaseReflection) {
     populator::populate(interner, reflection);
 }
+
+/// Checks the inheritance of a class-like entity against the rest of the codebase.
+///
+/// This reports the abstract methods that a concrete class or an enum does not implement, the methods
+/// which are not compatible with the methods they override, whether by their visibility, their parameters,
+/// or their return type, the overridden final methods and the extended final classes, and the interfaces
+/// extending other class-like entities.
+///
+/// # Parameters
+///
+/// - `interner`: The `ThreadedInterner` instance used for string interning.
+/// - `codebase`: The populated `CodebaseReflection`, containing the ancestors of the class-like entity.
+/// - `class_like`: The `ClassLikeReflection` to check.
+///
+/// # Returns
+///
+/// The issues found, reported on the class-like entity, with annotations on the declarations of its ancestors.
+#[inline]
+pub fn check_inheritance(
+    interner: &ThreadedInterner,
+    codebase: &CodebaseReflection,
+    class_like: &ClassLikeReflection,
+) -> IssueCollection {
+    inheritance::check_inheritance(interner, codebase, class_like)
+}
@@ -1,7 +1,11 @@
+use std::collections::hash_map::Entry;
+
+use ahash::HashMap;
 use mago_interner::StringIdentifier;
 use mago_interner::ThreadedInterner;
 use mago_reflection::class_like::ClassLikeReflection;
 use mago_reflection::identifier::ClassLikeName;
+use mago_reflection::identifier::Name;
 use mago_reflection::CodebaseReflection;
 
 #[inline(always)]
@@ -10,12 +14,18 @@ pub fn populate(interner: &ThreadedInterner, codebase: &mut CodebaseReflection)
         return;
     }
 
-    let new_class_like_names = codebase
+    resolve_class_like_aliases(interner, codebase);
+
+    let mut new_class_like_names = codebase
         .class_like_reflections
         .iter()
         .filter_map(|(name, reflection)| if !reflection.is_populated { Some(*name) } else { None })
         .collect::<Vec<_>>();
 
+    // The class-likes are populated in the order of their names, so that the inherited names, and members,
+    // are the same from one run to another, no matter the order of the reflections.
+    new_class_like_names.sort_by_cached_key(|name| name.get_key(interner));
+
     for name in &new_class_like_names {
         if let Some(reflection) = codebase.class_like_reflections.get_mut(name) {
             reflection.properties.declaring_members = Default::default();
@@ -60,6 +70,52 @@ pub fn populate(interner: &ThreadedInterner, codebase: &mut CodebaseReflection)
     codebase.populated = true;
 }
 
+/// Resolves the aliases declared with `class_alias()` to the class-like entities they refer to, so that the
+/// reflection of a class-like entity is found under the names of its aliases.
+///
+/// An alias may refer to another alias, and the aliases whose names are already taken, or which refer to
+/// unknown class-like entities, are ignored.
+fn resolve_class_like_aliases(interner: &ThreadedInterner, codebase: &mut CodebaseReflection) {
+    let mut unresolved = codebase.class_like_aliases.iter().collect::<Vec<_>>();
+    loop {
+        let count = unresolved.len();
+        unresolved.retain(|(lowercase_name, alias)| {
+            if codebase.class_like_names.contains_key(*lowercase_name) {
+                return false;
+            }
+
+            let Some(original) = codebase.class_like_names.get(&interner.lowered(&alias.original)).copied() else {
+                return true;
+            };
+
+            codebase.class_like_names.insert(**lowercase_name, original);
+
+            false
+        });
+
+        if unresolved.is_empty() || unresolved.len() == count {
+            break;
+        }
+    }
+}
+
+/// Records the name of an ancestor, keeping the one written first when the ancestor is named at several
+/// locations, so that the recorded names do not depend on the order of the sets they are taken from.
+fn insert_ancestor_name(interner: &ThreadedInterner, names: &mut HashMap<StringIdentifier, Name>, name: Name) {
+    let location = |name: &Name| (interner.lookup(&name.span.start.source.0), name.span.start.offset);
+
+    match names.entry(interner.lowered(&name.value)) {
+        Entry::Occupied(mut entry) => {
+            if location(&name) < location(entry.get()) {
+                entry.insert(name);
+            }
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(name);
+        }
+    }
+}
+
 #[inline]
 fn populate_class_like_reflection(
     interner: &ThreadedInterner,
@@ -180,10 +236,8 @@ fn populate_data_from_parent_classlike(
             continue;
         }
 
-        let identifier = extended_class.value;
-
         reflection.inheritance.all_extended_classes.insert(*extended_class);
-        reflection.inheritance.names.insert(interner.lowered(&identifier), *extended_class);
+        insert_ancestor_name(interner, &mut reflection.inheritance.names, *extended_class);
     }
 
     for implemented_interface in &parent_reflection.inheritance.all_implemented_interfaces {
@@ -191,10 +245,8 @@ fn populate_data_from_parent_classlike(
             continue;
         }
 
-        let identifier = implemented_interface.value;
-
         reflection.inheritance.all_implemented_interfaces.insert(*implemented_interface);
-        reflection.inheritance.names.insert(interner.lowered(&identifier), *implemented_interface);
+        insert_ancestor_name(interner, &mut reflection.inheritance.names, *implemented_interface);
     }
 
     for used_trait in &parent_reflection.used_traits {
@@ -238,7 +290,80 @@ fn populate_data_from_trait(
     };
 
     inherit_properties_from_parent(reflection, trait_reflection);
-    inherit_methods_from_parent(reflection, trait_reflection);
+
+    // The methods excluded from this trait by an `insteadof` adaptation are taken from another trait.
+    let excluded_methods = reflection
+        .trait_precedences
+        .iter()
+        .filter(|precedence| precedence.excluded_traits.contains(&trait_name_id))
+        .map(|precedence| interner.lowered(&precedence.method_name))
+        .collect::<Vec<_>>();
+
+    if excluded_methods.is_empty() {
+        inherit_methods_from_parent(reflection, trait_reflection);
+    } else {
+        let mut trait_reflection = trait_reflection.clone();
+        let methods = &mut trait_reflection.methods;
+        let is_excluded = |method_name: &StringIdentifier| excluded_methods.contains(&interner.lowered(method_name));
+
+        methods.appering_members.retain(|method_name, _| !is_excluded(method_name));
+        methods.declaring_members.retain(|method_name, _| !is_excluded(method_name));
+        methods.inheritable_members.retain(|method_name, _| !is_excluded(method_name));
+
+        inherit_methods_from_parent(reflection, &trait_reflection);
+    }
+
+    inherit_method_aliases_from_trait(interner, reflection, trait_reflection, trait_name_id);
+}
+
+/// Adds the methods of the given trait that are aliased by the class-like under a new name, such as
+/// `bar` in `use A { foo as bar; }`.
+///
+/// An alias does not replace a method of the same name declared by the class-like itself.
+#[inline]
+fn inherit_method_aliases_from_trait(
+    interner: &ThreadedInterner,
+    reflection: &mut ClassLikeReflection,
+    trait_reflection: &ClassLikeReflection,
+    trait_name_id: StringIdentifier,
+) {
+    let class_name = reflection.name;
+    let class_is_trait = reflection.is_trait();
+
+    for alias in reflection.trait_aliases.clone() {
+        let Some(alias_name) = alias.alias else {
+            continue;
+        };
+
+        if alias.trait_name.is_some_and(|alias_trait_name| alias_trait_name != trait_name_id) {
+            continue;
+        }
+
+        let method_name_id = interner.lowered(&alias.method_name);
+        let Some((method_name, declaring_class)) = trait_reflection
+            .methods
+            .declaring_members
+            .iter()
+            .find(|(method_name, _)| interner.lowered(method_name) == method_name_id)
+        else {
+            continue;
+        };
+
+        let alias_name_id = interner.lowered(&alias_name.value);
+        if reflection.methods.members.keys().any(|method_name| interner.lowered(method_name) == alias_name_id) {
+            continue;
+        }
+
+        let appering_class_like =
+            trait_reflection.methods.appering_members.get(method_name).copied().unwrap_or(*declaring_class);
+
+        reflection
+            .methods
+            .appering_members
+            .insert(alias_name.value, if class_is_trait { class_name } else { appering_class_like });
+        reflection.methods.declaring_members.insert(alias_name.value, *declaring_class);
+        reflection.methods.inheritable_members.insert(alias_name.value, *declaring_class);
+    }
 }
 
 #[inline]
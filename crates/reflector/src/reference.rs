@@ -0,0 +1,432 @@
+use ahash::HashSet;
+
+use mago_ast::ast::*;
+use mago_interner::StringIdentifier;
+use mago_interner::ThreadedInterner;
+use mago_names::Names;
+use mago_names::ResolutionKind;
+use mago_reflection::reference::DynamicUsageKind;
+use mago_reflection::reference::ReferenceIndex;
+use mago_reflection::reference::ReferenceKind;
+use mago_reflection::reference::ReferencedSymbol;
+use mago_span::HasSpan;
+use mago_walker::MutWalker;
+
+/// The functions which call, or access, the symbols named by their arguments, such as `call_user_func()`.
+const DYNAMIC_FUNCTIONS: [(&str, DynamicUsageKind); 4] = [
+    ("call_user_func", DynamicUsageKind::FunctionCall),
+    ("call_user_func_array", DynamicUsageKind::FunctionCall),
+    ("forward_static_call", DynamicUsageKind::FunctionCall),
+    ("constant", DynamicUsageKind::ConstantAccess),
+];
+
+/// The class-like being walked, used to resolve `self`, `static`, `parent`, and `$this`.
+#[derive(Debug, Clone, Copy)]
+struct Scope {
+    /// The name of the class-like, which is not known for anonymous classes.
+    name: Option<StringIdentifier>,
+    /// The name of the class extended by the class-like, if any.
+    parent: Option<StringIdentifier>,
+}
+
+/// The class-like named on the left of `::`, or after `new`.
+enum ClassTarget {
+    /// The class-like with the given name.
+    Named(StringIdentifier),
+    /// A class-like which is not named, such as `self` within an anonymous class.
+    Unknown,
+    /// A class-like whose name is only known at runtime, such as `$class`.
+    Dynamic,
+}
+
+#[derive(Debug)]
+pub struct Context<'a> {
+    interner: &'a ThreadedInterner,
+    names: &'a Names,
+    scopes: Vec<Scope>,
+    /// The offsets of the names of class-likes within `catch` clauses.
+    catch_hints: HashSet<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct ReferenceWalker {
+    pub index: ReferenceIndex,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(interner: &'a ThreadedInterner, names: &'a Names) -> Self {
+        Self { interner, names, scopes: vec![], catch_hints: HashSet::default() }
+    }
+
+    fn enter_class_like(&mut self, name: Option<&LocalIdentifier>, extends: Option<&Extends>) {
+        let name = name.and_then(|name| self.resolved(name));
+        let parent = extends.and_then(|extends| extends.types.first()).and_then(|parent| self.resolved(parent));
+
+        self.scopes.push(Scope { name, parent });
+    }
+
+    fn exit_class_like(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn resolved(&self, node: &impl HasSpan) -> Option<StringIdentifier> {
+        self.names.resolved(&node.span().start).map(|(name, _)| name)
+    }
+
+    fn current_class_like(&self) -> Option<StringIdentifier> {
+        self.scopes.last().and_then(|scope| scope.name)
+    }
+
+    fn resolve_class(&self, expression: &Expression) -> ClassTarget {
+        let resolved = match expression {
+            Expression::Identifier(identifier) => self.resolved(identifier),
+            Expression::Self_(_) | Expression::Static(_) => self.current_class_like(),
+            Expression::Parent(_) => self.scopes.last().and_then(|scope| scope.parent),
+            _ => return ClassTarget::Dynamic,
+        };
+
+        match resolved {
+            Some(name) => ClassTarget::Named(name),
+            None => ClassTarget::Unknown,
+        }
+    }
+
+    fn is_this(&self, expression: &Expression) -> bool {
+        matches!(
+            expression,
+            Expression::Variable(Variable::Direct(variable)) if self.interner.lookup(&variable.name) == "$this"
+        )
+    }
+}
+
+impl ReferenceWalker {
+    fn add_class_like(&mut self, context: &Context<'_>, node: &impl HasSpan, kind: ReferenceKind) {
+        if let Some(name) = context.resolved(node) {
+            self.index.add_reference(ReferencedSymbol::class_like(context.interner, &name), kind, node.span());
+        }
+    }
+
+    /// Records a reference to the function, or the constant, named by the given identifier, and to the global
+    /// one, if the name falls back to it at runtime.
+    fn add_global(
+        &mut self,
+        context: &Context<'_>,
+        identifier: &Identifier,
+        kind: ReferenceKind,
+        symbol: fn(&ThreadedInterner, &StringIdentifier) -> ReferencedSymbol,
+    ) {
+        let Some((name, resolution)) = context.names.resolved(&identifier.span().start) else {
+            return;
+        };
+
+        self.index.add_reference(symbol(context.interner, &name), kind, identifier.span());
+        if resolution == ResolutionKind::GlobalFallback {
+            self.index.add_reference(symbol(context.interner, &identifier.value()), kind, identifier.span());
+        }
+    }
+
+    fn add_function(&mut self, context: &Context<'_>, function: &Expression, kind: ReferenceKind) {
+        match function {
+            Expression::Identifier(identifier) => {
+                self.add_global(context, identifier, kind, ReferencedSymbol::function);
+
+                let name = context.interner.lookup(&identifier.value());
+                let name = name.strip_prefix('\\').unwrap_or(name);
+                if let Some((_, usage)) =
+                    DYNAMIC_FUNCTIONS.iter().find(|(function, _)| function.eq_ignore_ascii_case(name))
+                {
+                    self.index.add_dynamic_usage(*usage, function.span());
+                }
+            }
+            Expression::Closure(_) | Expression::ArrowFunction(_) => {}
+            Expression::Parenthesized(parenthesized)
+                if matches!(
+                    parenthesized.expression.as_ref(),
+                    Expression::Closure(_) | Expression::ArrowFunction(_)
+                ) => {}
+            _ => self.index.add_dynamic_usage(DynamicUsageKind::FunctionCall, function.span()),
+        }
+    }
+
+    fn add_method(
+        &mut self,
+        context: &Context<'_>,
+        object: &Expression,
+        method: &ClassLikeMemberSelector,
+        kind: ReferenceKind,
+    ) {
+        let ClassLikeMemberSelector::Identifier(identifier) = method else {
+            self.index.add_dynamic_usage(DynamicUsageKind::MethodCall, method.span());
+
+            return;
+        };
+
+        let symbol = match context.current_class_like() {
+            Some(class_like) if context.is_this(object) => {
+                ReferencedSymbol::method(context.interner, &class_like, &identifier.value)
+            }
+            _ => ReferencedSymbol::any_method(context.interner, &identifier.value),
+        };
+
+        self.index.add_reference(symbol, kind, identifier.span());
+    }
+
+    fn add_static_method(
+        &mut self,
+        context: &Context<'_>,
+        class: &Expression,
+        method: &ClassLikeMemberSelector,
+        kind: ReferenceKind,
+    ) {
+        let class_like = self.add_static_access(context, class);
+
+        let ClassLikeMemberSelector::Identifier(identifier) = method else {
+            self.index.add_dynamic_usage(DynamicUsageKind::MethodCall, method.span());
+
+            return;
+        };
+
+        let symbol = match class_like {
+            Some(class_like) => ReferencedSymbol::method(context.interner, &class_like, &identifier.value),
+            None => ReferencedSymbol::any_method(context.interner, &identifier.value),
+        };
+
+        self.index.add_reference(symbol, kind, identifier.span());
+    }
+
+    /// Records a reference to the class-like named on the left of `::`, returning its name, if known.
+    fn add_static_access(&mut self, context: &Context<'_>, class: &Expression) -> Option<StringIdentifier> {
+        match context.resolve_class(class) {
+            ClassTarget::Named(name) => {
+                if let Expression::Identifier(identifier) = class {
+                    self.index.add_reference(
+                        ReferencedSymbol::class_like(context.interner, &name),
+                        ReferenceKind::StaticAccess,
+                        identifier.span(),
+                    );
+                }
+
+                Some(name)
+            }
+            ClassTarget::Unknown => None,
+            ClassTarget::Dynamic => {
+                self.index.add_dynamic_usage(DynamicUsageKind::StaticAccess, class.span());
+
+                None
+            }
+        }
+    }
+}
+
+impl<'a> MutWalker<Context<'a>> for ReferenceWalker {
+    fn walk_in_class(&mut self, class: &Class, context: &mut Context<'a>) {
+        context.enter_class_like(Some(&class.name), class.extends.as_ref());
+    }
+
+    fn walk_out_class(&mut self, _class: &Class, context: &mut Context<'a>) {
+        context.exit_class_like();
+    }
+
+    fn walk_in_interface(&mut self, interface: &Interface, context: &mut Context<'a>) {
+        context.enter_class_like(Some(&interface.name), None);
+    }
+
+    fn walk_out_interface(&mut self, _interface: &Interface, context: &mut Context<'a>) {
+        context.exit_class_like();
+    }
+
+    fn walk_in_trait(&mut self, r#trait: &Trait, context: &mut Context<'a>) {
+        context.enter_class_like(Some(&r#trait.name), None);
+    }
+
+    fn walk_out_trait(&mut self, _trait: &Trait, context: &mut Context<'a>) {
+        context.exit_class_like();
+    }
+
+    fn walk_in_enum(&mut self, r#enum: &Enum, context: &mut Context<'a>) {
+        context.enter_class_like(Some(&r#enum.name), None);
+    }
+
+    fn walk_out_enum(&mut self, _enum: &Enum, context: &mut Context<'a>) {
+        context.exit_class_like();
+    }
+
+    fn walk_in_anonymous_class(&mut self, anonymous_class: &AnonymousClass, context: &mut Context<'a>) {
+        context.enter_class_like(None, anonymous_class.extends.as_ref());
+    }
+
+    fn walk_out_anonymous_class(&mut self, _anonymous_class: &AnonymousClass, context: &mut Context<'a>) {
+        context.exit_class_like();
+    }
+
+    fn walk_in_extends(&mut self, extends: &Extends, context: &mut Context<'a>) {
+        for parent in extends.types.iter() {
+            self.add_class_like(context, parent, ReferenceKind::Extension);
+        }
+    }
+
+    fn walk_in_implements(&mut self, implements: &Implements, context: &mut Context<'a>) {
+        for interface in implements.types.iter() {
+            self.add_class_like(context, interface, ReferenceKind::Implementation);
+        }
+    }
+
+    fn walk_in_trait_use(&mut self, trait_use: &TraitUse, context: &mut Context<'a>) {
+        for trait_name in trait_use.trait_names.iter() {
+            self.add_class_like(context, trait_name, ReferenceKind::TraitUse);
+        }
+    }
+
+    fn walk_in_try_catch_clause(&mut self, try_catch_clause: &TryCatchClause, context: &mut Context<'a>) {
+        fn collect(hint: &Hint, offsets: &mut HashSet<usize>) {
+            match hint {
+                Hint::Identifier(identifier) => {
+                    offsets.insert(identifier.span().start.offset);
+                }
+                Hint::Union(union) => {
+                    collect(&union.left, offsets);
+                    collect(&union.right, offsets);
+                }
+                Hint::Parenthesized(parenthesized) => collect(&parenthesized.hint, offsets),
+                _ => {}
+            }
+        }
+
+        collect(&try_catch_clause.hint, &mut context.catch_hints);
+    }
+
+    fn walk_in_hint(&mut self, hint: &Hint, context: &mut Context<'a>) {
+        if let Hint::Identifier(identifier) = hint {
+            let kind = if context.catch_hints.contains(&identifier.span().start.offset) {
+                ReferenceKind::Catch
+            } else {
+                ReferenceKind::TypeHint
+            };
+
+            self.add_class_like(context, identifier, kind);
+        }
+    }
+
+    fn walk_in_attribute(&mut self, attribute: &Attribute, context: &mut Context<'a>) {
+        self.add_class_like(context, &attribute.name, ReferenceKind::Attribute);
+    }
+
+    fn walk_in_instantiation(&mut self, instantiation: &Instantiation, context: &mut Context<'a>) {
+        match context.resolve_class(&instantiation.class) {
+            ClassTarget::Named(name) => self.index.add_reference(
+                ReferencedSymbol::class_like(context.interner, &name),
+                ReferenceKind::Instantiation,
+                instantiation.class.span(),
+            ),
+            ClassTarget::Unknown => {}
+            // Anonymous classes are instantiated where they are declared.
+            ClassTarget::Dynamic if matches!(instantiation.class, Expression::AnonymousClass(_)) => {}
+            ClassTarget::Dynamic => {
+                self.index.add_dynamic_usage(DynamicUsageKind::Instantiation, instantiation.class.span())
+            }
+        }
+    }
+
+    fn walk_in_binary(&mut self, binary: &Binary, context: &mut Context<'a>) {
+        if let (BinaryOperator::Instanceof(_), Expression::Identifier(identifier)) =
+            (binary.operator, binary.rhs.as_ref())
+        {
+            self.add_class_like(context, identifier, ReferenceKind::Instanceof);
+        }
+    }
+
+    fn walk_in_function_call(&mut self, function_call: &FunctionCall, context: &mut Context<'a>) {
+        self.add_function(context, &function_call.function, ReferenceKind::Call);
+    }
+
+    fn walk_in_function_closure_creation(
+        &mut self,
+        function_closure_creation: &FunctionClosureCreation,
+        context: &mut Context<'a>,
+    ) {
+        self.add_function(context, &function_closure_creation.function, ReferenceKind::ClosureCreation);
+    }
+
+    fn walk_in_method_call(&mut self, method_call: &MethodCall, context: &mut Context<'a>) {
+        self.add_method(context, &method_call.object, &method_call.method, ReferenceKind::Call);
+    }
+
+    fn walk_in_null_safe_method_call(&mut self, null_safe_method_call: &NullSafeMethodCall, context: &mut Context<'a>) {
+        self.add_method(context, &null_safe_method_call.object, &null_safe_method_call.method, ReferenceKind::Call);
+    }
+
+    fn walk_in_method_closure_creation(
+        &mut self,
+        method_closure_creation: &MethodClosureCreation,
+        context: &mut Context<'a>,
+    ) {
+        self.add_method(
+            context,
+            &method_closure_creation.object,
+            &method_closure_creation.method,
+            ReferenceKind::ClosureCreation,
+        );
+    }
+
+    fn walk_in_static_method_call(&mut self, static_method_call: &StaticMethodCall, context: &mut Context<'a>) {
+        self.add_static_method(context, &static_method_call.class, &static_method_call.method, ReferenceKind::Call);
+    }
+
+    fn walk_in_static_method_closure_creation(
+        &mut self,
+        static_method_closure_creation: &StaticMethodClosureCreation,
+        context: &mut Context<'a>,
+    ) {
+        self.add_static_method(
+            context,
+            &static_method_closure_creation.class,
+            &static_method_closure_creation.method,
+            ReferenceKind::ClosureCreation,
+        );
+    }
+
+    fn walk_in_static_property_access(
+        &mut self,
+        static_property_access: &StaticPropertyAccess,
+        context: &mut Context<'a>,
+    ) {
+        self.add_static_access(context, &static_property_access.class);
+    }
+
+    fn walk_in_class_constant_access(
+        &mut self,
+        class_constant_access: &ClassConstantAccess,
+        context: &mut Context<'a>,
+    ) {
+        let constant = match &class_constant_access.constant {
+            ClassLikeConstantSelector::Identifier(identifier) => identifier,
+            ClassLikeConstantSelector::Expression(selector) => {
+                self.add_static_access(context, &class_constant_access.class);
+                self.index.add_dynamic_usage(DynamicUsageKind::ConstantAccess, selector.span());
+
+                return;
+            }
+        };
+
+        // `Foo::class` only takes the name of the class-like, which may not even exist.
+        if context.interner.lookup(&constant.value).eq_ignore_ascii_case("class") {
+            if let Expression::Identifier(identifier) = &class_constant_access.class {
+                self.add_class_like(context, identifier, ReferenceKind::ClassName);
+            }
+
+            return;
+        }
+
+        if let Some(class_like) = self.add_static_access(context, &class_constant_access.class) {
+            self.index.add_reference(
+                ReferencedSymbol::class_like_constant(context.interner, &class_like, &constant.value),
+                ReferenceKind::ConstantAccess,
+                constant.span(),
+            );
+        }
+    }
+
+    fn walk_in_constant_access(&mut self, constant_access: &ConstantAccess, context: &mut Context<'a>) {
+        self.add_global(context, &constant_access.name, ReferenceKind::ConstantAccess, ReferencedSymbol::constant);
+    }
+}
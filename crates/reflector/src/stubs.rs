@@ -0,0 +1,272 @@
+//! The reflections of stubs, such as those of PHP itself, and of its extensions, stored in a binary format.
+//!
+//! Stubs declare some elements differently across versions of PHP, so each stub is reflected once for
+//! every range of versions in which its reflection is the same, and the reflections applying to the
+//! targeted version are selected when loading them, without parsing the stubs again.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::io::Write;
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use mago_ast::Program;
+use mago_interner::ThreadedInterner;
+use mago_names::Names;
+use mago_php_version::PHPVersion;
+use mago_reflection::CodebaseReflection;
+use mago_source::Source;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The bytes starting the encoded reflections of stubs.
+const MAGIC: &[u8; 4] = b"MGSR";
+
+/// The version of the binary format, incremented whenever the layout of the header changes.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// The version of the reflector that encodes the reflections, whose layout may change with every version.
+const REFLECTOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The number of chunks in which the reflections are compressed, so that they can be decoded in parallel.
+const CHUNKS: usize = 64;
+
+/// The versions of PHP for which the stubs are reflected, from the oldest to the newest.
+const PHP_VERSIONS: [PHPVersion; 10] = [
+    PHPVersion::PHP70,
+    PHPVersion::PHP71,
+    PHPVersion::PHP72,
+    PHPVersion::PHP73,
+    PHPVersion::PHP74,
+    PHPVersion::PHP80,
+    PHPVersion::PHP81,
+    PHPVersion::PHP82,
+    PHPVersion::PHP83,
+    PHPVersion::PHP84,
+];
+
+/// The reflection of a stub, as it applies to a range of versions of PHP.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StubReflection {
+    /// The oldest version of PHP to which the reflection applies.
+    pub from: PHPVersion,
+    /// The newest version of PHP to which the reflection applies.
+    pub to: PHPVersion,
+    pub reflection: CodebaseReflection,
+}
+
+impl StubReflection {
+    /// Determines whether the reflection applies to the given version of PHP.
+    pub fn applies_to(&self, php_version: PHPVersion) -> bool {
+        self.from <= php_version && php_version <= self.to
+    }
+}
+
+/// An error returned when the reflections of stubs cannot be encoded, or decoded.
+#[derive(Debug)]
+pub enum StubsError {
+    /// The data does not start with the header of encoded stubs.
+    InvalidHeader,
+    /// The data was encoded with another version of the binary format, or of the reflector.
+    UnsupportedVersion(u16, String),
+    Encoding(String),
+    Decoding(String),
+}
+
+impl std::fmt::Display for StubsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "the data does not contain reflections of stubs"),
+            Self::UnsupportedVersion(format_version, reflector_version) => write!(
+                f,
+                "the stubs were reflected in format {} by version {} of the reflector, expected format {} by version {}",
+                format_version, reflector_version, FORMAT_VERSION, REFLECTOR_VERSION
+            ),
+            Self::Encoding(error) => write!(f, "failed to encode the reflections of stubs: {}", error),
+            Self::Decoding(error) => write!(f, "failed to decode the reflections of stubs: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for StubsError {}
+
+/// Reflects the given stub for every supported version of PHP.
+///
+/// Each declaration is reflected once for every range of consecutive versions in which its reflection is
+/// the same, so that the declarations which do not depend on the version of PHP are only stored once.
+///
+/// The issues found while reflecting the stub, such as duplicate declarations, are not kept.
+///
+/// # Returns
+///
+/// The reflections of the stub, one for each range of versions, ordered by these ranges.
+pub fn reflect(interner: &ThreadedInterner, source: &Source, program: &Program, names: &Names) -> Vec<StubReflection> {
+    let versions =
+        PHP_VERSIONS.map(|php_version| crate::reflect_for_version(interner, source, program, names, php_version));
+
+    let mut ranges: BTreeMap<(usize, usize), CodebaseReflection> = BTreeMap::new();
+    for (index, codebase) in versions.iter().enumerate() {
+        for (name, function_like) in codebase.function_like_reflections.iter() {
+            let range = range(&versions, index, |codebase| codebase.function_like_reflections.get(name), function_like);
+            if let Some(range) = range {
+                ranges.entry(range).or_default().register_function_like(interner, function_like.clone());
+            }
+        }
+
+        for (name, class_like) in codebase.class_like_reflections.iter() {
+            let range = range(&versions, index, |codebase| codebase.class_like_reflections.get(name), class_like);
+            if let Some(range) = range {
+                ranges.entry(range).or_default().register_class_like(interner, class_like.clone());
+            }
+        }
+
+        for (name, constant) in codebase.constant_reflections.iter() {
+            let range = range(&versions, index, |codebase| codebase.constant_reflections.get(name), constant);
+            if let Some(range) = range {
+                ranges.entry(range).or_default().register_constant(interner, constant.clone());
+            }
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|((from, to), reflection)| StubReflection { from: PHP_VERSIONS[from], to: PHP_VERSIONS[to], reflection })
+        .collect()
+}
+
+/// Returns the range of the indices of the versions in which the reflection of the declaration found in the
+/// version at the given index is the same, or `None` if the range starts at a previous version.
+fn range<'a, T: PartialEq + 'a>(
+    versions: &'a [CodebaseReflection],
+    index: usize,
+    get: impl Fn(&'a CodebaseReflection) -> Option<&'a T>,
+    reflection: &T,
+) -> Option<(usize, usize)> {
+    if index > 0 && get(&versions[index - 1]) == Some(reflection) {
+        return None;
+    }
+
+    let mut last = index;
+    while last + 1 < versions.len() && get(&versions[last + 1]) == Some(reflection) {
+        last += 1;
+    }
+
+    Some((index, last))
+}
+
+/// Encodes the given reflections of stubs, along with the table of the strings their identifiers represent,
+/// so that they can be decoded by another interner.
+///
+/// The encoded data starts with a header, made of magic bytes, and of the versions of the binary format and
+/// of the reflector, followed by the compressed table of strings, and by the reflections, compressed in
+/// chunks which are decoded in parallel.
+pub fn encode(interner: &ThreadedInterner, reflections: &[StubReflection]) -> Result<Vec<u8>, StubsError> {
+    let (chunks, strings) = interner.with_string_table_serialization(|| {
+        reflections.chunks(reflections.len().div_ceil(CHUNKS).max(1)).map(compress).collect::<Result<Vec<_>, _>>()
+    });
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(FORMAT_VERSION.to_le_bytes());
+    bytes.push(REFLECTOR_VERSION.len() as u8);
+    bytes.extend(REFLECTOR_VERSION.as_bytes());
+    for section in std::iter::once(compress(&strings)).chain(chunks?.into_iter().map(Ok)) {
+        let section = section?;
+
+        bytes.extend((section.len() as u64).to_le_bytes());
+        bytes.extend(section);
+    }
+
+    Ok(bytes)
+}
+
+/// Decodes the reflections of stubs encoded by [`encode`], interning their strings into the given interner.
+pub fn decode(interner: &ThreadedInterner, bytes: &[u8]) -> Result<Vec<StubReflection>, StubsError> {
+    let header = bytes.strip_prefix(MAGIC).ok_or(StubsError::InvalidHeader)?;
+    let [low, high, length, ..] = *header else {
+        return Err(StubsError::InvalidHeader);
+    };
+
+    let format_version = u16::from_le_bytes([low, high]);
+    let (reflector_version, mut rest) =
+        header[3..].split_at_checked(length as usize).ok_or(StubsError::InvalidHeader)?;
+    let reflector_version = String::from_utf8_lossy(reflector_version);
+    if format_version != FORMAT_VERSION || reflector_version != REFLECTOR_VERSION {
+        return Err(StubsError::UnsupportedVersion(format_version, reflector_version.into_owned()));
+    }
+
+    let mut sections = vec![];
+    while !rest.is_empty() {
+        let (length, remaining) = rest.split_first_chunk::<8>().ok_or(StubsError::InvalidHeader)?;
+        let (section, remaining) =
+            remaining.split_at_checked(u64::from_le_bytes(*length) as usize).ok_or(StubsError::InvalidHeader)?;
+
+        sections.push(section);
+        rest = remaining;
+    }
+
+    let Some((table, chunks)) = sections.split_first() else {
+        return Err(StubsError::InvalidHeader);
+    };
+
+    let strings: Vec<String> = decompress(table)?;
+    let threads = std::thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+
+    std::thread::scope(|scope| {
+        let tasks = chunks
+            .chunks(chunks.len().div_ceil(threads).max(1))
+            .map(|chunks| {
+                let strings = &strings;
+
+                scope.spawn(move || {
+                    interner.with_string_table_deserialization(strings, || {
+                        chunks
+                            .iter()
+                            .map(|chunk| decompress::<Vec<StubReflection>>(chunk))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut reflections = vec![];
+        for task in tasks {
+            let chunks = task.join().map_err(|_| StubsError::Decoding("a decoding thread panicked".to_string()))??;
+
+            reflections.extend(chunks.into_iter().flatten());
+        }
+
+        Ok(reflections)
+    })
+}
+
+/// Serializes, and compresses, the given value.
+fn compress(value: &(impl Serialize + ?Sized)) -> Result<Vec<u8>, StubsError> {
+    let mut content = vec![];
+    ciborium::into_writer(value, &mut content).map_err(|error| StubsError::Encoding(error.to_string()))?;
+
+    let mut encoder = DeflateEncoder::new(vec![], Compression::best());
+    encoder.write_all(&content).map_err(|error| StubsError::Encoding(error.to_string()))?;
+    encoder.finish().map_err(|error| StubsError::Encoding(error.to_string()))
+}
+
+/// Decompresses, and deserializes, a value compressed by [`compress`].
+fn decompress<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<T, StubsError> {
+    let mut content = vec![];
+    DeflateDecoder::new(bytes).read_to_end(&mut content).map_err(|error| StubsError::Decoding(error.to_string()))?;
+
+    ciborium::from_reader(content.as_slice()).map_err(|error| StubsError::Decoding(error.to_string()))
+}
+
+/// Merges the reflections of stubs which apply to the given version of PHP into a single reflection.
+pub fn select(
+    interner: &ThreadedInterner,
+    reflections: Vec<StubReflection>,
+    php_version: PHPVersion,
+) -> CodebaseReflection {
+    reflections
+        .into_iter()
+        .filter(|reflection| reflection.applies_to(php_version))
+        .fold(CodebaseReflection::new(), |codebase, stub| crate::merge(interner, codebase, stub.reflection))
+}
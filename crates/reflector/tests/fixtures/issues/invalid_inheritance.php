@@ -0,0 +1,43 @@
+<?php
+
+interface Shape
+{
+    public function area(): float;
+}
+
+abstract class Base implements Shape
+{
+    abstract protected function name(): string;
+
+    final public function describe(): string
+    {
+        return $this->name();
+    }
+
+    public function scale(float $factor): static
+    {
+        return $this;
+    }
+}
+
+final class Square extends Base
+{
+    private function name(): string
+    {
+        return 'square';
+    }
+
+    public function describe(): string
+    {
+        return 'a square';
+    }
+
+    public function scale(float $factor, bool $round): int
+    {
+        return 0;
+    }
+}
+
+class Cube extends Square
+{
+}
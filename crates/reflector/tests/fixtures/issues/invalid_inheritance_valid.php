@@ -0,0 +1,34 @@
+<?php
+
+interface Shape
+{
+    public function area(): float;
+}
+
+abstract class Base implements Shape
+{
+    abstract protected function name(): string;
+
+    public function scale(float $factor): Base
+    {
+        return $this;
+    }
+}
+
+class Square extends Base
+{
+    public function area(): float
+    {
+        return 1.0;
+    }
+
+    public function name(): string
+    {
+        return 'square';
+    }
+
+    public function scale(float|int $factor, bool $round = false): Square
+    {
+        return $this;
+    }
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use mago_interner::ThreadedInterner;
+use mago_names::Names;
+use mago_parser::parse_source;
+use mago_reporting::Issue;
+use mago_source::line_index::ColumnEncoding;
+use mago_source::SourceManager;
+
+/// Renders the inheritance issues of the class-likes of the given code as text, one issue per block, with
+/// 1-based lines and columns, in the order of the class-likes they are reported on.
+fn render_issues(code: &str) -> String {
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source_id = manager.insert_content("code.php".to_string(), code.to_string(), true);
+    let source = manager.load(&source_id).expect("the source can be loaded");
+    let (program, errors) = parse_source(&interner, &source);
+    assert!(errors.is_empty(), "the fixture cannot be parsed: {:?}", errors);
+
+    let names = Names::resolve(&interner, &program);
+    let mut codebase = mago_reflector::reflect(&interner, &source, &program, &names);
+    mago_reflector::populate(&interner, &mut codebase);
+
+    let mut class_likes = codebase.class_like_reflections.values().collect::<Vec<_>>();
+    class_likes.sort_by_key(|class_like| class_like.span.start.offset);
+
+    let issues = class_likes
+        .into_iter()
+        .flat_map(|class_like| mago_reflector::check_inheritance(&interner, &codebase, class_like))
+        .collect::<Vec<Issue>>();
+
+    let location = |offset: usize| {
+        let position = manager.line_column(&source_id, offset, ColumnEncoding::Utf8CodePoints).unwrap();
+
+        format!("{}:{}", position.line + 1, position.column + 1)
+    };
+
+    let mut output = String::new();
+    for issue in issues.iter() {
+        output.push_str(&format!("{}: {}\n", issue.level.to_string().to_lowercase(), issue.message));
+        for annotation in issue.annotations.iter() {
+            output.push_str(&format!(
+                "  {} {}-{}: {}\n",
+                if annotation.is_primary() { "primary" } else { "secondary" },
+                location(annotation.span.start.offset),
+                location(annotation.span.end.offset),
+                annotation.message.as_deref().unwrap_or_default(),
+            ));
+        }
+
+        if let Some(help) = issue.help.as_ref() {
+            output.push_str(&format!("  help: {}\n", help));
+        }
+    }
+
+    output
+}
+
+/// Compares the issues of every file in the `fixtures/issues` directory with the ones expected in the `.txt` file
+/// next to it, which is written instead if the `UPDATE_SNAPSHOTS` environment variable is set.
+#[test]
+pub fn test_issues_of_fixtures() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/issues");
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+
+    let mut paths: Vec<_> = std::fs::read_dir(directory)
+        .expect("the fixtures directory can be read")
+        .map(|entry| entry.expect("the fixtures directory can be read").path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "php"))
+        .collect();
+
+    paths.sort();
+    assert!(!paths.is_empty(), "no fixtures were found");
+
+    for path in paths {
+        let code = std::fs::read_to_string(&path).expect("the fixture can be read");
+        let rendered = render_issues(&code);
+        let snapshot = path.with_extension("txt");
+
+        if update {
+            std::fs::write(&snapshot, &rendered).expect("the snapshot can be written");
+
+            continue;
+        }
+
+        let expected = std::fs::read_to_string(&snapshot).unwrap_or_default();
+
+        pretty_assertions::assert_eq!(expected, rendered, "the issues of `{}` have changed", path.display());
+    }
+}
@@ -1,6 +1,7 @@
 use codespan_reporting::files::Error as FilesError;
 use serde_json::Error as JsonError;
 use std::io::Error as IoError;
+use std::path::PathBuf;
 
 use mago_source::error::SourceError;
 
@@ -10,8 +11,11 @@ pub enum ReportingError {
     JsonError(JsonError),
     FilesError(FilesError),
     IoError(IoError),
+    OutputError(PathBuf, IoError),
     InvalidTarget(String),
     InvalidFormat(String),
+    InvalidGroupBy(String),
+    InvalidPathStyle(String),
 }
 
 impl std::fmt::Display for ReportingError {
@@ -21,8 +25,11 @@ impl std::fmt::Display for ReportingError {
             Self::JsonError(error) => write!(f, "Json error: {}", error),
             Self::FilesError(error) => write!(f, "Files error: {}", error),
             Self::IoError(error) => write!(f, "IO error: {}", error),
+            Self::OutputError(path, error) => write!(f, "Failed to write report to `{}`: {}", path.display(), error),
             Self::InvalidTarget(target) => write!(f, "Invalid target: {}", target),
             Self::InvalidFormat(format) => write!(f, "Invalid format: {}", format),
+            Self::InvalidGroupBy(group_by) => write!(f, "Invalid grouping: {}", group_by),
+            Self::InvalidPathStyle(paths) => write!(f, "Invalid path style: {}", paths),
         }
     }
 }
@@ -34,8 +41,11 @@ impl std::error::Error for ReportingError {
             Self::JsonError(error) => Some(error),
             Self::FilesError(error) => Some(error),
             Self::IoError(error) => Some(error),
+            Self::OutputError(_, error) => Some(error),
             Self::InvalidTarget(_) => None,
             Self::InvalidFormat(_) => None,
+            Self::InvalidGroupBy(_) => None,
+            Self::InvalidPathStyle(_) => None,
         }
     }
 }
@@ -1,13 +1,17 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+
 use termcolor::WriteColor;
 
 use mago_interner::ThreadedInterner;
+use mago_source::line_index::ColumnEncoding;
 use mago_source::HasSource;
 use mago_source::SourceManager;
 
 use crate::error::ReportingError;
 use crate::internal::emitter::utils::long_message;
+use crate::internal::emitter::utils::source_name;
 use crate::internal::emitter::utils::xml_encode;
+use crate::reporter::ReportingOptions;
 use crate::IssueCollection;
 use crate::Level;
 
@@ -16,20 +20,25 @@ pub fn checkstyle_format(
     sources: &SourceManager,
     interner: &ThreadedInterner,
     issues: IssueCollection,
+    options: &ReportingOptions,
 ) -> Result<Option<Level>, ReportingError> {
     let highest_level = issues.get_highest_level();
 
     // Group issues by file
-    let mut issues_by_file: HashMap<String, Vec<String>> = HashMap::new();
+    let mut issues_by_file: BTreeMap<String, Vec<String>> = BTreeMap::new();
 
     for issue in issues.iter() {
         let (filename, line, column) = match issue.annotations.iter().find(|annotation| annotation.is_primary()) {
             Some(annotation) => {
                 let source = sources.load(&annotation.span.source())?;
 
-                let filename = interner.lookup(&source.identifier.0).to_string();
-                let line = source.line_number(annotation.span.start.offset) + 1;
-                let column = source.column_number(annotation.span.start.offset) + 1;
+                let filename = source_name(&source, interner, options);
+                let position = sources.line_column(
+                    &source.identifier,
+                    annotation.span.start.offset,
+                    ColumnEncoding::Utf8CodePoints,
+                )?;
+                let (line, column) = (position.line + 1, position.column + 1);
 
                 (filename, line, column)
             }
@@ -43,10 +52,20 @@ pub fn checkstyle_format(
         };
 
         let message = xml_encode(long_message(issue));
-        let error_tag = format!(
-            "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" />",
-            line, column, severity, message
-        );
+        let error_tag = match issue.code.as_ref() {
+            Some(code) => format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"mago.{}\" />",
+                line,
+                column,
+                severity,
+                message,
+                xml_encode(code)
+            ),
+            None => format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" />",
+                line, column, severity, message
+            ),
+        };
 
         issues_by_file.entry(filename).or_default().push(error_tag);
     }
@@ -70,3 +89,55 @@ pub fn checkstyle_format(
 
     Ok(highest_level)
 }
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use crate::Annotation;
+    use crate::Issue;
+
+    use super::*;
+
+    #[test]
+    fn test_checkstyle_output() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let b = manager.insert_content("src/b.php".to_string(), "<?php\n\neval('');".to_string(), true);
+        let a = manager.insert_content("src/a.php".to_string(), "<?php if ($a < 1) {}".to_string(), true);
+        let span = |source, start, end| Span::new(Position::new(source, start), Position::new(source, end));
+
+        let issues = IssueCollection::from([
+            Issue::error("Unsafe use of `eval` construct.")
+                .with_code("safety/no-eval")
+                .with_annotation(Annotation::primary(span(b, 7, 11))),
+            Issue::warning("Comparing `$a < 1`.").with_annotation(Annotation::primary(span(a, 10, 16))),
+            Issue::help("An issue on the first file.")
+                .with_code("strictness/a&b")
+                .with_annotation(Annotation::primary(span(a, 6, 8))),
+        ]);
+
+        let mut writer = NoColor::new(Vec::new());
+        checkstyle_format(&mut writer, &manager, &interner, issues, &ReportingOptions::default()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            [
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<checkstyle>"#,
+                r#"  <file name="src/a.php">"#,
+                r#"    <error line="1" column="11" severity="warning" message="Comparing `$a &lt; 1`." />"#,
+                r#"    <error line="1" column="7" severity="info" message="An issue on the first file." source="mago.strictness/a&amp;b" />"#,
+                r#"  </file>"#,
+                r#"  <file name="src/b.php">"#,
+                r#"    <error line="3" column="1" severity="error" message="Unsafe use of `eval` construct." source="mago.safety/no-eval" />"#,
+                r#"  </file>"#,
+                r#"</checkstyle>"#,
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -1,23 +1,36 @@
 use std::cmp::Ordering;
 use std::ops::Range;
 
+use ahash::HashMap;
 use codespan_reporting::diagnostic::Diagnostic;
 use codespan_reporting::diagnostic::Label;
 use codespan_reporting::diagnostic::LabelStyle;
 use codespan_reporting::diagnostic::Severity;
+use codespan_reporting::files::column_index;
 use codespan_reporting::files::Error;
 use codespan_reporting::files::Files;
 use codespan_reporting::term;
 use codespan_reporting::term::Config;
 use codespan_reporting::term::DisplayStyle;
+use termcolor::Buffer;
+use termcolor::Color;
+use termcolor::ColorSpec;
 use termcolor::WriteColor;
 
+use mago_fixer::SafetyClassification;
 use mago_interner::ThreadedInterner;
 use mago_source::error::SourceError;
+use mago_source::line_index::ColumnEncoding;
+use mago_source::HasSource;
+use mago_source::Source;
 use mago_source::SourceIdentifier;
 use mago_source::SourceManager;
 
 use crate::error::ReportingError;
+use crate::internal::emitter::utils::fix_note;
+use crate::internal::emitter::utils::source_name;
+use crate::reporter::GroupBy;
+use crate::reporter::ReportingOptions;
 use crate::Annotation;
 use crate::AnnotationKind;
 use crate::Issue;
@@ -29,14 +42,16 @@ pub fn rich_format(
     sources: &SourceManager,
     interner: &ThreadedInterner,
     issues: IssueCollection,
+    options: &ReportingOptions,
 ) -> Result<Option<Level>, ReportingError> {
-    codespan_format_with_config(
-        writer,
-        sources,
-        interner,
-        issues,
-        Config { display_style: DisplayStyle::Rich, ..Default::default() },
-    )
+    match options.group_by {
+        GroupBy::File => {
+            codespan_format_with_config(writer, sources, interner, issues, config(DisplayStyle::Rich, options), options)
+        }
+        group_by => {
+            grouped_format(writer, sources, interner, issues, group_by, config(DisplayStyle::Rich, options), options)
+        }
+    }
 }
 
 pub fn medium_format(
@@ -44,14 +59,9 @@ pub fn medium_format(
     sources: &SourceManager,
     interner: &ThreadedInterner,
     issues: IssueCollection,
+    options: &ReportingOptions,
 ) -> Result<Option<Level>, ReportingError> {
-    codespan_format_with_config(
-        writer,
-        sources,
-        interner,
-        issues,
-        Config { display_style: DisplayStyle::Medium, ..Default::default() },
-    )
+    codespan_format_with_config(writer, sources, interner, issues, config(DisplayStyle::Medium, options), options)
 }
 
 pub fn short_format(
@@ -59,14 +69,23 @@ pub fn short_format(
     sources: &SourceManager,
     interner: &ThreadedInterner,
     issues: IssueCollection,
+    options: &ReportingOptions,
 ) -> Result<Option<Level>, ReportingError> {
-    codespan_format_with_config(
-        writer,
-        sources,
-        interner,
-        issues,
-        Config { display_style: DisplayStyle::Short, ..Default::default() },
-    )
+    codespan_format_with_config(writer, sources, interner, issues, config(DisplayStyle::Short, options), options)
+}
+
+fn config(display_style: DisplayStyle, options: &ReportingOptions) -> Config {
+    let mut config = Config { display_style, ..Default::default() };
+
+    if let Some(context_lines) = options.context_lines {
+        config.before_label_lines = context_lines;
+        config.after_label_lines = context_lines;
+        // Always render multi-line spans in full, regardless of the number of context lines.
+        config.start_context_lines = usize::MAX;
+        config.end_context_lines = usize::MAX;
+    }
+
+    config
 }
 
 fn codespan_format_with_config(
@@ -75,116 +94,452 @@ fn codespan_format_with_config(
     interner: &ThreadedInterner,
     issues: IssueCollection,
     config: Config,
+    options: &ReportingOptions,
 ) -> Result<Option<Level>, ReportingError> {
-    let files = SourceManagerFile(sources, interner);
+    let highest_level = issues.get_highest_level();
+    let footer = footer(&issues);
+
+    for mut issue in issues {
+        let help_uri = issue.help_uri.take();
+        let files = SourceManagerFile::for_issue(sources, interner, options, &mut issue)?;
+        let diagnostic: Diagnostic<SourceIdentifier> = issue.into();
+
+        match help_uri {
+            Some(help_uri) => emit_with_docs_trailer(writer, &config, &files, &diagnostic, &help_uri)?,
+            None => term::emit(writer, &config, &files, &diagnostic)?,
+        }
+    }
+
+    if let Some(footer) = footer {
+        term::emit(writer, &config, &SourceManagerFile::new(sources, interner, options), &footer)?;
+    }
+
+    Ok(highest_level)
+}
 
+/// Renders the issues as a condensed list per rule or per level, instead of one code frame per issue.
+fn grouped_format(
+    writer: &mut dyn WriteColor,
+    sources: &SourceManager,
+    interner: &ThreadedInterner,
+    issues: IssueCollection,
+    group_by: GroupBy,
+    config: Config,
+    options: &ReportingOptions,
+) -> Result<Option<Level>, ReportingError> {
     let highest_level = issues.get_highest_level();
-    let mut errors = 0;
-    let mut warnings = 0;
-    let mut notes = 0;
-    let mut help = 0;
-    let mut suggestions = 0;
-
-    for issue in issues {
-        match &issue.level {
-            Level::Note => {
-                notes += 1;
-            }
-            Level::Help => {
-                help += 1;
-            }
-            Level::Warning => {
-                warnings += 1;
-            }
-            Level::Error => {
-                errors += 1;
-            }
+    let footer = footer(&issues);
+
+    for (key, group) in issues.group_by(group_by, interner) {
+        let level = group.get_highest_level().unwrap_or(Level::Note);
+
+        writer.set_color(ColorSpec::new().set_bold(true).set_fg(Some(level_color(level))))?;
+        write!(writer, "{}", key)?;
+        writer.set_color(ColorSpec::new().set_dimmed(true))?;
+        write!(writer, " ({} issue(s))", group.len())?;
+        writer.reset()?;
+        writeln!(writer)?;
+
+        let help_uri = match group_by {
+            GroupBy::Rule => group.iter().find_map(|issue| issue.help_uri.as_deref()),
+            _ => None,
+        };
+
+        if let Some(help_uri) = help_uri {
+            writer.set_color(ColorSpec::new().set_dimmed(true))?;
+            write!(writer, "docs: {}", help_uri)?;
+            writer.reset()?;
+            writeln!(writer)?;
         }
 
-        if !issue.suggestions.is_empty() {
-            suggestions += 1;
+        for issue in group.iter() {
+            let location = match issue.annotations.iter().find(|annotation| annotation.is_primary()) {
+                Some(annotation) => {
+                    let source = sources.load(&annotation.span.source())?;
+                    let position = sources.line_column(
+                        &source.identifier,
+                        annotation.span.start.offset,
+                        ColumnEncoding::Utf8CodePoints,
+                    )?;
+
+                    format!("{}:{}:{}", source_name(&source, interner, options), position.line + 1, position.column + 1)
+                }
+                None => "<unknown>".to_string(),
+            };
+
+            write!(writer, "  {} ", location)?;
+            match group_by {
+                GroupBy::Level => {
+                    if let Some(code) = issue.code.as_deref() {
+                        write!(writer, "[{}] ", code)?;
+                    }
+                }
+                _ => {
+                    writer.set_color(ColorSpec::new().set_fg(Some(level_color(issue.level))))?;
+                    write!(writer, "{}:", issue.level.to_string().to_lowercase())?;
+                    writer.reset()?;
+                    write!(writer, " ")?;
+                }
+            }
+
+            writeln!(writer, "{}", issue.message.replace('\n', " "))?;
         }
 
-        let diagnostic: Diagnostic<SourceIdentifier> = issue.into();
+        writeln!(writer)?;
+    }
 
-        term::emit(writer, &config, &files, &diagnostic)?;
+    if let Some(footer) = footer {
+        term::emit(writer, &config, &SourceManagerFile::new(sources, interner, options), &footer)?;
     }
 
-    if let Some(highest_level) = highest_level {
-        let total_issues = errors + warnings + notes + help;
-        let mut message_notes = vec![];
-        if errors > 0 {
-            message_notes.push(format!("{} error(s)", errors));
-        }
+    Ok(highest_level)
+}
 
-        if warnings > 0 {
-            message_notes.push(format!("{} warning(s)", warnings));
-        }
+fn level_color(level: Level) -> Color {
+    match level {
+        Level::Note => Color::Green,
+        Level::Help => Color::Cyan,
+        Level::Warning => Color::Yellow,
+        Level::Error => Color::Red,
+    }
+}
 
-        if notes > 0 {
-            message_notes.push(format!("{} note(s)", notes));
+/// Builds the diagnostic summarizing the number of issues per level, if there are any issues.
+fn footer(issues: &IssueCollection) -> Option<Diagnostic<SourceIdentifier>> {
+    let highest_level = issues.get_highest_level()?;
+
+    let errors = issues.get_level_count(Level::Error);
+    let warnings = issues.get_level_count(Level::Warning);
+    let notes = issues.get_level_count(Level::Note);
+    let help = issues.get_level_count(Level::Help);
+    let (mut safe, mut potentially_unsafe, mut unsafe_) = (0, 0, 0);
+    for safety in issues.iter().filter_map(|issue| issue.get_fix_safety()) {
+        match safety {
+            SafetyClassification::Safe => safe += 1,
+            SafetyClassification::PotentiallyUnsafe => potentially_unsafe += 1,
+            SafetyClassification::Unsafe => unsafe_ += 1,
         }
+    }
+
+    let total_issues = errors + warnings + notes + help;
+    let mut message_notes = vec![];
+    if errors > 0 {
+        message_notes.push(format!("{} error(s)", errors));
+    }
+
+    if warnings > 0 {
+        message_notes.push(format!("{} warning(s)", warnings));
+    }
+
+    if notes > 0 {
+        message_notes.push(format!("{} note(s)", notes));
+    }
+
+    if help > 0 {
+        message_notes.push(format!("{} help message(s)", help));
+    }
 
-        if help > 0 {
-            message_notes.push(format!("{} help message(s)", help));
+    let mut diagnostic: Diagnostic<SourceIdentifier> = Diagnostic::new(highest_level.into()).with_message(format!(
+        "found {} issues: {}",
+        total_issues,
+        message_notes.join(", ")
+    ));
+
+    let fixable = safe + potentially_unsafe + unsafe_;
+    if fixable > 0 {
+        diagnostic = diagnostic.with_notes(vec![format!(
+            "{} issues contain auto-fix suggestions ({} safe, {} potentially unsafe, {} unsafe)",
+            fixable, safe, potentially_unsafe, unsafe_
+        )]);
+    }
+
+    Some(diagnostic)
+}
+
+/// Lines longer than this many bytes are truncated around the annotated span, so that
+/// minified sources do not flood the terminal.
+const MAXIMUM_LINE_WIDTH: usize = 240;
+
+const ELLIPSIS: &str = "…";
+
+struct SourceManagerFile<'a> {
+    manager: &'a SourceManager,
+    interner: &'a ThreadedInterner,
+    options: &'a ReportingOptions,
+    truncated: HashMap<SourceIdentifier, TruncatedSource<'a>>,
+}
+
+impl<'a> SourceManagerFile<'a> {
+    fn new(manager: &'a SourceManager, interner: &'a ThreadedInterner, options: &'a ReportingOptions) -> Self {
+        Self { manager, interner, options, truncated: HashMap::default() }
+    }
+
+    /// Creates the files used to render the given issue, truncating long lines in the sources it annotates.
+    ///
+    /// The annotations of the issue are moved to their position within the truncated sources.
+    fn for_issue(
+        manager: &'a SourceManager,
+        interner: &'a ThreadedInterner,
+        options: &'a ReportingOptions,
+        issue: &mut Issue,
+    ) -> Result<Self, ReportingError> {
+        let mut files = Self::new(manager, interner, options);
+
+        let mut offsets: HashMap<SourceIdentifier, Vec<usize>> = HashMap::default();
+        for annotation in issue.annotations.iter() {
+            offsets.entry(annotation.span.start.source).or_default().push(annotation.span.start.offset);
         }
 
-        let mut diagnostic: Diagnostic<SourceIdentifier> = Diagnostic::new(highest_level.into()).with_message(format!(
-            "found {} issues: {}",
-            total_issues,
-            message_notes.join(", ")
-        ));
+        for (source_id, offsets) in offsets {
+            let source = manager.load(&source_id)?;
 
-        if suggestions > 0 {
-            diagnostic = diagnostic.with_notes(vec![format!("{} issues contain auto-fix suggestions", suggestions)]);
+            if let Some(truncated) = TruncatedSource::new(interner.lookup(&source.content), &source.lines, &offsets) {
+                files.truncated.insert(source_id, truncated);
+            }
+        }
+
+        for annotation in issue.annotations.iter_mut() {
+            if let Some(truncated) = files.truncated.get(&annotation.span.start.source) {
+                annotation.span.start.offset = truncated.map_offset(annotation.span.start.offset);
+                annotation.span.end.offset = truncated.map_offset(annotation.span.end.offset);
+            }
         }
 
-        term::emit(writer, &config, &files, &diagnostic)?;
+        Ok(files)
     }
 
-    Ok(highest_level)
+    fn load(&self, file_id: SourceIdentifier) -> Result<Source, Error> {
+        self.manager.load(&file_id).map_err(file_error)
+    }
 }
 
-struct SourceManagerFile<'a>(&'a SourceManager, &'a ThreadedInterner);
+fn file_error(error: SourceError) -> Error {
+    match error {
+        SourceError::UnavailableSource(_) => Error::FileMissing,
+        SourceError::IOError(error) => Error::Io(error),
+        SourceError::InvalidUtf8(path) => Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("invalid utf-8 sequence in {:?}", path),
+        )),
+    }
+}
 
-impl<'a> Files<'a> for SourceManagerFile<'_> {
+impl<'a> Files<'a> for SourceManagerFile<'a> {
     type FileId = SourceIdentifier;
-    type Name = &'a str;
+    type Name = String;
     type Source = &'a str;
 
-    fn name(&'a self, file_id: SourceIdentifier) -> Result<&'a str, Error> {
-        self.0.load(&file_id).map(|source| self.1.lookup(&source.identifier.value())).map_err(|e| match e {
-            SourceError::UnavailableSource(_) => Error::FileMissing,
-            SourceError::IOError(error) => Error::Io(error),
-        })
+    fn name(&'a self, file_id: SourceIdentifier) -> Result<String, Error> {
+        self.load(file_id).map(|source| source_name(&source, self.interner, self.options))
     }
 
     fn source(&'a self, file_id: SourceIdentifier) -> Result<&'a str, Error> {
-        self.0.load(&file_id).map(|source| self.1.lookup(&source.content)).map_err(|e| match e {
-            SourceError::UnavailableSource(_) => Error::FileMissing,
-            SourceError::IOError(error) => Error::Io(error),
-        })
+        if let Some(truncated) = self.truncated.get(&file_id) {
+            return Ok(&truncated.content);
+        }
+
+        self.load(file_id).map(|source| self.interner.lookup(&source.content))
     }
 
-    fn line_index(&self, file_id: SourceIdentifier, byte_index: usize) -> Result<usize, Error> {
-        let source = self.0.load(&file_id).map_err(|e| match e {
-            SourceError::UnavailableSource(_) => Error::FileMissing,
-            SourceError::IOError(error) => Error::Io(error),
-        })?;
+    fn line_index(&'a self, file_id: SourceIdentifier, byte_index: usize) -> Result<usize, Error> {
+        if let Some(truncated) = self.truncated.get(&file_id) {
+            return Ok(truncated.line_index(byte_index));
+        }
 
-        Ok(source.line_number(byte_index))
+        Ok(self.load(file_id)?.line_number(byte_index))
     }
 
-    fn line_range(&self, file_id: SourceIdentifier, line_index: usize) -> Result<Range<usize>, Error> {
-        let source = self.0.load(&file_id).map_err(|e| match e {
-            SourceError::UnavailableSource(_) => Error::FileMissing,
-            SourceError::IOError(error) => Error::Io(error),
-        })?;
+    fn column_number(
+        &'a self,
+        file_id: SourceIdentifier,
+        line_index: usize,
+        byte_index: usize,
+    ) -> Result<usize, Error> {
+        if let Some(truncated) = self.truncated.get(&file_id) {
+            return Ok(truncated.column_number(line_index, byte_index));
+        }
+
+        let position =
+            self.manager.line_column(&file_id, byte_index, ColumnEncoding::Utf8CodePoints).map_err(file_error)?;
+
+        Ok(position.column + 1)
+    }
+
+    fn line_range(&'a self, file_id: SourceIdentifier, line_index: usize) -> Result<Range<usize>, Error> {
+        if let Some(truncated) = self.truncated.get(&file_id) {
+            return codespan_line_range(&truncated.lines, truncated.content.len(), line_index);
+        }
+
+        let source = self.load(file_id)?;
 
         codespan_line_range(&source.lines, source.size, line_index)
     }
 }
 
+/// A copy of a source in which every line longer than [`MAXIMUM_LINE_WIDTH`] is cut down to a
+/// window around the first annotated offset on that line, with an ellipsis marking the cut.
+struct TruncatedSource<'a> {
+    original: &'a str,
+    content: String,
+    lines: Vec<usize>,
+    mappings: Vec<LineMapping>,
+}
+
+struct LineMapping {
+    /// The byte range of the line in the original source, excluding the line terminator.
+    original: Range<usize>,
+    /// The start of the line in the truncated source.
+    start: usize,
+    /// The byte range of the original line that is kept, if the line was truncated.
+    window: Option<Range<usize>>,
+}
+
+impl<'a> TruncatedSource<'a> {
+    /// Truncates the long lines of the given source, or returns `None` if it has no long lines.
+    fn new(original: &'a str, lines: &[usize], offsets: &[usize]) -> Option<Self> {
+        let line_ends = |index: usize| {
+            let start = lines[index];
+            let next = lines.get(index + 1).copied().unwrap_or(original.len());
+            let end = start + original[start..next].trim_end_matches(['\n', '\r']).len();
+
+            (start, end, next)
+        };
+
+        if !(0..lines.len()).any(|index| {
+            let (start, end, _) = line_ends(index);
+
+            end - start > MAXIMUM_LINE_WIDTH
+        }) {
+            return None;
+        }
+
+        let mut content = String::with_capacity(original.len().min(lines.len() * (MAXIMUM_LINE_WIDTH + 8)));
+        let mut truncated_lines = Vec::with_capacity(lines.len());
+        let mut mappings = Vec::with_capacity(lines.len());
+        for index in 0..lines.len() {
+            let (start, end, next) = line_ends(index);
+            let line_start = content.len();
+
+            let window = if end - start > MAXIMUM_LINE_WIDTH {
+                let anchor = offsets.iter().copied().filter(|offset| (start..=end).contains(offset)).min();
+                let anchor = anchor.unwrap_or(start);
+
+                let window_start =
+                    floor_char_boundary(original, anchor.saturating_sub(MAXIMUM_LINE_WIDTH / 4).max(start));
+                let window_end = floor_char_boundary(original, (window_start + MAXIMUM_LINE_WIDTH).min(end));
+
+                if window_start > start {
+                    content.push_str(ELLIPSIS);
+                }
+
+                content.push_str(&original[window_start..window_end]);
+
+                if window_end < end {
+                    content.push_str(ELLIPSIS);
+                }
+
+                content.push_str(&original[end..next]);
+
+                Some(window_start..window_end)
+            } else {
+                content.push_str(&original[start..next]);
+
+                None
+            };
+
+            truncated_lines.push(line_start);
+            mappings.push(LineMapping { original: start..end, start: line_start, window });
+        }
+
+        Some(Self { original, content, lines: truncated_lines, mappings })
+    }
+
+    /// Maps an offset in the original source to the corresponding offset in the truncated source.
+    ///
+    /// Offsets that fall outside of the kept window of a truncated line are clamped to the window.
+    fn map_offset(&self, offset: usize) -> usize {
+        let index = self.mappings.partition_point(|mapping| mapping.original.start <= offset).saturating_sub(1);
+        let mapping = &self.mappings[index];
+
+        let Some(window) = &mapping.window else {
+            return mapping.start + offset.saturating_sub(mapping.original.start);
+        };
+
+        let prefix = if window.start > mapping.original.start { ELLIPSIS.len() } else { 0 };
+        let suffix = if window.end < mapping.original.end { ELLIPSIS.len() } else { 0 };
+
+        if offset > mapping.original.end {
+            // The offset points into the line terminator.
+            mapping.start + prefix + window.len() + suffix + (offset - mapping.original.end)
+        } else {
+            mapping.start + prefix + (offset.clamp(window.start, window.end) - window.start)
+        }
+    }
+
+    fn line_index(&self, byte_index: usize) -> usize {
+        self.lines.partition_point(|start| *start <= byte_index).saturating_sub(1)
+    }
+
+    /// Returns the column of the given offset in the truncated source, as it was in the original source.
+    fn column_number(&self, line_index: usize, byte_index: usize) -> usize {
+        let Some(mapping) = self.mappings.get(line_index) else {
+            return 1;
+        };
+
+        let offset = match &mapping.window {
+            Some(window) => {
+                let prefix = if window.start > mapping.original.start { ELLIPSIS.len() } else { 0 };
+
+                window.start + byte_index.saturating_sub(mapping.start + prefix).min(window.len())
+            }
+            None => mapping.original.start + byte_index.saturating_sub(mapping.start),
+        };
+
+        column_index(self.original, mapping.original.start..mapping.original.end, offset) + 1
+    }
+}
+
+/// Emits the given diagnostic, followed by a dimmed `docs: <uri>` line.
+///
+/// The rich display style terminates every diagnostic with an empty line, so the diagnostic is
+/// rendered into a buffer first, allowing the trailer to be placed before that empty line.
+fn emit_with_docs_trailer(
+    writer: &mut dyn WriteColor,
+    config: &Config,
+    files: &SourceManagerFile<'_>,
+    diagnostic: &Diagnostic<SourceIdentifier>,
+    help_uri: &str,
+) -> Result<(), ReportingError> {
+    let mut buffer = if writer.supports_color() { Buffer::ansi() } else { Buffer::no_color() };
+    term::emit(&mut buffer, config, files, diagnostic)?;
+
+    let mut rendered = buffer.as_slice();
+    let trailing_empty_line = matches!(config.display_style, DisplayStyle::Rich) && rendered.ends_with(b"\n\n");
+    if trailing_empty_line {
+        rendered = &rendered[..rendered.len() - 1];
+    }
+
+    writer.write_all(rendered)?;
+    writer.set_color(ColorSpec::new().set_dimmed(true))?;
+    write!(writer, "docs: {}", help_uri)?;
+    writer.reset()?;
+    writeln!(writer)?;
+
+    if trailing_empty_line {
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+fn floor_char_boundary(content: &str, mut index: usize) -> usize {
+    while !content.is_char_boundary(index) {
+        index -= 1;
+    }
+
+    index
+}
+
 fn codespan_line_start(lines: &[usize], size: usize, line_index: usize) -> Result<usize, Error> {
     match line_index.cmp(&lines.len()) {
         Ordering::Less => Ok(lines.get(line_index).cloned().expect("failed despite previous check")),
@@ -234,6 +589,7 @@ impl From<Level> for Severity {
 
 impl From<Issue> for Diagnostic<SourceIdentifier> {
     fn from(issue: Issue) -> Diagnostic<SourceIdentifier> {
+        let fix = fix_note(&issue);
         let mut diagnostic = Diagnostic::new(issue.level.into()).with_message(issue.message);
 
         if let Some(code) = issue.code {
@@ -252,6 +608,10 @@ impl From<Issue> for Diagnostic<SourceIdentifier> {
             diagnostic.notes.push(format!("Help: {}", help));
         }
 
+        if let Some(fix) = fix {
+            diagnostic.notes.push(fix);
+        }
+
         if let Some(link) = issue.link {
             diagnostic.notes.push(format!("See: {}", link));
         }
@@ -259,3 +619,84 @@ impl From<Issue> for Diagnostic<SourceIdentifier> {
         diagnostic
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use crate::internal::emitter::Emitter;
+    use crate::reporter::ReportingFormat;
+
+    use super::*;
+
+    fn line_starts(content: &str) -> Vec<usize> {
+        std::iter::once(0).chain(content.match_indices('\n').map(|(index, _)| index + 1)).collect()
+    }
+
+    #[test]
+    fn test_short_lines_are_not_truncated() {
+        let content = "<?php\n\necho 1;\n";
+
+        assert!(TruncatedSource::new(content, &line_starts(content), &[8]).is_none());
+    }
+
+    #[test]
+    fn test_long_line_is_truncated_around_offset() {
+        let long_line = "x".repeat(1000) + "==" + &"y".repeat(1000);
+        let content = format!("<?php\n{long_line}\necho 1;\n");
+        let offset = 6 + 1000;
+
+        let truncated = TruncatedSource::new(&content, &line_starts(&content), &[offset]).unwrap();
+        let mapped = truncated.map_offset(offset);
+
+        assert_eq!(truncated.lines.len(), 4);
+        assert_eq!(&truncated.content[mapped..mapped + 2], "==");
+        assert_eq!(truncated.column_number(1, mapped), 1001);
+        assert!(truncated.content.lines().all(|line| line.len() <= MAXIMUM_LINE_WIDTH + 2 * ELLIPSIS.len()));
+
+        // Lines following the truncated line keep their content and columns.
+        let echo = truncated.map_offset(content.find("echo").unwrap());
+        assert_eq!(&truncated.content[echo..echo + 4], "echo");
+        assert_eq!(truncated.column_number(2, echo), 1);
+    }
+
+    fn render_with_help_uri(format: ReportingFormat) -> String {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("a.php".to_string(), "<?php eval('');".to_string(), true);
+
+        let issue = Issue::error("Unsafe use of `eval`.")
+            .with_code("safety/no-eval")
+            .with_help_uri("https://www.php.net/manual/en/function.eval.php")
+            .with_annotation(Annotation::primary(Span::new(Position::new(source, 6), Position::new(source, 10))));
+
+        let mut writer = NoColor::new(Vec::new());
+        format
+            .emit(&mut writer, &manager, &interner, IssueCollection::from(vec![issue]), &ReportingOptions::default())
+            .unwrap();
+
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_docs_trailer_is_placed_before_the_empty_line_of_rich_diagnostics() {
+        let output = render_with_help_uri(ReportingFormat::Rich);
+
+        assert!(
+            output.contains("\ndocs: https://www.php.net/manual/en/function.eval.php\n\n"),
+            "unexpected output: {output}"
+        );
+        assert!(!output.contains("\n\ndocs:"), "unexpected output: {output}");
+    }
+
+    #[test]
+    fn test_docs_trailer_follows_short_diagnostics() {
+        let output = render_with_help_uri(ReportingFormat::Short);
+        let mut lines = output.lines();
+
+        assert!(lines.next().unwrap().ends_with("Unsafe use of `eval`."));
+        assert_eq!(lines.next(), Some("docs: https://www.php.net/manual/en/function.eval.php"));
+    }
+}
@@ -1,14 +1,17 @@
 use std::cmp::Ordering;
 
 use ahash::HashMap;
+use ahash::HashSet;
 use termcolor::Color;
 use termcolor::ColorSpec;
 use termcolor::WriteColor;
 
 use mago_interner::ThreadedInterner;
+use mago_source::HasSource;
 use mago_source::SourceManager;
 
 use crate::error::ReportingError;
+use crate::reporter::ReportingOptions;
 use crate::IssueCollection;
 use crate::Level;
 
@@ -17,33 +20,55 @@ pub fn count_format(
     _sources: &SourceManager,
     _interner: &ThreadedInterner,
     issues: IssueCollection,
+    _options: &ReportingOptions,
 ) -> Result<Option<Level>, ReportingError> {
     let highest_level = issues.get_highest_level();
 
-    // Count occurrences of each issue level
+    // Count occurrences of each rule, per level
     let mut counts = HashMap::default();
+    let mut files = HashSet::default();
     issues.iter().for_each(|issue| {
-        *counts.entry(issue.level).or_insert(0) += 1;
+        *counts.entry((issue.code.as_deref().unwrap_or("other"), issue.level)).or_insert(0) += 1;
+
+        if let Some(annotation) = issue.annotations.iter().find(|annotation| annotation.is_primary()) {
+            files.insert(annotation.span.source());
+        }
     });
 
     let mut counts_vec: Vec<_> = counts.into_iter().collect();
-    counts_vec.sort_by(|(level_a, count_a), (level_b, count_b)| match count_b.cmp(count_a) {
-        Ordering::Equal => level_a.cmp(level_b),
+    counts_vec.sort_by(|((rule_a, level_a), count_a), ((rule_b, level_b), count_b)| match count_b.cmp(count_a) {
+        Ordering::Equal => level_b.cmp(level_a).then_with(|| rule_a.cmp(rule_b)),
         other => other,
     });
 
+    let count_width = counts_vec.first().map(|(_, count)| count.to_string().len()).unwrap_or(1);
+
     // Write counts to the writer
-    for (level, count) in counts_vec {
+    for ((rule, level), count) in counts_vec {
         let color = level_color(&level);
         let mut spec = ColorSpec::new();
 
+        write!(writer, "{:>width$}  ", count, width = count_width)?;
         writer.set_color(spec.set_fg(Some(color)).set_bold(true))?;
-        write!(writer, "{level}: ")?;
-        writer.set_color(spec.set_bold(false))?;
-        writeln!(writer, "{}", count)?;
+        write!(writer, "{:<7}", level.to_string().to_lowercase())?;
         writer.reset()?;
+        writeln!(writer, "  {}", rule)?;
     }
 
+    if !issues.is_empty() {
+        writeln!(writer)?;
+    }
+
+    writeln!(
+        writer,
+        "{} error(s), {} warning(s), {} help message(s), {} note(s) across {} file(s)",
+        issues.get_level_count(Level::Error),
+        issues.get_level_count(Level::Warning),
+        issues.get_level_count(Level::Help),
+        issues.get_level_count(Level::Note),
+        files.len()
+    )?;
+
     Ok(highest_level)
 }
 
@@ -55,3 +80,66 @@ fn level_color(level: &Level) -> Color {
         Level::Help => Color::Green,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use crate::Annotation;
+    use crate::Issue;
+
+    use super::*;
+
+    fn render(issues: IssueCollection, manager: &SourceManager, interner: &ThreadedInterner) -> String {
+        let mut writer = NoColor::new(Vec::new());
+        count_format(&mut writer, manager, interner, issues, &ReportingOptions::default()).unwrap();
+
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_count_output_shows_totals_per_rule() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let a = manager.insert_content("a.php".to_string(), "<?php eval('');".to_string(), true);
+        let b = manager.insert_content("b.php".to_string(), "<?php eval('');".to_string(), true);
+        let annotation = |source| Annotation::primary(Span::new(Position::new(source, 6), Position::new(source, 10)));
+
+        let mut issues = vec![];
+        for _ in 0..10 {
+            issues
+                .push(Issue::error("Unsafe use of `eval`.").with_code("safety/no-eval").with_annotation(annotation(a)));
+        }
+
+        issues.push(Issue::warning("Unsafe use of `eval`.").with_code("safety/no-eval").with_annotation(annotation(b)));
+        issues.push(Issue::warning("A warning.").with_code("best-practices/a").with_annotation(annotation(b)));
+        issues.push(Issue::note("A note without a code."));
+
+        assert_eq!(
+            render(IssueCollection::from(issues), &manager, &interner),
+            [
+                "10  error    safety/no-eval",
+                " 1  warning  best-practices/a",
+                " 1  warning  safety/no-eval",
+                " 1  note     other",
+                "",
+                "10 error(s), 2 warning(s), 0 help message(s), 1 note(s) across 2 file(s)",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_count_output_without_issues() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+
+        assert_eq!(
+            render(IssueCollection::new(), &manager, &interner),
+            "0 error(s), 0 warning(s), 0 help message(s), 0 note(s) across 0 file(s)\n"
+        );
+    }
+}
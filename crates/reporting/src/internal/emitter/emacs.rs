@@ -1,10 +1,13 @@
 use termcolor::WriteColor;
 
 use mago_interner::ThreadedInterner;
+use mago_source::line_index::ColumnEncoding;
 use mago_source::HasSource;
 use mago_source::SourceManager;
 
 use crate::error::ReportingError;
+use crate::internal::emitter::utils::source_name;
+use crate::reporter::ReportingOptions;
 use crate::IssueCollection;
 use crate::Level;
 
@@ -13,17 +16,23 @@ pub fn emacs_format(
     sources: &SourceManager,
     interner: &ThreadedInterner,
     issues: IssueCollection,
+    options: &ReportingOptions,
 ) -> Result<Option<Level>, ReportingError> {
     let highest_level = issues.get_highest_level();
 
+    let mut entries = vec![];
     for issue in issues.iter() {
         let (file_path, line, column) = match issue.annotations.iter().find(|annotation| annotation.is_primary()) {
             Some(annotation) => {
                 let source = sources.load(&annotation.span.source())?;
 
-                let file_path = interner.lookup(&source.identifier.0).to_string();
-                let line = source.line_number(annotation.span.start.offset) + 1;
-                let column = source.column_number(annotation.span.start.offset) + 1;
+                let file_path = source_name(&source, interner, options);
+                let position = sources.line_column(
+                    &source.identifier,
+                    annotation.span.start.offset,
+                    ColumnEncoding::Utf8CodePoints,
+                )?;
+                let (line, column) = (position.line + 1, position.column + 1);
 
                 (file_path, line, column)
             }
@@ -32,20 +41,72 @@ pub fn emacs_format(
 
         let severity = match issue.level {
             Level::Error => "error",
-            Level::Warning | Level::Note | Level::Help => "warning",
+            Level::Warning => "warning",
+            Level::Help => "help",
+            Level::Note => "note",
         };
 
-        let mut message = issue.message.clone();
-        if let Some(link) = issue.link.as_deref() {
-            message.push_str(" (see ");
-            message.push_str(link);
-            message.push(')');
+        let mut message = issue.message.replace('\n', " ");
+        if let Some(code) = issue.code.as_deref() {
+            message.push_str(" [");
+            message.push_str(code);
+            message.push(']');
         }
 
-        let issue_type = issue.code.as_deref().unwrap_or("other");
+        entries.push((file_path, line, column, severity, message));
+    }
+
+    // Sort by file, then by position, so that the output of two runs can be compared.
+    entries.sort();
 
-        writeln!(writer, "{}:{}:{}:{} - {}: {}", file_path, line, column, severity, issue_type, message)?;
+    for (file_path, line, column, severity, message) in entries {
+        writeln!(writer, "{}:{}:{}: {}: {}", file_path, line, column, severity, message)?;
     }
 
     Ok(highest_level)
 }
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use crate::Annotation;
+    use crate::Issue;
+
+    use super::*;
+
+    #[test]
+    fn test_emacs_output_is_sorted_by_file_and_position() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let b = manager.insert_content("src/b.php".to_string(), "<?php eval('');".to_string(), true);
+        let a = manager.insert_content("src/a.php".to_string(), "<?php\n$a;\n\n$b;".to_string(), true);
+        let span = |source, start, end| Span::new(Position::new(source, start), Position::new(source, end));
+
+        let issues = IssueCollection::from([
+            Issue::error("Unsafe use of `eval` construct.")
+                .with_code("safety/no-eval")
+                .with_annotation(Annotation::primary(span(b, 6, 10))),
+            Issue::warning("The variable `$b`\nhas no effect.").with_annotation(Annotation::primary(span(a, 11, 13))),
+            Issue::note("The variable `$a` has no effect.")
+                .with_code("redundancy/redundant-expression-statement")
+                .with_annotation(Annotation::primary(span(a, 6, 8))),
+        ]);
+
+        let mut writer = NoColor::new(Vec::new());
+        emacs_format(&mut writer, &manager, &interner, issues, &ReportingOptions::default()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            [
+                "src/a.php:2:1: note: The variable `$a` has no effect. [redundancy/redundant-expression-statement]",
+                "src/a.php:4:1: warning: The variable `$b` has no effect.",
+                "src/b.php:1:7: error: Unsafe use of `eval` construct. [safety/no-eval]",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -1,11 +1,14 @@
 use termcolor::WriteColor;
 
 use mago_interner::ThreadedInterner;
+use mago_source::line_index::ColumnEncoding;
 use mago_source::HasSource;
 use mago_source::SourceManager;
 
 use crate::error::ReportingError;
 use crate::internal::emitter::utils::long_message;
+use crate::internal::emitter::utils::source_name;
+use crate::reporter::ReportingOptions;
 use crate::IssueCollection;
 use crate::Level;
 
@@ -14,6 +17,7 @@ pub fn github_format(
     sources: &SourceManager,
     interner: &ThreadedInterner,
     issues: IssueCollection,
+    options: &ReportingOptions,
 ) -> Result<Option<Level>, ReportingError> {
     let highest_level = issues.get_highest_level();
 
@@ -28,32 +32,115 @@ pub fn github_format(
         let properties = match issue.annotations.iter().find(|annotation| annotation.is_primary()) {
             Some(annotation) => {
                 let source = sources.load(&annotation.span.source())?;
-                let name = interner.lookup(&source.identifier.0);
-                let start_line = source.line_number(annotation.span.start.offset) + 1;
-                let end_line = source.line_number(annotation.span.end.offset) + 1;
+                let name = source_name(&source, interner, options);
+                let name = escape_property(&name);
+                let start = sources.line_column(
+                    &source.identifier,
+                    annotation.span.start.offset,
+                    ColumnEncoding::Utf8CodePoints,
+                )?;
+                let end = sources.line_column(
+                    &source.identifier,
+                    annotation.span.end.offset,
+                    ColumnEncoding::Utf8CodePoints,
+                )?;
+
+                let location = format!(
+                    "file={name},line={},col={},endLine={},endColumn={}",
+                    start.line + 1,
+                    start.column + 1,
+                    end.line + 1,
+                    end.column + 1
+                );
 
                 if let Some(code) = issue.code.as_ref() {
-                    format!("file={name},line={start_line},endLine={end_line},title={code}")
+                    format!("{location},title={}", escape_property(code))
                 } else {
-                    format!("file={name},line={start_line},endLine={end_line}")
+                    location
                 }
             }
             None => {
                 if let Some(code) = issue.code.as_ref() {
-                    format!("title={code}")
+                    format!("title={}", escape_property(code))
                 } else {
                     String::new()
                 }
             }
         };
 
-        // we must use `%0A` instead of `\n`.
-        //
-        // see: https://github.com/actions/toolkit/issues/193
-        let message = long_message(issue).replace("\n", "%0A");
+        let message = escape_data(&long_message(issue));
 
         writeln!(writer, "::{} {}::{}", level, properties, message)?;
     }
 
     Ok(highest_level)
 }
+
+/// Escapes the message of a workflow command.
+///
+/// Newlines must be written as `%0A` instead of `\n`.
+///
+/// see: https://github.com/actions/toolkit/issues/193
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command, which additionally may not contain `:` or `,`.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use crate::Annotation;
+    use crate::Issue;
+
+    use super::*;
+
+    #[test]
+    fn test_escape_data() {
+        assert_eq!(escape_data("100% done\r\nnext"), "100%25 done%0D%0Anext");
+        assert_eq!(escape_data("a: b, c"), "a: b, c");
+    }
+
+    #[test]
+    fn test_escape_property() {
+        assert_eq!(escape_property("src/a,b.php"), "src/a%2Cb.php");
+        assert_eq!(escape_property("C:\\a.php"), "C%3A\\a.php");
+    }
+
+    #[test]
+    fn test_github_output() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("src/a,b.php".to_string(), "<?php\n\neval('');".to_string(), true);
+        let span = Span::new(Position::new(source, 7), Position::new(source, 15));
+
+        let issues = IssueCollection::from([
+            Issue::error("Unsafe use of `eval` construct.")
+                .with_code("safety/no-eval")
+                .with_help("Remove it.")
+                .with_annotation(Annotation::primary(span)),
+            Issue::help("An issue without a location.").with_code("other:rule"),
+            Issue::note("An issue without a code, or a location."),
+        ]);
+
+        let mut writer = NoColor::new(Vec::new());
+        github_format(&mut writer, &manager, &interner, issues, &ReportingOptions::default()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            [
+                "::error file=src/a%2Cb.php,line=3,col=1,endLine=3,endColumn=9,title=safety/no-eval::Unsafe use of `eval` construct.%0A%0AHelp: Remove it.",
+                "::notice title=other%3Arule::An issue without a location.",
+                "::notice ::An issue without a code, or a location.",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
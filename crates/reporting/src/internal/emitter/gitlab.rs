@@ -0,0 +1,150 @@
+use ahash::HashMap;
+use serde_json::json;
+use termcolor::WriteColor;
+
+use mago_interner::ThreadedInterner;
+use mago_source::HasSource;
+use mago_source::SourceManager;
+
+use crate::error::ReportingError;
+use crate::internal::emitter::utils::source_name;
+use crate::reporter::ReportingOptions;
+use crate::IssueCollection;
+use crate::Level;
+
+pub fn gitlab_format(
+    writer: &mut dyn WriteColor,
+    sources: &SourceManager,
+    interner: &ThreadedInterner,
+    issues: IssueCollection,
+    options: &ReportingOptions,
+) -> Result<Option<Level>, ReportingError> {
+    let highest_level = issues.get_highest_level();
+
+    let mut occurrences: HashMap<u64, usize> = HashMap::default();
+    let mut entries = vec![];
+    for issue in issues.iter() {
+        let severity = match issue.level {
+            Level::Error => "critical",
+            Level::Warning => "major",
+            Level::Help => "minor",
+            Level::Note => "info",
+        };
+
+        let check_name = issue.code.as_deref().unwrap_or("other");
+
+        let (path, line, line_content) = match issue.annotations.iter().find(|annotation| annotation.is_primary()) {
+            Some(annotation) => {
+                let source = sources.load(&annotation.span.source())?;
+
+                let path = source_name(&source, interner, options);
+                let line = source.line_number(annotation.span.start.offset);
+                let content = interner.lookup(&source.content);
+                let line_start = source.lines[line];
+                let line_end = source.lines.get(line + 1).copied().unwrap_or(source.size);
+
+                (path, line + 1, content[line_start..line_end].trim().to_string())
+            }
+            None => ("<unknown>".to_string(), 1, String::new()),
+        };
+
+        // The fingerprint must survive unrelated edits to the file, so it is derived from the
+        // content of the offending line rather than from its position.
+        let mut hash = fnv1a(0xcbf29ce484222325, check_name.as_bytes());
+        hash = fnv1a(hash, path.as_bytes());
+        hash = fnv1a(hash, line_content.as_bytes());
+
+        // Distinguish identical issues reported on identical lines within the same file.
+        let occurrence = occurrences.entry(hash).or_insert(0);
+        let fingerprint = format!("{:016x}{:016x}", hash, fnv1a(hash, &occurrence.to_le_bytes()));
+        *occurrence += 1;
+
+        entries.push(json!({
+            "description": issue.message,
+            "check_name": check_name,
+            "fingerprint": fingerprint,
+            "severity": severity,
+            "location": {
+                "path": path,
+                "lines": {
+                    "begin": line,
+                },
+            },
+        }));
+    }
+
+    serde_json::to_writer_pretty(&mut *writer, &entries)?;
+    writeln!(writer)?;
+
+    Ok(highest_level)
+}
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use serde_json::Value;
+    use termcolor::NoColor;
+
+    use crate::Annotation;
+    use crate::Issue;
+
+    use super::*;
+
+    /// Renders an error on every occurrence of `eval` in the given content.
+    fn render(content: &str) -> Vec<Value> {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("src/a.php".to_string(), content.to_string(), true);
+
+        let issues = IssueCollection::from(content.match_indices("eval").map(|(start, _)| {
+            Issue::error("Unsafe use of `eval` construct.").with_code("safety/no-eval").with_annotation(
+                Annotation::primary(Span::new(Position::new(source, start), Position::new(source, start + 4))),
+            )
+        }));
+
+        let mut writer = NoColor::new(Vec::new());
+        gitlab_format(&mut writer, &manager, &interner, issues, &ReportingOptions::default()).unwrap();
+
+        serde_json::from_slice::<Vec<Value>>(&writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_gitlab_output() {
+        let entries = render("<?php\n\neval('');");
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["description"], "Unsafe use of `eval` construct.");
+        assert_eq!(entries[0]["check_name"], "safety/no-eval");
+        assert_eq!(entries[0]["severity"], "critical");
+        assert_eq!(entries[0]["location"], json!({ "path": "src/a.php", "lines": { "begin": 3 } }));
+        assert_eq!(entries[0]["fingerprint"].as_str().unwrap().len(), 32);
+    }
+
+    #[test]
+    fn test_gitlab_fingerprints_do_not_depend_on_the_line_number() {
+        let before = render("<?php\neval('');\n");
+        let after = render("<?php\n\n// A new comment.\neval('');\n");
+
+        assert_eq!(before[0]["location"]["lines"]["begin"], 2);
+        assert_eq!(after[0]["location"]["lines"]["begin"], 4);
+        assert_eq!(before[0]["fingerprint"], after[0]["fingerprint"]);
+    }
+
+    #[test]
+    fn test_gitlab_fingerprints_of_identical_issues_are_distinct() {
+        let entries = render("<?php\neval('');\neval('');\n");
+
+        assert_eq!(entries.len(), 2);
+        assert_ne!(entries[0]["fingerprint"], entries[1]["fingerprint"]);
+    }
+}
@@ -0,0 +1,332 @@
+use std::collections::BTreeMap;
+
+use termcolor::WriteColor;
+
+use mago_interner::ThreadedInterner;
+use mago_source::line_index::ColumnEncoding;
+use mago_source::HasSource;
+use mago_source::Source;
+use mago_source::SourceManager;
+
+use crate::error::ReportingError;
+use crate::internal::emitter::utils::source_name;
+use crate::internal::emitter::utils::xml_encode;
+use crate::reporter::ReportingOptions;
+use crate::Annotation;
+use crate::Issue;
+use crate::IssueCollection;
+use crate::Level;
+
+/// The number of issues above which code excerpts are reduced to the offending line only.
+const LARGE_COLLECTION_THRESHOLD: usize = 10_000;
+
+/// The maximum number of lines shown in a single code excerpt.
+const MAXIMUM_EXCERPT_LINES: usize = 8;
+
+/// The maximum number of bytes shown per line of a code excerpt.
+const MAXIMUM_EXCERPT_LINE_LENGTH: usize = 240;
+
+/// The source of a file, loaded once, along with the issues reported in it.
+type FileEntry<'a> = (Option<Source>, Vec<(&'a Issue, Option<&'a Annotation>)>);
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem; color: #1f2328; }
+h1 { font-size: 1.6rem; }
+h2 { font-size: 1.2rem; margin-top: 2rem; }
+table { border-collapse: collapse; margin-bottom: 1rem; }
+th, td { border: 1px solid #d0d7de; padding: 0.3rem 0.8rem; text-align: left; }
+th { background: #f6f8fa; }
+details { border: 1px solid #d0d7de; border-radius: 6px; margin-bottom: 0.6rem; padding: 0.4rem 0.8rem; }
+summary { cursor: pointer; font-weight: 600; }
+ul { list-style: none; padding-left: 0; }
+li { margin: 0.8rem 0; }
+pre { background: #f6f8fa; padding: 0.6rem; overflow-x: auto; font-size: 0.85rem; }
+mark { background: #ffd8b5; }
+.level { display: inline-block; min-width: 5rem; font-weight: 600; }
+.error { color: #cf222e; }
+.warning { color: #9a6700; }
+.help { color: #1a7f37; }
+.note { color: #0969da; }
+.code { color: #57606a; font-family: monospace; }
+.muted { color: #57606a; }
+"#;
+
+pub fn html_format(
+    writer: &mut dyn WriteColor,
+    sources: &SourceManager,
+    interner: &ThreadedInterner,
+    issues: IssueCollection,
+    options: &ReportingOptions,
+) -> Result<Option<Level>, ReportingError> {
+    let highest_level = issues.get_highest_level();
+    let total = issues.len();
+    let truncate_excerpts = total > LARGE_COLLECTION_THRESHOLD;
+
+    let mut rules: BTreeMap<(&str, Level), usize> = BTreeMap::new();
+    let mut files: BTreeMap<&str, FileEntry<'_>> = BTreeMap::new();
+    for issue in issues.iter() {
+        *rules.entry((issue.code.as_deref().unwrap_or("other"), issue.level)).or_insert(0) += 1;
+
+        match issue.annotations.iter().find(|annotation| annotation.is_primary()) {
+            Some(annotation) => {
+                let source_id = annotation.span.source();
+                let name = interner.lookup(&source_id.0);
+
+                // Sources are loaded once per file, as cloning them for every issue is too costly.
+                if !files.contains_key(name) {
+                    files.insert(name, (Some(sources.load(&source_id)?), vec![]));
+                }
+
+                if let Some((_, entries)) = files.get_mut(name) {
+                    entries.push((issue, Some(annotation)));
+                }
+            }
+            None => {
+                files.entry("<unknown>").or_insert_with(|| (None, vec![])).1.push((issue, None));
+            }
+        }
+    }
+
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html lang=\"en\">")?;
+    writeln!(writer, "<head>")?;
+    writeln!(writer, "<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>Mago Report</title>")?;
+    writeln!(writer, "<style>{}</style>", STYLE)?;
+    writeln!(writer, "</head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<h1>Mago Report</h1>")?;
+    writeln!(writer, "<p class=\"muted\">Found {} issue(s) across {} file(s).</p>", total, files.len())?;
+
+    writeln!(writer, "<h2>Issues per level</h2>")?;
+    writeln!(writer, "<table>")?;
+    writeln!(writer, "<tr><th>Level</th><th>Count</th></tr>")?;
+    for level in [Level::Error, Level::Warning, Level::Help, Level::Note] {
+        let count = issues.get_level_count(level);
+        if count > 0 {
+            writeln!(writer, "<tr><td class=\"{}\">{}</td><td>{}</td></tr>", level_class(level), level, count)?;
+        }
+    }
+    writeln!(writer, "</table>")?;
+
+    writeln!(writer, "<h2>Issues per rule</h2>")?;
+    writeln!(writer, "<table>")?;
+    writeln!(writer, "<tr><th>Rule</th><th>Level</th><th>Count</th></tr>")?;
+    for ((rule, level), count) in rules.iter() {
+        writeln!(
+            writer,
+            "<tr><td class=\"code\">{}</td><td class=\"{}\">{}</td><td>{}</td></tr>",
+            xml_encode(rule),
+            level_class(*level),
+            level,
+            count
+        )?;
+    }
+    writeln!(writer, "</table>")?;
+
+    writeln!(writer, "<h2>Files</h2>")?;
+    for (name, (source, entries)) in files {
+        let name = match &source {
+            Some(source) => source_name(source, interner, options),
+            None => name.to_string(),
+        };
+
+        writeln!(writer, "<details>")?;
+        writeln!(
+            writer,
+            "<summary>{} <span class=\"muted\">({} issue(s))</span></summary>",
+            xml_encode(&name),
+            entries.len()
+        )?;
+        writeln!(writer, "<ul>")?;
+
+        for (issue, location) in entries {
+            writeln!(writer, "<li>")?;
+            write!(writer, "<span class=\"level {}\">{}</span> ", level_class(issue.level), issue.level)?;
+            if let Some(code) = issue.code.as_deref() {
+                write!(writer, "<span class=\"code\">[{}]</span> ", xml_encode(code))?;
+            }
+
+            write!(writer, "{}", xml_encode(&issue.message))?;
+
+            if let (Some(source), Some(annotation)) = (&source, location) {
+                let position = sources.line_column(
+                    &source.identifier,
+                    annotation.span.start.offset,
+                    ColumnEncoding::Utf8CodePoints,
+                )?;
+                let (line, column) = (position.line + 1, position.column + 1);
+
+                writeln!(writer, " <span class=\"muted\">at line {}, column {}</span>", line, column)?;
+                write_excerpt(writer, interner, source, annotation, truncate_excerpts)?;
+            } else {
+                writeln!(writer)?;
+            }
+
+            for note in issue.notes.iter() {
+                writeln!(writer, "<p class=\"muted\">{}</p>", xml_encode(note))?;
+            }
+
+            if let Some(help) = issue.help.as_deref() {
+                writeln!(writer, "<p><strong>Help:</strong> {}</p>", xml_encode(help))?;
+            }
+
+            if let Some(link) = issue.link.as_deref() {
+                writeln!(writer, "<p><a href=\"{}\">{}</a></p>", xml_encode(link), xml_encode(link))?;
+            }
+
+            if let Some(help_uri) = issue.help_uri.as_deref() {
+                writeln!(
+                    writer,
+                    "<p class=\"muted\">docs: <a href=\"{}\">{}</a></p>",
+                    xml_encode(help_uri),
+                    xml_encode(help_uri)
+                )?;
+            }
+
+            writeln!(writer, "</li>")?;
+        }
+
+        writeln!(writer, "</ul>")?;
+        writeln!(writer, "</details>")?;
+    }
+
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")?;
+
+    Ok(highest_level)
+}
+
+fn write_excerpt(
+    writer: &mut dyn WriteColor,
+    interner: &ThreadedInterner,
+    source: &Source,
+    annotation: &Annotation,
+    truncate: bool,
+) -> Result<(), ReportingError> {
+    let content = interner.lookup(&source.content);
+    let start = annotation.span.start.offset.min(content.len());
+    let end = annotation.span.end.offset.clamp(start, content.len());
+
+    let first_line = source.line_number(start);
+    let last_line =
+        if truncate { first_line } else { source.line_number(end).min(first_line + MAXIMUM_EXCERPT_LINES - 1) };
+
+    write!(writer, "<pre>")?;
+    for line in first_line..=last_line {
+        let line_start = source.lines[line];
+        let line_end = source.lines.get(line + 1).map(|next| next - 1).unwrap_or(content.len());
+        let line_end = floor_char_boundary(content, line_end.min(line_start + MAXIMUM_EXCERPT_LINE_LENGTH));
+
+        let highlight_start = floor_char_boundary(content, start.clamp(line_start, line_end));
+        let highlight_end = floor_char_boundary(content, end.clamp(highlight_start, line_end));
+
+        write!(writer, "{:>5} | ", line + 1)?;
+        write!(writer, "{}", xml_encode(&content[line_start..highlight_start]))?;
+        write!(writer, "<mark>{}</mark>", xml_encode(&content[highlight_start..highlight_end]))?;
+        writeln!(writer, "{}", xml_encode(content[highlight_end..line_end].trim_end_matches('\r')))?;
+    }
+    writeln!(writer, "</pre>")?;
+
+    Ok(())
+}
+
+fn floor_char_boundary(content: &str, mut index: usize) -> usize {
+    while !content.is_char_boundary(index) {
+        index -= 1;
+    }
+
+    index
+}
+
+fn level_class(level: Level) -> &'static str {
+    match level {
+        Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Help => "help",
+        Level::Note => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use super::*;
+
+    fn render(content: &str, issues: impl Fn(Span) -> Vec<Issue>) -> String {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("src/a.php".to_string(), content.to_string(), true);
+        let start = content.find("eval").unwrap();
+        let span = Span::new(Position::new(source, start), Position::new(source, start + 4));
+
+        let mut writer = NoColor::new(Vec::new());
+        html_format(
+            &mut writer,
+            &manager,
+            &interner,
+            IssueCollection::from(issues(span)),
+            &ReportingOptions::default(),
+        )
+        .unwrap();
+
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_html_report_summarizes_the_issues() {
+        let output = render("<?php\n$a = '<b>';\neval($a);\n", |span| {
+            vec![
+                Issue::error("Unsafe use of `eval` with <script>.")
+                    .with_code("safety/no-eval")
+                    .with_help("Remove the call & the variable.")
+                    .with_help_uri("https://example.com/no-eval?a=1&b=2")
+                    .with_annotation(Annotation::primary(span)),
+                Issue::error("Another `eval`.").with_code("safety/no-eval").with_annotation(Annotation::primary(span)),
+                Issue::note("An issue without a location."),
+            ]
+        });
+
+        assert!(output.starts_with("<!DOCTYPE html>\n"));
+        assert!(output.ends_with("</body>\n</html>\n"));
+        assert!(output.contains("Found 3 issue(s) across 2 file(s)."));
+        assert!(output.contains("<tr><td class=\"error\">Error</td><td>2</td></tr>"));
+        assert!(
+            output.contains("<tr><td class=\"code\">safety/no-eval</td><td class=\"error\">Error</td><td>2</td></tr>")
+        );
+        assert!(output.contains("<tr><td class=\"code\">other</td><td class=\"note\">Note</td><td>1</td></tr>"));
+        assert!(output.contains("<summary>src/a.php <span class=\"muted\">(2 issue(s))</span></summary>"));
+        assert!(output.contains("<summary>&lt;unknown&gt; <span class=\"muted\">(1 issue(s))</span></summary>"));
+
+        // Messages, help, links, and code excerpts are escaped.
+        assert!(output
+            .contains("Unsafe use of `eval` with &lt;script&gt;. <span class=\"muted\">at line 3, column 1</span>"));
+        assert!(output.contains("<p><strong>Help:</strong> Remove the call &amp; the variable.</p>"));
+        assert!(output.contains("<a href=\"https://example.com/no-eval?a=1&amp;b=2\">"));
+        assert!(output.contains("<pre>    3 | <mark>eval</mark>($a);\n</pre>"));
+        assert!(!output.contains("<script>"));
+    }
+
+    #[test]
+    fn test_html_excerpts_are_limited() {
+        let long_line = "x".repeat(MAXIMUM_EXCERPT_LINE_LENGTH * 2);
+        let lines = (0..MAXIMUM_EXCERPT_LINES * 2).map(|_| long_line.as_str()).collect::<Vec<_>>().join("\n");
+        let content = format!("<?php\neval({});", lines);
+
+        let output = render(&content, |span| {
+            let span = Span::new(span.start, Position::new(span.start.source, content.len()));
+
+            vec![Issue::error("A long issue.").with_annotation(Annotation::primary(span))]
+        });
+
+        let excerpt = &output[output.find("<pre>").unwrap()..output.find("</pre>").unwrap()];
+
+        assert_eq!(excerpt.lines().count(), MAXIMUM_EXCERPT_LINES);
+        assert!(excerpt
+            .lines()
+            .all(|line| line.len() <= MAXIMUM_EXCERPT_LINE_LENGTH + "<pre>    2 | <mark></mark>".len()));
+    }
+}
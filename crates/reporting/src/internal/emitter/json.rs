@@ -5,6 +5,7 @@ use mago_source::SourceManager;
 
 use crate::error::ReportingError;
 use crate::internal::Expandable;
+use crate::reporter::ReportingOptions;
 use crate::IssueCollection;
 use crate::Level;
 
@@ -13,11 +14,41 @@ pub fn json_format(
     sources: &SourceManager,
     interner: &ThreadedInterner,
     issues: IssueCollection,
+    options: &ReportingOptions,
 ) -> Result<Option<Level>, ReportingError> {
     let highest_level = issues.get_highest_level();
-    let issues = issues.expand(sources, interner)?;
+    let issues = issues.expand(sources, interner, options)?;
 
     serde_json::to_writer_pretty(writer, &issues)?;
 
     Ok(highest_level)
 }
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use crate::Annotation;
+    use crate::Issue;
+
+    use super::*;
+
+    #[test]
+    fn test_json_uses_forward_slashes() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("src\\Foo\\Bar.php".to_string(), "<?php eval('');".to_string(), true);
+        let issues = IssueCollection::from([Issue::error("Unsafe use of `eval` construct.")
+            .with_code("safety/no-eval")
+            .with_annotation(Annotation::primary(Span::new(Position::new(source, 6), Position::new(source, 10))))]);
+
+        let mut writer = NoColor::new(Vec::new());
+        json_format(&mut writer, &manager, &interner, issues, &ReportingOptions::default()).unwrap();
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+
+        assert!(output.contains("\"identifier\": \"src/Foo/Bar.php\""));
+        assert!(!output.contains('\\'));
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+
+use termcolor::WriteColor;
+
+use mago_interner::ThreadedInterner;
+use mago_source::line_index::ColumnEncoding;
+use mago_source::HasSource;
+use mago_source::SourceManager;
+
+use crate::error::ReportingError;
+use crate::internal::emitter::utils::long_message;
+use crate::internal::emitter::utils::source_name;
+use crate::internal::emitter::utils::xml_encode;
+use crate::reporter::ReportingOptions;
+use crate::IssueCollection;
+use crate::Level;
+
+/// A single `<testcase>` element, derived from an issue.
+struct TestCase {
+    name: String,
+    level: Level,
+    code: String,
+    message: String,
+    body: String,
+}
+
+pub fn junit_format(
+    writer: &mut dyn WriteColor,
+    sources: &SourceManager,
+    interner: &ThreadedInterner,
+    issues: IssueCollection,
+    options: &ReportingOptions,
+) -> Result<Option<Level>, ReportingError> {
+    let highest_level = issues.get_highest_level();
+
+    // Group issues by file, making sure files without issues are included as well. The identifiers are
+    // collected first, as loading a source while iterating over the sources of the manager deadlocks.
+    let mut suites: BTreeMap<String, Vec<TestCase>> = BTreeMap::new();
+    for source_id in sources.user_defined_source_ids().collect::<Vec<_>>() {
+        suites.entry(source_name(&sources.load(&source_id)?, interner, options)).or_default();
+    }
+
+    for issue in issues.iter() {
+        let (filename, line, column) = match issue.annotations.iter().find(|annotation| annotation.is_primary()) {
+            Some(annotation) => {
+                let source = sources.load(&annotation.span.source())?;
+
+                let filename = source_name(&source, interner, options);
+                let position = sources.line_column(
+                    &source.identifier,
+                    annotation.span.start.offset,
+                    ColumnEncoding::Utf8CodePoints,
+                )?;
+                let (line, column) = (position.line + 1, position.column + 1);
+
+                (filename, line, column)
+            }
+            None => ("<unknown>".to_string(), 0, 0),
+        };
+
+        let code = issue.code.clone().unwrap_or_else(|| "other".to_string());
+        let body = format!("{}\n\nRule: {}\nLocation: {}:{}:{}", long_message(issue), code, filename, line, column);
+
+        suites.entry(filename).or_default().push(TestCase {
+            name: format!("{} at {}:{}", code, line, column),
+            level: issue.level,
+            code,
+            message: issue.message.clone(),
+            body,
+        });
+    }
+
+    let total_tests: usize = suites.values().map(|cases| cases.len().max(1)).sum();
+    let total_failures = issues.get_level_count(Level::Error);
+
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(
+        writer,
+        "<testsuites name=\"mago\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+        total_tests, total_failures
+    )?;
+
+    for (filename, cases) in suites {
+        let filename = xml_encode(&filename);
+        let failures = cases.iter().filter(|case| case.level == Level::Error).count();
+
+        writeln!(
+            writer,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+            filename,
+            cases.len().max(1),
+            failures
+        )?;
+
+        if cases.is_empty() {
+            // A file without issues is reported as a single passing test case.
+            writeln!(writer, "    <testcase name=\"{}\" classname=\"{}\" />", filename, filename)?;
+        }
+
+        for case in cases {
+            writeln!(writer, "    <testcase name=\"{}\" classname=\"{}\">", xml_encode(&case.name), filename)?;
+
+            if case.level == Level::Error {
+                writeln!(
+                    writer,
+                    "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+                    xml_encode(&case.code),
+                    xml_encode(&case.message),
+                    xml_encode(&case.body)
+                )?;
+            } else {
+                // JUnit has no notion of warnings, so lower levels are attached as output of a passing test case.
+                writeln!(
+                    writer,
+                    "      <system-out>{}: {}</system-out>",
+                    case.level.to_string().to_lowercase(),
+                    xml_encode(&case.body)
+                )?;
+            }
+
+            writeln!(writer, "    </testcase>")?;
+        }
+
+        writeln!(writer, "  </testsuite>")?;
+    }
+
+    writeln!(writer, "</testsuites>")?;
+
+    Ok(highest_level)
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use crate::Annotation;
+    use crate::Issue;
+
+    use super::*;
+
+    fn render(issues: impl Fn(Span) -> Vec<Issue>) -> String {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("src/a.php".to_string(), "<?php\n\n$a = 1 < 2;".to_string(), true);
+        manager.insert_content("src/b.php".to_string(), "<?php\n".to_string(), true);
+        let span = Span::new(Position::new(source, 7), Position::new(source, 9));
+
+        let mut writer = NoColor::new(Vec::new());
+        junit_format(
+            &mut writer,
+            &manager,
+            &interner,
+            IssueCollection::from(issues(span)),
+            &ReportingOptions::default(),
+        )
+        .unwrap();
+
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_junit_escapes_special_characters() {
+        let output = render(|span| {
+            vec![Issue::error("Comparing `$a < 2` & \"2\" is <unsafe>.")
+                .with_code("strictness/a&b")
+                .with_annotation(Annotation::primary(span))]
+        });
+
+        assert!(output.contains("type=\"strictness/a&amp;b\""));
+        assert!(output.contains("message=\"Comparing `$a &lt; 2` &amp; &quot;2&quot; is &lt;unsafe&gt;.\""));
+        assert!(output.contains(
+            ">Comparing `$a &lt; 2` &amp; &quot;2&quot; is &lt;unsafe&gt;.&#10;&#10;Rule: strictness/a&amp;b"
+        ));
+        assert!(!output.contains("<unsafe>"));
+    }
+
+    #[test]
+    fn test_junit_output() {
+        let output = render(|span| {
+            vec![
+                Issue::error("Unsafe use of `eval` construct.")
+                    .with_code("safety/no-eval")
+                    .with_annotation(Annotation::primary(span)),
+                Issue::warning("Missing docblock.")
+                    .with_code("comment/docblock")
+                    .with_annotation(Annotation::primary(span)),
+            ]
+        });
+
+        assert_eq!(
+            output,
+            [
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<testsuites name="mago" tests="3" failures="1" errors="0">"#,
+                r#"  <testsuite name="src/a.php" tests="2" failures="1" errors="0">"#,
+                r#"    <testcase name="safety/no-eval at 3:1" classname="src/a.php">"#,
+                r#"      <failure type="safety/no-eval" message="Unsafe use of `eval` construct.">Unsafe use of `eval` construct.&#10;&#10;Rule: safety/no-eval&#10;Location: src/a.php:3:1</failure>"#,
+                r#"    </testcase>"#,
+                r#"    <testcase name="comment/docblock at 3:1" classname="src/a.php">"#,
+                r#"      <system-out>warning: Missing docblock.&#10;&#10;Rule: comment/docblock&#10;Location: src/a.php:3:1</system-out>"#,
+                r#"    </testcase>"#,
+                r#"  </testsuite>"#,
+                r#"  <testsuite name="src/b.php" tests="1" failures="0" errors="0">"#,
+                r#"    <testcase name="src/b.php" classname="src/b.php" />"#,
+                r#"  </testsuite>"#,
+                r#"</testsuites>"#,
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -5,17 +5,24 @@ use mago_source::SourceManager;
 
 use crate::error::ReportingError;
 use crate::reporter::ReportingFormat;
+use crate::reporter::ReportingOptions;
 use crate::IssueCollection;
 use crate::Level;
 
-mod utils;
+pub(super) mod utils;
 
 pub mod checkstyle;
 pub mod codespan;
 pub mod count;
 pub mod emacs;
 pub mod github;
+pub mod gitlab;
+pub mod html;
 pub mod json;
+pub mod junit;
+pub mod rdjson;
+pub mod sarif;
+pub mod teamcity;
 
 pub trait Emitter {
     fn emit(
@@ -24,6 +31,7 @@ pub trait Emitter {
         sources: &SourceManager,
         interner: &ThreadedInterner,
         issues: IssueCollection,
+        options: &ReportingOptions,
     ) -> Result<Option<Level>, ReportingError>;
 }
 
@@ -34,6 +42,7 @@ where
         &SourceManager,
         &ThreadedInterner,
         IssueCollection,
+        &ReportingOptions,
     ) -> Result<Option<Level>, ReportingError>,
 {
     fn emit(
@@ -42,8 +51,9 @@ where
         sources: &SourceManager,
         interner: &ThreadedInterner,
         issues: IssueCollection,
+        options: &ReportingOptions,
     ) -> Result<Option<Level>, ReportingError> {
-        self(writer, sources, interner, issues)
+        self(writer, sources, interner, issues, options)
     }
 }
 
@@ -54,16 +64,25 @@ impl Emitter for ReportingFormat {
         sources: &SourceManager,
         interner: &ThreadedInterner,
         issues: IssueCollection,
+        options: &ReportingOptions,
     ) -> Result<Option<Level>, ReportingError> {
         match self {
-            ReportingFormat::Rich => codespan::rich_format.emit(writer, sources, interner, issues),
-            ReportingFormat::Medium => codespan::medium_format.emit(writer, sources, interner, issues),
-            ReportingFormat::Short => codespan::short_format.emit(writer, sources, interner, issues),
-            ReportingFormat::Github => github::github_format.emit(writer, sources, interner, issues),
-            ReportingFormat::Json => json::json_format.emit(writer, sources, interner, issues),
-            ReportingFormat::Count => count::count_format.emit(writer, sources, interner, issues),
-            ReportingFormat::Checkstyle => checkstyle::checkstyle_format.emit(writer, sources, interner, issues),
-            ReportingFormat::Emacs => emacs::emacs_format.emit(writer, sources, interner, issues),
+            ReportingFormat::Rich => codespan::rich_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Medium => codespan::medium_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Short => codespan::short_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Github => github::github_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Json => json::json_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Count => count::count_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Checkstyle => {
+                checkstyle::checkstyle_format.emit(writer, sources, interner, issues, options)
+            }
+            ReportingFormat::Emacs => emacs::emacs_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Sarif => sarif::sarif_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Junit => junit::junit_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Gitlab => gitlab::gitlab_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Html => html::html_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Rdjson => rdjson::rdjson_format.emit(writer, sources, interner, issues, options),
+            ReportingFormat::Teamcity => teamcity::teamcity_format.emit(writer, sources, interner, issues, options),
         }
     }
 }
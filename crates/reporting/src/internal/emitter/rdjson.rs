@@ -0,0 +1,180 @@
+use serde_json::json;
+use serde_json::Value;
+use termcolor::WriteColor;
+
+use mago_fixer::FixOperation;
+use mago_interner::ThreadedInterner;
+use mago_source::line_index::ColumnEncoding;
+use mago_source::HasSource;
+use mago_source::Source;
+use mago_source::SourceManager;
+
+use crate::error::ReportingError;
+use crate::internal::emitter::utils::long_message;
+use crate::internal::emitter::utils::source_name;
+use crate::reporter::ReportingOptions;
+use crate::IssueCollection;
+use crate::Level;
+
+pub fn rdjson_format(
+    writer: &mut dyn WriteColor,
+    sources: &SourceManager,
+    interner: &ThreadedInterner,
+    issues: IssueCollection,
+    options: &ReportingOptions,
+) -> Result<Option<Level>, ReportingError> {
+    let highest_level = issues.get_highest_level();
+
+    let mut diagnostics = vec![];
+    for issue in issues.iter() {
+        let severity = match issue.level {
+            Level::Error => "ERROR",
+            Level::Warning => "WARNING",
+            Level::Help | Level::Note => "INFO",
+        };
+
+        let mut diagnostic = json!({
+            "message": long_message(issue),
+            "severity": severity,
+        });
+
+        if let Some(annotation) = issue.annotations.iter().find(|annotation| annotation.is_primary()) {
+            let source = sources.load(&annotation.span.source())?;
+
+            diagnostic["location"] = json!({
+                "path": source_name(&source, interner, options),
+                "range": range(sources, &source, annotation.span.start.offset, annotation.span.end.offset)?,
+            });
+        }
+
+        if let Some(code) = issue.code.as_ref() {
+            diagnostic["code"] = match issue.help_uri.as_ref().or(issue.link.as_ref()) {
+                Some(url) => json!({ "value": code, "url": url }),
+                None => json!({ "value": code }),
+            };
+        }
+
+        let mut suggestions = vec![];
+        for (source_id, plan) in issue.suggestions.iter() {
+            let source = sources.load(source_id)?;
+
+            for operation in plan.get_operations() {
+                suggestions.push(match operation {
+                    FixOperation::Insert { offset, text, .. } => {
+                        json!({ "range": range(sources, &source, *offset, *offset)?, "text": text })
+                    }
+                    FixOperation::Replace { range: replaced, text, .. } => {
+                        json!({ "range": range(sources, &source, replaced.start, replaced.end)?, "text": text })
+                    }
+                    FixOperation::Delete { range: deleted, .. } => {
+                        json!({ "range": range(sources, &source, deleted.start, deleted.end)?, "text": "" })
+                    }
+                });
+            }
+        }
+
+        if !suggestions.is_empty() {
+            diagnostic["suggestions"] = json!(suggestions);
+        }
+
+        diagnostics.push(diagnostic);
+    }
+
+    let result = json!({
+        "source": {
+            "name": "mago",
+            "url": env!("CARGO_PKG_HOMEPAGE"),
+        },
+        "diagnostics": diagnostics,
+    });
+
+    serde_json::to_writer_pretty(&mut *writer, &result)?;
+    writeln!(writer)?;
+
+    Ok(highest_level)
+}
+
+/// Builds an rdjson range, where lines and columns are 1-based, and columns are counted in UTF-8 bytes.
+fn range(sources: &SourceManager, source: &Source, start: usize, end: usize) -> Result<Value, ReportingError> {
+    let start = sources.line_column(&source.identifier, start, ColumnEncoding::Utf8Bytes)?;
+    let end = sources.line_column(&source.identifier, end, ColumnEncoding::Utf8Bytes)?;
+
+    Ok(json!({
+        "start": {
+            "line": start.line + 1,
+            "column": start.column + 1,
+        },
+        "end": {
+            "line": end.line + 1,
+            "column": end.column + 1,
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_fixer::FixPlan;
+    use mago_fixer::SafetyClassification;
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use crate::Annotation;
+    use crate::Issue;
+
+    use super::*;
+
+    #[test]
+    fn test_rdjson_diagnostics_use_one_based_byte_columns() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("a.php".to_string(), "<?php\n$é = eval('');".to_string(), true);
+
+        let mut plan = FixPlan::new();
+        plan.replace(12..16, "assert", SafetyClassification::Unsafe);
+        plan.insert(20, ";", SafetyClassification::Safe);
+        plan.delete(5..6, SafetyClassification::Safe);
+
+        let issues = IssueCollection::from(vec![
+            Issue::error("Unsafe use of `eval`.")
+                .with_code("safety/no-eval")
+                .with_help_uri("https://example.com/no-eval")
+                .with_annotation(Annotation::primary(Span::new(Position::new(source, 12), Position::new(source, 16))))
+                .with_suggestion(source, plan),
+            Issue::note("A note without a code or a location."),
+        ]);
+
+        let mut writer = NoColor::new(Vec::new());
+        let level = rdjson_format(&mut writer, &manager, &interner, issues, &ReportingOptions::default()).unwrap();
+        let output: Value = serde_json::from_slice(&writer.into_inner()).unwrap();
+
+        assert_eq!(level, Some(Level::Error));
+        assert_eq!(output["source"]["name"], "mago");
+
+        let diagnostics = output["diagnostics"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 2);
+
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic["severity"], "ERROR");
+        assert_eq!(diagnostic["code"], json!({ "value": "safety/no-eval", "url": "https://example.com/no-eval" }));
+        assert_eq!(diagnostic["location"]["path"], "a.php");
+        assert_eq!(
+            diagnostic["location"]["range"],
+            json!({ "start": { "line": 2, "column": 7 }, "end": { "line": 2, "column": 11 } })
+        );
+        assert_eq!(
+            diagnostic["suggestions"],
+            json!([
+                { "range": { "start": { "line": 2, "column": 7 }, "end": { "line": 2, "column": 11 } }, "text": "assert" },
+                { "range": { "start": { "line": 2, "column": 15 }, "end": { "line": 2, "column": 15 } }, "text": ";" },
+                { "range": { "start": { "line": 1, "column": 6 }, "end": { "line": 2, "column": 1 } }, "text": "" },
+            ])
+        );
+
+        let diagnostic = &diagnostics[1];
+        assert_eq!(diagnostic["severity"], "INFO");
+        assert!(diagnostic.get("code").is_none());
+        assert!(diagnostic.get("location").is_none());
+        assert!(diagnostic.get("suggestions").is_none());
+    }
+}
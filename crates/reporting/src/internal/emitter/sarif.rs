@@ -0,0 +1,314 @@
+use ahash::HashMap;
+use serde_json::json;
+use serde_json::Value;
+use termcolor::WriteColor;
+
+use mago_interner::ThreadedInterner;
+use mago_source::line_index::ColumnEncoding;
+use mago_source::HasSource;
+use mago_source::SourceManager;
+
+use crate::error::ReportingError;
+use crate::internal::emitter::utils::long_message;
+use crate::internal::emitter::utils::source_name;
+use crate::reporter::ReportingOptions;
+use crate::Annotation;
+use crate::IssueCollection;
+use crate::Level;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+pub fn sarif_format(
+    writer: &mut dyn WriteColor,
+    sources: &SourceManager,
+    interner: &ThreadedInterner,
+    issues: IssueCollection,
+    options: &ReportingOptions,
+) -> Result<Option<Level>, ReportingError> {
+    let highest_level = issues.get_highest_level();
+
+    // Every distinct code is described once, in the order of its first issue, by a rule that only
+    // depends on the code, while the message of each issue is reported by its result.
+    let mut rules: Vec<Value> = vec![];
+    let mut rule_indices: HashMap<&str, usize> = HashMap::default();
+    for issue in issues.iter() {
+        let Some(code) = issue.code.as_deref() else {
+            continue;
+        };
+
+        if !rule_indices.contains_key(code) {
+            rule_indices.insert(code, rules.len());
+            rules.push(rule(code, issue.help_uri.as_ref().or(issue.link.as_ref()), options));
+        }
+    }
+
+    let mut results: Vec<Value> = vec![];
+    for issue in issues.iter() {
+        let level = match issue.level {
+            Level::Error => "error",
+            Level::Warning => "warning",
+            Level::Help | Level::Note => "note",
+        };
+
+        let mut result = json!({
+            "level": level,
+            "message": { "text": long_message(issue) },
+        });
+
+        if let Some(code) = issue.code.as_deref() {
+            result["ruleId"] = json!(code);
+            result["ruleIndex"] = json!(rule_indices[code]);
+        }
+
+        let mut locations = vec![];
+        let mut related_locations = vec![];
+        for annotation in issue.annotations.iter() {
+            let location = location(sources, interner, annotation, options)?;
+
+            if annotation.is_primary() && locations.is_empty() {
+                locations.push(location);
+            } else {
+                related_locations.push(location);
+            }
+        }
+
+        if !locations.is_empty() {
+            result["locations"] = json!(locations);
+        }
+
+        if !related_locations.is_empty() {
+            for (id, location) in related_locations.iter_mut().enumerate() {
+                location["id"] = json!(id);
+            }
+
+            result["relatedLocations"] = json!(related_locations);
+        }
+
+        results.push(result);
+    }
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [
+            {
+                "tool": {
+                    "driver": {
+                        "name": "mago",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                        "rules": rules,
+                    }
+                },
+                "columnKind": "utf16CodeUnits",
+                "results": results,
+            }
+        ]
+    });
+
+    serde_json::to_writer_pretty(&mut *writer, &log)?;
+    writeln!(writer)?;
+
+    Ok(highest_level)
+}
+
+/// Returns the SARIF rule describing the given code, named after its last segment, e.g. `no-eval` for
+/// `safety/no-eval`, with the description given in the options, or one derived from its name.
+fn rule(code: &str, help_uri: Option<&String>, options: &ReportingOptions) -> Value {
+    let name = code.rsplit('/').next().unwrap_or(code);
+
+    let description = match options.rule_descriptions.get(code) {
+        Some(description) => description.clone(),
+        None => {
+            let mut description = name.replace(['-', '_'], " ");
+            if let Some(first) = description.get(..1) {
+                description.replace_range(..1, &first.to_uppercase());
+            }
+
+            description
+        }
+    };
+
+    let mut rule = json!({
+        "id": code,
+        "name": name,
+        "shortDescription": { "text": description },
+    });
+
+    if let Some(help_uri) = help_uri {
+        rule["helpUri"] = json!(help_uri);
+    }
+
+    rule
+}
+
+fn location(
+    sources: &SourceManager,
+    interner: &ThreadedInterner,
+    annotation: &Annotation,
+    options: &ReportingOptions,
+) -> Result<Value, ReportingError> {
+    let source = sources.load(&annotation.span.source())?;
+
+    // SARIF lines and columns are 1-based, and `endColumn` points one past the last character. Columns
+    // are counted in UTF-16 code units, as declared by the `columnKind` of the run.
+    let uri = source_name(&source, interner, options);
+    let start =
+        sources.line_column(&source.identifier, annotation.span.start.offset, ColumnEncoding::Utf16CodeUnits)?;
+    let end = sources.line_column(&source.identifier, annotation.span.end.offset, ColumnEncoding::Utf16CodeUnits)?;
+
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": uri },
+            "region": {
+                "startLine": start.line + 1,
+                "startColumn": start.column + 1,
+                "endLine": end.line + 1,
+                "endColumn": end.column + 1,
+            }
+        }
+    });
+
+    if let Some(message) = annotation.message.as_ref() {
+        location["message"] = json!({ "text": message });
+    }
+
+    Ok(location)
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+    use termcolor::NoColor;
+
+    use crate::internal::emitter::Emitter;
+    use crate::Issue;
+
+    use super::*;
+
+    fn render(format: impl Emitter) -> String {
+        render_code(format, "<?php eval('');", 6..10)
+    }
+
+    fn render_code(format: impl Emitter, code: &str, range: std::ops::Range<usize>) -> String {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("src\\Foo\\Bar.php".to_string(), code.to_string(), true);
+        let span = Span::new(Position::new(source, range.start), Position::new(source, range.end));
+        let issues = IssueCollection::from([Issue::error("Unsafe use of `eval` construct.")
+            .with_code("safety/no-eval")
+            .with_annotation(Annotation::primary(span))]);
+
+        let mut writer = NoColor::new(Vec::new());
+        format.emit(&mut writer, &manager, &interner, issues, &ReportingOptions::default()).unwrap();
+
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_sarif_uses_forward_slashes() {
+        let output = render(sarif_format);
+
+        assert!(output.contains("\"uri\": \"src/Foo/Bar.php\""));
+        assert!(!output.contains('\\'));
+    }
+
+    #[test]
+    fn test_sarif_rules_without_a_description_are_described_by_their_name() {
+        let log: Value = serde_json::from_str(&render(sarif_format)).unwrap();
+
+        assert_eq!(log["runs"][0]["tool"]["driver"]["rules"][0]["shortDescription"]["text"], "No eval");
+    }
+
+    #[test]
+    fn test_sarif_columns_are_counted_in_utf16_code_units() {
+        // `😀` is 4 bytes, or 2 UTF-16 code units.
+        let code = "<?php $a = '😀'; eval('');";
+        let start = code.find("eval").unwrap();
+        let output = render_code(sarif_format, code, start..start + 4);
+
+        assert!(output.contains("\"columnKind\": \"utf16CodeUnits\""));
+        assert!(output.contains("\"startColumn\": 18"));
+        assert!(output.contains("\"endColumn\": 22"));
+    }
+
+    #[test]
+    fn test_sarif_log_follows_the_structure_of_the_schema() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("foo.php".to_string(), "<?php eval('');\neval('');".to_string(), true);
+        let span = |start, end| Span::new(Position::new(source, start), Position::new(source, end));
+        let issues = IssueCollection::from([
+            Issue::error("Unsafe use of `eval` construct.")
+                .with_code("safety/no-eval")
+                .with_help_uri("https://example.com/no-eval")
+                .with_annotation(Annotation::primary(span(6, 10))),
+            Issue::warning("Another message of the same rule.")
+                .with_code("safety/no-eval")
+                .with_annotation(Annotation::primary(span(16, 20)))
+                .with_annotation(Annotation::secondary(span(6, 10)).with_message("The first one.")),
+            Issue::note("An issue without a code."),
+        ]);
+
+        let options = ReportingOptions {
+            rule_descriptions: HashMap::from_iter([(
+                "safety/no-eval".to_string(),
+                "Reports uses of the `eval` construct.".to_string(),
+            )]),
+            ..Default::default()
+        };
+
+        let mut writer = NoColor::new(Vec::new());
+        sarif_format(&mut writer, &manager, &interner, issues, &options).unwrap();
+        let log: Value = serde_json::from_slice(&writer.into_inner()).unwrap();
+
+        assert_eq!(log["$schema"], SARIF_SCHEMA);
+        assert_eq!(log["version"], SARIF_VERSION);
+
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "mago");
+        assert_eq!(run["columnKind"], "utf16CodeUnits");
+
+        // The rules are described once per code, independently of the messages of the issues.
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["id"], "safety/no-eval");
+        assert_eq!(rules[0]["name"], "no-eval");
+        assert_eq!(rules[0]["shortDescription"]["text"], "Reports uses of the `eval` construct.");
+        assert_eq!(rules[0]["helpUri"], "https://example.com/no-eval");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        for result in results {
+            assert!(["none", "note", "warning", "error"].contains(&result["level"].as_str().unwrap()));
+            assert!(result["message"]["text"].is_string());
+
+            if let Some(index) = result["ruleIndex"].as_u64() {
+                assert_eq!(rules[index as usize]["id"], result["ruleId"]);
+            }
+
+            for location in result["locations"].as_array().into_iter().flatten() {
+                let region = &location["physicalLocation"]["region"];
+
+                assert!(location["physicalLocation"]["artifactLocation"]["uri"].is_string());
+                assert!(region["startLine"].as_u64().unwrap() >= 1);
+                assert!(region["startColumn"].as_u64().unwrap() >= 1);
+                assert!(region["endLine"].as_u64().unwrap() >= region["startLine"].as_u64().unwrap());
+            }
+        }
+
+        assert_eq!(
+            results[0]["message"]["text"],
+            "Unsafe use of `eval` construct.\n\nDocs: https://example.com/no-eval"
+        );
+        assert_eq!(results[1]["message"]["text"], "Another message of the same rule.");
+        assert_eq!(results[1]["ruleIndex"], 0);
+        assert_eq!(results[1]["locations"][0]["physicalLocation"]["region"]["startLine"], 2);
+        assert_eq!(results[1]["relatedLocations"][0]["id"], 0);
+        assert_eq!(results[1]["relatedLocations"][0]["message"]["text"], "The first one.");
+        assert!(results[2].get("ruleId").is_none());
+        assert!(results[2].get("locations").is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeSet;
+
+use termcolor::WriteColor;
+
+use mago_interner::ThreadedInterner;
+use mago_source::HasSource;
+use mago_source::SourceManager;
+
+use crate::error::ReportingError;
+use crate::internal::emitter::utils::long_message;
+use crate::internal::emitter::utils::source_name;
+use crate::reporter::ReportingOptions;
+use crate::IssueCollection;
+use crate::Level;
+
+pub fn teamcity_format(
+    writer: &mut dyn WriteColor,
+    sources: &SourceManager,
+    interner: &ThreadedInterner,
+    issues: IssueCollection,
+    options: &ReportingOptions,
+) -> Result<Option<Level>, ReportingError> {
+    let highest_level = issues.get_highest_level();
+
+    // Every inspection must reference a previously declared inspection type.
+    let types: BTreeSet<&str> = issues.iter().map(|issue| issue.code.as_deref().unwrap_or("other")).collect();
+    for type_id in types {
+        let type_id = escape(type_id);
+
+        writeln!(
+            writer,
+            "##teamcity[inspectionType id='{}' name='{}' category='mago' description='{}']",
+            type_id, type_id, type_id
+        )?;
+    }
+
+    for issue in issues.iter() {
+        let severity = match issue.level {
+            Level::Error => "ERROR",
+            Level::Warning => "WARNING",
+            Level::Help => "WEAK WARNING",
+            Level::Note => "INFO",
+        };
+
+        let (file, line) = match issue.annotations.iter().find(|annotation| annotation.is_primary()) {
+            Some(annotation) => {
+                let source = sources.load(&annotation.span.source())?;
+
+                let file = source_name(&source, interner, options);
+                let line = source.line_number(annotation.span.start.offset) + 1;
+
+                (file, line)
+            }
+            None => ("<unknown>".to_string(), 1),
+        };
+
+        writeln!(
+            writer,
+            "##teamcity[inspection typeId='{}' file='{}' line='{}' message='{}' SEVERITY='{}']",
+            escape(issue.code.as_deref().unwrap_or("other")),
+            escape(&file),
+            line,
+            escape(&long_message(issue)),
+            severity
+        )?;
+    }
+
+    Ok(highest_level)
+}
+
+/// Escapes a value of a service message attribute.
+///
+/// see: https://www.jetbrains.com/help/teamcity/service-messages.html#Escaped+Values
+fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        let next = match c {
+            '|' => "||",
+            '\'' => "|'",
+            '\n' => "|n",
+            '\r' => "|r",
+            '[' => "|[",
+            ']' => "|]",
+            '\u{0085}' => "|x",
+            '\u{2028}' => "|l",
+            '\u{2029}' => "|p",
+            _ => {
+                result.push(c);
+
+                continue;
+            }
+        };
+
+        result.push_str(next);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_plain_value() {
+        assert_eq!(escape("no-empty-catch"), "no-empty-catch");
+        assert_eq!(escape(""), "");
+    }
+
+    #[test]
+    fn test_escape_special_characters() {
+        assert_eq!(escape("a|b"), "a||b");
+        assert_eq!(escape("it's"), "it|'s");
+        assert_eq!(escape("[array]"), "|[array|]");
+        assert_eq!(escape("first\r\nsecond"), "first|r|nsecond");
+    }
+
+    #[test]
+    fn test_escape_unicode_line_separators() {
+        assert_eq!(escape("a\u{0085}b\u{2028}c\u{2029}d"), "a|xb|lc|pd");
+        assert_eq!(escape("caf\u{e9}"), "caf\u{e9}");
+    }
+
+    #[test]
+    fn test_escape_does_not_double_escape() {
+        assert_eq!(escape("||'"), "|||||'");
+    }
+}
@@ -1,5 +1,25 @@
+use mago_fixer::SafetyClassification;
+use mago_interner::ThreadedInterner;
+use mago_source::Source;
+
+use crate::reporter::PathStyle;
+use crate::reporter::ReportingOptions;
 use crate::Issue;
 
+/// Returns the name of the given source as it should be displayed in reports.
+///
+/// Source names are relative to the source root, unless the source lives outside of it. When absolute
+/// paths are requested, the full path is used for sources that have one. Separators are always
+/// normalized to forward slashes, so that reports look the same on every platform.
+pub fn source_name(source: &Source, interner: &ThreadedInterner, options: &ReportingOptions) -> String {
+    let name = match (options.paths, source.path.as_ref()) {
+        (PathStyle::Absolute, Some(path)) => path.to_string_lossy().into_owned(),
+        _ => interner.lookup(&source.identifier.0).to_string(),
+    };
+
+    name.replace('\\', "/")
+}
+
 pub fn xml_encode(input: impl AsRef<str>) -> String {
     let input = input.as_ref();
     // the result will never be smaller than the input,
@@ -28,6 +48,21 @@ pub fn xml_encode(input: impl AsRef<str>) -> String {
     result
 }
 
+/// Returns a note describing how safe the suggested fix of the given issue is, and how to apply it.
+///
+/// Returns `None` if the issue has no suggestions.
+pub fn fix_note(issue: &Issue) -> Option<String> {
+    let note = match issue.get_fix_safety()? {
+        SafetyClassification::Safe => "Fix: safe, applied by `mago fix`.",
+        SafetyClassification::PotentiallyUnsafe => {
+            "Fix: potentially unsafe, applied by `mago fix --potentially-unsafe`."
+        }
+        SafetyClassification::Unsafe => "Fix: unsafe, applied by `mago fix --unsafe`.",
+    };
+
+    Some(note.to_string())
+}
+
 pub fn long_message(issue: &Issue) -> String {
     let mut message = issue.message.clone();
     if !issue.notes.is_empty() {
@@ -44,10 +79,70 @@ pub fn long_message(issue: &Issue) -> String {
         message.push_str(help.as_str());
     }
 
+    if let Some(fix) = fix_note(issue) {
+        message.push_str("\n\n");
+        message.push_str(&fix);
+    }
+
     if let Some(link) = issue.link.as_ref() {
         message.push_str("\n\nAMore information: ");
         message.push_str(link.as_str());
     }
 
+    if let Some(help_uri) = issue.help_uri.as_ref() {
+        message.push_str("\n\nDocs: ");
+        message.push_str(help_uri.as_str());
+    }
+
     message
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use mago_source::SourceIdentifier;
+
+    use super::*;
+
+    fn source(interner: &ThreadedInterner, name: &str, path: Option<&str>) -> Source {
+        Source {
+            identifier: SourceIdentifier(interner.intern(name), true),
+            path: path.map(PathBuf::from),
+            content: interner.intern(""),
+            size: 0,
+            lines: vec![0],
+        }
+    }
+
+    fn options(paths: PathStyle) -> ReportingOptions {
+        ReportingOptions { paths, ..Default::default() }
+    }
+
+    #[test]
+    fn test_source_name_normalizes_windows_separators() {
+        let interner = ThreadedInterner::new();
+        let source = source(&interner, "src\\Foo\\Bar.php", Some("C:\\project\\src\\Foo\\Bar.php"));
+
+        assert_eq!(source_name(&source, &interner, &options(PathStyle::Relative)), "src/Foo/Bar.php");
+        assert_eq!(source_name(&source, &interner, &options(PathStyle::Absolute)), "C:/project/src/Foo/Bar.php");
+    }
+
+    #[test]
+    fn test_source_name_falls_back_to_name_without_path() {
+        let interner = ThreadedInterner::new();
+        let source = source(&interner, "@stubs\\core.php", None);
+
+        assert_eq!(source_name(&source, &interner, &options(PathStyle::Relative)), "@stubs/core.php");
+        assert_eq!(source_name(&source, &interner, &options(PathStyle::Absolute)), "@stubs/core.php");
+    }
+
+    #[test]
+    fn test_source_name_keeps_names_outside_of_root() {
+        let interner = ThreadedInterner::new();
+        let source = source(&interner, "/opt/vendor/lib.php", Some("/opt/vendor/lib.php"));
+
+        assert_eq!(source_name(&source, &interner, &options(PathStyle::Relative)), "/opt/vendor/lib.php");
+        assert_eq!(source_name(&source, &interner, &options(PathStyle::Absolute)), "/opt/vendor/lib.php");
+    }
+}
@@ -1,9 +1,8 @@
-use std::path::PathBuf;
-
 use serde::Deserialize;
 use serde::Serialize;
 
 use mago_fixer::FixPlan;
+use mago_fixer::SafetyClassification;
 use mago_interner::ThreadedInterner;
 use mago_source::error::SourceError;
 use mago_source::SourceIdentifier;
@@ -11,6 +10,8 @@ use mago_source::SourceManager;
 use mago_span::Position;
 use mago_span::Span;
 
+use crate::internal::emitter::utils::source_name;
+use crate::reporter::ReportingOptions;
 use crate::Annotation;
 use crate::AnnotationKind;
 use crate::Issue;
@@ -25,7 +26,7 @@ pub mod writer;
 pub struct ExpandedSourceIdentifier {
     pub identifier: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub path: Option<PathBuf>,
+    pub path: Option<String>,
     pub size: usize,
     pub user_defined: bool,
 }
@@ -67,10 +68,14 @@ pub struct ExpandedIssue {
     pub help: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help_uri: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub annotations: Vec<ExpandedAnnotation>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub suggestions: Vec<(ExpandedSourceIdentifier, FixPlan)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix_safety: Option<SafetyClassification>,
 }
 
 /// A collection of expanded issues.
@@ -80,7 +85,12 @@ pub struct ExpandedIssueCollection {
 }
 
 pub trait Expandable<T> {
-    fn expand(&self, manager: &SourceManager, interner: &ThreadedInterner) -> Result<T, SourceError>;
+    fn expand(
+        &self,
+        manager: &SourceManager,
+        interner: &ThreadedInterner,
+        options: &ReportingOptions,
+    ) -> Result<T, SourceError>;
 }
 
 impl Expandable<ExpandedSourceIdentifier> for SourceIdentifier {
@@ -88,12 +98,13 @@ impl Expandable<ExpandedSourceIdentifier> for SourceIdentifier {
         &self,
         manager: &SourceManager,
         interner: &ThreadedInterner,
+        options: &ReportingOptions,
     ) -> Result<ExpandedSourceIdentifier, SourceError> {
         let source = manager.load(self)?;
 
         Ok(ExpandedSourceIdentifier {
-            identifier: interner.lookup(&source.identifier.0).to_string(),
-            path: source.path.clone(),
+            identifier: source_name(&source, interner, options),
+            path: source.path.as_ref().map(|path| path.to_string_lossy().replace('\\', "/")),
             size: source.size,
             user_defined: source.identifier.is_user_defined(),
         })
@@ -101,11 +112,16 @@ impl Expandable<ExpandedSourceIdentifier> for SourceIdentifier {
 }
 
 impl Expandable<ExpandedPosition> for Position {
-    fn expand(&self, manager: &SourceManager, interner: &ThreadedInterner) -> Result<ExpandedPosition, SourceError> {
+    fn expand(
+        &self,
+        manager: &SourceManager,
+        interner: &ThreadedInterner,
+        options: &ReportingOptions,
+    ) -> Result<ExpandedPosition, SourceError> {
         let source = manager.load(&self.source)?;
 
         Ok(ExpandedPosition {
-            source: self.source.expand(manager, interner)?,
+            source: self.source.expand(manager, interner, options)?,
             offset: self.offset,
             line: source.line_number(self.offset),
         })
@@ -113,31 +129,49 @@ impl Expandable<ExpandedPosition> for Position {
 }
 
 impl Expandable<ExpandedSpan> for Span {
-    fn expand(&self, manager: &SourceManager, interner: &ThreadedInterner) -> Result<ExpandedSpan, SourceError> {
-        Ok(ExpandedSpan { start: self.start.expand(manager, interner)?, end: self.end.expand(manager, interner)? })
+    fn expand(
+        &self,
+        manager: &SourceManager,
+        interner: &ThreadedInterner,
+        options: &ReportingOptions,
+    ) -> Result<ExpandedSpan, SourceError> {
+        Ok(ExpandedSpan {
+            start: self.start.expand(manager, interner, options)?,
+            end: self.end.expand(manager, interner, options)?,
+        })
     }
 }
 
 impl Expandable<ExpandedAnnotation> for Annotation {
-    fn expand(&self, manager: &SourceManager, interner: &ThreadedInterner) -> Result<ExpandedAnnotation, SourceError> {
+    fn expand(
+        &self,
+        manager: &SourceManager,
+        interner: &ThreadedInterner,
+        options: &ReportingOptions,
+    ) -> Result<ExpandedAnnotation, SourceError> {
         Ok(ExpandedAnnotation {
             message: self.message.clone(),
             kind: self.kind,
-            span: self.span.expand(manager, interner)?,
+            span: self.span.expand(manager, interner, options)?,
         })
     }
 }
 
 impl Expandable<ExpandedIssue> for Issue {
-    fn expand(&self, manager: &SourceManager, interner: &ThreadedInterner) -> Result<ExpandedIssue, SourceError> {
+    fn expand(
+        &self,
+        manager: &SourceManager,
+        interner: &ThreadedInterner,
+        options: &ReportingOptions,
+    ) -> Result<ExpandedIssue, SourceError> {
         let mut annotations = Vec::new();
         for annotation in &self.annotations {
-            annotations.push(annotation.expand(manager, interner)?);
+            annotations.push(annotation.expand(manager, interner, options)?);
         }
 
         let mut suggestions = Vec::new();
         for (source, fix) in &self.suggestions {
-            suggestions.push((source.expand(manager, interner)?, fix.clone()));
+            suggestions.push((source.expand(manager, interner, options)?, fix.clone()));
         }
 
         Ok(ExpandedIssue {
@@ -147,8 +181,10 @@ impl Expandable<ExpandedIssue> for Issue {
             notes: self.notes.clone(),
             help: self.help.clone(),
             link: self.link.clone(),
+            help_uri: self.help_uri.clone(),
             annotations,
             suggestions,
+            fix_safety: self.get_fix_safety(),
         })
     }
 }
@@ -158,10 +194,11 @@ impl Expandable<ExpandedIssueCollection> for IssueCollection {
         &self,
         manager: &SourceManager,
         interner: &ThreadedInterner,
+        options: &ReportingOptions,
     ) -> Result<ExpandedIssueCollection, SourceError> {
         let mut expanded_issues = Vec::new();
         for issue in self.issues.iter() {
-            expanded_issues.push(issue.expand(manager, interner)?);
+            expanded_issues.push(issue.expand(manager, interner, options)?);
         }
 
         Ok(ExpandedIssueCollection { issues: expanded_issues })
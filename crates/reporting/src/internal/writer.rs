@@ -21,14 +21,15 @@ impl ReportWriter {
     /// # Parameters
     ///
     /// - `target`: The output target, either `Target::Stdout` or `Target::Stderr`.
+    /// - `color_choice`: Whether the output written to the target is colored.
     ///
     /// # Returns
     ///
     /// A new `ReportWriter` instance configured for the specified target.
-    pub fn new(target: ReportingTarget) -> Self {
+    pub fn new(target: ReportingTarget, color_choice: ColorChoice) -> Self {
         let stream = match target {
-            ReportingTarget::Stdout => StandardStream::stdout(ColorChoice::Auto),
-            ReportingTarget::Stderr => StandardStream::stderr(ColorChoice::Auto),
+            ReportingTarget::Stdout => StandardStream::stdout(color_choice),
+            ReportingTarget::Stderr => StandardStream::stderr(color_choice),
         };
 
         Self { inner: Arc::new(Mutex::new(stream)) }
@@ -43,7 +44,7 @@ impl ReportWriter {
     /// # Panics
     ///
     /// Panics if the internal `Mutex` is poisoned.
-    pub fn lock(&self) -> Gaurd {
+    pub fn lock(&self) -> Gaurd<'_> {
         Gaurd(self.inner.lock().expect("writer lock poisoned, this should never happen"))
     }
 }
@@ -1,15 +1,21 @@
+use std::cmp::Reverse;
 use std::collections::hash_map::Entry;
 use std::iter::Once;
 
 use ahash::HashMap;
+use ahash::HashSet;
 use serde::Deserialize;
 use serde::Serialize;
 use strum::Display;
 
 use mago_fixer::FixPlan;
+use mago_fixer::SafetyClassification;
+use mago_interner::ThreadedInterner;
 use mago_source::SourceIdentifier;
 use mago_span::Span;
 
+use crate::reporter::GroupBy;
+
 mod internal;
 
 pub mod error;
@@ -36,16 +42,18 @@ pub struct Annotation {
 }
 
 /// Represents the severity level of an issue.
+///
+/// Levels are ordered by severity, from `Note` (lowest) to `Error` (highest).
 #[derive(Debug, PartialEq, Eq, Ord, Copy, Clone, Hash, PartialOrd, Deserialize, Serialize, Display)]
 pub enum Level {
-    /// An error, indicating a problem that prevents the code from functioning correctly.
-    Error,
-    /// A warning, indicating a potential problem that may need attention.
-    Warning,
-    /// A help message, suggesting possible solutions or further actions.
-    Help,
     /// A note, providing additional information or context.
     Note,
+    /// A help message, suggesting possible solutions or further actions.
+    Help,
+    /// A warning, indicating a potential problem that may need attention.
+    Warning,
+    /// An error, indicating a problem that prevents the code from functioning correctly.
+    Error,
 }
 
 /// Represents an issue identified in the code.
@@ -63,6 +71,8 @@ pub struct Issue {
     pub help: Option<String>,
     /// An optional link to external resources for more information about the issue.
     pub link: Option<String>,
+    /// An optional URI pointing to the documentation of the rule that reported the issue.
+    pub help_uri: Option<String>,
     /// Annotations associated with the issue, providing additional context or highlighting specific code spans.
     pub annotations: Vec<Annotation>,
     /// Modification suggestions that can be applied to fix the issue.
@@ -170,7 +180,7 @@ impl Level {
     /// # Examples
     ///
     /// ```
-    /// use mago::reporting::Level;
+    /// use mago_reporting::Level;
     ///
     /// let level = Level::Error;
     /// assert_eq!(level.downgrade(), Level::Warning);
@@ -212,6 +222,7 @@ impl Issue {
             notes: Vec::new(),
             help: None,
             link: None,
+            help_uri: None,
             suggestions: Vec::new(),
         }
     }
@@ -363,6 +374,22 @@ impl Issue {
         self
     }
 
+    /// Add a documentation URI to this issue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mago_reporting::Issue;
+    ///
+    /// let issue = Issue::error("This is an error").with_help_uri("https://example.com/rules/my-rule");
+    /// ```
+    #[must_use]
+    pub fn with_help_uri(mut self, help_uri: impl Into<String>) -> Self {
+        self.help_uri = Some(help_uri.into());
+
+        self
+    }
+
     /// Add a code modification suggestion to this issue.
     #[must_use]
     pub fn with_suggestion(mut self, source: SourceIdentifier, plan: FixPlan) -> Self {
@@ -376,6 +403,14 @@ impl Issue {
     pub fn take_suggestions(&mut self) -> Vec<(SourceIdentifier, FixPlan)> {
         self.suggestions.drain(..).collect()
     }
+
+    /// Returns the safety classification a user must opt into for all the suggestions of this issue
+    /// to be applied, i.e. the least safe classification of their operations.
+    ///
+    /// Returns `None` if the issue has no suggestions.
+    pub fn get_fix_safety(&self) -> Option<SafetyClassification> {
+        self.suggestions.iter().filter_map(|(_, plan)| plan.get_maximum_safety_classification()).max()
+    }
 }
 
 impl IssueCollection {
@@ -441,6 +476,81 @@ impl IssueCollection {
         self.issues.iter()
     }
 
+    /// Sorts the issues by source name, then start offset, then level (most severe first), then code.
+    ///
+    /// The message is used as a final tie-breaker, so that the order does not depend on the order
+    /// in which the issues were collected. Issues without a primary annotation come first.
+    pub fn sort(&mut self, interner: &ThreadedInterner) {
+        self.issues.sort_by_cached_key(|issue| {
+            let location = issue
+                .annotations
+                .iter()
+                .find(|annotation| annotation.is_primary())
+                .map(|annotation| (interner.lookup(&annotation.span.start.source.0), annotation.span.start.offset));
+
+            (location, Reverse(issue.level), issue.code.clone(), issue.message.clone())
+        });
+    }
+
+    /// Removes duplicated issues, keeping the first occurrence of each.
+    ///
+    /// Two issues are considered duplicates when they share the same code, primary span, level, and
+    /// message; the annotations, notes, and suggestions of the retained copy are kept as-is.
+    pub fn deduplicate(&mut self) {
+        let mut seen = HashSet::default();
+
+        self.issues.retain(|issue| {
+            let span =
+                issue.annotations.iter().find(|annotation| annotation.is_primary()).map(|annotation| annotation.span);
+
+            seen.insert((issue.code.clone(), span, issue.level, issue.message.clone()))
+        });
+    }
+
+    /// Splits the issues into groups according to the given grouping.
+    ///
+    /// Each group is returned along with its key: the source name for [`GroupBy::File`], the issue
+    /// code for [`GroupBy::Rule`], and the level name for [`GroupBy::Level`]. Issues without a primary
+    /// annotation are grouped under `<unknown>`, and issues without a code under `other`.
+    ///
+    /// Files appear in the order they are first encountered, rules are ordered by their code, and
+    /// levels from the most severe to the least severe. Within a group, issues keep their order.
+    pub fn group_by(self, group_by: GroupBy, interner: &ThreadedInterner) -> Vec<(String, IssueCollection)> {
+        let mut groups: Vec<(String, IssueCollection)> = Vec::new();
+        let mut indices: HashMap<String, usize> = HashMap::default();
+
+        for issue in self.issues {
+            let key = match group_by {
+                GroupBy::File => issue
+                    .annotations
+                    .iter()
+                    .find(|annotation| annotation.is_primary())
+                    .map(|annotation| interner.lookup(&annotation.span.start.source.0).to_string())
+                    .unwrap_or_else(|| "<unknown>".to_string()),
+                GroupBy::Rule => issue.code.clone().unwrap_or_else(|| "other".to_string()),
+                GroupBy::Level => issue.level.to_string(),
+            };
+
+            match indices.entry(key) {
+                Entry::Occupied(occupied_entry) => {
+                    groups[*occupied_entry.get()].1.push(issue);
+                }
+                Entry::Vacant(vacant_entry) => {
+                    groups.push((vacant_entry.key().clone(), IssueCollection::from([issue])));
+                    vacant_entry.insert(groups.len() - 1);
+                }
+            }
+        }
+
+        match group_by {
+            GroupBy::File => {}
+            GroupBy::Rule => groups.sort_by(|(a, _), (b, _)| a.cmp(b)),
+            GroupBy::Level => groups.sort_by_key(|(_, issues)| Reverse(issues.get_highest_level())),
+        }
+
+        groups
+    }
+
     pub fn to_fix_plans(self) -> HashMap<SourceIdentifier, FixPlan> {
         let mut plans: HashMap<SourceIdentifier, FixPlan> = HashMap::default();
         for issue in self.issues.into_iter().filter(|issue| !issue.suggestions.is_empty()) {
@@ -490,3 +600,202 @@ impl FromIterator<Issue> for IssueCollection {
         Self { issues: iter.into_iter().collect() }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mago_source::SourceManager;
+    use mago_span::Position;
+    use mago_span::Span;
+
+    use super::*;
+
+    fn issue(level: Level, code: &str, source: SourceIdentifier, offset: usize) -> Issue {
+        let span = Span::new(Position::new(source, offset), Position::new(source, offset + 1));
+
+        Issue::new(level, format!("{code} at {offset}")).with_code(code).with_annotation(Annotation::primary(span))
+    }
+
+    fn issues(interner: &ThreadedInterner) -> Vec<Issue> {
+        let manager = SourceManager::new(interner.clone());
+        let b = manager.insert_content("b.php".to_string(), "<?php echo 2;".to_string(), true);
+        let a = manager.insert_content("a.php".to_string(), "<?php echo 1;".to_string(), true);
+
+        vec![
+            issue(Level::Warning, "strictness/require-strict-types", b, 0),
+            issue(Level::Note, "comment/no-empty-comments", a, 6),
+            issue(Level::Error, "safety/no-eval", a, 6),
+            issue(Level::Error, "analysis/undefined-function", a, 6),
+            issue(Level::Help, "naming/function", a, 0),
+            Issue::error("parse error without location"),
+        ]
+    }
+
+    #[test]
+    fn test_sort_orders_by_source_offset_level_and_code() {
+        let interner = ThreadedInterner::new();
+        let mut collection = IssueCollection::from(issues(&interner));
+        collection.sort(&interner);
+
+        let messages: Vec<_> = collection.iter().map(|issue| issue.message.as_str()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "parse error without location",
+                "naming/function at 0",
+                "analysis/undefined-function at 6",
+                "safety/no-eval at 6",
+                "comment/no-empty-comments at 6",
+                "strictness/require-strict-types at 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_is_independent_of_collection_order() {
+        let interner = ThreadedInterner::new();
+        let original = issues(&interner);
+
+        let mut forward = IssueCollection::from(original.clone());
+        let mut backward = IssueCollection::from(original.clone().into_iter().rev());
+        let mut rotated = IssueCollection::from(original[3..].iter().chain(original[..3].iter()).cloned());
+
+        forward.sort(&interner);
+        backward.sort(&interner);
+        rotated.sort(&interner);
+
+        let expected = serde_json::to_string(&forward).unwrap();
+
+        assert_eq!(expected, serde_json::to_string(&backward).unwrap());
+        assert_eq!(expected, serde_json::to_string(&rotated).unwrap());
+    }
+
+    fn group_keys(groups: &[(String, IssueCollection)]) -> Vec<(&str, usize)> {
+        groups.iter().map(|(key, issues)| (key.as_str(), issues.len())).collect()
+    }
+
+    #[test]
+    fn test_group_by_file_keeps_first_appearance_order() {
+        let interner = ThreadedInterner::new();
+        let groups = IssueCollection::from(issues(&interner)).group_by(GroupBy::File, &interner);
+
+        assert_eq!(group_keys(&groups), vec![("b.php", 1), ("a.php", 4), ("<unknown>", 1)]);
+    }
+
+    #[test]
+    fn test_group_by_rule_orders_by_code() {
+        let interner = ThreadedInterner::new();
+        let mut collection = IssueCollection::from(issues(&interner));
+        collection.push(Issue::warning("second eval").with_code("safety/no-eval"));
+
+        let groups = collection.group_by(GroupBy::Rule, &interner);
+
+        assert_eq!(
+            group_keys(&groups),
+            vec![
+                ("analysis/undefined-function", 1),
+                ("comment/no-empty-comments", 1),
+                ("naming/function", 1),
+                ("other", 1),
+                ("safety/no-eval", 2),
+                ("strictness/require-strict-types", 1),
+            ]
+        );
+
+        let messages: Vec<_> = groups[4].1.iter().map(|issue| issue.message.as_str()).collect();
+        assert_eq!(messages, vec!["safety/no-eval at 6", "second eval"]);
+    }
+
+    #[test]
+    fn test_group_by_level_orders_by_severity() {
+        let interner = ThreadedInterner::new();
+        let groups = IssueCollection::from(issues(&interner)).group_by(GroupBy::Level, &interner);
+
+        assert_eq!(group_keys(&groups), vec![("Error", 3), ("Warning", 1), ("Help", 1), ("Note", 1)]);
+    }
+
+    #[test]
+    fn test_deduplicate_removes_issues_from_overlapping_sources() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        // The same file, reachable through two overlapping source paths.
+        let first = manager.insert_content("src/a.php".to_string(), "<?php echo 1;".to_string(), true);
+        let second = manager.insert_content("src/a.php".to_string(), "<?php echo 1;".to_string(), true);
+
+        let mut collection = IssueCollection::from([
+            issue(Level::Error, "safety/no-eval", first, 6),
+            issue(Level::Warning, "strictness/require-strict-types", first, 0),
+            issue(Level::Error, "safety/no-eval", second, 6),
+            issue(Level::Warning, "strictness/require-strict-types", second, 0),
+        ]);
+        collection.deduplicate();
+
+        assert_eq!(collection.len(), 2);
+    }
+
+    #[test]
+    fn test_deduplicate_keeps_distinct_issues() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("a.php".to_string(), "<?php echo 1;".to_string(), true);
+
+        let retained = issue(Level::Error, "safety/no-eval", source, 6).with_note("retained");
+        let mut collection = IssueCollection::from([
+            retained.clone(),
+            issue(Level::Error, "safety/no-eval", source, 6).with_note("duplicate"),
+            issue(Level::Warning, "safety/no-eval", source, 6),
+            issue(Level::Error, "safety/no-eval", source, 7),
+            issue(Level::Error, "analysis/undefined-function", source, 6),
+            Issue::error("parse error without location"),
+            Issue::error("parse error without location"),
+        ]);
+        collection.deduplicate();
+
+        assert_eq!(collection.len(), 5);
+        assert_eq!(collection.iter().next(), Some(&retained));
+    }
+
+    #[test]
+    fn test_fix_safety_is_the_least_safe_classification_of_all_suggestions() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("a.php".to_string(), "<?php echo 1;".to_string(), true);
+
+        let mut safe = FixPlan::new();
+        safe.delete(0..1, SafetyClassification::Safe);
+
+        let mut unsafe_plan = FixPlan::new();
+        unsafe_plan.replace(1..2, "a", SafetyClassification::PotentiallyUnsafe);
+        unsafe_plan.insert(3, "b", SafetyClassification::Unsafe);
+
+        let issue = issue(Level::Warning, "strictness/require-identity-comparison", source, 6);
+        assert_eq!(issue.get_fix_safety(), None);
+
+        let issue = issue.with_suggestion(source, safe);
+        assert_eq!(issue.get_fix_safety(), Some(SafetyClassification::Safe));
+
+        let issue = issue.with_suggestion(source, unsafe_plan);
+        assert_eq!(issue.get_fix_safety(), Some(SafetyClassification::Unsafe));
+    }
+
+    #[test]
+    fn test_levels_are_ordered_by_severity() {
+        assert!(Level::Note < Level::Help);
+        assert!(Level::Help < Level::Warning);
+        assert!(Level::Warning < Level::Error);
+
+        let interner = ThreadedInterner::new();
+        let collection = IssueCollection::from(issues(&interner));
+
+        assert_eq!(collection.get_highest_level(), Some(Level::Error));
+        assert!(collection.has_minimum_level(Level::Error));
+
+        let collection = IssueCollection::from(vec![Issue::help("a help message"), Issue::note("a note")]);
+
+        assert_eq!(collection.get_highest_level(), Some(Level::Help));
+        assert!(collection.has_minimum_level(Level::Note));
+        assert!(collection.has_minimum_level(Level::Help));
+        assert!(!collection.has_minimum_level(Level::Warning));
+        assert!(!IssueCollection::new().has_minimum_level(Level::Note));
+    }
+}
@@ -1,9 +1,15 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
 use std::str::FromStr;
 
+use ahash::HashMap;
 use serde::Deserialize;
 use serde::Serialize;
 use strum::Display;
 use strum::VariantNames;
+use termcolor::NoColor;
 
 use mago_interner::ThreadedInterner;
 use mago_source::SourceManager;
@@ -15,6 +21,8 @@ use crate::Issue;
 use crate::IssueCollection;
 use crate::Level;
 
+pub use termcolor::ColorChoice;
+
 /// Defines the output target for the `ReportWriter`.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, VariantNames)]
 #[serde(rename_all = "lowercase")]
@@ -32,15 +40,79 @@ pub enum ReportingTarget {
 #[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "lowercase")]
 pub enum ReportingFormat {
+    /// Code excerpts with annotations, for terminals, with columns counted in Unicode code points.
     #[default]
     Rich,
+    /// One line per issue, followed by its notes, with columns counted in Unicode code points.
     Medium,
+    /// One line per issue, with columns counted in Unicode code points.
     Short,
+    /// GitHub Actions workflow commands, with columns counted in Unicode code points.
     Github,
+    /// A JSON document, with positions given as byte offsets.
     Json,
+    /// The number of issues per level.
     Count,
+    /// A Checkstyle XML report, with columns counted in Unicode code points.
     Checkstyle,
+    /// One line per issue, as expected by Emacs, with columns counted in Unicode code points.
     Emacs,
+    /// A SARIF log, with columns counted in UTF-16 code units, as declared by its `columnKind`.
+    Sarif,
+    /// A JUnit XML report, with columns counted in Unicode code points.
+    Junit,
+    /// A GitLab code quality report, with lines only.
+    Gitlab,
+    /// A standalone HTML page, with columns counted in Unicode code points.
+    Html,
+    /// A reviewdog diagnostic result, with columns counted in UTF-8 bytes, as required by the format.
+    Rdjson,
+    /// TeamCity service messages, with lines only.
+    Teamcity,
+}
+
+/// How issues are grouped when they are reported.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, VariantNames)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum GroupBy {
+    /// Group issues by the file they were found in.
+    #[default]
+    File,
+    /// Group issues by the rule that reported them.
+    Rule,
+    /// Group issues by their severity level.
+    Level,
+}
+
+/// How source paths are displayed in reports.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, VariantNames)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum PathStyle {
+    /// Display paths relative to the source root, falling back to absolute paths for sources outside of it.
+    #[default]
+    Relative,
+    /// Display absolute paths.
+    Absolute,
+}
+
+/// Options that control how the reporting formats render issues.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ReportingOptions {
+    /// The number of source lines to show above and below each annotated span in code frames.
+    ///
+    /// When `None`, the default frame of the format is used.
+    pub context_lines: Option<usize>,
+    /// How issues are grouped in formats that support grouping.
+    pub group_by: GroupBy,
+    /// How source paths are displayed.
+    pub paths: PathStyle,
+    /// The descriptions of the rules, keyed by the codes of the issues they report.
+    ///
+    /// Formats which describe the rules of a report, such as SARIF, fall back to a description derived
+    /// from the code for codes without one.
+    pub rule_descriptions: HashMap<String, String>,
 }
 
 #[derive(Clone)]
@@ -49,11 +121,62 @@ pub struct Reporter {
     manager: SourceManager,
     target: ReportingTarget,
     writer: ReportWriter,
+    sort: bool,
+    options: ReportingOptions,
 }
 
 impl Reporter {
-    pub fn new(interner: ThreadedInterner, manager: SourceManager, target: ReportingTarget) -> Self {
-        Self { interner, manager, target, writer: ReportWriter::new(target) }
+    pub fn new(
+        interner: ThreadedInterner,
+        manager: SourceManager,
+        target: ReportingTarget,
+        color_choice: ColorChoice,
+    ) -> Self {
+        Self {
+            interner,
+            manager,
+            target,
+            writer: ReportWriter::new(target, color_choice),
+            sort: true,
+            options: ReportingOptions::default(),
+        }
+    }
+
+    /// Sets whether issues are sorted before being reported, which is enabled by default.
+    ///
+    /// When disabled, issues are reported in the order they were collected.
+    pub fn with_sorting(mut self, sort: bool) -> Self {
+        self.sort = sort;
+
+        self
+    }
+
+    /// Sets the number of source lines to show above and below each annotated span in code frames.
+    pub fn with_context_lines(mut self, context_lines: Option<usize>) -> Self {
+        self.options.context_lines = context_lines;
+
+        self
+    }
+
+    /// Sets how issues are grouped in formats that support grouping.
+    pub fn with_grouping(mut self, group_by: GroupBy) -> Self {
+        self.options.group_by = group_by;
+
+        self
+    }
+
+    /// Sets how source paths are displayed.
+    pub fn with_path_style(mut self, paths: PathStyle) -> Self {
+        self.options.paths = paths;
+
+        self
+    }
+
+    /// Sets the descriptions of the rules, keyed by the codes of the issues they report.
+    pub fn with_rule_descriptions(mut self, descriptions: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.options.rule_descriptions = descriptions.into_iter().collect();
+
+        self
     }
 
     pub fn report(
@@ -61,7 +184,46 @@ impl Reporter {
         issues: impl IntoIterator<Item = Issue>,
         format: ReportingFormat,
     ) -> Result<Option<Level>, ReportingError> {
-        format.emit(&mut self.writer.lock(), &self.manager, &self.interner, IssueCollection::from(issues))
+        format.emit(&mut self.writer.lock(), &self.manager, &self.interner, self.collect(issues), &self.options)
+    }
+
+    /// Writes the report to the file at the given path instead of the configured target.
+    ///
+    /// Missing parent directories are created, and an existing file is truncated.
+    pub fn report_to_file(
+        &self,
+        issues: impl IntoIterator<Item = Issue>,
+        format: ReportingFormat,
+        path: &Path,
+    ) -> Result<Option<Level>, ReportingError> {
+        let output_error = |error| ReportingError::OutputError(path.to_path_buf(), error);
+
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(output_error)?;
+        }
+
+        let file = File::create(path).map_err(output_error)?;
+        let mut writer = NoColor::new(BufWriter::new(file));
+
+        let highest_level = format
+            .emit(&mut writer, &self.manager, &self.interner, self.collect(issues), &self.options)
+            .map_err(|error| match error {
+                ReportingError::IoError(error) => output_error(error),
+                error => error,
+            })?;
+
+        writer.get_mut().flush().map_err(output_error)?;
+
+        Ok(highest_level)
+    }
+
+    fn collect(&self, issues: impl IntoIterator<Item = Issue>) -> IssueCollection {
+        let mut issues = IssueCollection::from(issues);
+        if self.sort {
+            issues.sort(&self.interner);
+        }
+
+        issues
     }
 }
 
@@ -74,6 +236,8 @@ impl std::fmt::Debug for Reporter {
             .field("interner", &self.interner)
             .field("manager", &self.manager)
             .field("target", &self.target)
+            .field("sort", &self.sort)
+            .field("options", &self.options)
             .finish_non_exhaustive()
     }
 }
@@ -103,7 +267,94 @@ impl FromStr for ReportingFormat {
             "count" => Ok(Self::Count),
             "checkstyle" => Ok(Self::Checkstyle),
             "emacs" => Ok(Self::Emacs),
+            "sarif" => Ok(Self::Sarif),
+            "junit" => Ok(Self::Junit),
+            "gitlab" => Ok(Self::Gitlab),
+            "html" => Ok(Self::Html),
+            "rdjson" => Ok(Self::Rdjson),
+            "teamcity" => Ok(Self::Teamcity),
             _ => Err(ReportingError::InvalidFormat(s.to_string())),
         }
     }
 }
+
+impl FromStr for GroupBy {
+    type Err = ReportingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "file" => Ok(Self::File),
+            "rule" => Ok(Self::Rule),
+            "level" => Ok(Self::Level),
+            _ => Err(ReportingError::InvalidGroupBy(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for PathStyle {
+    type Err = ReportingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "relative" => Ok(Self::Relative),
+            "absolute" => Ok(Self::Absolute),
+            _ => Err(ReportingError::InvalidPathStyle(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_span::Position;
+    use mago_span::Span;
+
+    use crate::Annotation;
+
+    use super::*;
+
+    #[test]
+    fn test_report_to_file_creates_parent_directories_and_truncates() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("a.php".to_string(), "<?php eval('');".to_string(), true);
+        let reporter = Reporter::new(interner, manager, ReportingTarget::Stdout, ColorChoice::Always);
+
+        let directory = std::env::temp_dir().join(format!("mago-reporter-{}", std::process::id()));
+        let path = directory.join("reports").join("lint.txt");
+
+        let issue = Issue::error("Unsafe use of `eval`.")
+            .with_code("safety/no-eval")
+            .with_annotation(Annotation::primary(Span::new(Position::new(source, 6), Position::new(source, 10))));
+
+        let level = reporter.report_to_file(vec![issue.clone(), issue], ReportingFormat::Short, &path).unwrap();
+        assert_eq!(level, Some(Level::Error));
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("safety/no-eval"));
+        assert!(!written.contains('\u{1b}'), "reports written to files must not contain color codes");
+
+        let level = reporter.report_to_file(Vec::new(), ReportingFormat::Count, &path).unwrap();
+        assert_eq!(level, None);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "0 error(s), 0 warning(s), 0 help message(s), 0 note(s) across 0 file(s)\n"
+        );
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_report_to_file_fails_with_the_output_path() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let reporter = Reporter::new(interner, manager, ReportingTarget::Stdout, ColorChoice::Never);
+
+        let directory = std::env::temp_dir().join(format!("mago-reporter-directory-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let error = reporter.report_to_file(Vec::new(), ReportingFormat::Json, &directory).unwrap_err();
+        assert!(matches!(error, ReportingError::OutputError(ref path, _) if path == &directory));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
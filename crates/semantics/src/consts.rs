@@ -131,3 +131,109 @@ pub const RESERVED_KEYWORDS: [&str; 77] = [
     "fn",
     "match",
 ];
+
+pub const THIS_VARIABLE: &str = "$this";
+
+/// The variables that are defined by PHP in every scope, or in the scope of methods, for `$this`.
+pub const PREDEFINED_VARIABLES: [&str; 11] = [
+    THIS_VARIABLE,
+    "$GLOBALS",
+    "$_SERVER",
+    "$_GET",
+    "$_POST",
+    "$_FILES",
+    "$_COOKIE",
+    "$_SESSION",
+    "$_REQUEST",
+    "$_ENV",
+    "$http_response_header",
+];
+
+/// The functions that define the variables of the local scope, which cannot be known statically.
+pub const SCOPE_DEFINING_FUNCTIONS: [&str; 1] = ["extract"];
+
+/// The parameters of internal functions that are passed by reference to be assigned, such as the
+/// matches of `preg_match()`, as pairs of the name of the function and the index of the parameter.
+pub const OUT_PARAMETERS: [(&str, usize); 47] = [
+    ("preg_match", 2),
+    ("preg_match_all", 2),
+    ("preg_replace", 4),
+    ("preg_replace_callback", 4),
+    ("preg_replace_callback_array", 3),
+    ("str_replace", 3),
+    ("str_ireplace", 3),
+    ("parse_str", 1),
+    ("mb_parse_str", 1),
+    ("exec", 1),
+    ("exec", 2),
+    ("system", 1),
+    ("passthru", 1),
+    ("similar_text", 2),
+    ("sscanf", 2),
+    ("sscanf", 3),
+    ("sscanf", 4),
+    ("sscanf", 5),
+    ("fscanf", 2),
+    ("fscanf", 3),
+    ("fscanf", 4),
+    ("fscanf", 5),
+    ("getimagesize", 1),
+    ("is_callable", 2),
+    ("headers_sent", 0),
+    ("headers_sent", 1),
+    ("flock", 2),
+    ("fsockopen", 2),
+    ("fsockopen", 3),
+    ("stream_socket_client", 1),
+    ("stream_socket_client", 2),
+    ("stream_socket_server", 1),
+    ("stream_socket_server", 2),
+    ("proc_open", 2),
+    ("getmxrr", 1),
+    ("getmxrr", 2),
+    ("dns_get_record", 2),
+    ("dns_get_record", 3),
+    ("openssl_sign", 1),
+    ("openssl_seal", 1),
+    ("openssl_seal", 2),
+    ("openssl_open", 1),
+    ("openssl_encrypt", 5),
+    ("openssl_private_encrypt", 1),
+    ("openssl_public_encrypt", 1),
+    ("openssl_private_decrypt", 1),
+    ("openssl_public_decrypt", 1),
+];
+
+// The codes of the reported issues, which allow suppressing them with the `@mago-expect`, `@mago-ignore-file`,
+// and `@mago-disable` comments, and in the baseline.
+pub const CONTINUE_TARGETING_SWITCH_CODE: &str = "semantics/continue-targeting-switch";
+pub const DUPLICATE_ARRAY_KEY_CODE: &str = "semantics/duplicate-array-key";
+pub const DUPLICATE_ENUM_VALUE_CODE: &str = "semantics/duplicate-enum-value";
+pub const DUPLICATE_MATCH_CONDITION_CODE: &str = "semantics/duplicate-match-condition";
+pub const DUPLICATE_MEMBER_CODE: &str = "semantics/duplicate-member";
+pub const DUPLICATE_SWITCH_CASE_CODE: &str = "semantics/duplicate-switch-case";
+pub const FINAL_PRIVATE_METHOD_CODE: &str = "semantics/final-private-method";
+pub const INVALID_ARGUMENT_CODE: &str = "semantics/invalid-argument";
+pub const INVALID_ATTRIBUTE_CODE: &str = "semantics/invalid-attribute";
+pub const INVALID_CLASS_LIKE_CODE: &str = "semantics/invalid-class-like";
+pub const INVALID_CLASS_LIKE_CONSTANT_CODE: &str = "semantics/invalid-class-like-constant";
+pub const INVALID_DECLARE_CODE: &str = "semantics/invalid-declare";
+pub const INVALID_HALT_COMPILER_CODE: &str = "semantics/invalid-halt-compiler";
+pub const INVALID_INHERITANCE_CODE: &str = "semantics/invalid-inheritance";
+pub const INVALID_LOOP_CONTROL_CODE: &str = "semantics/invalid-loop-control";
+pub const INVALID_MAGIC_METHOD_CODE: &str = "semantics/invalid-magic-method";
+pub const INVALID_MATCH_CODE: &str = "semantics/invalid-match";
+pub const INVALID_METHOD_CODE: &str = "semantics/invalid-method";
+pub const INVALID_NAMESPACE_CODE: &str = "semantics/invalid-namespace";
+pub const INVALID_PARAMETER_CODE: &str = "semantics/invalid-parameter";
+pub const INVALID_PROPERTY_CODE: &str = "semantics/invalid-property";
+pub const INVALID_RETURN_CODE: &str = "semantics/invalid-return";
+pub const INVALID_SWITCH_CODE: &str = "semantics/invalid-switch";
+pub const INVALID_TRAIT_USE_CODE: &str = "semantics/invalid-trait-use";
+pub const INVALID_TRY_CODE: &str = "semantics/invalid-try";
+pub const INVALID_TYPE_CODE: &str = "semantics/invalid-type";
+pub const POSSIBLY_UNDEFINED_VARIABLE_CODE: &str = "semantics/possibly-undefined-variable";
+pub const UNDEFINED_LABEL_CODE: &str = "semantics/undefined-label";
+pub const UNDEFINED_VARIABLE_CODE: &str = "semantics/undefined-variable";
+pub const UNREACHABLE_CODE_CODE: &str = "semantics/unreachable-code";
+pub const UNSUPPORTED_FEATURE_CODE: &str = "semantics/unsupported-feature";
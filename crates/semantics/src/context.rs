@@ -1,26 +1,33 @@
 use mago_ast::ast::*;
 use mago_ast::Node;
 use mago_ast::Program;
+use mago_ast::Trivia;
 use mago_interner::ThreadedInterner;
 use mago_names::Names;
+use mago_php_version::PHPVersion;
+use mago_reporting::Annotation;
 use mago_reporting::Issue;
 use mago_reporting::IssueCollection;
 use mago_span::HasSpan;
 use mago_span::Position;
 use mago_span::Span;
 
+use crate::consts::UNSUPPORTED_FEATURE_CODE;
+
 #[derive(Debug)]
 pub struct Context<'a> {
     pub interner: &'a ThreadedInterner,
+    pub version: PHPVersion,
     program: &'a Program,
     names: &'a Names,
     issues: IssueCollection,
     ancestors: Vec<Span>,
+    hints: Vec<bool>,
 }
 
 impl<'a> Context<'a> {
-    pub fn new(interner: &'a ThreadedInterner, program: &'a Program, names: &'a Names) -> Self {
-        Self { interner, program, names, issues: IssueCollection::default(), ancestors: vec![] }
+    pub fn new(interner: &'a ThreadedInterner, version: PHPVersion, program: &'a Program, names: &'a Names) -> Self {
+        Self { interner, version, program, names, issues: IssueCollection::default(), ancestors: vec![], hints: vec![] }
     }
 
     pub fn program(&self) -> Node<'a> {
@@ -31,6 +38,27 @@ impl<'a> Context<'a> {
         self.issues.push(issue);
     }
 
+    /// Reports an error if the given feature, introduced in the given version of PHP, is used while an older
+    /// version is targeted.
+    pub fn check_version(&mut self, feature: &str, introduced_in: PHPVersion, span: Span) {
+        if self.version.is_supported(introduced_in) {
+            return;
+        }
+
+        self.report(
+            Issue::error(format!("{} requires PHP {} or later.", feature, introduced_in))
+                .with_code(UNSUPPORTED_FEATURE_CODE)
+                .with_annotation(Annotation::primary(span).with_message(format!("{} used here.", feature)))
+                .with_note(format!("The targeted PHP version is {}.", self.version))
+                .with_help("Target a newer version of PHP with the `php_version` option, or avoid using this feature."),
+        );
+    }
+
+    /// Returns the docblock immediately preceding the given span, if any.
+    pub fn docblock(&self, span: Span) -> Option<&'a Trivia> {
+        self.program.comments.docblock(span)
+    }
+
     pub fn lookup_name(&self, position: &Position) -> &'a str {
         self.interner.lookup(self.names.get(position))
     }
@@ -84,6 +112,31 @@ impl<'a> Context<'a> {
         self.ancestors.pop();
     }
 
+    /// Returns whether the statement at the given span is in the outermost scope of the program, either at its
+    /// top level, or in the body of a namespace declared without braces.
+    pub fn is_in_outermost_scope(&self, span: Span) -> bool {
+        self.program.statements.iter().any(|statement| match statement {
+            Statement::Namespace(namespace) => match &namespace.body {
+                NamespaceBody::Implicit(body) => body.statements.iter().any(|statement| statement.span() == span),
+                NamespaceBody::BraceDelimited(_) => false,
+            },
+            statement => statement.span() == span,
+        })
+    }
+
+    pub fn push_hint(&mut self, hint: &Hint) {
+        self.hints.push(matches!(hint, Hint::Union(_) | Hint::Nullable(_)));
+    }
+
+    pub fn pop_hint(&mut self) {
+        self.hints.pop();
+    }
+
+    /// Returns whether the type hint being walked is a member of a union type, or is made nullable with `?`.
+    pub fn is_in_union_or_nullable_hint(&self) -> bool {
+        self.hints.len() >= 2 && self.hints[self.hints.len() - 2]
+    }
+
     pub fn take_issue_collection(self) -> IssueCollection {
         self.issues
     }
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use mago_ast::ast::*;
+use mago_ast::Node;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_walker::Walker;
+
+use crate::consts::DUPLICATE_ARRAY_KEY_CODE;
+use crate::consts::DUPLICATE_ENUM_VALUE_CODE;
+use crate::consts::DUPLICATE_MATCH_CONDITION_CODE;
+use crate::consts::DUPLICATE_SWITCH_CASE_CODE;
+use crate::context::Context;
+use crate::evaluator::Evaluator;
+use crate::evaluator::Value;
+
+/// Reports the values which are duplicated where PHP compares them, as evaluated at compile time: the cases of
+/// `switch` statements and the conditions of `match` expressions which are never matched, the keys of array
+/// literals whose value is overwritten, and the cases of backed enums sharing a value.
+///
+/// Only the values which are identical are reported, as the loose comparison of a `switch` statement is not
+/// transitive, e.g. `case 0:` does not shadow `case '0.0':`.
+#[derive(Clone, Debug)]
+pub struct DuplicatesWalker<'e, 'a> {
+    evaluator: &'e Evaluator<'a>,
+    /// The spans of the array literals which destructure a value, and whose keys are read rather than written.
+    destructurings: HashSet<Span>,
+}
+
+impl<'e, 'a> DuplicatesWalker<'e, 'a> {
+    pub fn new(context: &Context<'_>, evaluator: &'e Evaluator<'a>) -> Self {
+        let mut destructurings = HashSet::new();
+        for node in context.program().descendants() {
+            match node {
+                Node::AssignmentOperation(Assignment { lhs, operator: AssignmentOperator::Assign(_), .. }) => {
+                    collect_destructurings(lhs, &mut destructurings);
+                }
+                Node::Foreach(foreach) => match &foreach.target {
+                    ForeachTarget::Value(target) => collect_destructurings(&target.value, &mut destructurings),
+                    ForeachTarget::KeyValue(target) => collect_destructurings(&target.value, &mut destructurings),
+                },
+                _ => {}
+            }
+        }
+
+        Self { evaluator, destructurings }
+    }
+
+    fn check_keys(&self, span: Span, elements: &[ArrayElement], context: &mut Context<'_>) {
+        if self.destructurings.contains(&span) {
+            return;
+        }
+
+        let mut first_uses = HashMap::new();
+        for (element, key) in elements.iter().zip(self.evaluator.keys(elements)) {
+            let Some(key) = key else {
+                continue;
+            };
+
+            match element {
+                ArrayElement::KeyValue(element) => {
+                    let Some(&(first_use, implicit)) = first_uses.get(&key) else {
+                        first_uses.insert(key, (element.key.span(), false));
+
+                        continue;
+                    };
+
+                    context.report(
+                        Issue::warning(format!(
+                            "The key `{}` is used more than once in the array, so its previous value is overwritten.",
+                            key.describe()
+                        ))
+                        .with_code(DUPLICATE_ARRAY_KEY_CODE)
+                        .with_annotation(
+                            Annotation::primary(element.key.span()).with_message("The key is used again here."),
+                        )
+                        .with_annotation(Annotation::secondary(first_use).with_message(if implicit {
+                            "The key is first assigned implicitly to this value."
+                        } else {
+                            "The key is first used here."
+                        }))
+                        .with_help("Remove the overwritten entry, or change its key."),
+                    );
+                }
+                // The implicit keys follow the largest integer key, so they are never used before.
+                ArrayElement::Value(element) => {
+                    first_uses.insert(key, (element.value.span(), true));
+                }
+                ArrayElement::Variadic(_) | ArrayElement::Missing(_) => {}
+            }
+        }
+    }
+}
+
+impl Walker<Context<'_>> for DuplicatesWalker<'_, '_> {
+    fn walk_in_switch(&self, switch: &Switch, context: &mut Context<'_>) {
+        let mut values: Vec<(Value, Span)> = vec![];
+        for case in switch.body.cases() {
+            let SwitchCase::Expression(case) = case else {
+                continue;
+            };
+
+            let value = self.evaluator.evaluate(&case.expression);
+            if !value.is_known() {
+                continue;
+            }
+
+            let Some((_, first)) = values.iter().find(|(previous, _)| previous.is_identical(&value) == Some(true))
+            else {
+                values.push((value, case.expression.span()));
+
+                continue;
+            };
+
+            context.report(
+                Issue::warning(format!(
+                    "The value `{}` of this case is already matched by a previous case of the switch statement.",
+                    value.describe()
+                ))
+                .with_code(DUPLICATE_SWITCH_CASE_CODE)
+                .with_annotation(
+                    Annotation::primary(case.expression.span()).with_message("This case is never matched."),
+                )
+                .with_annotation(Annotation::secondary(*first).with_message("The value is first matched by this case."))
+                .with_help("Remove the duplicate case, or change its value."),
+            );
+        }
+    }
+
+    fn walk_in_match(&self, r#match: &Match, context: &mut Context<'_>) {
+        let mut values: Vec<(Value, Span)> = vec![];
+        for arm in r#match.arms.iter() {
+            let MatchArm::Expression(arm) = arm else {
+                continue;
+            };
+
+            for condition in arm.conditions.iter() {
+                let value = self.evaluator.evaluate(condition);
+                if !value.is_known() {
+                    continue;
+                }
+
+                let Some((_, first)) = values.iter().find(|(previous, _)| previous.is_identical(&value) == Some(true))
+                else {
+                    values.push((value, condition.span()));
+
+                    continue;
+                };
+
+                context.report(
+                    Issue::warning(format!(
+                        "The value `{}` of this condition is already matched by a previous arm of the match expression.",
+                        value.describe()
+                    )).with_code(DUPLICATE_MATCH_CONDITION_CODE)
+                    .with_annotation(Annotation::primary(condition.span()).with_message("This condition is never matched."))
+                    .with_annotation(
+                        Annotation::secondary(*first).with_message("The value is first matched by this condition."),
+                    )
+                    .with_help("Remove the duplicate condition, or change its value."),
+                );
+            }
+        }
+    }
+
+    fn walk_in_array(&self, array: &Array, context: &mut Context<'_>) {
+        self.check_keys(array.span(), array.elements.as_slice(), context);
+    }
+
+    fn walk_in_legacy_array(&self, legacy_array: &LegacyArray, context: &mut Context<'_>) {
+        self.check_keys(legacy_array.span(), legacy_array.elements.as_slice(), context);
+    }
+
+    fn walk_in_enum(&self, r#enum: &Enum, context: &mut Context<'_>) {
+        let mut values: Vec<(Value, &LocalIdentifier, Span)> = vec![];
+        for member in r#enum.members.iter() {
+            let ClassLikeMember::EnumCase(EnumCase { item: EnumCaseItem::Backed(item), .. }) = member else {
+                continue;
+            };
+
+            let value = self.evaluator.evaluate(&item.value);
+            if !value.is_known() {
+                continue;
+            }
+
+            let Some((_, first_name, first)) =
+                values.iter().find(|(previous, _, _)| previous.is_identical(&value) == Some(true))
+            else {
+                values.push((value, &item.name, item.value.span()));
+
+                continue;
+            };
+
+            let first_name = context.interner.lookup(&first_name.value);
+
+            context.report(
+                Issue::error(format!(
+                    "The cases `{}` and `{}` of the enum `{}` have the same value `{}`.",
+                    first_name,
+                    context.interner.lookup(&item.name.value),
+                    context.lookup_name(&r#enum.name.span.start),
+                    value.describe()
+                ))
+                .with_code(DUPLICATE_ENUM_VALUE_CODE)
+                .with_annotation(Annotation::primary(item.value.span()).with_message("This value is already used."))
+                .with_annotation(
+                    Annotation::secondary(*first)
+                        .with_message(format!("The case `{}` first uses it here.", first_name)),
+                )
+                .with_help("Use a distinct value for each case of the enum."),
+            );
+        }
+    }
+}
+
+/// Collects the spans of the array literals destructuring a value, including the nested ones.
+fn collect_destructurings(expression: &Expression, spans: &mut HashSet<Span>) {
+    let elements = match expression {
+        Expression::Array(array) => array.elements.as_slice(),
+        Expression::LegacyArray(array) => array.elements.as_slice(),
+        Expression::List(list) => list.elements.as_slice(),
+        _ => return,
+    };
+
+    spans.insert(expression.span());
+    for element in elements {
+        match element {
+            ArrayElement::KeyValue(element) => collect_destructurings(&element.value, spans),
+            ArrayElement::Value(element) => collect_destructurings(&element.value, spans),
+            ArrayElement::Variadic(_) | ArrayElement::Missing(_) => {}
+        }
+    }
+}
@@ -0,0 +1,1582 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use mago_ast::ast::*;
+use mago_ast::Node;
+use mago_ast::Program;
+use mago_ast::Sequence;
+use mago_interner::ThreadedInterner;
+use mago_names::Names;
+use mago_php_version::PHPVersion;
+use mago_span::HasPosition;
+use mago_span::HasSpan;
+use mago_span::Span;
+
+/// The number of significant digits of the floats converted to strings, which is the default value of the
+/// `precision` setting of PHP.
+const FLOAT_PRECISION: usize = 14;
+
+/// The characters which may surround a numeric string.
+const WHITESPACE: &[u8] = b" \t\n\r\x0B\x0C";
+
+/// The value of a constant expression, as PHP evaluates it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    /// A string, as bytes, since the strings of PHP are not necessarily valid UTF-8.
+    String(Vec<u8>),
+    /// An array, as its entries in the order they were inserted.
+    Array(Vec<(ArrayKey, Value)>),
+    /// A case of an enum, by the name of the enum and the name of the case.
+    EnumCase(String, String),
+    /// A value which is not known at compile time, such as the result of a function call, or of an operation
+    /// which fails at runtime.
+    Unknown,
+}
+
+/// The key of an entry of an array, which is either an integer, or a string that is not a decimal integer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArrayKey {
+    Integer(i64),
+    String(Vec<u8>),
+}
+
+/// A value converted to a number, as the operands of arithmetic operations are.
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+/// The next integer key of an array, used by the entries which do not specify one.
+#[derive(Debug)]
+struct NextKey {
+    next: Option<i128>,
+    /// Whether the next key may be negative, following a negative key, as of PHP 8.3.
+    follows_negative_keys: bool,
+}
+
+/// A constant, by the index of its class-like entity, if any, and its name.
+type ConstantKey = (Option<usize>, String);
+
+/// A class-like entity of the program, and its constants.
+#[derive(Debug)]
+struct ClassLike<'a> {
+    span: Span,
+    /// The name of the class-like entity, or `None` for an anonymous class.
+    name: Option<String>,
+    /// The name of the extended class, if any.
+    parent: Option<String>,
+    /// The lowercased names of the class-like entities the constants may be inherited from.
+    ancestors: Vec<String>,
+    constants: HashMap<String, &'a Expression>,
+    /// The cases of the enum, and the expressions of their values, if it is backed.
+    cases: HashMap<String, Option<&'a Expression>>,
+    is_trait: bool,
+}
+
+/// Evaluates the constant expressions of a program, such as the values of its constants, or the cases of its
+/// `switch` statements.
+///
+/// The constants referenced are evaluated when they are declared in the program, with `const` or `define()`,
+/// or in its class-like entities, as well as a few built-in constants, which do not depend on the platform.
+/// Anything else, such as calls, variables, or operations which fail at runtime, is unknown.
+#[derive(Debug)]
+pub struct Evaluator<'a> {
+    interner: &'a ThreadedInterner,
+    version: PHPVersion,
+    names: &'a Names,
+    constants: HashMap<String, &'a Expression>,
+    class_likes: Vec<ClassLike<'a>>,
+    class_like_indices: HashMap<String, usize>,
+    /// The spans of the named namespaces, and their names.
+    namespaces: Vec<(Span, &'a str)>,
+    /// The values of the constants evaluated so far, or `None` for the constants being evaluated.
+    values: Mutex<HashMap<ConstantKey, Option<Value>>>,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new(interner: &'a ThreadedInterner, version: PHPVersion, program: &'a Program, names: &'a Names) -> Self {
+        let mut evaluator = Self {
+            interner,
+            version,
+            names,
+            constants: HashMap::new(),
+            class_likes: vec![],
+            class_like_indices: HashMap::new(),
+            namespaces: vec![],
+            values: Mutex::new(HashMap::new()),
+        };
+
+        evaluator.collect_defined_constants(program.statements.iter());
+        for node in Node::Program(program).descendants() {
+            match node {
+                Node::Namespace(Namespace { name: Some(name), .. }) => {
+                    evaluator.namespaces.push((node.span(), interner.lookup(&name.value())));
+                }
+                Node::Constant(constant) => {
+                    for item in constant.items.iter() {
+                        if let Some(name) = evaluator.resolve(&item.name) {
+                            evaluator.constants.entry(constant_key(name)).or_insert(&item.value);
+                        }
+                    }
+                }
+                Node::Class(class) => {
+                    let parent = class.extends.as_ref().and_then(|extends| extends.types.first());
+                    let implemented = class.implements.iter().flat_map(|implements| implements.types.iter());
+
+                    evaluator.collect_class_like(
+                        class.span(),
+                        Some(&class.name),
+                        parent,
+                        parent.into_iter().chain(implemented),
+                        &class.members,
+                        false,
+                    );
+                }
+                Node::AnonymousClass(class) => {
+                    let parent = class.extends.as_ref().and_then(|extends| extends.types.first());
+                    let implemented = class.implements.iter().flat_map(|implements| implements.types.iter());
+
+                    evaluator.collect_class_like(
+                        class.span(),
+                        None,
+                        parent,
+                        parent.into_iter().chain(implemented),
+                        &class.members,
+                        false,
+                    );
+                }
+                Node::Interface(interface) => {
+                    let extended = interface.extends.iter().flat_map(|extends| extends.types.iter());
+
+                    evaluator.collect_class_like(
+                        interface.span(),
+                        Some(&interface.name),
+                        None,
+                        extended,
+                        &interface.members,
+                        false,
+                    );
+                }
+                Node::Enum(r#enum) => {
+                    let implemented = r#enum.implements.iter().flat_map(|implements| implements.types.iter());
+
+                    evaluator.collect_class_like(
+                        r#enum.span(),
+                        Some(&r#enum.name),
+                        None,
+                        implemented,
+                        &r#enum.members,
+                        false,
+                    );
+                }
+                Node::Trait(r#trait) => {
+                    evaluator.collect_class_like(
+                        r#trait.span(),
+                        Some(&r#trait.name),
+                        None,
+                        std::iter::empty(),
+                        &r#trait.members,
+                        true,
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        evaluator
+    }
+
+    /// Evaluates the given expression, returning `Value::Unknown` if its value is not known at compile time.
+    pub fn evaluate(&self, expression: &Expression) -> Value {
+        match expression {
+            Expression::Parenthesized(parenthesized) => self.evaluate(&parenthesized.expression),
+            Expression::Literal(literal) => self.literal(literal),
+            Expression::Binary(binary) => self.binary(binary),
+            Expression::UnaryPrefix(unary) => self.unary(unary),
+            Expression::Conditional(conditional) => {
+                let condition = self.evaluate(&conditional.condition);
+
+                match (condition.to_bool(), &conditional.then) {
+                    (Some(true), Some(then)) => self.evaluate(then),
+                    (Some(true), None) => condition,
+                    (Some(false), _) => self.evaluate(&conditional.r#else),
+                    (None, _) => Value::Unknown,
+                }
+            }
+            Expression::Array(array) => self.array(array.elements.as_slice()),
+            Expression::LegacyArray(array) => self.array(array.elements.as_slice()),
+            Expression::ArrayAccess(access) => self.array_access(access),
+            Expression::ConstantAccess(access) => self.constant(&access.name),
+            // The identifiers on the left-hand side of an operator are not converted to constant accesses.
+            Expression::Identifier(identifier) => self.constant(identifier),
+            Expression::Access(access) => match access.as_ref() {
+                Access::ClassConstant(access) => self.class_constant(access),
+                Access::Property(access) => self.enum_case_property(&access.object, &access.property),
+                Access::NullSafeProperty(access) => self.enum_case_property(&access.object, &access.property),
+                Access::StaticProperty(_) => Value::Unknown,
+            },
+            _ => Value::Unknown,
+        }
+    }
+
+    /// Returns the keys of the elements of an array literal, or `None` for the elements whose key is not known,
+    /// and for the spread elements.
+    pub fn keys(&self, elements: &[ArrayElement]) -> Vec<Option<ArrayKey>> {
+        let mut next = Some(NextKey::new(self.version));
+        let mut keys = Vec::with_capacity(elements.len());
+        for element in elements {
+            let key = match element {
+                ArrayElement::KeyValue(element) => {
+                    let key = ArrayKey::from_value(&self.evaluate(&element.key));
+                    match &key {
+                        // An unknown key may be an integer, which the next key depends on.
+                        None => next = None,
+                        Some(key) => next.iter_mut().for_each(|next| next.observe(key)),
+                    }
+
+                    key
+                }
+                ArrayElement::Value(_) => next.as_mut().and_then(NextKey::append),
+                ArrayElement::Variadic(element) => {
+                    match self.evaluate(&element.value) {
+                        Value::Array(entries) => {
+                            for (key, _) in entries {
+                                if matches!(key, ArrayKey::Integer(_))
+                                    && next.as_mut().and_then(NextKey::append).is_none()
+                                {
+                                    next = None;
+                                }
+                            }
+                        }
+                        _ => next = None,
+                    }
+
+                    None
+                }
+                ArrayElement::Missing(_) => None,
+            };
+
+            keys.push(key);
+        }
+
+        keys
+    }
+
+    fn literal(&self, literal: &Literal) -> Value {
+        match literal {
+            Literal::String(string) => {
+                let raw = self.interner.lookup(&string.value).as_bytes();
+                let Some(content) = raw.get(1..raw.len().saturating_sub(1)) else {
+                    return Value::Unknown;
+                };
+
+                match string.kind {
+                    LiteralStringKind::SingleQuoted => Value::String(unescape_single_quoted(content)),
+                    LiteralStringKind::DoubleQuoted => {
+                        unescape_double_quoted(content).map_or(Value::Unknown, Value::String)
+                    }
+                }
+            }
+            Literal::Integer(integer) => match integer.value.map(i64::try_from) {
+                Some(Ok(value)) => Value::Integer(value),
+                // The integer literals which do not fit in a 64-bit signed integer are evaluated as floats.
+                _ => parse_float(self.interner.lookup(&integer.raw)).map_or(Value::Unknown, Value::Float),
+            },
+            Literal::Float(float) => Value::Float(float.value.into_inner()),
+            Literal::True(_) => Value::Bool(true),
+            Literal::False(_) => Value::Bool(false),
+            Literal::Null(_) => Value::Null,
+        }
+    }
+
+    fn binary(&self, binary: &Binary) -> Value {
+        let lhs = self.evaluate(&binary.lhs);
+
+        // The right-hand side of the logical operators, and of the null coalescing operator, is only evaluated
+        // when the left-hand side does not determine the result.
+        match binary.operator {
+            BinaryOperator::And(_) | BinaryOperator::LowAnd(_) => {
+                return match lhs.to_bool() {
+                    Some(false) => Value::Bool(false),
+                    Some(true) => self.evaluate(&binary.rhs).to_bool().map_or(Value::Unknown, Value::Bool),
+                    None => Value::Unknown,
+                };
+            }
+            BinaryOperator::Or(_) | BinaryOperator::LowOr(_) => {
+                return match lhs.to_bool() {
+                    Some(true) => Value::Bool(true),
+                    Some(false) => self.evaluate(&binary.rhs).to_bool().map_or(Value::Unknown, Value::Bool),
+                    None => Value::Unknown,
+                };
+            }
+            BinaryOperator::Elvis(_) => {
+                return match lhs.to_bool() {
+                    Some(true) => lhs,
+                    Some(false) => self.evaluate(&binary.rhs),
+                    None => Value::Unknown,
+                };
+            }
+            BinaryOperator::NullCoalesce(_) => {
+                return match lhs {
+                    Value::Null => self.evaluate(&binary.rhs),
+                    value => value,
+                };
+            }
+            _ => {}
+        }
+
+        let rhs = self.evaluate(&binary.rhs);
+        if !lhs.is_known() || !rhs.is_known() {
+            return Value::Unknown;
+        }
+
+        operation(&binary.operator, &lhs, &rhs).unwrap_or(Value::Unknown)
+    }
+
+    fn unary(&self, unary: &UnaryPrefix) -> Value {
+        let operand = self.evaluate(&unary.operand);
+        if !operand.is_known() {
+            return Value::Unknown;
+        }
+
+        let value = match unary.operator {
+            UnaryPrefixOperator::Not(_) => operand.to_bool().map(|value| Value::Bool(!value)),
+            UnaryPrefixOperator::Negation(_) => {
+                operand.to_number().map(|number| multiply(number, Number::Integer(-1)).into_value())
+            }
+            UnaryPrefixOperator::Plus(_) => operand.to_number().map(Number::into_value),
+            UnaryPrefixOperator::BitwiseNot(_) => match operand {
+                Value::Integer(value) => Some(Value::Integer(!value)),
+                Value::Float(value) => float_to_integer(value).map(|value| Value::Integer(!value)),
+                Value::String(bytes) => Some(Value::String(bytes.iter().map(|byte| !byte).collect())),
+                _ => None,
+            },
+            UnaryPrefixOperator::BoolCast(..) | UnaryPrefixOperator::BooleanCast(..) => {
+                operand.to_bool().map(Value::Bool)
+            }
+            UnaryPrefixOperator::IntCast(..) | UnaryPrefixOperator::IntegerCast(..) => {
+                operand.cast_to_integer().map(Value::Integer)
+            }
+            UnaryPrefixOperator::FloatCast(..)
+            | UnaryPrefixOperator::DoubleCast(..)
+            | UnaryPrefixOperator::RealCast(..) => operand.cast_to_float().map(Value::Float),
+            UnaryPrefixOperator::StringCast(..) | UnaryPrefixOperator::BinaryCast(..) => {
+                operand.to_php_string().map(Value::String)
+            }
+            UnaryPrefixOperator::ArrayCast(..) => match operand {
+                Value::Null => Some(Value::Array(vec![])),
+                Value::Array(entries) => Some(Value::Array(entries)),
+                Value::EnumCase(..) => None,
+                value => Some(Value::Array(vec![(ArrayKey::Integer(0), value)])),
+            },
+            _ => None,
+        };
+
+        value.unwrap_or(Value::Unknown)
+    }
+
+    fn array(&self, elements: &[ArrayElement]) -> Value {
+        let mut entries: Vec<(ArrayKey, Value)> = Vec::with_capacity(elements.len());
+        let mut positions: HashMap<ArrayKey, usize> = HashMap::new();
+        let mut next = NextKey::new(self.version);
+
+        let mut insert = |key: ArrayKey, value: Value| match positions.get(&key) {
+            Some(position) => entries[*position].1 = value,
+            None => {
+                positions.insert(key.clone(), entries.len());
+                entries.push((key, value));
+            }
+        };
+
+        for element in elements {
+            let (key, value) = match element {
+                ArrayElement::KeyValue(element) => {
+                    let Some(key) = ArrayKey::from_value(&self.evaluate(&element.key)) else {
+                        return Value::Unknown;
+                    };
+
+                    next.observe(&key);
+
+                    (key, self.evaluate(&element.value))
+                }
+                ArrayElement::Value(element) => {
+                    let Some(key) = next.append() else {
+                        return Value::Unknown;
+                    };
+
+                    (key, self.evaluate(&element.value))
+                }
+                ArrayElement::Variadic(element) => {
+                    let Value::Array(spread) = self.evaluate(&element.value) else {
+                        return Value::Unknown;
+                    };
+
+                    // The integer keys of the spread array are renumbered, while its string keys are preserved,
+                    // which is supported as of PHP 8.1.
+                    for (key, value) in spread {
+                        let key = match key {
+                            ArrayKey::Integer(_) => match next.append() {
+                                Some(key) => key,
+                                None => return Value::Unknown,
+                            },
+                            ArrayKey::String(_) if !self.version.is_supported(PHPVersion::PHP81) => {
+                                return Value::Unknown;
+                            }
+                            key => key,
+                        };
+
+                        insert(key, value);
+                    }
+
+                    continue;
+                }
+                ArrayElement::Missing(_) => return Value::Unknown,
+            };
+
+            if !value.is_known() {
+                return Value::Unknown;
+            }
+
+            insert(key, value);
+        }
+
+        Value::Array(entries)
+    }
+
+    fn array_access(&self, access: &ArrayAccess) -> Value {
+        let index = self.evaluate(&access.index);
+
+        match self.evaluate(&access.array) {
+            Value::Array(entries) => {
+                let Some(key) = ArrayKey::from_value(&index) else {
+                    return Value::Unknown;
+                };
+
+                // Reading an undefined key results in `null`, with a warning.
+                entries.into_iter().find(|(existing, _)| *existing == key).map_or(Value::Null, |(_, value)| value)
+            }
+            Value::String(bytes) => {
+                let Value::Integer(offset) = index else {
+                    return Value::Unknown;
+                };
+
+                let offset = if offset < 0 { offset + bytes.len() as i64 } else { offset };
+
+                usize::try_from(offset)
+                    .ok()
+                    .and_then(|offset| bytes.get(offset))
+                    .map_or(Value::Unknown, |byte| Value::String(vec![*byte]))
+            }
+            _ => Value::Unknown,
+        }
+    }
+
+    fn constant(&self, identifier: &Identifier) -> Value {
+        let name = self.constant_name(identifier);
+        if let Some(value) = self.global_constant(&name) {
+            return value;
+        }
+
+        // An unqualified name, which is not imported, falls back to the global constant of the same name.
+        let name = match identifier {
+            Identifier::Local(local) if !self.names.is_imported(local) => self.interner.lookup(&local.value),
+            _ => &name,
+        };
+
+        self.global_constant(name).or_else(|| builtin_constant(name)).unwrap_or(Value::Unknown)
+    }
+
+    /// Returns the fully qualified name of a constant, resolving it within its namespace if it was not resolved
+    /// while resolving the names of the program.
+    fn constant_name(&self, identifier: &Identifier) -> String {
+        if let Some(name) = self.resolve(identifier) {
+            return name.to_owned();
+        }
+
+        let name = self.interner.lookup(&identifier.value());
+        if let Identifier::FullyQualified(_) = identifier {
+            return name.trim_start_matches('\\').to_owned();
+        }
+
+        let span = identifier.span();
+        match self.namespaces.iter().find(|(namespace, _)| namespace.contains(&span)) {
+            Some((_, namespace)) => format!("{}\\{}", namespace, name),
+            None => name.to_owned(),
+        }
+    }
+
+    fn global_constant(&self, name: &str) -> Option<Value> {
+        let key = constant_key(name);
+        let expression = self.constants.get(&key)?;
+
+        Some(self.memoized((None, key), expression))
+    }
+
+    fn class_constant(&self, access: &ClassConstantAccess) -> Value {
+        let ClassLikeConstantSelector::Identifier(constant) = &access.constant else {
+            return Value::Unknown;
+        };
+
+        let name = self.interner.lookup(&constant.value);
+        if name.eq_ignore_ascii_case("class") {
+            return self.class_name(&access.class).map_or(Value::Unknown, |name| Value::String(name.into_bytes()));
+        }
+
+        self.class_like_of(&access.class)
+            .and_then(|index| self.class_like_constant(index, name, &mut HashSet::new()))
+            .unwrap_or(Value::Unknown)
+    }
+
+    fn enum_case_property(&self, object: &Expression, property: &ClassLikeMemberSelector) -> Value {
+        let (Value::EnumCase(enum_name, case_name), ClassLikeMemberSelector::Identifier(property)) =
+            (self.evaluate(object), property)
+        else {
+            return Value::Unknown;
+        };
+
+        match self.interner.lookup(&property.value) {
+            "name" => Value::String(case_name.into_bytes()),
+            "value" => {
+                let Some(&index) = self.class_like_indices.get(&enum_name.to_ascii_lowercase()) else {
+                    return Value::Unknown;
+                };
+
+                match self.class_likes[index].cases.get(&case_name) {
+                    Some(Some(expression)) => self.memoized((Some(index), case_name), expression),
+                    _ => Value::Unknown,
+                }
+            }
+            _ => Value::Unknown,
+        }
+    }
+
+    /// Returns the value of the constant, or of the enum case, with the given name in the given class-like entity,
+    /// or in the ones it inherits from.
+    fn class_like_constant(&self, index: usize, name: &str, visited: &mut HashSet<usize>) -> Option<Value> {
+        if !visited.insert(index) {
+            return None;
+        }
+
+        let class_like = &self.class_likes[index];
+        if let Some(expression) = class_like.constants.get(name) {
+            return Some(self.memoized((Some(index), name.to_owned()), expression));
+        }
+
+        if class_like.cases.contains_key(name) {
+            return Some(Value::EnumCase(class_like.name.clone()?, name.to_owned()));
+        }
+
+        class_like.ancestors.iter().find_map(|ancestor| {
+            let &ancestor = self.class_like_indices.get(ancestor)?;
+
+            self.class_like_constant(ancestor, name, visited)
+        })
+    }
+
+    /// Evaluates the value of a constant once, as it may be referenced many times.
+    fn memoized(&self, key: ConstantKey, expression: &Expression) -> Value {
+        let mut values = self.values.lock().expect("the values are not poisoned");
+        if let Some(value) = values.get(&key) {
+            // A constant whose value references itself, directly or not, is not known.
+            return value.clone().unwrap_or(Value::Unknown);
+        }
+
+        values.insert(key.clone(), None);
+        drop(values);
+
+        let value = self.evaluate(expression);
+        self.values.lock().expect("the values are not poisoned").insert(key, Some(value.clone()));
+
+        value
+    }
+
+    /// Returns the index of the class-like entity referenced by the given expression, which is either a name,
+    /// `self`, or `parent`, if it is declared in the program.
+    ///
+    /// The class-like entity referenced by `static` is not known, as it may override the constants.
+    fn class_like_of(&self, class: &Expression) -> Option<usize> {
+        match class {
+            Expression::Identifier(identifier) => {
+                self.class_like_indices.get(&self.resolve(identifier)?.to_ascii_lowercase()).copied()
+            }
+            Expression::Self_(keyword) => self.scope(keyword.span),
+            Expression::Parent(keyword) => {
+                let parent = self.class_likes[self.scope(keyword.span)?].parent.as_ref()?;
+
+                self.class_like_indices.get(&parent.to_ascii_lowercase()).copied()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the class-like entity referenced by the given expression, as `::class` does.
+    fn class_name(&self, class: &Expression) -> Option<String> {
+        match class {
+            Expression::Identifier(identifier) => self.resolve(identifier).map(str::to_owned),
+            Expression::Self_(keyword) => self.class_likes[self.scope(keyword.span)?].name.clone(),
+            Expression::Parent(keyword) => self.class_likes[self.scope(keyword.span)?].parent.clone(),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the innermost class-like entity enclosing the given span, which `self` refers to.
+    ///
+    /// In a trait, `self` refers to the class using it, which is not known.
+    fn scope(&self, span: Span) -> Option<usize> {
+        let (index, class_like) = self
+            .class_likes
+            .iter()
+            .enumerate()
+            .filter(|(_, class_like)| {
+                class_like.span.start.offset <= span.start.offset && span.end.offset <= class_like.span.end.offset
+            })
+            .max_by_key(|(_, class_like)| class_like.span.start.offset)?;
+
+        if class_like.is_trait {
+            None
+        } else {
+            Some(index)
+        }
+    }
+
+    fn resolve(&self, position: &impl HasPosition) -> Option<&'a str> {
+        let position = position.position();
+        if !self.names.contains(&position) {
+            return None;
+        }
+
+        Some(self.interner.lookup(self.names.get(&position)))
+    }
+
+    /// Collects the constants defined with `define()` at the top level of the program, as the ones defined
+    /// conditionally may not be.
+    fn collect_defined_constants(&mut self, statements: impl Iterator<Item = &'a Statement>) {
+        for statement in statements {
+            let expression = match statement {
+                Statement::Namespace(namespace) => {
+                    self.collect_defined_constants(namespace.statements().iter());
+
+                    continue;
+                }
+                Statement::Expression(statement) => &statement.expression,
+                _ => continue,
+            };
+
+            let Expression::Call(Call::Function(FunctionCall { function, arguments })) = expression else {
+                continue;
+            };
+
+            let Expression::Identifier(function) = function.as_ref() else {
+                continue;
+            };
+
+            let function_name = self.interner.lookup(&function.value());
+            if !function_name.trim_start_matches('\\').eq_ignore_ascii_case("define") {
+                continue;
+            }
+
+            let [Argument::Positional(name), Argument::Positional(value)] = arguments.arguments.as_slice() else {
+                continue;
+            };
+
+            let (None, Expression::Literal(Literal::String(_))) = (name.ellipsis, &name.value) else {
+                continue;
+            };
+
+            if let Value::String(name) = self.evaluate(&name.value) {
+                self.constants.entry(constant_key(&String::from_utf8_lossy(&name))).or_insert(&value.value);
+            }
+        }
+    }
+
+    fn collect_class_like<'b>(
+        &mut self,
+        span: Span,
+        name: Option<&LocalIdentifier>,
+        parent: Option<&Identifier>,
+        ancestors: impl Iterator<Item = &'b Identifier>,
+        members: &'a Sequence<ClassLikeMember>,
+        is_trait: bool,
+    ) {
+        let name = match name {
+            Some(name) => match self.resolve(name) {
+                Some(name) => Some(name.to_owned()),
+                None => return,
+            },
+            None => None,
+        };
+
+        let mut ancestors: Vec<String> =
+            ancestors.filter_map(|ancestor| self.resolve(ancestor)).map(str::to_ascii_lowercase).collect();
+
+        let mut constants = HashMap::new();
+        let mut cases = HashMap::new();
+        for member in members.iter() {
+            match member {
+                ClassLikeMember::Constant(constant) => {
+                    for item in constant.items.iter() {
+                        constants.entry(self.interner.lookup(&item.name.value).to_owned()).or_insert(&item.value);
+                    }
+                }
+                ClassLikeMember::EnumCase(case) => {
+                    let value = match &case.item {
+                        EnumCaseItem::Unit(_) => None,
+                        EnumCaseItem::Backed(item) => Some(&item.value),
+                    };
+
+                    cases.entry(self.interner.lookup(&case.item.name().value).to_owned()).or_insert(value);
+                }
+                ClassLikeMember::TraitUse(trait_use) => {
+                    ancestors.extend(
+                        trait_use.trait_names.iter().filter_map(|name| self.resolve(name)).map(str::to_ascii_lowercase),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(name) = &name {
+            self.class_like_indices.entry(name.to_ascii_lowercase()).or_insert(self.class_likes.len());
+        }
+
+        let parent = parent.and_then(|parent| self.resolve(parent)).map(str::to_owned);
+
+        self.class_likes.push(ClassLike { span, name, parent, ancestors, constants, cases, is_trait });
+    }
+}
+
+impl Value {
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Value::Unknown)
+    }
+
+    /// Converts the value to a boolean, as `(bool)` does.
+    pub fn to_bool(&self) -> Option<bool> {
+        Some(match self {
+            Value::Null => false,
+            Value::Bool(value) => *value,
+            Value::Integer(value) => *value != 0,
+            Value::Float(value) => *value != 0.0,
+            Value::String(bytes) => !(bytes.is_empty() || bytes == b"0"),
+            Value::Array(entries) => !entries.is_empty(),
+            Value::EnumCase(..) => true,
+            Value::Unknown => return None,
+        })
+    }
+
+    /// Converts the value to a string, as `(string)` does, or returns `None` if it cannot be converted.
+    ///
+    /// The floats are converted with the default precision of 14 significant digits.
+    pub fn to_php_string(&self) -> Option<Vec<u8>> {
+        Some(match self {
+            Value::Null | Value::Bool(false) => vec![],
+            Value::Bool(true) => b"1".to_vec(),
+            Value::Integer(value) => value.to_string().into_bytes(),
+            Value::Float(value) => format_float(*value).into_bytes(),
+            Value::String(bytes) => bytes.clone(),
+            Value::Array(_) | Value::EnumCase(..) | Value::Unknown => return None,
+        })
+    }
+
+    /// Determines whether the values are identical, as `===` does, or returns `None` if either is unknown.
+    ///
+    /// As the floats are compared by value, `NAN` is not identical to itself, while `0.0` and `-0.0` are.
+    pub fn is_identical(&self, other: &Value) -> Option<bool> {
+        Some(match (self, other) {
+            (Value::Unknown, _) | (_, Value::Unknown) => return None,
+            (Value::Null, Value::Null) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Integer(a), Value::Integer(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Array(a), Value::Array(b)) => {
+                if a.len() != b.len() {
+                    return Some(false);
+                }
+
+                for ((a_key, a_value), (b_key, b_value)) in a.iter().zip(b) {
+                    if a_key != b_key || !a_value.is_identical(b_value)? {
+                        return Some(false);
+                    }
+                }
+
+                true
+            }
+            (Value::EnumCase(a_enum, a_case), Value::EnumCase(b_enum, b_case)) => {
+                a_enum.eq_ignore_ascii_case(b_enum) && a_case == b_case
+            }
+            _ => false,
+        })
+    }
+
+    /// Determines whether the values are equal, as `==` does since PHP 8.0, or returns `None` if it is not known.
+    ///
+    /// A number and a numeric string are compared as numbers, while a number and a non-numeric string are
+    /// compared as strings.
+    pub fn is_equal(&self, other: &Value) -> Option<bool> {
+        Some(match (self, other) {
+            (Value::Unknown, _) | (_, Value::Unknown) => return None,
+            (Value::Null, Value::Null) => true,
+            (Value::Bool(value), other) | (other, Value::Bool(value)) => other.to_bool()? == *value,
+            (Value::Null, Value::String(bytes)) | (Value::String(bytes), Value::Null) => bytes.is_empty(),
+            (Value::Null, other) | (other, Value::Null) => !other.to_bool()?,
+            (Value::String(a), Value::String(b)) => match (parse_numeric(a), parse_numeric(b)) {
+                (Some((a, true)), Some((b, true))) => numbers_are_equal(a, b),
+                _ => a == b,
+            },
+            (Value::String(bytes), number @ (Value::Integer(_) | Value::Float(_)))
+            | (number @ (Value::Integer(_) | Value::Float(_)), Value::String(bytes)) => match parse_numeric(bytes) {
+                Some((parsed, true)) => numbers_are_equal(parsed, number.to_number()?),
+                _ => number.to_php_string()? == *bytes,
+            },
+            (Value::Integer(_) | Value::Float(_), Value::Integer(_) | Value::Float(_)) => {
+                numbers_are_equal(self.to_number()?, other.to_number()?)
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                if a.len() != b.len() {
+                    return Some(false);
+                }
+
+                for (key, a_value) in a {
+                    let Some((_, b_value)) = b.iter().find(|(other, _)| other == key) else {
+                        return Some(false);
+                    };
+
+                    if !a_value.is_equal(b_value)? {
+                        return Some(false);
+                    }
+                }
+
+                true
+            }
+            (Value::Array(_), _) | (_, Value::Array(_)) => false,
+            (Value::EnumCase(..), Value::EnumCase(..)) => self.is_identical(other)?,
+            _ => return None,
+        })
+    }
+
+    /// Describes the value as PHP code, for the messages of the issues.
+    pub fn describe(&self) -> String {
+        match self {
+            Value::Null => "null".to_string(),
+            Value::Bool(value) => value.to_string(),
+            Value::Integer(value) => value.to_string(),
+            Value::Float(value) => {
+                let formatted = format_float(*value);
+                if formatted.bytes().all(|byte| byte.is_ascii_digit() || byte == b'-') {
+                    format!("{}.0", formatted)
+                } else {
+                    formatted
+                }
+            }
+            Value::String(bytes) => describe_string(bytes),
+            Value::Array(_) => "array".to_string(),
+            Value::EnumCase(enum_name, case_name) => format!("{}::{}", enum_name, case_name),
+            Value::Unknown => "unknown".to_string(),
+        }
+    }
+
+    /// Converts the value to a number, as the operands of the arithmetic operators are, or returns `None` if
+    /// it cannot be converted, such as an array, or a non-numeric string.
+    ///
+    /// A string starting with a number, such as `'5 apples'`, is converted to it, with a warning.
+    fn to_number(&self) -> Option<Number> {
+        match self {
+            Value::Null => Some(Number::Integer(0)),
+            Value::Bool(value) => Some(Number::Integer(*value as i64)),
+            Value::Integer(value) => Some(Number::Integer(*value)),
+            Value::Float(value) => Some(Number::Float(*value)),
+            Value::String(bytes) => parse_numeric(bytes).map(|(number, _)| number),
+            Value::Array(_) | Value::EnumCase(..) | Value::Unknown => None,
+        }
+    }
+
+    /// Converts the value to an integer, as the operands of the modulo, bitwise, and shift operators are.
+    fn to_integer(&self) -> Option<i64> {
+        match self.to_number()? {
+            Number::Integer(value) => Some(value),
+            Number::Float(value) => float_to_integer(value),
+        }
+    }
+
+    /// Converts the value to an integer, as `(int)` does, which, unlike the operators, accepts any string.
+    fn cast_to_integer(&self) -> Option<i64> {
+        match self {
+            // Infinity and `NAN` are converted to zero.
+            Value::Float(value) if !value.is_finite() => Some(0),
+            Value::String(bytes) => match parse_numeric(bytes) {
+                Some((Number::Integer(value), _)) => Some(value),
+                Some((Number::Float(value), _)) => float_to_integer(value),
+                None => Some(0),
+            },
+            Value::Array(entries) => Some(!entries.is_empty() as i64),
+            _ => self.to_integer(),
+        }
+    }
+
+    /// Converts the value to a float, as `(float)` does.
+    fn cast_to_float(&self) -> Option<f64> {
+        match self {
+            Value::String(bytes) => Some(parse_numeric(bytes).map_or(0.0, |(number, _)| number.as_float())),
+            Value::Array(entries) => Some(if entries.is_empty() { 0.0 } else { 1.0 }),
+            _ => self.to_number().map(Number::as_float),
+        }
+    }
+}
+
+impl ArrayKey {
+    /// Converts the value to a key, as PHP does when it is used as one, or returns `None` if it cannot be.
+    ///
+    /// The decimal integer strings, such as `'8'`, but not `'08'`, are converted to integers, the booleans to
+    /// `0` and `1`, the floats are truncated, and `null` is converted to an empty string.
+    pub fn from_value(value: &Value) -> Option<ArrayKey> {
+        match value {
+            Value::Null => Some(ArrayKey::String(vec![])),
+            Value::Bool(value) => Some(ArrayKey::Integer(*value as i64)),
+            Value::Integer(value) => Some(ArrayKey::Integer(*value)),
+            Value::Float(value) => float_to_integer(*value).map(ArrayKey::Integer),
+            Value::String(bytes) => Some(match parse_decimal_integer(bytes) {
+                Some(value) => ArrayKey::Integer(value),
+                None => ArrayKey::String(bytes.clone()),
+            }),
+            Value::Array(_) | Value::EnumCase(..) | Value::Unknown => None,
+        }
+    }
+
+    /// Describes the key as PHP code, for the messages of the issues.
+    pub fn describe(&self) -> String {
+        match self {
+            ArrayKey::Integer(value) => value.to_string(),
+            ArrayKey::String(bytes) => describe_string(bytes),
+        }
+    }
+}
+
+impl Number {
+    fn as_float(self) -> f64 {
+        match self {
+            Number::Integer(value) => value as f64,
+            Number::Float(value) => value,
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Number::Integer(value) => Value::Integer(value),
+            Number::Float(value) => Value::Float(value),
+        }
+    }
+}
+
+impl NextKey {
+    fn new(version: PHPVersion) -> Self {
+        Self { next: None, follows_negative_keys: version.is_supported(PHPVersion::PHP83) }
+    }
+
+    fn observe(&mut self, key: &ArrayKey) {
+        if let ArrayKey::Integer(key) = key {
+            let following = *key as i128 + 1;
+            let following = if self.follows_negative_keys { following } else { following.max(0) };
+
+            self.next = Some(self.next.map_or(following, |next| next.max(following)));
+        }
+    }
+
+    /// Returns the key of an entry appended to the array, or `None` if the largest key is already used, which
+    /// is an error.
+    fn append(&mut self) -> Option<ArrayKey> {
+        let key = i64::try_from(self.next.unwrap_or(0)).ok()?;
+        self.next = Some(key as i128 + 1);
+
+        Some(ArrayKey::Integer(key))
+    }
+}
+
+/// Applies a binary operator, other than the logical and null coalescing ones, to the known operands, or returns
+/// `None` if the operation fails at runtime, or is not supported.
+fn operation(operator: &BinaryOperator, lhs: &Value, rhs: &Value) -> Option<Value> {
+    match operator {
+        BinaryOperator::Addition(_) => match (lhs, rhs) {
+            (Value::Array(lhs), Value::Array(rhs)) => {
+                let mut entries = lhs.clone();
+                for (key, value) in rhs {
+                    if !lhs.iter().any(|(existing, _)| existing == key) {
+                        entries.push((key.clone(), value.clone()));
+                    }
+                }
+
+                Some(Value::Array(entries))
+            }
+            _ => Some(add(lhs.to_number()?, rhs.to_number()?).into_value()),
+        },
+        BinaryOperator::Subtraction(_) => Some(subtract(lhs.to_number()?, rhs.to_number()?).into_value()),
+        BinaryOperator::Multiplication(_) => Some(multiply(lhs.to_number()?, rhs.to_number()?).into_value()),
+        BinaryOperator::Division(_) => divide(lhs.to_number()?, rhs.to_number()?).map(Number::into_value),
+        BinaryOperator::Modulo(_) => modulo(lhs.to_integer()?, rhs.to_integer()?).map(Value::Integer),
+        BinaryOperator::Exponentiation(_) => Some(power(lhs.to_number()?, rhs.to_number()?).into_value()),
+        BinaryOperator::BitwiseAnd(_) => bitwise(lhs, rhs, |a, b| a & b, |a, b| a & b, false),
+        BinaryOperator::BitwiseOr(_) => bitwise(lhs, rhs, |a, b| a | b, |a, b| a | b, true),
+        BinaryOperator::BitwiseXor(_) => bitwise(lhs, rhs, |a, b| a ^ b, |a, b| a ^ b, false),
+        BinaryOperator::LeftShift(_) => shift_left(lhs.to_integer()?, rhs.to_integer()?).map(Value::Integer),
+        BinaryOperator::RightShift(_) => shift_right(lhs.to_integer()?, rhs.to_integer()?).map(Value::Integer),
+        BinaryOperator::StringConcat(_) => Some(Value::String([lhs.to_php_string()?, rhs.to_php_string()?].concat())),
+        BinaryOperator::Equal(_) => lhs.is_equal(rhs).map(Value::Bool),
+        BinaryOperator::NotEqual(_) | BinaryOperator::AngledNotEqual(_) => {
+            lhs.is_equal(rhs).map(|equal| Value::Bool(!equal))
+        }
+        BinaryOperator::Identical(_) => lhs.is_identical(rhs).map(Value::Bool),
+        BinaryOperator::NotIdentical(_) => lhs.is_identical(rhs).map(|identical| Value::Bool(!identical)),
+        BinaryOperator::LowXor(_) => Some(Value::Bool(lhs.to_bool()? ^ rhs.to_bool()?)),
+        _ => None,
+    }
+}
+
+/// Adds the numbers, an integer overflow resulting in a float, as in PHP.
+fn add(a: Number, b: Number) -> Number {
+    match (a, b) {
+        (Number::Integer(a), Number::Integer(b)) => {
+            a.checked_add(b).map_or(Number::Float(a as f64 + b as f64), Number::Integer)
+        }
+        _ => Number::Float(a.as_float() + b.as_float()),
+    }
+}
+
+fn subtract(a: Number, b: Number) -> Number {
+    match (a, b) {
+        (Number::Integer(a), Number::Integer(b)) => {
+            a.checked_sub(b).map_or(Number::Float(a as f64 - b as f64), Number::Integer)
+        }
+        _ => Number::Float(a.as_float() - b.as_float()),
+    }
+}
+
+fn multiply(a: Number, b: Number) -> Number {
+    match (a, b) {
+        (Number::Integer(a), Number::Integer(b)) => {
+            a.checked_mul(b).map_or(Number::Float(a as f64 * b as f64), Number::Integer)
+        }
+        _ => Number::Float(a.as_float() * b.as_float()),
+    }
+}
+
+/// Divides the numbers, resulting in an integer only if both are integers, and the division is exact, or
+/// returns `None` for a division by zero, which throws a `DivisionByZeroError`.
+fn divide(a: Number, b: Number) -> Option<Number> {
+    if b.as_float() == 0.0 {
+        return None;
+    }
+
+    Some(match (a, b) {
+        (Number::Integer(a), Number::Integer(b)) if a.checked_rem(b) == Some(0) => {
+            a.checked_div(b).map_or(Number::Float(a as f64 / b as f64), Number::Integer)
+        }
+        _ => Number::Float(a.as_float() / b.as_float()),
+    })
+}
+
+/// Computes the remainder of the integers, or returns `None` for a modulo by zero, which throws a
+/// `DivisionByZeroError`.
+fn modulo(a: i64, b: i64) -> Option<i64> {
+    if b == 0 {
+        return None;
+    }
+
+    // `PHP_INT_MIN % -1` is `0`.
+    Some(a.wrapping_rem(b))
+}
+
+fn power(base: Number, exponent: Number) -> Number {
+    if let (Number::Integer(base), Number::Integer(exponent)) = (base, exponent) {
+        if let Some(value) = u32::try_from(exponent).ok().and_then(|exponent| base.checked_pow(exponent)) {
+            return Number::Integer(value);
+        }
+    }
+
+    Number::Float(base.as_float().powf(exponent.as_float()))
+}
+
+/// Applies a bitwise operator, to the bytes of the operands if both are strings, or to their integer values.
+///
+/// For strings, the result is as long as the shortest operand, or the longest one with `|`.
+fn bitwise(
+    lhs: &Value,
+    rhs: &Value,
+    integers: impl Fn(i64, i64) -> i64,
+    bytes: impl Fn(u8, u8) -> u8,
+    longest: bool,
+) -> Option<Value> {
+    if let (Value::String(a), Value::String(b)) = (lhs, rhs) {
+        let length = if longest { a.len().max(b.len()) } else { a.len().min(b.len()) };
+        let result = (0..length)
+            .map(|index| bytes(a.get(index).copied().unwrap_or(0), b.get(index).copied().unwrap_or(0)))
+            .collect();
+
+        return Some(Value::String(result));
+    }
+
+    Some(Value::Integer(integers(lhs.to_integer()?, rhs.to_integer()?)))
+}
+
+/// Shifts the integer to the left, or returns `None` for a negative shift, which throws an `ArithmeticError`.
+fn shift_left(value: i64, shift: i64) -> Option<i64> {
+    match shift {
+        ..0 => None,
+        0..64 => Some(value.wrapping_shl(shift as u32)),
+        _ => Some(0),
+    }
+}
+
+/// Shifts the integer to the right, preserving its sign.
+fn shift_right(value: i64, shift: i64) -> Option<i64> {
+    match shift {
+        ..0 => None,
+        0..64 => Some(value >> shift),
+        _ => Some(if value < 0 { -1 } else { 0 }),
+    }
+}
+
+fn numbers_are_equal(a: Number, b: Number) -> bool {
+    match (a, b) {
+        (Number::Integer(a), Number::Integer(b)) => a == b,
+        _ => a.as_float() == b.as_float(),
+    }
+}
+
+/// Converts the float to an integer by truncation, or returns `None` if it is not finite, or out of range.
+fn float_to_integer(value: f64) -> Option<i64> {
+    // The bounds are -2^63, and 2^63, which are represented exactly.
+    if value.is_finite() && (i64::MIN as f64..-(i64::MIN as f64)).contains(&value) {
+        Some(value as i64)
+    } else {
+        None
+    }
+}
+
+/// Parses a numeric string, with optional surrounding whitespace, returning its number, and whether the whole
+/// string is numeric, rather than only its leading part, or `None` if it does not start with a number.
+///
+/// The integers which do not fit in a 64-bit signed integer are parsed as floats.
+fn parse_numeric(bytes: &[u8]) -> Option<(Number, bool)> {
+    let count_digits =
+        |from: usize| bytes.get(from..).map_or(0, |rest| rest.iter().take_while(|byte| byte.is_ascii_digit()).count());
+
+    let start = bytes.iter().take_while(|byte| WHITESPACE.contains(byte)).count();
+    let mut end = start;
+    if matches!(bytes.get(end), Some(b'+' | b'-')) {
+        end += 1;
+    }
+
+    let mut digits = count_digits(end);
+    end += digits;
+
+    let mut is_float = false;
+    if bytes.get(end) == Some(&b'.') {
+        let fraction = count_digits(end + 1);
+        if digits + fraction > 0 {
+            end += 1 + fraction;
+            digits += fraction;
+            is_float = true;
+        }
+    }
+
+    if digits == 0 {
+        return None;
+    }
+
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let mut exponent = end + 1;
+        if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+            exponent += 1;
+        }
+
+        let exponent_digits = count_digits(exponent);
+        if exponent_digits > 0 {
+            end = exponent + exponent_digits;
+            is_float = true;
+        }
+    }
+
+    let text = std::str::from_utf8(&bytes[start..end]).ok()?;
+    let number = match text.parse::<i64>() {
+        Ok(value) if !is_float => Number::Integer(value),
+        _ => Number::Float(text.parse().ok()?),
+    };
+
+    Some((number, bytes[end..].iter().all(|byte| WHITESPACE.contains(byte))))
+}
+
+/// Parses a float literal, which may contain underscores, or be written in hexadecimal, octal, or binary, as
+/// the integer literals which do not fit in a 64-bit signed integer.
+fn parse_float(raw: &str) -> Option<f64> {
+    let raw = raw.replace('_', "");
+    let lowered = raw.to_ascii_lowercase();
+
+    let (digits, radix) = if let Some(digits) = lowered.strip_prefix("0x") {
+        (digits, 16)
+    } else if let Some(digits) = lowered.strip_prefix("0b") {
+        (digits, 2)
+    } else if let Some(digits) = lowered.strip_prefix("0o") {
+        (digits, 8)
+    } else if lowered.len() > 1 && lowered.starts_with('0') && lowered.bytes().all(|byte| byte.is_ascii_digit()) {
+        (&lowered[1..], 8)
+    } else {
+        return lowered.parse().ok();
+    };
+
+    digits.chars().try_fold(0.0, |value: f64, digit| Some(value * radix as f64 + digit.to_digit(radix)? as f64))
+}
+
+/// Parses a decimal integer string without leading zeros, or a sign other than `-`, as the array keys.
+fn parse_decimal_integer(bytes: &[u8]) -> Option<i64> {
+    let digits = bytes.strip_prefix(b"-").unwrap_or(bytes);
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) || (digits[0] == b'0' && bytes.len() > 1) {
+        return None;
+    }
+
+    std::str::from_utf8(bytes).ok()?.parse().ok()
+}
+
+/// Formats the float as PHP converts it to a string, with the given number of significant digits, using the
+/// scientific notation for the exponents below -4, or above the precision.
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        return "NAN".to_string();
+    }
+
+    if value.is_infinite() {
+        return if value > 0.0 { "INF" } else { "-INF" }.to_string();
+    }
+
+    let sign = if value.is_sign_negative() { "-" } else { "" };
+    if value == 0.0 {
+        return format!("{}0", sign);
+    }
+
+    let scientific = format!("{:.*e}", FLOAT_PRECISION - 1, value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').expect("the float is formatted in scientific notation");
+    let exponent: i32 = exponent.parse().expect("the exponent is an integer");
+    let digits = mantissa.replace('.', "");
+    let digits = digits.trim_end_matches('0');
+
+    // The position of the decimal point, relative to the first significant digit.
+    let point = exponent + 1;
+    if point < -3 || point > FLOAT_PRECISION as i32 {
+        let (first, rest) = digits.split_at(1);
+
+        format!(
+            "{}{}.{}E{}{}",
+            sign,
+            first,
+            if rest.is_empty() { "0" } else { rest },
+            if exponent < 0 { '-' } else { '+' },
+            exponent.abs()
+        )
+    } else if point <= 0 {
+        format!("{}0.{}{}", sign, "0".repeat(-point as usize), digits)
+    } else if digits.len() <= point as usize {
+        format!("{}{}{}", sign, digits, "0".repeat(point as usize - digits.len()))
+    } else {
+        let (integer, fraction) = digits.split_at(point as usize);
+
+        format!("{}{}.{}", sign, integer, fraction)
+    }
+}
+
+fn describe_string(bytes: &[u8]) -> String {
+    format!("'{}'", String::from_utf8_lossy(bytes).replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Returns the value of the built-in constants which do not depend on the platform, or the configuration.
+fn builtin_constant(name: &str) -> Option<Value> {
+    Some(match name {
+        "PHP_INT_MAX" => Value::Integer(i64::MAX),
+        "PHP_INT_MIN" => Value::Integer(i64::MIN),
+        "PHP_INT_SIZE" => Value::Integer(8),
+        "PHP_FLOAT_EPSILON" => Value::Float(f64::EPSILON),
+        "PHP_FLOAT_MAX" => Value::Float(f64::MAX),
+        "PHP_FLOAT_MIN" => Value::Float(f64::MIN_POSITIVE),
+        "PHP_FLOAT_DIG" => Value::Integer(15),
+        "NAN" => Value::Float(f64::NAN),
+        "INF" => Value::Float(f64::INFINITY),
+        "M_PI" => Value::Float(std::f64::consts::PI),
+        "M_E" => Value::Float(std::f64::consts::E),
+        _ => return None,
+    })
+}
+
+/// Returns the key of a global constant, whose namespace is case-insensitive, unlike its name.
+fn constant_key(name: &str) -> String {
+    let name = name.trim_start_matches('\\');
+
+    match name.rsplit_once('\\') {
+        Some((namespace, name)) => format!("{}\\{}", namespace.to_ascii_lowercase(), name),
+        None => name.to_owned(),
+    }
+}
+
+/// Unescapes the content of a single-quoted string, in which only `\\` and `\'` are escape sequences.
+fn unescape_single_quoted(content: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(content.len());
+    let mut index = 0;
+    while index < content.len() {
+        match (content[index], content.get(index + 1)) {
+            (b'\\', Some(escaped @ (b'\\' | b'\''))) => {
+                bytes.push(*escaped);
+                index += 2;
+            }
+            (byte, _) => {
+                bytes.push(byte);
+                index += 1;
+            }
+        }
+    }
+
+    bytes
+}
+
+/// Unescapes the content of a double-quoted string without interpolation, or returns `None` if it contains an
+/// invalid Unicode escape sequence, which is a compile error.
+fn unescape_double_quoted(content: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(content.len());
+    let mut index = 0;
+    while index < content.len() {
+        let (b'\\', Some(&escaped)) = (content[index], content.get(index + 1)) else {
+            bytes.push(content[index]);
+            index += 1;
+
+            continue;
+        };
+
+        index += 2;
+        match escaped {
+            b'n' => bytes.push(b'\n'),
+            b't' => bytes.push(b'\t'),
+            b'r' => bytes.push(b'\r'),
+            b'v' => bytes.push(0x0B),
+            b'e' => bytes.push(0x1B),
+            b'f' => bytes.push(0x0C),
+            b'\\' | b'$' | b'"' => bytes.push(escaped),
+            b'0'..=b'7' => {
+                let length = content[index..].iter().take(2).take_while(|byte| matches!(byte, b'0'..=b'7')).count();
+                let value = content[index - 1..index + length]
+                    .iter()
+                    .fold(0u32, |value, digit| value * 8 + (digit - b'0') as u32);
+
+                // The octal escape sequences above `\377` overflow.
+                bytes.push(value as u8);
+                index += length;
+            }
+            b'x' if content.get(index).is_some_and(u8::is_ascii_hexdigit) => {
+                let length = content[index..].iter().take(2).take_while(|byte| byte.is_ascii_hexdigit()).count();
+                let digits = std::str::from_utf8(&content[index..index + length]).ok()?;
+
+                bytes.push(u8::from_str_radix(digits, 16).ok()?);
+                index += length;
+            }
+            b'u' if content.get(index) == Some(&b'{') => {
+                let length = content[index..].iter().position(|byte| *byte == b'}')?;
+                let digits = std::str::from_utf8(&content[index + 1..index + length]).ok()?;
+                let character = char::from_u32(u32::from_str_radix(digits, 16).ok()?)?;
+
+                bytes.extend_from_slice(character.encode_utf8(&mut [0; 4]).as_bytes());
+                index += length + 1;
+            }
+            _ => bytes.extend_from_slice(&[b'\\', escaped]),
+        }
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_source::SourceManager;
+
+    use super::*;
+
+    /// Evaluates the expression returned by the last statement of the given code.
+    fn evaluate(code: &str) -> Value {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source_id = manager.insert_content("code.php".to_string(), format!("<?php\n\n{}\n", code), true);
+        let source = manager.load(&source_id).expect("the source can be loaded");
+        let (program, errors) = mago_parser::parse_source(&interner, &source);
+        assert!(errors.is_empty(), "the code cannot be parsed: {:?}", errors);
+
+        let names = Names::resolve(&interner, &program);
+        let evaluator = Evaluator::new(&interner, PHPVersion::LATEST, &program, &names);
+        let statements = match program.statements.last() {
+            Some(Statement::Namespace(namespace)) => namespace.statements().as_slice(),
+            _ => program.statements.as_slice(),
+        };
+
+        let Some(Statement::Return(Return { value: Some(value), .. })) = statements.last() else {
+            panic!("the code does not end with a return statement");
+        };
+
+        evaluator.evaluate(value)
+    }
+
+    fn string(value: &str) -> Value {
+        Value::String(value.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_integer_overflow() {
+        // An integer overflow results in a float, rather than wrapping around.
+        assert_eq!(evaluate("return PHP_INT_MAX + 1;"), Value::Float(9.223372036854776e18));
+        assert_eq!(evaluate("return PHP_INT_MIN - 1;"), Value::Float(-9.223372036854776e18));
+        assert_eq!(evaluate("return PHP_INT_MAX * 2;"), Value::Float(1.8446744073709552e19));
+        assert_eq!(evaluate("return -PHP_INT_MIN;"), Value::Float(9.223372036854776e18));
+        assert_eq!(evaluate("return PHP_INT_MIN / -1;"), Value::Float(9.223372036854776e18));
+        assert_eq!(evaluate("return 2 ** 63;"), Value::Float(9.223372036854776e18));
+        assert_eq!(evaluate("return 2 ** 62;"), Value::Integer(4611686018427387904));
+        assert_eq!(evaluate("return 9223372036854775808;"), Value::Float(9.223372036854776e18));
+        assert_eq!(evaluate("return 0x7FFFFFFFFFFFFFFF;"), Value::Integer(i64::MAX));
+
+        // The remainder, and the shifts, never overflow.
+        assert_eq!(evaluate("return PHP_INT_MIN % -1;"), Value::Integer(0));
+        assert_eq!(evaluate("return 1 << 63;"), Value::Integer(i64::MIN));
+        assert_eq!(evaluate("return 1 << 64;"), Value::Integer(0));
+        assert_eq!(evaluate("return -8 >> 65;"), Value::Integer(-1));
+
+        // The operations throwing an error are not known.
+        assert_eq!(evaluate("return 1 << -1;"), Value::Unknown);
+        assert_eq!(evaluate("return 1 % 0;"), Value::Unknown);
+        assert_eq!(evaluate("return 1 / 0;"), Value::Unknown);
+        assert_eq!(evaluate("return 1 + [];"), Value::Unknown);
+        assert_eq!(evaluate("return 'abc' * 2;"), Value::Unknown);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(evaluate("return 6 / 2;"), Value::Integer(3));
+        assert_eq!(evaluate("return 7 / 2;"), Value::Float(3.5));
+        assert_eq!(evaluate("return 2 ** -1;"), Value::Float(0.5));
+        assert_eq!(evaluate("return -7 % 3;"), Value::Integer(-1));
+        assert_eq!(evaluate("return 7.9 % 3;"), Value::Integer(1));
+        assert_eq!(evaluate("return '5' + '1.5';"), Value::Float(6.5));
+        assert_eq!(evaluate("return ' 5 ' + null + true;"), Value::Integer(6));
+        assert_eq!(evaluate("return '5 apples' + 1;"), Value::Integer(6));
+        assert_eq!(evaluate("return ~5 | 0b11;"), Value::Integer(-5));
+        assert_eq!(evaluate("return 'ab' ^ '  ';"), string("AB"));
+        assert_eq!(evaluate("return (int) '12abc' + (int) 'abc' + (int) NAN;"), Value::Integer(12));
+    }
+
+    #[test]
+    fn test_float_equality() {
+        // Floats are compared by value, so `NAN` is not equal to itself, but `0.0` is identical to `-0.0`.
+        assert_eq!(evaluate("return NAN === NAN;"), Value::Bool(false));
+        assert_eq!(evaluate("return NAN == NAN;"), Value::Bool(false));
+        assert_eq!(evaluate("return 0.0 === -0.0;"), Value::Bool(true));
+        assert_eq!(evaluate("return 0.1 + 0.2 == 0.3;"), Value::Bool(false));
+        assert_eq!(evaluate("return 1 == 1.0;"), Value::Bool(true));
+        assert_eq!(evaluate("return 1 === 1.0;"), Value::Bool(false));
+
+        // Floats are converted to strings with 14 significant digits.
+        assert_eq!(evaluate("return (0.1 + 0.2) . '';"), string("0.3"));
+        assert_eq!(evaluate("return 1.0 . '|' . -0.0 . '|' . 1.5e-7 . '|' . 0.0001;"), string("1|-0|1.5E-7|0.0001"));
+        assert_eq!(evaluate("return 1e13 . '|' . 1e14 . '|' . -1e100;"), string("10000000000000|1.0E+14|-1.0E+100"));
+        assert_eq!(evaluate("return 123456789.12345678 . '|' . INF . '|' . NAN;"), string("123456789.12346|INF|NAN"));
+    }
+
+    #[test]
+    fn test_loose_equality() {
+        assert_eq!(evaluate("return '1e3' == '1000';"), Value::Bool(true));
+        assert_eq!(evaluate("return 'abc' == 0;"), Value::Bool(false));
+        assert_eq!(evaluate("return '1' == '01';"), Value::Bool(true));
+        assert_eq!(evaluate("return 'abc' == 'ABC';"), Value::Bool(false));
+        assert_eq!(evaluate("return null == false;"), Value::Bool(true));
+        assert_eq!(evaluate("return '0' == false;"), Value::Bool(true));
+        assert_eq!(evaluate("return '0' == null;"), Value::Bool(false));
+        assert_eq!(evaluate("return [1, 2] == [1 => 2, 0 => 1];"), Value::Bool(true));
+        assert_eq!(evaluate("return [1, 2] === [1 => 2, 0 => 1];"), Value::Bool(false));
+    }
+
+    #[test]
+    fn test_arrays() {
+        // The keys are cast to integers, or strings, the later values overwriting the earlier ones.
+        assert_eq!(
+            evaluate("return ['1' => 'a', 1 => 'b', true => 'c', 1.7 => 'd', null => 'e', '01' => 'f', '-0' => 'g'];"),
+            Value::Array(vec![
+                (ArrayKey::Integer(1), string("d")),
+                (ArrayKey::String(vec![]), string("e")),
+                (ArrayKey::String(b"01".to_vec()), string("f")),
+                (ArrayKey::String(b"-0".to_vec()), string("g")),
+            ])
+        );
+
+        // As of PHP 8.3, the next key follows the largest key, even when it is negative.
+        assert_eq!(
+            evaluate("return [-5 => 'a', 'b', ...[7 => 'c'], ...['d' => 'e']];"),
+            Value::Array(vec![
+                (ArrayKey::Integer(-5), string("a")),
+                (ArrayKey::Integer(-4), string("b")),
+                (ArrayKey::Integer(-3), string("c")),
+                (ArrayKey::String(b"d".to_vec()), string("e")),
+            ])
+        );
+
+        assert_eq!(evaluate("return ['a' => 1] + ['a' => 2, 'b' => 3];"), evaluate("return ['a' => 1, 'b' => 3];"));
+        assert_eq!(evaluate("return [1, 2, 3][1] . 'abc'[-1] . (['a' => 1]['b'] ?? 'x');"), string("2cx"));
+        assert_eq!(evaluate("return [PHP_INT_MAX => 1, 2];"), Value::Unknown);
+    }
+
+    #[test]
+    fn test_constants() {
+        let code = r#"
+            namespace App;
+
+            const A = 2;
+            const B = A * 3;
+            define('App\C', B . PHP_EOL);
+
+            interface HasSize {
+                const SIZE = \PHP_INT_SIZE;
+            }
+
+            class Foo implements HasSize {
+                const C = self::D . ',' . self::SIZE;
+                const D = 'd';
+            }
+
+            enum Suit: string {
+                case Hearts = 'H';
+                const Wild = self::Hearts;
+            }
+
+            return [B, Foo::C, Foo::class, Suit::Wild->value, Suit::Hearts->name, Suit::Wild === Suit::Hearts];
+        "#;
+
+        assert_eq!(
+            evaluate(code),
+            Value::Array(vec![
+                (ArrayKey::Integer(0), Value::Integer(6)),
+                (ArrayKey::Integer(1), string("d,8")),
+                (ArrayKey::Integer(2), string("App\\Foo")),
+                (ArrayKey::Integer(3), string("H")),
+                (ArrayKey::Integer(4), string("Hearts")),
+                (ArrayKey::Integer(5), Value::Bool(true)),
+            ])
+        );
+
+        // The constants depending on the platform, or referencing themselves, are not known.
+        assert_eq!(evaluate("define('C', PHP_EOL); return C;"), Value::Unknown);
+        assert_eq!(evaluate("const E = E + 1; return E;"), Value::Unknown);
+        assert_eq!(evaluate("class Foo { const A = static::B; const B = 1; } return Foo::A;"), Value::Unknown);
+    }
+
+    #[test]
+    fn test_strings() {
+        assert_eq!(evaluate(r#"return "a\tb\x41\101\u{e9}\$\q";"#), string("a\tbAA\u{e9}$\\q"));
+        assert_eq!(evaluate(r#"return 'a\'b\\c\n';"#), string("a'b\\c\\n"));
+        assert_eq!(evaluate(r#"return "\400";"#), Value::String(vec![0]));
+        assert_eq!(evaluate(r#"return "\u{D800}";"#), Value::Unknown);
+    }
+}
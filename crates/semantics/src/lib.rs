@@ -15,29 +15,52 @@
 //! - **Name Resolution**: Associates identifiers with their declarations.
 //! - **Symbol Table Construction**: Records all symbols (classes, functions, variables) for quick lookup.
 //! - **Semantic Analysis**: Checks for semantic correctness and reports issues.
+//! - **Variable Analysis**: Reports the variables that are read before they are assigned, or possibly not assigned.
+//! - **Reachability Analysis**: Reports the code that is never executed.
+//! - **Constant Evaluation**: Evaluates the constant expressions, to report the duplicate cases, conditions, and keys.
+
+use std::sync::OnceLock;
 
 use serde::Deserialize;
 use serde::Serialize;
 
+use mago_ast::index::NodeId;
+use mago_ast::index::NodeIndex;
+use mago_ast::Node;
 use mago_ast::Program;
 use mago_interner::ThreadedInterner;
 use mago_names::Names;
 use mago_parser::error::ParseError;
+use mago_parser::settings::ParserSettings;
+use mago_php_version::PHPVersion;
 use mago_reporting::IssueCollection;
 use mago_source::Source;
 use mago_walker::Walker;
 
 use crate::context::Context;
+use crate::duplicates::DuplicatesWalker;
+use crate::evaluator::Evaluator;
+use crate::loops::LoopsWalker;
+use crate::unreachable::UnreachableWalker;
+use crate::variables::VariablesWalker;
+use crate::version::VersionWalker;
 use crate::walker::SemanticsWalker;
 
 mod consts;
 mod context;
+mod duplicates;
+mod evaluator;
+mod loops;
+mod unreachable;
+mod utils;
+mod variables;
+mod version;
 mod walker;
 
 /// The `Semantics` struct encapsulates all the information obtained after performing semantic analysis
 /// on a PHP source code file. It includes the original source code, the parsed abstract syntax tree (AST),
 /// any parse errors encountered, resolved names, the symbol table, and a collection of semantic issues.
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Semantics {
     /// The original PHP source code, including its name and content.
     pub source: Source,
@@ -45,8 +68,8 @@ pub struct Semantics {
     /// The abstract syntax tree (AST) resulting from parsing the source code.
     pub program: Program,
 
-    /// An optional parse error, if one occurred during parsing.
-    pub parse_error: Option<ParseError>,
+    /// The parse errors encountered during parsing, in the order they were encountered.
+    pub parse_errors: Vec<ParseError>,
 
     /// The resolved names within the source code, used for identifier resolution.
     pub names: Names,
@@ -54,6 +77,10 @@ pub struct Semantics {
     /// A collection of semantic issues found during analysis, such as invalid inheritance,
     ///  improper returns, duplicate names, etc.
     pub issues: IssueCollection,
+
+    /// The index of the nodes of the program, built the first time it is needed.
+    #[serde(skip)]
+    node_index: OnceLock<NodeIndex>,
 }
 
 impl Semantics {
@@ -63,7 +90,9 @@ impl Semantics {
     /// # Parameters
     ///
     /// - `interner`: A reference to a `ThreadedInterner` used for string interning, which helps in
-    ///               efficiently handling string comparisons and memory usage.
+    ///   efficiently handling string comparisons and memory usage.
+    /// - `version`: The targeted version of PHP, features introduced in newer versions are reported as issues.
+    /// - `settings`: The settings of the parser, such as whether short open tags are enabled.
     /// - `source`: The `Source` object representing the PHP source code to be analyzed.
     ///
     /// # Returns
@@ -74,14 +103,21 @@ impl Semantics {
     /// # Steps
     ///
     /// 1. **Parsing**: The source code is parsed into an abstract syntax tree (AST).
-    ///    If there are syntax errors, they are captured in `parse_error`.
+    ///    If there are syntax errors, they are captured in `parse_errors`, and the parser recovers
+    ///    from them where possible, so that the rest of the source code is still analyzed.
     /// 2. **Name Resolution**: Resolves all the names in the AST, linking identifiers to their declarations.
     /// 3. **Symbol Table Construction**: Builds a symbol table containing all the symbols (classes, functions, constants, etc.) defined in the source code.
     /// 4. **Semantic Analysis**: Checks the AST for semantic correctness, such as type checking, scope rules, etc., and collects any issues.
-    pub fn build(interner: &ThreadedInterner, source: Source) -> Self {
+    /// 5. **Version Checks**: Reports the features used in the source code that the targeted version of PHP does not support.
+    /// 6. **Variable Analysis**: Reports the variables that are read before they are assigned in functions, methods, and closures.
+    /// 7. **Reachability Analysis**: Reports the statements following a `return`, `throw`, `exit`, or another statement that does not complete.
+    /// 8. **Loop Checks**: Reports the `break` and `continue` statements outside of loops, or with invalid levels.
+    /// 9. **Duplicate Checks**: Reports the duplicate values of the cases of `switch` statements, the conditions of `match`
+    ///    expressions, the keys of array literals, and the cases of backed enums, as evaluated at compile time.
+    pub fn build(interner: &ThreadedInterner, version: PHPVersion, settings: ParserSettings, source: Source) -> Self {
         // Parse the source code into an AST.
-        // The parser returns a tuple containing the AST and an optional parse error.
-        let (program, parse_error) = mago_parser::parse_source(interner, &source);
+        // The parser returns a tuple containing the AST and the parse errors it recovered from.
+        let (program, parse_errors) = mago_parser::parse_source_with_settings(interner, &source, settings);
 
         // Resolve names in the AST.
         // This step links identifiers to their declarations, handling scopes and imports.
@@ -89,23 +125,42 @@ impl Semantics {
 
         // Perform semantic analysis and collect issues.
         // This includes checks for type correctness, proper usage of constructs, etc.
-        let mut context = Context::new(interner, &program, &names);
+        let mut context = Context::new(interner, version, &program, &names);
         SemanticsWalker.walk_program(&program, &mut context);
+
+        // Report the features that are not supported by the targeted version of PHP.
+        // The parser accepts the syntax of all versions, so that newer syntax is reported as such,
+        // instead of as a parse error.
+        VersionWalker.walk_program(&program, &mut context);
+
+        // Report the variables that are read before they are assigned, in the scope of function-likes.
+        VariablesWalker::new(&context).walk_program(&program, &mut context);
+
+        // Report the statements that are never executed, as they follow a statement which does not complete.
+        UnreachableWalker::new(&context).walk_program(&program, &mut context);
+
+        // Report the `break` and `continue` statements whose level does not target an enclosing loop.
+        LoopsWalker.walk_program(&program, &mut context);
+
+        // Report the values which are duplicated, as evaluated at compile time, such as the cases of a `switch`
+        // statement which are never matched, or the keys of an array literal whose value is overwritten.
+        let evaluator = Evaluator::new(interner, version, &program, &names);
+        DuplicatesWalker::new(&context, &evaluator).walk_program(&program, &mut context);
         let issues = context.take_issue_collection();
 
         // Return the Semantics object containing all analysis results.
-        Self { source, program, parse_error, names, issues }
+        Self { source, program, parse_errors, names, issues, node_index: OnceLock::new() }
     }
 
     /// Determines whether the semantic analysis was successful,
     /// i.e., no parse errors or semantic issues were found.
     pub fn is_valid(&self) -> bool {
-        self.parse_error.is_none() && self.issues.is_empty()
+        self.parse_errors.is_empty() && self.issues.is_empty()
     }
 
     /// Determines whether the source code contains any parse errors.
     pub fn has_parse_error(&self) -> bool {
-        self.parse_error.is_some()
+        !self.parse_errors.is_empty()
     }
 
     /// Determines whether the source code contains any semantic issues.
@@ -117,4 +172,30 @@ impl Semantics {
     pub fn is_external(&self) -> bool {
         self.source.identifier.is_external()
     }
+
+    /// Returns the index of the nodes of the program, to find the parent, the enclosing scope,
+    /// or the siblings of a node.
+    ///
+    /// The index is built the first time it is requested, and reused afterwards.
+    pub fn node_index(&self) -> &NodeIndex {
+        self.node_index.get_or_init(|| NodeIndex::new(&self.program))
+    }
+
+    /// Returns the node of the program with the given identifier in the node index.
+    pub fn node(&self, id: NodeId) -> Node<'_> {
+        self.node_index().get(&self.program, id)
+    }
 }
+
+/// The node index is derived from the program, so it is not compared.
+impl PartialEq for Semantics {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+            && self.program == other.program
+            && self.parse_errors == other.parse_errors
+            && self.names == other.names
+            && self.issues == other.issues
+    }
+}
+
+impl Eq for Semantics {}
@@ -0,0 +1,214 @@
+use mago_ast::ast::*;
+use mago_ast::Node;
+use mago_ast::Program;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_walker::Walker;
+
+use crate::consts::CONTINUE_TARGETING_SWITCH_CODE;
+use crate::consts::INVALID_LOOP_CONTROL_CODE;
+use crate::context::Context;
+
+/// Reports the `break` and `continue` statements which are not in a loop, or a `switch` statement, or whose level
+/// is not a positive literal integer, or exceeds the number of loops and `switch` statements enclosing them.
+///
+/// The loops of a scope are not visible from the functions, closures, and arrow functions declared in it.
+#[derive(Clone, Debug)]
+pub struct LoopsWalker;
+
+impl Walker<Context<'_>> for LoopsWalker {
+    fn walk_in_program(&self, program: &Program, context: &mut Context<'_>) {
+        Analyzer::new(context, None).node(Node::Program(program));
+    }
+
+    fn walk_in_function(&self, function: &Function, context: &mut Context<'_>) {
+        Analyzer::new(context, Some(("function", function.span()))).node(Node::Block(&function.body));
+    }
+
+    fn walk_in_method(&self, method: &Method, context: &mut Context<'_>) {
+        if let MethodBody::Concrete(body) = &method.body {
+            Analyzer::new(context, Some(("method", method.span()))).node(Node::Block(body));
+        }
+    }
+
+    fn walk_in_property_hook(&self, property_hook: &PropertyHook, context: &mut Context<'_>) {
+        if let PropertyHookBody::Concrete(PropertyHookConcreteBody::Block(block)) = &property_hook.body {
+            Analyzer::new(context, Some(("property hook", property_hook.span()))).node(Node::Block(block));
+        }
+    }
+
+    fn walk_in_closure(&self, closure: &Closure, context: &mut Context<'_>) {
+        Analyzer::new(context, Some(("closure", closure.span()))).node(Node::Block(&closure.body));
+    }
+}
+
+/// A loop, or a `switch` statement, which may be targeted by the `break` and `continue` statements of its body.
+#[derive(Clone, Copy, Debug)]
+struct Target {
+    switch: bool,
+    keyword: Span,
+}
+
+struct Analyzer<'c, 'a> {
+    context: &'c mut Context<'a>,
+    /// The kind, and the span, of the function-like being analyzed, if any.
+    scope: Option<(&'static str, Span)>,
+    targets: Vec<Target>,
+}
+
+impl<'c, 'a> Analyzer<'c, 'a> {
+    fn new(context: &'c mut Context<'a>, scope: Option<(&'static str, Span)>) -> Self {
+        Self { context, scope, targets: vec![] }
+    }
+
+    fn node(&mut self, node: Node<'_>) {
+        match node {
+            // The function-likes declared in the scope are analyzed separately, with their own loops.
+            Node::Function(_)
+            | Node::Method(_)
+            | Node::PropertyHook(_)
+            | Node::Closure(_)
+            | Node::ArrowFunction(_)
+            | Node::AnonymousClass(_)
+            | Node::Class(_)
+            | Node::Enum(_)
+            | Node::Interface(_)
+            | Node::Trait(_) => {}
+            Node::While(r#while) => self.target(node, false, r#while.r#while.span()),
+            Node::DoWhile(do_while) => self.target(node, false, do_while.r#do.span()),
+            Node::For(r#for) => self.target(node, false, r#for.r#for.span()),
+            Node::Foreach(foreach) => self.target(node, false, foreach.foreach.span()),
+            Node::Switch(switch) => self.target(node, true, switch.switch.span()),
+            Node::Break(r#break) => self.jump("break", r#break.span(), r#break.level.as_ref()),
+            Node::Continue(r#continue) => self.jump("continue", r#continue.span(), r#continue.level.as_ref()),
+            _ => {
+                for child in node.children() {
+                    self.node(child);
+                }
+            }
+        }
+    }
+
+    fn target(&mut self, node: Node<'_>, switch: bool, keyword: Span) {
+        self.targets.push(Target { switch, keyword });
+        for child in node.children() {
+            self.node(child);
+        }
+
+        self.targets.pop();
+    }
+
+    fn jump(&mut self, keyword: &str, span: Span, level: Option<&Expression>) {
+        let Some(level) = self.level(keyword, span, level) else {
+            return;
+        };
+
+        let Some(outermost) = self.targets.first().copied() else {
+            let mut issue =
+                Issue::error(format!("The `{}` statement is not in a loop, or a `switch` statement.", keyword))
+                    .with_code(INVALID_LOOP_CONTROL_CODE)
+                    .with_annotation(Annotation::primary(span).with_message(format!("`{}` used here.", keyword)));
+
+            if let Some((kind, scope)) = self.scope {
+                issue = issue
+                    .with_annotation(
+                        Annotation::secondary(scope).with_message(format!("No loop encloses it in this {}.", kind)),
+                    )
+                    .with_note("The loops enclosing a function-like are not visible from its body.");
+            }
+
+            self.context.report(issue.with_help(format!(
+                "Remove the `{}` statement, or move it into a loop, or a `switch` statement.",
+                keyword
+            )));
+
+            return;
+        };
+
+        let Some(index) = self.targets.len().checked_sub(level) else {
+            let enclosing = self.targets.len();
+
+            self.context.report(
+                Issue::error(format!(
+                    "Cannot `{}` {} levels, as only {} loop{} or `switch` statement{} enclose{} this statement.",
+                    keyword,
+                    level,
+                    enclosing,
+                    if enclosing == 1 { "" } else { "s" },
+                    if enclosing == 1 { "" } else { "s" },
+                    if enclosing == 1 { "s" } else { "" },
+                ))
+                .with_code(INVALID_LOOP_CONTROL_CODE)
+                .with_annotation(Annotation::primary(span).with_message(format!("`{}` used here.", keyword)))
+                .with_annotation(
+                    Annotation::secondary(outermost.keyword).with_message("This is the outermost enclosing statement."),
+                )
+                .with_help(format!("Use a level of at most {}.", enclosing)),
+            );
+
+            return;
+        };
+
+        let target = self.targets[index];
+        if keyword == "continue" && target.switch {
+            // The nearest loop enclosing the `switch` statement, which `continue` may have been meant to target.
+            let help = match self.targets[..index].iter().rposition(|target| !target.switch) {
+                Some(position) => format!(
+                    "Use `continue {}` to continue the enclosing loop, or `break` to exit the `switch` statement.",
+                    self.targets.len() - position
+                ),
+                None => "Use `break` to exit the `switch` statement.".to_string(),
+            };
+
+            self.context.report(
+                Issue::warning("The `continue` statement targeting a `switch` statement acts like `break`.")
+                    .with_code(CONTINUE_TARGETING_SWITCH_CODE)
+                    .with_annotation(Annotation::primary(span).with_message("`continue` used here."))
+                    .with_annotation(Annotation::secondary(target.keyword).with_message("This `switch` is targeted."))
+                    .with_help(help),
+            );
+        }
+    }
+
+    /// Returns the level of a `break` or `continue` statement, reporting it if it is not a positive literal integer.
+    fn level(&mut self, keyword: &str, span: Span, level: Option<&Expression>) -> Option<usize> {
+        let Some(expression) = level else {
+            return Some(1);
+        };
+
+        let mut value = expression;
+        while let Expression::Parenthesized(parenthesized) = value {
+            value = &parenthesized.expression;
+        }
+
+        let issue = match value {
+            Expression::Literal(Literal::Integer(LiteralInteger { value: Some(value), .. })) if *value > 0 => {
+                return Some(*value as usize);
+            }
+            Expression::Literal(Literal::Integer(_)) => {
+                Issue::error(format!("The level of the `{}` statement must be a positive integer.", keyword))
+                    .with_code(INVALID_LOOP_CONTROL_CODE)
+            }
+            Expression::UnaryPrefix(UnaryPrefix { operator: UnaryPrefixOperator::Negation(_), operand })
+                if matches!(operand.as_ref(), Expression::Literal(Literal::Integer(_))) =>
+            {
+                Issue::error(format!("The level of the `{}` statement must be a positive integer.", keyword))
+                    .with_code(INVALID_LOOP_CONTROL_CODE)
+            }
+            _ => Issue::error(format!("The level of the `{}` statement must be a literal integer.", keyword))
+                .with_code(INVALID_LOOP_CONTROL_CODE)
+                .with_note("Levels computed at runtime are not supported since PHP 5.4."),
+        };
+
+        self.context.report(
+            issue
+                .with_annotation(Annotation::primary(span).with_message(format!("`{}` used here.", keyword)))
+                .with_annotation(Annotation::secondary(expression.span()).with_message("Invalid level."))
+                .with_help(format!("Use a positive literal integer, such as `{} 1;`, as level.", keyword)),
+        );
+
+        None
+    }
+}
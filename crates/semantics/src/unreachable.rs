@@ -0,0 +1,386 @@
+use std::collections::HashSet;
+
+use mago_ast::ast::*;
+use mago_ast::Node;
+use mago_ast::Program;
+use mago_interner::StringIdentifier;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_walker::Walker;
+
+use crate::consts::UNREACHABLE_CODE_CODE;
+use crate::context::Context;
+use crate::utils::is_always_true;
+
+/// Reports the statements that are never executed, as they follow a statement which does not complete in the same
+/// block, such as `return`, `throw`, `exit`, `break`, or a call to a function returning `never`.
+///
+/// The unreachable statements following each other are reported as a single region, which ends at a label
+/// targeted by a `goto` statement. The functions returning `never` are the ones declared in the program.
+#[derive(Clone, Debug)]
+pub struct UnreachableWalker {
+    never_returning_functions: HashSet<String>,
+    labels: HashSet<StringIdentifier>,
+}
+
+impl UnreachableWalker {
+    pub fn new(context: &Context<'_>) -> Self {
+        let mut never_returning_functions = HashSet::new();
+        let mut labels = HashSet::new();
+        for node in context.program().descendants() {
+            match node {
+                Node::Function(function) => {
+                    if let Some(FunctionLikeReturnTypeHint { hint: Hint::Never(_), .. }) = function.return_type_hint {
+                        never_returning_functions
+                            .insert(context.lookup_name(&function.name.span.start).to_ascii_lowercase());
+                    }
+                }
+                Node::Goto(goto) => {
+                    labels.insert(goto.label.value);
+                }
+                _ => {}
+            }
+        }
+
+        Self { never_returning_functions, labels }
+    }
+}
+
+impl Walker<Context<'_>> for UnreachableWalker {
+    fn walk_in_program(&self, program: &Program, context: &mut Context<'_>) {
+        Analyzer::new(self, context).statements(program.statements.as_slice());
+    }
+
+    fn walk_in_function(&self, function: &Function, context: &mut Context<'_>) {
+        Analyzer::new(self, context).statements(function.body.statements.as_slice());
+    }
+
+    fn walk_in_method(&self, method: &Method, context: &mut Context<'_>) {
+        if let MethodBody::Concrete(body) = &method.body {
+            Analyzer::new(self, context).statements(body.statements.as_slice());
+        }
+    }
+
+    fn walk_in_property_hook(&self, property_hook: &PropertyHook, context: &mut Context<'_>) {
+        if let PropertyHookBody::Concrete(PropertyHookConcreteBody::Block(block)) = &property_hook.body {
+            Analyzer::new(self, context).statements(block.statements.as_slice());
+        }
+    }
+
+    fn walk_in_closure(&self, closure: &Closure, context: &mut Context<'_>) {
+        Analyzer::new(self, context).statements(closure.body.statements.as_slice());
+    }
+}
+
+/// A loop, or a `switch` statement, targeted by the `break` and `continue` statements of its body.
+#[derive(Debug, Default)]
+struct Target {
+    switch: bool,
+    broken: bool,
+    continued: bool,
+}
+
+/// Analyzes the statements of a function-like, or of the top level of the program.
+///
+/// The analysis of each statement returns whether its execution completes, continuing with the following
+/// statement.
+struct Analyzer<'w, 'c, 'a> {
+    walker: &'w UnreachableWalker,
+    context: &'c mut Context<'a>,
+    targets: Vec<Target>,
+}
+
+impl<'w, 'c, 'a> Analyzer<'w, 'c, 'a> {
+    fn new(walker: &'w UnreachableWalker, context: &'c mut Context<'a>) -> Self {
+        Self { walker, context, targets: vec![] }
+    }
+
+    fn statements(&mut self, statements: &[Statement]) -> bool {
+        // The statement which does not complete, and the first and last unreachable statements following it.
+        let mut terminator: Option<(Span, Option<(Span, Span)>)> = None;
+        for statement in statements {
+            if let Some((terminating, region)) = &mut terminator {
+                if !self.is_jumped_to(statement) {
+                    if !is_hoisted_or_markup(statement) {
+                        let span = statement.span();
+
+                        *region = Some(region.map_or((span, span), |(first, _)| (first, span)));
+                    }
+
+                    continue;
+                }
+
+                if let Some((first, last)) = region {
+                    self.report(*terminating, *first, *last);
+                }
+
+                terminator = None;
+            }
+
+            if !self.statement(statement) {
+                terminator = Some((statement.span(), None));
+            }
+        }
+
+        match terminator {
+            Some((terminating, region)) => {
+                if let Some((first, last)) = region {
+                    self.report(terminating, first, last);
+                }
+
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) -> bool {
+        match statement {
+            Statement::Namespace(namespace) => match &namespace.body {
+                NamespaceBody::Implicit(body) => self.statements(body.statements.as_slice()),
+                NamespaceBody::BraceDelimited(block) => self.statements(block.statements.as_slice()),
+            },
+            Statement::Declare(declare) => match &declare.body {
+                DeclareBody::Statement(statement) => self.statement(statement),
+                DeclareBody::ColonDelimited(body) => self.statements(body.statements.as_slice()),
+            },
+            Statement::Block(block) => self.statements(block.statements.as_slice()),
+            Statement::Return(_) | Statement::Goto(_) | Statement::HaltCompiler(_) => false,
+            Statement::Continue(r#continue) => {
+                self.jump(r#continue.level.as_ref(), true);
+
+                false
+            }
+            Statement::Break(r#break) => {
+                self.jump(r#break.level.as_ref(), false);
+
+                false
+            }
+            Statement::Expression(statement) => !self.diverges(&statement.expression),
+            Statement::If(r#if) => self.r#if(r#if),
+            Statement::Switch(switch) => self.switch(switch),
+            Statement::Try(r#try) => self.r#try(r#try),
+            Statement::While(r#while) => {
+                let (_, target) = self.target(false, |analyzer| match &r#while.body {
+                    WhileBody::Statement(statement) => analyzer.statement(statement),
+                    WhileBody::ColonDelimited(body) => analyzer.statements(body.statements.as_slice()),
+                });
+
+                target.broken || !is_always_true(&r#while.condition)
+            }
+            Statement::DoWhile(do_while) => {
+                let (completes, target) = self.target(false, |analyzer| analyzer.statement(&do_while.statement));
+
+                // The condition is only evaluated if the end of the body, or a `continue` statement, is reached.
+                target.broken || ((completes || target.continued) && !is_always_true(&do_while.condition))
+            }
+            Statement::For(r#for) => {
+                let (_, target) = self.target(false, |analyzer| match &r#for.body {
+                    ForBody::Statement(statement) => analyzer.statement(statement),
+                    ForBody::ColonDelimited(body) => analyzer.statements(body.statements.as_slice()),
+                });
+
+                // A `for` loop without conditions is infinite.
+                target.broken || r#for.conditions.last().is_some_and(|condition| !is_always_true(condition))
+            }
+            Statement::Foreach(foreach) => {
+                self.target(false, |analyzer| match &foreach.body {
+                    ForeachBody::Statement(statement) => analyzer.statement(statement),
+                    ForeachBody::ColonDelimited(body) => analyzer.statements(body.statements.as_slice()),
+                });
+
+                true
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns whether the evaluation of the given expression never completes, such as `throw` and `exit`.
+    fn diverges(&self, expression: &Expression) -> bool {
+        match expression {
+            Expression::Parenthesized(parenthesized) => self.diverges(&parenthesized.expression),
+            Expression::Throw(_) => true,
+            Expression::Construct(construct) => matches!(construct.as_ref(), Construct::Exit(_) | Construct::Die(_)),
+            Expression::Call(Call::Function(call)) => match call.function.as_ref() {
+                Expression::Identifier(identifier) => self.is_never_returning(identifier),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn is_never_returning(&self, identifier: &Identifier) -> bool {
+        let functions = &self.walker.never_returning_functions;
+        if functions.is_empty() {
+            return false;
+        }
+
+        if functions.contains(&self.context.lookup_name(&identifier.span().start).to_ascii_lowercase()) {
+            return true;
+        }
+
+        // Unqualified names of functions fall back to the global namespace.
+        let name = self.context.interner.lookup(&identifier.value());
+
+        !name.contains('\\') && functions.contains(&name.to_ascii_lowercase())
+    }
+
+    /// Returns whether the given statement is, or contains, a label targeted by a `goto` statement, making the
+    /// code following it reachable.
+    fn is_jumped_to(&self, statement: &Statement) -> bool {
+        let labels = &self.walker.labels;
+        if labels.is_empty() {
+            return false;
+        }
+
+        if let Statement::Label(label) = statement {
+            return labels.contains(&label.name.value);
+        }
+
+        Node::Statement(statement)
+            .descendants()
+            .into_iter()
+            .any(|node| matches!(node, Node::Label(label) if labels.contains(&label.name.value)))
+    }
+
+    fn r#if(&mut self, r#if: &If) -> bool {
+        match &r#if.body {
+            IfBody::Statement(body) => {
+                let mut completes = self.statement(&body.statement);
+                for clause in body.else_if_clauses.iter() {
+                    completes = self.statement(&clause.statement) || completes;
+                }
+
+                match &body.else_clause {
+                    Some(clause) => self.statement(&clause.statement) || completes,
+                    None => true,
+                }
+            }
+            IfBody::ColonDelimited(body) => {
+                let mut completes = self.statements(body.statements.as_slice());
+                for clause in body.else_if_clauses.iter() {
+                    completes = self.statements(clause.statements.as_slice()) || completes;
+                }
+
+                match &body.else_clause {
+                    Some(clause) => self.statements(clause.statements.as_slice()) || completes,
+                    None => true,
+                }
+            }
+        }
+    }
+
+    fn switch(&mut self, switch: &Switch) -> bool {
+        let cases = match &switch.body {
+            SwitchBody::BraceDelimited(body) => &body.cases,
+            SwitchBody::ColonDelimited(body) => &body.cases,
+        };
+
+        // A case which does not end with a `break` statement falls through to the next one, so only the execution
+        // of the last case may reach the end of the `switch` statement.
+        let ((has_default, completes), target) = self.target(true, |analyzer| {
+            let mut has_default = false;
+            let mut completes = true;
+            for case in cases.iter() {
+                let statements = match case {
+                    SwitchCase::Expression(case) => case.statements.as_slice(),
+                    SwitchCase::Default(case) => {
+                        has_default = true;
+
+                        case.statements.as_slice()
+                    }
+                };
+
+                completes = analyzer.statements(statements);
+            }
+
+            (has_default, completes)
+        });
+
+        target.broken || !has_default || completes
+    }
+
+    fn r#try(&mut self, r#try: &Try) -> bool {
+        let mut completes = self.statements(r#try.block.statements.as_slice());
+        for clause in r#try.catch_clauses.iter() {
+            completes = self.statements(clause.block.statements.as_slice()) || completes;
+        }
+
+        match &r#try.finally_clause {
+            Some(clause) => self.statements(clause.block.statements.as_slice()) && completes,
+            None => completes,
+        }
+    }
+
+    /// Analyzes the body of a loop, or of a `switch` statement, and returns how its `break` and `continue`
+    /// statements target it.
+    fn target<T>(&mut self, switch: bool, analyze: impl FnOnce(&mut Self) -> T) -> (T, Target) {
+        self.targets.push(Target { switch, ..Default::default() });
+        let result = analyze(self);
+
+        (result, self.targets.pop().unwrap_or_default())
+    }
+
+    fn jump(&mut self, level: Option<&Expression>, r#continue: bool) {
+        let level = match level {
+            Some(Expression::Literal(Literal::Integer(LiteralInteger { value: Some(level), .. }))) => *level as usize,
+            _ => 1,
+        };
+
+        let Some(index) = self.targets.len().checked_sub(level.max(1)) else {
+            return;
+        };
+
+        // A `continue` statement targeting a `switch` statement acts like a `break` statement.
+        let target = &mut self.targets[index];
+        if r#continue && !target.switch {
+            target.continued = true;
+        } else {
+            target.broken = true;
+        }
+    }
+
+    fn report(&mut self, terminator: Span, first: Span, last: Span) {
+        let region = first.join(last);
+
+        let mut issue = Issue::warning("Unreachable code detected.")
+            .with_code(UNREACHABLE_CODE_CODE)
+            .with_annotation(Annotation::primary(first).with_message("This code is never executed."));
+
+        if region != first {
+            issue = issue.with_annotation(
+                Annotation::secondary(region).with_message("The code of this region is never executed."),
+            );
+        }
+
+        self.context.report(
+            issue
+                .with_annotation(
+                    Annotation::secondary(terminator).with_message("The execution does not continue past this point."),
+                )
+                .with_help("Remove the unreachable code, or change the control flow so that it can be executed."),
+        );
+    }
+}
+
+/// Returns whether the given statement is not executed in sequence, such as the declaration of a function, or the
+/// markup outside of the PHP tags, and so is not reported as unreachable.
+fn is_hoisted_or_markup(statement: &Statement) -> bool {
+    matches!(
+        statement,
+        Statement::OpeningTag(_)
+            | Statement::ClosingTag(_)
+            | Statement::Inline(_)
+            | Statement::Use(_)
+            | Statement::Class(_)
+            | Statement::Interface(_)
+            | Statement::Trait(_)
+            | Statement::Enum(_)
+            | Statement::Function(_)
+            | Statement::HaltCompiler(_)
+            | Statement::Noop(_)
+            | Statement::Error(_)
+    )
+}
@@ -0,0 +1,11 @@
+use mago_ast::ast::*;
+
+/// Returns whether the given loop condition is always true, such as in `while (true)`.
+pub fn is_always_true(condition: &Expression) -> bool {
+    match condition {
+        Expression::Parenthesized(parenthesized) => is_always_true(&parenthesized.expression),
+        Expression::Literal(Literal::True(_)) => true,
+        Expression::Literal(Literal::Integer(LiteralInteger { value: Some(value), .. })) => *value != 0,
+        _ => false,
+    }
+}
@@ -0,0 +1,1054 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use mago_ast::ast::*;
+use mago_ast::Node;
+use mago_docblock::document::Element;
+use mago_docblock::document::TagKind;
+use mago_interner::StringIdentifier;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_walker::Walker;
+
+use crate::consts::OUT_PARAMETERS;
+use crate::consts::POSSIBLY_UNDEFINED_VARIABLE_CODE;
+use crate::consts::PREDEFINED_VARIABLES;
+use crate::consts::SCOPE_DEFINING_FUNCTIONS;
+use crate::consts::UNDEFINED_VARIABLE_CODE;
+use crate::context::Context;
+use crate::utils::is_always_true;
+
+/// Reports the variables that are read before they are assigned, in the scope of a function-like.
+///
+/// Variables which are not assigned on any path leading to a read are reported as undefined, and variables
+/// which are only assigned on some of them, such as in one branch of an `if` statement, as possibly undefined.
+///
+/// Arrow functions share the scope of the function-like they are declared in, and the code at the top level of
+/// a file is not checked, as its variables may be defined by the files including it.
+#[derive(Clone, Debug)]
+pub struct VariablesWalker {
+    references: References,
+}
+
+/// The parameters passed by reference of the functions, and methods, declared in a program, by lowercased name.
+///
+/// Variables passed to them are assigned by the call, and so are the variables passed to the methods which are
+/// not declared in the program, or to dynamic calls, as their parameters are not known.
+#[derive(Clone, Debug, Default)]
+struct References {
+    functions: HashMap<String, Vec<usize>>,
+    methods: HashMap<String, Vec<usize>>,
+}
+
+impl VariablesWalker {
+    pub fn new(context: &Context<'_>) -> Self {
+        let mut references = References::default();
+        for node in context.program().descendants() {
+            let (declarations, name, parameters) = match node {
+                Node::Function(function) => (&mut references.functions, &function.name, &function.parameters),
+                Node::Method(method) => (&mut references.methods, &method.name, &method.parameters),
+                _ => continue,
+            };
+
+            let positions = declarations.entry(context.interner.lookup(&name.value).to_ascii_lowercase()).or_default();
+            for (position, parameter) in parameters.parameters.iter().enumerate() {
+                if parameter.ampersand.is_some() && !positions.contains(&position) {
+                    positions.push(position);
+                }
+            }
+        }
+
+        Self { references }
+    }
+}
+
+impl Walker<Context<'_>> for VariablesWalker {
+    fn walk_in_function(&self, function: &Function, context: &mut Context<'_>) {
+        let mut analyzer = Analyzer::new(context, &self.references, "function", function.span());
+        analyzer.define_parameters(&function.parameters);
+        analyzer.statements(function.body.statements.as_slice());
+    }
+
+    fn walk_in_method(&self, method: &Method, context: &mut Context<'_>) {
+        let MethodBody::Concrete(body) = &method.body else {
+            return;
+        };
+
+        let mut analyzer = Analyzer::new(context, &self.references, "method", method.span());
+        analyzer.define_parameters(&method.parameters);
+        analyzer.statements(body.statements.as_slice());
+    }
+
+    fn walk_in_property_hook(&self, property_hook: &PropertyHook, context: &mut Context<'_>) {
+        let PropertyHookBody::Concrete(body) = &property_hook.body else {
+            return;
+        };
+
+        let is_set = context.interner.lookup(&property_hook.name.value).eq_ignore_ascii_case("set");
+        let mut analyzer = Analyzer::new(context, &self.references, "property hook", property_hook.span());
+        match &property_hook.parameters {
+            Some(parameters) => analyzer.define_parameters(parameters),
+            // The `set` hook has an implicit `$value` parameter if it does not declare one.
+            None if is_set => analyzer.define_name("$value"),
+            None => {}
+        }
+
+        match body {
+            PropertyHookConcreteBody::Block(block) => analyzer.statements(block.statements.as_slice()),
+            PropertyHookConcreteBody::Expression(body) => analyzer.expression(&body.expression),
+        }
+    }
+
+    fn walk_in_closure(&self, closure: &Closure, context: &mut Context<'_>) {
+        let mut analyzer = Analyzer::new(context, &self.references, "closure", closure.span());
+        analyzer.define_parameters(&closure.parameters);
+        if let Some(use_clause) = &closure.use_clause {
+            for variable in use_clause.variables.iter() {
+                analyzer.define(&variable.variable);
+            }
+        }
+
+        analyzer.statements(closure.body.statements.as_slice());
+    }
+}
+
+/// Whether a variable is assigned on all the paths leading to a point of the code, or only on some of them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Assigned {
+    Always,
+    Sometimes,
+}
+
+/// The variables assigned at a point of the code.
+#[derive(Clone, Debug, Default)]
+struct State {
+    variables: HashMap<StringIdentifier, Assigned>,
+    /// Whether variables may have been assigned dynamically, such as by `extract()`, or by an included file.
+    dynamic: bool,
+    /// Whether this point of the code cannot be reached, such as after a `return` statement.
+    unreachable: bool,
+}
+
+/// The states at the `break` and `continue` statements targeting a loop, or a `switch` statement.
+#[derive(Debug, Default)]
+struct Jumps {
+    breaks: Vec<State>,
+    continues: Vec<State>,
+}
+
+impl State {
+    fn unreachable() -> Self {
+        Self { unreachable: true, ..Default::default() }
+    }
+
+    /// Returns this state as if it could be reached, to join the assignments made before it could not.
+    fn reachable(self) -> Self {
+        Self { unreachable: false, ..self }
+    }
+
+    /// Merges the states of two paths leading to the same point of the code.
+    fn join(self, other: Self) -> Self {
+        if self.unreachable {
+            return other;
+        }
+
+        if other.unreachable {
+            return self;
+        }
+
+        let mut variables = HashMap::with_capacity(self.variables.len().max(other.variables.len()));
+        for (name, assigned) in &self.variables {
+            let assigned = match other.variables.get(name) {
+                Some(Assigned::Always) => *assigned,
+                _ => Assigned::Sometimes,
+            };
+
+            variables.insert(*name, assigned);
+        }
+
+        for name in other.variables.keys() {
+            variables.entry(*name).or_insert(Assigned::Sometimes);
+        }
+
+        Self { variables, dynamic: self.dynamic || other.dynamic, unreachable: false }
+    }
+}
+
+struct Analyzer<'c, 'a> {
+    context: &'c mut Context<'a>,
+    references: &'c References,
+    /// The kind, and the span, of the function-like whose scope is analyzed.
+    scope: (&'static str, Span),
+    state: State,
+    /// The jumps targeting the enclosing loops, and `switch` statements, from the outermost to the innermost.
+    jumps: Vec<Jumps>,
+    /// The number of enclosing analyses whose issues are not reported, such as the first pass over a loop.
+    silent: usize,
+    reported: HashSet<Span>,
+}
+
+impl<'c, 'a> Analyzer<'c, 'a> {
+    fn new(context: &'c mut Context<'a>, references: &'c References, kind: &'static str, span: Span) -> Self {
+        Self {
+            context,
+            references,
+            scope: (kind, span),
+            state: State::default(),
+            jumps: vec![],
+            silent: 0,
+            reported: HashSet::new(),
+        }
+    }
+
+    fn define(&mut self, variable: &DirectVariable) {
+        self.state.variables.insert(variable.name, Assigned::Always);
+    }
+
+    fn define_name(&mut self, name: &str) {
+        let name = self.context.interner.intern(name);
+
+        self.state.variables.insert(name, Assigned::Always);
+    }
+
+    fn define_parameters(&mut self, parameters: &FunctionLikeParameterList) {
+        for parameter in parameters.parameters.iter() {
+            self.define(&parameter.variable);
+        }
+    }
+
+    /// Defines the variables documented with `@var` in the docblock preceding the given span, such as `$foo` in
+    /// `/** @var Foo $foo */`, which are assumed to be assigned by code that cannot be followed.
+    fn define_documented_variables(&mut self, span: Span) {
+        let Some(docblock) = self.context.docblock(span) else {
+            return;
+        };
+
+        let Ok(document) = mago_docblock::parse_trivia(self.context.interner, docblock) else {
+            return;
+        };
+
+        for element in document.elements {
+            let Element::Tag(tag) = element else {
+                continue;
+            };
+
+            if !matches!(tag.kind, TagKind::Var | TagKind::PsalmVar | TagKind::PhpstanVar) {
+                continue;
+            }
+
+            let description = self.context.interner.lookup(&tag.description);
+            let Some(name) = description.split_whitespace().find(|word| word.starts_with('$')) else {
+                continue;
+            };
+
+            let end = name[1..]
+                .find(|character: char| !character.is_alphanumeric() && character != '_' && character.is_ascii())
+                .map_or(name.len(), |end| end + 1);
+
+            self.define_name(&name[..end]);
+        }
+    }
+
+    fn read(&mut self, variable: &DirectVariable) {
+        let name = self.context.interner.lookup(&variable.name);
+        if PREDEFINED_VARIABLES.contains(&name) {
+            return;
+        }
+
+        let assigned = self.state.variables.get(&variable.name).copied();
+        if assigned == Some(Assigned::Always) || self.silent > 0 || self.state.dynamic || self.state.unreachable {
+            return;
+        }
+
+        // The following reads of the variable are not reported again.
+        self.define(variable);
+        if !self.reported.insert(variable.span) {
+            return;
+        }
+
+        let (kind, scope) = self.scope;
+        let issue = if assigned.is_none() {
+            Issue::warning(format!("Variable `{}` is not defined.", name))
+                .with_code(UNDEFINED_VARIABLE_CODE)
+                .with_annotation(
+                    Annotation::primary(variable.span)
+                        .with_message(format!("Variable `{}` is read here, but it is not assigned before.", name)),
+                )
+        } else {
+            Issue::help(format!("Variable `{}` might not be defined.", name))
+                .with_code(POSSIBLY_UNDEFINED_VARIABLE_CODE)
+                .with_annotation(Annotation::primary(variable.span).with_message(format!(
+                    "Variable `{}` is read here, but it is only assigned on some of the paths leading here.",
+                    name
+                )))
+        };
+
+        let help = if kind == "closure" {
+            "Assign the variable before reading it, or import it from the enclosing scope with `use`."
+        } else {
+            "Assign the variable before reading it, or check whether it is set with `isset()`."
+        };
+
+        self.context.report(
+            issue
+                .with_annotation(
+                    Annotation::secondary(scope).with_message(format!("Variables are local to this {}.", kind)),
+                )
+                .with_help(help),
+        );
+    }
+
+    fn statements(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            self.statement(statement);
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        self.define_documented_variables(statement.span());
+
+        match statement {
+            // Declarations have their own scope.
+            Statement::Function(_)
+            | Statement::Class(_)
+            | Statement::Interface(_)
+            | Statement::Trait(_)
+            | Statement::Enum(_) => {}
+            Statement::Block(block) => self.statements(block.statements.as_slice()),
+            Statement::If(r#if) => self.r#if(r#if),
+            Statement::While(r#while) => self.r#while(r#while),
+            Statement::DoWhile(do_while) => self.do_while(do_while),
+            Statement::For(r#for) => self.r#for(r#for),
+            Statement::Foreach(foreach) => self.foreach(foreach),
+            Statement::Switch(switch) => self.switch(switch),
+            Statement::Try(r#try) => self.r#try(r#try),
+            Statement::Return(r#return) => {
+                if let Some(value) = &r#return.value {
+                    self.expression(value);
+                }
+
+                self.state.unreachable = true;
+            }
+            Statement::Break(r#break) => self.jump(r#break.level.as_ref(), false),
+            Statement::Continue(r#continue) => self.jump(r#continue.level.as_ref(), true),
+            Statement::Global(global) => {
+                for variable in global.variables.iter() {
+                    match variable {
+                        Variable::Direct(variable) => self.define(variable),
+                        variable => {
+                            self.children(Node::Variable(variable));
+                            self.state.dynamic = true;
+                        }
+                    }
+                }
+            }
+            Statement::Static(r#static) => {
+                for item in r#static.items.iter() {
+                    match item {
+                        StaticItem::Abstract(item) => self.define(&item.variable),
+                        StaticItem::Concrete(item) => {
+                            self.expression(&item.value);
+                            self.define(&item.variable);
+                        }
+                    }
+                }
+            }
+            Statement::Unset(unset) => {
+                for value in unset.values.iter() {
+                    match value {
+                        Expression::Variable(Variable::Direct(variable)) => {
+                            self.state.variables.remove(&variable.name);
+                        }
+                        value => self.quiet(value),
+                    }
+                }
+            }
+            // A label may be jumped to from anywhere in the function-like, with any variables assigned.
+            Statement::Label(_) => {
+                self.state.unreachable = false;
+                self.state.dynamic = true;
+            }
+            Statement::Goto(_) => self.state.unreachable = true,
+            statement => self.children(Node::Statement(statement)),
+        }
+    }
+
+    fn children(&mut self, node: Node<'_>) {
+        for child in node.children() {
+            child.traverse(&mut |node| {
+                match node {
+                    Node::Statement(statement) => self.statement(statement),
+                    Node::Expression(expression) => self.expression(expression),
+                    Node::DirectVariable(variable) => self.read(variable),
+                    _ => return true,
+                }
+
+                false
+            });
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Variable(Variable::Direct(variable)) => self.read(variable),
+            Expression::AssignmentOperation(assignment) => self.assignment(assignment),
+            Expression::Binary(binary) => self.binary(binary),
+            Expression::Conditional(conditional) => self.conditional(conditional),
+            Expression::UnaryPrefix(UnaryPrefix { operator, operand }) => match operator {
+                UnaryPrefixOperator::ErrorControl(_) => self.quiet(operand),
+                // A reference to a variable that is not defined defines it, as `null`.
+                UnaryPrefixOperator::Reference(_) => self.assign(operand, false),
+                UnaryPrefixOperator::PreIncrement(_) | UnaryPrefixOperator::PreDecrement(_) => {
+                    self.assign(operand, true)
+                }
+                _ => self.expression(operand),
+            },
+            Expression::UnaryPostfix(postfix) => self.assign(&postfix.operand, true),
+            Expression::Construct(construct) => self.construct(construct),
+            Expression::Throw(throw) => {
+                self.expression(&throw.exception);
+                self.state.unreachable = true;
+            }
+            Expression::Call(Call::Function(call)) => self.function_call(call),
+            Expression::Call(Call::Method(call)) => {
+                self.expression(&call.object);
+                self.method_call(&call.method, &call.arguments);
+            }
+            Expression::Call(Call::NullSafeMethod(call)) => {
+                self.expression(&call.object);
+                self.method_call(&call.method, &call.arguments);
+            }
+            Expression::Call(Call::StaticMethod(call)) => {
+                self.expression(&call.class);
+                self.method_call(&call.method, &call.arguments);
+            }
+            Expression::Access(access) => match access.as_ref() {
+                // The property of `Foo::$bar` is not a variable.
+                Access::StaticProperty(access) => {
+                    self.expression(&access.class);
+                    if !matches!(access.property, Variable::Direct(_)) {
+                        self.children(Node::Variable(&access.property));
+                    }
+                }
+                access => self.children(Node::Access(access)),
+            },
+            Expression::Match(r#match) => self.r#match(r#match),
+            Expression::Closure(closure) => self.closure(closure),
+            Expression::ArrowFunction(arrow_function) => self.arrow_function(arrow_function),
+            Expression::AnonymousClass(anonymous_class) => {
+                if let Some(arguments) = &anonymous_class.arguments {
+                    self.children(Node::ArgumentList(arguments));
+                }
+            }
+            expression => self.children(Node::Expression(expression)),
+        }
+    }
+
+    /// Analyzes an expression whose variables are not reported if they are not defined, such as `$foo` in
+    /// `isset($foo[$bar])`, while the variables of its offsets, such as `$bar`, still are.
+    fn quiet(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Variable(Variable::Direct(_)) => {}
+            Expression::Parenthesized(parenthesized) => self.quiet(&parenthesized.expression),
+            Expression::ArrayAccess(access) => {
+                self.quiet(&access.array);
+                self.expression(&access.index);
+            }
+            Expression::Access(access) => match access.as_ref() {
+                Access::Property(access) => {
+                    self.quiet(&access.object);
+                    self.children(Node::ClassLikeMemberSelector(&access.property));
+                }
+                Access::NullSafeProperty(access) => {
+                    self.quiet(&access.object);
+                    self.children(Node::ClassLikeMemberSelector(&access.property));
+                }
+                _ => self.expression(expression),
+            },
+            expression => self.expression(expression),
+        }
+    }
+
+    fn assignment(&mut self, assignment: &Assignment) {
+        match assignment.operator {
+            AssignmentOperator::Assign(_) => {
+                self.expression(&assignment.rhs);
+                self.assign(&assignment.lhs, false);
+            }
+            AssignmentOperator::Coalesce(_) => {
+                self.quiet(&assignment.lhs);
+                self.expression(&assignment.rhs);
+                self.assign(&assignment.lhs, false);
+            }
+            _ => {
+                self.expression(&assignment.rhs);
+                self.assign(&assignment.lhs, true);
+            }
+        }
+    }
+
+    /// Assigns the given target, such as `$foo` in `$foo = 1`, `[$foo, $bar] = $baz`, or `$foo[] = 1`, reading
+    /// it before for compound assignments, such as `$foo .= 'bar'`.
+    fn assign(&mut self, target: &Expression, compound: bool) {
+        match target {
+            Expression::Variable(Variable::Direct(variable)) => {
+                if compound {
+                    self.read(variable);
+                }
+
+                self.define(variable);
+            }
+            Expression::Variable(variable) => {
+                self.children(Node::Variable(variable));
+                self.state.dynamic = true;
+            }
+            Expression::Array(array) => self.destructure(array.elements.iter()),
+            Expression::LegacyArray(array) => self.destructure(array.elements.iter()),
+            Expression::List(list) => self.destructure(list.elements.iter()),
+            // Assigning an offset of a variable that is not defined defines it, as an array.
+            Expression::ArrayAccess(access) => {
+                self.expression(&access.index);
+                self.assign(&access.array, compound);
+            }
+            Expression::ArrayAppend(append) => self.assign(&append.array, compound),
+            Expression::UnaryPrefix(UnaryPrefix { operator: UnaryPrefixOperator::Reference(_), operand }) => {
+                self.assign(operand, compound)
+            }
+            target => self.expression(target),
+        }
+    }
+
+    fn destructure<'e>(&mut self, elements: impl Iterator<Item = &'e ArrayElement>) {
+        for element in elements {
+            match element {
+                ArrayElement::KeyValue(element) => {
+                    self.expression(&element.key);
+                    self.assign(&element.value, false);
+                }
+                ArrayElement::Value(element) => self.assign(&element.value, false),
+                ArrayElement::Variadic(element) => self.expression(&element.value),
+                ArrayElement::Missing(_) => {}
+            }
+        }
+    }
+
+    fn binary(&mut self, binary: &Binary) {
+        match binary.operator {
+            BinaryOperator::And(_) | BinaryOperator::LowAnd(_) => {
+                self.expression(&binary.lhs);
+                self.branch(|analyzer| {
+                    analyzer.narrow(&binary.lhs, true);
+                    analyzer.expression(&binary.rhs);
+                });
+            }
+            BinaryOperator::Or(_) | BinaryOperator::LowOr(_) => {
+                self.expression(&binary.lhs);
+                self.branch(|analyzer| {
+                    analyzer.narrow(&binary.lhs, false);
+                    analyzer.expression(&binary.rhs);
+                });
+            }
+            BinaryOperator::NullCoalesce(_) => {
+                self.quiet(&binary.lhs);
+                self.branch(|analyzer| analyzer.expression(&binary.rhs));
+            }
+            BinaryOperator::Elvis(_) => {
+                self.expression(&binary.lhs);
+                self.branch(|analyzer| analyzer.expression(&binary.rhs));
+            }
+            _ => {
+                self.expression(&binary.lhs);
+                self.expression(&binary.rhs);
+            }
+        }
+    }
+
+    /// Analyzes code that may not be executed, such as the right operand of `&&`.
+    fn branch(&mut self, analyze: impl FnOnce(&mut Self)) {
+        let before = self.state.clone();
+        analyze(self);
+
+        self.state = before.join(std::mem::take(&mut self.state));
+    }
+
+    fn conditional(&mut self, conditional: &Conditional) {
+        self.expression(&conditional.condition);
+
+        let otherwise = self.state.clone();
+        if let Some(then) = &conditional.then {
+            self.narrow(&conditional.condition, true);
+            self.expression(then);
+        }
+
+        let then = std::mem::replace(&mut self.state, otherwise);
+        self.narrow(&conditional.condition, false);
+        self.expression(&conditional.r#else);
+
+        self.state = then.join(std::mem::take(&mut self.state));
+    }
+
+    fn construct(&mut self, construct: &Construct) {
+        match construct {
+            Construct::Isset(isset) => {
+                for value in isset.values.iter() {
+                    self.quiet(value);
+                }
+            }
+            Construct::Empty(empty) => self.quiet(&empty.value),
+            Construct::Eval(_)
+            | Construct::Include(_)
+            | Construct::IncludeOnce(_)
+            | Construct::Require(_)
+            | Construct::RequireOnce(_) => {
+                self.children(Node::Construct(construct));
+                self.state.dynamic = true;
+            }
+            Construct::Exit(_) | Construct::Die(_) => {
+                self.children(Node::Construct(construct));
+                self.state.unreachable = true;
+            }
+            Construct::Print(_) => self.children(Node::Construct(construct)),
+        }
+    }
+
+    fn function_call(&mut self, call: &FunctionCall) {
+        self.expression(&call.function);
+
+        let interner = self.context.interner;
+        let name = match call.function.as_ref() {
+            Expression::Identifier(identifier) => {
+                let name = interner.lookup(&identifier.value());
+
+                Some(name.strip_prefix('\\').unwrap_or(name).to_ascii_lowercase())
+            }
+            _ => None,
+        };
+
+        let declared = name.as_ref().and_then(|name| self.references.functions.get(name));
+        self.arguments(&call.arguments, |index| match &name {
+            Some(name) => declared.is_some_and(|positions| positions.contains(&index)) || is_out_parameter(name, index),
+            None => true,
+        });
+
+        if name.is_some_and(|name| SCOPE_DEFINING_FUNCTIONS.contains(&name.as_str())) {
+            self.state.dynamic = true;
+        }
+    }
+
+    fn method_call(&mut self, method: &ClassLikeMemberSelector, arguments: &ArgumentList) {
+        self.children(Node::ClassLikeMemberSelector(method));
+
+        let declared = match method {
+            ClassLikeMemberSelector::Identifier(identifier) => {
+                self.references.methods.get(&self.context.interner.lookup(&identifier.value).to_ascii_lowercase())
+            }
+            _ => None,
+        };
+
+        self.arguments(arguments, |index| declared.is_none_or(|positions| positions.contains(&index)));
+    }
+
+    /// Analyzes the given arguments, assigning the variables passed to the parameters passed by reference.
+    fn arguments(&mut self, arguments: &ArgumentList, by_reference: impl Fn(usize) -> bool) {
+        for (index, argument) in arguments.arguments.iter().enumerate() {
+            match argument {
+                Argument::Positional(argument) if argument.ellipsis.is_none() && by_reference(index) => {
+                    self.assign(&argument.value, false);
+                }
+                argument => self.children(Node::Argument(argument)),
+            }
+        }
+    }
+
+    fn r#match(&mut self, r#match: &Match) {
+        self.expression(&r#match.expression);
+
+        // A `match` expression without a matching arm throws an error, so the state after it is the join of the
+        // states after its arms.
+        let before = std::mem::replace(&mut self.state, State::unreachable());
+        let mut after = State::unreachable();
+        for arm in r#match.arms.iter() {
+            self.state = before.clone();
+            match arm {
+                MatchArm::Expression(arm) => {
+                    for condition in arm.conditions.iter() {
+                        self.expression(condition);
+                    }
+
+                    self.expression(&arm.expression);
+                }
+                MatchArm::Default(arm) => self.expression(&arm.expression),
+            }
+
+            after = after.join(std::mem::take(&mut self.state));
+        }
+
+        self.state = after;
+    }
+
+    /// Analyzes the variables imported by a closure, whose body has its own scope.
+    fn closure(&mut self, closure: &Closure) {
+        let Some(use_clause) = &closure.use_clause else {
+            return;
+        };
+
+        for variable in use_clause.variables.iter() {
+            if variable.ampersand.is_some() {
+                self.define(&variable.variable);
+            } else {
+                self.read(&variable.variable);
+            }
+        }
+    }
+
+    /// Analyzes an arrow function, which captures the variables of the enclosing scope by value, so that the
+    /// variables it assigns are not assigned after it.
+    fn arrow_function(&mut self, arrow_function: &ArrowFunction) {
+        let before = self.state.clone();
+        self.define_parameters(&arrow_function.parameters);
+        self.expression(&arrow_function.expression);
+
+        self.state = before;
+    }
+
+    /// Defines the variables that are known to be set when the given condition is truthy, or falsy, such as
+    /// `$foo` in the body of `if (isset($foo)) { ... }`.
+    fn narrow(&mut self, condition: &Expression, truthy: bool) {
+        match condition {
+            Expression::Parenthesized(parenthesized) => self.narrow(&parenthesized.expression, truthy),
+            Expression::UnaryPrefix(UnaryPrefix { operator: UnaryPrefixOperator::Not(_), operand }) => {
+                self.narrow(operand, !truthy)
+            }
+            Expression::Binary(binary) => match binary.operator {
+                BinaryOperator::And(_) | BinaryOperator::LowAnd(_) if truthy => {
+                    self.narrow(&binary.lhs, truthy);
+                    self.evaluated(&binary.rhs);
+                    self.narrow(&binary.rhs, truthy);
+                }
+                BinaryOperator::Or(_) | BinaryOperator::LowOr(_) if !truthy => {
+                    self.narrow(&binary.lhs, truthy);
+                    self.evaluated(&binary.rhs);
+                    self.narrow(&binary.rhs, truthy);
+                }
+                _ => {}
+            },
+            Expression::Construct(construct) => match construct.as_ref() {
+                Construct::Isset(isset) if truthy => {
+                    for value in isset.values.iter() {
+                        self.define_root(value);
+                    }
+                }
+                Construct::Empty(empty) if !truthy => self.define_root(&empty.value),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Applies the assignments of an operand known to have been evaluated, such as the right operand of `&&` when
+    /// the condition holds, without reporting its reads again.
+    fn evaluated(&mut self, expression: &Expression) {
+        self.silent += 1;
+        self.expression(expression);
+        self.silent -= 1;
+    }
+
+    /// Defines the variable at the root of the given expression, such as `$foo` in `$foo['bar']->baz`.
+    fn define_root(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Variable(Variable::Direct(variable)) => self.define(variable),
+            Expression::Parenthesized(parenthesized) => self.define_root(&parenthesized.expression),
+            Expression::ArrayAccess(access) => self.define_root(&access.array),
+            Expression::Access(access) => match access.as_ref() {
+                Access::Property(access) => self.define_root(&access.object),
+                Access::NullSafeProperty(access) => self.define_root(&access.object),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn r#if(&mut self, r#if: &If) {
+        let (clauses, r#else): (Vec<(&Expression, &[Statement])>, _) = match &r#if.body {
+            IfBody::Statement(body) => (
+                std::iter::once((&r#if.condition, std::slice::from_ref(&body.statement)))
+                    .chain(
+                        body.else_if_clauses
+                            .iter()
+                            .map(|clause| (&clause.condition, std::slice::from_ref(&clause.statement))),
+                    )
+                    .collect(),
+                body.else_clause.as_ref().map(|clause| std::slice::from_ref(&clause.statement)),
+            ),
+            IfBody::ColonDelimited(body) => (
+                std::iter::once((&r#if.condition, body.statements.as_slice()))
+                    .chain(body.else_if_clauses.iter().map(|clause| (&clause.condition, clause.statements.as_slice())))
+                    .collect(),
+                body.else_clause.as_ref().map(|clause| clause.statements.as_slice()),
+            ),
+        };
+
+        let mut after = State::unreachable();
+        for (condition, statements) in clauses {
+            self.expression(condition);
+
+            let otherwise = self.state.clone();
+            self.narrow(condition, true);
+            self.statements(statements);
+
+            after = after.join(std::mem::replace(&mut self.state, otherwise));
+            self.narrow(condition, false);
+        }
+
+        if let Some(statements) = r#else {
+            self.statements(statements);
+        }
+
+        self.state = after.join(std::mem::take(&mut self.state));
+    }
+
+    fn r#while(&mut self, r#while: &While) {
+        let statements = match &r#while.body {
+            WhileBody::Statement(statement) => std::slice::from_ref(statement),
+            WhileBody::ColonDelimited(body) => body.statements.as_slice(),
+        };
+
+        self.expression(&r#while.condition);
+
+        let entry = std::mem::take(&mut self.state);
+        let (start, end, breaks) = self.iterate(entry, &mut |analyzer| {
+            analyzer.narrow(&r#while.condition, true);
+            analyzer.statements(statements);
+        });
+
+        self.state = end;
+        self.expression(&r#while.condition);
+
+        let end = std::mem::take(&mut self.state);
+        self.exit(start.join(end), Some(&r#while.condition), breaks);
+    }
+
+    fn do_while(&mut self, do_while: &DoWhile) {
+        let entry = std::mem::take(&mut self.state);
+        let (_, end, breaks) = self.iterate(entry, &mut |analyzer| analyzer.statement(&do_while.statement));
+
+        self.state = end;
+        self.expression(&do_while.condition);
+
+        let end = std::mem::take(&mut self.state);
+        self.exit(end, Some(&do_while.condition), breaks);
+    }
+
+    fn r#for(&mut self, r#for: &For) {
+        let statements = match &r#for.body {
+            ForBody::Statement(statement) => std::slice::from_ref(statement),
+            ForBody::ColonDelimited(body) => body.statements.as_slice(),
+        };
+
+        for initialization in r#for.initializations.iter() {
+            self.expression(initialization);
+        }
+
+        for condition in r#for.conditions.iter() {
+            self.expression(condition);
+        }
+
+        let condition = r#for.conditions.last();
+        let entry = std::mem::take(&mut self.state);
+        let (start, end, breaks) = self.iterate(entry, &mut |analyzer| {
+            if let Some(condition) = condition {
+                analyzer.narrow(condition, true);
+            }
+
+            analyzer.statements(statements);
+        });
+
+        self.state = end;
+        for increment in r#for.increments.iter() {
+            self.expression(increment);
+        }
+
+        for condition in r#for.conditions.iter() {
+            self.expression(condition);
+        }
+
+        let end = std::mem::take(&mut self.state);
+        match condition {
+            Some(condition) => self.exit(start.join(end), Some(condition), breaks),
+            // A loop without a condition is only exited by a `break` statement.
+            None => self.exit(State::unreachable(), None, breaks),
+        }
+    }
+
+    fn foreach(&mut self, foreach: &Foreach) {
+        let statements = match &foreach.body {
+            ForeachBody::Statement(statement) => std::slice::from_ref(statement),
+            ForeachBody::ColonDelimited(body) => body.statements.as_slice(),
+        };
+
+        self.expression(&foreach.expression);
+
+        let entry = std::mem::take(&mut self.state);
+        let (start, end, breaks) = self.iterate(entry, &mut |analyzer| {
+            match &foreach.target {
+                ForeachTarget::Value(target) => analyzer.assign(&target.value, false),
+                ForeachTarget::KeyValue(target) => {
+                    analyzer.assign(&target.key, false);
+                    analyzer.assign(&target.value, false);
+                }
+            }
+
+            analyzer.statements(statements);
+        });
+
+        self.exit(start.join(end), None, breaks);
+    }
+
+    /// Analyzes the body of a loop, starting with the given state before its first iteration.
+    ///
+    /// The body is first analyzed without reporting issues, to find the variables assigned by an iteration,
+    /// which may be assigned at the start of the next ones. Returns the state at the start of any iteration,
+    /// the state at the end of any iteration, and the states at the `break` statements.
+    fn iterate(&mut self, entry: State, body: &mut dyn FnMut(&mut Self)) -> (State, State, Vec<State>) {
+        let mut start = entry;
+
+        // The first pass is enough to find the variables assigned by the loop, if the enclosing code is only
+        // analyzed for the same reason.
+        if self.silent == 0 {
+            self.silent += 1;
+            let (end, _) = self.iteration(start.clone(), body);
+            self.silent -= 1;
+
+            start = start.join(end);
+        }
+
+        let (end, breaks) = self.iteration(start.clone(), body);
+
+        (start, end, breaks)
+    }
+
+    fn iteration(&mut self, start: State, body: &mut dyn FnMut(&mut Self)) -> (State, Vec<State>) {
+        self.state = start;
+        self.jumps.push(Jumps::default());
+        body(self);
+
+        let jumps = self.jumps.pop().unwrap_or_default();
+        let end = jumps.continues.into_iter().fold(std::mem::take(&mut self.state), State::join);
+
+        (end, jumps.breaks)
+    }
+
+    /// Continues after a loop, from the state in which its condition is evaluated, and the states at its `break`
+    /// statements.
+    fn exit(&mut self, state: State, condition: Option<&Expression>, breaks: Vec<State>) {
+        self.state = match condition {
+            Some(condition) if is_always_true(condition) => State::unreachable(),
+            _ => state,
+        };
+
+        if let Some(condition) = condition {
+            self.narrow(condition, false);
+        }
+
+        self.state = breaks.into_iter().fold(std::mem::take(&mut self.state), State::join);
+    }
+
+    fn jump(&mut self, level: Option<&Expression>, r#continue: bool) {
+        let level = match level {
+            Some(Expression::Literal(Literal::Integer(LiteralInteger { value: Some(level), .. }))) => *level as usize,
+            _ => 1,
+        };
+
+        if let Some(index) = self.jumps.len().checked_sub(level.max(1)) {
+            let state = self.state.clone();
+            if r#continue {
+                self.jumps[index].continues.push(state);
+            } else {
+                self.jumps[index].breaks.push(state);
+            }
+        }
+
+        self.state.unreachable = true;
+    }
+
+    fn switch(&mut self, switch: &Switch) {
+        let cases = match &switch.body {
+            SwitchBody::BraceDelimited(body) => &body.cases,
+            SwitchBody::ColonDelimited(body) => &body.cases,
+        };
+
+        self.expression(&switch.expression);
+
+        let entry = std::mem::take(&mut self.state);
+        let mut has_default = false;
+        let mut fallthrough = State::unreachable();
+
+        self.jumps.push(Jumps::default());
+        for case in cases.iter() {
+            self.state = entry.clone().join(fallthrough);
+
+            let statements = match case {
+                SwitchCase::Expression(case) => {
+                    self.expression(&case.expression);
+
+                    case.statements.as_slice()
+                }
+                SwitchCase::Default(case) => {
+                    has_default = true;
+
+                    case.statements.as_slice()
+                }
+            };
+
+            self.statements(statements);
+            fallthrough = std::mem::take(&mut self.state);
+        }
+
+        // A `continue` statement targeting a `switch` statement acts like a `break` statement.
+        let jumps = self.jumps.pop().unwrap_or_default();
+        let after = if has_default { fallthrough } else { fallthrough.join(entry) };
+
+        self.state = jumps.breaks.into_iter().chain(jumps.continues).fold(after, State::join);
+    }
+
+    fn r#try(&mut self, r#try: &Try) {
+        let before = self.state.clone();
+        self.statements(r#try.block.statements.as_slice());
+
+        // Any statement of the `try` block may throw, so its assignments are only possible in the `catch` clauses.
+        let mut after = std::mem::take(&mut self.state);
+        let thrown = before.join(after.clone().reachable());
+        for clause in r#try.catch_clauses.iter() {
+            self.state = thrown.clone();
+            if let Some(variable) = &clause.variable {
+                self.define(variable);
+            }
+
+            self.statements(clause.block.statements.as_slice());
+            after = after.join(std::mem::take(&mut self.state));
+        }
+
+        let Some(finally_clause) = &r#try.finally_clause else {
+            self.state = after;
+
+            return;
+        };
+
+        // The `finally` clause is executed whether an exception is thrown or not, and caught or not.
+        self.state = thrown.join(after.clone().reachable());
+        self.statements(finally_clause.block.statements.as_slice());
+
+        self.silent += 1;
+        self.state = after;
+        self.statements(finally_clause.block.statements.as_slice());
+        self.silent -= 1;
+    }
+}
+
+/// Returns whether the given parameter of the given internal function, lowercased, is passed by reference to be
+/// assigned.
+fn is_out_parameter(function: &str, index: usize) -> bool {
+    OUT_PARAMETERS.iter().any(|(name, parameter)| *parameter == index && *name == function)
+}
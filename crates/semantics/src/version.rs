@@ -0,0 +1,135 @@
+use mago_ast::ast::*;
+use mago_php_version::PHPVersion;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_walker::Walker;
+
+use crate::context::Context;
+
+/// Reports the features that are not supported by the targeted version of PHP.
+#[derive(Clone, Debug)]
+pub struct VersionWalker;
+
+impl Walker<Context<'_>> for VersionWalker {
+    fn walk_in_class(&self, class: &Class, context: &mut Context<'_>) {
+        if let Some(readonly) = class.modifiers.get_readonly() {
+            context.check_version("Readonly class", PHPVersion::PHP82, readonly.span());
+        }
+    }
+
+    fn walk_in_anonymous_class(&self, anonymous_class: &AnonymousClass, context: &mut Context<'_>) {
+        if let Some(readonly) = anonymous_class.modifiers.get_readonly() {
+            context.check_version("Readonly anonymous class", PHPVersion::PHP83, readonly.span());
+        }
+    }
+
+    fn walk_in_enum(&self, r#enum: &Enum, context: &mut Context<'_>) {
+        context.check_version("Enum", PHPVersion::PHP81, Span::between(r#enum.r#enum.span(), r#enum.name.span()));
+    }
+
+    fn walk_in_plain_property(&self, plain_property: &PlainProperty, context: &mut Context<'_>) {
+        if let Some(readonly) = plain_property.modifiers.get_readonly() {
+            context.check_version("Readonly property", PHPVersion::PHP81, readonly.span());
+        }
+    }
+
+    fn walk_in_function_like_parameter(&self, parameter: &FunctionLikeParameter, context: &mut Context<'_>) {
+        if let Some(readonly) = parameter.modifiers.get_readonly() {
+            context.check_version("Readonly property", PHPVersion::PHP81, readonly.span());
+        }
+    }
+
+    fn walk_in_property_hook_list(&self, property_hook_list: &PropertyHookList, context: &mut Context<'_>) {
+        context.check_version("Property hook", PHPVersion::PHP84, property_hook_list.span());
+    }
+
+    fn walk_in_modifier(&self, modifier: &Modifier, context: &mut Context<'_>) {
+        if modifier.is_write_visibility() {
+            context.check_version("Asymmetric visibility", PHPVersion::PHP84, modifier.span());
+        }
+    }
+
+    fn walk_in_class_like_constant(&self, class_like_constant: &ClassLikeConstant, context: &mut Context<'_>) {
+        if let Some(hint) = &class_like_constant.hint {
+            context.check_version("Typed class constant", PHPVersion::PHP83, hint.span());
+        }
+    }
+
+    fn walk_in_hint(&self, hint: &Hint, context: &mut Context<'_>) {
+        match hint {
+            Hint::Never(_) => context.check_version("The `never` type", PHPVersion::PHP81, hint.span()),
+            Hint::Parenthesized(_) => {
+                context.check_version("Disjunctive normal form type", PHPVersion::PHP82, hint.span());
+            }
+            _ => {}
+        }
+    }
+
+    fn walk_in_literal_integer(&self, literal_integer: &LiteralInteger, context: &mut Context<'_>) {
+        if let [b'0', b'o' | b'O', ..] = context.interner.lookup(&literal_integer.raw).as_bytes() {
+            context.check_version("Explicit octal notation", PHPVersion::PHP81, literal_integer.span());
+        }
+    }
+
+    fn walk_in_closure_creation(&self, closure_creation: &ClosureCreation, context: &mut Context<'_>) {
+        context.check_version("First-class callable syntax", PHPVersion::PHP81, closure_creation.span());
+    }
+
+    fn walk_in_named_argument(&self, named_argument: &NamedArgument, context: &mut Context<'_>) {
+        context.check_version(
+            "Named argument",
+            PHPVersion::PHP80,
+            Span::between(named_argument.name.span(), named_argument.colon),
+        );
+    }
+
+    fn walk_in_match(&self, r#match: &Match, context: &mut Context<'_>) {
+        context.check_version("Match expression", PHPVersion::PHP80, r#match.r#match.span());
+    }
+
+    fn walk_in_null_safe_property_access(&self, access: &NullSafePropertyAccess, context: &mut Context<'_>) {
+        context.check_version("Nullsafe operator", PHPVersion::PHP80, access.question_mark_arrow);
+        check_new_without_parentheses(&access.object, context);
+    }
+
+    fn walk_in_null_safe_method_call(&self, call: &NullSafeMethodCall, context: &mut Context<'_>) {
+        context.check_version("Nullsafe operator", PHPVersion::PHP80, call.question_mark_arrow);
+        check_new_without_parentheses(&call.object, context);
+    }
+
+    fn walk_in_property_access(&self, access: &PropertyAccess, context: &mut Context<'_>) {
+        check_new_without_parentheses(&access.object, context);
+    }
+
+    fn walk_in_static_property_access(&self, access: &StaticPropertyAccess, context: &mut Context<'_>) {
+        check_new_without_parentheses(&access.class, context);
+    }
+
+    fn walk_in_class_constant_access(&self, access: &ClassConstantAccess, context: &mut Context<'_>) {
+        check_new_without_parentheses(&access.class, context);
+    }
+
+    fn walk_in_array_access(&self, access: &ArrayAccess, context: &mut Context<'_>) {
+        check_new_without_parentheses(&access.array, context);
+    }
+
+    fn walk_in_function_call(&self, call: &FunctionCall, context: &mut Context<'_>) {
+        check_new_without_parentheses(&call.function, context);
+    }
+
+    fn walk_in_method_call(&self, call: &MethodCall, context: &mut Context<'_>) {
+        check_new_without_parentheses(&call.object, context);
+    }
+
+    fn walk_in_static_method_call(&self, call: &StaticMethodCall, context: &mut Context<'_>) {
+        check_new_without_parentheses(&call.class, context);
+    }
+}
+
+/// Checks the object of a member access, or of a call, which is not wrapped in parentheses if it is a `new`
+/// expression, such as in `new Foo()->bar()`.
+fn check_new_without_parentheses(object: &Expression, context: &mut Context<'_>) {
+    if let Expression::Instantiation(_) | Expression::AnonymousClass(_) = object {
+        context.check_version("Member access on `new` without parentheses", PHPVersion::PHP84, object.span());
+    }
+}
@@ -16,8 +16,28 @@ use crate::consts::CONSTRUCTOR_MAGIC_METHOD;
 use crate::consts::DEBUG_INFO_MAGIC_METHOD;
 use crate::consts::DECLARE_DIRECTIVES;
 use crate::consts::DESTRUCTOR_MAGIC_METHOD;
+use crate::consts::DUPLICATE_MEMBER_CODE;
 use crate::consts::ENCODING_DECLARE_DIRECTIVE;
+use crate::consts::FINAL_PRIVATE_METHOD_CODE;
 use crate::consts::GET_MAGIC_METHOD;
+use crate::consts::INVALID_ARGUMENT_CODE;
+use crate::consts::INVALID_ATTRIBUTE_CODE;
+use crate::consts::INVALID_CLASS_LIKE_CODE;
+use crate::consts::INVALID_CLASS_LIKE_CONSTANT_CODE;
+use crate::consts::INVALID_DECLARE_CODE;
+use crate::consts::INVALID_HALT_COMPILER_CODE;
+use crate::consts::INVALID_INHERITANCE_CODE;
+use crate::consts::INVALID_MAGIC_METHOD_CODE;
+use crate::consts::INVALID_MATCH_CODE;
+use crate::consts::INVALID_METHOD_CODE;
+use crate::consts::INVALID_NAMESPACE_CODE;
+use crate::consts::INVALID_PARAMETER_CODE;
+use crate::consts::INVALID_PROPERTY_CODE;
+use crate::consts::INVALID_RETURN_CODE;
+use crate::consts::INVALID_SWITCH_CODE;
+use crate::consts::INVALID_TRAIT_USE_CODE;
+use crate::consts::INVALID_TRY_CODE;
+use crate::consts::INVALID_TYPE_CODE;
 use crate::consts::INVOKE_MAGIC_METHOD;
 use crate::consts::ISSET_MAGIC_METHOD;
 use crate::consts::MAGIC_METHODS;
@@ -30,6 +50,7 @@ use crate::consts::SOFT_RESERVED_KEYWORDS_MINUS_SYMBOL_ALLOWED;
 use crate::consts::STRICT_TYPES_DECLARE_DIRECTIVE;
 use crate::consts::TICKS_DECLARE_DIRECTIVE;
 use crate::consts::TO_STRING_MAGIC_METHOD;
+use crate::consts::UNDEFINED_LABEL_CODE;
 use crate::consts::UNSERIALIZE_MAGIC_METHOD;
 use crate::consts::UNSET_MAGIC_METHOD;
 use crate::consts::WAKEUP_MAGIC_METHOD;
@@ -58,6 +79,7 @@ impl SemanticsWalker {
                     class_like_name,
                     extends.types.len()
                 ))
+                .with_code(INVALID_INHERITANCE_CODE)
                 .with_annotation(Annotation::primary(extends.span()).with_message("Multiple extensions found here."))
                 .with_annotation(
                     Annotation::secondary(class_like_span)
@@ -73,6 +95,7 @@ impl SemanticsWalker {
             if extended_fqcn.eq_ignore_ascii_case(class_like_fqcn) {
                 context.report(
                     Issue::error(format!("{} `{}` cannot extend itself.", class_like_kind, class_like_name))
+                        .with_code(INVALID_INHERITANCE_CODE)
                         .with_annotation(
                             Annotation::primary(extended_type.span()).with_message(format!(
                                 "{} `{}` extends itself here.",
@@ -101,6 +124,7 @@ impl SemanticsWalker {
                         "{} `{}` cannot extend reserved keyword `{}`.",
                         class_like_kind, class_like_name, extended_name
                     ))
+                    .with_code(INVALID_INHERITANCE_CODE)
                     .with_annotation(
                         Annotation::primary(extended_type.span()).with_message("Extension uses a reserved keyword."),
                     )
@@ -135,6 +159,7 @@ impl SemanticsWalker {
                 if implemented_fqcn.eq_ignore_ascii_case(class_like_fqcn) {
                     context.report(
                         Issue::error(format!("{} `{}` cannot implement itself.", class_like_kind, class_like_name))
+                            .with_code(INVALID_INHERITANCE_CODE)
                             .with_annotation(Annotation::primary(implemented_type.span()).with_message(format!(
                                 "{} `{}` implements itself here.",
                                 class_like_kind, class_like_name
@@ -162,6 +187,7 @@ impl SemanticsWalker {
                         "{} `{}` cannot implement reserved keyword `{}`.",
                         class_like_kind, class_like_name, implemented_name
                     ))
+                    .with_code(INVALID_INHERITANCE_CODE)
                     .with_annotation(
                         Annotation::primary(implemented_type.span()).with_message("This is a reserved keyword."),
                     )
@@ -208,6 +234,7 @@ impl SemanticsWalker {
                             "Property `{}::{}` cannot be declared abstract",
                             class_like_name, first_variable_name
                         ))
+                        .with_code(INVALID_PROPERTY_CODE)
                         .with_annotation(
                             Annotation::primary(modifier.span())
                                 .with_message("`abstract` modifier cannot be used on properties"),
@@ -229,6 +256,7 @@ impl SemanticsWalker {
                                 "Readonly property `{}::{}` cannot be static.",
                                 class_like_name, first_variable_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span())
                                     .with_message("`static` modifier cannot be used on readonly properties."),
@@ -253,6 +281,7 @@ impl SemanticsWalker {
                                 "Property `{}::{}` has multiple `static` modifiers.",
                                 class_like_name, first_variable_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("Duplicate `static` modifier."),
                             )
@@ -276,6 +305,7 @@ impl SemanticsWalker {
                                 "static property `{}::{}` cannot have a write visibility modifier.",
                                 class_like_name, first_variable_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("Duplicate visibility modifier."),
                             )
@@ -302,6 +332,7 @@ impl SemanticsWalker {
                                 "Static property `{}::{}` cannot be readonly.",
                                 class_like_name, first_variable_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span())
                                     .with_message("`readonly` modifier cannot be used on static properties."),
@@ -326,6 +357,7 @@ impl SemanticsWalker {
                                 "Property `{}::{}` has multiple `readonly` modifiers.",
                                 class_like_name, first_variable_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("Duplicate `readonly` modifier."),
                             )
@@ -349,6 +381,7 @@ impl SemanticsWalker {
                     if let Some(last_final) = last_final {
                         context.report(
                             Issue::error("Property has multiple `final` modifiers.")
+                                .with_code(INVALID_PROPERTY_CODE)
                                 .with_annotation(
                                     Annotation::primary(modifier.span()).with_message("Duplicate `final` modifier."),
                                 )
@@ -377,6 +410,7 @@ impl SemanticsWalker {
                                 "Property `{}::{}` has multiple visibility modifiers.",
                                 class_like_name, first_variable_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("Duplicate visibility modifier."),
                             )
@@ -396,13 +430,14 @@ impl SemanticsWalker {
 
                     last_read_visibility = Some(modifier.span());
                 }
-                Modifier::PrivateSet(_) => {
+                Modifier::PublicSet(_) | Modifier::ProtectedSet(_) | Modifier::PrivateSet(_) => {
                     if let Some(last_visibility) = last_write_visibility {
                         context.report(
                             Issue::error(format!(
                                 "Property `{}::{}` has multiple write visibility modifiers.",
                                 class_like_name, first_variable_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span())
                                     .with_message("Duplicate write visibility modifier."),
@@ -428,6 +463,7 @@ impl SemanticsWalker {
                                 "Static property `{}::{}` cannot have a write visibility modifier.",
                                 class_like_name, first_variable_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("Write visibility modifier."),
                             )
@@ -450,6 +486,63 @@ impl SemanticsWalker {
             }
         }
 
+        if let Some(write_visibility) = modifiers.get_first_write_visibility() {
+            let read_visibility = modifiers.get_first_read_visibility();
+            if visibility_level(write_visibility) < read_visibility.map_or(0, visibility_level) {
+                let write_visibility_name = write_visibility.as_str(context.interner);
+                let read_visibility_name = read_visibility.map_or("public", |m| m.as_str(context.interner));
+
+                context.report(
+                    Issue::error(format!(
+                        "Write visibility of property `{}::{}` cannot be more permissive than its read visibility.",
+                        class_like_name, first_variable_name
+                    ))
+                    .with_code(INVALID_PROPERTY_CODE)
+                    .with_annotation(
+                        Annotation::primary(write_visibility.span())
+                            .with_message(format!("Write visibility `{}` applied here.", write_visibility_name)),
+                    )
+                    .with_annotations(read_visibility.map(|read_visibility| {
+                        Annotation::secondary(read_visibility.span())
+                            .with_message(format!("Read visibility `{}` applied here.", read_visibility_name))
+                    }))
+                    .with_annotation(
+                        Annotation::secondary(first_variable.span())
+                            .with_message(format!("Property `{}` declared here.", first_variable_name)),
+                    )
+                    .with_annotation(
+                        Annotation::secondary(class_like_span)
+                            .with_message(format!("{} `{}` defined here.", class_like_kind, class_like_fqcn)),
+                    )
+                    .with_help(format!(
+                        "Use a write visibility at most as permissive as `{}`, or remove it.",
+                        read_visibility_name
+                    )),
+                );
+            }
+
+            if property.hint().is_none() {
+                context.report(
+                    Issue::error(format!(
+                        "Property `{}::{}` with asymmetric visibility must have a type hint.",
+                        class_like_name, first_variable_name
+                    ))
+                    .with_code(INVALID_PROPERTY_CODE)
+                    .with_annotation(
+                        Annotation::primary(write_visibility.span()).with_message("Write visibility modifier."),
+                    )
+                    .with_annotation(
+                        Annotation::secondary(first_variable.span())
+                            .with_message(format!("Property `{}` declared here.", first_variable_name)),
+                    )
+                    .with_annotation(
+                        Annotation::secondary(class_like_span)
+                            .with_message(format!("{} `{}` defined here.", class_like_kind, class_like_fqcn)),
+                    ),
+                );
+            }
+        }
+
         if let Some(var) = property.var() {
             if !modifiers.is_empty() {
                 let first = modifiers.first().unwrap();
@@ -460,6 +553,7 @@ impl SemanticsWalker {
                         "Var property `{}::{}` cannot have modifiers.",
                         class_like_name, first_variable_name
                     ))
+                    .with_code(INVALID_PROPERTY_CODE)
                     .with_annotation(
                         Annotation::primary(first.span().join(last.span())).with_message("Modifiers used here."),
                     )
@@ -486,6 +580,7 @@ impl SemanticsWalker {
                         "Property `{}::{}` cannot have type `{}`.",
                         class_like_name, first_variable_name, hint_name
                     ))
+                    .with_code(INVALID_PROPERTY_CODE)
                     .with_annotation(
                         Annotation::primary(hint.span())
                             .with_message(format!("Type `{}` is not allowed on properties.", hint_name)),
@@ -507,6 +602,7 @@ impl SemanticsWalker {
                     "Readonly property `{}::{}` must have a type hint.",
                     class_like_name, first_variable_name
                 ))
+                .with_code(INVALID_PROPERTY_CODE)
                 .with_annotation(Annotation::primary(readonly).with_message("Property is marked as readonly here."))
                 .with_annotation(
                     Annotation::secondary(first_variable.span())
@@ -532,6 +628,7 @@ impl SemanticsWalker {
                                     "Property `{}::{}` value contains a non-constant expression.",
                                     class_like_name, item_name
                                 ))
+                                .with_code(INVALID_PROPERTY_CODE)
                                 .with_annotation(
                                     Annotation::primary(property_concrete_item.value.span())
                                         .with_message("This is a non-constant expression."),
@@ -555,6 +652,7 @@ impl SemanticsWalker {
                                     "Readonly property `{}::{}` cannot have a default value.",
                                     class_like_name, item_name
                                 ))
+                                .with_code(INVALID_PROPERTY_CODE)
                                 .with_annotation(
                                     Annotation::primary(property_concrete_item.value.span())
                                         .with_message("This is a default value."),
@@ -588,6 +686,7 @@ impl SemanticsWalker {
                             "Hooked property `{}::{}` cannot be readonly.",
                             class_like_name, item_name
                         ))
+                        .with_code(INVALID_PROPERTY_CODE)
                         .with_annotation(Annotation::primary(readonly).with_message(format!(
                             "Property `{}::{}` is marked as readonly here.",
                             class_like_name, item_name
@@ -610,6 +709,7 @@ impl SemanticsWalker {
                 if let Some(r#static) = last_static {
                     context.report(
                         Issue::error(format!("Hooked property `{}::{}` cannot be static.", class_like_name, item_name))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(Annotation::primary(r#static).with_message(format!(
                                 "Property `{}::{}` is marked as static here.",
                                 class_like_name, item_name
@@ -643,6 +743,7 @@ impl SemanticsWalker {
                                 "Hook `{}` for property `{}::{}` cannot have modifiers.",
                                 name, class_like_name, item_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(first.span().join(last.span()))
                                     .with_message("Hook modifiers here."),
@@ -662,6 +763,7 @@ impl SemanticsWalker {
                         if let PropertyHookBody::Abstract(property_hook_abstract_body) = &hook.body {
                             context.report(
                                 Issue::error(format!("Non-abstract property hook `{}` must have a body.", name))
+                                    .with_code(INVALID_PROPERTY_CODE)
                                     .with_annotation(
                                         Annotation::primary(property_hook_abstract_body.span())
                                             .with_message("Abstract hook body here."),
@@ -690,7 +792,7 @@ impl SemanticsWalker {
                                         Issue::error(format!(
                                             "Hook `{}` of property `{}::{}` must accept exactly one parameter, found {}.",
                                             name, class_like_name, item_name, parameters.parameters.len()
-                                        ))
+                                        )).with_code(INVALID_PROPERTY_CODE)
                                         .with_annotation(Annotation::primary(parameters.span()).with_message("Parameters are defined here."))
                                         .with_annotation(
                                             Annotation::secondary(hook.name.span()).with_message(
@@ -716,6 +818,7 @@ impl SemanticsWalker {
                                                 "Parameter `{}` of hook `{}::{}::{}` must contain a type hint.",
                                                 first_parameter_name, class_like_name, item_name, name
                                             ))
+                                            .with_code(INVALID_PROPERTY_CODE)
                                             .with_annotation(
                                                 Annotation::primary(first_parameter.variable.span()).with_message(
                                                     format!("Parameter `{}` declared here.", first_parameter_name),
@@ -747,6 +850,7 @@ impl SemanticsWalker {
                                                 "Parameter `{}` of hook `{}::{}::{}` must not be variadic.",
                                                 first_parameter_name, class_like_name, item_name, name
                                             ))
+                                            .with_code(INVALID_PROPERTY_CODE)
                                             .with_annotation(Annotation::primary(ellipsis.span()).with_message(
                                                 format!(
                                                     "Parameter `{}` is marked as variadic here.",
@@ -784,6 +888,7 @@ impl SemanticsWalker {
                                                 "Parameter `{}` of hook `{}::{}::{}` must not be pass-by-reference.",
                                                 first_parameter_name, class_like_name, item_name, name
                                             ))
+                                            .with_code(INVALID_PROPERTY_CODE)
                                             .with_annotation(Annotation::primary(ampersand.span()).with_message(
                                                 format!(
                                                     "Parameter `{}` is marked as pass-by-reference here.",
@@ -821,6 +926,7 @@ impl SemanticsWalker {
                                                 "Parameter `{}` of hook `{}::{}::{}` must not have a default value.",
                                                 first_parameter_name, class_like_name, item_name, name
                                             ))
+                                            .with_code(INVALID_PROPERTY_CODE)
                                             .with_annotation(Annotation::primary(default_value.span()))
                                             .with_annotation(
                                                 Annotation::secondary(first_parameter.variable.span()).with_message(
@@ -856,6 +962,7 @@ impl SemanticsWalker {
                                         "Hook `{}` of property `{}::{}` must not have a parameters list.",
                                         name, class_like_name, item_name
                                     ))
+                                    .with_code(INVALID_PROPERTY_CODE)
                                     .with_annotation(
                                         Annotation::primary(parameters.span())
                                             .with_message("Parameters are defined here."),
@@ -883,6 +990,7 @@ impl SemanticsWalker {
                                     "Hooked property `{}::{}` contains an unknwon hook `{}`, expected `set` or `get`.",
                                     class_like_name, item_name, name
                                 ))
+                                .with_code(INVALID_PROPERTY_CODE)
                                 .with_annotation(
                                     Annotation::primary(hook.name.span())
                                         .with_message(format!("Hook `{}` declared here.", name)),
@@ -908,6 +1016,7 @@ impl SemanticsWalker {
                                 "Hook `{}` has already been defined for property `{}::{}`.",
                                 name, class_like_name, item_name
                             ))
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(hook.name.span())
                                     .with_message(format!("Duplicate hook `{}`.", name)),
@@ -959,6 +1068,7 @@ impl SemanticsWalker {
                                 "duplicate `static` modifier on method `{}::{}`",
                                 class_like_name, method_name
                             ))
+                            .with_code(INVALID_METHOD_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("duplicate `static` modifier"),
                             )
@@ -989,6 +1099,7 @@ impl SemanticsWalker {
                                 "method `{}::{}` cannot be both `final` and `abstract`",
                                 class_like_name, method_name
                             ))
+                            .with_code(INVALID_METHOD_CODE)
                             .with_annotation(Annotation::primary(modifier.span()).with_message("`final` modifier"))
                             .with_annotation(Annotation::primary(abstract_modifier).with_message("`abstract` modifier"))
                             .with_annotation(
@@ -1012,6 +1123,7 @@ impl SemanticsWalker {
                                 "duplicate `final` modifier on method `{}::{}`",
                                 class_like_name, method_name
                             ))
+                            .with_code(INVALID_METHOD_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("duplicate `final` modifier"),
                             )
@@ -1040,6 +1152,7 @@ impl SemanticsWalker {
                                 "method `{}::{}` cannot be both `final` and `abstract`",
                                 class_like_name, method_name
                             ))
+                            .with_code(INVALID_METHOD_CODE)
                             .with_annotation(Annotation::primary(modifier.span()).with_message("`abstract` modifier"))
                             .with_annotation(Annotation::primary(final_modifier).with_message("`final` modifier"))
                             .with_annotation(
@@ -1063,6 +1176,7 @@ impl SemanticsWalker {
                                 "duplicate `abstract` modifier on method `{}::{}`",
                                 class_like_name, method_name
                             ))
+                            .with_code(INVALID_METHOD_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("duplicate `abstract` modifier"),
                             )
@@ -1089,6 +1203,7 @@ impl SemanticsWalker {
                 Modifier::Readonly(_) => {
                     context.report(
                         Issue::error("`readonly` modifier is not allowed on methods".to_string())
+                            .with_code(INVALID_METHOD_CODE)
                             .with_annotation(Annotation::primary(modifier.span()).with_message("`readonly` modifier"))
                             .with_annotation(
                                 Annotation::secondary(method.span()).with_message(format!(
@@ -1111,6 +1226,7 @@ impl SemanticsWalker {
                                 "duplicate visibility modifier on method `{}::{}`",
                                 class_like_name, method_name
                             ))
+                            .with_code(INVALID_METHOD_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("duplicate visibility modifier"),
                             )
@@ -1138,11 +1254,15 @@ impl SemanticsWalker {
                         last_visibility = Some(modifier.span());
                     }
                 }
-                Modifier::PrivateSet(_) => {
+                Modifier::PublicSet(_) | Modifier::ProtectedSet(_) | Modifier::PrivateSet(_) => {
+                    let modifier_name = modifier.as_str(context.interner);
+
                     context.report(
-                        Issue::error("`private(set)` modifier is not allowed on methods".to_string())
+                        Issue::error(format!("`{}` modifier is not allowed on methods", modifier_name))
+                            .with_code(INVALID_METHOD_CODE)
                             .with_annotation(
-                                Annotation::primary(modifier.span()).with_message("`private(set)` modifier"),
+                                Annotation::primary(modifier.span())
+                                    .with_message(format!("`{}` modifier", modifier_name)),
                             )
                             .with_annotation(
                                 Annotation::secondary(method.span()).with_message(format!(
@@ -1161,6 +1281,32 @@ impl SemanticsWalker {
             }
         }
 
+        if let (Some(final_modifier), Some(Modifier::Private(private_modifier))) =
+            (last_final, method.modifiers.get_first_visibility())
+        {
+            // Private constructors may be final, to prevent child classes from declaring their own constructor.
+            if !method_name.eq_ignore_ascii_case(CONSTRUCTOR_MAGIC_METHOD) {
+                context.report(
+                    Issue::warning(format!(
+                        "private method `{}::{}` cannot be final as it is never overridden by other classes",
+                        class_like_name, method_name
+                    ))
+                    .with_code(FINAL_PRIVATE_METHOD_CODE)
+                    .with_annotation(Annotation::primary(final_modifier).with_message("`final` modifier"))
+                    .with_annotation(Annotation::secondary(private_modifier.span).with_message("`private` modifier"))
+                    .with_annotation(
+                        Annotation::secondary(method.span())
+                            .with_message(format!("method `{}::{}` defined here.", class_like_name, method_name)),
+                    )
+                    .with_annotation(
+                        Annotation::secondary(class_like_span)
+                            .with_message(format!("{} `{}` is defined here.", class_like_kind, class_like_fqcn)),
+                    )
+                    .with_help("remove the `final` modifier, or make the method protected"),
+                );
+            }
+        }
+
         for (magic_method, parameter_count, must_be_public, must_be_static, can_have_return_type) in
             MAGIC_METHOD_SEMANTICS
         {
@@ -1194,6 +1340,7 @@ impl SemanticsWalker {
 
                         context.report(
                             Issue::error(message)
+                                .with_code(INVALID_METHOD_CODE)
                                 .with_annotation(Annotation::primary(method.parameters.span()))
                                 .with_annotation(Annotation::secondary(method.span()).with_message(format!(
                                     "method `{}::{}` defined here.",
@@ -1210,6 +1357,7 @@ impl SemanticsWalker {
                 if *must_be_public && !is_public {
                     context.report(
                         Issue::error(format!("magic method `{}::{}` must be public", class_like_name, method_name))
+                            .with_code(INVALID_MAGIC_METHOD_CODE)
                             .with_annotation(
                                 Annotation::primary(last_visibility.unwrap())
                                     .with_message("non-public visibility modifier"),
@@ -1236,6 +1384,7 @@ impl SemanticsWalker {
                                 "magic method `{}::{}` cannot be static",
                                 class_like_name, method_name
                             ))
+                            .with_code(INVALID_MAGIC_METHOD_CODE)
                             .with_annotation(Annotation::primary(*span).with_message("`static` modifier"))
                             .with_annotation(
                                 Annotation::secondary(method.span()).with_message(format!(
@@ -1254,6 +1403,7 @@ impl SemanticsWalker {
                     None if *must_be_static => {
                         context.report(
                             Issue::error(format!("magic method `{}::{}` must be static", class_like_name, method_name))
+                                .with_code(INVALID_MAGIC_METHOD_CODE)
                                 .with_annotation(Annotation::primary(method.name.span()))
                                 .with_annotation(
                                     Annotation::secondary(class_like_span)
@@ -1275,6 +1425,7 @@ impl SemanticsWalker {
                                 "magic method `{}::{}` cannot have a return type hint",
                                 class_like_name, method_name
                             ))
+                            .with_code(INVALID_MAGIC_METHOD_CODE)
                             .with_annotation(Annotation::primary(hint.span()))
                             .with_annotation(
                                 Annotation::secondary(method.span()).with_message(format!(
@@ -1294,6 +1445,33 @@ impl SemanticsWalker {
             }
         }
 
+        if method_name.eq_ignore_ascii_case(TO_STRING_MAGIC_METHOD) {
+            if let Some(return_type_hint) = &method.return_type_hint {
+                if !matches!(return_type_hint.hint, Hint::String(_)) {
+                    context.report(
+                        Issue::error(format!(
+                            "magic method `{}::{}` must have a `string` return type when declared",
+                            class_like_name, method_name
+                        ))
+                        .with_code(INVALID_MAGIC_METHOD_CODE)
+                        .with_annotation(
+                            Annotation::primary(return_type_hint.hint.span())
+                                .with_message(format!("return type `{}`", context.lookup_hint(&return_type_hint.hint))),
+                        )
+                        .with_annotation(
+                            Annotation::secondary(method.span())
+                                .with_message(format!("method `{}::{}` defined here.", class_like_name, method_name)),
+                        )
+                        .with_annotation(
+                            Annotation::secondary(class_like_span)
+                                .with_message(format!("{} `{}` is defined here.", class_like_kind, class_like_fqcn)),
+                        )
+                        .with_help("change the return type hint to `string`, or remove it"),
+                    );
+                }
+            }
+        }
+
         match &method.body {
             MethodBody::Abstract(method_abstract_body) => {
                 if !class_like_is_interface && !method.modifiers.contains_abstract() {
@@ -1302,6 +1480,7 @@ impl SemanticsWalker {
                             "non-abstract method `{}::{}` must have a concrete body",
                             class_like_name, method_name,
                         ))
+                        .with_code(INVALID_METHOD_CODE)
                         .with_annotation(Annotation::primary(method_abstract_body.span()))
                         .with_annotations([
                             Annotation::secondary(class_like_span)
@@ -1319,6 +1498,7 @@ impl SemanticsWalker {
                             "method `{}::{}` is abstract and cannot have a concrete body",
                             class_like_name, method_name,
                         ))
+                        .with_code(INVALID_METHOD_CODE)
                         .with_annotation(Annotation::primary(body.span()))
                         .with_annotations([
                             Annotation::primary(abstract_modifier.span()),
@@ -1328,20 +1508,6 @@ impl SemanticsWalker {
                                 .with_message(format!("method `{}::{}` defined here.", class_like_name, method_name)),
                         ]),
                     );
-                } else if class_like_is_interface {
-                    context.report(
-                        Issue::error(format!(
-                            "interface method `{}::{}` is implicitly abstract and cannot have a concrete body",
-                            class_like_name, method_name,
-                        ))
-                        .with_annotation(Annotation::primary(body.span()))
-                        .with_annotations([
-                            Annotation::secondary(class_like_span)
-                                .with_message(format!("{} `{}` is defined here.", class_like_kind, class_like_fqcn)),
-                            Annotation::secondary(method.span())
-                                .with_message(format!("method `{}::{}` defined here.", class_like_name, method_name)),
-                        ]),
-                    );
                 }
 
                 let hint = if let Some(return_hint) = &method.return_type_hint {
@@ -1361,6 +1527,7 @@ impl SemanticsWalker {
                                         "method `{}::{}` with return type of `void` must not return a value",
                                         class_like_name, method_name,
                                     ))
+                                    .with_code(INVALID_RETURN_CODE)
                                     .with_annotation(Annotation::primary(val.span()))
                                     .with_annotations([
                                         Annotation::secondary(class_like_span).with_message(format!(
@@ -1384,6 +1551,7 @@ impl SemanticsWalker {
                                     "function `{}::{}` with return type of `never` must not return",
                                     class_like_name, method_name,
                                 ))
+                                .with_code(INVALID_RETURN_CODE)
                                 .with_annotation(Annotation::primary(r#return.span()))
                                 .with_annotations([
                                     Annotation::secondary(class_like_span).with_message(format!(
@@ -1407,6 +1575,7 @@ impl SemanticsWalker {
                                         "method `{}::{}` with return type must return a value",
                                         class_like_name, method_name,
                                     ))
+                                    .with_code(INVALID_RETURN_CODE)
                                     .with_annotation(Annotation::primary(r#return.span()))
                                     .with_annotations([
                                         Annotation::secondary(class_like_span).with_message(format!(
@@ -1466,6 +1635,7 @@ impl SemanticsWalker {
 
                                 context.report(
                                     Issue::error(message)
+                                        .with_code(DUPLICATE_MEMBER_CODE)
                                         .with_annotation(Annotation::primary(item.variable().span()))
                                         .with_annotations([
                                             Annotation::secondary(*span).with_message(format!(
@@ -1503,6 +1673,7 @@ impl SemanticsWalker {
 
                             context.report(
                                 Issue::error(message)
+                                    .with_code(DUPLICATE_MEMBER_CODE)
                                     .with_annotation(Annotation::primary(item_variable.span()))
                                     .with_annotations([
                                         Annotation::secondary(*span).with_message(format!(
@@ -1534,6 +1705,7 @@ impl SemanticsWalker {
                                 "{} method `{}::{}` has already been defined",
                                 class_like_kind, class_like_name, method_name
                             ))
+                            .with_code(DUPLICATE_MEMBER_CODE)
                             .with_annotation(Annotation::primary(method.name.span()))
                             .with_annotations([
                                 Annotation::secondary(*previous).with_message("previous definition"),
@@ -1568,6 +1740,7 @@ impl SemanticsWalker {
 
                                     context.report(
                                         Issue::error(message)
+                                            .with_code(DUPLICATE_MEMBER_CODE)
                                             .with_annotation(Annotation::primary(parameter.variable.span()))
                                             .with_annotations([
                                                 Annotation::secondary(*span).with_message(format!(
@@ -1599,6 +1772,7 @@ impl SemanticsWalker {
                                         "{} constant `{}::{}` has already been defined",
                                         class_like_kind, class_like_name, name,
                                     ))
+                                    .with_code(DUPLICATE_MEMBER_CODE)
                                     .with_annotation(Annotation::primary(item.name.span()))
                                     .with_annotations([
                                         Annotation::secondary(*span).with_message(format!(
@@ -1617,6 +1791,7 @@ impl SemanticsWalker {
                                         "{} case `{}::{}` and constant `{}::{}` cannot have the same name",
                                         class_like_kind, class_like_name, name, class_like_name, name
                                     ))
+                                    .with_code(DUPLICATE_MEMBER_CODE)
                                     .with_annotation(Annotation::primary(item.name.span()))
                                     .with_annotations([
                                         Annotation::secondary(*span).with_message(format!(
@@ -1645,6 +1820,7 @@ impl SemanticsWalker {
                                     "{} case `{}::{}` and constant `{}::{}` cannot have the same name",
                                     class_like_kind, class_like_name, name, class_like_name, name
                                 ))
+                                .with_code(DUPLICATE_MEMBER_CODE)
                                 .with_annotation(Annotation::primary(enum_case.item.name().span()))
                                 .with_annotations([
                                     Annotation::secondary(*span).with_message(format!(
@@ -1663,6 +1839,7 @@ impl SemanticsWalker {
                                     "{} case `{}::{}` has already been defined",
                                     class_like_kind, class_like_name, name,
                                 ))
+                                .with_code(DUPLICATE_MEMBER_CODE)
                                 .with_annotation(Annotation::primary(enum_case.item.name().span()))
                                 .with_annotations([
                                     Annotation::secondary(*span).with_message(format!(
@@ -1685,6 +1862,152 @@ impl SemanticsWalker {
                 _ => {}
             }
         }
+
+        for member in members.iter() {
+            if let ClassLikeMember::TraitUse(trait_use) = member {
+                self.process_trait_use(trait_use, &mut method_names, class_like_kind, class_like_fqcn, context);
+            }
+        }
+    }
+
+    /// Checks the adaptations of a trait use, such as `A::foo insteadof B;`, and `B::foo as protected bar;`.
+    ///
+    /// The names given to aliased methods are added to `method_names`, so that they are checked against the
+    /// methods of the class-like, and against each other.
+    #[inline]
+    fn process_trait_use(
+        &self,
+        trait_use: &TraitUse,
+        method_names: &mut Vec<(Span, StringIdentifier)>,
+        class_like_kind: &str,
+        class_like_fqcn: &str,
+        context: &mut Context<'_>,
+    ) {
+        let TraitUseSpecification::Concrete(specification) = &trait_use.specification else {
+            return;
+        };
+
+        let used_traits: Vec<std::string::String> = trait_use
+            .trait_names
+            .iter()
+            .map(|trait_name| context.lookup_name(&trait_name.span().start).to_ascii_lowercase())
+            .collect();
+
+        let check_trait_is_used = |trait_name: &Identifier, context: &mut Context<'_>| {
+            let trait_fqcn = context.lookup_name(&trait_name.span().start);
+            if used_traits.contains(&trait_fqcn.to_ascii_lowercase()) {
+                return;
+            }
+
+            context.report(
+                Issue::error(format!(
+                    "Trait `{}` is not used by {} `{}`.",
+                    trait_fqcn, class_like_kind, class_like_fqcn
+                ))
+                .with_code(INVALID_TRAIT_USE_CODE)
+                .with_annotation(
+                    Annotation::primary(trait_name.span()).with_message("Trait referenced in an adaptation here."),
+                )
+                .with_annotation(Annotation::secondary(trait_use.span()).with_message("Traits used here."))
+                .with_help(format!(
+                    "Add `{}` to the list of used traits, or reference one of the used traits instead.",
+                    trait_fqcn
+                )),
+            );
+        };
+
+        for adaptation in specification.adaptations.iter() {
+            match adaptation {
+                TraitUseAdaptation::Precedence(precedence) => {
+                    let reference = &precedence.method_reference;
+                    let trait_name = context.interner.lookup(&reference.trait_name.value());
+                    let method_name = context.interner.lookup(&reference.method_name.value);
+
+                    check_trait_is_used(&reference.trait_name, context);
+
+                    if precedence.trait_names.is_empty() {
+                        context.report(
+                            Issue::error(format!(
+                                "The `insteadof` adaptation of method `{}::{}` does not exclude any trait.",
+                                trait_name, method_name
+                            ))
+                            .with_code(INVALID_TRAIT_USE_CODE)
+                            .with_annotation(
+                                Annotation::primary(precedence.span()).with_message("Adaptation defined here."),
+                            )
+                            .with_help("List the traits the method is not taken from after `insteadof`."),
+                        );
+                    }
+
+                    let trait_fqcn = context.lookup_name(&reference.trait_name.span().start);
+                    for excluded_trait in precedence.trait_names.iter() {
+                        check_trait_is_used(excluded_trait, context);
+
+                        if context.lookup_name(&excluded_trait.span().start).eq_ignore_ascii_case(trait_fqcn) {
+                            context.report(
+                                Issue::error(format!(
+                                    "Method `{}::{}` is taken from trait `{}`, which is also excluded.",
+                                    trait_name, method_name, trait_fqcn
+                                ))
+                                .with_code(INVALID_TRAIT_USE_CODE)
+                                .with_annotation(
+                                    Annotation::primary(excluded_trait.span()).with_message("Trait excluded here."),
+                                )
+                                .with_annotation(
+                                    Annotation::secondary(reference.span()).with_message("Method taken from here."),
+                                )
+                                .with_help(format!("Remove `{}` from the list of excluded traits.", trait_name)),
+                            );
+                        }
+                    }
+                }
+                TraitUseAdaptation::Alias(alias) => {
+                    let method_name = match &alias.method_reference {
+                        TraitUseMethodReference::Identifier(identifier) => context.interner.lookup(&identifier.value),
+                        TraitUseMethodReference::Absolute(reference) => {
+                            check_trait_is_used(&reference.trait_name, context);
+
+                            context.interner.lookup(&reference.method_name.value)
+                        }
+                    };
+
+                    let Some(alias_name) = &alias.alias else {
+                        if alias.visibility.is_none() {
+                            context.report(
+                                Issue::error(format!(
+                                    "The `as` adaptation of method `{}` neither changes its visibility, nor its name.",
+                                    method_name
+                                ))
+                                .with_code(INVALID_TRAIT_USE_CODE)
+                                .with_annotation(
+                                    Annotation::primary(alias.span()).with_message("Adaptation defined here."),
+                                )
+                                .with_help("Add a visibility, a new name, or both, after `as`."),
+                            );
+                        }
+
+                        continue;
+                    };
+
+                    let alias_value = context.interner.lookup(&alias_name.value);
+                    let alias_lowered_id = context.interner.intern(alias_value.to_ascii_lowercase());
+
+                    if let Some((previous, _)) = method_names.iter().find(|(_, name)| alias_lowered_id.eq(name)) {
+                        context.report(
+                            Issue::error(format!(
+                                "Trait method `{}` cannot be aliased as `{}`, as {} `{}` already has a method with that name.",
+                                method_name, alias_value, class_like_kind, class_like_fqcn
+                            )).with_code(INVALID_TRAIT_USE_CODE)
+                            .with_annotation(Annotation::primary(alias_name.span()).with_message("Alias defined here."))
+                            .with_annotation(Annotation::secondary(*previous).with_message("previous definition"))
+                            .with_help("Rename the alias, or the conflicting method."),
+                        );
+                    } else {
+                        method_names.push((alias_name.span(), alias_lowered_id));
+                    }
+                }
+            }
+        }
     }
 
     #[inline]
@@ -1704,12 +2027,18 @@ impl SemanticsWalker {
         let mut last_visibility: Option<Span> = None;
         for modifier in class_like_constant.modifiers.iter() {
             match modifier {
-                Modifier::Readonly(k) | Modifier::Static(k) | Modifier::Abstract(k) | Modifier::PrivateSet(k) => {
+                Modifier::Readonly(k)
+                | Modifier::Static(k)
+                | Modifier::Abstract(k)
+                | Modifier::PublicSet(k)
+                | Modifier::ProtectedSet(k)
+                | Modifier::PrivateSet(k) => {
                     context.report(
                         Issue::error(format!(
                             "`{}` modifier is not allowed on constants",
                             context.interner.lookup(&k.value),
                         ))
+                        .with_code(INVALID_CLASS_LIKE_CONSTANT_CODE)
                         .with_annotation(Annotation::primary(modifier.span()))
                         .with_annotations([
                             Annotation::secondary(first_item.span()).with_message(format!(
@@ -1725,6 +2054,7 @@ impl SemanticsWalker {
                     if let Some(last_final) = last_final {
                         context.report(
                             Issue::error("duplicate `final` modifier on constant")
+                                .with_code(INVALID_CLASS_LIKE_CONSTANT_CODE)
                                 .with_annotation(Annotation::primary(modifier.span()))
                                 .with_annotations([
                                     Annotation::secondary(last_final).with_message("previous `final` modifier"),
@@ -1746,6 +2076,7 @@ impl SemanticsWalker {
                     if let Some(last_visibility) = last_visibility {
                         context.report(
                             Issue::error("duplicate visibility modifier on constant")
+                                .with_code(INVALID_CLASS_LIKE_CONSTANT_CODE)
                                 .with_annotation(Annotation::primary(modifier.span()))
                                 .with_annotations([
                                     Annotation::secondary(last_visibility).with_message("previous visibility modifier"),
@@ -1775,6 +2106,7 @@ impl SemanticsWalker {
                         "Constant `{}::{}` value contains a non-constant expression.",
                         class_like_name, item_name
                     ))
+                    .with_code(INVALID_CLASS_LIKE_CONSTANT_CODE)
                     .with_annotation(Annotation::primary(item.value.span()))
                     .with_annotations([
                         Annotation::secondary(item.name.span()).with_message(format!(
@@ -1787,6 +2119,31 @@ impl SemanticsWalker {
                 );
             }
         }
+
+        if let Some(hint) = class_like_constant.hint.as_ref().and_then(find_disallowed_constant_hint) {
+            let hint_name = context.lookup_hint(hint);
+
+            context.report(
+                Issue::error(format!(
+                    "Constant `{}::{}` cannot have type `{}`.",
+                    class_like_name, first_item_name, hint_name
+                ))
+                .with_code(INVALID_CLASS_LIKE_CONSTANT_CODE)
+                .with_annotation(
+                    Annotation::primary(hint.span())
+                        .with_message(format!("Type `{}` is not allowed on constants.", hint_name)),
+                )
+                .with_annotations([
+                    Annotation::secondary(first_item.span()).with_message(format!(
+                        "{} constant `{}::{}` is declared here.",
+                        class_like_kind, class_like_name, first_item_name
+                    )),
+                    Annotation::secondary(class_like_span)
+                        .with_message(format!("{} `{}` is declared here.", class_like_kind, class_like_fqcn)),
+                ])
+                .with_help("Remove the type, or use a type other than `void`, `never`, and `callable`."),
+            );
+        }
     }
 
     #[inline]
@@ -1799,6 +2156,7 @@ impl SemanticsWalker {
             if parameter.is_promoted_property() {
                 context.report(
                     Issue::error("Promoted properties are not allowed outside of constructors.")
+                        .with_code(INVALID_PROPERTY_CODE)
                         .with_annotation(
                             Annotation::primary(parameter.span()).with_message("Promoted property found here."),
                         )
@@ -1855,6 +2213,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if name.eq_ignore_ascii_case(STRICT_TYPES_DECLARE_DIRECTIVE) {
                         context.report(
                             Issue::error("Strict type declaration must be the first statement in the file.")
+                                .with_code(INVALID_DECLARE_CODE)
                                 .with_annotation(
                                     Annotation::primary(declare.span())
                                         .with_message("Strict type declaration found here."),
@@ -1895,6 +2254,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
             if let Statement::Namespace(namespace) = statement {
                 context.report(
                     Issue::error("Namespace must be the first statement in the file.")
+                        .with_code(INVALID_NAMESPACE_CODE)
                         .with_annotation(
                             Annotation::primary(namespace.span()).with_message("Namespace statement found here."),
                         )
@@ -1924,6 +2284,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if namespace.name.is_none() {
                         context.report(
                             Issue::error("Unbraced namespace must be named.")
+                                .with_code(INVALID_NAMESPACE_CODE)
                                 .with_annotation(
                                     Annotation::primary(namespace.span().join(body.terminator.span()))
                                         .with_message("Unnamed unbraced namespace."),
@@ -1941,6 +2302,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             Issue::error(
                                 "Cannot mix unbraced namespace declarations with braced namespace declarations.",
                             )
+                            .with_code(INVALID_NAMESPACE_CODE)
                             .with_annotation(
                                 Annotation::primary(namespace_span)
                                     .with_message("This is an unbraced namespace declaration."),
@@ -1965,6 +2327,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             Issue::error(
                                 "Cannot mix braced namespace declarations with unbraced namespace declarations.",
                             )
+                            .with_code(INVALID_NAMESPACE_CODE)
                             .with_annotation(
                                 Annotation::primary(namespace_span)
                                     .with_message("This is a braced namespace declaration."),
@@ -1985,17 +2348,6 @@ impl Walker<Context<'_>> for SemanticsWalker {
         }
     }
 
-    fn walk_in_short_opening_tag(&self, short_opening_tag: &ShortOpeningTag, context: &mut Context<'_>) {
-        context.report(
-            Issue::error("Short opening tag `<?` is no longer supported.")
-                .with_annotation(
-                    Annotation::primary(short_opening_tag.span()).with_message("Short opening tag used here."),
-                )
-                .with_note("Short opening tags have been removed in modern PHP versions.")
-                .with_help("Replace the short opening tag with the full opening tag `<?php`."),
-        );
-    }
-
     fn walk_in_declare(&self, declare: &Declare, context: &mut Context<'_>) {
         for item in declare.items.iter() {
             let name = context.interner.lookup(&item.name.value);
@@ -2009,7 +2361,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
 
                     if !matches!(value, Some(0) | Some(1)) {
                         context.report(
-                            Issue::error("The `strict_types` directive must be set to either `0` or `1`.")
+                            Issue::error("The `strict_types` directive must be set to either `0` or `1`.").with_code(INVALID_DECLARE_CODE)
                                 .with_annotation(
                                     Annotation::primary(item.value.span())
                                         .with_message("Invalid value assigned to the directive."),
@@ -2025,6 +2377,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
 
                         context.report(
                             Issue::error("The `strict_types` directive must be declared at the top level.")
+                                .with_code(INVALID_DECLARE_CODE)
                                 .with_annotation(
                                     Annotation::primary(declare.span()).with_message("Directive declared here."),
                                 )
@@ -2039,7 +2392,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 TICKS_DECLARE_DIRECTIVE => {
                     if !matches!(item.value, Expression::Literal(Literal::Integer(_))) {
                         context.report(
-                            Issue::error("The `ticks` directive must be set to a literal integer.")
+                            Issue::error("The `ticks` directive must be set to a literal integer.").with_code(INVALID_DECLARE_CODE)
                                 .with_annotation(
                                     Annotation::primary(item.value.span())
                                         .with_message("Invalid value assigned to the directive."),
@@ -2052,7 +2405,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 ENCODING_DECLARE_DIRECTIVE => {
                     if !matches!(item.value, Expression::Literal(Literal::String(_))) {
                         context.report(
-                            Issue::error("The `encoding` declare directive must be set to a literal integer")
+                            Issue::error("The `encoding` declare directive must be set to a literal integer").with_code(INVALID_DECLARE_CODE)
                                 .with_annotation(
                                     Annotation::primary(item.value.span())
                                         .with_message("Invalid value assigned to the directive."),
@@ -2069,6 +2422,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             name,
                             DECLARE_DIRECTIVES.join("`, `")
                         ))
+                        .with_code(INVALID_DECLARE_CODE)
                         .with_annotation(
                             Annotation::primary(item.name.span()).with_message("Unsupported directive used here."),
                         )
@@ -2089,7 +2443,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
             let parent = context.get_ancestor(context.get_ancestors_len() - 2);
 
             context.report(
-                Issue::error("Namespace declaration must be at the top level.")
+                Issue::error("Namespace declaration must be at the top level.").with_code(INVALID_NAMESPACE_CODE)
                     .with_annotation(
                         Annotation::primary(namespace.span())
                             .with_message("Namespace declared here."),
@@ -2104,42 +2458,73 @@ impl Walker<Context<'_>> for SemanticsWalker {
         }
     }
 
+    fn walk_in_halt_compiler(&self, halt_compiler: &HaltCompiler, context: &mut Context<'_>) {
+        if context.is_in_outermost_scope(halt_compiler.span()) {
+            return;
+        }
+
+        context.report(
+            Issue::error("`__halt_compiler` can only be used from the outermost scope.").with_code(INVALID_HALT_COMPILER_CODE)
+                .with_annotation(
+                    Annotation::primary(halt_compiler.halt_compiler.span())
+                        .with_message("`__halt_compiler` used here."),
+                )
+                .with_note("PHP stops compiling the file at `__halt_compiler`, so it can not be used inside of another statement, or of a namespace declared with braces.")
+                .with_help("Move the `__halt_compiler` statement to the top level of the file."),
+        );
+    }
+
     fn walk_in_hint(&self, hint: &Hint, context: &mut Context<'_>) {
+        context.push_hint(hint);
+
         match hint {
-            Hint::Parenthesized(parenthesized_hint) => {
-                if !parenthesized_hint.hint.is_parenthesizable() {
-                    let val = context.lookup_hint(&parenthesized_hint.hint);
+            Hint::Parenthesized(parenthesized_hint) if !parenthesized_hint.hint.is_parenthesizable() => {
+                let val = context.lookup_hint(&parenthesized_hint.hint);
 
-                    context.report(
-                        Issue::error(format!("Type `{}` cannot be parenthesized.", val))
-                            .with_annotation(
-                                Annotation::primary(parenthesized_hint.hint.span())
-                                    .with_message("Invalid parenthesized type."),
-                            )
-                            .with_annotation(
-                                Annotation::secondary(parenthesized_hint.span())
-                                    .with_message("Parenthesized type defined here."),
-                            )
-                            .with_note("Only union or intersection types can be enclosed in parentheses.")
-                            .with_help("Remove the parentheses around the type."),
-                    );
-                }
+                context.report(
+                    Issue::error(format!("Type `{}` cannot be parenthesized.", val))
+                        .with_code(INVALID_TYPE_CODE)
+                        .with_annotation(
+                            Annotation::primary(parenthesized_hint.hint.span())
+                                .with_message("Invalid parenthesized type."),
+                        )
+                        .with_annotation(
+                            Annotation::secondary(parenthesized_hint.span())
+                                .with_message("Parenthesized type defined here."),
+                        )
+                        .with_note("Only intersection types can be enclosed in parentheses.")
+                        .with_help("Remove the parentheses around the type."),
+                );
             }
-            Hint::Nullable(nullable_hint) => {
-                if nullable_hint.hint.is_standalone() || nullable_hint.hint.is_complex() {
-                    let val = context.lookup_hint(&nullable_hint.hint);
+            Hint::Parenthesized(parenthesized_hint) if !context.is_in_union_or_nullable_hint() => {
+                let val = context.lookup_hint(hint);
 
-                    context.report(
-                        Issue::error(format!("Type `{}` cannot be nullable.", val))
-                            .with_annotation(
-                                Annotation::primary(nullable_hint.hint.span()).with_message("Invalid nullable type."),
-                            )
-                            .with_annotation(
-                                Annotation::secondary(nullable_hint.span()).with_message("Nullable type defined here."),
-                            )
-                            .with_help("Replace the type or remove the nullable modifier."),
-                    );
-                }
+                context.report(
+                    Issue::error(format!("Type `{}` must be part of a union.", val)).with_code(INVALID_TYPE_CODE)
+                        .with_annotation(
+                            Annotation::primary(parenthesized_hint.span())
+                                .with_message("Parenthesized intersection type defined here."),
+                        )
+                        .with_note(
+                            "Parenthesized intersection types can only be used as members of a union type in disjunctive normal form, such as `(A&B)|null`.",
+                        )
+                        .with_help("Remove the parentheses around the type, or add it to a union."),
+                );
+            }
+            Hint::Nullable(nullable_hint) if nullable_hint.hint.is_standalone() || nullable_hint.hint.is_complex() => {
+                let val = context.lookup_hint(&nullable_hint.hint);
+
+                context.report(
+                    Issue::error(format!("Type `{}` cannot be nullable.", val))
+                        .with_code(INVALID_TYPE_CODE)
+                        .with_annotation(
+                            Annotation::primary(nullable_hint.hint.span()).with_message("Invalid nullable type."),
+                        )
+                        .with_annotation(
+                            Annotation::secondary(nullable_hint.span()).with_message("Nullable type defined here."),
+                        )
+                        .with_help("Replace the type or remove the nullable modifier."),
+                );
             }
             Hint::Union(union_hint) => {
                 if !union_hint.left.is_unionable() {
@@ -2147,6 +2532,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
 
                     context.report(
                         Issue::error(format!("Type `{}` cannot be part of a union.", val))
+                            .with_code(INVALID_TYPE_CODE)
                             .with_annotation(
                                 Annotation::primary(union_hint.left.span()).with_message("Invalid union type."),
                             )
@@ -2163,6 +2549,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
 
                     context.report(
                         Issue::error(format!("Type `{}` cannot be part of a union.", val))
+                            .with_code(INVALID_TYPE_CODE)
                             .with_annotation(
                                 Annotation::primary(union_hint.right.span()).with_message("Invalid union type."),
                             )
@@ -2173,6 +2560,8 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             .with_help("Replace the type or remove it from the union."),
                     );
                 }
+
+                report_duplicate_hint_members(context, &union_hint.left, &union_hint.right, true);
             }
             Hint::Intersection(intersection_hint) => {
                 if !intersection_hint.left.is_intersectable() {
@@ -2180,6 +2569,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
 
                     context.report(
                         Issue::error(format!("Type `{}` cannot be part of an intersection.", val))
+                            .with_code(INVALID_TYPE_CODE)
                             .with_annotation(
                                 Annotation::primary(intersection_hint.left.span())
                                     .with_message("Invalid intersection type."),
@@ -2188,7 +2578,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 Annotation::secondary(intersection_hint.ampersand)
                                     .with_message("Intersection operator `&` used here."),
                             )
-                            .with_note("Union and standalone types cannot be part of an intersection.")
+                            .with_note("Union, parenthesized, and standalone types cannot be part of an intersection.")
                             .with_help("Replace the type or remove it from the intersection."),
                     );
                 }
@@ -2198,6 +2588,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
 
                     context.report(
                         Issue::error(format!("Type `{}` cannot be part of an intersection.", val))
+                            .with_code(INVALID_TYPE_CODE)
                             .with_annotation(
                                 Annotation::primary(intersection_hint.right.span())
                                     .with_message("Invalid intersection type."),
@@ -2206,19 +2597,26 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 Annotation::secondary(intersection_hint.ampersand)
                                     .with_message("Intersection operator `&` used here."),
                             )
-                            .with_note("Union and standalone types cannot be part of an intersection.")
+                            .with_note("Union, parenthesized, and standalone types cannot be part of an intersection.")
                             .with_help("Replace the type or remove it from the intersection."),
                     );
                 }
+
+                report_duplicate_hint_members(context, &intersection_hint.left, &intersection_hint.right, false);
             }
             _ => {}
         }
     }
 
+    fn walk_out_hint(&self, _hint: &Hint, context: &mut Context<'_>) {
+        context.pop_hint();
+    }
+
     fn walk_in_try(&self, r#try: &Try, context: &mut Context<'_>) {
         if r#try.catch_clauses.is_empty() && r#try.finally_clause.is_none() {
             context.report(
                 Issue::error("Cannot use `try` without a `catch` or `finally` clause.")
+                    .with_code(INVALID_TRY_CODE)
                     .with_annotation(
                         Annotation::primary(r#try.span()).with_message("`try` statement without `catch` or `finally`."),
                     )
@@ -2248,6 +2646,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 if parameter.is_promoted_property() {
                     context.report(
                         Issue::error("Promoted properties are not allowed in abstract constructors.")
+                            .with_code(INVALID_PROPERTY_CODE)
                             .with_annotation(
                                 Annotation::primary(parameter.span()).with_message("Promoted property used here."),
                             )
@@ -2275,6 +2674,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
         {
             context.report(
                 Issue::error(format!("Class `{}` name cannot be a reserved keyword.", class_name))
+                    .with_code(INVALID_CLASS_LIKE_CODE)
                     .with_annotation(
                         Annotation::primary(class.name.span())
                             .with_message(format!("Class name `{}` conflicts with a reserved keyword.", class_name)),
@@ -2296,6 +2696,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 Modifier::Static(_) => {
                     context.report(
                         Issue::error(format!("Class `{}` cannot have the `static` modifier.", class_name))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(
                                 Annotation::primary(modifier.span()).with_message("`static` modifier applied here."),
                             )
@@ -2309,6 +2710,8 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 Modifier::Public(keyword)
                 | Modifier::Protected(keyword)
                 | Modifier::Private(keyword)
+                | Modifier::PublicSet(keyword)
+                | Modifier::ProtectedSet(keyword)
                 | Modifier::PrivateSet(keyword) => {
                     let visibility_name = context.interner.lookup(&keyword.value);
 
@@ -2317,6 +2720,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             "Class `{}` cannot have the `{}` visibility modifier.",
                             class_name, visibility_name
                         ))
+                        .with_code(INVALID_CLASS_LIKE_CODE)
                         .with_annotation(
                             Annotation::primary(keyword.span())
                                 .with_message(format!("`{}` modifier applied here.", visibility_name)),
@@ -2332,6 +2736,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if let Some(span) = last_abstract {
                         context.report(
                             Issue::error(format!("Abstract class `{}` cannot have the `final` modifier.", class_name))
+                                .with_code(INVALID_CLASS_LIKE_CODE)
                                 .with_annotation(
                                     Annotation::primary(keyword.span()).with_message("`final` modifier applied here."),
                                 )
@@ -2348,6 +2753,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if let Some(span) = last_final {
                         context.report(
                             Issue::error(format!("Class `{}` cannot have multiple `final` modifiers.", class_name))
+                                .with_code(INVALID_CLASS_LIKE_CODE)
                                 .with_annotation(
                                     Annotation::primary(keyword.span())
                                         .with_message("Duplicate `final` modifier applied here."),
@@ -2367,6 +2773,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if let Some(span) = last_final {
                         context.report(
                             Issue::error(format!("Final class `{}` cannot have the `abstract` modifier.", class_name))
+                                .with_code(INVALID_CLASS_LIKE_CODE)
                                 .with_annotation(
                                     Annotation::primary(keyword.span())
                                         .with_message("`abstract` modifier applied here."),
@@ -2383,6 +2790,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if let Some(span) = last_abstract {
                         context.report(
                             Issue::error(format!("Class `{}` cannot have multiple `abstract` modifiers.", class_name))
+                                .with_code(INVALID_CLASS_LIKE_CODE)
                                 .with_annotation(
                                     Annotation::primary(keyword.span())
                                         .with_message("Duplicate `abstract` modifier applied here."),
@@ -2403,6 +2811,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if let Some(span) = last_readonly {
                         context.report(
                             Issue::error(format!("Class `{}` cannot have multiple `readonly` modifiers.", class_name))
+                                .with_code(INVALID_CLASS_LIKE_CODE)
                                 .with_annotation(
                                     Annotation::primary(keyword.span())
                                         .with_message("Duplicate `readonly` modifier applied here."),
@@ -2437,6 +2846,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 ClassLikeMember::EnumCase(case) => {
                     context.report(
                         Issue::error(format!("Class `{}` cannot contain enum cases.", class_name))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(Annotation::primary(case.span()).with_message("Enum case found in class."))
                             .with_annotation(
                                 Annotation::secondary(class.span())
@@ -2454,6 +2864,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 "Class `{}` contains an abstract method `{}`, so the class must be declared abstract.",
                                 class_name, method_name
                             ))
+                            .with_code(INVALID_METHOD_CODE)
                             .with_annotation(
                                 Annotation::primary(class.name.span())
                                     .with_message("Class is missing the `abstract` modifier."),
@@ -2499,6 +2910,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
         {
             context.report(
                 Issue::error(format!("Interface `{}` name cannot be a reserved keyword.", interface_name))
+                    .with_code(INVALID_CLASS_LIKE_CODE)
                     .with_annotation(
                         Annotation::primary(interface.name.span())
                             .with_message(format!("Interface `{}` declared here.", interface_name)),
@@ -2537,6 +2949,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 ClassLikeMember::TraitUse(trait_use) => {
                     context.report(
                         Issue::error(format!("Interface `{}` cannot use traits.", interface_name))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(Annotation::primary(trait_use.span()).with_message("Trait use statement."))
                             .with_annotation(
                                 Annotation::secondary(interface.span())
@@ -2547,7 +2960,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 }
                 ClassLikeMember::EnumCase(case) => {
                     context.report(
-                        Issue::error(format!("Interface `{}` cannot contain enum cases.", interface_name))
+                        Issue::error(format!("Interface `{}` cannot contain enum cases.", interface_name)).with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(
                                 Annotation::primary(case.span())
                                     .with_message("Enum case declared here."),
@@ -2577,7 +2990,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             Issue::error(format!(
                                 "Interface method `{}::{}` cannot have `{}` modifier.",
                                 interface_name, method_name, visibility_name
-                            ))
+                            )).with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(
                                 Annotation::primary(visibility.span())
                                     .with_message(format!("`{}` modifier applied here.", visibility_name)),
@@ -2600,6 +3013,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 "Interface method `{}::{}` cannot have a body.",
                                 interface_name, method_name
                             ))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotations([
                                 Annotation::primary(body.span()).with_message("Method body declared here."),
                                 Annotation::primary(method.name.span()).with_message("Method name defined here."),
@@ -2616,7 +3030,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             Issue::error(format!(
                                 "Interface method `{}::{}` must not be abstract.",
                                 interface_name, method_name
-                            ))
+                            )).with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(
                                 Annotation::primary(abstract_modifier.span())
                                     .with_message("Abstract modifier applied here."),
@@ -2652,7 +3066,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 Issue::error(format!(
                                     "Interface `{}` cannot have non-hooked properties.",
                                     interface_name
-                                ))
+                                )).with_code(INVALID_CLASS_LIKE_CODE)
                                 .with_annotation(
                                     Annotation::primary(plain_property.span())
                                         .with_message("Non-hooked property declared here."),
@@ -2681,7 +3095,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                     non_public_read_visibilities.push(modifier);
                                 }
 
-                                if matches!(modifier, Modifier::PrivateSet(_)) {
+                                if modifier.is_write_visibility() {
                                     write_visibilities.push(modifier);
                                 }
                             }
@@ -2693,7 +3107,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                     Issue::error(format!(
                                         "Interface virtual property `{}::{}` must not specify asymmetric visibility.",
                                         interface_name, property_name,
-                                    ))
+                                    )).with_code(INVALID_CLASS_LIKE_CODE)
                                     .with_annotation(
                                         Annotation::primary(visibility.span())
                                             .with_message(format!("Asymmetric visibility modifier `{}` applied here.", visibility_name)),
@@ -2717,6 +3131,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                         "Interface virtual property `{}::{}` cannot have `{}` modifier.",
                                         interface_name, property_name, visibility_name,
                                     ))
+                                    .with_code(INVALID_CLASS_LIKE_CODE)
                                     .with_annotation(Annotation::primary(visibility.span()).with_message(format!(
                                         "Visibility modifier `{}` applied here.",
                                         visibility_name
@@ -2738,6 +3153,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                         "Interface virtual property `{}::{}` must be declared public.",
                                         interface_name, property_name
                                     ))
+                                    .with_code(INVALID_CLASS_LIKE_CODE)
                                     .with_annotation(
                                         Annotation::primary(hooked_property.span())
                                             .with_message("Property defined here."),
@@ -2755,7 +3171,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                         Issue::error(format!(
                                             "Interface virtual property `{}::{}` cannot be abstract.",
                                             interface_name, property_name
-                                        ))
+                                        )).with_code(INVALID_CLASS_LIKE_CODE)
                                         .with_annotation(
                                             Annotation::primary(abstract_modifier.span())
                                                 .with_message("Abstract modifier applied here."),
@@ -2777,7 +3193,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                     Issue::error(format!(
                                         "Interface virtual property `{}::{}` cannot have a default value.",
                                         interface_name, property_name
-                                    ))
+                                    )).with_code(INVALID_CLASS_LIKE_CODE)
                                     .with_annotation(
                                         Annotation::primary(item.equals.join(item.value.span()))
                                             .with_message("Default value assigned here."),
@@ -2803,6 +3219,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                             "Interface virtual property `{}::{}` must be abstract.",
                                             interface_name, property_name
                                         ))
+                                        .with_code(INVALID_CLASS_LIKE_CODE)
                                         .with_annotation(
                                             Annotation::primary(property_hook_concrete_body.span())
                                                 .with_message("Body defined here."),
@@ -2847,7 +3264,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             Issue::error(format!(
                                 "Interface constant cannot have `{}` visibility modifier.",
                                 visibility_name,
-                            ))
+                            )).with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(
                                 Annotation::primary(visibility.span())
                                     .with_message(format!("Visibility modifier `{}` applied here.", visibility_name)),
@@ -2886,6 +3303,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
         {
             context.report(
                 Issue::error(format!("Trait `{}` name cannot be a reserved keyword.", class_like_name))
+                    .with_code(INVALID_CLASS_LIKE_CODE)
                     .with_annotation(
                         Annotation::primary(r#trait.name.span())
                             .with_message(format!("Trait `{}` declared here.", class_like_name)),
@@ -2898,13 +3316,14 @@ impl Walker<Context<'_>> for SemanticsWalker {
             );
         }
 
-        self.process_members(&r#trait.members, r#trait.span(), class_like_name, class_like_fqcn, "trait", context);
+        self.process_members(&r#trait.members, r#trait.span(), "trait", class_like_name, class_like_fqcn, context);
 
         for member in r#trait.members.iter() {
             match &member {
                 ClassLikeMember::EnumCase(case) => {
                     context.report(
                         Issue::error(format!("Trait `{}` cannot contain enum cases.", class_like_name))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(Annotation::primary(case.span()).with_message("Enum case defined here."))
                             .with_annotation(
                                 Annotation::secondary(r#trait.span())
@@ -2963,6 +3382,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
         {
             context.report(
                 Issue::error(format!("Enum `{}` name cannot be a reserved keyword.", enum_name))
+                    .with_code(INVALID_CLASS_LIKE_CODE)
                     .with_annotation(
                         Annotation::primary(r#enum.name.span())
                             .with_message(format!("Reserved keyword used as the enum name `{}`.", enum_name)),
@@ -2984,6 +3404,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                         "Enum `{}` backing type must be either `string` or `int`, but found `{}`.",
                         enum_name, key
                     ))
+                    .with_code(INVALID_CLASS_LIKE_CODE)
                     .with_annotation(
                         Annotation::primary(hint.span())
                             .with_message(format!("Invalid backing type `{}` specified here.", key)),
@@ -3001,7 +3422,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
             self.process_implements(implements, r#enum.span(), "enum", enum_name, enum_fqcn, true, context);
         }
 
-        self.process_members(&r#enum.members, r#enum.span(), enum_name, enum_fqcn, "enum", context);
+        self.process_members(&r#enum.members, r#enum.span(), "enum", enum_name, enum_fqcn, context);
 
         for member in r#enum.members.iter() {
             match &member {
@@ -3017,6 +3438,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                         "Case `{}` of backed enum `{}` must have a value.",
                                         item_name, enum_name
                                     ))
+                                    .with_code(INVALID_CLASS_LIKE_CODE)
                                     .with_annotation(
                                         Annotation::primary(case.span())
                                             .with_message(format!("Case `{}` defined here.", item_name)),
@@ -3039,6 +3461,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                         "Case `{}` of unbacked enum `{}` must not have a value.",
                                         item_name, enum_name
                                     ))
+                                    .with_code(INVALID_CLASS_LIKE_CODE)
                                     .with_annotation(
                                         Annotation::primary(item.equals.span().join(item.value.span()))
                                             .with_message("Value assigned to the enum case."),
@@ -3072,6 +3495,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 "Enum `{}` cannot contain magic method `{}`.",
                                 enum_name, magic_method
                             ))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(
                                 Annotation::primary(method.name.span)
                                     .with_message(format!("Magic method `{}` declared here.", method_name)),
@@ -3090,6 +3514,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if let Some(abstract_modifier) = method.modifiers.get_abstract() {
                         context.report(
                             Issue::error(format!("Enum method `{}::{}` must not be abstract.", enum_name, method_name))
+                                .with_code(INVALID_CLASS_LIKE_CODE)
                                 .with_annotation(
                                     Annotation::primary(abstract_modifier.span())
                                         .with_message("Abstract modifier found here."),
@@ -3121,6 +3546,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 ClassLikeMember::Property(property) => {
                     context.report(
                         Issue::error(format!("Enum `{}` cannot have properties.", enum_name))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(
                                 Annotation::primary(property.span()).with_message("Property defined here."),
                             )
@@ -3156,6 +3582,8 @@ impl Walker<Context<'_>> for SemanticsWalker {
             match &modifier {
                 Modifier::Static(_)
                 | Modifier::Abstract(_)
+                | Modifier::PublicSet(_)
+                | Modifier::ProtectedSet(_)
                 | Modifier::PrivateSet(_)
                 | Modifier::Public(_)
                 | Modifier::Protected(_)
@@ -3167,6 +3595,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             "Anonymous class `{}` cannot have the `{}` modifier.",
                             ANONYMOUS_CLASS_NAME, modifier_name
                         ))
+                        .with_code(INVALID_CLASS_LIKE_CODE)
                         .with_annotation(
                             Annotation::primary(modifier.span())
                                 .with_message(format!("`{}` modifier applied here.", modifier_name)),
@@ -3185,6 +3614,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 "Anonymous class `{}` cannot have multiple `final` modifiers.",
                                 ANONYMOUS_CLASS_NAME
                             ))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotation(
                                 Annotation::primary(keyword.span())
                                     .with_message("Duplicate `final` modifier applied here."),
@@ -3209,6 +3639,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 "Anonymous class `{}` cannot have multiple `readonly` modifiers.",
                                 ANONYMOUS_CLASS_NAME
                             ))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotations([
                                 Annotation::primary(keyword.span)
                                     .with_message("Duplicate `readonly` modifier applied here."),
@@ -3263,6 +3694,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 ClassLikeMember::EnumCase(case) => {
                     context.report(
                         Issue::error(format!("Anonymous class `{}` cannot contain enum cases.", ANONYMOUS_CLASS_NAME))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotations([
                                 Annotation::primary(case.span()).with_message("Enum case defined here."),
                                 Annotation::secondary(anonymous_class.span())
@@ -3280,6 +3712,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 "Method `{}` in anonymous class `{}` must not be abstract.",
                                 method_name, ANONYMOUS_CLASS_NAME
                             ))
+                            .with_code(INVALID_CLASS_LIKE_CODE)
                             .with_annotations([
                                 Annotation::primary(abstract_modifier.span())
                                     .with_message("Abstract modifier applied here."),
@@ -3352,6 +3785,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 "Function `{}` with return type `void` must not return a value.",
                                 name
                             ))
+                            .with_code(INVALID_RETURN_CODE)
                             .with_annotation(Annotation::primary(val.span()).with_message("Return value found here."))
                             .with_annotation(
                                 Annotation::secondary(function.span())
@@ -3366,6 +3800,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 for r#return in returns {
                     context.report(
                         Issue::error(format!("Function `{}` with return type `never` must not return.", name))
+                            .with_code(INVALID_RETURN_CODE)
                             .with_annotation(
                                 Annotation::primary(r#return.span()).with_message("Return statement found here."),
                             )
@@ -3382,6 +3817,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if r#return.value.is_none() {
                         context.report(
                             Issue::error(format!("Function `{}` with a return type must return a value.", name))
+                                .with_code(INVALID_RETURN_CODE)
                                 .with_annotation(
                                     Annotation::primary(r#return.span())
                                         .with_message("Empty return statement found here."),
@@ -3414,6 +3850,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 if let Some(ellipsis) = ellipsis {
                     context.report(
                         Issue::error("Cannot use argument unpacking in attribute arguments.")
+                            .with_code(INVALID_ATTRIBUTE_CODE)
                             .with_annotation(
                                 Annotation::primary(ellipsis.span()).with_message("Argument unpacking used here."),
                             )
@@ -3428,6 +3865,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 if !value.is_constant(true) {
                     context.report(
                         Issue::error(format!("Attribute `{}` argument contains a non-constant expression.", name))
+                            .with_code(INVALID_ATTRIBUTE_CODE)
                             .with_annotations([
                                 Annotation::primary(value.span()).with_message("Non-constant expression used here."),
                                 Annotation::secondary(attribute.name.span())
@@ -3463,6 +3901,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
 
         let mut issue =
             Issue::error(format!("Undefined `goto` label `{}`.", going_to))
+                .with_code(UNDEFINED_LABEL_CODE)
                 .with_annotation(Annotation::primary(goto.label.span).with_message("This `goto` label is not defined."))
                 .with_annotations(suggestions.iter().map(|(name, span)| {
                     Annotation::secondary(*span).with_message(format!("Did you mean `{}`?", name))
@@ -3495,6 +3934,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                         if let Some(last_named_argument) = last_named_argument {
                             context.report(
                                 Issue::error("Cannot use argument unpacking after a named argument.")
+                                    .with_code(INVALID_ARGUMENT_CODE)
                                     .with_annotation(
                                         Annotation::primary(ellipsis.span()).with_message("Unpacking argument here."),
                                     )
@@ -3510,6 +3950,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                         if let Some(named_argument) = last_named_argument {
                             context.report(
                                 Issue::error("Cannot use positional argument after a named argument.")
+                                    .with_code(INVALID_ARGUMENT_CODE)
                                     .with_annotation(
                                         Annotation::primary(positional_argument.span())
                                             .with_message("Positional argument defined here."),
@@ -3524,6 +3965,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                         if let Some(unpacking) = last_unpacking {
                             context.report(
                                 Issue::error("Cannot use positional argument after argument unpacking.")
+                                    .with_code(INVALID_ARGUMENT_CODE)
                                     .with_annotation(
                                         Annotation::primary(positional_argument.span())
                                             .with_message("Positional argument defined here."),
@@ -3540,6 +3982,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if let Some(ellipsis) = named_argument.ellipsis {
                         context.report(
                             Issue::error("Cannot use argument unpacking in named arguments.")
+                                .with_code(INVALID_ARGUMENT_CODE)
                                 .with_annotation(
                                     Annotation::primary(ellipsis.span())
                                         .with_message("Unpacking argument defined here."),
@@ -3575,6 +4018,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if let Some(val) = &r#return.value {
                         context.report(
                             Issue::error("Closure with a return type of `void` must not return a value.")
+                                .with_code(INVALID_RETURN_CODE)
                                 .with_annotation(
                                     Annotation::primary(val.span())
                                         .with_message("This value is not allowed with a `void` return type."),
@@ -3593,6 +4037,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 for r#return in returns {
                     context.report(
                         Issue::error("Closure with a return type of `never` must not include a return statement.")
+                            .with_code(INVALID_RETURN_CODE)
                             .with_annotation(
                                 Annotation::primary(r#return.span())
                                     .with_message("Return statement is not allowed with a `never` return type."),
@@ -3611,6 +4056,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                     if r#return.value.is_none() {
                         context.report(
                             Issue::error("Closure with a return type must return a value.")
+                                .with_code(INVALID_RETURN_CODE)
                                 .with_annotation(
                                     Annotation::primary(r#return.span()).with_message("Missing return value."),
                                 )
@@ -3639,6 +4085,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
             if let Hint::Void(_) = &return_hint.hint {
                 context.report(
                     Issue::error("Arrow function cannot have a return type of `void`.")
+                        .with_code(INVALID_RETURN_CODE)
                         .with_annotation(
                             Annotation::primary(return_hint.hint.span())
                                 .with_message("Return type `void` is not valid for an arrow function."),
@@ -3668,6 +4115,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
             {
                 context.report(
                     Issue::error(format!("Parameter `{}` is already defined.", name))
+                        .with_code(INVALID_PARAMETER_CODE)
                         .with_annotation(
                             Annotation::primary(parameter.variable.span())
                                 .with_message("This parameter is redefined here."),
@@ -3693,6 +4141,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                 name,
                                 context.interner.lookup(&keyword.value)
                             ))
+                            .with_code(INVALID_PARAMETER_CODE)
                             .with_annotation(Annotation::primary(modifier.span()).with_message(format!(
                                 "Invalid `{}` modifier used here.",
                                 context.interner.lookup(&keyword.value)
@@ -3711,6 +4160,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                     "Parameter `{}` cannot have multiple `readonly` modifiers.",
                                     name
                                 ))
+                                .with_code(INVALID_PARAMETER_CODE)
                                 .with_annotation(
                                     Annotation::primary(modifier.span())
                                         .with_message("Duplicate `readonly` modifier used here."),
@@ -3731,6 +4181,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                                     "Parameter `{}` cannot have multiple visibility modifiers.",
                                     name
                                 ))
+                                .with_code(INVALID_PARAMETER_CODE)
                                 .with_annotation(
                                     Annotation::primary(modifier.span())
                                         .with_message("Duplicate visibility modifier used here."),
@@ -3744,13 +4195,14 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             last_read_visibility = Some(modifier.span());
                         }
                     }
-                    Modifier::PrivateSet(_) => {
+                    Modifier::PublicSet(_) | Modifier::ProtectedSet(_) | Modifier::PrivateSet(_) => {
                         if let Some(s) = last_write_visibility {
                             context.report(
                                 Issue::error(format!(
                                     "Parameter `{}` cannot have multiple write visibility modifiers.",
                                     name
                                 ))
+                                .with_code(INVALID_PARAMETER_CODE)
                                 .with_annotation(
                                     Annotation::primary(modifier.span())
                                         .with_message("Duplicate write visibility modifier used here."),
@@ -3768,6 +4220,56 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 }
             }
 
+            if let Some(write_visibility) = parameter.modifiers.get_first_write_visibility() {
+                let read_visibility = parameter.modifiers.get_first_read_visibility();
+                if visibility_level(write_visibility) < read_visibility.map_or(0, visibility_level) {
+                    let write_visibility_name = write_visibility.as_str(context.interner);
+                    let read_visibility_name = read_visibility.map_or("public", |m| m.as_str(context.interner));
+
+                    context.report(
+                        Issue::error(format!(
+                            "Write visibility of promoted property `{}` cannot be more permissive than its read visibility.",
+                            name
+                        )).with_code(INVALID_PARAMETER_CODE)
+                        .with_annotation(
+                            Annotation::primary(write_visibility.span())
+                                .with_message(format!("Write visibility `{}` used here.", write_visibility_name)),
+                        )
+                        .with_annotations(read_visibility.map(|read_visibility| {
+                            Annotation::secondary(read_visibility.span())
+                                .with_message(format!("Read visibility `{}` used here.", read_visibility_name))
+                        }))
+                        .with_annotation(
+                            Annotation::secondary(parameter.variable.span)
+                                .with_message(format!("Parameter `{}` defined here.", name)),
+                        )
+                        .with_help(format!(
+                            "Use a write visibility at most as permissive as `{}`, or remove it.",
+                            read_visibility_name
+                        )),
+                    );
+                }
+
+                if parameter.hint.is_none() {
+                    context.report(
+                        Issue::error(format!(
+                            "Promoted property `{}` with asymmetric visibility must have a type hint.",
+                            name
+                        ))
+                        .with_code(INVALID_PARAMETER_CODE)
+                        .with_annotation(
+                            Annotation::primary(write_visibility.span())
+                                .with_message("Write visibility modifier used here."),
+                        )
+                        .with_annotation(
+                            Annotation::secondary(parameter.variable.span)
+                                .with_message(format!("Parameter `{}` defined here.", name)),
+                        )
+                        .with_help("Add a type hint to the parameter."),
+                    );
+                }
+            }
+
             if let Some((n, s)) = last_variadic {
                 context.report(
                     Issue::error(format!(
@@ -3775,6 +4277,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                         name,
                         context.interner.lookup(&n)
                     ))
+                    .with_code(INVALID_PARAMETER_CODE)
                     .with_annotation(
                         Annotation::primary(parameter.variable.span())
                             .with_message(format!("Parameter `{}` is defined here.", name)),
@@ -3798,6 +4301,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             "Invalid parameter definition: variadic parameter `{}` cannot have a default value.",
                             name
                         ))
+                        .with_code(INVALID_PARAMETER_CODE)
                         .with_annotation(
                             Annotation::primary(default.span()).with_message(format!(
                                 "Default value is defined for variadic parameter `{}` here.",
@@ -3825,6 +4329,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                             "Invalid parameter type: bottom type `{}` cannot be used as a parameter type.",
                             hint_name
                         ))
+                        .with_code(INVALID_PARAMETER_CODE)
                         .with_annotation(
                             Annotation::primary(hint.span())
                                 .with_message(format!("Bottom type `{}` is not allowed here.", hint_name)),
@@ -3848,6 +4353,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
                 if let Some(previous) = last_default {
                     context.report(
                         Issue::error("A match expression can only have one default arm.")
+                            .with_code(INVALID_MATCH_CODE)
                             .with_annotation(
                                 Annotation::primary(default_arm.span())
                                     .with_message("This is a duplicate default arm."),
@@ -3874,7 +4380,7 @@ impl Walker<Context<'_>> for SemanticsWalker {
             if let SwitchCase::Default(default_case) = &case {
                 if let Some(previous) = last_default {
                     context.report(
-                        Issue::error("A switch statement can only have one default case.")
+                        Issue::error("A switch statement can only have one default case.").with_code(INVALID_SWITCH_CODE)
                             .with_annotation(
                                 Annotation::primary(default_case.span()).with_message("This is a duplicate default case."),
                             )
@@ -3950,3 +4456,87 @@ fn hint_contains_generator(context: &mut Context<'_>, hint: &Hint) -> bool {
         _ => false,
     }
 }
+
+/// Returns the first type within the given type of a class-like constant that constants cannot have.
+fn find_disallowed_constant_hint(hint: &Hint) -> Option<&Hint> {
+    match hint {
+        Hint::Void(_) | Hint::Never(_) | Hint::Callable(_) => Some(hint),
+        Hint::Parenthesized(parenthesized_hint) => find_disallowed_constant_hint(&parenthesized_hint.hint),
+        Hint::Nullable(nullable_hint) => find_disallowed_constant_hint(&nullable_hint.hint),
+        Hint::Union(union_hint) => {
+            find_disallowed_constant_hint(&union_hint.left).or_else(|| find_disallowed_constant_hint(&union_hint.right))
+        }
+        Hint::Intersection(intersection_hint) => find_disallowed_constant_hint(&intersection_hint.left)
+            .or_else(|| find_disallowed_constant_hint(&intersection_hint.right)),
+        _ => None,
+    }
+}
+
+/// Reports the members of the right-hand side of a union, or an intersection, type that are already members
+/// of its left-hand side, such as the second `A` in `A|B|A`.
+fn report_duplicate_hint_members(context: &mut Context<'_>, left: &Hint, right: &Hint, is_union: bool) {
+    let mut left_members = vec![];
+    collect_hint_members(left, is_union, &mut left_members);
+    let mut right_members = vec![];
+    collect_hint_members(right, is_union, &mut right_members);
+
+    for member in right_members {
+        let key = hint_member_key(context, member);
+        let Some(first) = left_members.iter().find(|first| hint_member_key(context, first) == key) else {
+            continue;
+        };
+
+        let val = context.lookup_hint(member);
+        let kind = if is_union { "union" } else { "intersection" };
+
+        context.report(
+            Issue::error(format!("Duplicate type `{}` is redundant.", val))
+                .with_code(INVALID_TYPE_CODE)
+                .with_annotation(Annotation::primary(member.span()).with_message("Duplicate type."))
+                .with_annotation(Annotation::secondary(first.span()).with_message("Type first used here."))
+                .with_note(format!("Each type can only be used once in an {} type.", kind))
+                .with_help("Remove the duplicate type."),
+        );
+    }
+}
+
+/// Collects the members of the given type, which is a member of a union, or an intersection, type.
+fn collect_hint_members<'a>(hint: &'a Hint, is_union: bool, members: &mut Vec<&'a Hint>) {
+    match hint {
+        Hint::Union(union_hint) if is_union => {
+            collect_hint_members(&union_hint.left, is_union, members);
+            collect_hint_members(&union_hint.right, is_union, members);
+        }
+        Hint::Intersection(intersection_hint) if !is_union => {
+            collect_hint_members(&intersection_hint.left, is_union, members);
+            collect_hint_members(&intersection_hint.right, is_union, members);
+        }
+        _ => members.push(hint),
+    }
+}
+
+/// Returns the key used to compare the members of union and intersection types, which ignores case,
+/// and the order of the members of parenthesized intersection types, so that `(A&B)` and `(b&a)` are equal.
+fn hint_member_key(context: &Context<'_>, hint: &Hint) -> String {
+    match hint {
+        Hint::Parenthesized(parenthesized_hint) => {
+            let mut members = vec![];
+            collect_hint_members(&parenthesized_hint.hint, false, &mut members);
+
+            let mut keys: Vec<String> = members.into_iter().map(|member| hint_member_key(context, member)).collect();
+            keys.sort();
+
+            format!("({})", keys.join("&"))
+        }
+        _ => context.lookup_hint(hint).to_lowercase(),
+    }
+}
+
+/// Returns how restrictive the given visibility modifier is, from `0` for public to `2` for private.
+fn visibility_level(modifier: &Modifier) -> u8 {
+    match modifier {
+        Modifier::Protected(_) | Modifier::ProtectedSet(_) => 1,
+        Modifier::Private(_) | Modifier::PrivateSet(_) => 2,
+        _ => 0,
+    }
+}
@@ -0,0 +1,6 @@
+<?php
+
+class Shape
+{
+    abstract public function area(): float;
+}
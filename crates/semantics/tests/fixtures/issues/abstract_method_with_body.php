@@ -0,0 +1,9 @@
+<?php
+
+abstract class Shape
+{
+    abstract public function area(): float
+    {
+        return 0.0;
+    }
+}
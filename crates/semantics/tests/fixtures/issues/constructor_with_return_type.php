@@ -0,0 +1,8 @@
+<?php
+
+class Point
+{
+    public function __construct(): void
+    {
+    }
+}
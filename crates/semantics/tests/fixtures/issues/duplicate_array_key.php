@@ -0,0 +1,19 @@
+<?php
+
+function options(string $name): array
+{
+    [$first, $first] = [$name, $name];
+
+    return [
+        'name' => $name,
+        'first' => $first,
+        'value',
+        'name' => 'overwritten',
+        '0' => 'overwritten',
+        1.5 => 'a float key',
+        true => 'overwritten',
+        $name => 'unknown',
+    ];
+}
+
+$legacy = array(null => 'a', '' => 'b', PHP_INT_SIZE => 'c', 8 => 'd');
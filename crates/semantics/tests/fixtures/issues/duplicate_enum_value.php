@@ -0,0 +1,19 @@
+<?php
+
+enum Suit: string
+{
+    case Hearts = 'H';
+    case Spades = 'S';
+    case Diamonds = 'D';
+    case Clubs = 'C';
+    case Hidden = 'H';
+}
+
+enum Priority: int
+{
+    private const BASE = 10;
+
+    case Low = self::BASE;
+    case Medium = self::BASE * 2;
+    case High = 20;
+}
@@ -0,0 +1,18 @@
+<?php
+
+final class Size
+{
+    public const SMALL = 1;
+    public const LARGE = self::SMALL * 2;
+}
+
+function describe(int $size): string
+{
+    return match ($size) {
+        Size::SMALL => 'small',
+        Size::LARGE, 3 => 'large',
+        2 => 'never',
+        4, 1 + 1 + 1 => 'huge',
+        default => 'unknown',
+    };
+}
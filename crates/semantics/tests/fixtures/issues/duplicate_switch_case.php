@@ -0,0 +1,23 @@
+<?php
+
+const DRAFT = 'draft';
+
+function label(mixed $status): string
+{
+    switch ($status) {
+        case DRAFT:
+            return 'Draft';
+        case 'published':
+            return 'Published';
+        case 'dr' . 'aft':
+            return 'Never';
+        case 1:
+        case 1.0:
+        case '1':
+            return 'One';
+        case 1:
+            return 'Never';
+        default:
+            return 'Unknown';
+    }
+}
@@ -0,0 +1,13 @@
+<?php
+
+enum Suit
+{
+    case Hearts;
+
+    public string $color;
+
+    public function __construct(string $color)
+    {
+        $this->color = $color;
+    }
+}
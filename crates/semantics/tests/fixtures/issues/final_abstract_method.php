@@ -0,0 +1,6 @@
+<?php
+
+abstract class Shape
+{
+    final abstract public function area(): float;
+}
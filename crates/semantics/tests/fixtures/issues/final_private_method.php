@@ -0,0 +1,13 @@
+<?php
+
+class Shape
+{
+    final private function __construct()
+    {
+    }
+
+    final private function compute(): float
+    {
+        return 0.0;
+    }
+}
@@ -0,0 +1,11 @@
+<?php
+
+interface Shape
+{
+    protected function area(): float;
+
+    public function perimeter(): float
+    {
+        return 0.0;
+    }
+}
@@ -0,0 +1,19 @@
+<?php
+
+function run(array $items): void
+{
+    foreach ($items as $item) {
+        break 2;
+    }
+
+    while (true) {
+        break 0;
+    }
+
+    switch ($items) {
+        case []:
+            continue;
+    }
+
+    continue;
+}
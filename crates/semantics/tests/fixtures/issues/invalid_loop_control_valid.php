@@ -0,0 +1,22 @@
+<?php
+
+function run(array $items): void
+{
+    foreach ($items as $item) {
+        switch ($item) {
+            case 1:
+                continue 2;
+            default:
+                break 2;
+        }
+    }
+
+    while (true):
+        $callback = function (array $values) {
+            foreach ($values as $value) {
+                continue;
+            }
+        };
+        break;
+    endwhile;
+}
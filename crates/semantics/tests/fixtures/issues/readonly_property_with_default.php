@@ -0,0 +1,6 @@
+<?php
+
+class Point
+{
+    public readonly int $x = 0;
+}
@@ -0,0 +1,6 @@
+<?php
+
+class Registry
+{
+    public static readonly array $items;
+}
@@ -0,0 +1,17 @@
+<?php
+
+class Name
+{
+    public function __toString(): ?string
+    {
+        return null;
+    }
+}
+
+class Title
+{
+    public function __toString(): string
+    {
+        return '';
+    }
+}
@@ -0,0 +1,10 @@
+<?php
+
+function greet(bool $formal): string
+{
+    if ($formal) {
+        $greeting = 'Good morning';
+    }
+
+    return $greeting . ', ' . $name;
+}
@@ -0,0 +1,21 @@
+<?php
+
+function total(array $items, int &$count): int
+{
+    global $tax;
+    static $calls = 0;
+
+    [$first, $second] = $items;
+    $sum = $first + $second;
+    foreach ($items as $key => $item) {
+        $sum += $key * $item;
+    }
+
+    $apply = function () use ($sum, $tax) {
+        return $sum + $tax + $_GET['extra'];
+    };
+
+    $count = ++$calls;
+
+    return $apply();
+}
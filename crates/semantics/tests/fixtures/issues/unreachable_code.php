@@ -0,0 +1,22 @@
+<?php
+
+function check(int $value): int
+{
+    if ($value > 0) {
+        return 1;
+    } else {
+        throw new Exception('negative');
+    }
+
+    $value++;
+    return $value;
+}
+
+function loop(): void
+{
+    while (true) {
+        echo 'forever';
+    }
+
+    echo 'never';
+}
@@ -0,0 +1,23 @@
+<?php
+
+function check(int $value): int
+{
+    switch ($value) {
+        case 1:
+            $value++;
+        case 2:
+            return $value;
+    }
+
+    while (true) {
+        if ($value > 10) {
+            break;
+        }
+
+        $value++;
+    }
+
+    goto end;
+    end:
+    return $value;
+}
@@ -1,9 +1,13 @@
+use std::path::PathBuf;
+
 use crate::SourceIdentifier;
 
 #[derive(Debug)]
 pub enum SourceError {
     UnavailableSource(SourceIdentifier),
     IOError(std::io::Error),
+    /// The file contains invalid UTF-8 sequences, which would be lost by writing the new content to it.
+    InvalidUtf8(PathBuf),
 }
 
 impl std::fmt::Display for SourceError {
@@ -11,6 +15,9 @@ impl std::fmt::Display for SourceError {
         match self {
             Self::UnavailableSource(source_identifier) => write!(f, "source is not available: {:?}", source_identifier),
             Self::IOError(error) => write!(f, "error loading source: {}", error),
+            Self::InvalidUtf8(path) => {
+                write!(f, "refusing to write {:?}, as its invalid utf-8 sequences would be lost", path)
+            }
         }
     }
 }
@@ -18,7 +25,7 @@ impl std::fmt::Display for SourceError {
 impl std::error::Error for SourceError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::UnavailableSource(_) => None,
+            Self::UnavailableSource(_) | Self::InvalidUtf8(_) => None,
             Self::IOError(error) => Some(error),
         }
     }
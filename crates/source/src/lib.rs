@@ -1,4 +1,6 @@
 use std::borrow::Cow;
+use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -10,8 +12,12 @@ use mago_interner::StringIdentifier;
 use mago_interner::ThreadedInterner;
 
 use crate::error::SourceError;
+use crate::line_index::ColumnEncoding;
+use crate::line_index::LineColumn;
+use crate::line_index::LineIndex;
 
 pub mod error;
+pub mod line_index;
 
 /// A unique identifier for a source, consisting of a string identifier and a user-defined flag.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
@@ -40,6 +46,11 @@ struct SourceEntry {
     path: Option<PathBuf>,
     /// The content of the source.
     content: Option<(StringIdentifier, usize, Vec<usize>)>,
+    /// The index of the lines of the content, built the first time a column is requested.
+    line_index: Option<Arc<LineIndex>>,
+    /// The end of the file from its first invalid UTF-8 sequence, as it is in the content, where the invalid
+    /// sequences are replaced, and as it is in the file, so that it can be written back as it was.
+    invalid_tail: Option<(String, Vec<u8>)>,
 }
 
 /// A manager for sources, which stores sources and provides methods to insert and retrieve them.
@@ -99,7 +110,7 @@ impl Source {
     ///
     /// The column number for the given byte offset (0-based index).
     pub fn column_number(&self, offset: usize) -> usize {
-        let line_start = self.lines.binary_search(&offset).unwrap_or_else(|next_line| self.lines[next_line - 1]);
+        let line_start = self.lines[self.line_number(offset)];
 
         offset - line_start
     }
@@ -138,7 +149,10 @@ impl SourceManager {
             return source_id;
         }
 
-        self.sources.insert(source_id, SourceEntry { name, path: Some(path), content: None });
+        self.sources.insert(
+            source_id,
+            SourceEntry { name, path: Some(path), content: None, line_index: None, invalid_tail: None },
+        );
 
         source_id
     }
@@ -164,11 +178,33 @@ impl SourceManager {
         let size = content.len();
         let content = self.interner.intern(content);
 
-        self.sources.insert(source_id, SourceEntry { name, path: None, content: Some((content, size, lines)) });
+        self.sources.insert(
+            source_id,
+            SourceEntry {
+                name,
+                path: None,
+                content: Some((content, size, lines)),
+                line_index: None,
+                invalid_tail: None,
+            },
+        );
 
         source_id
     }
 
+    /// Removes the source with the given identifier from the manager.
+    ///
+    /// # Parameters
+    ///
+    /// - `source_id`: The identifier of the source to remove.
+    ///
+    /// # Returns
+    ///
+    /// Whether the manager contained a source with the given identifier.
+    pub fn remove(&self, source_id: &SourceIdentifier) -> bool {
+        self.sources.remove(source_id).is_some()
+    }
+
     /// Checks whether the manager contains a source with the given identifier.
     ///
     /// # Parameters
@@ -221,20 +257,26 @@ impl SourceManager {
             }),
             None => {
                 let path = entry.path.clone().expect("source entry must contain either content or path");
-                let content = std::fs::read(&path)
-                    .map(|bytes| match String::from_utf8_lossy(&bytes) {
-                        Cow::Borrowed(str) => str.to_string(),
-                        Cow::Owned(string) => {
-                            tracing::warn!(
-                                "encountered invalid utf-8 sequence in file {:?}. behavior with non-utf-8 files is undefined and may lead to unexpected results.",
-                                path,
-                            );
-
-                            string
-                        }
-                    })?;
+                let bytes = std::fs::read(&path)?;
+                let (content, invalid_tail) = match String::from_utf8_lossy(&bytes) {
+                    Cow::Borrowed(str) => (str.to_string(), None),
+                    Cow::Owned(string) => {
+                        tracing::warn!(
+                            "encountered invalid utf-8 sequence in file {:?}. behavior with non-utf-8 files is undefined and may lead to unexpected results.",
+                            path,
+                        );
+
+                        // The content is the same as the file up to the first invalid sequence.
+                        let valid_up_to =
+                            std::str::from_utf8(&bytes).map_or_else(|error| error.valid_up_to(), str::len);
+                        let invalid_tail = (string[valid_up_to..].to_string(), bytes[valid_up_to..].to_vec());
+
+                        (string, Some(invalid_tail))
+                    }
+                };
 
                 let (_, v) = entry.pair_mut();
+                v.invalid_tail = invalid_tail;
 
                 let lines: Vec<_> = line_starts(&content).collect();
                 let size = content.len();
@@ -249,9 +291,69 @@ impl SourceManager {
         }
     }
 
+    /// Retrieve the line, and the column in the given encoding, of the given byte offset in a source.
+    ///
+    /// The index of the lines of the source is built the first time this method is called for it.
+    ///
+    /// # Parameters
+    ///
+    /// - `source_id`: The identifier of the source.
+    /// - `offset`: The byte offset to retrieve the line and column for.
+    /// - `encoding`: The unit in which the column is counted.
+    ///
+    /// # Returns
+    ///
+    /// The line and column of the offset (0-based), or an error if the source could not be loaded.
+    pub fn line_column(
+        &self,
+        source_id: &SourceIdentifier,
+        offset: usize,
+        encoding: ColumnEncoding,
+    ) -> Result<LineColumn, SourceError> {
+        let source = self.load(source_id)?;
+        let content = self.interner.lookup(&source.content);
+
+        let index = {
+            let mut entry = self.sources.get_mut(source_id).ok_or(SourceError::UnavailableSource(*source_id))?;
+
+            entry.line_index.get_or_insert_with(|| Arc::new(LineIndex::new(content))).clone()
+        };
+
+        Ok(index.line_column(content, offset, encoding))
+    }
+
+    /// Updates the content of the source with the given identifier, without writing it to disk.
+    ///
+    /// # Parameters
+    ///
+    /// - `source_id`: The identifier of the source to update.
+    /// - `content`: The new content of the source.
+    ///
+    /// # Returns
+    ///
+    /// An error if the source does not exist.
+    pub fn update(&self, source_id: SourceIdentifier, content: String) -> Result<(), SourceError> {
+        let mut entry = self.sources.get_mut(&source_id).ok_or(SourceError::UnavailableSource(source_id))?;
+
+        let lines = line_starts(&content).collect();
+        let size = content.len();
+        let content = self.interner.intern(content);
+
+        let (_, v) = entry.pair_mut();
+        v.content = Some((content, size, lines));
+        v.line_index = None;
+
+        Ok(())
+    }
+
     pub fn write(&self, source_id: SourceIdentifier, content: String) -> Result<(), SourceError> {
         let mut entry = self.sources.get_mut(&source_id).ok_or(SourceError::UnavailableSource(source_id))?;
 
+        let bytes = match entry.value().path.as_ref() {
+            Some(path) => Some(encode(path, entry.value().invalid_tail.as_ref(), &content)?.into_owned()),
+            None => None,
+        };
+
         // Update the content of the source entry.
         let lines = line_starts(&content).collect();
         let size = content.len();
@@ -265,8 +367,33 @@ impl SourceManager {
         }
 
         v.content = Some((content, size, lines));
-        if let Some(path) = entry.value().path.as_ref() {
-            std::fs::write(path, self.interner.lookup(&content)).map_err(SourceError::IOError)?;
+        v.line_index = None;
+        if let (Some(path), Some(bytes)) = (entry.value().path.as_ref(), bytes) {
+            write_atomically(path, &bytes).map_err(SourceError::IOError)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the current content of the source with the given identifier to its file.
+    ///
+    /// This is useful to persist content previously changed with [`SourceManager::update`]. Sources that
+    /// are not backed by a file are left untouched.
+    ///
+    /// # Parameters
+    ///
+    /// - `source_id`: The identifier of the source to save.
+    ///
+    /// # Returns
+    ///
+    /// An error if the source does not exist, or could not be written.
+    pub fn save(&self, source_id: &SourceIdentifier) -> Result<(), SourceError> {
+        let source = self.load(source_id)?;
+        if let Some(path) = source.path.as_ref() {
+            let invalid_tail = self.sources.get(source_id).and_then(|entry| entry.invalid_tail.clone());
+            let bytes = encode(path, invalid_tail.as_ref(), self.interner.lookup(&source.content))?;
+
+            write_atomically(path, &bytes).map_err(SourceError::IOError)?;
         }
 
         Ok(())
@@ -292,6 +419,137 @@ impl<T: HasSource> HasSource for Box<T> {
     }
 }
 
+/// Returns the bytes to write to the file at the given path for the given content.
+///
+/// The invalid UTF-8 sequences of a file, such as in the binary data following `__halt_compiler();`, are replaced
+/// when loading it. The original bytes are restored if the content still ends with the replaced tail of the file,
+/// otherwise the file is not written, as its invalid sequences would be lost.
+fn encode<'c>(
+    path: &Path,
+    invalid_tail: Option<&(String, Vec<u8>)>,
+    content: &'c str,
+) -> Result<Cow<'c, [u8]>, SourceError> {
+    let Some((replaced, original)) = invalid_tail else {
+        return Ok(Cow::Borrowed(content.as_bytes()));
+    };
+
+    match content.strip_suffix(replaced.as_str()) {
+        Some(prefix) => Ok(Cow::Owned([prefix.as_bytes(), original].concat())),
+        None => Err(SourceError::InvalidUtf8(path.to_path_buf())),
+    }
+}
+
+/// Replaces the content of the file at the given path, without ever leaving it partially written.
+///
+/// The content is written to a temporary file in the same directory, which is then renamed over the
+/// original file. The permissions, and on Unix the ownership, of the original file are preserved when
+/// possible. Symbolic links are resolved, so that their target is replaced instead of the link itself.
+fn write_atomically(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let path = std::fs::canonicalize(path)?;
+    let metadata = std::fs::metadata(&path)?;
+
+    let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    let temporary = path.with_file_name(format!(".{}.{}.mago.tmp", file_name, std::process::id()));
+
+    let result = (|| {
+        let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(&temporary)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        drop(file);
+
+        std::fs::set_permissions(&temporary, metadata.permissions())?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            // Changing the owner requires privileges the current user may not have, the file then belongs to them.
+            if let Err(error) = std::os::unix::fs::chown(&temporary, Some(metadata.uid()), Some(metadata.gid())) {
+                tracing::debug!("could not preserve the ownership of {:?}: {}", path, error);
+            }
+        }
+
+        std::fs::rename(&temporary, &path)
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temporary);
+    }
+
+    result
+}
+
 fn line_starts(source: &str) -> impl '_ + Iterator<Item = usize> {
     std::iter::once(0).chain(source.match_indices('\n').map(|(i, _)| i + 1))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temporary_file(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("mago-source-{}-{}.php", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn test_invalid_utf8_data_is_written_back_as_is() {
+        let path = temporary_file("halt", b"<?php  foo();\n__halt_compiler();\x00\xff\xfe data \xc3");
+
+        let manager = SourceManager::new(ThreadedInterner::new());
+        let source_id = manager.insert_path("halt.php".to_string(), path.clone(), true);
+        let source = manager.load(&source_id).unwrap();
+        let content = manager.interner.lookup(&source.content).replacen("  ", " ", 1);
+
+        manager.write(source_id, content).unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"<?php foo();\n__halt_compiler();\x00\xff\xfe data \xc3");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_utf8_sequences_are_not_lost() {
+        let path = temporary_file("lost", b"<?php echo '\xff'; foo();");
+
+        let manager = SourceManager::new(ThreadedInterner::new());
+        let source_id = manager.insert_path("lost.php".to_string(), path.clone(), true);
+        let source = manager.load(&source_id).unwrap();
+        let content = manager.interner.lookup(&source.content).replace("foo", "bar");
+
+        assert!(matches!(manager.write(source_id, content), Err(SourceError::InvalidUtf8(_))));
+        assert_eq!(std::fs::read(&path).unwrap(), b"<?php echo '\xff'; foo();");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_files_are_replaced_atomically_through_symbolic_links() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = temporary_file("atomic", b"<?php echo 1;");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+        let link = path.with_extension("link.php");
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&path, &link).unwrap();
+
+        let manager = SourceManager::new(ThreadedInterner::new());
+        let source_id = manager.insert_path("atomic.php".to_string(), link.clone(), true);
+        manager.write(source_id, "<?php echo 2;".to_string()).unwrap();
+
+        assert!(std::fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read(&path).unwrap(), b"<?php echo 2;");
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o640);
+
+        let directory = std::fs::read_dir(path.parent().unwrap()).unwrap();
+        let prefix = format!(".{}.", path.file_name().unwrap().to_string_lossy());
+        assert!(
+            directory.filter_map(Result::ok).all(|entry| !entry.file_name().to_string_lossy().starts_with(&prefix)),
+            "the temporary file was not removed"
+        );
+
+        std::fs::remove_file(&link).unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,147 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The unit in which the columns of positions are counted.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ColumnEncoding {
+    /// Columns are counted in UTF-8 bytes, as the offsets of positions are.
+    Utf8Bytes,
+    /// Columns are counted in Unicode code points, as characters are displayed in terminals.
+    Utf8CodePoints,
+    /// Columns are counted in UTF-16 code units, as expected by the language server protocol.
+    Utf16CodeUnits,
+}
+
+/// A line and a column in a source, both 0-based.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// An index of the lines of a source, used to compute the line and column of byte offsets.
+///
+/// The columns of ASCII-only lines are the same in every encoding, so they are computed without
+/// looking at the content of the line.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    /// The byte offset of the start of each line.
+    starts: Vec<usize>,
+    /// Whether each line only contains ASCII characters.
+    ascii: Vec<bool>,
+    /// The length of the byte order mark at the start of the source, which is not counted in columns.
+    bom: usize,
+}
+
+impl LineIndex {
+    /// Creates an index of the lines of the given content.
+    pub fn new(content: &str) -> Self {
+        let starts: Vec<usize> = std::iter::once(0).chain(content.match_indices('\n').map(|(i, _)| i + 1)).collect();
+
+        let ascii = starts
+            .iter()
+            .enumerate()
+            .map(|(line, &start)| {
+                let end = starts.get(line + 1).copied().unwrap_or(content.len());
+
+                content.as_bytes()[start..end].is_ascii()
+            })
+            .collect();
+
+        let bom = if content.starts_with('\u{feff}') { '\u{feff}'.len_utf8() } else { 0 };
+
+        Self { starts, ascii, bom }
+    }
+
+    /// Returns the line, and the column in the given encoding, of the given byte offset.
+    ///
+    /// An offset in the middle of a multi-byte character is counted as the start of that character, and an
+    /// offset past the end of the content as the end of the content.
+    pub fn line_column(&self, content: &str, offset: usize, encoding: ColumnEncoding) -> LineColumn {
+        let offset = offset.min(content.len());
+        let line = self.starts.partition_point(|&start| start <= offset) - 1;
+        if line == 0 && offset <= self.bom {
+            return LineColumn { line, column: 0 };
+        }
+
+        let start = if line == 0 { self.bom } else { self.starts[line] };
+
+        let column = if encoding == ColumnEncoding::Utf8Bytes || self.ascii[line] {
+            offset - start
+        } else {
+            let end = self.starts.get(line + 1).copied().unwrap_or(content.len());
+
+            content[start..end]
+                .char_indices()
+                .take_while(|(index, character)| start + index + character.len_utf8() <= offset)
+                .map(|(_, character)| match encoding {
+                    ColumnEncoding::Utf16CodeUnits => character.len_utf16(),
+                    _ => 1,
+                })
+                .sum()
+        };
+
+        LineColumn { line, column }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(content: &str, offset: usize) -> (usize, [usize; 3]) {
+        let index = LineIndex::new(content);
+        let bytes = index.line_column(content, offset, ColumnEncoding::Utf8Bytes);
+        let code_points = index.line_column(content, offset, ColumnEncoding::Utf8CodePoints);
+        let code_units = index.line_column(content, offset, ColumnEncoding::Utf16CodeUnits);
+
+        assert_eq!(bytes.line, code_points.line);
+        assert_eq!(bytes.line, code_units.line);
+
+        (bytes.line, [bytes.column, code_points.column, code_units.column])
+    }
+
+    #[test]
+    fn test_ascii_columns() {
+        let content = "<?php\n\necho 1;\n";
+
+        assert_eq!(columns(content, 0), (0, [0, 0, 0]));
+        assert_eq!(columns(content, 6), (1, [0, 0, 0]));
+        assert_eq!(columns(content, 12), (2, [5, 5, 5]));
+    }
+
+    #[test]
+    fn test_multi_byte_columns() {
+        // `é` is 2 bytes, `世` is 3 bytes, and `😀` is 4 bytes, or 2 UTF-16 code units.
+        let content = "<?php\n$é = '世界😀'; $a = 1;\n";
+        let offset = content.find("$a").unwrap();
+
+        assert_eq!(columns(content, offset), (1, [20, 12, 13]));
+    }
+
+    #[test]
+    fn test_offset_inside_of_a_character() {
+        let content = "'世'";
+
+        assert_eq!(columns(content, 2), (0, [2, 1, 1]));
+        assert_eq!(columns(content, 4), (0, [4, 2, 2]));
+    }
+
+    #[test]
+    fn test_byte_order_mark_is_not_counted() {
+        let content = "\u{feff}<?php echo 1;\necho 2;";
+
+        assert_eq!(columns(content, 0), (0, [0, 0, 0]));
+        assert_eq!(columns(content, 1), (0, [0, 0, 0]));
+        assert_eq!(columns(content, 9), (0, [6, 6, 6]));
+        assert_eq!(columns(content, content.find("echo 2").unwrap()), (1, [0, 0, 0]));
+    }
+
+    #[test]
+    fn test_final_line_without_newline() {
+        let content = "<?php\necho '世';";
+
+        assert_eq!(columns(content, content.len()), (1, [11, 9, 9]));
+        assert_eq!(columns(content, content.len() + 10), (1, [11, 9, 9]));
+    }
+}
@@ -222,7 +222,9 @@ pub enum TokenKind {
     Private,                     // `private`
     PrivateSet,                  // `private(set)`
     Protected,                   // `protected`
+    ProtectedSet,                // `protected(set)`
     Public,                      // `public`
+    PublicSet,                   // `public(set)`
     QualifiedIdentifier,         // `Namespace\Class`
     Question,                    // `?`
     QuestionColon,               // `?:`
@@ -399,7 +401,9 @@ impl TokenKind {
                 | TokenKind::Private
                 | TokenKind::PrivateSet
                 | TokenKind::Protected
+                | TokenKind::ProtectedSet
                 | TokenKind::Public
+                | TokenKind::PublicSet
                 | TokenKind::RequireOnce
                 | TokenKind::Require
                 | TokenKind::Return
@@ -488,14 +492,23 @@ impl TokenKind {
 
     #[inline(always)]
     pub fn is_visibility_modifier(&self) -> bool {
-        matches!(self, T!["public" | "protected" | "private" | "private(set)"])
+        matches!(self, T!["public" | "protected" | "private" | "public(set)" | "protected(set)" | "private(set)"])
     }
 
     #[inline(always)]
     pub fn is_modifier(&self) -> bool {
         matches!(
             self,
-            T!["public" | "protected" | "private" | "private(set)" | "static" | "final" | "abstract" | "readonly"]
+            T!["public"
+                | "protected"
+                | "private"
+                | "public(set)"
+                | "protected(set)"
+                | "private(set)"
+                | "static"
+                | "final"
+                | "abstract"
+                | "readonly"]
         )
     }
 
@@ -537,7 +550,9 @@ impl TokenKind {
                 | "private"
                 | "private(set)"
                 | "protected"
+                | "protected(set)"
                 | "public"
+                | "public(set)"
                 | "include"
                 | "include_once"
                 | "eval"
@@ -689,6 +704,201 @@ impl TokenKind {
                 | "die"]
         )
     }
+
+    /// Returns how the token kind is referred to in messages, such as `` `;` `` for a semicolon, or `variable`.
+    pub fn description(&self) -> &'static str {
+        match self {
+            TokenKind::Whitespace => "whitespace",
+            TokenKind::Eval => "`eval`",
+            TokenKind::Die => "`die`",
+            TokenKind::Self_ => "`self`",
+            TokenKind::Parent => "`parent`",
+            TokenKind::Backtick => "`` ` ``",
+            TokenKind::DocumentStart(_) => "start of a heredoc, or nowdoc, string",
+            TokenKind::DocumentEnd => "end of a heredoc, or nowdoc, string",
+            TokenKind::From => "`from`",
+            TokenKind::Print => "`print`",
+            TokenKind::Dollar => "`$`",
+            TokenKind::HaltCompiler => "`__halt_compiler`",
+            TokenKind::Readonly => "`readonly`",
+            TokenKind::Global => "`global`",
+            TokenKind::Abstract => "`abstract`",
+            TokenKind::Ampersand => "`&`",
+            TokenKind::AmpersandEqual => "`&=`",
+            TokenKind::AmpersandAmpersand => "`&&`",
+            TokenKind::AmpersandAmpersandEqual => "`&&=`",
+            TokenKind::Array => "`array`",
+            TokenKind::ArrayCast => "`(array)`",
+            TokenKind::MinusGreaterThan => "`->`",
+            TokenKind::QuestionMinusGreaterThan => "`?->`",
+            TokenKind::At => "`@`",
+            TokenKind::As => "`as`",
+            TokenKind::Asterisk => "`*`",
+            TokenKind::HashLeftBracket => "`#[`",
+            TokenKind::Bang => "`!`",
+            TokenKind::BangEqual => "`!=`",
+            TokenKind::LessThanGreaterThan => "`<>`",
+            TokenKind::BangEqualEqual => "`!==`",
+            TokenKind::LessThanEqualGreaterThan => "`<=>`",
+            TokenKind::BoolCast => "`(bool)`",
+            TokenKind::BooleanCast => "`(boolean)`",
+            TokenKind::And => "`and`",
+            TokenKind::Or => "`or`",
+            TokenKind::Break => "`break`",
+            TokenKind::Callable => "`callable`",
+            TokenKind::Caret => "`^`",
+            TokenKind::CaretEqual => "`^=`",
+            TokenKind::Case => "`case`",
+            TokenKind::Catch => "`catch`",
+            TokenKind::Class => "`class`",
+            TokenKind::ClassConstant => "`__CLASS__`",
+            TokenKind::TraitConstant => "`__TRAIT__`",
+            TokenKind::FunctionConstant => "`__FUNCTION__`",
+            TokenKind::MethodConstant => "`__METHOD__`",
+            TokenKind::LineConstant => "`__LINE__`",
+            TokenKind::FileConstant => "`__FILE__`",
+            TokenKind::Clone => "`clone`",
+            TokenKind::MinusEqual => "`-=`",
+            TokenKind::CloseTag => "`?>`",
+            TokenKind::QuestionQuestion => "`??`",
+            TokenKind::QuestionQuestionEqual => "`??=`",
+            TokenKind::AsteriskEqual => "`*=`",
+            TokenKind::Colon => "`:`",
+            TokenKind::Comma => "`,`",
+            TokenKind::SingleLineComment => "comment",
+            TokenKind::HashComment => "comment",
+            TokenKind::MultiLineComment => "comment",
+            TokenKind::DocBlockComment => "docblock",
+            TokenKind::Const => "`const`",
+            TokenKind::PartialLiteralString => "unclosed string literal",
+            TokenKind::LiteralString => "string literal",
+            TokenKind::Continue => "`continue`",
+            TokenKind::Declare => "`declare`",
+            TokenKind::MinusMinus => "`--`",
+            TokenKind::Default => "`default`",
+            TokenKind::DirConstant => "`__DIR__`",
+            TokenKind::SlashEqual => "`/=`",
+            TokenKind::Do => "`do`",
+            TokenKind::DollarLeftBrace => "`${`",
+            TokenKind::Dot => "`.`",
+            TokenKind::DotEqual => "`.=`",
+            TokenKind::EqualGreaterThan => "`=>`",
+            TokenKind::DoubleCast => "`(double)`",
+            TokenKind::RealCast => "`(real)`",
+            TokenKind::FloatCast => "`(float)`",
+            TokenKind::ColonColon => "`::`",
+            TokenKind::EqualEqual => "`==`",
+            TokenKind::DoubleQuote => "`\"`",
+            TokenKind::Else => "`else`",
+            TokenKind::Echo => "`echo`",
+            TokenKind::DotDotDot => "`...`",
+            TokenKind::ElseIf => "`elseif`",
+            TokenKind::Empty => "`empty`",
+            TokenKind::EndDeclare => "`enddeclare`",
+            TokenKind::EndFor => "`endfor`",
+            TokenKind::EndForeach => "`endforeach`",
+            TokenKind::EndIf => "`endif`",
+            TokenKind::EndSwitch => "`endswitch`",
+            TokenKind::EndWhile => "`endwhile`",
+            TokenKind::Enum => "`enum`",
+            TokenKind::Equal => "`=`",
+            TokenKind::Extends => "`extends`",
+            TokenKind::False => "`false`",
+            TokenKind::Final => "`final`",
+            TokenKind::Finally => "`finally`",
+            TokenKind::LiteralFloat => "float literal",
+            TokenKind::Fn => "`fn`",
+            TokenKind::For => "`for`",
+            TokenKind::Foreach => "`foreach`",
+            TokenKind::FullyQualifiedIdentifier => "fully qualified identifier",
+            TokenKind::Function => "`function`",
+            TokenKind::Goto => "`goto`",
+            TokenKind::GreaterThan => "`>`",
+            TokenKind::GreaterThanEqual => "`>=`",
+            TokenKind::Identifier => "identifier",
+            TokenKind::If => "`if`",
+            TokenKind::Implements => "`implements`",
+            TokenKind::Include => "`include`",
+            TokenKind::IncludeOnce => "`include_once`",
+            TokenKind::PlusPlus => "`++`",
+            TokenKind::InlineText => "inline text",
+            TokenKind::InlineShebang => "shebang",
+            TokenKind::Instanceof => "`instanceof`",
+            TokenKind::Insteadof => "`insteadof`",
+            TokenKind::Exit => "`exit`",
+            TokenKind::Unset => "`unset`",
+            TokenKind::Isset => "`isset`",
+            TokenKind::List => "`list`",
+            TokenKind::LiteralInteger => "integer literal",
+            TokenKind::IntCast => "`(int)`",
+            TokenKind::IntegerCast => "`(integer)`",
+            TokenKind::Interface => "`interface`",
+            TokenKind::LeftBrace => "`{`",
+            TokenKind::LeftBracket => "`[`",
+            TokenKind::LeftParenthesis => "`(`",
+            TokenKind::LeftShift => "`<<`",
+            TokenKind::LeftShiftEqual => "`<<=`",
+            TokenKind::RightShift => "`>>`",
+            TokenKind::RightShiftEqual => "`>>=`",
+            TokenKind::LessThan => "`<`",
+            TokenKind::LessThanEqual => "`<=`",
+            TokenKind::Match => "`match`",
+            TokenKind::Minus => "`-`",
+            TokenKind::Namespace => "`namespace`",
+            TokenKind::NamespaceSeparator => "`\\`",
+            TokenKind::NamespaceConstant => "`__NAMESPACE__`",
+            TokenKind::New => "`new`",
+            TokenKind::Null => "`null`",
+            TokenKind::ObjectCast => "`(object)`",
+            TokenKind::UnsetCast => "`(unset)`",
+            TokenKind::OpenTag => "`<?php`",
+            TokenKind::EchoTag => "`<?=`",
+            TokenKind::ShortOpenTag => "`<?`",
+            TokenKind::Percent => "`%`",
+            TokenKind::PercentEqual => "`%=`",
+            TokenKind::Pipe => "`|`",
+            TokenKind::PipeEqual => "`|=`",
+            TokenKind::Plus => "`+`",
+            TokenKind::PlusEqual => "`+=`",
+            TokenKind::AsteriskAsterisk => "`**`",
+            TokenKind::AsteriskAsteriskEqual => "`**=`",
+            TokenKind::Private => "`private`",
+            TokenKind::PrivateSet => "`private(set)`",
+            TokenKind::Protected => "`protected`",
+            TokenKind::ProtectedSet => "`protected(set)`",
+            TokenKind::Public => "`public`",
+            TokenKind::PublicSet => "`public(set)`",
+            TokenKind::QualifiedIdentifier => "qualified identifier",
+            TokenKind::Question => "`?`",
+            TokenKind::QuestionColon => "`?:`",
+            TokenKind::Require => "`require`",
+            TokenKind::RequireOnce => "`require_once`",
+            TokenKind::Return => "`return`",
+            TokenKind::RightBrace => "`}`",
+            TokenKind::RightBracket => "`]`",
+            TokenKind::RightParenthesis => "`)`",
+            TokenKind::Semicolon => "`;`",
+            TokenKind::Slash => "`/`",
+            TokenKind::Static => "`static`",
+            TokenKind::StringCast => "`(string)`",
+            TokenKind::BinaryCast => "`(binary)`",
+            TokenKind::StringPart => "string part",
+            TokenKind::Switch => "`switch`",
+            TokenKind::Throw => "`throw`",
+            TokenKind::Trait => "`trait`",
+            TokenKind::EqualEqualEqual => "`===`",
+            TokenKind::True => "`true`",
+            TokenKind::Try => "`try`",
+            TokenKind::Use => "`use`",
+            TokenKind::Var => "`var`",
+            TokenKind::Variable => "variable",
+            TokenKind::Yield => "`yield`",
+            TokenKind::While => "`while`",
+            TokenKind::Tilde => "`~`",
+            TokenKind::PipePipe => "`||`",
+            TokenKind::Xor => "`xor`",
+        }
+    }
 }
 
 impl Token {
@@ -1155,9 +1365,15 @@ macro_rules! T {
     ("private") => {
         $crate::TokenKind::Private
     };
+    ("protected(set)") => {
+        $crate::TokenKind::ProtectedSet
+    };
     ("protected") => {
         $crate::TokenKind::Protected
     };
+    ("public(set)") => {
+        $crate::TokenKind::PublicSet
+    };
     ("public") => {
         $crate::TokenKind::Public
     };
@@ -1,4 +1,5 @@
 use mago_ast::Expression;
+use mago_interner::StringIdentifier;
 use mago_interner::ThreadedInterner;
 use mago_names::Names;
 use mago_reflection::r#type::kind::TypeKind;
@@ -38,6 +39,11 @@ pub fn infere<'ast>(
     TypeReflection { kind, inferred: true, span: expression.span() }
 }
 
+/// Returns the type kind of the given literal string, without its quotes, such as `foo` for `'foo'`.
+pub fn literal_string_kind(interner: &ThreadedInterner, value: StringIdentifier) -> TypeKind {
+    internal::get_literal_string_value_kind(interner, value, false)
+}
+
 /// Infers the general type kind of an expression without using a codebase for context.
 ///
 /// # Arguments
@@ -8,7 +8,10 @@ use mago_ast::Program;
 use mago_formatter::settings::FormatSettings;
 use mago_interner::StringIdentifier;
 use mago_interner::ThreadedInterner;
+use mago_names::ResolutionKind;
 use mago_parser::parse_source;
+use mago_parser::settings::ParserSettings;
+use mago_php_version::PHPVersion;
 use mago_reporting::Issue;
 use mago_reporting::IssueCollection;
 use mago_semantics::Semantics;
@@ -37,14 +40,14 @@ struct CodeInsight<'a> {
     /// The abstract syntax tree (AST) resulting from parsing the source code.
     pub program: Program,
 
-    /// An optional parse error, if one occurred during parsing.
-    pub parse_error: Option<Issue>,
+    /// The parse errors encountered during parsing, in the order they were encountered.
+    pub parse_errors: Vec<Issue>,
 
     /// The resolved names within the source code, used for identifier resolution.
     ///
     /// Each resolved name is represented as a tuple containing a byte offset and
-    /// a tuple containing a `StringIdentifier` and a boolean flag indicating whether the name was imported.
-    pub names: HashSet<(&'a usize, &'a (StringIdentifier, bool))>,
+    /// a tuple containing a `StringIdentifier` and the `ResolutionKind` describing how the name was resolved.
+    pub names: HashSet<(&'a usize, &'a (StringIdentifier, ResolutionKind))>,
 
     /// The symbol table containing definitions of classes, functions, constants, etc.
     pub symbols: SymbolTable,
@@ -127,9 +130,9 @@ pub fn mago_format(code: String, settings: Option<String>) -> Result<JsValue, Js
     let source_id = manager.insert_content("code.php".to_string(), code, true);
 
     let source = manager.load(&source_id).map_err(|e| JsValue::from_str(&e.to_string()))?;
-    let (program, parse_error) = parse_source(&interner, &source);
+    let (program, parse_errors) = parse_source(&interner, &source);
 
-    if let Some(err) = parse_error {
+    if let Some(err) = parse_errors.first() {
         return Err(JsValue::from_str(&err.to_string()));
     }
 
@@ -209,9 +212,9 @@ pub fn mago_get_insight(code: String, format_settings: Option<String>) -> Result
     let manager = SourceManager::new(interner.clone());
     let source_id = manager.insert_content("code.php".to_string(), code, true);
     let source = manager.load(&source_id).map_err(|e| JsValue::from_str(&e.to_string()))?;
-    let semantics = Semantics::build(&interner, source);
+    let semantics = Semantics::build(&interner, PHPVersion::LATEST, ParserSettings::default(), source);
     let mut formatted = None;
-    if semantics.parse_error.is_none() {
+    if !semantics.has_parse_error() {
         formatted = Some(mago_formatter::format(settings, &interner, &semantics.source, &semantics.program));
     }
 
@@ -220,7 +223,7 @@ pub fn mago_get_insight(code: String, format_settings: Option<String>) -> Result
     Ok(serde_wasm_bindgen::to_value(&CodeInsight {
         strings: interner.all(),
         program: semantics.program,
-        parse_error: semantics.parse_error.as_ref().map(|e| e.into()),
+        parse_errors: semantics.parse_errors.iter().map(|e| e.into()).collect(),
         names: semantics.names.all(),
         symbols,
         semantic_issues: semantics.issues,
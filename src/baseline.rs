@@ -0,0 +1,377 @@
+use std::path::Path;
+
+use ahash::HashSet;
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_interner::ThreadedInterner;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_source::SourceManager;
+
+use crate::error::Error;
+
+/// A set of known issues that are not reported.
+///
+/// Each issue is identified by a fingerprint that does not depend on its line number, so that
+/// unrelated edits elsewhere in the file do not invalidate the baseline.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(default)]
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// The fingerprint of a single baselined issue.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// The code of the rule that reported the issue.
+    pub rule: String,
+    /// The path of the file containing the issue, relative to the source root.
+    pub file: String,
+    /// A hash of the trimmed content of the line the issue starts on.
+    pub hash: String,
+    /// The index of the issue among the issues sharing the same rule, file, and hash.
+    pub occurrence: usize,
+}
+
+impl Baseline {
+    /// Creates a baseline containing every given issue.
+    pub fn generate(
+        interner: &ThreadedInterner,
+        manager: &SourceManager,
+        issues: &IssueCollection,
+    ) -> Result<Self, Error> {
+        let mut entries = fingerprint(interner, manager, issues)?;
+        entries.sort();
+
+        Ok(Self { entries })
+    }
+
+    /// Reads the baseline stored at the given path.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path).map_err(|error| Error::Baseline(path.to_path_buf(), error))?;
+
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Writes the baseline to the given path, creating missing parent directories.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let content = toml::to_string(self)?;
+
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|error| Error::Baseline(path.to_path_buf(), error))?;
+        }
+
+        std::fs::write(path, content).map_err(|error| Error::Baseline(path.to_path_buf(), error))
+    }
+
+    /// Removes the issues that are part of the baseline from the collection.
+    ///
+    /// Returns the remaining issues, along with the outcome of the matching.
+    pub fn filter(
+        &self,
+        interner: &ThreadedInterner,
+        manager: &SourceManager,
+        issues: IssueCollection,
+    ) -> Result<(IssueCollection, BaselineMatches), Error> {
+        let mut unmatched: HashSet<&BaselineEntry> = self.entries.iter().collect();
+        let fingerprints = fingerprint(interner, manager, &issues)?;
+
+        let mut baselined = 0;
+        let mut remaining = IssueCollection::new();
+        for (issue, fingerprint) in issues.into_iter().zip(fingerprints) {
+            if unmatched.remove(&fingerprint) {
+                baselined += 1;
+            } else {
+                remaining.push(issue);
+            }
+        }
+
+        // Keep the order of the baseline file, so that stale entries are listed deterministically.
+        let unused = self.entries.iter().filter(|entry| unmatched.contains(entry)).cloned().collect();
+
+        Ok((remaining, BaselineMatches { baselined, unused }))
+    }
+
+    /// Removes the given entries from the baseline.
+    pub fn prune(&mut self, entries: &[BaselineEntry]) {
+        let entries: HashSet<&BaselineEntry> = entries.iter().collect();
+
+        self.entries.retain(|entry| !entries.contains(entry));
+    }
+}
+
+/// The outcome of filtering issues through a baseline.
+#[derive(Debug, Default)]
+pub struct BaselineMatches {
+    /// The number of issues that matched a baseline entry.
+    pub baselined: usize,
+    /// The baseline entries that did not match any issue.
+    pub unused: Vec<BaselineEntry>,
+}
+
+impl BaselineMatches {
+    /// Only keeps the unused entries referring to files that are part of the analyzed sources.
+    pub fn retain_analyzed(&mut self, interner: &ThreadedInterner, manager: &SourceManager) {
+        let files = analyzed_files(interner, manager);
+
+        self.unused.retain(|entry| files.contains(&entry.file));
+    }
+
+    /// Creates a note for every unused baseline entry.
+    ///
+    /// Entries referring to files that are not part of the analyzed sources, for example because they
+    /// were deleted, are reported as such.
+    pub fn unused_entry_issues(&self, interner: &ThreadedInterner, manager: &SourceManager) -> IssueCollection {
+        let files = analyzed_files(interner, manager);
+
+        IssueCollection::from(self.unused.iter().map(|entry| {
+            let issue = Issue::note(format!("Unused baseline entry for `{}` in `{}`.", entry.rule, entry.file))
+                .with_help("Run `mago lint --prune-baseline` to remove unused entries from the baseline.");
+
+            if files.contains(&entry.file) {
+                issue.with_note("The issue no longer occurs, or the line it was reported on has changed.")
+            } else {
+                issue.with_note("The file no longer exists, or is no longer part of the analyzed sources.")
+            }
+        }))
+    }
+}
+
+/// Returns the names, as stored in baseline entries, of the user defined sources.
+fn analyzed_files(interner: &ThreadedInterner, manager: &SourceManager) -> HashSet<String> {
+    manager.user_defined_source_ids().map(|source_id| interner.lookup(&source_id.0).replace('\\', "/")).collect()
+}
+
+/// Computes the fingerprint of every issue, in the order of the collection.
+///
+/// Occurrence indices are assigned in source order, so that they do not depend on the order in
+/// which the issues were collected.
+fn fingerprint(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    issues: &IssueCollection,
+) -> Result<Vec<BaselineEntry>, Error> {
+    let mut keyed = Vec::with_capacity(issues.len());
+    for (index, issue) in issues.iter().enumerate() {
+        let (file, hash, offset) = locate(interner, manager, issue)?;
+        let rule = issue.code.clone().unwrap_or_else(|| "other".to_string());
+
+        keyed.push((BaselineEntry { rule, file, hash, occurrence: 0 }, offset, issue.message.clone(), index));
+    }
+
+    keyed.sort();
+
+    let mut entries: Vec<Option<BaselineEntry>> = vec![None; keyed.len()];
+    let mut previous: Option<BaselineEntry> = None;
+    for (mut entry, _, _, index) in keyed {
+        if let Some(previous) = &previous {
+            if previous.rule == entry.rule && previous.file == entry.file && previous.hash == entry.hash {
+                entry.occurrence = previous.occurrence + 1;
+            }
+        }
+
+        previous = Some(entry.clone());
+        entries[index] = Some(entry);
+    }
+
+    Ok(entries.into_iter().map(|entry| entry.expect("every issue has a fingerprint")).collect())
+}
+
+/// Returns the file, line content hash, and start offset of the primary annotation of the issue.
+fn locate(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    issue: &Issue,
+) -> Result<(String, String, usize), Error> {
+    let Some(annotation) = issue.annotations.iter().find(|annotation| annotation.is_primary()) else {
+        return Ok((String::new(), hash(""), 0));
+    };
+
+    let source = manager.load(&annotation.span.start.source)?;
+    let content = interner.lookup(&source.content);
+    let offset = annotation.span.start.offset;
+
+    let line = source.line_number(offset);
+    let start = source.lines.get(line).copied().unwrap_or(0).min(content.len());
+    let end = source.lines.get(line + 1).copied().unwrap_or(content.len()).min(content.len());
+    let line_content = content.get(start..end).unwrap_or_default().trim();
+
+    let file = interner.lookup(&source.identifier.0).replace('\\', "/");
+
+    Ok((file, hash(line_content), offset))
+}
+
+/// Hashes the given content using 64-bit FNV-1a, which is stable across platforms and releases.
+fn hash(content: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_reporting::Annotation;
+    use mago_source::SourceIdentifier;
+    use mago_span::Position;
+    use mago_span::Span;
+
+    use super::*;
+
+    fn issue(source: SourceIdentifier, code: &str, content: &str, needle: &str) -> Issue {
+        let start = content.find(needle).expect("needle not found");
+        let span = Span::new(Position::new(source, start), Position::new(source, start + needle.len()));
+
+        Issue::error(format!("`{}` is not allowed.", needle)).with_code(code).with_annotation(Annotation::primary(span))
+    }
+
+    fn sources(files: &[(&str, &str)]) -> (ThreadedInterner, SourceManager, Vec<SourceIdentifier>) {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let ids = files
+            .iter()
+            .map(|(name, content)| manager.insert_content(name.to_string(), content.to_string(), true))
+            .collect();
+
+        (interner, manager, ids)
+    }
+
+    #[test]
+    fn test_generated_entries_are_sorted_and_numbered_per_line() {
+        let content = "<?php\neval('');\neval('');\nglobal $a;\n";
+        let (interner, manager, ids) = sources(&[("src\\a.php", content)]);
+        let issues = IssueCollection::from([
+            issue(ids[0], "safety/no-global", content, "global"),
+            issue(ids[0], "safety/no-eval", content, "eval"),
+            issue(ids[0], "safety/no-eval", content, "eval('');\nglobal"),
+        ]);
+
+        let baseline = Baseline::generate(&interner, &manager, &issues).unwrap();
+
+        assert_eq!(
+            baseline.entries.iter().map(|entry| (&*entry.rule, &*entry.file, entry.occurrence)).collect::<Vec<_>>(),
+            vec![
+                ("safety/no-eval", "src/a.php", 0),
+                ("safety/no-eval", "src/a.php", 1),
+                ("safety/no-global", "src/a.php", 0)
+            ]
+        );
+        assert_eq!(baseline.entries[0].hash, baseline.entries[1].hash);
+        assert_ne!(baseline.entries[0].hash, baseline.entries[2].hash);
+    }
+
+    #[test]
+    fn test_baselined_issues_are_filtered_regardless_of_their_line() {
+        let before = "<?php\neval('');\nglobal $a;\n";
+        let after = "<?php\n\n// A new comment.\neval('');\nglobal $a;\nglobal $b;\n";
+        let (interner, manager, ids) = sources(&[("a.php", before), ("b.php", after)]);
+
+        let baseline = Baseline::generate(
+            &interner,
+            &manager,
+            &IssueCollection::from([
+                issue(ids[0], "safety/no-eval", before, "eval"),
+                issue(ids[0], "safety/no-global", before, "global $a"),
+            ]),
+        )
+        .unwrap();
+
+        // The same issues, moved to other lines of the file, along with a new one.
+        let baseline = Baseline {
+            entries: baseline
+                .entries
+                .into_iter()
+                .map(|entry| BaselineEntry { file: "b.php".to_string(), ..entry })
+                .collect(),
+        };
+        let issues = IssueCollection::from([
+            issue(ids[1], "safety/no-eval", after, "eval"),
+            issue(ids[1], "safety/no-global", after, "global $a"),
+            issue(ids[1], "safety/no-global", after, "global $b"),
+        ]);
+
+        let (remaining, matches) = baseline.filter(&interner, &manager, issues).unwrap();
+
+        assert_eq!(matches.baselined, 2);
+        assert!(matches.unused.is_empty());
+        assert_eq!(
+            remaining.iter().map(|issue| issue.message.as_str()).collect::<Vec<_>>(),
+            ["`global $b` is not allowed."]
+        );
+    }
+
+    #[test]
+    fn test_baselines_are_saved_and_loaded() {
+        let directory = std::env::temp_dir().join(format!("mago-baseline-{}", std::process::id()));
+        let path = directory.join("nested").join("baseline.toml");
+        let baseline = Baseline {
+            entries: vec![BaselineEntry {
+                rule: "safety/no-eval".to_string(),
+                file: "src/a.php".to_string(),
+                hash: hash("eval('');"),
+                occurrence: 0,
+            }],
+        };
+
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(loaded, baseline);
+        assert!(matches!(Baseline::load(&path), Err(Error::Baseline(..))));
+    }
+
+    #[test]
+    fn test_unused_entries_are_reported_and_pruned() {
+        let content = "<?php\neval('');\n";
+        let (interner, manager, ids) = sources(&[("a.php", content)]);
+        let entry = |rule: &str, file: &str, line: &str| BaselineEntry {
+            rule: rule.to_string(),
+            file: file.to_string(),
+            hash: hash(line),
+            occurrence: 0,
+        };
+
+        let mut baseline = Baseline {
+            entries: vec![
+                entry("safety/no-eval", "a.php", "eval('');"),
+                entry("safety/no-global", "a.php", "global $a;"),
+                entry("safety/no-eval", "deleted.php", "eval('');"),
+            ],
+        };
+
+        let issues = IssueCollection::from([issue(ids[0], "safety/no-eval", content, "eval")]);
+        let (remaining, mut matches) = baseline.filter(&interner, &manager, issues).unwrap();
+
+        assert!(remaining.is_empty());
+        assert_eq!(matches.unused, baseline.entries[1..]);
+
+        let notes = matches.unused_entry_issues(&interner, &manager);
+        assert_eq!(
+            notes.iter().map(|issue| (issue.message.as_str(), issue.notes[0].as_str())).collect::<Vec<_>>(),
+            [
+                (
+                    "Unused baseline entry for `safety/no-global` in `a.php`.",
+                    "The issue no longer occurs, or the line it was reported on has changed."
+                ),
+                (
+                    "Unused baseline entry for `safety/no-eval` in `deleted.php`.",
+                    "The file no longer exists, or is no longer part of the analyzed sources."
+                ),
+            ]
+        );
+
+        // Only the entries of the analyzed files are pruned, e.g. when linting a subset of the sources.
+        matches.retain_analyzed(&interner, &manager);
+        baseline.prune(&matches.unused);
+
+        assert_eq!(
+            baseline.entries,
+            [entry("safety/no-eval", "a.php", "eval('');"), entry("safety/no-eval", "deleted.php", "eval('');")]
+        );
+    }
+}
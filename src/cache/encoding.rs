@@ -0,0 +1,935 @@
+//! A compact binary encoding of the values implementing the traits of `serde`, used to store the entries of the cache.
+//!
+//! Unlike self-describing formats, the names of the fields of structs, and of the variants of enums, are not
+//! stored: structs are stored as the sequence of their fields, and enums as the index of their variant, followed
+//! by its content. Integers are stored as variable-length integers, and sequences, maps, and strings are prefixed
+//! by their length.
+//!
+//! Each struct is prefixed by the number of its fields which are serialized, or skipped, and by a mask of the
+//! skipped ones, so that an empty value can be deserialized in place of the skipped fields, and of the missing
+//! trailing fields, such as the content of the unit variants of adjacently tagged enums; only the first 64
+//! fields of a struct can be skipped. This prefix also allows deserializing the structs whose type is not known,
+//! such as the content of the struct variants of adjacently tagged enums.
+
+use serde::de;
+use serde::de::DeserializeSeed;
+use serde::de::IntoDeserializer;
+use serde::de::Visitor;
+use serde::ser;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An error returned when a value cannot be encoded, or decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingError(String);
+
+impl std::fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+impl ser::Error for EncodingError {
+    fn custom<T: std::fmt::Display>(message: T) -> Self {
+        Self(message.to_string())
+    }
+}
+
+impl de::Error for EncodingError {
+    fn custom<T: std::fmt::Display>(message: T) -> Self {
+        Self(message.to_string())
+    }
+}
+
+/// Encodes the given value.
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, EncodingError> {
+    let mut encoder = Encoder { output: vec![] };
+    value.serialize(&mut encoder)?;
+
+    Ok(encoder.output)
+}
+
+/// Decodes a value encoded by [`to_bytes`], which must span all the given bytes.
+pub fn from_bytes<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, EncodingError> {
+    let mut decoder = Decoder { input: bytes };
+    let value = T::deserialize(&mut decoder)?;
+    if !decoder.input.is_empty() {
+        return Err(EncodingError(format!("{} trailing bytes", decoder.input.len())));
+    }
+
+    Ok(value)
+}
+
+struct Encoder {
+    output: Vec<u8>,
+}
+
+impl Encoder {
+    fn write_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.output.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+
+        self.output.push(value as u8);
+    }
+
+    fn write_signed(&mut self, value: i64) {
+        self.write_varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn write_length(&mut self, length: Option<usize>) -> Result<(), EncodingError> {
+        let length = length.ok_or_else(|| EncodingError("the length of sequences and maps must be known".into()))?;
+        self.write_varint(length as u64);
+
+        Ok(())
+    }
+
+    fn start_struct(&mut self) -> StructEncoder<'_> {
+        // The number of fields, and the mask of the skipped ones, are small, so a single byte is reserved for each.
+        let start = self.output.len();
+        self.output.extend([0, 0]);
+
+        StructEncoder { encoder: self, start, index: 0, skipped: 0 }
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Encoder {
+    type Ok = ();
+    type Error = EncodingError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = StructEncoder<'a>;
+    type SerializeStructVariant = StructEncoder<'a>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, value: bool) -> Result<(), EncodingError> {
+        self.output.push(value as u8);
+
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), EncodingError> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), EncodingError> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), EncodingError> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), EncodingError> {
+        self.write_signed(value);
+
+        Ok(())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), EncodingError> {
+        self.serialize_u64(value as u64)
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), EncodingError> {
+        self.serialize_u64(value as u64)
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), EncodingError> {
+        self.serialize_u64(value as u64)
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), EncodingError> {
+        self.write_varint(value);
+
+        Ok(())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<(), EncodingError> {
+        self.output.extend(value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), EncodingError> {
+        self.output.extend(value.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), EncodingError> {
+        self.serialize_u64(value as u64)
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), EncodingError> {
+        self.serialize_bytes(value.as_bytes())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), EncodingError> {
+        self.write_varint(value.len() as u64);
+        self.output.extend(value);
+
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), EncodingError> {
+        self.output.push(0);
+
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), EncodingError> {
+        self.output.push(1);
+
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), EncodingError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), EncodingError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+    ) -> Result<(), EncodingError> {
+        self.serialize_u32(index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), EncodingError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), EncodingError> {
+        self.write_varint(index as u64);
+
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, length: Option<usize>) -> Result<Self, EncodingError> {
+        self.write_length(length)?;
+
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _length: usize) -> Result<Self, EncodingError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _length: usize) -> Result<Self, EncodingError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        _length: usize,
+    ) -> Result<Self, EncodingError> {
+        self.write_varint(index as u64);
+
+        Ok(self)
+    }
+
+    fn serialize_map(self, length: Option<usize>) -> Result<Self, EncodingError> {
+        self.write_length(length)?;
+
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _length: usize) -> Result<StructEncoder<'a>, EncodingError> {
+        Ok(self.start_struct())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        _length: usize,
+    ) -> Result<StructEncoder<'a>, EncodingError> {
+        self.write_varint(index as u64);
+
+        Ok(self.start_struct())
+    }
+}
+
+impl ser::SerializeSeq for &mut Encoder {
+    type Ok = ();
+    type Error = EncodingError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodingError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), EncodingError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut Encoder {
+    type Ok = ();
+    type Error = EncodingError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodingError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), EncodingError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut Encoder {
+    type Ok = ();
+    type Error = EncodingError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodingError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), EncodingError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut Encoder {
+    type Ok = ();
+    type Error = EncodingError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodingError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), EncodingError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for &mut Encoder {
+    type Ok = ();
+    type Error = EncodingError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), EncodingError> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodingError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), EncodingError> {
+        Ok(())
+    }
+}
+
+/// Encodes the fields of a struct, recording the ones which are skipped.
+struct StructEncoder<'a> {
+    encoder: &'a mut Encoder,
+    /// The offset of the number of fields, followed by the mask of the skipped ones.
+    start: usize,
+    index: u32,
+    skipped: u64,
+}
+
+impl StructEncoder<'_> {
+    fn field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodingError> {
+        self.index += 1;
+
+        value.serialize(&mut *self.encoder)
+    }
+
+    fn skip(&mut self) -> Result<(), EncodingError> {
+        if self.index >= u64::BITS {
+            return Err(EncodingError("only the first 64 fields of a struct can be skipped".into()));
+        }
+
+        self.skipped |= 1 << self.index;
+        self.index += 1;
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), EncodingError> {
+        if self.index < 0x80 && self.skipped < 0x80 {
+            self.encoder.output[self.start] = self.index as u8;
+            self.encoder.output[self.start + 1] = self.skipped as u8;
+        } else {
+            let mut header = Encoder { output: vec![] };
+            header.write_varint(self.index as u64);
+            header.write_varint(self.skipped);
+            self.encoder.output.splice(self.start..self.start + 2, header.output);
+        }
+
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for StructEncoder<'_> {
+    type Ok = ();
+    type Error = EncodingError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<(), EncodingError> {
+        self.field(value)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<(), EncodingError> {
+        self.skip()
+    }
+
+    fn end(self) -> Result<(), EncodingError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for StructEncoder<'_> {
+    type Ok = ();
+    type Error = EncodingError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<(), EncodingError> {
+        self.field(value)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<(), EncodingError> {
+        self.skip()
+    }
+
+    fn end(self) -> Result<(), EncodingError> {
+        self.finish()
+    }
+}
+
+struct Decoder<'de> {
+    input: &'de [u8],
+}
+
+impl<'de> Decoder<'de> {
+    fn read_byte(&mut self) -> Result<u8, EncodingError> {
+        let (byte, rest) = self.input.split_first().ok_or_else(|| EncodingError("unexpected end of input".into()))?;
+        self.input = rest;
+
+        Ok(*byte)
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'de [u8], EncodingError> {
+        let (bytes, rest) =
+            self.input.split_at_checked(length).ok_or_else(|| EncodingError("unexpected end of input".into()))?;
+        self.input = rest;
+
+        Ok(bytes)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, EncodingError> {
+        let mut value = 0u64;
+        for shift in (0..u64::BITS).step_by(7) {
+            let byte = self.read_byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte < 0x80 {
+                return Ok(value);
+            }
+        }
+
+        Err(EncodingError("invalid variable-length integer".into()))
+    }
+
+    fn read_signed(&mut self) -> Result<i64, EncodingError> {
+        let value = self.read_varint()?;
+
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn read_length(&mut self) -> Result<usize, EncodingError> {
+        let length = self.read_varint()? as usize;
+        // Each element takes at least one byte, except for the units, which are never stored in sequences.
+        if length > self.input.len() {
+            return Err(EncodingError(format!("invalid length {}", length)));
+        }
+
+        Ok(length)
+    }
+
+    fn read_str(&mut self) -> Result<&'de str, EncodingError> {
+        let length = self.read_length()?;
+
+        std::str::from_utf8(self.read_bytes(length)?).map_err(|error| EncodingError(error.to_string()))
+    }
+
+    fn read_integer<T: TryFrom<u64>>(&mut self) -> Result<T, EncodingError> {
+        T::try_from(self.read_varint()?).map_err(|_| EncodingError("integer out of range".into()))
+    }
+
+    fn read_signed_integer<T: TryFrom<i64>>(&mut self) -> Result<T, EncodingError> {
+        T::try_from(self.read_signed()?).map_err(|_| EncodingError("integer out of range".into()))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Decoder<'de> {
+    type Error = EncodingError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    /// The types of the values are not stored, so the values deserialized without a known type can only be
+    /// structs, such as the content of the struct variants of adjacently tagged enums, whose fields are
+    /// deserialized as a map keyed by their indices.
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        let count = self.read_varint()? as usize;
+        let skipped = self.read_varint()?;
+
+        visitor.visit_map(Fields { decoder: self, count, skipped, index: 0, length: count })
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, EncodingError> {
+        Err(EncodingError("the encoding does not describe the types of the values, so none can be ignored".into()))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        match self.read_byte()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            byte => Err(EncodingError(format!("invalid boolean {}", byte))),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_i8(self.read_signed_integer()?)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_i16(self.read_signed_integer()?)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_i32(self.read_signed_integer()?)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_i64(self.read_signed()?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_u8(self.read_integer()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_u16(self.read_integer()?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_u32(self.read_integer()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_u64(self.read_varint()?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        let bytes = self.read_bytes(4)?;
+
+        visitor.visit_f32(f32::from_le_bytes(bytes.try_into().expect("four bytes were read")))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        let bytes = self.read_bytes(8)?;
+
+        visitor.visit_f64(f64::from_le_bytes(bytes.try_into().expect("eight bytes were read")))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        let character =
+            char::from_u32(self.read_integer()?).ok_or_else(|| EncodingError("invalid character".into()))?;
+
+        visitor.visit_char(character)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_borrowed_str(self.read_str()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        let length = self.read_length()?;
+
+        visitor.visit_borrowed_bytes(self.read_bytes(length)?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        match self.read_byte()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            byte => Err(EncodingError(format!("invalid option {}", byte))),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, EncodingError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, EncodingError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        let remaining = self.read_length()?;
+
+        visitor.visit_seq(Elements { decoder: self, remaining })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, length: usize, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_seq(Elements { decoder: self, remaining: length })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        length: usize,
+        visitor: V,
+    ) -> Result<V::Value, EncodingError> {
+        self.deserialize_tuple(length, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        let remaining = self.read_length()?;
+
+        visitor.visit_map(Elements { decoder: self, remaining })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, EncodingError> {
+        let count = self.read_varint()? as usize;
+        let skipped = self.read_varint()?;
+
+        visitor.visit_seq(Fields { decoder: self, count, skipped, index: 0, length: fields.len() })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, EncodingError> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_u64(self.read_varint()?)
+    }
+}
+
+/// The elements of a sequence, or the entries of a map, of the given length.
+struct Elements<'a, 'de> {
+    decoder: &'a mut Decoder<'de>,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Elements<'_, 'de> {
+    type Error = EncodingError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, EncodingError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        seed.deserialize(&mut *self.decoder).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Elements<'_, 'de> {
+    type Error = EncodingError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, EncodingError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        seed.deserialize(&mut *self.decoder).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, EncodingError> {
+        seed.deserialize(&mut *self.decoder)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// The fields of a struct, the skipped, and missing ones being deserialized as their empty value.
+struct Fields<'a, 'de> {
+    decoder: &'a mut Decoder<'de>,
+    /// The number of fields which were serialized, or skipped.
+    count: usize,
+    skipped: u64,
+    index: usize,
+    length: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Fields<'_, 'de> {
+    type Error = EncodingError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, EncodingError> {
+        if self.index == self.length {
+            return Ok(None);
+        }
+
+        self.index += 1;
+
+        de::MapAccess::next_value_seed(self, seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.length - self.index)
+    }
+}
+
+/// The fields of a struct whose type is not known, as the entries of a map, keyed by the indices of the fields.
+impl<'de> de::MapAccess<'de> for Fields<'_, 'de> {
+    type Error = EncodingError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, EncodingError> {
+        if self.index == self.length {
+            return Ok(None);
+        }
+
+        self.index += 1;
+
+        seed.deserialize((self.index as u64 - 1).into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, EncodingError> {
+        // The index of the field has already been advanced.
+        let index = self.index - 1;
+        let skipped = index >= self.count || (index < u64::BITS as usize && self.skipped & (1 << index) != 0);
+
+        if skipped {
+            seed.deserialize(Skipped)
+        } else {
+            seed.deserialize(&mut *self.decoder)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.length - self.index)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Decoder<'de> {
+    type Error = EncodingError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), EncodingError> {
+        let index: u32 = self.read_integer()?;
+        let variant = seed.deserialize(index.into_deserializer())?;
+
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Decoder<'de> {
+    type Error = EncodingError;
+
+    fn unit_variant(self) -> Result<(), EncodingError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, EncodingError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, length: usize, visitor: V) -> Result<V::Value, EncodingError> {
+        de::Deserializer::deserialize_tuple(self, length, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, EncodingError> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
+
+/// The value of a skipped, or missing field, which is only known to be empty, such as `None`, or an empty sequence.
+struct Skipped;
+
+impl<'de> de::Deserializer<'de> for Skipped {
+    type Error = EncodingError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_none()
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_seq(de::value::SeqDeserializer::new(std::iter::empty::<()>()))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, EncodingError> {
+        visitor.visit_map(de::value::MapDeserializer::new(std::iter::empty::<((), ())>()))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit unit_struct
+        newtype_struct tuple tuple_struct struct enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    use mago_fixer::FixPlan;
+    use mago_fixer::SafetyClassification;
+    use mago_reporting::Annotation;
+    use mago_reporting::Issue;
+    use mago_source::SourceIdentifier;
+    use mago_span::Position;
+    use mago_span::Span;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Kind {
+        Unit,
+        Newtype(i32),
+        Tuple(u8, String),
+        Struct { name: String, value: Option<f64> },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "type", content = "value")]
+    enum Tagged {
+        Empty,
+        Named(String),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Value {
+        id: u64,
+        offset: i64,
+        flag: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        code: Option<String>,
+        kinds: Vec<Kind>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        notes: Vec<String>,
+        tagged: Vec<Tagged>,
+        entries: BTreeMap<String, (char, f32)>,
+    }
+
+    #[test]
+    fn test_values_round_trip() {
+        let value = Value {
+            id: u64::MAX,
+            offset: -1234567,
+            flag: true,
+            code: None,
+            kinds: vec![
+                Kind::Unit,
+                Kind::Newtype(-5),
+                Kind::Tuple(255, "tuple".to_string()),
+                Kind::Struct { name: "struct".to_string(), value: Some(1.5) },
+            ],
+            notes: vec![],
+            tagged: vec![Tagged::Empty, Tagged::Named("named".to_string())],
+            entries: BTreeMap::from([("é".to_string(), ('ü', 0.25)), ("b".to_string(), ('b', -2.0))]),
+        };
+
+        let bytes = to_bytes(&value).unwrap();
+        assert_eq!(from_bytes::<Value>(&bytes).unwrap(), value);
+
+        let value = Value { code: Some("code".to_string()), notes: vec!["note".to_string()], ..value };
+        let bytes = to_bytes(&value).unwrap();
+        assert_eq!(from_bytes::<Value>(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn test_issues_round_trip() {
+        let mut plan = FixPlan::new();
+        plan.replace(1..5, "replacement", SafetyClassification::Safe);
+        plan.delete(6..7, SafetyClassification::Unsafe);
+
+        let source = SourceIdentifier::dummy();
+        let span = Span::new(Position::new(source, 1), Position::new(source, 5));
+        let issues = vec![
+            Issue::error("message"),
+            Issue::warning("message")
+                .with_code("code")
+                .with_annotation(Annotation::primary(span).with_message("annotation"))
+                .with_note("note")
+                .with_help("help")
+                .with_suggestion(source, plan),
+        ];
+
+        let bytes = to_bytes(&issues).unwrap();
+        assert_eq!(from_bytes::<Vec<Issue>>(&bytes).unwrap(), issues);
+    }
+
+    #[test]
+    fn test_invalid_bytes_are_rejected() {
+        let bytes = to_bytes(&vec!["a".to_string(), "b".to_string()]).unwrap();
+
+        assert!(from_bytes::<Vec<String>>(&bytes[..bytes.len() - 1]).is_err());
+        assert!(from_bytes::<Vec<String>>(&[bytes.as_slice(), &[0]].concat()).is_err());
+        assert!(from_bytes::<Vec<String>>(&[0xff, 0xff, 0xff]).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use ahash::HashSet;
+use sha2::Digest;
+use sha2::Sha256;
+
+use mago_interner::ThreadedInterner;
+use mago_parser::settings::ParserSettings;
+use mago_php_version::PHPVersion;
+use mago_reflection::CodebaseReflection;
+use mago_source::Source;
+
+use crate::config::cache::CacheConfiguration;
+use crate::consts::VERSION;
+use crate::error::Error;
+
+pub mod encoding;
+
+/// The bytes starting each entry of the cache.
+const MAGIC: &[u8; 4] = b"MGRC";
+
+/// The directory of the cache in which the reflections of the sources are stored.
+const REFLECTIONS_DIR: &str = "reflections";
+
+/// An on-disk cache of the reflections of the sources, so that the sources which did not change since the
+/// previous run, such as the dependencies, do not need to be parsed, and reflected again.
+///
+/// Each reflection is stored in its own file, named after the hash of the content and the name of its source,
+/// of the version of mago, and of the settings affecting the reflection, so that the entries never need to be
+/// invalidated. The interned strings are stored along with the reflection, to be interned again when loaded.
+///
+/// As the entries of changed sources are never overwritten, the entries which were not used by a run that
+/// looked up every source are removed by [`ReflectionCache::prune`]. The cache can also be removed entirely
+/// with `mago cache clear`.
+///
+/// Failing to read, or write an entry is never an error: the source is reflected again instead.
+#[derive(Debug, Clone)]
+pub struct ReflectionCache {
+    /// The directory of the reflections, or `None` if the cache is disabled.
+    directory: Option<PathBuf>,
+    /// The names of the entries that were looked up, shared by the clones of the cache.
+    used: Arc<Mutex<HashSet<String>>>,
+}
+
+impl ReflectionCache {
+    /// Creates a cache as configured, which is disabled if the configuration disables it.
+    pub fn new(configuration: &CacheConfiguration) -> Self {
+        Self {
+            directory: configuration.enabled.then(|| configuration.directory.join(REFLECTIONS_DIR)),
+            used: Arc::new(Mutex::new(HashSet::default())),
+        }
+    }
+
+    /// Returns the cached reflection of the given source, or reflects it with the given function, and caches
+    /// the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `interner` - The interner to intern the strings of a cached reflection into.
+    /// * `source` - The source to reflect.
+    /// * `php_version` - The targeted version of PHP, for which the source is reflected.
+    /// * `settings` - The settings used to parse the source.
+    /// * `reflect` - The function reflecting the source when its reflection is not cached.
+    pub fn get_or_reflect(
+        &self,
+        interner: &ThreadedInterner,
+        source: &Source,
+        php_version: PHPVersion,
+        settings: ParserSettings,
+        reflect: impl FnOnce() -> CodebaseReflection,
+    ) -> CodebaseReflection {
+        let Some(directory) = &self.directory else {
+            return reflect();
+        };
+
+        let key = key(interner, source, php_version, settings);
+        let path = directory.join(&key);
+        self.used.lock().expect("cache lock poisoned, this should never happen").insert(key);
+
+        if let Some(reflection) = read(interner, &path) {
+            return reflection;
+        }
+
+        let reflection = reflect();
+        if let Err(error) = write(interner, &path, &reflection) {
+            tracing::debug!("failed to cache the reflection of `{}`: {}", interner.lookup(&source.identifier.0), error);
+        }
+
+        reflection
+    }
+
+    /// Removes the entries that were not looked up through this cache, or any of its clones, such as the
+    /// entries of the previous content of the sources that changed, or of the sources that were removed.
+    ///
+    /// This must only be called once every source was looked up, as the entries of the other sources
+    /// would be removed as well.
+    ///
+    /// Returns the number of removed entries.
+    pub fn prune(&self) -> usize {
+        let Some(directory) = &self.directory else {
+            return 0;
+        };
+
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return 0;
+        };
+
+        let used = self.used.lock().expect("cache lock poisoned, this should never happen");
+        let mut removed = 0;
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name();
+            if used.contains(name.to_string_lossy().as_ref()) {
+                continue;
+            }
+
+            if let Err(error) = std::fs::remove_file(entry.path()) {
+                tracing::debug!("failed to remove the unused cache entry `{}`: {}", entry.path().display(), error);
+
+                continue;
+            }
+
+            removed += 1;
+        }
+
+        if removed > 0 {
+            tracing::debug!("removed {} unused cache entries from `{}`", removed, directory.display());
+        }
+
+        removed
+    }
+}
+
+/// Removes the given directory of the cache, along with all its entries.
+///
+/// Returns whether the directory existed.
+pub fn clear(directory: &Path) -> Result<bool, Error> {
+    if !directory.exists() {
+        return Ok(false);
+    }
+
+    std::fs::remove_dir_all(directory).map_err(|error| Error::Cache(directory.to_path_buf(), error))?;
+
+    Ok(true)
+}
+
+/// Returns the name of the entry of the reflection of the given source.
+fn key(interner: &ThreadedInterner, source: &Source, php_version: PHPVersion, settings: ParserSettings) -> String {
+    let mut hasher = Sha256::new();
+    for part in
+        [VERSION, &php_version.to_string(), interner.lookup(&source.identifier.0), interner.lookup(&source.content)]
+    {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+
+    hasher.update([settings.short_open_tags as u8, source.identifier.1 as u8]);
+
+    hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Reads the reflection stored in the entry at the given path, if any.
+///
+/// A corrupted entry is removed, so that it is written again.
+fn read(interner: &ThreadedInterner, path: &Path) -> Option<CodebaseReflection> {
+    let mut bytes = vec![];
+    std::fs::File::open(path).ok()?.read_to_end(&mut bytes).ok()?;
+
+    let reflection = decode(interner, &bytes);
+    if reflection.is_none() {
+        tracing::warn!("the cache entry `{}` is corrupted, reflecting its source again", path.display());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    reflection
+}
+
+fn decode(interner: &ThreadedInterner, bytes: &[u8]) -> Option<CodebaseReflection> {
+    let (length, rest) = bytes.strip_prefix(MAGIC)?.split_first_chunk::<8>()?;
+    let (strings, reflection) = rest.split_at_checked(u64::from_le_bytes(*length) as usize)?;
+    let strings: Vec<&str> = encoding::from_bytes(strings).ok()?;
+
+    interner.with_string_table_deserialization(&strings, || encoding::from_bytes(reflection).ok())
+}
+
+/// Writes the given reflection to the entry at the given path, replacing it atomically.
+fn write(interner: &ThreadedInterner, path: &Path, reflection: &CodebaseReflection) -> Result<(), String> {
+    let (content, strings) = interner.with_string_table_serialization(|| encoding::to_bytes(reflection));
+
+    let content = content.map_err(|error| error.to_string())?;
+    let table = encoding::to_bytes(&strings).map_err(|error| error.to_string())?;
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend((table.len() as u64).to_le_bytes());
+    bytes.extend(table);
+    bytes.extend(content);
+
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory).map_err(|error| error.to_string())?;
+    }
+
+    // A partially written entry would be corrupted, so the entry is only renamed once it is complete.
+    let temporary = path.with_extension(format!("{}.tmp", std::process::id()));
+    std::fs::write(&temporary, bytes).map_err(|error| error.to_string())?;
+    std::fs::rename(&temporary, path).map_err(|error| {
+        let _ = std::fs::remove_file(&temporary);
+
+        error.to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mago_names::Names;
+    use mago_parser::parse_source;
+    use mago_source::SourceManager;
+
+    fn reflect(interner: &ThreadedInterner, source: &Source) -> CodebaseReflection {
+        let (program, _) = parse_source(interner, source);
+        let names = Names::resolve(interner, &program);
+
+        mago_reflector::reflect(interner, source, &program, &names)
+    }
+
+    fn directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("mago-cache-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+
+        directory
+    }
+
+    #[test]
+    fn test_cached_reflection_is_loaded_by_another_interner() {
+        let directory = directory("loaded");
+        let cache = ReflectionCache::new(&CacheConfiguration { enabled: true, directory: directory.clone() });
+        let content = "<?php namespace Acme; function foo(int $bar): void {} class Baz {}";
+
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.load(&manager.insert_content("foo.php".to_string(), content.to_string(), false)).unwrap();
+        let reflected = cache.get_or_reflect(&interner, &source, PHPVersion::LATEST, ParserSettings::default(), || {
+            reflect(&interner, &source)
+        });
+
+        let other = ThreadedInterner::new();
+        other.intern("shifting the identifiers of the other interner");
+        let manager = SourceManager::new(other.clone());
+        let source = manager.load(&manager.insert_content("foo.php".to_string(), content.to_string(), false)).unwrap();
+        let cached = cache.get_or_reflect(&other, &source, PHPVersion::LATEST, ParserSettings::default(), || {
+            panic!("the reflection should be cached")
+        });
+
+        assert!(cached.function_exists(&other, &other.intern("Acme\\foo")));
+        assert!(cached.class_exists(&other, &other.intern("Acme\\Baz")));
+        assert_eq!(reflected.function_like_reflections.len(), cached.function_like_reflections.len());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn test_corrupted_entry_is_reflected_again() {
+        let directory = directory("corrupted");
+        let cache = ReflectionCache::new(&CacheConfiguration { enabled: true, directory: directory.clone() });
+
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.load(&manager.insert_content("foo.php".to_string(), "<?php".to_string(), false)).unwrap();
+        let key = key(&interner, &source, PHPVersion::LATEST, ParserSettings::default());
+
+        std::fs::create_dir_all(directory.join(REFLECTIONS_DIR)).unwrap();
+        std::fs::write(directory.join(REFLECTIONS_DIR).join(&key), b"MGRC\xff\xff").unwrap();
+
+        let mut reflected = false;
+        cache.get_or_reflect(&interner, &source, PHPVersion::LATEST, ParserSettings::default(), || {
+            reflected = true;
+
+            reflect(&interner, &source)
+        });
+
+        assert!(reflected);
+        assert!(read(&interner, &directory.join(REFLECTIONS_DIR).join(&key)).is_some());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn test_key_depends_on_the_php_version() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.load(&manager.insert_content("foo.php".to_string(), "<?php".to_string(), false)).unwrap();
+        let settings = ParserSettings::default();
+
+        assert_ne!(
+            key(&interner, &source, PHPVersion::PHP80, settings),
+            key(&interner, &source, PHPVersion::PHP84, settings)
+        );
+        assert_eq!(
+            key(&interner, &source, PHPVersion::PHP84, settings),
+            key(&interner, &source, PHPVersion::PHP84, settings)
+        );
+    }
+
+    #[test]
+    fn test_unused_entries_are_pruned() {
+        let directory = directory("pruned");
+        let configuration = CacheConfiguration { enabled: true, directory: directory.clone() };
+        let settings = ParserSettings::default();
+
+        let interner = ThreadedInterner::new();
+        let run = |sources: &[(&str, &str)]| {
+            let manager = SourceManager::new(interner.clone());
+            let cache = ReflectionCache::new(&configuration);
+            for (name, content) in sources {
+                let source_id = manager.insert_content(name.to_string(), content.to_string(), false);
+                let source = manager.load(&source_id).unwrap();
+
+                cache
+                    .clone()
+                    .get_or_reflect(&interner, &source, PHPVersion::LATEST, settings, || reflect(&interner, &source));
+            }
+
+            cache.prune()
+        };
+
+        assert_eq!(run(&[("foo.php", "<?php function foo() {}"), ("bar.php", "<?php function bar() {}")]), 0);
+        // The entry of the previous content of `foo.php` is removed, along with the one of `bar.php`.
+        assert_eq!(run(&[("foo.php", "<?php function foo(): void {}")]), 2);
+        assert_eq!(std::fs::read_dir(directory.join(REFLECTIONS_DIR)).unwrap().count(), 1);
+
+        let disabled = ReflectionCache::new(&CacheConfiguration { enabled: false, directory: directory.clone() });
+        assert_eq!(disabled.prune(), 0);
+        assert_eq!(std::fs::read_dir(directory.join(REFLECTIONS_DIR)).unwrap().count(), 1);
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+}
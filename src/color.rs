@@ -0,0 +1,118 @@
+use std::ffi::OsString;
+use std::io::IsTerminal;
+
+use serde::Deserialize;
+use serde::Serialize;
+use strum::Display;
+use strum::EnumString;
+use strum::VariantNames;
+
+use mago_reporting::reporter::ColorChoice as ReporterColorChoice;
+use mago_reporting::reporter::ReportingTarget;
+
+/// Controls whether the output of mago is colored.
+#[derive(
+    Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString, VariantNames,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ColorChoice {
+    /// Use colors when writing to a terminal, unless disabled by the environment.
+    #[default]
+    Auto,
+    /// Always use colors.
+    Always,
+    /// Never use colors.
+    Never,
+}
+
+impl ColorChoice {
+    /// Determines whether output written to the given stream should be colored.
+    ///
+    /// In `auto` mode, `NO_COLOR` disables colors and `CLICOLOR_FORCE` enables them, when set to a
+    /// non-empty value; otherwise colors are used only if the stream is a terminal.
+    ///
+    /// see: https://no-color.org, https://bixense.com/clicolors
+    pub fn use_colors(&self, stream: ReportingTarget) -> bool {
+        self.resolve(
+            |name| std::env::var_os(name),
+            || match stream {
+                ReportingTarget::Stdout => std::io::stdout().is_terminal(),
+                ReportingTarget::Stderr => std::io::stderr().is_terminal(),
+            },
+        )
+    }
+
+    /// Resolves the color choice using the given environment lookup, and terminal detection.
+    fn resolve(&self, env: impl Fn(&str) -> Option<OsString>, is_terminal: impl FnOnce() -> bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                if env("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+                    return false;
+                }
+
+                if env("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0") {
+                    return true;
+                }
+
+                if env("TERM").is_some_and(|value| value == "dumb") {
+                    return false;
+                }
+
+                is_terminal()
+            }
+        }
+    }
+
+    /// Returns the color choice of a reporter writing to the given target.
+    pub fn for_reporter(&self, target: ReportingTarget) -> ReporterColorChoice {
+        if self.use_colors(target) {
+            ReporterColorChoice::Always
+        } else {
+            ReporterColorChoice::Never
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(choice: ColorChoice, variables: &[(&str, &str)], is_terminal: bool) -> bool {
+        choice.resolve(
+            |name| variables.iter().find(|(variable, _)| *variable == name).map(|(_, value)| OsString::from(value)),
+            || is_terminal,
+        )
+    }
+
+    #[test]
+    fn test_explicit_choices_ignore_the_environment() {
+        assert!(resolve(ColorChoice::Always, &[("NO_COLOR", "1")], false));
+        assert!(!resolve(ColorChoice::Never, &[("CLICOLOR_FORCE", "1")], true));
+    }
+
+    #[test]
+    fn test_auto_honors_no_color_and_clicolor_force() {
+        assert!(resolve(ColorChoice::Auto, &[], true));
+        assert!(!resolve(ColorChoice::Auto, &[], false));
+
+        assert!(!resolve(ColorChoice::Auto, &[("NO_COLOR", "1")], true));
+        assert!(!resolve(ColorChoice::Auto, &[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], true));
+        assert!(resolve(ColorChoice::Auto, &[("NO_COLOR", "")], true));
+
+        assert!(resolve(ColorChoice::Auto, &[("CLICOLOR_FORCE", "1")], false));
+        assert!(!resolve(ColorChoice::Auto, &[("CLICOLOR_FORCE", "0")], false));
+        assert!(!resolve(ColorChoice::Auto, &[("CLICOLOR_FORCE", "")], false));
+
+        assert!(!resolve(ColorChoice::Auto, &[("TERM", "dumb")], true));
+    }
+
+    #[test]
+    fn test_color_choices_are_parsed_case_insensitively() {
+        assert_eq!("ALWAYS".parse::<ColorChoice>().unwrap(), ColorChoice::Always);
+        assert_eq!("never".parse::<ColorChoice>().unwrap(), ColorChoice::Never);
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+}
@@ -1,46 +1,98 @@
 use std::process::ExitCode;
 
 use clap::Parser;
-use mago_names::Names;
 use serde_json::json;
+use serde_json::Value;
+use strum::Display;
+use strum::EnumString;
+use strum::VariantNames;
 use termtree::Tree;
 
-use mago_ast::node::NodeKind;
 use mago_ast::Node;
 use mago_interner::ThreadedInterner;
-use mago_parser::parse_source;
+use mago_names::Names;
+use mago_parser::parse_source_with_settings;
+use mago_parser::settings::ParserSettings;
 use mago_reporting::reporter::Reporter;
 use mago_reporting::reporter::ReportingFormat;
 use mago_reporting::reporter::ReportingTarget;
 use mago_reporting::Issue;
 use mago_source::SourceManager;
+use mago_span::HasSpan;
 
+use crate::color::ColorChoice;
 use crate::enum_variants;
 use crate::error::Error;
 
+/// The name of the source read from stdin.
+const STDIN_SOURCE_NAME: &str = "<stdin>";
+
+/// The formats in which the AST can be printed.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, VariantNames)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum AstFormat {
+    /// An indented tree of the nodes, with the source text of the leaf nodes.
+    #[default]
+    Tree,
+    /// The program serialized as JSON, with the interned strings resolved.
+    Json,
+}
+
 /// Represents the `ast` command, which parses a PHP file and prints its abstract syntax tree (AST).
 #[derive(Parser, Debug)]
 #[command(
     name = "ast",
     about = "parse and visualize the abstract syntax tree (AST) of a PHP file",
     long_about = r#"
-The `ast` command parses a PHP file and outputs its abstract syntax tree (AST).
+The `ast` command parses a PHP file, or the source read from stdin, and outputs its abstract syntax tree (AST).
 
 This command helps you understand the structure of your PHP code and debug parsing issues.
+
+The `json` format serializes the whole program, with every enum variant tagged by its name, and the interned strings, such as identifiers and literals, resolved; it is meant for external tools.
 "#
 )]
 pub struct AstCommand {
     /// Path to the PHP file to be parsed.
-    #[arg(long, short = 'f', help = "specify the PHP file to parse", required = true)]
-    pub file: String,
+    #[arg(
+        help = "specify the PHP file to parse",
+        required_unless_present_any = ["file_option", "stdin"],
+        conflicts_with_all = ["file_option", "stdin"]
+    )]
+    pub file: Option<String>,
+
+    /// Path to the PHP file to be parsed, as given with the `--file` option of the previous versions.
+    #[arg(id = "file_option", long = "file", short = 'f', value_name = "FILE", hide = true, conflicts_with = "stdin")]
+    pub file_option: Option<String>,
+
+    /// Read the source to parse from stdin, instead of a file.
+    #[arg(long, help = "read the source to parse from stdin")]
+    pub stdin: bool,
+
+    /// The format in which the AST is printed.
+    #[arg(
+        long,
+        default_value_t,
+        help = "choose the format in which the AST is printed",
+        ignore_case = true,
+        value_parser = enum_variants!(AstFormat)
+    )]
+    pub format: AstFormat,
+
+    /// Print the AST in JSON format, as an alias of `--format json`.
+    #[arg(long, hide = true, conflicts_with = "format")]
+    pub json: bool,
+
+    /// Include the spans of the nodes in the output.
+    #[arg(long, help = "include the byte offsets of the nodes in the output")]
+    pub include_spans: bool,
 
     /// Include resolved names in the output.
     #[arg(long, help = "include resolved names in the output to show symbol resolution")]
     pub include_names: bool,
 
-    /// Output the AST in JSON format for integration with other tools.
-    #[arg(long, help = "output the AST in JSON format")]
-    pub json: bool,
+    /// Treat `<?` as an opening tag, as with the `short_open_tag` ini setting of PHP.
+    #[arg(long, help = "treat `<?` as an opening tag, instead of inline text")]
+    pub short_open_tags: bool,
 
     /// Specify where the results should be reported.
     #[arg(
@@ -63,6 +115,22 @@ pub struct AstCommand {
     pub reporting_format: ReportingFormat,
 }
 
+impl AstCommand {
+    /// Returns the path of the file to parse, given either as an argument or with `--file`.
+    fn file(&self) -> Option<&String> {
+        self.file.as_ref().or(self.file_option.as_ref())
+    }
+
+    /// Returns the format in which the AST is printed, taking `--json` into account.
+    fn format(&self) -> AstFormat {
+        if self.json {
+            AstFormat::Json
+        } else {
+            self.format
+        }
+    }
+}
+
 /// Executes the AST command with the provided options.
 ///
 /// # Arguments
@@ -75,66 +143,101 @@ pub struct AstCommand {
 ///
 /// # Errors
 ///
-/// An error is returned if the file does not exist or is not readable.
-pub async fn execute(command: AstCommand) -> Result<ExitCode, Error> {
-    let file_path = std::path::Path::new(&command.file).to_path_buf();
+/// An error is returned if the file, or stdin, is not readable.
+pub async fn execute(command: AstCommand, color: ColorChoice) -> Result<ExitCode, Error> {
+    // Initialize interner and source manager.
+    let interner = ThreadedInterner::new();
+    let source_manager = SourceManager::new(interner.clone());
 
-    // Verify if the file exists and is readable.
-    if !file_path.exists() {
-        mago_feedback::error!("File '{}' does not exist.", command.file);
+    // Load the source file, or the source read from stdin.
+    let source_id = match command.file() {
+        Some(file) => {
+            let file_path = std::path::Path::new(file).to_path_buf();
 
-        return Ok(ExitCode::FAILURE);
-    }
+            // Verify if the file exists and is readable.
+            if !file_path.exists() {
+                mago_feedback::error!("File '{}' does not exist.", file);
 
-    if !file_path.is_file() {
-        mago_feedback::error!("The path '{}' is not a file.", command.file);
+                return Ok(ExitCode::FAILURE);
+            }
 
-        return Ok(ExitCode::FAILURE);
-    }
+            if !file_path.is_file() {
+                mago_feedback::error!("The path '{}' is not a file.", file);
 
-    // Initialize interner and source manager.
-    let interner = ThreadedInterner::new();
-    let source_manager = SourceManager::new(interner.clone());
+                return Ok(ExitCode::FAILURE);
+            }
+
+            source_manager.insert_path(file.clone(), file_path, true)
+        }
+        None => {
+            let content = std::io::read_to_string(std::io::stdin()).map_err(Error::ReadingStdin)?;
+
+            source_manager.insert_content(STDIN_SOURCE_NAME.to_string(), content, true)
+        }
+    };
 
-    // Load the source file.
-    let source_id = source_manager.insert_path(command.file.clone(), file_path, true);
     let source = source_manager.load(&source_id)?;
 
     // Parse the source file into an AST.
-    let (ast, error) = parse_source(&interner, &source);
+    let settings = ParserSettings { short_open_tags: command.short_open_tags };
+    let (ast, errors) = parse_source_with_settings(&interner, &source, settings);
 
-    let has_error = error.is_some();
-    if command.json {
-        // Prepare and display JSON output.
-        let result = json!({
-            "interner": interner.all().into_iter().collect::<Vec<_>>(),
-            "program": ast,
-            "error": error.map(|e| Into::<Issue>::into(&e)),
-        });
+    let has_error = !errors.is_empty();
+    match command.format() {
+        AstFormat::Json => {
+            let issues = errors.iter().map(Into::<Issue>::into).collect::<Vec<_>>();
+            let mut result = interner
+                .with_resolved_serialization(|| serde_json::to_value(json!({ "program": ast, "errors": issues })))?;
 
-        println!("{}", serde_json::to_string_pretty(&result)?);
-    } else {
-        // Display the AST as a tree.
-        let tree = node_to_tree(Node::Program(&ast));
-
-        println!("{tree}");
+            if !command.include_spans {
+                remove_spans(&mut result["program"]);
+            }
 
-        if command.include_names {
-            let names = Names::resolve(&interner, &ast);
+            if command.include_names {
+                let names = Names::resolve(&interner, &ast);
 
-            for (position, (value, is_imported)) in names.all() {
-                let name = interner.lookup(value);
+                let mut names = names.all().into_iter().collect::<Vec<_>>();
+                names.sort_by_key(|(position, _)| **position);
 
-                println!("{}: {}{}", position, name, if *is_imported { " (imported)" } else { "" });
+                result["names"] = names
+                    .into_iter()
+                    .map(|(position, (value, resolution))| {
+                        json!({ "offset": position, "name": interner.lookup(value), "resolution": resolution })
+                    })
+                    .collect();
             }
+
+            println!("{}", serde_json::to_string_pretty(&result)?);
         }
+        AstFormat::Tree => {
+            // Display the AST as a tree.
+            let content = interner.lookup(&source.content);
+            let tree = node_to_tree(Node::Program(&ast), content, command.include_spans);
+
+            println!("{tree}");
+
+            if command.include_names {
+                let names = Names::resolve(&interner, &ast);
 
-        // Report errors if any exist.
-        if let Some(error) = &error {
-            let issue = Into::<Issue>::into(error);
+                for (position, (value, resolution)) in names.all() {
+                    let name = interner.lookup(value);
+
+                    println!("{}: {} ({})", position, name, resolution);
+                }
+            }
 
-            Reporter::new(interner.clone(), source_manager, command.reporting_target)
-                .report([issue], command.reporting_format)?;
+            // Report errors if any exist.
+            if has_error {
+                let issues = errors.iter().map(Into::<Issue>::into).collect::<Vec<_>>();
+
+                Reporter::new(
+                    interner.clone(),
+                    source_manager,
+                    command.reporting_target,
+                    color.for_reporter(command.reporting_target),
+                )
+                .report(issues, command.reporting_format)?;
+            }
         }
     }
 
@@ -143,18 +246,93 @@ pub async fn execute(command: AstCommand) -> Result<ExitCode, Error> {
 
 /// Converts an AST node into a tree structure for visualization.
 ///
+/// Leaf nodes, such as identifiers and literals, are labeled with their source text.
+///
 /// # Arguments
 ///
 /// * `node` - The AST node to be converted into a tree.
+/// * `content` - The content of the source the node was parsed from.
+/// * `include_spans` - Whether to label the nodes with their byte offsets.
 ///
 /// # Returns
 ///
 /// A `Tree` representation of the AST node and its children.
-fn node_to_tree(node: Node<'_>) -> Tree<NodeKind> {
-    let mut tree = Tree::new(node.kind());
-    for child in node.children() {
-        tree.push(node_to_tree(child));
+fn node_to_tree(node: Node<'_>, content: &str, include_spans: bool) -> Tree<String> {
+    let span = node.span();
+    let children = node.children();
+
+    let mut label = node.kind().to_string();
+    if children.is_empty() {
+        if let Some(text) = content.get(span.start.offset..span.end.offset) {
+            label.push_str(&format!(" {:?}", text));
+        }
+    }
+
+    if include_spans {
+        label.push_str(&format!(" @ {}..{}", span.start.offset, span.end.offset));
+    }
+
+    let mut tree = Tree::new(label);
+    for child in children {
+        tree.push(node_to_tree(child, content, include_spans));
     }
 
     tree
 }
+
+/// Removes the spans from the given serialized AST.
+///
+/// Spans are serialized as objects with a `start` and an `end` position, each of which has an `offset`.
+fn remove_spans(value: &mut Value) {
+    let is_position = |value: &Value| value.get("offset").is_some_and(Value::is_u64);
+    let is_span = |value: &Value| match value.as_object() {
+        Some(object) => {
+            object.len() == 2
+                && object.get("start").is_some_and(is_position)
+                && object.get("end").is_some_and(is_position)
+        }
+        None => false,
+    };
+
+    match value {
+        Value::Object(object) => {
+            object.retain(|_, value| !is_span(value));
+            object.values_mut().for_each(remove_spans);
+        }
+        Value::Array(array) => {
+            array.retain(|value| !is_span(value));
+            array.iter_mut().for_each(remove_spans);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(arguments: &[&str]) -> Result<AstCommand, clap::Error> {
+        AstCommand::try_parse_from(std::iter::once("ast").chain(arguments.iter().copied()))
+    }
+
+    #[test]
+    fn test_file_is_given_as_an_argument_or_with_an_option() {
+        assert_eq!(command(&["foo.php"]).unwrap().file().map(String::as_str), Some("foo.php"));
+        assert_eq!(command(&["--file", "foo.php"]).unwrap().file().map(String::as_str), Some("foo.php"));
+        assert_eq!(command(&["-f", "foo.php"]).unwrap().file().map(String::as_str), Some("foo.php"));
+        assert_eq!(command(&["--stdin"]).unwrap().file(), None);
+
+        assert!(command(&[]).is_err());
+        assert!(command(&["foo.php", "--file", "bar.php"]).is_err());
+        assert!(command(&["--file", "foo.php", "--stdin"]).is_err());
+    }
+
+    #[test]
+    fn test_json_is_an_alias_of_the_json_format() {
+        assert_eq!(command(&["foo.php"]).unwrap().format(), AstFormat::Tree);
+        assert_eq!(command(&["foo.php", "--format", "json"]).unwrap().format(), AstFormat::Json);
+        assert_eq!(command(&["foo.php", "--json"]).unwrap().format(), AstFormat::Json);
+
+        assert!(command(&["foo.php", "--json", "--format", "tree"]).is_err());
+    }
+}
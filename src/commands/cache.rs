@@ -0,0 +1,53 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use clap::Subcommand;
+
+use mago_feedback::info;
+
+use crate::cache;
+use crate::config::Configuration;
+use crate::error::Error;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "cache",
+    about = "manage the cache of the reflections of the sources",
+    long_about = r#"
+The `cache` command manages the cache in which Mago stores the reflections of the sources.
+
+The reflections of the sources whose content did not change, such as the dependencies, are loaded from
+the cache instead of parsing the sources again. The cache is configured in the `[cache]` section of the
+configuration, and can be bypassed with the `--no-cache` option.
+
+The entries which were not used by a `lint`, or `fix` run, such as those of the previous content of the
+changed sources, are removed after the run. The `clear` subcommand removes all the entries at once.
+"#
+)]
+pub struct CacheCommand {
+    #[command(subcommand)]
+    pub command: CacheSubcommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheSubcommand {
+    /// Remove all the entries of the cache.
+    #[command(name = "clear", about = "remove all the entries of the cache")]
+    Clear,
+}
+
+pub fn execute(command: CacheCommand, configuration: Configuration) -> Result<ExitCode, Error> {
+    match command.command {
+        CacheSubcommand::Clear => {
+            let directory = &configuration.cache.directory;
+
+            if cache::clear(directory)? {
+                info!("Cleared the cache at `{}`.", directory.display());
+            } else {
+                info!("The cache at `{}` is already empty.", directory.display());
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,212 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use mago_interner::ThreadedInterner;
+use mago_reflection::query::Symbol;
+use mago_reflection::query::SymbolKind;
+use mago_reflection::query::SymbolQuery;
+use mago_reporting::reporter::Reporter;
+use mago_reporting::reporter::ReportingFormat;
+use mago_reporting::reporter::ReportingTarget;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+
+use crate::cache::ReflectionCache;
+use crate::color::ColorChoice;
+use crate::config::Configuration;
+use crate::enum_variants;
+use crate::error::Error;
+use crate::reflection::reflect_all_user_defined_sources;
+use crate::reflection::ExternalReflection;
+use crate::source;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "find",
+    about = "find the class-likes, functions, constants, and members of the codebase by name",
+    long_about = r#"
+The `find` command reflects the codebase, and lists the symbols whose names match the given query, along with
+the location of their declarations.
+
+The query is a fully qualified name, such as `App\Service\UserRepository`, or a class-like name and a member
+name separated by `::`, such as `App\Service\UserRepository::find`. In names, `*` matches any sequence of
+characters, including namespace separators, and `?` matches any single character.
+
+Class-likes, and their members, are looked up unless `--function` or `--constant` is given. Each symbol is
+reported as a note, so that the results can be written in any of the reporting formats, such as `json`.
+
+Anonymous classes, closures, and arrow functions are only looked up with `--include-anonymous`, under the names
+synthesized from their locations, such as `anonymous-class@src/Kernel.php:120-480`, or `closure@src/Kernel.php:*`.
+"#
+)]
+pub struct FindCommand {
+    /// The name, or pattern, of the symbols to find.
+    #[arg(help = "the name, or pattern, of the symbols to find, such as `App\\*Repository` or `App\\Foo::bar`")]
+    pub query: String,
+
+    /// Look up class-likes, in addition to the other selected kinds of symbols.
+    #[arg(long, help = "look up classes, interfaces, enums, and traits, and their members")]
+    pub class_like: bool,
+
+    /// Look up functions, instead of class-likes.
+    #[arg(long, help = "look up functions, instead of class-likes")]
+    pub function: bool,
+
+    /// Look up constants, instead of class-likes.
+    #[arg(long, help = "look up constants, instead of class-likes")]
+    pub constant: bool,
+
+    /// Compare names regardless of the case of their letters.
+    #[arg(long, short = 'i', help = "compare names regardless of the case of their letters")]
+    pub ignore_case: bool,
+
+    /// Look up anonymous classes, closures, and arrow functions, under the names synthesized from their locations.
+    #[arg(long, help = "look up anonymous classes, closures, and arrow functions, such as `closure@src/Kernel.php:*`")]
+    pub include_anonymous: bool,
+
+    /// Only look up the symbols declared in the project sources, not in the dependencies, nor in the stubs.
+    #[arg(long, help = "only look up the symbols declared in the project sources")]
+    pub user_defined_only: bool,
+
+    /// Import the reflection written by `mago reflect`, instead of reflecting the external sources.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "user_defined_only",
+        help = "import the reflection written by `mago reflect --output`, instead of reflecting the dependencies and stubs"
+    )]
+    pub import_reflection: Option<PathBuf>,
+
+    /// Specify where the results should be reported.
+    #[arg(
+        long,
+        default_value_t,
+        help = "specify where the results should be reported",
+        ignore_case = true,
+        value_parser = enum_variants!(ReportingTarget)
+    )]
+    pub reporting_target: ReportingTarget,
+
+    /// Choose the format for reporting the symbols.
+    #[arg(
+        long,
+        default_value_t = ReportingFormat::Short,
+        help = "choose the format for reporting the symbols",
+        ignore_case = true,
+        value_parser = enum_variants!(ReportingFormat)
+    )]
+    pub reporting_format: ReportingFormat,
+}
+
+impl FindCommand {
+    /// Returns the kinds of symbols selected by the command.
+    fn kinds(&self) -> Vec<SymbolKind> {
+        let mut kinds = vec![];
+        if self.class_like || (!self.function && !self.constant) {
+            kinds.extend(SymbolKind::class_likes());
+            kinds.extend(SymbolKind::members());
+        }
+
+        if self.function {
+            kinds.push(SymbolKind::Function);
+        }
+
+        if self.constant {
+            kinds.push(SymbolKind::Constant);
+        }
+
+        kinds
+    }
+
+    /// Returns the query of the symbols selected by the command.
+    fn query(&self) -> SymbolQuery {
+        SymbolQuery::new(&self.query)
+            .with_kinds(self.kinds())
+            .with_case_insensitivity(self.ignore_case)
+            .with_user_defined_only(self.user_defined_only)
+            .with_anonymous(self.include_anonymous)
+    }
+}
+
+pub async fn execute(
+    command: FindCommand,
+    configuration: Configuration,
+    color: ColorChoice,
+) -> Result<ExitCode, Error> {
+    // Neither the stubs, nor the dependencies, are reflected when only the project sources are looked up.
+    let external = ExternalReflection::new(!command.user_defined_only, command.import_reflection.as_deref());
+    let interner = ThreadedInterner::new();
+    let manager = source::load(&interner, &configuration.source, external.is_available(), &[]).await?;
+
+    let cache = ReflectionCache::new(&configuration.cache);
+    let codebase = external.load(&interner, &manager, &configuration.source, configuration.php_version, &cache).await?;
+
+    let user_defined =
+        reflect_all_user_defined_sources(&interner, &manager, &configuration.source, configuration.php_version, &cache)
+            .await?;
+    let codebase = mago_reflector::merge(&interner, codebase, user_defined);
+
+    let symbols = command.query().find(&interner, &codebase);
+    if symbols.is_empty() {
+        mago_feedback::info!("No symbols match `{}`.", command.query);
+
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let issues = symbols.iter().map(|symbol| symbol_issue(&interner, symbol)).collect::<Vec<_>>();
+
+    // The symbols are reported in the order of their names, instead of the order of their locations.
+    Reporter::new(interner, manager, command.reporting_target, color.for_reporter(command.reporting_target))
+        .with_sorting(false)
+        .report(issues, command.reporting_format)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Creates the note reporting the given symbol, annotating its name in its declaration.
+fn symbol_issue(interner: &ThreadedInterner, symbol: &Symbol<'_>) -> Issue {
+    let kind = symbol.kind();
+
+    Issue::note(format!("{} `{}`", kind, symbol.name(interner)))
+        .with_code(format!("symbol/{}", kind.as_str().replace(' ', "-")))
+        .with_annotation(Annotation::primary(symbol.span()).with_message(format!("The {} is declared here.", kind)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(arguments: &[&str]) -> FindCommand {
+        FindCommand::parse_from(std::iter::once("find").chain(arguments.iter().copied()))
+    }
+
+    #[test]
+    fn test_class_likes_and_members_are_looked_up_by_default() {
+        let kinds = command(&["App\\*"]).kinds();
+
+        assert!(kinds.contains(&SymbolKind::Class));
+        assert!(kinds.contains(&SymbolKind::Method));
+        assert!(!kinds.contains(&SymbolKind::Function));
+    }
+
+    #[test]
+    fn test_kinds_are_selected_by_flags() {
+        assert_eq!(command(&["--function", "array_*"]).kinds(), [SymbolKind::Function]);
+        assert_eq!(command(&["--function", "--constant", "E_*"]).kinds(), [SymbolKind::Function, SymbolKind::Constant]);
+        assert!(command(&["--class-like", "--function", "Foo"]).kinds().contains(&SymbolKind::Trait));
+    }
+
+    #[test]
+    fn test_anonymous_symbols_are_looked_up_with_a_flag() {
+        let kinds = command(&["*"]).kinds();
+
+        assert_eq!(command(&["*"]).query(), SymbolQuery::new("*").with_kinds(kinds.clone()));
+        assert_eq!(
+            command(&["--include-anonymous", "*"]).query(),
+            SymbolQuery::new("*").with_kinds(kinds).with_anonymous(true)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::io::BufRead;
+use std::io::Write;
+
+use mago_fixer::SafetyClassification;
+use mago_interner::ThreadedInterner;
+use mago_source::line_index::ColumnEncoding;
+use mago_source::SourceManager;
+
+use crate::commands::fix::Fix;
+use crate::error::Error;
+use crate::utils;
+
+/// The answer given to the prompt shown for every fix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    /// Apply the fix.
+    Yes,
+    /// Skip the fix.
+    No,
+    /// Apply the fix, and every remaining fix of the same file.
+    All,
+    /// Skip the fix, and every remaining fix.
+    Quit,
+}
+
+/// Shows every fix, grouped by file, and asks whether it should be applied.
+///
+/// Fixes are shown as a diff of the changes they make on their own, and the accepted fixes are
+/// returned so they can be merged and applied per file, like in batch mode. Quitting, or reaching
+/// the end of the input, keeps the fixes accepted so far.
+pub(super) fn review(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    mut fixes: Vec<Fix>,
+    mut input: impl BufRead,
+    mut output: impl Write,
+    use_colors: bool,
+) -> Result<Vec<Fix>, Error> {
+    fixes.sort_by_cached_key(|fix| {
+        let offset =
+            fix.issue.annotations.iter().find(|annotation| annotation.is_primary()).map(|a| a.span.start.offset);
+
+        (interner.lookup(&fix.source.0).to_string(), offset)
+    });
+
+    let total = fixes.len();
+    let mut accepted = Vec::with_capacity(total);
+    let mut accept_all_in = None;
+    for (index, fix) in fixes.into_iter().enumerate() {
+        if accept_all_in == Some(fix.source) {
+            accepted.push(fix);
+
+            continue;
+        }
+
+        let source = manager.load(&fix.source)?;
+        let name = interner.lookup(&source.identifier.0);
+        let original = interner.lookup(&source.content);
+        let fixed = fix.plan.execute(original).get_fixed();
+
+        let location = match fix.issue.annotations.iter().find(|annotation| annotation.is_primary()) {
+            Some(annotation) => {
+                let position = manager.line_column(
+                    &source.identifier,
+                    annotation.span.start.offset,
+                    ColumnEncoding::Utf8CodePoints,
+                )?;
+                let (line, column) = (position.line + 1, position.column + 1);
+
+                format!("{}:{}:{}", name, line, column)
+            }
+            None => name.to_string(),
+        };
+
+        let header = format!(
+            "[{}/{}] {}: {}\n  --> {} ({})",
+            index + 1,
+            total,
+            fix.issue.code.as_deref().unwrap_or("other"),
+            fix.issue.message,
+            location,
+            safety_name(fix.plan.get_maximum_safety_classification())
+        );
+
+        writeln!(output, "\n{}", header).map_err(Error::Prompting)?;
+        write!(output, "{}", utils::unified_diff(name, original, &fixed, use_colors)).map_err(Error::Prompting)?;
+
+        match prompt(&mut input, &mut output).map_err(Error::Prompting)? {
+            Answer::Yes => accepted.push(fix),
+            Answer::No => {}
+            Answer::All => {
+                accept_all_in = Some(fix.source);
+                accepted.push(fix);
+            }
+            Answer::Quit => break,
+        }
+    }
+
+    Ok(accepted)
+}
+
+/// Asks whether a fix should be applied, until a valid answer is given.
+///
+/// Reaching the end of the input is treated as quitting.
+fn prompt(input: &mut impl BufRead, output: &mut impl Write) -> std::io::Result<Answer> {
+    loop {
+        write!(output, "Apply this fix? [y]es / [n]o / [a]ll in this file / [q]uit: ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+
+            return Ok(Answer::Quit);
+        }
+
+        if let Some(answer) = parse_answer(&line) {
+            return Ok(answer);
+        }
+    }
+}
+
+fn parse_answer(line: &str) -> Option<Answer> {
+    match line.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => Some(Answer::Yes),
+        "n" | "no" => Some(Answer::No),
+        "a" | "all" => Some(Answer::All),
+        "q" | "quit" => Some(Answer::Quit),
+        _ => None,
+    }
+}
+
+fn safety_name(safety: Option<SafetyClassification>) -> &'static str {
+    match safety {
+        Some(SafetyClassification::Safe) | None => "safe",
+        Some(SafetyClassification::PotentiallyUnsafe) => "potentially unsafe",
+        Some(SafetyClassification::Unsafe) => "unsafe",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_fixer::FixPlan;
+    use mago_reporting::Annotation;
+    use mago_reporting::Issue;
+    use mago_span::Position;
+    use mago_span::Span;
+
+    use super::*;
+
+    fn fixes(manager: &SourceManager) -> Vec<Fix> {
+        let a = manager.insert_content("a.php".to_string(), "<?php $a = (1); $b = (2);".to_string(), true);
+        let b = manager.insert_content("b.php".to_string(), "<?php $c = (3);".to_string(), true);
+
+        [(b, 11), (a, 21), (a, 11)]
+            .into_iter()
+            .map(|(source, offset)| {
+                let mut plan = FixPlan::new();
+                plan.delete(offset..offset + 1, SafetyClassification::Safe);
+                plan.delete(offset + 2..offset + 3, SafetyClassification::Safe);
+
+                let span = Span::new(Position::new(source, offset), Position::new(source, offset + 3));
+                let issue = Issue::help(format!("Redundant parentheses at {}.", offset))
+                    .with_code("redundancy/redundant-parentheses")
+                    .with_annotation(Annotation::primary(span));
+
+                Fix { source, issue, plan }
+            })
+            .collect()
+    }
+
+    fn accepted(answers: &str) -> (Vec<String>, String) {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+
+        let mut output = vec![];
+        let accepted = review(&interner, &manager, fixes(&manager), answers.as_bytes(), &mut output, false).unwrap();
+
+        (accepted.into_iter().map(|fix| fix.issue.message).collect(), String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_review_groups_fixes_by_file() {
+        let (accepted, output) = accepted("y\nn\ny\n");
+
+        assert_eq!(accepted, vec!["Redundant parentheses at 11.", "Redundant parentheses at 11."]);
+        assert!(output
+            .contains("[1/3] redundancy/redundant-parentheses: Redundant parentheses at 11.\n  --> a.php:1:12 (safe)"));
+        assert!(output.contains("--- a/a.php\n+++ b/a.php\n"));
+        assert!(output.contains("+<?php $a = 1; $b = (2);"));
+        assert!(output
+            .contains("[3/3] redundancy/redundant-parentheses: Redundant parentheses at 11.\n  --> b.php:1:12 (safe)"));
+    }
+
+    #[test]
+    fn test_review_accepts_all_fixes_in_file() {
+        let (accepted, output) = accepted("a\nn\n");
+
+        assert_eq!(accepted, vec!["Redundant parentheses at 11.", "Redundant parentheses at 21."]);
+        assert!(!output.contains("[2/3]"));
+    }
+
+    #[test]
+    fn test_review_quits_and_keeps_accepted_fixes() {
+        assert_eq!(accepted("y\nq\n").0, vec!["Redundant parentheses at 11."]);
+        assert_eq!(accepted("y\n").0, vec!["Redundant parentheses at 11."]);
+    }
+
+    #[test]
+    fn test_review_asks_again_on_invalid_answers() {
+        let (accepted, output) = accepted("maybe\n\nYES\nno\nq\n");
+
+        assert_eq!(accepted, vec!["Redundant parentheses at 11."]);
+        assert_eq!(output.matches("Apply this fix?").count(), 5);
+    }
+}
@@ -0,0 +1,919 @@
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use ahash::HashMap;
+use ahash::HashSet;
+use clap::Parser;
+
+use mago_ast::compare::structural_diff;
+use mago_ast::compare::structural_node_diff;
+use mago_ast::compare::CompareOptions;
+use mago_ast::Node;
+use mago_ast::Program;
+use mago_feedback::create_progress_bar;
+use mago_feedback::remove_progress_bar;
+use mago_feedback::ProgressBarTheme;
+use mago_fixer::FixOperation;
+use mago_fixer::FixPlan;
+use mago_fixer::SafetyClassification;
+use mago_interner::ThreadedInterner;
+use mago_lexer::input::Input;
+use mago_parser::parse_with_settings;
+use mago_parser::settings::ParserSettings;
+use mago_reporting::reporter::ReportingTarget;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_source::Source;
+use mago_source::SourceIdentifier;
+use mago_source::SourceManager;
+use mago_span::HasSpan;
+use mago_span::Position;
+use mago_span::Span;
+
+use crate::color::ColorChoice;
+use crate::commands::lint::lint_sources;
+use crate::commands::lint::RuleSelection;
+use crate::config::Configuration;
+use crate::error::Error;
+use crate::reflection::ExternalReflection;
+use crate::source;
+use crate::utils;
+
+mod interactive;
+mod stdin;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "fix",
+    about = "apply fixes for lint issues identified during linting",
+    long_about = r#"
+The `fix` command automatically applies fixes for issues identified during the linting process.
+
+This command streamlines the process of addressing lint issues, improving code quality and consistency.
+"#
+)]
+pub struct FixCommand {
+    /// The files or directories to fix, instead of all the configured sources.
+    #[arg(value_name = "PATH", help = "the files or directories to fix, defaults to all the configured sources")]
+    pub sources: Vec<PathBuf>,
+
+    /// Only apply the fixes of the given rules.
+    #[arg(
+        long,
+        value_name = "RULE",
+        help = "only apply the fixes of the given rule, can be repeated (e.g. `redundancy/redundant-parentheses`)"
+    )]
+    pub only: Vec<String>,
+
+    /// Apply fixes that are marked as unsafe, including potentially unsafe fixes.
+    #[arg(
+        long,
+        short = 'u',
+        help = "apply fixes marked as unsafe, including those with potentially destructive changes"
+    )]
+    pub r#unsafe: bool,
+
+    /// Apply fixes that are marked as potentially unsafe.
+    #[arg(long, short = 'p', help = "apply fixes marked as potentially unsafe, which may require manual review")]
+    pub potentially_unsafe: bool,
+
+    /// Run the command without writing any changes to disk.
+    #[arg(long, short = 'd', help = "preview the fixes without applying them, showing what changes would be made")]
+    pub dry_run: bool,
+
+    /// Review every fix before applying it.
+    #[arg(long, short = 'i', conflicts_with = "dry_run", help = "review every fix, and choose which ones to apply")]
+    pub interactive: bool,
+
+    /// The maximum number of lint and fix passes to make.
+    ///
+    /// Fixes overlapping with other fixes are skipped, and get another chance in the next pass, on the updated
+    /// sources. Passes stop early once no fix is skipped, no change is made, or the same fixes keep overlapping.
+    #[arg(
+        long,
+        default_value_t = 3,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "the maximum number of passes to make, re-linting the fixed sources to apply skipped overlapping fixes",
+        long_help = "the maximum number of passes to make, re-linting the fixed sources to apply skipped overlapping \
+                     fixes\n\n\
+                     fixes overlapping with other fixes are skipped, and get another chance in the next pass, on the \
+                     updated sources; passes stop early once no fix is skipped, no change is made, or the same fixes \
+                     keep overlapping"
+    )]
+    pub max_iterations: u32,
+
+    /// Read the source to fix from stdin, and write the fixed source to stdout.
+    ///
+    /// The remaining issues are reported on stderr, and the full source is written to stdout even when
+    /// no fix is applied, so that editors can always replace their buffer with the output.
+    #[arg(
+        long,
+        conflicts_with_all = ["sources", "dry_run", "interactive"],
+        help = "read the source to fix from stdin, and write the fixed source to stdout",
+        long_help = "read the source to fix from stdin, and write the fixed source to stdout\n\n\
+                     the remaining issues are reported on stderr, and the full source is written to stdout even when \
+                     no fix is applied, so that editors can always replace their buffer with the output"
+    )]
+    pub stdin: bool,
+
+    /// The path of the source read from stdin, used by path based rules and configuration.
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "stdin",
+        help = "the path of the source read from stdin, relative to the current directory (e.g. `src/Foo.php`)"
+    )]
+    pub stdin_path: Option<PathBuf>,
+
+    /// Keep a copy of every modified file, with the given suffix appended to its name.
+    #[arg(
+        long,
+        value_name = "SUFFIX",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = ".bak",
+        conflicts_with_all = ["dry_run", "stdin"],
+        help = "keep a copy of every modified file, named after it with the given suffix appended [default: .bak]"
+    )]
+    pub backup: Option<String>,
+
+    /// Overwrite existing backup files.
+    #[arg(long, requires = "backup", help = "overwrite existing backup files")]
+    pub force: bool,
+
+    /// Check that the fixed sources still parse, and that their fixes only changed the code they target.
+    ///
+    /// The fixes of a source are only applied if its fixed code has no more parse errors than before, and
+    /// its AST only differs from the original one within the ranges changed by the fixes.
+    #[arg(
+        long,
+        help = "check that the fixed sources still parse, and that only the code targeted by fixes changed",
+        long_help = "check that the fixed sources still parse, and that only the code targeted by fixes changed\n\n\
+                     the fixes of a source are only applied if its fixed code has no more parse errors than before, \
+                     and its AST only differs from the original one within the ranges changed by the fixes"
+    )]
+    pub verify_fixes: bool,
+}
+
+/// A fix suggested for an issue, limited to the operations allowed by the requested safety classification.
+struct Fix {
+    source: SourceIdentifier,
+    issue: Issue,
+    plan: FixPlan,
+}
+
+/// The location and rule of a fix, as shown in the summary of the command.
+#[derive(Debug)]
+struct FixDescription {
+    source: String,
+    line: usize,
+    code: String,
+    message: String,
+}
+
+/// A fix that was skipped, because it overlaps with another fix applied to the same source.
+#[derive(Debug)]
+struct Conflict {
+    skipped: FixDescription,
+    applied: FixDescription,
+}
+
+/// The outcome of the lint and fix passes.
+struct Passes {
+    /// The content of every changed source, before any fix was applied.
+    originals: HashMap<SourceIdentifier, Source>,
+    /// The number of applied fixes, per rule.
+    applied_fixes: BTreeMap<String, usize>,
+    skipped_unsafe: usize,
+    skipped_potentially_unsafe: usize,
+    /// The fixes skipped by the last pass, because they overlap with other fixes.
+    conflicts: Vec<Conflict>,
+    /// The sources whose fixes were not applied, because they could not be verified, along with the reason.
+    rejected: Vec<(SourceIdentifier, String, String)>,
+    /// Why no further pass was made, if some fixes were still skipped.
+    stop_reason: StopReason,
+}
+
+/// The reason why no further pass was made while some fixes were still skipped.
+#[derive(Debug, Clone, Copy)]
+enum StopReason {
+    Interactive,
+    Unchanged,
+    Repeated,
+    MaximumIterations(u32),
+}
+
+impl FixCommand {
+    pub fn get_classification(&self) -> SafetyClassification {
+        if self.r#unsafe {
+            SafetyClassification::Unsafe
+        } else if self.potentially_unsafe {
+            SafetyClassification::PotentiallyUnsafe
+        } else {
+            SafetyClassification::Safe
+        }
+    }
+}
+
+pub async fn execute(command: FixCommand, configuration: Configuration, color: ColorChoice) -> Result<ExitCode, Error> {
+    if command.interactive && !std::io::stdin().is_terminal() {
+        mago_feedback::error!(
+            "The interactive mode requires a terminal. To preview the fixes without applying them, use the `--dry-run` flag."
+        );
+
+        return Ok(ExitCode::FAILURE);
+    }
+
+    // Unknown rules are reported before loading the sources, so that no file is touched because of a typo.
+    let selection = match RuleSelection::new(&command.only, &[]) {
+        Ok(selection) => selection,
+        Err(error) => {
+            mago_feedback::error!("{}", error);
+
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    if command.stdin {
+        return stdin::fix_stdin(&command, &configuration, &selection, color).await;
+    }
+
+    let selection_paths = match source::canonicalize_selection(&command.sources) {
+        Ok(paths) => paths,
+        Err(path) => {
+            mago_feedback::error!("Path '{}' does not exist.", path.display());
+
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    // Initialize the interner for managing identifiers.
+    let interner = ThreadedInterner::new();
+    // Load sources, the sources that are not selected are loaded as external sources, and are therefore not fixed.
+    let source_manager = source::load(&interner, &configuration.source, true, &selection_paths).await?;
+
+    let passes = run_passes(&interner, &source_manager, &configuration, &selection, &command, color).await?;
+    let use_colors = color.use_colors(ReportingTarget::Stdout);
+
+    // Changes are printed in a deterministic order, so that the diffs of a dry run are always the same.
+    let mut changed_sources: Vec<Source> = passes
+        .originals
+        .values()
+        .filter(|original| {
+            source_manager.load(&original.identifier).is_ok_and(|current| current.content != original.content)
+        })
+        .cloned()
+        .collect();
+    changed_sources.sort_by(|a, b| interner.lookup(&a.identifier.0).cmp(interner.lookup(&b.identifier.0)));
+
+    if !command.dry_run && !write_sources(&source_manager, &changed_sources, &command)? {
+        return Ok(ExitCode::FAILURE);
+    }
+
+    if command.dry_run {
+        for original in changed_sources.iter() {
+            let current = source_manager.load(&original.identifier)?;
+            let name = interner.lookup(&original.identifier.0);
+
+            print!(
+                "{}",
+                utils::unified_diff(
+                    name,
+                    interner.lookup(&original.content),
+                    interner.lookup(&current.content),
+                    use_colors
+                )
+            );
+        }
+    }
+
+    passes.warn_unapplied();
+
+    let changed_files = changed_sources.len();
+    if changed_files == 0 {
+        mago_feedback::info!("No fixes were applied");
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let total_fixes: usize = passes.applied_fixes.values().sum();
+    if command.dry_run {
+        mago_feedback::info!("Found {} fix(es) that would change {} file(s):", total_fixes, changed_files);
+    } else {
+        mago_feedback::info!("Applied {} fix(es), changing {} file(s):", total_fixes, changed_files);
+    }
+
+    for (code, fixes) in passes.applied_fixes.iter() {
+        mago_feedback::info!("  - {}: {} fix(es)", code, fixes);
+    }
+
+    Ok(if command.dry_run { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+/// Lints the sources, and applies the fixes found, until no overlapping fix is skipped anymore.
+///
+/// The fixed contents are only kept in the source manager, so that nothing is written to disk until
+/// every pass is done.
+async fn run_passes(
+    interner: &ThreadedInterner,
+    source_manager: &SourceManager,
+    configuration: &Configuration,
+    selection: &RuleSelection,
+    command: &FixCommand,
+    color: ColorChoice,
+) -> Result<Passes, Error> {
+    let use_colors = color.use_colors(ReportingTarget::Stdout);
+    // Rejected fixes would be suggested again by another pass, so fixes are only reviewed once.
+    let max_iterations = if command.interactive { 1 } else { command.max_iterations };
+
+    let mut passes = Passes {
+        originals: HashMap::default(),
+        applied_fixes: BTreeMap::new(),
+        skipped_unsafe: 0,
+        skipped_potentially_unsafe: 0,
+        conflicts: vec![],
+        rejected: vec![],
+        stop_reason: StopReason::MaximumIterations(max_iterations),
+    };
+
+    for _ in 0..max_iterations {
+        let (issues, _) =
+            lint_sources(interner, source_manager, configuration, selection, &ExternalReflection::Reflect, color, true)
+                .await?;
+
+        let mut fixes;
+        (fixes, passes.skipped_unsafe, passes.skipped_potentially_unsafe) =
+            filter_fixes(interner, issues, command.get_classification());
+
+        if command.interactive {
+            let stdin = std::io::stdin();
+
+            fixes = interactive::review(interner, source_manager, fixes, stdin.lock(), std::io::stdout(), use_colors)?;
+        }
+
+        // The fixes of rejected sources would be rejected again, as they are suggested for the same code.
+        let rejected: HashSet<SourceIdentifier> = passes.rejected.iter().map(|(source, _, _)| *source).collect();
+        fixes.retain(|fix| !rejected.contains(&fix.source));
+
+        let (fixes, conflicts) = resolve_conflicts(interner, source_manager, fixes)?;
+        let plans = merge_fixes(fixes);
+
+        let total = plans.len();
+        let progress_bar = create_progress_bar(
+            total,
+            "✨  Fixing",
+            ProgressBarTheme::Magenta,
+            color.use_colors(ReportingTarget::Stderr),
+        );
+        let mut handles = Vec::with_capacity(total);
+        for (source, plan, codes) in plans.into_iter() {
+            handles.push(tokio::spawn({
+                let source_manager = source_manager.clone();
+                let interner = interner.clone();
+                let progress_bar = progress_bar.clone();
+                let verify = command.verify_fixes.then(|| configuration.source.parser_settings());
+
+                async move {
+                    let source = source_manager.load(&source)?;
+                    let fixed = plan.execute(interner.lookup(&source.content)).get_fixed();
+                    let rejection =
+                        verify.and_then(|settings| verify_fixes(&interner, &source, &plan, &fixed, settings));
+
+                    progress_bar.inc(1);
+
+                    Result::<_, Error>::Ok((source, fixed, codes, rejection))
+                }
+            }));
+        }
+
+        let mut changed = false;
+        for handle in handles {
+            let (source, fixed, codes, rejection) = handle.await??;
+            if interner.lookup(&source.content) == fixed {
+                continue;
+            }
+
+            if let Some(reason) = rejection {
+                passes.rejected.push((source.identifier, interner.lookup(&source.identifier.0).to_string(), reason));
+
+                continue;
+            }
+
+            source_manager.update(source.identifier, fixed)?;
+
+            passes.originals.entry(source.identifier).or_insert(source);
+            for code in codes {
+                *passes.applied_fixes.entry(code).or_default() += 1;
+            }
+
+            changed = true;
+        }
+
+        remove_progress_bar(progress_bar);
+
+        let repeated = !conflicts.is_empty()
+            && conflicts.len() == passes.conflicts.len()
+            && conflicts.iter().zip(passes.conflicts.iter()).all(|(a, b)| a.is_same_as(b));
+
+        passes.conflicts = conflicts;
+        if passes.conflicts.is_empty() {
+            break;
+        }
+
+        passes.stop_reason = if command.interactive {
+            StopReason::Interactive
+        } else if !changed {
+            StopReason::Unchanged
+        } else if repeated {
+            StopReason::Repeated
+        } else {
+            continue;
+        };
+
+        break;
+    }
+
+    Ok(passes)
+}
+
+impl Passes {
+    /// Warns about the fixes that were not applied, and why.
+    fn warn_unapplied(&self) {
+        if self.skipped_unsafe > 0 {
+            mago_feedback::warn!(
+                "Skipped {} fixes because they were marked as unsafe. To apply those fixes, use the `--unsafe` flag.",
+                self.skipped_unsafe
+            );
+        }
+
+        if self.skipped_potentially_unsafe > 0 {
+            mago_feedback::warn!(
+                "Skipped {} fixes because they were marked as potentially unsafe. To apply those fixes, use the `--potentially-unsafe` flag.",
+                self.skipped_potentially_unsafe
+            );
+        }
+
+        if !self.conflicts.is_empty() {
+            mago_feedback::warn!(
+                "Skipped {} fix(es) because they overlap with other fixes, {}:",
+                self.conflicts.len(),
+                self.stop_reason.describe()
+            );
+
+            for conflict in self.conflicts.iter() {
+                mago_feedback::warn!("  - {}", conflict);
+            }
+        }
+
+        if !self.rejected.is_empty() {
+            mago_feedback::warn!(
+                "Skipped the fixes of {} file(s) because they could not be verified:",
+                self.rejected.len()
+            );
+
+            for (_, name, reason) in self.rejected.iter() {
+                mago_feedback::warn!("  - `{}`: {}", name, reason);
+            }
+        }
+    }
+}
+
+/// Checks that the given fixed content of the given source still parses, and that the code which is not
+/// targeted by the operations of the given plan is unchanged.
+///
+/// Returns why the fixes cannot be applied, if they cannot.
+fn verify_fixes(
+    interner: &ThreadedInterner,
+    source: &Source,
+    plan: &FixPlan,
+    fixed: &str,
+    settings: ParserSettings,
+) -> Option<String> {
+    let content = interner.lookup(&source.content);
+    let (original, original_errors) =
+        parse_with_settings(interner, Input::new(source.identifier, content.as_bytes()), settings);
+    let (fixed, fixed_errors) =
+        parse_with_settings(interner, Input::new(source.identifier, fixed.as_bytes()), settings);
+
+    if fixed_errors.len() > original_errors.len() {
+        return Some(format!("the fixed code cannot be parsed, {}", fixed_errors[original_errors.len()]));
+    }
+
+    let operations = plan.get_operations();
+    let is_targeted = |span: Span| {
+        operations
+            .iter()
+            .map(|operation| operation.get_range())
+            .any(|range| range.start <= span.end.offset && span.start.offset <= range.end)
+    };
+
+    // The differences are reported for the nodes at the same place in both trees, which are not always the
+    // same code: prepending `null|` to `int|string` nests the whole union differently. A node that is not
+    // targeted is therefore only considered changed if it cannot be found where the fixes moved it.
+    let is_moved = |span: Span| {
+        let Some(node) = find_node(&original, span, |_| true) else {
+            return false;
+        };
+
+        let shift: isize = operations
+            .iter()
+            .filter(|operation| operation.get_range().end <= span.start.offset)
+            .map(|operation| match operation {
+                FixOperation::Insert { text, .. } => text.len() as isize,
+                FixOperation::Replace { range, text, .. } => text.len() as isize - range.len() as isize,
+                FixOperation::Delete { range, .. } => -(range.len() as isize),
+            })
+            .sum();
+
+        let moved = Span::new(
+            Position::new(span.start.source, span.start.offset.saturating_add_signed(shift)),
+            Position::new(span.end.source, span.end.offset.saturating_add_signed(shift)),
+        );
+
+        find_node(&fixed, moved, |candidate| candidate.kind() == node.kind()).is_some_and(|candidate| {
+            let options = CompareOptions { limit: 1, ..CompareOptions::default() };
+
+            structural_node_diff((&original, node), (&fixed, candidate), interner, options).is_empty()
+        })
+    };
+
+    let options = CompareOptions { limit: usize::MAX, ..CompareOptions::default() };
+    let untargeted = structural_diff(&original, &fixed, interner, options)
+        .into_iter()
+        .find(|difference| !is_targeted(difference.left) && !is_moved(difference.left))?;
+
+    Some(format!(
+        "the fixes changed code they do not target, at line {} ({})",
+        source.line_number(untargeted.left.start.offset) + 1,
+        untargeted.path
+    ))
+}
+
+/// Returns the outermost node of the given program with the given span, for which the given function returns `true`.
+fn find_node<'a>(program: &'a Program, span: Span, f: impl Fn(Node<'a>) -> bool) -> Option<Node<'a>> {
+    let mut found = None;
+    Node::Program(program).traverse(&mut |node| {
+        let node_span = node.span();
+        if found.is_some() || node_span.start.offset > span.start.offset || node_span.end.offset < span.end.offset {
+            return false;
+        }
+
+        if node_span.start.offset == span.start.offset && node_span.end.offset == span.end.offset && f(node) {
+            found = Some(node);
+        }
+
+        true
+    });
+
+    found
+}
+
+/// Writes the fixed content of the given sources to disk, backing up the original files if requested.
+///
+/// Returns `false`, without writing anything, if a backup file already exists and `--force` is not used.
+fn write_sources(manager: &SourceManager, sources: &[Source], command: &FixCommand) -> Result<bool, Error> {
+    if let Some(suffix) = &command.backup {
+        let backups: Vec<_> = sources
+            .iter()
+            .filter_map(|source| source.path.as_ref())
+            .map(|path| {
+                let mut backup = path.clone().into_os_string();
+                backup.push(suffix);
+
+                (path, PathBuf::from(backup))
+            })
+            .collect();
+
+        if !command.force {
+            if let Some((_, backup)) = backups.iter().find(|(_, backup)| backup.exists()) {
+                mago_feedback::error!(
+                    "Backup file '{}' already exists. To overwrite existing backups, use the `--force` flag.",
+                    backup.display()
+                );
+
+                return Ok(false);
+            }
+        }
+
+        for (path, backup) in backups {
+            std::fs::copy(path, &backup).map_err(|error| Error::Backup(backup, error))?;
+        }
+    }
+
+    for source in sources {
+        manager.save(&source.identifier)?;
+    }
+
+    Ok(true)
+}
+
+impl FixDescription {
+    fn new(interner: &ThreadedInterner, source: &Source, fix: &Fix) -> Self {
+        let offset = match fix.issue.annotations.iter().find(|annotation| annotation.is_primary()) {
+            Some(annotation) => annotation.span.start.offset,
+            None => fix.plan.get_range().map(|range| range.start).unwrap_or(0),
+        };
+
+        Self {
+            source: interner.lookup(&source.identifier.0).to_string(),
+            line: source.line_number(offset) + 1,
+            code: fix.issue.code.clone().unwrap_or_else(|| "other".to_string()),
+            message: fix.issue.message.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for FixDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` at {}:{}", self.code, self.source, self.line)
+    }
+}
+
+impl Conflict {
+    /// Determines whether this conflict is between the same fixes as the given one, which may have moved
+    /// since, as fixes applied by a previous pass shift the lines that follow them.
+    fn is_same_as(&self, other: &Conflict) -> bool {
+        let key = |description: &FixDescription| {
+            (description.source.clone(), description.code.clone(), description.message.clone())
+        };
+
+        key(&self.skipped) == key(&other.skipped) && key(&self.applied) == key(&other.applied)
+    }
+}
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the fix for {} overlaps with the fix for {}", self.skipped, self.applied)
+    }
+}
+
+impl StopReason {
+    fn describe(&self) -> String {
+        match self {
+            StopReason::Interactive => "run the command again to review them".to_string(),
+            StopReason::Unchanged => "and another pass would not change anything".to_string(),
+            StopReason::Repeated => "and the same conflicts remained after another pass".to_string(),
+            StopReason::MaximumIterations(iterations) => {
+                format!("and the maximum of {} pass(es) was reached, use `--max-iterations` to allow more", iterations)
+            }
+        }
+    }
+}
+
+/// Returns the fixes suggested for the given issues, along with the number of skipped unsafe and
+/// potentially unsafe fixes.
+///
+/// Operations that are less safe than the given classification are removed from the fixes, and
+/// fixes without any remaining operation are dropped.
+fn filter_fixes(
+    interner: &ThreadedInterner,
+    issues: IssueCollection,
+    classification: SafetyClassification,
+) -> (Vec<Fix>, usize, usize) {
+    let mut skipped_unsafe = 0;
+    let mut skipped_potentially_unsafe = 0;
+
+    let mut fixes = vec![];
+    for mut issue in issues {
+        for (source, plan) in issue.take_suggestions() {
+            if plan.is_empty() {
+                continue;
+            }
+
+            let mut operations = vec![];
+            for operation in plan.take_operations() {
+                match operation.get_safety_classification() {
+                    SafetyClassification::Unsafe => {
+                        if classification == SafetyClassification::Unsafe {
+                            operations.push(operation);
+                        } else {
+                            skipped_unsafe += 1;
+
+                            mago_feedback::warn!(
+                                "Skipping a fix for `{}` because it contains unsafe changes.",
+                                interner.lookup(&source.0)
+                            );
+                        }
+                    }
+                    SafetyClassification::PotentiallyUnsafe => {
+                        if classification == SafetyClassification::Unsafe
+                            || classification == SafetyClassification::PotentiallyUnsafe
+                        {
+                            operations.push(operation);
+                        } else {
+                            skipped_potentially_unsafe += 1;
+
+                            mago_feedback::warn!(
+                                "Skipping a fix for `{}` because it contains potentially unsafe changes.",
+                                interner.lookup(&source.0)
+                            );
+                        }
+                    }
+                    SafetyClassification::Safe => {
+                        operations.push(operation);
+                    }
+                }
+            }
+
+            if !operations.is_empty() {
+                fixes.push(Fix { source, issue: issue.clone(), plan: FixPlan::from_operations(operations) });
+            }
+        }
+    }
+
+    (fixes, skipped_unsafe, skipped_potentially_unsafe)
+}
+
+/// Drops the fixes that overlap with another fix of the same source, so the remaining fixes can be applied together.
+///
+/// Fixes are considered in the order of the content they affect, and a fix is skipped when it overlaps
+/// with a fix that was already selected.
+fn resolve_conflicts(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    fixes: Vec<Fix>,
+) -> Result<(Vec<Fix>, Vec<Conflict>), Error> {
+    let mut per_source: HashMap<SourceIdentifier, Vec<Fix>> = HashMap::default();
+    for fix in fixes {
+        per_source.entry(fix.source).or_default().push(fix);
+    }
+
+    let mut per_source: Vec<_> = per_source.into_iter().collect();
+    per_source.sort_by(|(a, _), (b, _)| interner.lookup(&a.0).cmp(interner.lookup(&b.0)));
+
+    let mut selected = vec![];
+    let mut conflicts = vec![];
+    for (source, fixes) in per_source {
+        let plans: Vec<FixPlan> = fixes.iter().map(|fix| fix.plan.clone()).collect();
+        let (selected_indices, skipped) = mago_fixer::resolve_conflicts(&plans);
+        if skipped.is_empty() {
+            selected.extend(fixes);
+
+            continue;
+        }
+
+        let source = manager.load(&source)?;
+        for (skipped, applied) in skipped {
+            conflicts.push(Conflict {
+                skipped: FixDescription::new(interner, &source, &fixes[skipped]),
+                applied: FixDescription::new(interner, &source, &fixes[applied]),
+            });
+        }
+
+        let mut fixes: Vec<Option<Fix>> = fixes.into_iter().map(Some).collect();
+        selected.extend(selected_indices.into_iter().filter_map(|index| fixes[index].take()));
+    }
+
+    Ok((selected, conflicts))
+}
+
+/// Merges the given fixes into a single plan per source, along with the rule of every fix it contains.
+///
+/// Overlapping operations are resolved when the merged plan is executed, so fixes can be applied
+/// together without their offsets drifting.
+fn merge_fixes(fixes: Vec<Fix>) -> Vec<(SourceIdentifier, FixPlan, Vec<String>)> {
+    let mut plans: HashMap<SourceIdentifier, (FixPlan, Vec<String>)> = HashMap::default();
+    for fix in fixes {
+        let (plan, codes) = plans.entry(fix.source).or_insert_with(|| (FixPlan::new(), vec![]));
+
+        plan.merge(fix.plan);
+        codes.push(fix.issue.code.unwrap_or_else(|| "other".to_string()));
+    }
+
+    plans.into_iter().map(|(source, (plan, codes))| (source, plan, codes)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_php_version::PHPVersion;
+    use mago_reflection::CodebaseReflection;
+    use mago_semantics::Semantics;
+
+    use super::*;
+    use crate::commands::lint::Linters;
+    use crate::config::linter::LinterConfiguration;
+
+    fn verify(code: &str, fixed: &str, range: std::ops::Range<usize>) -> Option<String> {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.load(&manager.insert_content("a.php".to_string(), code.to_string(), true)).unwrap();
+
+        let mut plan = FixPlan::new();
+        plan.delete(range, SafetyClassification::Safe);
+
+        verify_fixes(&interner, &source, &plan, fixed, ParserSettings::default())
+    }
+
+    #[test]
+    fn test_fixes_changing_the_targeted_code_are_verified() {
+        assert_eq!(None, verify("<?php $a = (1);\n$b = 2;", "<?php $a = 1;\n$b = 2;", 11..12));
+        assert_eq!(None, verify("<?php $a = 1;;\n$b = 2;", "<?php $a = 1;\n$b = 2;", 13..14));
+    }
+
+    #[test]
+    fn test_fixes_moving_the_code_they_do_not_target_are_verified() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let code = "<?php function foo(int|string|array $a = null) {}";
+        let source = manager.load(&manager.insert_content("a.php".to_string(), code.to_string(), true)).unwrap();
+
+        // The union is nested differently once `null` is prepended to it.
+        let mut plan = FixPlan::new();
+        plan.insert(19, "null|", SafetyClassification::Safe);
+        let fixed = plan.execute(code).get_fixed();
+
+        assert_eq!(None, verify_fixes(&interner, &source, &plan, &fixed, ParserSettings::default()));
+    }
+
+    #[test]
+    fn test_fixes_breaking_the_code_are_rejected() {
+        let rejection = verify("<?php $a = (1);", "<?php $a = 1);", 11..12).unwrap();
+
+        assert!(rejection.starts_with("the fixed code cannot be parsed"), "{}", rejection);
+    }
+
+    #[test]
+    fn test_fixes_changing_other_code_are_rejected() {
+        let rejection = verify("<?php $a = (1);\n$b = 2;", "<?php $a = (1);\n$b = 3;", 11..12).unwrap();
+
+        assert!(rejection.starts_with("the fixes changed code they do not target, at line 2"), "{}", rejection);
+    }
+
+    #[test]
+    fn test_only_the_fixes_of_the_selected_rules_are_applied() {
+        let code = "<?php $a = (1);;";
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.load(&manager.insert_content("a.php".to_string(), code.to_string(), true)).unwrap();
+        let semantics = Semantics::build(&interner, PHPVersion::LATEST, ParserSettings::default(), source);
+
+        let fixes = |only: &[&str]| {
+            let only: Vec<String> = only.iter().map(|rule| rule.to_string()).collect();
+            let selection = RuleSelection::new(&only, &[]).unwrap();
+            let linters = Linters::new(
+                &interner,
+                PHPVersion::LATEST,
+                &LinterConfiguration { default_plugins: Some(false), ..Default::default() },
+                &selection,
+                CodebaseReflection::new(),
+            );
+            let issues = linters.for_source("a.php").lint(&semantics);
+            let (fixes, _, _) = filter_fixes(&interner, issues, SafetyClassification::Unsafe);
+
+            merge_fixes(fixes)
+                .into_iter()
+                .map(|(_, plan, mut codes)| {
+                    codes.sort();
+
+                    (plan.execute(code).get_fixed(), codes)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            fixes(&["redundancy/redundant-parentheses"]),
+            vec![("<?php $a = 1;;".to_string(), vec!["redundancy/redundant-parentheses".to_string()])]
+        );
+        assert_eq!(
+            fixes(&["redundancy/redundant-parentheses", "redundancy/redundant-noop"]),
+            vec![(
+                "<?php $a = 1;".to_string(),
+                vec!["redundancy/redundant-noop".to_string(), "redundancy/redundant-parentheses".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_backups_are_written_before_the_fixed_sources() {
+        let directory = std::env::temp_dir().join(format!("mago-fix-backup-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("a.php");
+        std::fs::write(&path, "<?php $a = (1);").unwrap();
+
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source_id = manager.insert_path("a.php".to_string(), path.clone(), true);
+        let sources = vec![manager.load(&source_id).unwrap()];
+        manager.update(source_id, "<?php $a = 1;".to_string()).unwrap();
+
+        let command = FixCommand::parse_from(["fix", "--backup"]);
+        assert!(write_sources(&manager, &sources, &command).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "<?php $a = 1;");
+        assert_eq!(std::fs::read_to_string(directory.join("a.php.bak")).unwrap(), "<?php $a = (1);");
+
+        // Existing backups are only overwritten with `--force`.
+        manager.update(source_id, "<?php $a = 2;".to_string()).unwrap();
+        assert!(!write_sources(&manager, &sources, &command).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "<?php $a = 1;");
+
+        let command = FixCommand::parse_from(["fix", "--backup=.orig", "--force"]);
+        assert!(write_sources(&manager, &sources, &command).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "<?php $a = 2;");
+        assert_eq!(std::fs::read_to_string(directory.join("a.php.orig")).unwrap(), "<?php $a = 1;");
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+use std::io::Write;
+use std::process::ExitCode;
+
+use mago_interner::ThreadedInterner;
+use mago_reporting::reporter::Reporter;
+use mago_reporting::reporter::ReportingFormat;
+use mago_reporting::reporter::ReportingTarget;
+
+use crate::color::ColorChoice;
+use crate::commands::fix::run_passes;
+use crate::commands::fix::FixCommand;
+use crate::commands::lint::lint_sources;
+use crate::commands::lint::RuleSelection;
+use crate::config::Configuration;
+use crate::consts::CURRENT_DIR;
+use crate::error::Error;
+use crate::reflection::ExternalReflection;
+use crate::source;
+
+/// The name of the source read from stdin, when no `--stdin-path` is given.
+const STDIN_SOURCE_NAME: &str = "<stdin>";
+
+/// Fixes the source read from stdin, writing the fixed source to stdout, and the remaining issues to stderr.
+///
+/// The full content is always written to stdout, even when no fix was applied, so that editors can
+/// unconditionally replace their buffer with it.
+pub(super) async fn fix_stdin(
+    command: &FixCommand,
+    configuration: &Configuration,
+    selection: &RuleSelection,
+    color: ColorChoice,
+) -> Result<ExitCode, Error> {
+    let content = std::io::read_to_string(std::io::stdin()).map_err(Error::ReadingStdin)?;
+    let fixed = fix_content(command, configuration, selection, color, content).await?;
+
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(fixed.as_bytes()).and_then(|_| stdout.flush()).map_err(Error::WritingStdout)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Fixes the given content, as the source named after `--stdin-path`, returning the fixed content.
+///
+/// The issues remaining after the fixes were applied are reported to stderr.
+async fn fix_content(
+    command: &FixCommand,
+    configuration: &Configuration,
+    selection: &RuleSelection,
+    color: ColorChoice,
+    content: String,
+) -> Result<String, Error> {
+    let name = match &command.stdin_path {
+        Some(path) => source::source_name(&configuration.source.root, &CURRENT_DIR.join(path)),
+        None => STDIN_SOURCE_NAME.to_string(),
+    };
+
+    let interner = ThreadedInterner::new();
+    let (manager, source) = source::load_virtual(&interner, &configuration.source, name, content).await?;
+
+    let passes = run_passes(&interner, &manager, configuration, selection, command, color).await?;
+    passes.warn_unapplied();
+
+    let (issues, _) =
+        lint_sources(&interner, &manager, configuration, selection, &ExternalReflection::Reflect, color, true).await?;
+    let fixed = manager.load(&source)?;
+
+    let reporter =
+        Reporter::new(interner.clone(), manager, ReportingTarget::Stderr, color.for_reporter(ReportingTarget::Stderr));
+    reporter.report(issues, ReportingFormat::default())?;
+
+    let fixes: usize = passes.applied_fixes.values().sum();
+    if fixes == 0 {
+        mago_feedback::info!("No fixes were applied");
+    } else {
+        mago_feedback::info!("Applied {} fix(es)", fixes);
+    }
+
+    Ok(interner.lookup(&fixed.content).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_stdin_content_replaces_the_source_at_the_stdin_path() {
+        let root = std::env::temp_dir().join(format!("mago-fix-stdin-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.php"), "<?php $b = (2);").unwrap();
+        let root = root.canonicalize().unwrap();
+
+        let path = root.join("src/a.php");
+        let command =
+            FixCommand::parse_from(["fix".as_ref(), "--stdin".as_ref(), "--stdin-path".as_ref(), path.as_os_str()]);
+        let mut configuration = Configuration::from_root(root.clone());
+        configuration.source.use_builtin_stubs = false;
+        configuration.cache.enabled = false;
+        let selection = RuleSelection::new(&["redundancy/redundant-parentheses".to_string()], &[]).unwrap();
+
+        let fixed = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(fix_content(
+                &command,
+                &configuration,
+                &selection,
+                ColorChoice::Never,
+                "<?php $a = (1);".to_string(),
+            ))
+            .unwrap();
+
+        assert_eq!(fixed, "<?php $a = 1;");
+        // The file at the stdin path is never written.
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "<?php $b = (2);");
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
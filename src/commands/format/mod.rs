@@ -0,0 +1,489 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use mago_feedback::create_progress_bar;
+use mago_feedback::remove_progress_bar;
+use mago_feedback::ProgressBarTheme;
+use mago_formatter::format;
+use mago_formatter::reformat;
+use mago_formatter::settings::FormatSettings;
+use mago_interner::ThreadedInterner;
+use mago_parser::parse_source_with_settings;
+use mago_parser::settings::ParserSettings;
+use mago_reporting::reporter::ReportingTarget;
+use mago_source::error::SourceError;
+use mago_source::Source;
+use mago_source::SourceIdentifier;
+use mago_source::SourceManager;
+
+use crate::color::ColorChoice;
+use crate::config::Configuration;
+use crate::error::Error;
+use crate::source;
+use crate::utils;
+
+mod stdin;
+
+/// Represents the `format` command, which is responsible for formatting source files
+/// according to specified rules in the configuration file.
+#[derive(Parser, Debug)]
+#[command(
+    name = "format",
+    aliases = ["fmt"],
+    about = "format source files to match defined style rules",
+    long_about = r#"
+The `format` command applies consistent formatting to source files based on the rules defined in the configuration file.
+
+This command helps maintain a consistent codebase style, improving readability and collaboration.
+"#
+)]
+pub struct FormatCommand {
+    /// The files or directories to format, instead of all the configured sources.
+    #[arg(value_name = "PATH", help = "the files or directories to format, defaults to all the configured sources")]
+    pub sources: Vec<PathBuf>,
+
+    /// Check if the files are already formatted, printing a diff for every file that would change.
+    #[arg(
+        long,
+        short = 'c',
+        alias = "dry-run",
+        short_alias = 'd',
+        help = "check if the source files are already formatted without making changes"
+    )]
+    pub check: bool,
+
+    /// Check that formatting is idempotent, by formatting every file twice, without making changes.
+    ///
+    /// A diff between the first and the second pass is printed for every file where they differ.
+    #[arg(
+        long,
+        conflicts_with = "check",
+        help = "check that formatting the formatted source files again does not change them, without making changes",
+        long_help = "check that formatting the formatted source files again does not change them, without making \
+                     changes\n\n\
+                     a diff between the first and the second pass is printed for every file where they differ"
+    )]
+    pub verify: bool,
+
+    /// Read the source to format from stdin, and write the formatted source to stdout.
+    ///
+    /// The full source is always written to stdout, unchanged when it is excluded or cannot be parsed,
+    /// so that editors can always replace their buffer with the output.
+    #[arg(
+        long,
+        conflicts_with_all = ["sources", "check", "verify"],
+        help = "read the source to format from stdin, and write the formatted source to stdout",
+        long_help = "read the source to format from stdin, and write the formatted source to stdout\n\n\
+                     the full source is always written to stdout, unchanged when it is excluded or cannot be \
+                     parsed, so that editors can always replace their buffer with the output"
+    )]
+    pub stdin: bool,
+
+    /// The path of the source read from stdin, used to decide whether it is excluded from formatting.
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "stdin",
+        help = "the path of the source read from stdin, relative to the current directory (e.g. `src/Foo.php`)"
+    )]
+    pub stdin_path: Option<PathBuf>,
+
+    /// Only format the statements covered by the given lines of the source read from stdin.
+    ///
+    /// The range is widened to the boundaries of the statements, or class members, it partially covers.
+    #[arg(
+        long,
+        value_name = "START:END",
+        requires = "stdin",
+        help = "only format the statements covered by the given lines, 1-based and inclusive (e.g. `10:20`)",
+        long_help = "only format the statements covered by the given lines, 1-based and inclusive (e.g. `10:20`)\n\n\
+                     the range is widened to the boundaries of the statements, or class members, it partially covers"
+    )]
+    pub range: Option<LineRange>,
+
+    /// Specify the width of the printed source code for formatting purposes.
+    #[arg(long, short = 'w', help = "set the maximum line width for the formatted source code", value_name = "WIDTH")]
+    pub print_width: Option<usize>,
+
+    /// Indent with tabs instead of spaces, overriding the configuration.
+    #[arg(long, help = "indent the formatted source code with tabs instead of spaces")]
+    pub use_tabs: bool,
+}
+
+/// An inclusive range of lines, 1-based, as given to `--range`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl std::str::FromStr for LineRange {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let Some((start, end)) = value.split_once(':') else {
+            return Err("expected a range of lines, such as `10:20`".to_string());
+        };
+
+        let start: usize = start.trim().parse().map_err(|_| format!("invalid start line `{}`", start))?;
+        let end: usize = end.trim().parse().map_err(|_| format!("invalid end line `{}`", end))?;
+
+        if start == 0 || end < start {
+            return Err(format!(
+                "invalid range of lines `{}`, lines start at 1, and the end cannot precede the start",
+                value
+            ));
+        }
+
+        Ok(Self { start, end })
+    }
+}
+
+/// Executes the format command with the provided configuration and options.
+///
+/// # Arguments
+/// * `command` - The `FormatCommand` structure containing user-specified options.
+/// * `configuration` - The application configuration loaded from file or defaults.
+/// * `color` - Whether to use colors in the output.
+///
+/// # Returns
+///
+/// Exit code: `0` if successful or no changes were needed, `1` if files need formatting when checking,
+/// or are not formatted idempotently when verifying, and `2` if the source read from stdin cannot be parsed.
+pub async fn execute(
+    command: FormatCommand,
+    mut configuration: Configuration,
+    color: ColorChoice,
+) -> Result<ExitCode, Error> {
+    // Update the print width in configuration if provided.
+    if let Some(width) = command.print_width {
+        configuration.format.print_width = Some(width);
+    }
+
+    if command.use_tabs {
+        configuration.format.use_tabs = Some(true);
+    }
+
+    if command.stdin {
+        return stdin::format_stdin(&command, &configuration);
+    }
+
+    let selection_paths = match source::canonicalize_selection(&command.sources) {
+        Ok(paths) => paths,
+        Err(path) => {
+            mago_feedback::error!("Path '{}' does not exist.", path.display());
+
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    // Initialize the interner for managing identifiers.
+    let interner = ThreadedInterner::new();
+    // Load sources, the sources that are not selected are loaded as external sources, and are therefore not formatted.
+    let source_manager = source::load(&interner, &configuration.source, false, &selection_paths).await?;
+
+    // Extract formatting settings from the configuration.
+    let settings = configuration.format.get_settings();
+
+    let mode = if command.verify {
+        Mode::Verify
+    } else if command.check {
+        Mode::Check
+    } else {
+        Mode::Write
+    };
+
+    // Format all sources and get the count of changed and skipped files.
+    let parser_settings = configuration.source.parser_settings();
+    let summary = format_all(interner, source_manager, settings, parser_settings, mode, color).await?;
+
+    if summary.skipped > 0 {
+        mago_feedback::warn!("Skipped {} source file(s) that could not be formatted safely.", summary.skipped);
+    }
+
+    if mode == Mode::Verify {
+        if summary.not_idempotent == 0 {
+            mago_feedback::info!("All source files are formatted idempotently.");
+
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        mago_feedback::error!("Found {} source file(s) that are not formatted idempotently.", summary.not_idempotent);
+
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let changed = summary.changed;
+
+    // Provide feedback and return appropriate exit code.
+    if changed == 0 {
+        mago_feedback::info!("All source files are already formatted.");
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    Ok(if mode == Mode::Check {
+        mago_feedback::info!("Found {} source files that need formatting.", changed);
+
+        ExitCode::FAILURE
+    } else {
+        mago_feedback::info!("Formatted {} source files successfully.", changed);
+
+        ExitCode::SUCCESS
+    })
+}
+
+/// Formats all source files using the provided settings.
+///
+/// # Arguments
+///
+/// * `interner` - The interner to manage source identifiers.
+/// * `source_manager` - The manager responsible for handling source files.
+/// * `settings` - Formatting settings to apply.
+/// * `parser_settings` - Settings of the parser, such as whether short open tags are enabled.
+/// * `mode` - Whether to apply, check, or verify the formatting.
+/// * `color` - Whether to use colors in the output.
+///
+/// # Returns
+///
+/// A result containing the number of files per outcome, or a source error.
+#[inline]
+async fn format_all(
+    interner: ThreadedInterner,
+    source_manager: SourceManager,
+    settings: FormatSettings,
+    parser_settings: ParserSettings,
+    mode: Mode,
+    color: ColorChoice,
+) -> Result<Summary, Error> {
+    // Collect all user-defined sources.
+    let sources: Vec<_> = source_manager.user_defined_source_ids().collect();
+
+    let length = sources.len();
+    let progress_bar = create_progress_bar(
+        length,
+        "✨ Formatting",
+        ProgressBarTheme::Magenta,
+        color.use_colors(ReportingTarget::Stderr),
+    );
+    let mut handles = Vec::with_capacity(length);
+    let use_colors = color.use_colors(ReportingTarget::Stdout);
+
+    // Spawn async tasks to format each source concurrently.
+    for source in sources.into_iter() {
+        handles.push(tokio::spawn({
+            let interner = interner.clone();
+            let manager = source_manager.clone();
+            let progress_bar = progress_bar.clone();
+
+            async move {
+                let result = format_source(&interner, &manager, &source, settings, parser_settings, mode, use_colors);
+
+                progress_bar.inc(1);
+
+                result
+            }
+        }));
+    }
+
+    let mut summary = Summary::default();
+
+    // Process each formatting task and update progress bar.
+    for handle in handles {
+        match handle.await?? {
+            Outcome::Unchanged => {}
+            Outcome::Changed => summary.changed += 1,
+            Outcome::Skipped => summary.skipped += 1,
+            Outcome::NotIdempotent => summary.not_idempotent += 1,
+        }
+    }
+
+    remove_progress_bar(progress_bar);
+
+    Ok(summary)
+}
+
+/// What to do with the formatted source files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Write the formatted source files.
+    Write,
+    /// Print a diff for every source file that is not formatted.
+    Check,
+    /// Format every source file twice, and print a diff for every file where the passes differ.
+    Verify,
+}
+
+/// The number of source files per outcome.
+#[derive(Debug, Default)]
+struct Summary {
+    changed: usize,
+    skipped: usize,
+    not_idempotent: usize,
+}
+
+/// The outcome of formatting a single source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// The source file is already formatted.
+    Unchanged,
+    /// The source file was formatted, or would be when checking.
+    Changed,
+    /// The source file could not be parsed, or its invalid UTF-8 sequences would be lost, and was left untouched.
+    Skipped,
+    /// Formatting the formatted source file again changes it, or it cannot be parsed anymore.
+    NotIdempotent,
+}
+
+/// Formats a single source file.
+///
+/// # Arguments
+///
+/// * `interner` - Reference to the interner for identifier management.
+/// * `manager` - Reference to the source manager.
+/// * `source` - Identifier of the source file to format.
+/// * `settings` - Formatting settings to apply.
+/// * `parser_settings` - Settings of the parser, such as whether short open tags are enabled.
+/// * `mode` - Whether to apply, check, or verify the formatting.
+/// * `use_colors` - Whether to use colors when displaying the diff.
+///
+/// # Returns
+///
+/// A result containing the outcome of formatting the file, or an error.
+#[inline]
+fn format_source(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    source: &SourceIdentifier,
+    settings: FormatSettings,
+    parser_settings: ParserSettings,
+    mode: Mode,
+    use_colors: bool,
+) -> Result<Outcome, Error> {
+    // Load the source file.
+    let source = manager.load(source)?;
+
+    // Parse the source file to generate an AST.
+    let (program, errors) = parse_source_with_settings(interner, &source, parser_settings);
+
+    let source_name = interner.lookup(&source.identifier.0);
+
+    // Formatting a partially parsed source would lose the code that could not be parsed.
+    if let Some(error) = errors.first() {
+        mago_feedback::warn!("Skipping formatting for source '{}': {}.", source_name, error);
+
+        return Ok(Outcome::Skipped);
+    }
+
+    let formatted = format(settings, interner, &source, &program);
+    if mode == Mode::Verify {
+        return Ok(verify_idempotency(interner, &source, settings, parser_settings, &formatted, use_colors));
+    }
+
+    let check = mode == Mode::Check;
+    match utils::apply_changes(interner, manager, &source, formatted, check, use_colors) {
+        Ok(true) => {}
+        Ok(false) => return Ok(Outcome::Unchanged),
+        // The invalid UTF-8 sequences of the file are only kept when they are in the data following
+        // `__halt_compiler();`, or otherwise at the end of the file.
+        Err(Error::Source(error @ SourceError::InvalidUtf8(_))) => {
+            mago_feedback::warn!("Skipping formatting for source '{}': {}.", source_name, error);
+
+            return Ok(Outcome::Skipped);
+        }
+        Err(error) => return Err(error),
+    }
+
+    if !check {
+        mago_feedback::info!("Formatted '{}'.", source_name);
+    }
+
+    Ok(Outcome::Changed)
+}
+
+/// Formats the formatted code of the source again, printing a diff between both passes if they differ.
+fn verify_idempotency(
+    interner: &ThreadedInterner,
+    source: &Source,
+    settings: FormatSettings,
+    parser_settings: ParserSettings,
+    formatted: &str,
+    use_colors: bool,
+) -> Outcome {
+    let source_name = interner.lookup(&source.identifier.0);
+
+    let Some(reformatted) = reformat(settings, parser_settings, interner, source, formatted) else {
+        mago_feedback::error!("Formatting source '{}' produces code that cannot be parsed.", source_name);
+
+        return Outcome::NotIdempotent;
+    };
+
+    if reformatted == formatted {
+        return Outcome::Unchanged;
+    }
+
+    mago_feedback::warn!("Formatting source '{}' is not idempotent.", source_name);
+
+    let diff = utils::unified_diff(source_name, formatted, &reformatted, use_colors);
+    mago_feedback::progress::GLOBAL_PROGRESS_MANAGER.suspend(|| {
+        print!("{}", diff);
+    });
+
+    Outcome::NotIdempotent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(configuration: &Configuration, arguments: &[&std::ffi::OsStr]) -> ExitCode {
+        let command = FormatCommand::parse_from(std::iter::once("format".as_ref()).chain(arguments.iter().copied()));
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(execute(command, configuration.clone(), ColorChoice::Never))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_check_and_format_only_the_selected_paths() {
+        let root = std::env::temp_dir().join(format!("mago-format-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.php"), "<?php echo    1;\n").unwrap();
+        std::fs::write(root.join("src/b.php"), "<?php echo    2;\n").unwrap();
+        let root = root.canonicalize().unwrap();
+
+        let configuration = Configuration::from_root(root.clone());
+        let a = root.join("src/a.php");
+        let b = root.join("src/b.php");
+
+        assert_eq!(run(&configuration, &["--check".as_ref(), a.as_os_str()]), ExitCode::FAILURE);
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "<?php echo    1;\n");
+
+        assert_eq!(run(&configuration, &[a.as_os_str()]), ExitCode::SUCCESS);
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "<?php\n\necho 1;\n");
+        assert_eq!(std::fs::read_to_string(&b).unwrap(), "<?php echo    2;\n");
+
+        assert_eq!(run(&configuration, &["--check".as_ref(), a.as_os_str()]), ExitCode::SUCCESS);
+        assert_eq!(run(&configuration, &["--check".as_ref()]), ExitCode::FAILURE);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_missing_paths_are_rejected() {
+        let root = std::env::temp_dir().join(format!("mago-format-missing-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+
+        let configuration = Configuration::from_root(root.clone());
+        let missing = root.join("src/Missing.php");
+
+        assert_eq!(run(&configuration, &["--check".as_ref(), missing.as_os_str()]), ExitCode::FAILURE);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
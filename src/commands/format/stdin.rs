@@ -0,0 +1,80 @@
+use std::io::Write;
+use std::process::ExitCode;
+
+use mago_formatter::format;
+use mago_formatter::format_range;
+use mago_interner::ThreadedInterner;
+use mago_parser::parse_source_with_settings;
+use mago_source::SourceManager;
+
+use crate::commands::format::FormatCommand;
+use crate::config::Configuration;
+use crate::error::Error;
+use crate::source;
+
+/// The name of the source read from stdin, when no `--stdin-path` is given.
+const STDIN_SOURCE_NAME: &str = "<stdin>";
+
+/// The exit code used when the source read from stdin cannot be parsed.
+const PARSE_ERROR_EXIT_CODE: u8 = 2;
+
+/// Formats the source read from stdin, writing the formatted source to stdout.
+///
+/// When the source is excluded, or cannot be parsed, it is written to stdout unchanged, so that
+/// editors never lose the content of their buffer.
+pub(super) fn format_stdin(command: &FormatCommand, configuration: &Configuration) -> Result<ExitCode, Error> {
+    let content = std::io::read_to_string(std::io::stdin()).map_err(Error::ReadingStdin)?;
+
+    let name = match &command.stdin_path {
+        Some(path) => {
+            let path = source::resolve_path(path);
+            let name = source::source_name(&configuration.source.root, &path);
+
+            if source::is_excluded_path(&configuration.source, &path) {
+                mago_feedback::info!("Source '{}' is excluded from formatting, leaving it unchanged.", name);
+
+                write_stdout(&content)?;
+
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            name
+        }
+        None => STDIN_SOURCE_NAME.to_string(),
+    };
+
+    let interner = ThreadedInterner::new();
+    let manager = SourceManager::new(interner.clone());
+    let source = manager.load(&manager.insert_content(name.clone(), content, true))?;
+
+    let (program, errors) = parse_source_with_settings(&interner, &source, configuration.source.parser_settings());
+    if let Some(error) = errors.first() {
+        mago_feedback::error!("Failed to parse source '{}', leaving it unchanged: {}.", name, error);
+
+        write_stdout(interner.lookup(&source.content))?;
+
+        return Ok(ExitCode::from(PARSE_ERROR_EXIT_CODE));
+    }
+
+    let settings = configuration.format.get_settings();
+    let formatted = match command.range {
+        Some(range) => {
+            // Lines past the end of the source are ignored.
+            let start = source.lines.get(range.start - 1).copied().unwrap_or(source.size);
+            let end = source.lines.get(range.end).copied().unwrap_or(source.size);
+
+            format_range(settings, &interner, &source, &program, start..end)
+        }
+        None => format(settings, &interner, &source, &program),
+    };
+
+    write_stdout(&formatted)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn write_stdout(content: &str) -> Result<(), Error> {
+    let mut stdout = std::io::stdout().lock();
+
+    stdout.write_all(content.as_bytes()).and_then(|_| stdout.flush()).map_err(Error::WritingStdout)
+}
@@ -0,0 +1,1605 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use clap::Parser;
+
+use mago_feedback::create_progress_bar;
+use mago_feedback::remove_progress_bar;
+use mago_feedback::ProgressBarTheme;
+use mago_interner::ThreadedInterner;
+use mago_linter::plugin::Plugin;
+use mago_linter::settings::RuleSettings;
+use mago_linter::settings::Settings;
+use mago_linter::Linter;
+use mago_parser::settings::ParserSettings;
+use mago_php_version::PHPVersion;
+use mago_reflection::reference::ReferenceIndex;
+use mago_reflection::CodebaseReflection;
+use mago_reflector::reflect;
+use mago_reporting::reporter::GroupBy;
+use mago_reporting::reporter::PathStyle;
+use mago_reporting::reporter::Reporter;
+use mago_reporting::reporter::ReportingFormat;
+use mago_reporting::reporter::ReportingTarget;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_reporting::Level;
+use mago_semantics::Semantics;
+use mago_source::SourceIdentifier;
+use mago_source::SourceManager;
+
+use crate::baseline::Baseline;
+use crate::cache::ReflectionCache;
+use crate::color::ColorChoice;
+use crate::config::linter::LinterConfiguration;
+use crate::config::linter::LinterFailLevel;
+use crate::config::linter::LinterLevel;
+use crate::config::linter::LinterOverrideConfiguration;
+use crate::config::Configuration;
+use crate::consts::BASELINE_FILE;
+use crate::enum_variants;
+use crate::error::Error;
+use crate::git;
+use crate::reflection::ExternalReflection;
+use crate::source;
+
+mod watch;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "lint",
+    about = "analyze and highlight issues in the project source code using configurable linting rules",
+    long_about = r#"
+The `lint` command is a powerful tool for analyzing your PHP codebase. By default, it performs
+a full analysis, including parsing, semantic checks, and linting based on customizable rules.
+
+This command is ideal for enforcing code quality standards, debugging issues, and maintaining
+a consistent, clean codebase. Use `--semantics-only` for a quick validation of code correctness
+or the default mode for a comprehensive analysis.
+"#
+)]
+pub struct LintCommand {
+    /// The files or directories to lint, instead of all the configured sources.
+    #[arg(value_name = "PATH", help = "the files or directories to lint, defaults to all the configured sources")]
+    pub sources: Vec<PathBuf>,
+
+    /// Filter the output to only show issues that can be automatically fixed with `mago fix`.
+    #[arg(long, short = 'f', help = "filter the output to only show fixable issues", default_value_t = false)]
+    pub fixable_only: bool,
+
+    /// Perform only semantic analysis (parsing and semantic checks).
+    #[arg(long, short = 's', help = "only perform parsing and semantic checks", default_value_t = false)]
+    pub semantics_only: bool,
+
+    /// Specify where the results should be reported.
+    #[arg(
+        long,
+        default_value_t,
+        help = "specify where the results should be reported",
+        ignore_case = true,
+        value_parser = enum_variants!(ReportingTarget)
+    )]
+    pub reporting_target: ReportingTarget,
+
+    /// Choose the format for reporting issues.
+    #[arg(
+        long,
+        default_value_t,
+        help = "choose the format for reporting issues",
+        ignore_case = true,
+        value_parser = enum_variants!(ReportingFormat)
+    )]
+    pub reporting_format: ReportingFormat,
+
+    /// Write the report to a file instead of the reporting target.
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "write the report to the given file instead of the reporting target, creating missing directories"
+    )]
+    pub reporting_output: Option<PathBuf>,
+
+    /// The minimum level of issues that causes the command to exit with a failure.
+    #[arg(
+        long,
+        help = "the minimum issue level that causes a non-zero exit code, or `never` to always succeed [default: error]",
+        ignore_case = true,
+        value_parser = enum_variants!(LinterFailLevel)
+    )]
+    pub minimum_fail_level: Option<LinterFailLevel>,
+
+    /// Report issues in the order they were collected instead of sorting them.
+    #[arg(
+        long,
+        help = "report issues in the order they were collected instead of sorting them",
+        default_value_t = false
+    )]
+    pub no_sort: bool,
+
+    /// The number of source lines to show above and below each issue.
+    #[arg(long, value_name = "N", help = "the number of source lines to show above and below each issue")]
+    pub context_lines: Option<usize>,
+
+    /// Change how issues are arranged in the `rich` format.
+    #[arg(
+        long,
+        default_value_t,
+        help = "group issues by file, rule, or level in the `rich` format",
+        ignore_case = true,
+        value_parser = enum_variants!(GroupBy)
+    )]
+    pub group_by: GroupBy,
+
+    /// How source paths are displayed in the report.
+    #[arg(
+        long,
+        default_value_t,
+        help = "display paths relative to the source root, or as absolute paths",
+        ignore_case = true,
+        value_parser = enum_variants!(PathStyle)
+    )]
+    pub paths: PathStyle,
+
+    /// Only lint the files changed compared to a git reference, and the untracked files.
+    #[arg(
+        long,
+        value_name = "REF",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "HEAD",
+        conflicts_with = "sources",
+        help = "only lint the files changed compared to the given git reference, and the untracked files [default: HEAD]"
+    )]
+    pub changed: Option<String>,
+
+    /// Skip the reflection of the rest of the codebase when linting changed files.
+    #[arg(
+        long,
+        requires = "changed",
+        help = "skip the reflection of the rest of the codebase when using `--changed`, disabling the rules relying on it",
+        default_value_t = false
+    )]
+    pub changed_no_reflection: bool,
+
+    /// Keep running, and lint the sources again whenever they change.
+    #[arg(
+        long,
+        help = "keep running, and lint the files again whenever they change, until interrupted",
+        conflicts_with_all = ["generate_baseline", "prune_baseline", "semantics_only", "changed"],
+        default_value_t = false
+    )]
+    pub watch: bool,
+
+    /// Write all current issues to a baseline file instead of reporting them.
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        conflicts_with_all = ["sources", "changed"],
+        help = "write all current issues to a baseline file instead of reporting them [default: the configured baseline, or mago-baseline.toml]"
+    )]
+    pub generate_baseline: Option<Option<PathBuf>>,
+
+    /// Remove the baseline entries that no longer match any issue.
+    #[arg(long, help = "remove the baseline entries that no longer match any issue", default_value_t = false)]
+    pub prune_baseline: bool,
+
+    /// Report duplicated issues instead of removing them.
+    #[arg(long, help = "report duplicated issues instead of removing them (for debugging)", default_value_t = false)]
+    pub allow_duplicates: bool,
+
+    /// Only run the given rules, disabling every other rule.
+    #[arg(long, value_name = "RULE", help = "only run the given rule, can be repeated (e.g. `safety/no-eval`)")]
+    pub only: Vec<String>,
+
+    /// Disable the given rules, on top of the configuration.
+    #[arg(long, value_name = "RULE", help = "disable the given rule on top of the configuration, can be repeated")]
+    pub exclude_rule: Vec<String>,
+
+    /// Do not print the summary footer after the report.
+    #[arg(long, help = "do not print the summary footer after the report", default_value_t = false)]
+    pub no_summary: bool,
+
+    /// Import the reflection written by `mago reflect`, instead of reflecting the external sources.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["semantics_only", "changed_no_reflection"],
+        help = "import the reflection written by `mago reflect --output`, instead of reflecting the dependencies and stubs"
+    )]
+    pub import_reflection: Option<PathBuf>,
+}
+
+/// Statistics collected while analyzing the project sources.
+#[derive(Debug, Default)]
+pub(super) struct LintStatistics {
+    /// The number of user-defined files that were scanned.
+    pub files: usize,
+    /// The number of files that could not be parsed.
+    pub unparsable_files: usize,
+    /// The time spent loading and parsing the sources, and building their semantics.
+    pub scan_duration: Duration,
+    /// The time spent reflecting the codebase, or `None` if no reflection was performed.
+    pub reflect_duration: Option<Duration>,
+    /// The time spent checking the inheritance of the class-likes, or `None` if it was not checked.
+    pub check_duration: Option<Duration>,
+    /// The time spent running the linter rules, or `None` if no linting was performed.
+    pub lint_duration: Option<Duration>,
+    /// The number of issues that were suppressed by the baseline.
+    pub baselined: usize,
+}
+
+/// The rules selected on the command line, applied on top of the configuration.
+#[derive(Debug, Clone, Default)]
+pub(super) struct RuleSelection {
+    /// The full names of the only rules to run, or an empty list to run the configured rules.
+    only: Vec<String>,
+    /// The full names of the rules to disable.
+    excluded: Vec<String>,
+}
+
+impl RuleSelection {
+    /// Creates a new rule selection, resolving the given rule names to their full names.
+    ///
+    /// Rules can be referenced either by their full name (`plugin/rule`) or by their name alone, in which
+    /// case all rules with that name are selected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing close matches if a rule does not exist.
+    pub fn new(only: &[String], excluded: &[String]) -> Result<Self, Error> {
+        let available = available_rules();
+
+        Ok(Self { only: resolve_rules(&available, only)?, excluded: resolve_rules(&available, excluded)? })
+    }
+
+    /// Disables all the rules of the given plugin, on top of the current selection.
+    pub fn without_plugin(mut self, plugin: &str) -> Self {
+        let prefix = format!("{}/", plugin);
+
+        self.only.retain(|rule| !rule.starts_with(&prefix));
+        self.excluded.extend(available_rules().into_iter().filter(|rule| rule.starts_with(&prefix)));
+        self
+    }
+
+    /// Applies the selection to the given settings, overriding the configured rule settings.
+    fn apply(&self, mut settings: Settings) -> Settings {
+        if !self.only.is_empty() {
+            for rule in available_rules() {
+                let rule_settings = if self.only.contains(&rule) {
+                    let plugin = rule.split_once('/').map(|(plugin, _)| plugin.to_string()).unwrap_or_default();
+                    if !settings.plugins.contains(&plugin) {
+                        settings.plugins.push(plugin);
+                    }
+
+                    let mut rule_settings =
+                        settings.get_rule_settings(&rule).cloned().unwrap_or_else(RuleSettings::enabled);
+                    rule_settings.enabled = true;
+                    rule_settings
+                } else {
+                    RuleSettings::disabled()
+                };
+
+                settings = settings.with_rule(rule, rule_settings);
+            }
+        }
+
+        for rule in &self.excluded {
+            settings = settings.with_rule(rule.clone(), RuleSettings::disabled());
+        }
+
+        settings
+    }
+}
+
+/// Returns the full names (`plugin/rule`) of all the rules provided by the linter plugins.
+fn available_rules() -> Vec<String> {
+    let mut rules = vec![];
+
+    mago_linter::foreach_plugin!(|plugin| {
+        let name = Plugin::get_name(&plugin);
+
+        for rule in Plugin::get_rules(&plugin) {
+            rules.push(format!("{}/{}", name, rule.get_name()));
+        }
+    });
+
+    rules
+}
+
+/// Returns the descriptions of all the rules provided by the linter plugins, keyed by their full names.
+fn rule_descriptions() -> Vec<(String, String)> {
+    let mut descriptions = vec![];
+
+    mago_linter::foreach_plugin!(|plugin| {
+        let name = Plugin::get_name(&plugin);
+
+        for rule in Plugin::get_rules(&plugin) {
+            descriptions.push((format!("{}/{}", name, rule.get_name()), rule.get_description().to_string()));
+        }
+    });
+
+    descriptions
+}
+
+/// Resolves the given rule names, which may omit the plugin name, to the full names of the available rules.
+fn resolve_rules(available: &[String], names: &[String]) -> Result<Vec<String>, Error> {
+    let mut resolved = vec![];
+    for name in names {
+        let name = name.trim().to_ascii_lowercase();
+        let matches: Vec<&String> = available
+            .iter()
+            .filter(|rule| **rule == name || rule.split_once('/').is_some_and(|(_, rule)| rule == name))
+            .collect();
+
+        if matches.is_empty() {
+            return Err(Error::UnknownRule(name.clone(), suggest_rules(available, &name)));
+        }
+
+        resolved.extend(matches.into_iter().cloned());
+    }
+
+    Ok(resolved)
+}
+
+/// Returns up to three available rules whose full name, or name alone, is close to the given name.
+fn suggest_rules(available: &[String], name: &str) -> Vec<String> {
+    let threshold = (name.len() / 3).max(2);
+
+    let mut candidates: Vec<(usize, &String)> = available
+        .iter()
+        .map(|rule| {
+            let short = rule.split_once('/').map(|(_, short)| short).unwrap_or(rule);
+
+            (edit_distance(rule, name).min(edit_distance(short, name)), rule)
+        })
+        .filter(|(distance, _)| *distance <= threshold)
+        .collect();
+
+    candidates.sort();
+    candidates.into_iter().take(3).map(|(_, rule)| rule.clone()).collect()
+}
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+pub async fn execute(
+    command: LintCommand,
+    configuration: Configuration,
+    color: ColorChoice,
+) -> Result<ExitCode, Error> {
+    let in_github_actions = std::env::var("GITHUB_ACTIONS").is_ok_and(|value| value == "true");
+    if in_github_actions && command.reporting_format != ReportingFormat::Github {
+        mago_feedback::info!(
+            "GitHub Actions detected, use `--reporting-format github` to annotate pull requests with the issues found."
+        );
+    }
+
+    // Unknown rules are reported before loading the sources, so that mistakes are caught early.
+    let selection = match RuleSelection::new(&command.only, &command.exclude_rule) {
+        Ok(selection) if command.changed_no_reflection => {
+            mago_feedback::info!("Skipping reflection, the rules of the `analysis` plugin are disabled.");
+
+            selection.without_plugin("analysis")
+        }
+        Ok(selection) => selection,
+        Err(error) => {
+            mago_feedback::error!("{}", error);
+
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let mut selection_paths = match source::canonicalize_selection(&command.sources) {
+        Ok(paths) => paths,
+        Err(path) => {
+            mago_feedback::error!("Path '{}' does not exist.", path.display());
+
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    if let Some(reference) = &command.changed {
+        let changed = match git::changed_files(&configuration.source.root, reference) {
+            Ok(changed) => changed,
+            Err(error) => {
+                mago_feedback::error!("{}", error);
+
+                return Ok(ExitCode::FAILURE);
+            }
+        };
+
+        selection_paths
+            .extend(changed.into_iter().filter(|path| source::is_user_defined_path(&configuration.source, path)));
+        if selection_paths.is_empty() {
+            mago_feedback::info!("No changed files to lint compared to `{}`.", reference);
+
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        mago_feedback::debug!("Linting {} changed file(s) compared to `{}`.", selection_paths.len(), reference);
+    }
+
+    if command.watch {
+        return watch::watch(command, configuration, selection, selection_paths, color).await;
+    }
+
+    let external = ExternalReflection::new(
+        !command.semantics_only && !command.changed_no_reflection,
+        command.import_reflection.as_deref(),
+    );
+    let interner = ThreadedInterner::new();
+    let source_manager =
+        source::load(&interner, &configuration.source, external.is_available(), &selection_paths).await?;
+
+    let started_at = Instant::now();
+    let (issues, mut statistics) = if command.semantics_only {
+        check_sources(
+            &interner,
+            &source_manager,
+            configuration.php_version,
+            configuration.source.parser_settings(),
+            color,
+            !command.allow_duplicates,
+        )
+        .await?
+    } else {
+        lint_sources(
+            &interner,
+            &source_manager,
+            &configuration,
+            &selection,
+            &external,
+            color,
+            !command.allow_duplicates,
+        )
+        .await?
+    };
+
+    if let Some(path) = command.generate_baseline {
+        let path = path.unwrap_or_else(|| baseline_path(&configuration));
+        let baseline = Baseline::generate(&interner, &source_manager, &issues)?;
+        baseline.save(&path)?;
+
+        mago_feedback::info!("Baseline with {} issue(s) written to `{}`.", baseline.entries.len(), path.display());
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    // Baselined issues are removed before computing the exit code, so that they do not cause a failure.
+    let (mut issues, unused_entries) = match load_baseline(&configuration)? {
+        Some(mut baseline) => {
+            let (issues, mut matches) = baseline.filter(&interner, &source_manager, issues)?;
+            if !selection_paths.is_empty() {
+                // Entries of the files that were not linted cannot be considered unused.
+                matches.retain_analyzed(&interner, &source_manager);
+            }
+
+            statistics.baselined = matches.baselined;
+
+            if command.semantics_only {
+                // Linter issues are not produced in this mode, so entries cannot be considered unused.
+                if command.prune_baseline {
+                    mago_feedback::warn!("The baseline cannot be pruned when only performing semantic checks.");
+                }
+
+                (issues, IssueCollection::new())
+            } else if command.prune_baseline {
+                baseline.prune(&matches.unused);
+                baseline.save(&baseline_path(&configuration))?;
+
+                mago_feedback::info!("Removed {} unused entries from the baseline.", matches.unused.len());
+
+                (issues, IssueCollection::new())
+            } else {
+                (issues, matches.unused_entry_issues(&interner, &source_manager))
+            }
+        }
+        None => {
+            if command.prune_baseline {
+                mago_feedback::warn!("No baseline found, there is nothing to prune.");
+            }
+
+            (issues, IssueCollection::new())
+        }
+    };
+
+    let minimum_fail_level =
+        command.minimum_fail_level.or(configuration.linter.minimum_fail_level).unwrap_or(LinterFailLevel::Error);
+    let should_fail = minimum_fail_level.as_level().is_some_and(|level| issues.has_minimum_level(level));
+
+    let reporter = create_reporter(&command, &configuration, interner, source_manager, color);
+
+    // Unused baseline entries are reported, but never cause a failure.
+    issues.extend(unused_entries);
+
+    let issues = if command.fixable_only { IssueCollection::from(issues.only_fixable()) } else { issues };
+    let summary = if command.no_summary { None } else { Some(summarize(&issues, &statistics, started_at.elapsed())) };
+
+    match &command.reporting_output {
+        Some(path) => reporter.report_to_file(issues, command.reporting_format, path)?,
+        None => reporter.report(issues, command.reporting_format)?,
+    };
+
+    if let Some(summary) = summary {
+        // The summary is always written to stderr, so that it does not interfere with machine-readable formats.
+        eprintln!("{}", summary);
+    }
+
+    Ok(if should_fail { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+/// Creates the reporter for the given command.
+fn create_reporter(
+    command: &LintCommand,
+    configuration: &Configuration,
+    interner: ThreadedInterner,
+    manager: SourceManager,
+    color: ColorChoice,
+) -> Reporter {
+    Reporter::new(interner, manager, command.reporting_target, color.for_reporter(command.reporting_target))
+        .with_sorting(!command.no_sort)
+        .with_context_lines(command.context_lines.or(configuration.reporting.context_lines))
+        .with_grouping(command.group_by)
+        .with_path_style(command.paths)
+        .with_rule_descriptions(rule_descriptions())
+}
+
+/// Returns the path of the baseline file, relative paths being resolved against the source root.
+fn baseline_path(configuration: &Configuration) -> PathBuf {
+    let path = configuration.linter.baseline.as_deref().unwrap_or(Path::new(BASELINE_FILE));
+
+    configuration.source.root.join(path)
+}
+
+/// Loads the baseline, if the configured baseline or the default baseline file exists.
+fn load_baseline(configuration: &Configuration) -> Result<Option<Baseline>, Error> {
+    let path = baseline_path(configuration);
+    if !path.exists() {
+        if configuration.linter.baseline.is_some() {
+            mago_feedback::warn!("Baseline `{}` does not exist, no issues will be suppressed.", path.display());
+        }
+
+        return Ok(None);
+    }
+
+    mago_feedback::debug!("Loading baseline from `{}`.", path.display());
+
+    Baseline::load(&path).map(Some)
+}
+
+/// Builds the summary footer printed after the report.
+fn summarize(issues: &IssueCollection, statistics: &LintStatistics, elapsed: Duration) -> String {
+    let mut files = format!("{} file(s) scanned", statistics.files);
+    if statistics.unparsable_files > 0 {
+        files.push_str(&format!(", {} with parse errors", statistics.unparsable_files));
+    }
+
+    let fixable = issues.iter().filter(|issue| !issue.suggestions.is_empty()).count();
+    let levels = [
+        (Level::Error, "error(s)"),
+        (Level::Warning, "warning(s)"),
+        (Level::Help, "help message(s)"),
+        (Level::Note, "note(s)"),
+    ]
+    .into_iter()
+    .map(|(level, label)| format!("{} {}", issues.get_level_count(level), label))
+    .collect::<Vec<_>>()
+    .join(", ");
+
+    let mut phases = vec![format!("scan {:.2?}", statistics.scan_duration)];
+    if let Some(reflect_duration) = statistics.reflect_duration {
+        phases.push(format!("reflect {:.2?}", reflect_duration));
+    }
+
+    if let Some(check_duration) = statistics.check_duration {
+        phases.push(format!("check {:.2?}", check_duration));
+    }
+
+    if let Some(lint_duration) = statistics.lint_duration {
+        phases.push(format!("lint {:.2?}", lint_duration));
+    }
+
+    let mut totals = format!("{} total ({}), {} fixable", issues.len(), levels, fixable);
+    if statistics.baselined > 0 {
+        totals.push_str(&format!(", {} suppressed by the baseline", statistics.baselined));
+    }
+
+    format!("Summary: {}\nIssues: {}\nTime: {:.2?} ({})", files, totals, elapsed, phases.join(", "))
+}
+
+pub(super) fn create_linter(
+    interner: &ThreadedInterner,
+    php_version: PHPVersion,
+    configuration: &LinterConfiguration,
+    selection: &RuleSelection,
+    codebase: Arc<CodebaseReflection>,
+) -> Linter {
+    let mut settings = Settings::new().with_php_version(php_version);
+
+    if let Some(level) = configuration.level {
+        settings = match level {
+            LinterLevel::Off => settings.off(),
+            LinterLevel::Help => settings.with_level(Level::Help),
+            LinterLevel::Note => settings.with_level(Level::Note),
+            LinterLevel::Warning => settings.with_level(Level::Warning),
+            LinterLevel::Error => settings.with_level(Level::Error),
+        };
+    }
+
+    if let Some(default_plugins) = configuration.default_plugins {
+        settings = settings.with_default_plugins(default_plugins);
+    }
+
+    settings = settings.with_plugins(configuration.plugins.clone());
+
+    for rule in &configuration.rules {
+        let rule_settings = match rule.level {
+            Some(linter_level) => match linter_level {
+                LinterLevel::Off => RuleSettings::disabled(),
+                LinterLevel::Help => RuleSettings::from_level(Some(Level::Help)),
+                LinterLevel::Note => RuleSettings::from_level(Some(Level::Note)),
+                LinterLevel::Warning => RuleSettings::from_level(Some(Level::Warning)),
+                LinterLevel::Error => RuleSettings::from_level(Some(Level::Error)),
+            },
+            None => RuleSettings::enabled(),
+        };
+
+        settings = settings.with_rule(rule.name.clone(), rule_settings.with_options(rule.options.clone()));
+    }
+
+    let settings = selection.apply(settings);
+    let mut linter = Linter::new(settings, interner.clone(), codebase);
+
+    mago_linter::foreach_plugin!(|plugin| {
+        linter.add_plugin(plugin);
+    });
+
+    linter
+}
+
+/// The linters used for the project sources: one for the base configuration, and one for each override.
+#[derive(Debug, Clone)]
+pub(super) struct Linters {
+    base: Linter,
+    overrides: Vec<(LinterOverrideConfiguration, Linter)>,
+}
+
+impl Linters {
+    pub fn new(
+        interner: &ThreadedInterner,
+        php_version: PHPVersion,
+        configuration: &LinterConfiguration,
+        selection: &RuleSelection,
+        codebase: CodebaseReflection,
+    ) -> Self {
+        // The codebase is shared by all the linters, instead of being copied for every override.
+        let codebase = Arc::new(codebase);
+        let overrides = configuration
+            .overrides
+            .iter()
+            .map(|r#override| {
+                let linter = create_linter(
+                    interner,
+                    php_version,
+                    &configuration.with_override(r#override),
+                    selection,
+                    codebase.clone(),
+                );
+
+                (r#override.clone(), linter)
+            })
+            .collect();
+
+        Self { base: create_linter(interner, php_version, configuration, selection, codebase), overrides }
+    }
+
+    /// Returns the linter for the source with the given name, the last matching override taking precedence.
+    pub fn for_source(&self, name: &str) -> &Linter {
+        self.overrides
+            .iter()
+            .rev()
+            .find(|(r#override, _)| r#override.matches(name))
+            .map(|(_, linter)| linter)
+            .unwrap_or(&self.base)
+    }
+
+    /// Checks if any of the linters has an enabled rule requiring the index of the references.
+    pub fn requires_references(&self) -> bool {
+        self.base.requires_references() || self.overrides.iter().any(|(_, linter)| linter.requires_references())
+    }
+
+    /// Sets the index of the references to the symbols of the codebase, shared by all the linters.
+    pub fn with_references(self, references: ReferenceIndex) -> Self {
+        let references = Arc::new(references);
+
+        Self {
+            base: self.base.with_references(references.clone()),
+            overrides: self
+                .overrides
+                .into_iter()
+                .map(|(r#override, linter)| (r#override, linter.with_references(references.clone())))
+                .collect(),
+        }
+    }
+}
+
+/// A user-defined source, along with its semantics and reflections.
+#[derive(Debug, Clone)]
+pub(super) struct ScannedSource {
+    pub semantics: Arc<Semantics>,
+    pub reflections: CodebaseReflection,
+}
+
+#[inline]
+pub(super) async fn lint_sources(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    configuration: &Configuration,
+    selection: &RuleSelection,
+    external: &ExternalReflection,
+    color: ColorChoice,
+    deduplicate: bool,
+) -> Result<(IssueCollection, LintStatistics), Error> {
+    // Collect all user-defined sources.
+    let sources: Vec<_> = manager.user_defined_source_ids().collect();
+    let mut statistics = LintStatistics { files: sources.len(), ..Default::default() };
+
+    let cache = ReflectionCache::new(&configuration.cache);
+    // When the external sources are reflected, every source is looked up in the cache, so that the other entries
+    // can be pruned once the sources are scanned.
+    let prune_cache = *external == ExternalReflection::Reflect;
+    let reflect_started_at = Instant::now();
+    let external = external.load(interner, manager, &configuration.source, configuration.php_version, &cache).await?;
+    let mut reflect_duration = reflect_started_at.elapsed();
+
+    let scan_started_at = Instant::now();
+    let parser_settings = configuration.source.parser_settings();
+    let scanned =
+        scan_sources(interner, manager, &cache, configuration.php_version, parser_settings, sources, color).await?;
+    statistics.unparsable_files = scanned.iter().filter(|source| source.semantics.has_parse_error()).count();
+    statistics.scan_duration = scan_started_at.elapsed();
+
+    if prune_cache {
+        cache.prune();
+    }
+
+    let reflect_started_at = Instant::now();
+    let mut semantics = Vec::with_capacity(scanned.len());
+    let mut codebase = build_codebase(
+        interner,
+        external,
+        scanned.into_iter().map(|source| {
+            semantics.push(source.semantics);
+
+            source.reflections
+        }),
+    );
+    reflect_duration += reflect_started_at.elapsed();
+    statistics.reflect_duration = Some(reflect_duration);
+
+    // The issues found while reflecting the codebase, such as duplicate declarations, and while checking
+    // the inheritance of its class-likes, are reported with the others.
+    let check_started_at = Instant::now();
+    let mut reflection_issues = std::mem::take(&mut codebase.issues);
+    reflection_issues.extend(check_inheritance(interner, &codebase, color));
+    statistics.check_duration = Some(check_started_at.elapsed());
+
+    let lint_started_at = Instant::now();
+    let mut linters = Linters::new(interner, configuration.php_version, &configuration.linter, selection, codebase);
+    if linters.requires_references() {
+        linters = linters.with_references(index_references(interner, &semantics, color).await?);
+    }
+
+    let linters = Arc::new(linters);
+    let results = lint_semantics(interner, &linters, semantics.clone(), color).await?;
+    statistics.lint_duration = Some(lint_started_at.elapsed());
+
+    let mut issues = IssueCollection::from(results.into_iter().flatten());
+    issues.extend(reflection_issues);
+    let mut issues = apply_suppressions(interner, &linters, &semantics, issues);
+    if deduplicate {
+        issues.deduplicate();
+    }
+
+    Ok((issues, statistics))
+}
+
+/// Builds the semantics, and reflections of the given sources, in parallel.
+///
+/// The reflections are loaded from the given cache when the content of their source did not change.
+///
+/// The scanned sources are returned sorted by name, so that when they are merged into the codebase, the
+/// first declaration of a symbol, and the duplicate ones reported, are the same from one run to another.
+pub(super) async fn scan_sources(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    cache: &ReflectionCache,
+    php_version: PHPVersion,
+    parser_settings: ParserSettings,
+    sources: Vec<SourceIdentifier>,
+    color: ColorChoice,
+) -> Result<Vec<ScannedSource>, Error> {
+    let progress_bar = create_progress_bar(
+        sources.len(),
+        "🔎  Scanning",
+        ProgressBarTheme::Yellow,
+        color.use_colors(ReportingTarget::Stderr),
+    );
+
+    let mut handles = Vec::with_capacity(sources.len());
+    for source_id in sources {
+        handles.push(tokio::spawn({
+            let interner = interner.clone();
+            let manager = manager.clone();
+            let cache = cache.clone();
+            let progress_bar = progress_bar.clone();
+
+            async move {
+                // Step 1: load the source
+                let source = manager.load(&source_id)?;
+                // Step 2: build semantics
+                let semantics = Semantics::build(&interner, php_version, parser_settings, source);
+                let reflections =
+                    cache.get_or_reflect(&interner, &semantics.source, php_version, parser_settings, || {
+                        reflect(&interner, &semantics.source, &semantics.program, &semantics.names)
+                    });
+                progress_bar.inc(1);
+
+                Result::<_, Error>::Ok(ScannedSource { semantics: Arc::new(semantics), reflections })
+            }
+        }));
+    }
+
+    let mut scanned = Vec::with_capacity(handles.len());
+    for handle in handles {
+        scanned.push(handle.await??);
+    }
+
+    remove_progress_bar(progress_bar);
+
+    scanned.sort_by(|a, b| {
+        interner.lookup(&a.semantics.source.identifier.0).cmp(interner.lookup(&b.semantics.source.identifier.0))
+    });
+
+    Ok(scanned)
+}
+
+/// Merges the reflections of the user-defined sources into the reflection of the external sources.
+pub(super) fn build_codebase(
+    interner: &ThreadedInterner,
+    external: CodebaseReflection,
+    reflections: impl IntoIterator<Item = CodebaseReflection>,
+) -> CodebaseReflection {
+    let mut codebase = external;
+    // The duplicate declarations among the external sources are not reported, as they are not for the user to fix.
+    codebase.issues = IssueCollection::new();
+    for reflection in reflections {
+        codebase = mago_reflector::merge(interner, codebase, reflection);
+    }
+
+    mago_reflector::populate(interner, &mut codebase);
+
+    codebase
+}
+
+/// Checks the inheritance of the user-defined class-likes against the rest of the codebase, such as the
+/// abstract methods they must implement, and the compatibility of the methods they override.
+pub(super) fn check_inheritance(
+    interner: &ThreadedInterner,
+    codebase: &CodebaseReflection,
+    color: ColorChoice,
+) -> IssueCollection {
+    let class_likes = codebase
+        .class_like_reflections
+        .values()
+        .filter(|class_like| class_like.span.start.source.is_user_defined())
+        .collect::<Vec<_>>();
+
+    let progress_bar = create_progress_bar(
+        class_likes.len(),
+        "🧬  Checking",
+        ProgressBarTheme::Yellow,
+        color.use_colors(ReportingTarget::Stderr),
+    );
+
+    let mut issues = IssueCollection::new();
+    for class_like in class_likes {
+        issues.extend(mago_reflector::check_inheritance(interner, codebase, class_like));
+        progress_bar.inc(1);
+    }
+
+    remove_progress_bar(progress_bar);
+
+    issues
+}
+
+/// Indexes the references made by the given semantics to the symbols of the codebase, in parallel.
+pub(super) async fn index_references(
+    interner: &ThreadedInterner,
+    semantics: &[Arc<Semantics>],
+    color: ColorChoice,
+) -> Result<ReferenceIndex, Error> {
+    let progress_bar = create_progress_bar(
+        semantics.len(),
+        "🔗  Indexing",
+        ProgressBarTheme::Yellow,
+        color.use_colors(ReportingTarget::Stderr),
+    );
+
+    let mut handles = Vec::with_capacity(semantics.len());
+    for semantic in semantics {
+        handles.push(tokio::spawn({
+            let interner = interner.clone();
+            let semantic = semantic.clone();
+            let progress_bar = progress_bar.clone();
+
+            async move {
+                let references = mago_reflector::index_references(&interner, &semantic.program, &semantic.names);
+                progress_bar.inc(1);
+
+                references
+            }
+        }));
+    }
+
+    let mut references = ReferenceIndex::new();
+    for handle in handles {
+        references.extend(handle.await?);
+    }
+
+    remove_progress_bar(progress_bar);
+
+    Ok(references)
+}
+
+/// Removes the issues suppressed by the comments of the given sources from the issues found while reflecting,
+/// checking, and linting them, regardless of the analysis which found them.
+///
+/// The suppressions of a source apply to the issues whose primary annotation is in this source, and the
+/// suppressions of every source are checked, so that the ones which do not match any issue are reported,
+/// unless they expect a rule which is not enabled by the linter of the source.
+pub(super) fn apply_suppressions(
+    interner: &ThreadedInterner,
+    linters: &Linters,
+    semantics: &[Arc<Semantics>],
+    issues: IssueCollection,
+) -> IssueCollection {
+    let mut issues_by_source: HashMap<SourceIdentifier, IssueCollection> = HashMap::default();
+    let mut remaining = IssueCollection::new();
+    for issue in issues {
+        let source = issue.annotations.iter().find(|annotation| annotation.is_primary()).map(|a| a.span.start.source);
+
+        match source {
+            Some(source) => issues_by_source.entry(source).or_default().push(issue),
+            None => remaining.push(issue),
+        }
+    }
+
+    for semantic in semantics {
+        let issues = issues_by_source.remove(&semantic.source.identifier).unwrap_or_default();
+        let enabled_rules = linters.for_source(interner.lookup(&semantic.source.identifier.value())).enabled_rules();
+
+        remaining.extend(mago_linter::suppression::apply_suppressions(interner, semantic, issues, &enabled_rules));
+    }
+
+    // The issues of the sources which were not linted, such as the external ones, are kept as they are.
+    remaining.extend(issues_by_source.into_values().flatten());
+
+    remaining
+}
+
+/// Lints the given semantics in parallel, returning the issues of each source in the same order.
+///
+/// The returned issues are not filtered by the suppression comments of the sources, see [`apply_suppressions`].
+pub(super) async fn lint_semantics(
+    interner: &ThreadedInterner,
+    linters: &Arc<Linters>,
+    semantics: Vec<Arc<Semantics>>,
+    color: ColorChoice,
+) -> Result<Vec<IssueCollection>, Error> {
+    let progress_bar = create_progress_bar(
+        semantics.len(),
+        "🧹  Linting",
+        ProgressBarTheme::Yellow,
+        color.use_colors(ReportingTarget::Stderr),
+    );
+
+    let mut handles = Vec::with_capacity(semantics.len());
+    for semantic in semantics {
+        handles.push(tokio::spawn({
+            let interner = interner.clone();
+            let linters = linters.clone();
+            let progress_bar = progress_bar.clone();
+
+            async move {
+                let linter = linters.for_source(interner.lookup(&semantic.source.identifier.0));
+                let mut issues = linter.lint(&semantic);
+                issues.extend(semantic.issues.iter().cloned());
+                for error in &semantic.parse_errors {
+                    issues.push(Into::<Issue>::into(error));
+                }
+
+                progress_bar.inc(1);
+
+                issues
+            }
+        }));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await?);
+    }
+
+    remove_progress_bar(progress_bar);
+
+    Ok(results)
+}
+
+#[inline]
+pub(super) async fn check_sources(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    php_version: PHPVersion,
+    parser_settings: ParserSettings,
+    color: ColorChoice,
+    deduplicate: bool,
+) -> Result<(IssueCollection, LintStatistics), Error> {
+    // Collect all user-defined sources.
+    let sources: Vec<_> = manager.user_defined_source_ids().collect();
+    let length = sources.len();
+    let mut statistics = LintStatistics { files: length, ..Default::default() };
+
+    let scan_started_at = Instant::now();
+    let progress_bar = create_progress_bar(
+        length,
+        "🔎  Scanning",
+        ProgressBarTheme::Yellow,
+        color.use_colors(ReportingTarget::Stderr),
+    );
+    let mut handles = Vec::with_capacity(length);
+    for source_id in sources {
+        handles.push(tokio::spawn({
+            let interner = interner.clone();
+            let manager = manager.clone();
+            let progress_bar = progress_bar.clone();
+
+            async move {
+                let source = manager.load(&source_id)?;
+                let semantics = Semantics::build(&interner, php_version, parser_settings, source);
+                progress_bar.inc(1);
+
+                Result::<_, Error>::Ok(semantics)
+            }
+        }));
+    }
+
+    let mut results = Vec::with_capacity(length);
+    for handle in handles {
+        let semantic = handle.await??;
+
+        if semantic.has_parse_error() {
+            statistics.unparsable_files += 1;
+        }
+
+        let mut issues = IssueCollection::from(semantic.parse_errors.iter().map(Into::<Issue>::into));
+        issues.extend(semantic.issues.iter().cloned());
+
+        // No linter rule is enabled, as the linter is not run.
+        results.extend(mago_linter::suppression::apply_suppressions(interner, &semantic, issues, &[]));
+    }
+
+    remove_progress_bar(progress_bar);
+
+    statistics.scan_duration = scan_started_at.elapsed();
+
+    let mut issues = IssueCollection::from(results);
+    if deduplicate {
+        issues.deduplicate();
+    }
+
+    Ok((issues, statistics))
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_fixer::FixPlan;
+    use mago_fixer::SafetyClassification;
+    use mago_source::SourceManager;
+
+    use super::*;
+    use crate::config::cache::CacheConfiguration;
+    use crate::config::linter::LinterRuleConfiguration;
+
+    fn lint(linters: &Linters, interner: &ThreadedInterner, name: &str, content: &str) -> Vec<String> {
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.load(&manager.insert_content(name.to_string(), content.to_string(), true)).unwrap();
+        let semantics = Semantics::build(interner, PHPVersion::LATEST, ParserSettings::default(), source);
+
+        linters.for_source(name).lint(&semantics).iter().filter_map(|issue| issue.code.clone()).collect()
+    }
+
+    #[test]
+    fn test_overrides_apply_to_matching_paths() {
+        let configuration = LinterConfiguration {
+            default_plugins: Some(false),
+            plugins: vec!["safety".to_string()],
+            overrides: vec![
+                LinterOverrideConfiguration {
+                    paths: vec!["tests".to_string()],
+                    rules: vec![LinterRuleConfiguration {
+                        name: "safety/no-eval".to_string(),
+                        level: Some(LinterLevel::Off),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                LinterOverrideConfiguration {
+                    paths: vec!["tests/Strict/**".to_string()],
+                    plugins: Some(vec!["safety".to_string()]),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let interner = ThreadedInterner::new();
+        let linters = Linters::new(
+            &interner,
+            PHPVersion::LATEST,
+            &configuration,
+            &RuleSelection::default(),
+            CodebaseReflection::new(),
+        );
+        let content = "<?php eval('1');";
+
+        assert_eq!(lint(&linters, &interner, "src/a.php", content), vec!["safety/no-eval".to_string()]);
+        assert!(lint(&linters, &interner, "tests/a.php", content).is_empty());
+        assert_eq!(lint(&linters, &interner, "tests/Strict/a.php", content), vec!["safety/no-eval".to_string()]);
+    }
+
+    #[test]
+    fn test_overrides_share_the_codebase() {
+        let configuration = LinterConfiguration {
+            overrides: vec![
+                LinterOverrideConfiguration { paths: vec!["tests".to_string()], ..Default::default() },
+                LinterOverrideConfiguration { paths: vec!["legacy".to_string()], ..Default::default() },
+            ],
+            ..Default::default()
+        };
+
+        let interner = ThreadedInterner::new();
+        let linters = Linters::new(
+            &interner,
+            PHPVersion::LATEST,
+            &configuration,
+            &RuleSelection::default(),
+            CodebaseReflection::new(),
+        );
+
+        let codebase = linters.for_source("src/a.php").codebase();
+        assert!(Arc::ptr_eq(codebase, linters.for_source("tests/a.php").codebase()));
+        assert!(Arc::ptr_eq(codebase, linters.for_source("legacy/a.php").codebase()));
+        assert_eq!(Arc::strong_count(codebase), 3);
+    }
+
+    #[test]
+    fn test_rule_selection_resolves_names() {
+        let selection = RuleSelection::new(&["no-eval".to_string()], &["SAFETY/no-ffi".to_string()]).unwrap();
+
+        assert_eq!(selection.only, vec!["safety/no-eval".to_string()]);
+        assert_eq!(selection.excluded, vec!["safety/no-ffi".to_string()]);
+    }
+
+    #[test]
+    fn test_rule_selection_suggests_close_matches() {
+        let error = RuleSelection::new(&["safety/no-evl".to_string()], &[]).unwrap_err();
+
+        assert!(
+            matches!(&error, Error::UnknownRule(rule, suggestions) if rule == "safety/no-evl" && suggestions[0] == "safety/no-eval")
+        );
+        assert!(
+            matches!(RuleSelection::new(&[], &["definitely-not-a-rule-name".to_string()]), Err(Error::UnknownRule(_, suggestions)) if suggestions.is_empty())
+        );
+    }
+
+    #[test]
+    fn test_only_runs_the_selected_rules() {
+        let configuration = LinterConfiguration { default_plugins: Some(false), ..Default::default() };
+        let selection = RuleSelection::new(&["safety/no-global".to_string()], &[]).unwrap();
+
+        let interner = ThreadedInterner::new();
+        let linters =
+            Linters::new(&interner, PHPVersion::LATEST, &configuration, &selection, CodebaseReflection::new());
+
+        assert_eq!(
+            lint(&linters, &interner, "src/a.php", "<?php eval('1'); global $a;"),
+            vec!["safety/no-global".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_code_following_an_invalid_numeric_literal_is_linted() {
+        let configuration = LinterConfiguration { default_plugins: Some(false), ..Default::default() };
+        let selection = RuleSelection::new(&["safety/no-eval".to_string()], &[]).unwrap();
+        let interner = ThreadedInterner::new();
+        let linters =
+            Linters::new(&interner, PHPVersion::LATEST, &configuration, &selection, CodebaseReflection::new());
+
+        let content = "<?php\neval('1');\n$c = 1__0;\neval('2');\n$x = ;\neval('3');\n";
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.load(&manager.insert_content("src/a.php".to_string(), content.to_string(), true)).unwrap();
+        let semantics = Semantics::build(&interner, PHPVersion::LATEST, ParserSettings::default(), source);
+
+        assert_eq!(2, semantics.parse_errors.len());
+        assert_eq!(lint(&linters, &interner, "src/a.php", content).len(), 3);
+    }
+
+    #[test]
+    fn test_rules_requiring_a_newer_php_version_are_skipped() {
+        let configuration = LinterConfiguration { default_plugins: Some(false), ..Default::default() };
+        let selection = RuleSelection::new(&["migration/str-contains".to_string()], &[]).unwrap();
+        let content = "<?php if (strpos($a, 'b') !== false) {}";
+
+        let interner = ThreadedInterner::new();
+        let linters = Linters::new(&interner, PHPVersion::PHP74, &configuration, &selection, CodebaseReflection::new());
+        assert_eq!(lint(&linters, &interner, "src/a.php", content), Vec::<String>::new());
+
+        let linters = Linters::new(&interner, PHPVersion::PHP80, &configuration, &selection, CodebaseReflection::new());
+        assert_eq!(lint(&linters, &interner, "src/a.php", content), vec!["migration/str-contains".to_string()]);
+    }
+
+    #[test]
+    fn test_unused_symbols_are_reported() {
+        let configuration = LinterConfiguration {
+            default_plugins: Some(false),
+            rules: vec![LinterRuleConfiguration {
+                name: "dead-code/unused-symbol".to_string(),
+                options: [("entry_points".to_string(), toml::Value::Array(vec!["App\\Controller\\*".into()]))]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let selection = RuleSelection::new(&["dead-code/unused-symbol".to_string()], &[]).unwrap();
+        let content = r#"<?php
+            namespace App;
+
+            interface Greeter { public function greet(): string; }
+
+            final class Hello implements Greeter {
+                const USED = 1;
+                const UNUSED = 2;
+
+                public function __construct() {}
+                public function greet(): string { return $this->format(self::USED); }
+                private function format(int $value): string { return (string) $value; }
+                public function unused(): void { $this->unused(); }
+                #[Route('/')]
+                public function wired(): void {}
+            }
+
+            final class Unused { public function unused(): void {} }
+
+            function greet(Greeter $greeter): string { return $greeter->greet(); }
+            function unused(): void { unused(); }
+
+            echo greet(new Hello());
+        "#;
+        let controller = "<?php namespace App\\Controller; final class HomeController { public function index() {} }";
+
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let mut semantics = vec![];
+        for (name, content) in [("src/a.php", content), ("src/Controller/HomeController.php", controller)] {
+            let source = manager.load(&manager.insert_content(name.to_string(), content.to_string(), true)).unwrap();
+            semantics.push(Semantics::build(&interner, PHPVersion::LATEST, ParserSettings::default(), source));
+        }
+
+        let codebase = build_codebase(
+            &interner,
+            CodebaseReflection::new(),
+            semantics
+                .iter()
+                .map(|semantics| reflect(&interner, &semantics.source, &semantics.program, &semantics.names)),
+        );
+        let mut references = ReferenceIndex::new();
+        for semantics in &semantics {
+            references.extend(mago_reflector::index_references(&interner, &semantics.program, &semantics.names));
+        }
+
+        let linters = Linters::new(&interner, PHPVersion::LATEST, &configuration, &selection, codebase)
+            .with_references(references);
+
+        let unused: Vec<_> = semantics
+            .iter()
+            .flat_map(|semantics| linters.for_source("src/a.php").lint(semantics))
+            .map(|issue| issue.message)
+            .collect();
+
+        assert_eq!(
+            unused,
+            vec![
+                "Unused class constant `App\\Hello::UNUSED`.".to_string(),
+                "Unused method `App\\Hello::unused`.".to_string(),
+                "Unused class `App\\Unused`.".to_string(),
+                "Unused function `App\\unused`.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_later_duplicate_declarations_are_reported_in_source_order() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let sources: Vec<_> = ["src/c.php", "src/a.php", "src/b.php"]
+            .into_iter()
+            .map(|name| manager.insert_content(name.to_string(), "<?php final class Foo {}".to_string(), true))
+            .collect();
+
+        let cache = ReflectionCache::new(&CacheConfiguration { enabled: false, ..Default::default() });
+        let scanned = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(scan_sources(
+                &interner,
+                &manager,
+                &cache,
+                PHPVersion::LATEST,
+                ParserSettings::default(),
+                sources,
+                ColorChoice::Never,
+            ))
+            .unwrap();
+
+        let codebase = build_codebase(&interner, CodebaseReflection::new(), scanned.into_iter().map(|s| s.reflections));
+        let mut duplicates: Vec<_> = codebase
+            .issues
+            .iter()
+            .filter_map(|issue| issue.annotations.iter().find(|annotation| annotation.is_primary()))
+            .map(|annotation| interner.lookup(&annotation.span.start.source.0).to_string())
+            .collect();
+        duplicates.sort();
+
+        assert_eq!(duplicates, vec!["src/b.php".to_string(), "src/c.php".to_string()]);
+    }
+
+    fn lint_with_suppressions(
+        linters: &Linters,
+        interner: &ThreadedInterner,
+        sources: &[(&str, &str)],
+    ) -> Vec<(String, String)> {
+        let manager = SourceManager::new(interner.clone());
+        let mut semantics = vec![];
+        let mut issues = IssueCollection::new();
+        for (name, content) in sources {
+            let source = manager.load(&manager.insert_content(name.to_string(), content.to_string(), true)).unwrap();
+            let semantic = Semantics::build(interner, PHPVersion::LATEST, ParserSettings::default(), source);
+
+            issues.extend(linters.for_source(name).lint(&semantic));
+            issues.extend(semantic.issues.iter().cloned());
+            semantics.push(Arc::new(semantic));
+        }
+
+        let mut remaining: Vec<_> = apply_suppressions(interner, linters, &semantics, issues)
+            .into_iter()
+            .map(|issue| {
+                let source =
+                    issue.annotations.iter().find(|annotation| annotation.is_primary()).unwrap().span.start.source;
+
+                (interner.lookup(&source.0).to_string(), issue.code.unwrap_or_default())
+            })
+            .collect();
+        remaining.sort();
+
+        remaining
+    }
+
+    #[test]
+    fn test_suppressions_apply_to_linter_issues() {
+        let configuration = LinterConfiguration { default_plugins: Some(false), ..Default::default() };
+        let selection =
+            RuleSelection::new(&["safety/no-eval".to_string(), "safety/no-global".to_string()], &[]).unwrap();
+        let interner = ThreadedInterner::new();
+        let linters =
+            Linters::new(&interner, PHPVersion::LATEST, &configuration, &selection, CodebaseReflection::new());
+
+        let content = "<?php\n// @mago-expect safety/no-eval\neval('1');\nglobal $a;\n";
+
+        assert_eq!(
+            lint_with_suppressions(&linters, &interner, &[("src/a.php", content)]),
+            vec![("src/a.php".to_string(), "safety/no-global".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_expectations_of_disabled_rules_are_not_reported() {
+        let configuration = LinterConfiguration { default_plugins: Some(false), ..Default::default() };
+        let selection = RuleSelection::new(&["safety/no-global".to_string()], &[]).unwrap();
+        let interner = ThreadedInterner::new();
+        let linters =
+            Linters::new(&interner, PHPVersion::LATEST, &configuration, &selection, CodebaseReflection::new());
+
+        let content = "<?php\n// @mago-expect safety/no-eval\n$a = 1;\n// @mago-expect no-global\n$b = 2;\n";
+
+        assert_eq!(
+            lint_with_suppressions(&linters, &interner, &[("src/a.php", content)]),
+            vec![("src/a.php".to_string(), "unused-suppression".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_suppressions_apply_to_semantic_issues() {
+        let configuration = LinterConfiguration { default_plugins: Some(false), ..Default::default() };
+        let interner = ThreadedInterner::new();
+        let linters = Linters::new(
+            &interner,
+            PHPVersion::LATEST,
+            &configuration,
+            &RuleSelection::new(&["safety/no-eval".to_string()], &[]).unwrap(),
+            CodebaseReflection::new(),
+        );
+
+        let expected = "<?php\nfunction a() {\n    // @mago-expect semantics/undefined-variable\n    return $a;\n}\n";
+        let ignored = "<?php\n// @mago-ignore-file undefined-variable\nfunction b() {\n    return $b;\n}\n";
+        let disabled = "<?php\n// @mago-disable undefined-variable\nfunction c() { return $c; }\n// @mago-enable undefined-variable\nfunction d() { return $d; }\n";
+        let unused = "<?php\n// @mago-expect semantics/undefined-variable\nfunction e($e) { return $e; }\n";
+
+        assert_eq!(
+            lint_with_suppressions(
+                &linters,
+                &interner,
+                &[("src/a.php", expected), ("src/b.php", ignored), ("src/c.php", disabled), ("src/e.php", unused)]
+            ),
+            vec![
+                ("src/c.php".to_string(), "semantics/undefined-variable".to_string()),
+                ("src/e.php".to_string(), "unused-suppression".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expectations_apply_to_the_statement_following_them() {
+        let configuration = LinterConfiguration { default_plugins: Some(false), ..Default::default() };
+        let selection =
+            RuleSelection::new(&["safety/no-eval".to_string(), "safety/no-global".to_string()], &[]).unwrap();
+        let interner = ThreadedInterner::new();
+        let linters =
+            Linters::new(&interner, PHPVersion::LATEST, &configuration, &selection, CodebaseReflection::new());
+
+        let fulfilled =
+            "<?php\n// @mago-expect no-eval\neval('1');\nglobal $a; // @mago-expect no-global\nglobal $b;\n";
+        let unfulfilled = "<?php\n// @mago-expect no-eval\n\neval('1');\n";
+        let multi_line = "<?php\n/**\n * @mago-expect safety/no-eval\n */\n$a = array_map(\n    fn($b) => eval($b),\n    $c,\n);\neval('2');\n";
+        let trailing_code = "<?php\n/* @mago-expect no-eval */ eval('3');\nglobal $d;\n";
+
+        assert_eq!(
+            lint_with_suppressions(
+                &linters,
+                &interner,
+                &[
+                    ("src/a.php", fulfilled),
+                    ("src/b.php", unfulfilled),
+                    ("src/c.php", multi_line),
+                    ("src/d.php", trailing_code)
+                ]
+            ),
+            vec![
+                ("src/a.php".to_string(), "safety/no-global".to_string()),
+                ("src/b.php".to_string(), "safety/no-eval".to_string()),
+                ("src/b.php".to_string(), "unused-suppression".to_string()),
+                ("src/c.php".to_string(), "safety/no-eval".to_string()),
+                ("src/d.php".to_string(), "safety/no-global".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fixable_issues_are_tagged_with_their_fix_safety() {
+        let configuration = LinterConfiguration { default_plugins: Some(false), ..Default::default() };
+        let selection = RuleSelection::new(
+            &[
+                "redundancy/redundant-parentheses".to_string(),
+                "best-practices/no-empty-loop".to_string(),
+                "strictness/require-identity-comparison".to_string(),
+            ],
+            &[],
+        )
+        .unwrap();
+
+        let interner = ThreadedInterner::new();
+        let linters =
+            Linters::new(&interner, PHPVersion::LATEST, &configuration, &selection, CodebaseReflection::new());
+
+        let manager = SourceManager::new(interner.clone());
+        let content = "<?php $a = (1); while ($a) {} if ($a == 1) {}";
+        let source = manager.load(&manager.insert_content("a.php".to_string(), content.to_string(), true)).unwrap();
+        let semantics = Semantics::build(&interner, PHPVersion::LATEST, ParserSettings::default(), source);
+
+        let mut safety: Vec<_> = linters
+            .for_source("a.php")
+            .lint(&semantics)
+            .iter()
+            .map(|issue| (issue.code.clone().unwrap_or_default(), issue.get_fix_safety()))
+            .collect();
+        safety.sort();
+
+        assert_eq!(
+            safety,
+            vec![
+                ("best-practices/no-empty-loop".to_string(), Some(SafetyClassification::PotentiallyUnsafe)),
+                ("redundancy/redundant-parentheses".to_string(), Some(SafetyClassification::Safe)),
+                ("strictness/require-identity-comparison".to_string(), Some(SafetyClassification::Unsafe)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summary_shows_counts_and_phase_timings() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content("src/a.php".to_string(), "<?php eval('1');".to_string(), true);
+
+        let mut plan = FixPlan::new();
+        plan.delete(6..16, SafetyClassification::Unsafe);
+
+        let issues = IssueCollection::from(vec![
+            Issue::error("Unsafe use of `eval`.").with_suggestion(source, plan),
+            Issue::error("Another error."),
+            Issue::warning("A warning."),
+            Issue::note("A note."),
+        ]);
+        let statistics = LintStatistics {
+            files: 3,
+            unparsable_files: 1,
+            scan_duration: Duration::from_millis(250),
+            reflect_duration: Some(Duration::from_millis(1500)),
+            lint_duration: Some(Duration::from_secs(2)),
+            baselined: 4,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            summarize(&issues, &statistics, Duration::from_secs(4)),
+            [
+                "Summary: 3 file(s) scanned, 1 with parse errors",
+                "Issues: 4 total (2 error(s), 1 warning(s), 0 help message(s), 1 note(s)), 1 fixable, 4 suppressed by the baseline",
+                "Time: 4.00s (scan 250.00ms, reflect 1.50s, lint 2.00s)",
+            ]
+            .join("\n")
+        );
+
+        assert_eq!(
+            summarize(&IssueCollection::new(), &LintStatistics { files: 1, ..Default::default() }, Duration::ZERO),
+            [
+                "Summary: 1 file(s) scanned",
+                "Issues: 0 total (0 error(s), 0 warning(s), 0 help message(s), 0 note(s)), 0 fixable",
+                "Time: 0.00ns (scan 0.00ns)",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use mago_interner::ThreadedInterner;
+use mago_reflection::reference::ReferenceIndex;
+use mago_reflection::CodebaseReflection;
+use mago_reporting::IssueCollection;
+use mago_source::SourceManager;
+use notify::RecursiveMode;
+use notify_debouncer_mini::new_debouncer;
+use notify_debouncer_mini::DebounceEventResult;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::baseline::Baseline;
+use crate::cache::ReflectionCache;
+use crate::color::ColorChoice;
+use crate::commands::lint::apply_suppressions;
+use crate::commands::lint::build_codebase;
+use crate::commands::lint::check_inheritance;
+use crate::commands::lint::create_reporter;
+use crate::commands::lint::index_references;
+use crate::commands::lint::lint_semantics;
+use crate::commands::lint::load_baseline;
+use crate::commands::lint::scan_sources;
+use crate::commands::lint::summarize;
+use crate::commands::lint::LintCommand;
+use crate::commands::lint::LintStatistics;
+use crate::commands::lint::Linters;
+use crate::commands::lint::RuleSelection;
+use crate::commands::lint::ScannedSource;
+use crate::config::Configuration;
+use crate::error::Error;
+use crate::reflection::ExternalReflection;
+use crate::source;
+
+/// The time during which the sources must not change before they are linted again, as editors often save
+/// files in multiple steps.
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// An event received while watching the sources.
+enum Event {
+    /// Files changed in the watched paths.
+    Changed,
+    /// The user asked to stop watching, e.g. by pressing Ctrl-C.
+    Interrupted,
+}
+
+/// The name, modification time, and size of every user-defined source file, keyed by path.
+type Snapshot = BTreeMap<PathBuf, (String, Option<SystemTime>, u64)>;
+
+/// A user-defined source that was linted in a previous iteration.
+struct CachedSource {
+    modified: Option<SystemTime>,
+    size: u64,
+    scanned: ScannedSource,
+    issues: IssueCollection,
+}
+
+/// The state kept across the iterations of the watch mode.
+struct Watcher {
+    command: LintCommand,
+    configuration: Configuration,
+    selection: RuleSelection,
+    color: ColorChoice,
+    interner: ThreadedInterner,
+    cache: ReflectionCache,
+    external: CodebaseReflection,
+    baseline: Option<Baseline>,
+    sources: BTreeMap<PathBuf, CachedSource>,
+    /// The references indexed in the previous iteration, if any rule required them.
+    references: Option<ReferenceIndex>,
+    reported: bool,
+}
+
+/// Lints the sources, then lints them again whenever they change, until interrupted.
+///
+/// External sources are only reflected once, and the semantics and reflections of the user-defined
+/// sources are reused across iterations, so that only the files whose content changed are scanned again.
+pub(super) async fn watch(
+    command: LintCommand,
+    configuration: Configuration,
+    selection: RuleSelection,
+    selection_paths: Vec<PathBuf>,
+    color: ColorChoice,
+) -> Result<ExitCode, Error> {
+    let (sender, mut events) = tokio::sync::mpsc::unbounded_channel();
+    listen_for_interruption(sender.clone());
+
+    let interner = ThreadedInterner::new();
+    let manager = source::load(&interner, &configuration.source, true, &selection_paths).await?;
+    let cache = ReflectionCache::new(&configuration.cache);
+    let external = ExternalReflection::new(true, command.import_reflection.as_deref())
+        .load(&interner, &manager, &configuration.source, configuration.php_version, &cache)
+        .await?;
+    let baseline = load_baseline(&configuration)?;
+
+    let mut watcher = Watcher {
+        command,
+        configuration,
+        selection,
+        color,
+        interner,
+        cache,
+        external,
+        baseline,
+        sources: BTreeMap::new(),
+        references: None,
+        reported: false,
+    };
+
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, move |result: DebounceEventResult| match result {
+        Ok(_) => {
+            sender.send(Event::Changed).ok();
+        }
+        Err(error) => mago_feedback::error!("{}", Error::Watching(error)),
+    })
+    .map_err(Error::Watching)?;
+
+    for path in watched_paths(&watcher.configuration, &selection_paths) {
+        debouncer.watcher().watch(&path, RecursiveMode::Recursive).map_err(Error::Watching)?;
+    }
+
+    let mut linted: Option<Snapshot> = None;
+    loop {
+        // Changes may not affect the sources, e.g. when a file that is not a source is written.
+        let snapshot = snapshot(&watcher.configuration, &selection_paths).await?;
+        if linted.as_ref() != Some(&snapshot) {
+            match watcher.lint(&snapshot).await {
+                Ok(true) => mago_feedback::info!("Watching for changes, press Ctrl-C to stop."),
+                Ok(false) => {}
+                Err(error) => mago_feedback::error!("{}", error),
+            }
+
+            linted = Some(snapshot);
+        }
+
+        match events.recv().await {
+            Some(Event::Changed) => {}
+            Some(Event::Interrupted) | None => break,
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+impl Watcher {
+    /// Lints the sources whose content changed since the previous iteration, or all the sources if the reflected
+    /// codebase changed, and reports all issues.
+    ///
+    /// Returns whether the issues were reported, which is not the case if no source was added, removed, or changed.
+    async fn lint(&mut self, snapshot: &Snapshot) -> Result<bool, Error> {
+        let started_at = Instant::now();
+        let manager = SourceManager::new(self.interner.clone());
+
+        let previous_length = self.sources.len();
+        self.sources.retain(|path, _| snapshot.contains_key(path));
+        let removed = self.sources.len() != previous_length;
+
+        let mut changed = vec![];
+        for (path, (name, modified, size)) in snapshot {
+            let source_id = manager.insert_path(name.clone(), path.clone(), true);
+
+            if let Some(cached) = self.sources.get_mut(path) {
+                if cached.modified == *modified && cached.size == *size {
+                    continue;
+                }
+
+                // Editors may touch files without changing their content.
+                if manager.load(&source_id)?.content == cached.scanned.semantics.source.content {
+                    cached.modified = *modified;
+                    cached.size = *size;
+
+                    continue;
+                }
+            }
+
+            changed.push(source_id);
+        }
+
+        if changed.is_empty() && !removed && self.reported {
+            return Ok(false);
+        }
+
+        let scan_started_at = Instant::now();
+        let php_version = self.configuration.php_version;
+        let parser_settings = self.configuration.source.parser_settings();
+        let scanned =
+            scan_sources(&self.interner, &manager, &self.cache, php_version, parser_settings, changed, self.color)
+                .await?;
+        let scan_duration = scan_started_at.elapsed();
+
+        // The issues of a source may depend on the other sources, e.g. the inherited members, or the references to
+        // its symbols, so all the sources are linted again whenever the codebase, or its references, change.
+        let mut codebase_changed = removed;
+        let mut semantics = Vec::with_capacity(scanned.len());
+        for scanned in scanned {
+            let path = scanned.semantics.source.path.clone().expect("user-defined sources are loaded from a path");
+            let (_, modified, size) = snapshot[&path];
+
+            codebase_changed |=
+                self.sources.get(&path).is_none_or(|cached| cached.scanned.reflections != scanned.reflections);
+
+            semantics.push(scanned.semantics.clone());
+            self.sources.insert(path, CachedSource { modified, size, scanned, issues: IssueCollection::new() });
+        }
+
+        let changed_files = semantics.len();
+
+        let reflect_started_at = Instant::now();
+        let mut codebase = build_codebase(
+            &self.interner,
+            self.external.clone(),
+            self.sources.values().map(|source| source.scanned.reflections.clone()),
+        );
+        let reflect_duration = reflect_started_at.elapsed();
+
+        let check_started_at = Instant::now();
+        let mut reflection_issues = std::mem::take(&mut codebase.issues);
+        reflection_issues.extend(check_inheritance(&self.interner, &codebase, self.color));
+        let check_duration = check_started_at.elapsed();
+
+        let lint_started_at = Instant::now();
+        let mut linters = Linters::new(
+            &self.interner,
+            self.configuration.php_version,
+            &self.configuration.linter,
+            &self.selection,
+            codebase,
+        );
+
+        // The references are indexed from all the sources, as the changed ones may reference the others.
+        if linters.requires_references() {
+            let semantics = self.sources.values().map(|source| source.scanned.semantics.clone()).collect::<Vec<_>>();
+            let references = index_references(&self.interner, &semantics, self.color).await?;
+
+            codebase_changed |= self.references.as_ref() != Some(&references);
+            self.references = Some(references.clone());
+            linters = linters.with_references(references);
+        }
+
+        if codebase_changed {
+            semantics = self.sources.values().map(|source| source.scanned.semantics.clone()).collect();
+        }
+
+        let linters = Arc::new(linters);
+        let results = lint_semantics(&self.interner, &linters, semantics.clone(), self.color).await?;
+        for (semantic, issues) in semantics.iter().zip(results) {
+            if let Some(source) = semantic.source.path.as_ref().and_then(|path| self.sources.get_mut(path)) {
+                source.issues = issues;
+            }
+        }
+
+        let lint_duration = lint_started_at.elapsed();
+
+        let mut issues = IssueCollection::from(self.sources.values().flat_map(|source| source.issues.iter().cloned()));
+        issues.extend(reflection_issues);
+
+        let all_semantics = self.sources.values().map(|source| source.scanned.semantics.clone()).collect::<Vec<_>>();
+        let mut issues = apply_suppressions(&self.interner, &linters, &all_semantics, issues);
+        if !self.command.allow_duplicates {
+            issues.deduplicate();
+        }
+
+        let mut statistics = LintStatistics {
+            files: self.sources.len(),
+            unparsable_files: self.sources.values().filter(|source| source.scanned.semantics.has_parse_error()).count(),
+            scan_duration,
+            reflect_duration: Some(reflect_duration),
+            check_duration: Some(check_duration),
+            lint_duration: Some(lint_duration),
+            baselined: 0,
+        };
+
+        if let Some(baseline) = &self.baseline {
+            let (remaining, matches) = baseline.filter(&self.interner, &manager, issues)?;
+
+            issues = remaining;
+            statistics.baselined = matches.baselined;
+        }
+
+        let issues = if self.command.fixable_only { IssueCollection::from(issues.only_fixable()) } else { issues };
+        let summary = summarize(&issues, &statistics, started_at.elapsed());
+
+        let mut stderr = std::io::stderr();
+        if stderr.is_terminal() {
+            // Clear the screen, and move the cursor to the top left corner.
+            eprint!("\x1B[2J\x1B[1;1H");
+        }
+
+        eprintln!("[{}] Linted {} changed file(s).", timestamp(), changed_files);
+
+        let reporter = create_reporter(&self.command, &self.configuration, self.interner.clone(), manager, self.color);
+        match &self.command.reporting_output {
+            Some(path) => reporter.report_to_file(issues, self.command.reporting_format, path)?,
+            None => reporter.report(issues, self.command.reporting_format)?,
+        };
+
+        if !self.command.no_summary {
+            eprintln!("{}", summary);
+        }
+
+        std::io::Write::flush(&mut stderr).ok();
+        self.reported = true;
+
+        Ok(true)
+    }
+}
+
+/// Returns the name, modification time, and size of every user-defined source file.
+async fn snapshot(configuration: &Configuration, selection_paths: &[PathBuf]) -> Result<Snapshot, Error> {
+    let files = source::user_defined_files(&configuration.source, selection_paths).await?;
+
+    Ok(files
+        .into_iter()
+        .filter_map(|(name, path)| {
+            // Files may be removed while scanning, e.g. temporary files written by editors.
+            let metadata = std::fs::metadata(&path).ok()?;
+
+            Some((path, (name, metadata.modified().ok(), metadata.len())))
+        })
+        .collect())
+}
+
+/// Returns the current time of day, in UTC, formatted as `HH:MM:SS`.
+fn timestamp() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0);
+
+    format!("{:02}:{:02}:{:02} UTC", seconds / 3600 % 24, seconds / 60 % 60, seconds % 60)
+}
+
+/// Returns the existing paths under which the user-defined source files are found, none being under another.
+fn watched_paths(configuration: &Configuration, selection_paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut paths = configuration
+        .source
+        .source_paths()
+        .iter()
+        .map(|path| path.base().to_path_buf())
+        .chain(selection_paths.iter().cloned())
+        .filter(|path| path.exists())
+        .collect::<Vec<_>>();
+
+    // Sorting the paths puts every path right after the ones it is under.
+    paths.sort();
+
+    let mut watched: Vec<PathBuf> = vec![];
+    for path in paths {
+        if !watched.iter().any(|parent| path.starts_with(parent)) {
+            watched.push(path);
+        }
+    }
+
+    watched
+}
+
+/// Listens for Ctrl-C, so that the watch mode stops cleanly instead of being killed.
+fn listen_for_interruption(sender: UnboundedSender<Event>) {
+    tokio::spawn(async move {
+        if let Err(error) = tokio::signal::ctrl_c().await {
+            mago_feedback::warn!("Unable to listen for Ctrl-C: {}", error);
+
+            return;
+        }
+
+        sender.send(Event::Interrupted).ok();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watched_paths_are_not_nested() {
+        let root = std::env::temp_dir().join(format!("mago-watch-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src/Entity")).unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+
+        let mut configuration = Configuration::from_root(root.clone());
+        configuration.source.paths =
+            vec![PathBuf::from("src/Entity"), PathBuf::from("src"), PathBuf::from("tests"), PathBuf::from("missing")];
+
+        let watched = watched_paths(&configuration, &[root.join("src/Entity/User.php")]);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(watched, vec![root.join("src"), root.join("tests")]);
+    }
+}
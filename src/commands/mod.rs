@@ -2,18 +2,34 @@ use clap::builder::styling::AnsiColor;
 use clap::builder::styling::Effects;
 use clap::builder::Styles;
 use clap::Parser;
+use clap::Subcommand;
+
+use mago_php_version::PHPVersion;
+
+use crate::color::ColorChoice;
 
 use crate::commands::ast::AstCommand;
+use crate::commands::cache::CacheCommand;
+use crate::commands::find::FindCommand;
 use crate::commands::fix::FixCommand;
 use crate::commands::format::FormatCommand;
 use crate::commands::lint::LintCommand;
+use crate::commands::references::ReferencesCommand;
+use crate::commands::reflect::ReflectCommand;
 use crate::commands::self_update::SelfUpdateCommand;
+use crate::commands::tokens::TokensCommand;
+use crate::enum_variants;
 
 pub mod ast;
+pub mod cache;
+pub mod find;
 pub mod fix;
 pub mod format;
 pub mod lint;
+pub mod references;
+pub mod reflect;
 pub mod self_update;
+pub mod tokens;
 
 pub const CLAP_STYLING: Styles = Styles::styled()
     .header(AnsiColor::Green.on_default().effects(Effects::BOLD))
@@ -49,9 +65,46 @@ pub const CLAP_STYLING: Styles = Styles::styled()
   Start transforming your workflow today. Learn more at: https://carthage.software/mago
   ----------------------------------------------------------------------------------------------
 "#)]
+pub struct MagoArguments {
+    /// Control when to use colors in the output.
+    #[arg(
+        long,
+        global = true,
+        default_value_t,
+        help = "control when to use colors in the output, honoring `NO_COLOR` and `CLICOLOR_FORCE` in auto mode",
+        ignore_case = true,
+        value_parser = enum_variants!(ColorChoice)
+    )]
+    pub color: ColorChoice,
+
+    /// The targeted version of PHP, overriding the `php_version` option of the configuration.
+    #[arg(
+        long,
+        global = true,
+        value_name = "VERSION",
+        help = "the targeted version of PHP, such as `8.1`, overriding the configuration"
+    )]
+    pub php_version: Option<PHPVersion>,
+
+    /// Do not read, nor write the cache of the reflections, overriding the configuration.
+    #[arg(
+        long,
+        global = true,
+        help = "do not use the cache of the reflections of the sources",
+        default_value_t = false
+    )]
+    pub no_cache: bool,
+
+    #[command(subcommand)]
+    pub command: MagoCommand,
+}
+
+#[derive(Subcommand, Debug)]
 pub enum MagoCommand {
     #[command(name = "ast")]
     Ast(AstCommand),
+    #[command(name = "tokens")]
+    Tokens(TokensCommand),
     #[command(name = "lint")]
     Lint(LintCommand),
     #[command(name = "fix")]
@@ -60,4 +113,12 @@ pub enum MagoCommand {
     Format(FormatCommand),
     #[command(name = "self-update")]
     SelfUpdate(SelfUpdateCommand),
+    #[command(name = "cache")]
+    Cache(CacheCommand),
+    #[command(name = "find")]
+    Find(FindCommand),
+    #[command(name = "reflect")]
+    Reflect(ReflectCommand),
+    #[command(name = "references")]
+    References(ReferencesCommand),
 }
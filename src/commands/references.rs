@@ -0,0 +1,400 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use mago_interner::ThreadedInterner;
+use mago_reflection::query::Symbol;
+use mago_reflection::query::SymbolKind;
+use mago_reflection::query::SymbolQuery;
+use mago_reflection::reference::DynamicUsage;
+use mago_reflection::reference::Reference;
+use mago_reflection::reference::ReferenceIndex;
+use mago_reflection::reference::ReferencedSymbol;
+use mago_reporting::reporter::Reporter;
+use mago_reporting::reporter::ReportingFormat;
+use mago_reporting::reporter::ReportingTarget;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+
+use crate::cache::ReflectionCache;
+use crate::color::ColorChoice;
+use crate::commands::lint::build_codebase;
+use crate::commands::lint::index_references;
+use crate::commands::lint::scan_sources;
+use crate::config::Configuration;
+use crate::enum_variants;
+use crate::error::Error;
+use crate::reflection::ExternalReflection;
+use crate::source;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "references",
+    about = "list the references to the class-likes, functions, constants, and members of the codebase",
+    long_about = r#"
+The `references` command indexes the references made by the project sources, and lists those to the symbols
+whose names match the given query, such as `App\Service\UserRepository`, or `App\Service\UserRepository::find`.
+The query is written as for the `find` command, and may contain the same `*` and `?` wildcards.
+
+Classes are referenced by their instantiations, static calls, type hints, `instanceof`, `::class`, `catch`
+clauses, and attributes, and by the class-likes extending, implementing, or using them. Functions and methods
+are referenced by their calls, and by the closures created from them, and constants by their accesses.
+
+Methods called on objects whose class is not known, such as `$object->find()`, are only listed with
+`--include-unresolved`, and the dynamic usages, such as `new $class()`, which may reference any symbol, are only
+listed with `--include-dynamic`. Each reference is reported as a note, so that the results can be written in any
+of the reporting formats, such as `json`.
+"#
+)]
+pub struct ReferencesCommand {
+    /// The name, or pattern, of the symbols whose references to list.
+    #[arg(help = "the name, or pattern, of the symbols whose references to list, such as `App\\Foo::bar`")]
+    pub query: String,
+
+    /// Compare names regardless of the case of their letters.
+    #[arg(long, short = 'i', help = "compare names regardless of the case of their letters")]
+    pub ignore_case: bool,
+
+    /// Also list the calls of methods with the same name, on objects whose class is not known.
+    #[arg(long, help = "also list the calls of methods with the same name, on objects whose class is not known")]
+    pub include_unresolved: bool,
+
+    /// Also list the dynamic usages, such as `new $class()`, which may reference any symbol.
+    #[arg(long, help = "also list the dynamic usages, such as `new $class()`, which may reference any symbol")]
+    pub include_dynamic: bool,
+
+    /// Import the reflection written by `mago reflect`, instead of reflecting the external sources.
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "import the reflection written by `mago reflect --output`, instead of reflecting the dependencies and stubs"
+    )]
+    pub import_reflection: Option<PathBuf>,
+
+    /// Specify where the results should be reported.
+    #[arg(
+        long,
+        default_value_t,
+        help = "specify where the results should be reported",
+        ignore_case = true,
+        value_parser = enum_variants!(ReportingTarget)
+    )]
+    pub reporting_target: ReportingTarget,
+
+    /// Choose the format for reporting the references.
+    #[arg(
+        long,
+        default_value_t = ReportingFormat::Short,
+        help = "choose the format for reporting the references",
+        ignore_case = true,
+        value_parser = enum_variants!(ReportingFormat)
+    )]
+    pub reporting_format: ReportingFormat,
+}
+
+impl ReferencesCommand {
+    /// Returns the query of the symbols selected by the command, whose references are indexed.
+    fn query(&self) -> SymbolQuery {
+        let kinds = SymbolKind::class_likes()
+            .into_iter()
+            .chain(SymbolKind::members().into_iter().filter(|kind| *kind != SymbolKind::Property))
+            .chain([SymbolKind::Function, SymbolKind::Constant]);
+
+        SymbolQuery::new(&self.query).with_kinds(kinds).with_case_insensitivity(self.ignore_case)
+    }
+}
+
+pub async fn execute(
+    command: ReferencesCommand,
+    configuration: Configuration,
+    color: ColorChoice,
+) -> Result<ExitCode, Error> {
+    let external = ExternalReflection::new(true, command.import_reflection.as_deref());
+    let interner = ThreadedInterner::new();
+    let manager = source::load(&interner, &configuration.source, true, &[]).await?;
+
+    let cache = ReflectionCache::new(&configuration.cache);
+    let external = external.load(&interner, &manager, &configuration.source, configuration.php_version, &cache).await?;
+
+    let sources = manager.user_defined_source_ids().collect();
+    let parser_settings = configuration.source.parser_settings();
+    let scanned =
+        scan_sources(&interner, &manager, &cache, configuration.php_version, parser_settings, sources, color).await?;
+
+    let mut semantics = Vec::with_capacity(scanned.len());
+    let codebase = build_codebase(
+        &interner,
+        external,
+        scanned.into_iter().map(|source| {
+            semantics.push(source.semantics);
+
+            source.reflections
+        }),
+    );
+
+    let symbols = command.query().find(&interner, &codebase);
+    if symbols.is_empty() {
+        mago_feedback::info!("No symbols match `{}`.", command.query);
+
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let index = index_references(&interner, &semantics, color).await?;
+
+    let mut issues = vec![];
+    let mut unresolved = 0;
+    for symbol in &symbols {
+        let (resolved, others) = references(&interner, &index, symbol);
+
+        issues.extend(resolved.iter().map(|reference| reference_issue(&interner, symbol, reference, true)));
+        if command.include_unresolved {
+            issues.extend(others.iter().map(|reference| reference_issue(&interner, symbol, reference, false)));
+        } else {
+            unresolved += others.len();
+        }
+    }
+
+    let dynamic_sources = index.get_sources_with_dynamic_usages();
+    if command.include_dynamic {
+        for source in &dynamic_sources {
+            issues.extend(index.get_dynamic_usages(source).iter().map(dynamic_usage_issue));
+        }
+    }
+
+    if issues.is_empty() {
+        mago_feedback::info!("No references to the symbols matching `{}` were found.", command.query);
+    }
+
+    if unresolved > 0 {
+        mago_feedback::info!(
+            "{} call(s) of methods with the same name, on objects whose class is not known, may also reference the symbols; list them with `--include-unresolved`.",
+            unresolved
+        );
+    }
+
+    if !command.include_dynamic && !dynamic_sources.is_empty() {
+        mago_feedback::info!(
+            "{} source(s) contain dynamic usages, which may also reference the symbols; list them with `--include-dynamic`.",
+            dynamic_sources.len()
+        );
+    }
+
+    Reporter::new(interner, manager, command.reporting_target, color.for_reporter(command.reporting_target))
+        .report(issues, command.reporting_format)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Returns the references to the given symbol, and those which may reference it, such as the calls of methods
+/// with the same name on objects whose class is not known.
+fn references(
+    interner: &ThreadedInterner,
+    index: &ReferenceIndex,
+    symbol: &Symbol<'_>,
+) -> (Vec<Reference>, Vec<Reference>) {
+    let mut referenced = ReferencedSymbol::of(interner, symbol).into_iter();
+    let resolved = referenced.next().map(|symbol| index.get_references(&symbol)).unwrap_or_default();
+    let others = referenced.flat_map(|symbol| index.get_references(&symbol)).collect();
+
+    (resolved, others)
+}
+
+/// Creates the note reporting a reference to the given symbol, annotating the referencing name.
+fn reference_issue(interner: &ThreadedInterner, symbol: &Symbol<'_>, reference: &Reference, resolved: bool) -> Issue {
+    let kind = symbol.kind();
+    let message = if resolved {
+        format!("The {} is referenced here, by a {}.", kind, reference.kind.as_str())
+    } else {
+        format!(
+            "The {} may be referenced here, by a {} on an object whose class is not known.",
+            kind,
+            reference.kind.as_str()
+        )
+    };
+
+    Issue::note(format!("Reference to {} `{}`", kind, symbol.name(interner)))
+        .with_code(format!("reference/{}", reference.kind.as_str().replace(' ', "-")))
+        .with_annotation(Annotation::primary(reference.span).with_message(message))
+}
+
+/// Creates the note reporting a dynamic usage, which may reference any symbol.
+fn dynamic_usage_issue(usage: &DynamicUsage) -> Issue {
+    Issue::note(format!("Possible reference by a {}", usage.kind.as_str()))
+        .with_code(format!("reference/{}", usage.kind.as_str().replace(' ', "-")))
+        .with_annotation(Annotation::primary(usage.span).with_message("The symbol used here is only known at runtime."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mago_names::Names;
+    use mago_parser::parse_source;
+    use mago_reflection::reference::DynamicUsageKind;
+    use mago_reflection::reference::ReferenceKind;
+    use mago_source::SourceManager;
+    use mago_span::Position;
+    use mago_span::Span;
+
+    fn index(interner: &ThreadedInterner, content: &str) -> ReferenceIndex {
+        let manager = SourceManager::new(interner.clone());
+        let source_id = manager.insert_content("src/example.php".to_string(), content.to_string(), true);
+        let source = manager.load(&source_id).unwrap();
+        let (program, _) = parse_source(interner, &source);
+        let names = Names::resolve(interner, &program);
+
+        mago_reflector::index_references(interner, &program, &names)
+    }
+
+    fn kinds(index: &ReferenceIndex, symbol: ReferencedSymbol) -> Vec<ReferenceKind> {
+        index.get_references(&symbol).into_iter().map(|reference| reference.kind).collect()
+    }
+
+    #[test]
+    fn test_class_likes_are_referenced_through_resolved_names() {
+        let interner = ThreadedInterner::new();
+        let index = index(
+            &interner,
+            r#"<?php
+                namespace App;
+
+                use Vendor\Lib\Service;
+
+                #[Service]
+                final class Foo extends Service implements \Countable {
+                    public function count(): int { return 0; }
+
+                    public function run(Service $service): Service {
+                        try {
+                            return new Service();
+                        } catch (Service|\RuntimeException $e) {
+                            return $service instanceof Service ? Service::create() : Service::class;
+                        }
+                    }
+                }
+            "#,
+        );
+
+        let service = ReferencedSymbol::class_like(&interner, &interner.intern("Vendor\\Lib\\Service"));
+        assert_eq!(
+            kinds(&index, service),
+            [
+                ReferenceKind::Attribute,
+                ReferenceKind::Extension,
+                ReferenceKind::TypeHint,
+                ReferenceKind::TypeHint,
+                ReferenceKind::Instantiation,
+                ReferenceKind::Catch,
+                ReferenceKind::Instanceof,
+                ReferenceKind::StaticAccess,
+                ReferenceKind::ClassName,
+            ]
+        );
+
+        let countable = ReferencedSymbol::class_like(&interner, &interner.intern("countable"));
+        assert_eq!(kinds(&index, countable), [ReferenceKind::Implementation]);
+        let create =
+            ReferencedSymbol::method(&interner, &interner.intern("Vendor\\Lib\\Service"), &interner.intern("CREATE"));
+        assert_eq!(kinds(&index, create), [ReferenceKind::Call]);
+        assert!(index.get_sources_with_dynamic_usages().is_empty());
+    }
+
+    #[test]
+    fn test_functions_methods_and_constants_are_referenced() {
+        let interner = ThreadedInterner::new();
+        let index = index(
+            &interner,
+            r#"<?php
+                namespace App;
+
+                class Foo {
+                    const BAR = 1;
+
+                    public function run(object $object): void {
+                        $this->helper();
+                        $object->helper();
+                        self::BAR;
+                        static::build(...);
+                        strlen(PHP_EOL);
+                        \App\format(namespace\LIMIT);
+                    }
+                }
+            "#,
+        );
+
+        let foo = interner.intern("App\\Foo");
+        assert_eq!(
+            kinds(&index, ReferencedSymbol::method(&interner, &foo, &interner.intern("helper"))),
+            [ReferenceKind::Call]
+        );
+        assert_eq!(
+            kinds(&index, ReferencedSymbol::any_method(&interner, &interner.intern("helper"))),
+            [ReferenceKind::Call]
+        );
+        assert_eq!(
+            kinds(&index, ReferencedSymbol::method(&interner, &foo, &interner.intern("build"))),
+            [ReferenceKind::ClosureCreation]
+        );
+        assert_eq!(
+            kinds(&index, ReferencedSymbol::class_like_constant(&interner, &foo, &interner.intern("BAR"))),
+            [ReferenceKind::ConstantAccess]
+        );
+
+        // Unqualified functions, and constants, may fall back to the global ones.
+        assert!(index.is_referenced(&ReferencedSymbol::function(&interner, &interner.intern("App\\strlen"))));
+        assert!(index.is_referenced(&ReferencedSymbol::function(&interner, &interner.intern("strlen"))));
+        assert!(index.is_referenced(&ReferencedSymbol::constant(&interner, &interner.intern("PHP_EOL"))));
+        assert!(index.is_referenced(&ReferencedSymbol::function(&interner, &interner.intern("App\\format"))));
+        assert!(!index.is_referenced(&ReferencedSymbol::function(&interner, &interner.intern("format"))));
+        assert!(index.is_referenced(&ReferencedSymbol::constant(&interner, &interner.intern("app\\LIMIT"))));
+    }
+
+    #[test]
+    fn test_dynamic_usages_are_recorded_for_their_source() {
+        let interner = ThreadedInterner::new();
+        let index = index(
+            &interner,
+            r#"<?php
+                $object = new $class();
+                $function();
+                $object->$method();
+                $class::create();
+                call_user_func([$object, 'run']);
+                $closure = (fn() => 1)();
+                $anonymous = new class {};
+            "#,
+        );
+
+        let sources = index.get_sources_with_dynamic_usages();
+        assert_eq!(sources.len(), 1);
+        assert_eq!(
+            index.get_dynamic_usages(&sources[0]).iter().map(|usage| usage.kind).collect::<Vec<_>>(),
+            [
+                DynamicUsageKind::Instantiation,
+                DynamicUsageKind::FunctionCall,
+                DynamicUsageKind::MethodCall,
+                DynamicUsageKind::StaticAccess,
+                DynamicUsageKind::FunctionCall,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive_references_are_not_references_from_outside() {
+        let interner = ThreadedInterner::new();
+        let content = "<?php function walk() { walk(); } function main() { walk(); }";
+        let index = index(&interner, content);
+
+        let walk = ReferencedSymbol::function(&interner, &interner.intern("walk"));
+        let references = index.get_references(&walk);
+        assert_eq!(references.len(), 2);
+
+        // The first function calls itself, and is called by the second one.
+        let source = references[0].span.start.source;
+        let first = Span::new(Position::new(source, 6), Position::new(source, content.find(" function main").unwrap()));
+        let both = Span::new(Position::new(source, 6), Position::new(source, content.len()));
+        assert!(index.is_referenced_outside(&walk, &first));
+        assert!(!index.is_referenced_outside(&walk, &both));
+    }
+}
@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use mago_interner::ThreadedInterner;
+
+use crate::cache::ReflectionCache;
+use crate::config::Configuration;
+use crate::error::Error;
+use crate::export;
+use crate::reflection::reflect_all_user_defined_sources;
+use crate::reflection::ExternalReflection;
+use crate::source;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "reflect",
+    about = "write the reflection of the codebase to a JSON file",
+    long_about = r#"
+The `reflect` command reflects the codebase, including the dependencies and the stubs, and writes the
+reflection to a JSON file: the class-likes along with their members, signatures, types, and inheritance,
+the functions, and the constants, with the locations of their declarations.
+
+The format of the file is versioned, and the same codebase is always written the same way, so that the
+file can be read by other tools, and compared across runs.
+
+The file can be imported by a later run of the same version of mago, with `--import-reflection`, instead of
+reflecting the dependencies and the stubs again, e.g. `mago lint --import-reflection codebase.json`.
+"#
+)]
+pub struct ReflectCommand {
+    /// The file to write the reflection to.
+    #[arg(
+        long,
+        short = 'o',
+        value_name = "PATH",
+        help = "the file to write the reflection to, such as `codebase.json`"
+    )]
+    pub output: PathBuf,
+
+    /// Import the reflection written by a previous run, instead of reflecting the external sources.
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "import the reflection written by a previous run, instead of reflecting the dependencies and stubs"
+    )]
+    pub import_reflection: Option<PathBuf>,
+}
+
+pub async fn execute(command: ReflectCommand, configuration: Configuration) -> Result<ExitCode, Error> {
+    let interner = ThreadedInterner::new();
+    let manager = source::load(&interner, &configuration.source, true, &[]).await?;
+
+    let cache = ReflectionCache::new(&configuration.cache);
+    let external = ExternalReflection::new(true, command.import_reflection.as_deref());
+    let codebase = external.load(&interner, &manager, &configuration.source, configuration.php_version, &cache).await?;
+    let user_defined =
+        reflect_all_user_defined_sources(&interner, &manager, &configuration.source, configuration.php_version, &cache)
+            .await?;
+
+    let mut codebase = mago_reflector::merge(&interner, codebase, user_defined);
+    mago_reflector::populate(&interner, &mut codebase);
+
+    export::export(&interner, &codebase, configuration.php_version, &command.output)?;
+
+    mago_feedback::info!(
+        "Reflection of {} class-like(s), {} function(s), and {} constant(s) written to `{}`.",
+        codebase.class_like_reflections.len(),
+        codebase.function_like_reflections.len(),
+        codebase.constant_reflections.len(),
+        command.output.display()
+    );
+
+    Ok(ExitCode::SUCCESS)
+}
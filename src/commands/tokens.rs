@@ -0,0 +1,173 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use serde_json::json;
+
+use mago_interner::ThreadedInterner;
+use mago_lexer::lex_lossless;
+use mago_reporting::reporter::Reporter;
+use mago_reporting::reporter::ReportingFormat;
+use mago_reporting::reporter::ReportingTarget;
+use mago_reporting::Issue;
+use mago_source::SourceManager;
+
+use crate::color::ColorChoice;
+use crate::enum_variants;
+use crate::error::Error;
+
+/// Represents the `tokens` command, which tokenizes a PHP file and prints its tokens.
+#[derive(Parser, Debug)]
+#[command(
+    name = "tokens",
+    about = "tokenize a PHP file and print its tokens, including whitespace and comments",
+    long_about = r#"
+The `tokens` command tokenizes a PHP file and outputs every token, one per line, with its kind, span, and text.
+
+Whitespace, comments, and inline text are included, so the text of the tokens reproduces the file byte for byte.
+"#
+)]
+pub struct TokensCommand {
+    /// Path to the PHP file to be tokenized.
+    #[arg(
+        help = "specify the PHP file to tokenize",
+        required_unless_present = "file_option",
+        conflicts_with = "file_option"
+    )]
+    pub file: Option<String>,
+
+    /// Path to the PHP file to be tokenized, as given with the `--file` option of the previous versions.
+    #[arg(id = "file_option", long = "file", short = 'f', value_name = "FILE", hide = true)]
+    pub file_option: Option<String>,
+
+    /// Treat `<?` as an opening tag, as with the `short_open_tag` ini setting of PHP.
+    #[arg(long, help = "treat `<?` as an opening tag, instead of inline text")]
+    pub short_open_tags: bool,
+
+    /// Output the tokens in JSON format for integration with other tools.
+    #[arg(long, help = "output the tokens in JSON format")]
+    pub json: bool,
+
+    /// Specify where the results should be reported.
+    #[arg(
+        long,
+        default_value_t,
+        help = "specify where the results should be reported",
+        ignore_case = true,
+        value_parser = enum_variants!(ReportingTarget)
+    )]
+    pub reporting_target: ReportingTarget,
+
+    /// Choose the format for reporting issues.
+    #[arg(
+        long,
+        default_value_t,
+        help = "choose the format for reporting issues",
+        ignore_case = true,
+        value_parser = enum_variants!(ReportingFormat)
+    )]
+    pub reporting_format: ReportingFormat,
+}
+
+impl TokensCommand {
+    /// Returns the path of the file to tokenize, given either as an argument or with `--file`.
+    fn file(&self) -> &str {
+        self.file.as_deref().or(self.file_option.as_deref()).unwrap_or_default()
+    }
+}
+
+/// Executes the tokens command with the provided options.
+///
+/// # Arguments
+///
+/// * `command` - The `TokensCommand` structure containing user-specified options.
+///
+/// # Returns
+///
+/// An `ExitCode` indicating the success or failure of the command.
+///
+/// # Errors
+///
+/// An error is returned if the file does not exist or is not readable.
+pub async fn execute(command: TokensCommand, color: ColorChoice) -> Result<ExitCode, Error> {
+    let file = command.file();
+    let file_path = std::path::Path::new(file).to_path_buf();
+
+    // Verify if the file exists and is readable.
+    if !file_path.exists() {
+        mago_feedback::error!("File '{}' does not exist.", file);
+
+        return Ok(ExitCode::FAILURE);
+    }
+
+    if !file_path.is_file() {
+        mago_feedback::error!("The path '{}' is not a file.", file);
+
+        return Ok(ExitCode::FAILURE);
+    }
+
+    // Initialize interner and source manager.
+    let interner = ThreadedInterner::new();
+    let source_manager = SourceManager::new(interner.clone());
+
+    // Load the source file.
+    let source_id = source_manager.insert_path(file.to_string(), file_path, true);
+    let source = source_manager.load(&source_id)?;
+
+    let (tokens, errors) = lex_lossless(&interner, &source, command.short_open_tags);
+
+    if command.json {
+        let result = json!({
+            "tokens": tokens.iter().map(|token| json!({
+                "kind": token.kind,
+                "start": token.span.start.offset,
+                "end": token.span.end.offset,
+                "text": interner.lookup(&token.value),
+            })).collect::<Vec<_>>(),
+            "errors": errors.iter().map(|error| Issue::from(*error)).collect::<Vec<_>>(),
+        });
+
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        for token in tokens.iter() {
+            println!(
+                "{:<28} {:>6}..{:<6} {:?}",
+                format!("{:?}", token.kind),
+                token.span.start.offset,
+                token.span.end.offset,
+                interner.lookup(&token.value)
+            );
+        }
+
+        // Report the errors, if any.
+        if !errors.is_empty() {
+            Reporter::new(
+                interner.clone(),
+                source_manager,
+                command.reporting_target,
+                color.for_reporter(command.reporting_target),
+            )
+            .report(errors.iter().map(|error| Issue::from(*error)), command.reporting_format)?;
+        }
+    }
+
+    Ok(if !errors.is_empty() { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(arguments: &[&str]) -> Result<TokensCommand, clap::Error> {
+        TokensCommand::try_parse_from(std::iter::once("tokens").chain(arguments.iter().copied()))
+    }
+
+    #[test]
+    fn test_file_is_given_as_an_argument_or_with_an_option() {
+        assert_eq!(command(&["foo.php"]).unwrap().file(), "foo.php");
+        assert_eq!(command(&["--file", "foo.php"]).unwrap().file(), "foo.php");
+        assert_eq!(command(&["-f", "foo.php", "--json"]).unwrap().file(), "foo.php");
+
+        assert!(command(&[]).is_err());
+        assert!(command(&["foo.php", "--file", "bar.php"]).is_err());
+    }
+}
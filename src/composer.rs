@@ -0,0 +1,172 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use mago_composer::AutoloadPsr0value;
+use mago_composer::AutoloadPsr4value;
+use mago_composer::ComposerPackage;
+use mago_composer::InstalledPackages;
+
+/// The directory the packages are installed in, when it is not configured in `composer.json`.
+const DEFAULT_VENDOR_DIR: &str = "vendor";
+
+/// Discovers the paths autoloaded by the packages installed by composer for the project at the given root,
+/// as listed in their `psr-4`, `psr-0`, `classmap`, and `files` autoload sections.
+///
+/// Nothing is discovered when the root does not contain a `composer.json` file, or when the packages are
+/// not installed.
+///
+/// # Arguments
+///
+/// * `root` - The root directory of the project.
+/// * `include_dev_dependencies` - Whether to include the packages which are only development dependencies.
+///
+/// # Returns
+///
+/// The canonical paths of the autoloaded files and directories which exist, sorted and deduplicated.
+pub fn discover_autoload_paths(root: &Path, include_dev_dependencies: bool) -> Vec<PathBuf> {
+    let Ok(manifest) = std::fs::read_to_string(root.join("composer.json")) else {
+        return vec![];
+    };
+
+    let vendor_dir = match ComposerPackage::from_str(&manifest) {
+        Ok(package) => package.config.and_then(|config| config.vendor_dir),
+        Err(error) => {
+            tracing::warn!("failed to parse `composer.json`, assuming the default vendor directory: {}", error);
+
+            None
+        }
+    };
+
+    let vendor_dir = root.join(vendor_dir.as_deref().unwrap_or(DEFAULT_VENDOR_DIR));
+    let installed_path = vendor_dir.join("composer").join("installed.json");
+    let Ok(installed) = std::fs::read_to_string(&installed_path) else {
+        tracing::debug!("`{}` does not exist, the composer packages are not installed", installed_path.display());
+
+        return vec![];
+    };
+
+    let installed = match InstalledPackages::from_str(&installed) {
+        Ok(installed) => installed,
+        Err(error) => {
+            tracing::warn!("failed to parse `{}`: {}", installed_path.display(), error);
+
+            return vec![];
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = autoload_paths(&vendor_dir, &installed, include_dev_dependencies)
+        .into_iter()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect();
+
+    paths.sort();
+    paths.dedup();
+
+    paths
+}
+
+/// Returns the paths autoloaded by the given installed packages, which may not exist.
+fn autoload_paths(vendor_dir: &Path, installed: &InstalledPackages, include_dev_dependencies: bool) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    for package in installed.packages.iter() {
+        if !include_dev_dependencies && installed.is_dev_package(package) {
+            continue;
+        }
+
+        let Some(autoload) = &package.autoload else {
+            continue;
+        };
+
+        let directory = match &package.install_path {
+            Some(install_path) => vendor_dir.join("composer").join(install_path),
+            None => vendor_dir.join(&package.name),
+        };
+
+        let psr_4 = autoload.psr_4.values().flat_map(|value| match value {
+            AutoloadPsr4value::Array(paths) => paths.as_slice(),
+            AutoloadPsr4value::String(path) => std::slice::from_ref(path),
+        });
+
+        let psr_0 = autoload.psr_0.values().flat_map(|value| match value {
+            AutoloadPsr0value::Array(paths) => paths.as_slice(),
+            AutoloadPsr0value::String(path) => std::slice::from_ref(path),
+        });
+
+        for path in psr_4.chain(psr_0).chain(autoload.classmap.iter()).chain(autoload.files.iter()) {
+            paths.push(directory.join(path));
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSTALLED: &str = r#"{
+        "packages": [
+            {
+                "name": "acme/library",
+                "install-path": "../acme/library",
+                "autoload": {
+                    "psr-4": { "Acme\\Library\\": "src/", "Acme\\Library\\Extra\\": ["extra/", "more/"] },
+                    "files": ["functions.php"]
+                }
+            },
+            {
+                "name": "acme/legacy",
+                "install-path": "../acme/legacy",
+                "autoload": { "psr-0": { "Acme_": "lib/" }, "classmap": ["classes/"] }
+            },
+            {
+                "name": "acme/testing",
+                "install-path": "../acme/testing",
+                "autoload": { "psr-4": { "Acme\\Testing\\": "" } }
+            },
+            {
+                "name": "acme/metapackage",
+                "install-path": null
+            }
+        ],
+        "dev": true,
+        "dev-package-names": ["acme/testing"]
+    }"#;
+
+    fn paths(installed: &str, include_dev_dependencies: bool) -> Vec<PathBuf> {
+        let installed = InstalledPackages::from_str(installed).unwrap();
+        let mut paths = autoload_paths(Path::new("/project/vendor"), &installed, include_dev_dependencies);
+        paths.sort();
+
+        paths
+    }
+
+    #[test]
+    fn test_autoload_paths_exclude_dev_dependencies() {
+        assert_eq!(
+            paths(INSTALLED, false),
+            [
+                "/project/vendor/composer/../acme/legacy/classes/",
+                "/project/vendor/composer/../acme/legacy/lib/",
+                "/project/vendor/composer/../acme/library/extra/",
+                "/project/vendor/composer/../acme/library/functions.php",
+                "/project/vendor/composer/../acme/library/more/",
+                "/project/vendor/composer/../acme/library/src/",
+            ]
+            .map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn test_autoload_paths_include_dev_dependencies() {
+        assert!(paths(INSTALLED, true).contains(&PathBuf::from("/project/vendor/composer/../acme/testing/")));
+    }
+
+    #[test]
+    fn test_autoload_paths_of_composer_1() {
+        let installed = r#"[{ "name": "acme/library", "autoload": { "psr-4": { "Acme\\": "src" } } }]"#;
+
+        assert_eq!(paths(installed, false), [PathBuf::from("/project/vendor/acme/library/src")]);
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use config::builder::BuilderState;
+use config::ConfigBuilder;
+use config::Value;
+use config::ValueKind;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config::ConfigurationEntry;
+use crate::consts::CACHE_DIR;
+use crate::error::Error;
+
+/// Configuration options for the cache of the reflections of the sources.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheConfiguration {
+    /// Whether the reflections of the sources are cached, so that the unchanged sources are not parsed again.
+    ///
+    /// Defaults to `true`.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// The directory of the cache, relative to the source root.
+    ///
+    /// The entries which were not used by the last `lint`, or `fix` run are removed, and `mago cache clear`
+    /// removes all of them.
+    ///
+    /// Defaults to `.mago/cache`.
+    #[serde(default = "default_directory")]
+    pub directory: PathBuf,
+}
+
+impl Default for CacheConfiguration {
+    fn default() -> Self {
+        Self { enabled: default_enabled(), directory: default_directory() }
+    }
+}
+
+impl ConfigurationEntry for CacheConfiguration {
+    fn configure<St: BuilderState>(self, builder: ConfigBuilder<St>) -> Result<ConfigBuilder<St>, Error> {
+        builder
+            .set_default("cache.enabled", Value::new(None, ValueKind::Boolean(self.enabled)))?
+            .set_default(
+                "cache.directory",
+                Value::new(None, ValueKind::String(self.directory.to_string_lossy().to_string())),
+            )
+            .map_err(Error::from)
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_directory() -> PathBuf {
+    PathBuf::from(CACHE_DIR)
+}
@@ -27,13 +27,23 @@ pub struct FormatterConfiguration {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_of_line: Option<EndOfLine>,
 
+    /// Specify which quotes to use for strings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub string_quotes: Option<StringQuotes>,
+
     /// Use single quotes instead of double quotes for strings.
+    ///
+    /// Deprecated: use `string_quotes` instead, which takes precedence over this option.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub single_quote: Option<bool>,
 
-    /// Enable or disable trailing commas in multi-line syntactic structures.
+    /// Whether to re-indent heredoc and nowdoc strings to the indentation of the code containing them.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub trailing_comma: Option<bool>,
+    pub normalize_heredoc_indent: Option<bool>,
+
+    /// Specify where to add trailing commas in multi-line syntactic structures.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trailing_comma: Option<TrailingComma>,
 
     /// Add spaces around the `=` in declare statements.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -43,15 +53,47 @@ pub struct FormatterConfiguration {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub keyword_case: Option<CasingStyle>,
 
+    /// Which syntax to use for array literals.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub array_syntax: Option<ArraySyntax>,
+
+    /// Which opening tag to use for blocks that only echo a single expression.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub echo_tag: Option<EchoTag>,
+
+    /// The number of blank lines after the opening PHP tag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blank_lines_after_opening_tag: Option<usize>,
+
     /// Blank line after the opening PHP tag.
+    ///
+    /// Deprecated: use `blank_lines_after_opening_tag` instead, which takes precedence over this option.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blank_line_after_open_tag: Option<bool>,
 
+    /// The number of blank lines between class-like members.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blank_lines_between_class_members: Option<usize>,
+
+    /// The number of blank lines before a `return` statement that follows another statement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blank_lines_before_return: Option<usize>,
+
+    /// The maximum number of consecutive blank lines to keep.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_consecutive_blank_lines: Option<usize>,
+
     /// In a control structure expression, is there a space after the opening parenthesis
     ///  and a space before the closing parenthesis?
     #[serde(skip_serializing_if = "Option::is_none")]
     pub control_space_parens: Option<bool>,
 
+    /// Brace style for declarations: classes, interfaces, traits, enums, functions, methods, and closures.
+    ///
+    /// The brace styles of the individual declarations take precedence over this option.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declaration_brace_style: Option<BraceStyle>,
+
     /// Brace style for closures.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub closure_brace_style: Option<BraceStyle>,
@@ -84,13 +126,21 @@ pub struct FormatterConfiguration {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub space_before_arrow_function_params: Option<bool>,
 
+    /// Whether to move an arrow function that is the last argument of a call onto its own line when it does not fit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefer_arrow_fn_single_expression: Option<bool>,
+
     /// Whether to put the `static` keyword before the visibility keyword.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub static_before_visibility: Option<bool>,
 
-    /// Null type hint style (`null|foo` or `?foo`).
+    /// How to write nullable types: `question_mark` (`?Foo`), `union_null` (`Foo|null`), or `preserve`.
+    #[serde(skip_serializing_if = "Option::is_none", alias = "null_type_hint")]
+    pub nullable_type_style: Option<NullableTypeStyle>,
+
+    /// Whether to sort the members of union types alphabetically, with `null` last.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub null_type_hint: Option<NullTypeHint>,
+    pub sort_union_types: Option<bool>,
 
     /// Spacing around binary operators.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -100,7 +150,17 @@ pub struct FormatterConfiguration {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub type_spacing: Option<usize>,
 
-    /// The minimum length of a method call chain that triggers line-breaking formatting.
+    /// The maximum number of calls in a method call chain that is kept on a single line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method_chain_breaking_threshold: Option<usize>,
+
+    /// Whether to wrap `new` expressions in parentheses when a member is accessed on them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parenthesize_new_in_chain: Option<bool>,
+
+    /// The minimum number of calls in a method call chain that triggers line-breaking formatting.
+    ///
+    /// Deprecated: use `method_chain_breaking_threshold` instead, which takes precedence over this option.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub method_chain_break_threshold: Option<usize>,
 
@@ -116,9 +176,35 @@ pub struct FormatterConfiguration {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preserve_multiline_binary_operations: Option<bool>,
 
+    /// Where to place binary operators when a binary operation is broken into multiple lines.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operator_position: Option<OperatorPosition>,
+
     /// How to format broken method/property chains.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub method_chain_breaking_style: Option<MethodChainBreakingStyle>,
+
+    /// Whether to sort the `use` statements at the top of a file, or of a namespace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_uses: Option<bool>,
+
+    /// The order of the groups of sorted `use` statements: `classes`, `functions`, and `constants`.
+    ///
+    /// Missing groups are placed after the given ones, in this default order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_groups: Option<Vec<UseGroup>>,
+
+    /// Whether to separate the groups of sorted `use` statements with a blank line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separate_use_groups: Option<bool>,
+
+    /// Whether to expand grouped `use` statements into one statement per name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand_use_groups: Option<bool>,
+
+    /// Whether to split attribute lists containing several attributes into one list per attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub separate_attribute_groups: Option<bool>,
 }
 
 impl FormatterConfiguration {
@@ -130,27 +216,61 @@ impl FormatterConfiguration {
             tab_width: self.tab_width.unwrap_or(d.tab_width),
             use_tabs: self.use_tabs.unwrap_or(d.use_tabs),
             end_of_line: self.end_of_line.unwrap_or(d.end_of_line),
-            single_quote: self.single_quote.unwrap_or(d.single_quote),
+            string_quotes: self
+                .string_quotes
+                .or(self.single_quote.map(|single| if single { StringQuotes::Single } else { StringQuotes::Double }))
+                .unwrap_or(d.string_quotes),
+            normalize_heredoc_indent: self.normalize_heredoc_indent.unwrap_or(d.normalize_heredoc_indent),
             trailing_comma: self.trailing_comma.unwrap_or(d.trailing_comma),
             space_around_declare_equals: self.space_around_declare_equals.unwrap_or(d.space_around_declare_equals),
             keyword_case: self.keyword_case.unwrap_or(d.keyword_case),
-            blank_line_after_open_tag: self.blank_line_after_open_tag.unwrap_or(d.blank_line_after_open_tag),
+            array_syntax: self.array_syntax.unwrap_or(d.array_syntax),
+            echo_tag: self.echo_tag.unwrap_or(d.echo_tag),
+            blank_lines_after_opening_tag: self
+                .blank_lines_after_opening_tag
+                .or(self.blank_line_after_open_tag.map(usize::from))
+                .unwrap_or(d.blank_lines_after_opening_tag),
+            blank_lines_between_class_members: self
+                .blank_lines_between_class_members
+                .unwrap_or(d.blank_lines_between_class_members),
+            blank_lines_before_return: self.blank_lines_before_return.or(d.blank_lines_before_return),
+            max_consecutive_blank_lines: self.max_consecutive_blank_lines.or(d.max_consecutive_blank_lines),
             control_space_parens: self.control_space_parens.unwrap_or(d.control_space_parens),
-            closure_brace_style: self.closure_brace_style.unwrap_or(d.closure_brace_style),
-            function_brace_style: self.function_brace_style.unwrap_or(d.function_brace_style),
-            method_brace_style: self.method_brace_style.unwrap_or(d.method_brace_style),
-            classlike_brace_style: self.classlike_brace_style.unwrap_or(d.classlike_brace_style),
+            closure_brace_style: self
+                .closure_brace_style
+                .or(self.declaration_brace_style)
+                .unwrap_or(d.closure_brace_style),
+            function_brace_style: self
+                .function_brace_style
+                .or(self.declaration_brace_style)
+                .unwrap_or(d.function_brace_style),
+            method_brace_style: self
+                .method_brace_style
+                .or(self.declaration_brace_style)
+                .unwrap_or(d.method_brace_style),
+            classlike_brace_style: self
+                .classlike_brace_style
+                .or(self.declaration_brace_style)
+                .unwrap_or(d.classlike_brace_style),
             control_brace_style: self.control_brace_style.unwrap_or(d.control_brace_style),
             space_before_closure_params: self.space_before_closure_params.unwrap_or(d.space_before_closure_params),
             space_after_closure_use: self.space_after_closure_use.unwrap_or(d.space_after_closure_use),
             space_before_arrow_function_params: self
                 .space_before_arrow_function_params
                 .unwrap_or(d.space_before_arrow_function_params),
+            prefer_arrow_fn_single_expression: self
+                .prefer_arrow_fn_single_expression
+                .unwrap_or(d.prefer_arrow_fn_single_expression),
             static_before_visibility: self.static_before_visibility.unwrap_or(d.static_before_visibility),
-            null_type_hint: self.null_type_hint.unwrap_or(d.null_type_hint),
+            nullable_type_style: self.nullable_type_style.unwrap_or(d.nullable_type_style),
+            sort_union_types: self.sort_union_types.unwrap_or(d.sort_union_types),
             binary_op_spacing: self.binary_op_spacing.unwrap_or(d.binary_op_spacing),
             type_spacing: self.type_spacing.unwrap_or(d.type_spacing),
-            method_chain_break_threshold: self.method_chain_break_threshold.unwrap_or(d.method_chain_break_threshold),
+            method_chain_breaking_threshold: self
+                .method_chain_breaking_threshold
+                .or(self.method_chain_break_threshold.map(|threshold| threshold.saturating_sub(1)))
+                .unwrap_or(d.method_chain_breaking_threshold),
+            parenthesize_new_in_chain: self.parenthesize_new_in_chain.unwrap_or(d.parenthesize_new_in_chain),
             break_promoted_properties_list: self
                 .break_promoted_properties_list
                 .unwrap_or(d.break_promoted_properties_list),
@@ -159,7 +279,13 @@ impl FormatterConfiguration {
             preserve_multiline_binary_operations: self
                 .preserve_multiline_binary_operations
                 .unwrap_or(d.preserve_multiline_binary_operations),
+            operator_position: self.operator_position.unwrap_or(d.operator_position),
             method_chain_breaking_style: self.method_chain_breaking_style.unwrap_or(d.method_chain_breaking_style),
+            sort_uses: self.sort_uses.unwrap_or(d.sort_uses),
+            use_groups: self.use_groups.as_deref().map(UseGroup::order).unwrap_or(d.use_groups),
+            separate_use_groups: self.separate_use_groups.unwrap_or(d.separate_use_groups),
+            expand_use_groups: self.expand_use_groups.unwrap_or(d.expand_use_groups),
+            separate_attribute_groups: self.separate_attribute_groups.unwrap_or(d.separate_attribute_groups),
         }
     }
 }
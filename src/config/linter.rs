@@ -1,10 +1,17 @@
+use std::path::PathBuf;
+
 use ahash::HashMap;
 use config::builder::BuilderState;
 use config::ConfigBuilder;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::Display;
+use strum::EnumString;
+use strum::VariantNames;
 use toml::value::Value;
 
+use mago_reporting::Level;
+
 use crate::config::ConfigurationEntry;
 use crate::error::Error;
 
@@ -17,12 +24,52 @@ pub enum LinterLevel {
     Error,
 }
 
+/// The minimum level of issues that makes the `lint` command exit with a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumString, VariantNames)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum LinterFailLevel {
+    Note,
+    Help,
+    Warning,
+    Error,
+    Never,
+}
+
+impl LinterFailLevel {
+    /// Returns the level at which the `lint` command fails, or `None` if it should never fail.
+    pub fn as_level(&self) -> Option<Level> {
+        match self {
+            LinterFailLevel::Note => Some(Level::Note),
+            LinterFailLevel::Help => Some(Level::Help),
+            LinterFailLevel::Warning => Some(Level::Warning),
+            LinterFailLevel::Error => Some(Level::Error),
+            LinterFailLevel::Never => None,
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LinterConfiguration {
     pub level: Option<LinterLevel>,
+    pub minimum_fail_level: Option<LinterFailLevel>,
+    pub baseline: Option<PathBuf>,
     pub default_plugins: Option<bool>,
     pub plugins: Vec<String>,
     pub rules: Vec<LinterRuleConfiguration>,
+    #[serde(default)]
+    pub overrides: Vec<LinterOverrideConfiguration>,
+}
+
+/// Linter settings that replace the base settings for the files matching any of the given paths.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct LinterOverrideConfiguration {
+    /// Glob patterns, or directories, relative to the source root.
+    pub paths: Vec<String>,
+    pub level: Option<LinterLevel>,
+    pub default_plugins: Option<bool>,
+    pub plugins: Option<Vec<String>>,
+    #[serde(default)]
+    pub rules: Vec<LinterRuleConfiguration>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
@@ -33,6 +80,49 @@ pub struct LinterRuleConfiguration {
     pub options: HashMap<String, Value>,
 }
 
+impl LinterConfiguration {
+    /// Returns the configuration to use for the files matching the given override.
+    ///
+    /// The level, and plugins of the override replace the base ones when set, and its rules replace
+    /// the base rules with the same name.
+    pub fn with_override(&self, r#override: &LinterOverrideConfiguration) -> LinterConfiguration {
+        let mut rules: Vec<LinterRuleConfiguration> = self
+            .rules
+            .iter()
+            .filter(|rule| !r#override.rules.iter().any(|overridden| overridden.name == rule.name))
+            .cloned()
+            .collect();
+
+        rules.extend(r#override.rules.iter().cloned());
+
+        LinterConfiguration {
+            level: r#override.level.or(self.level),
+            minimum_fail_level: self.minimum_fail_level,
+            baseline: self.baseline.clone(),
+            default_plugins: r#override.default_plugins.or(self.default_plugins),
+            plugins: r#override.plugins.clone().unwrap_or_else(|| self.plugins.clone()),
+            rules,
+            overrides: vec![],
+        }
+    }
+}
+
+impl LinterOverrideConfiguration {
+    /// Returns whether the given path, relative to the source root, matches any of the override paths.
+    ///
+    /// A path matches if it matches a glob pattern, or if it is located in a directory listed in the paths.
+    pub fn matches(&self, path: &str) -> bool {
+        let path = path.replace('\\', "/");
+
+        self.paths.iter().any(|pattern| {
+            let directory = pattern.trim_end_matches('/');
+
+            glob_match::glob_match(pattern, &path)
+                || path.strip_prefix(directory).is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
 impl ConfigurationEntry for LinterConfiguration {
     fn configure<St: BuilderState>(self, builder: ConfigBuilder<St>) -> Result<ConfigBuilder<St>, Error> {
         use ::config::Value;
@@ -40,10 +130,87 @@ impl ConfigurationEntry for LinterConfiguration {
 
         let builder = builder
             .set_default("linter.level", Value::new(None, ValueKind::Nil))?
+            .set_default("linter.minimum_fail_level", Value::new(None, ValueKind::Nil))?
+            .set_default("linter.baseline", Value::new(None, ValueKind::Nil))?
             .set_default("linter.default_plugins", Value::new(None, ValueKind::Nil))?
             .set_default("linter.plugins", Value::new(None, ValueKind::Array(vec![])))?
-            .set_default("linter.rules", Value::new(None, ValueKind::Array(vec![])))?;
+            .set_default("linter.rules", Value::new(None, ValueKind::Array(vec![])))?
+            .set_default("linter.overrides", Value::new(None, ValueKind::Array(vec![])))?;
 
         Ok(builder)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, level: LinterLevel) -> LinterRuleConfiguration {
+        LinterRuleConfiguration { name: name.to_string(), level: Some(level), options: HashMap::default() }
+    }
+
+    #[test]
+    fn test_override_matches_globs_and_directories() {
+        let r#override = LinterOverrideConfiguration {
+            paths: vec!["tests/".to_string(), "src/**/*Fixture.php".to_string()],
+            ..Default::default()
+        };
+
+        assert!(r#override.matches("tests/UserTest.php"));
+        assert!(r#override.matches("tests\\Unit\\UserTest.php"));
+        assert!(r#override.matches("src/Domain/UserFixture.php"));
+        assert!(!r#override.matches("src/Domain/User.php"));
+        assert!(!r#override.matches("tests-old/UserTest.php"));
+    }
+
+    #[test]
+    fn test_with_override_replaces_base_settings() {
+        let base = LinterConfiguration {
+            level: Some(LinterLevel::Error),
+            plugins: vec!["safety".to_string()],
+            rules: vec![rule("safety/no-eval", LinterLevel::Error), rule("safety/no-ffi", LinterLevel::Error)],
+            ..Default::default()
+        };
+
+        let r#override = LinterOverrideConfiguration {
+            paths: vec!["tests".to_string()],
+            level: Some(LinterLevel::Warning),
+            rules: vec![rule("safety/no-eval", LinterLevel::Off)],
+            ..Default::default()
+        };
+
+        let configuration = base.with_override(&r#override);
+
+        assert_eq!(configuration.level, Some(LinterLevel::Warning));
+        assert_eq!(configuration.plugins, vec!["safety".to_string()]);
+        assert_eq!(
+            configuration.rules,
+            vec![rule("safety/no-ffi", LinterLevel::Error), rule("safety/no-eval", LinterLevel::Off)]
+        );
+    }
+
+    #[test]
+    fn test_minimum_fail_level_maps_to_reporting_levels() {
+        assert_eq!("warning".parse::<LinterFailLevel>().unwrap(), LinterFailLevel::Warning);
+        assert_eq!("NEVER".parse::<LinterFailLevel>().unwrap(), LinterFailLevel::Never);
+        assert!("info".parse::<LinterFailLevel>().is_err());
+
+        assert_eq!(LinterFailLevel::Note.as_level(), Some(Level::Note));
+        assert_eq!(LinterFailLevel::Help.as_level(), Some(Level::Help));
+        assert_eq!(LinterFailLevel::Warning.as_level(), Some(Level::Warning));
+        assert_eq!(LinterFailLevel::Error.as_level(), Some(Level::Error));
+        assert_eq!(LinterFailLevel::Never.as_level(), None);
+    }
+
+    #[test]
+    fn test_minimum_fail_level_is_read_from_the_configuration_and_kept_by_overrides() {
+        let configuration: LinterConfiguration =
+            toml::from_str("minimum_fail_level = \"Warning\"\nplugins = []\nrules = []").unwrap();
+
+        assert_eq!(configuration.minimum_fail_level, Some(LinterFailLevel::Warning));
+
+        let r#override = LinterOverrideConfiguration { paths: vec!["tests".to_string()], ..Default::default() };
+
+        assert_eq!(configuration.with_override(&r#override).minimum_fail_level, Some(LinterFailLevel::Warning));
+    }
+}
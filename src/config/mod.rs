@@ -11,14 +11,20 @@ use config::ValueKind;
 use serde::Deserialize;
 use serde::Serialize;
 
+use mago_php_version::PHPVersion;
+
+use crate::config::cache::CacheConfiguration;
 use crate::config::formatter::FormatterConfiguration;
 use crate::config::linter::LinterConfiguration;
+use crate::config::reporting::ReportingConfiguration;
 use crate::config::source::SourceConfiguration;
 use crate::consts::*;
 use crate::error::Error;
 
+pub mod cache;
 pub mod formatter;
 pub mod linter;
+pub mod reporting;
 pub mod source;
 
 /// Configuration options for mago.
@@ -30,6 +36,12 @@ pub struct Configuration {
     /// The size of the stack for each thread.
     pub stack_size: usize,
 
+    /// The targeted version of PHP, such as `8.1`, defaulting to the newest supported version.
+    ///
+    /// Features introduced in newer versions are reported, and rules suggesting them are disabled.
+    #[serde(default)]
+    pub php_version: PHPVersion,
+
     /// Configuration options for source discovery.
     pub source: SourceConfiguration,
 
@@ -40,6 +52,13 @@ pub struct Configuration {
     /// Configuration options for the formatter.
     #[serde(default)]
     pub format: FormatterConfiguration,
+
+    /// Configuration options for reporting issues.
+    #[serde(default)]
+    pub reporting: ReportingConfiguration,
+
+    #[serde(default)]
+    pub cache: CacheConfiguration,
 }
 
 impl Configuration {
@@ -74,8 +93,11 @@ impl Configuration {
             source: SourceConfiguration::from_root(root),
             threads: *LOGICAL_CPUS,
             stack_size: DEFAULT_STACK_SIZE,
+            php_version: PHPVersion::LATEST,
             linter: LinterConfiguration::default(),
             format: FormatterConfiguration::default(),
+            reporting: ReportingConfiguration::default(),
+            cache: CacheConfiguration::default(),
         }
     }
 }
@@ -95,7 +117,8 @@ impl ConfigurationEntry for Configuration {
 
         let mut builder = builder
             .set_default("threads", Value::new(None, ValueKind::U64(self.threads as u64)))?
-            .set_default("stack_size", Value::new(None, ValueKind::U64(self.stack_size as u64)))?;
+            .set_default("stack_size", Value::new(None, ValueKind::U64(self.stack_size as u64)))?
+            .set_default("php_version", Value::new(None, ValueKind::String(self.php_version.to_string())))?;
 
         tracing::trace!("configuring source entry");
         builder = self.source.configure(builder)?;
@@ -106,6 +129,12 @@ impl ConfigurationEntry for Configuration {
         tracing::trace!("configuring formatter entry");
         builder = self.format.configure(builder)?;
 
+        tracing::trace!("configuring reporting entry");
+        builder = self.reporting.configure(builder)?;
+
+        tracing::trace!("configuring cache entry");
+        builder = self.cache.configure(builder)?;
+
         Ok(builder)
     }
 
@@ -140,9 +169,16 @@ impl ConfigurationEntry for Configuration {
             tracing::debug!("configuration specifies a stack size of {} bytes", self.stack_size);
         }
 
+        tracing::debug!("configuration targets PHP {}", self.php_version);
+
         self.source.normalize()?;
         self.linter.normalize()?;
 
+        // The cache directory is relative to the root, which is only known once normalized.
+        if self.cache.directory.is_relative() {
+            self.cache.directory = self.source.root.join(&self.cache.directory);
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,25 @@
+use config::builder::BuilderState;
+use config::ConfigBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config::ConfigurationEntry;
+use crate::error::Error;
+
+/// Configuration options for reporting issues.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportingConfiguration {
+    /// The number of source lines to show above and below each issue in code frames.
+    pub context_lines: Option<usize>,
+}
+
+impl ConfigurationEntry for ReportingConfiguration {
+    fn configure<St: BuilderState>(self, builder: ConfigBuilder<St>) -> Result<ConfigBuilder<St>, Error> {
+        use ::config::Value;
+        use ::config::ValueKind;
+
+        let builder = builder.set_default("reporting.context_lines", Value::new(None, ValueKind::Nil))?;
+
+        Ok(builder)
+    }
+}
@@ -1,12 +1,19 @@
+use std::borrow::Cow;
+use std::path::Component;
+use std::path::Path;
 use std::path::PathBuf;
 
 use config::builder::BuilderState;
 use config::ConfigBuilder;
 use config::Value;
 use config::ValueKind;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
 use serde::Deserialize;
 use serde::Serialize;
 
+use mago_parser::settings::ParserSettings;
+
 use crate::config::ConfigurationEntry;
 use crate::config::CURRENT_DIR;
 use crate::error::Error;
@@ -19,27 +26,88 @@ pub struct SourceConfiguration {
     /// Defaults to the current working directory.
     pub root: PathBuf,
 
-    /// Paths to user defined source files.
+    /// Paths to user defined source files, or glob patterns matching them, such as `src/**/Entity`.
     ///
     /// If empty, all files in the root directory are included.
     ///
     /// Defaults to `[]`.
     pub paths: Vec<PathBuf>,
 
-    /// Paths to non-user defined files to include in the scan.
+    /// Paths to non-user defined files to include in the scan, or glob patterns matching them.
+    ///
+    /// When a file is matched by both the paths and the includes, the most specific entry wins, that is the
+    /// one with the most literal components, and the last one listed among equally specific entries, the
+    /// includes being listed after the paths.
     ///
     /// Defaults to `[]`.
     pub includes: Vec<PathBuf>,
 
-    /// Patterns to exclude from the scan.
+    /// Paths, or glob patterns, to exclude from the scan, such as `**/generated/**`.
+    ///
+    /// Exclusions take precedence over both the paths, and the includes.
     ///
     /// Defaults to `[]`.
     pub excludes: Vec<String>,
 
+    /// Whether the glob patterns of the paths, includes, and excludes match paths regardless of their case.
+    ///
+    /// Patterns are matched against the paths relative to the root, `*` and `?` never matching a `/`, while `**`
+    /// matches any number of directories, along with character classes such as `[ab]`, and alternatives such
+    /// as `{src,lib}`. They are case-sensitive on all platforms unless this is enabled.
+    ///
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub case_insensitive: bool,
+
     /// File extensions to filter by.
     ///
     /// Defaults to `[".php"]`.
     pub extensions: Vec<String>,
+
+    /// Whether `<?` opens a block of PHP code, as with the `short_open_tag` ini setting of PHP.
+    ///
+    /// When disabled, the code following `<?` is inline text, while `<?php` and `<?=` always work.
+    ///
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub short_open_tags: bool,
+
+    /// Whether to use the reflections of the standard library of PHP, and of its common extensions,
+    /// which are bundled with mago, for the targeted version of PHP.
+    ///
+    /// Defaults to `true`.
+    #[serde(default = "default_use_builtin_stubs")]
+    pub use_builtin_stubs: bool,
+
+    /// Path to a directory of stubs to use instead of the bundled ones, such as a checkout of the
+    /// JetBrains phpstorm-stubs.
+    ///
+    /// Defaults to `None`.
+    #[serde(default)]
+    pub stubs: Option<PathBuf>,
+
+    /// Whether the paths autoloaded by the development dependencies installed by composer are included
+    /// in the scan, along with those of the other dependencies.
+    ///
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub include_dev_dependencies: bool,
+
+    /// Whether the files ignored by the `.gitignore` files of the repository are skipped when scanning the
+    /// user defined sources, along with those ignored by the `.magoignore` files, which are always respected.
+    ///
+    /// Defaults to `true`.
+    #[serde(default = "default_respect_gitignore")]
+    pub respect_gitignore: bool,
+}
+
+/// A path of the source configuration, matching either a path along with everything it contains, or the paths
+/// matching a glob pattern, along with everything they contain.
+#[derive(Debug, Clone)]
+pub struct SourcePath {
+    base: PathBuf,
+    pattern: Option<(GlobMatcher, Option<PathBuf>)>,
+    specificity: usize,
 }
 
 impl SourceConfiguration {
@@ -53,7 +121,54 @@ impl SourceConfiguration {
     ///
     /// A new `SourceConfiguration` with the given root directory.
     pub fn from_root(root: PathBuf) -> Self {
-        Self { root, paths: vec![], includes: vec![], excludes: vec![], extensions: vec![] }
+        Self {
+            root,
+            paths: vec![],
+            includes: vec![],
+            excludes: vec![],
+            extensions: vec![],
+            short_open_tags: false,
+            use_builtin_stubs: true,
+            stubs: None,
+            include_dev_dependencies: false,
+            respect_gitignore: true,
+            case_insensitive: false,
+        }
+    }
+
+    /// Returns whether the bundled reflections of the stubs are used, i.e. whether they are enabled, and not
+    /// overridden by a directory of stubs.
+    pub fn uses_builtin_stubs(&self) -> bool {
+        self.use_builtin_stubs && self.stubs.is_none()
+    }
+
+    /// Returns the user defined source paths, or the root when none is configured.
+    pub fn source_paths(&self) -> Vec<SourcePath> {
+        if self.paths.is_empty() {
+            return vec![SourcePath::path(self.root.clone())];
+        }
+
+        self.compile(self.paths.iter().map(|path| path.to_string_lossy()))
+    }
+
+    /// Returns the paths of the non-user defined source files to include in the scan.
+    pub fn include_paths(&self) -> Vec<SourcePath> {
+        self.compile(self.includes.iter().map(|path| path.to_string_lossy()))
+    }
+
+    /// Returns the paths excluded from the scan.
+    pub fn exclude_paths(&self) -> Vec<SourcePath> {
+        self.compile(self.excludes.iter().map(Cow::from))
+    }
+
+    fn compile<'a>(&self, entries: impl Iterator<Item = Cow<'a, str>>) -> Vec<SourcePath> {
+        // Invalid patterns are rejected when the configuration is loaded.
+        entries.filter_map(|entry| SourcePath::new(&self.root, &entry, self.case_insensitive).ok()).collect()
+    }
+
+    /// Returns the settings of the parser for the sources.
+    pub fn parser_settings(&self) -> ParserSettings {
+        ParserSettings { short_open_tags: self.short_open_tags }
     }
 }
 
@@ -67,7 +182,12 @@ impl ConfigurationEntry for SourceConfiguration {
             .set_default(
                 "source.extensions",
                 Value::new(None, ValueKind::Array(vec![Value::new(None, ValueKind::String("php".to_string()))])),
-            )
+            )?
+            .set_default("source.short_open_tags", Value::new(None, ValueKind::Boolean(false)))?
+            .set_default("source.use_builtin_stubs", Value::new(None, ValueKind::Boolean(true)))?
+            .set_default("source.include_dev_dependencies", Value::new(None, ValueKind::Boolean(false)))?
+            .set_default("source.respect_gitignore", Value::new(None, ValueKind::Boolean(true)))?
+            .set_default("source.case_insensitive", Value::new(None, ValueKind::Boolean(false)))
             .map_err(Error::from)
     }
 
@@ -78,27 +198,169 @@ impl ConfigurationEntry for SourceConfiguration {
         self.root = root.canonicalize().map_err(|e| Error::CanonicalizingPath(root, e))?;
 
         // Normalize source paths
-        self.paths = self
-            .paths
-            .iter()
-            .map(|p| {
-                let path = if p.is_absolute() { p.clone() } else { self.root.join(p) };
-
-                path.canonicalize().map_err(|e| Error::CanonicalizingPath(p.clone(), e))
-            })
-            .collect::<Result<Vec<PathBuf>, Error>>()?;
+        self.paths = normalize_paths(&self.root, &self.paths, self.case_insensitive)?;
 
         // Normalize include paths
-        self.includes = self
-            .includes
-            .iter()
-            .map(|p| {
-                let path = if p.is_absolute() { p.clone() } else { self.root.join(p) };
+        self.includes = normalize_paths(&self.root, &self.includes, self.case_insensitive)?;
+
+        // Validate the patterns of the excluded paths, which do not need to exist
+        for exclude in &self.excludes {
+            SourcePath::new(&self.root, exclude, self.case_insensitive)?;
+        }
+
+        // Normalize the stubs path
+        if let Some(stubs) = &self.stubs {
+            let path = if stubs.is_absolute() { stubs.clone() } else { self.root.join(stubs) };
 
-                path.canonicalize().map_err(|e| Error::CanonicalizingPath(p.clone(), e))
-            })
-            .collect::<Result<Vec<PathBuf>, Error>>()?;
+            self.stubs = Some(path.canonicalize().map_err(|e| Error::CanonicalizingPath(stubs.clone(), e))?);
+        }
 
         Ok(())
     }
 }
+
+impl SourcePath {
+    /// Creates a source path matching the given path, along with everything it contains.
+    pub fn path(path: PathBuf) -> Self {
+        Self { specificity: path.components().count(), base: path, pattern: None }
+    }
+
+    /// Creates a source path from the given entry of the configuration, which is either a path, or a glob pattern.
+    ///
+    /// Relative paths, and patterns, are relative to the given root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the entry if it is an invalid glob pattern.
+    pub fn new(root: &Path, entry: &str, case_insensitive: bool) -> Result<Self, Error> {
+        let pattern = entry.strip_prefix("./").unwrap_or(entry);
+        let path = Path::new(pattern);
+        if !is_glob(pattern) {
+            return Ok(Self::path(if path.is_absolute() { path.to_path_buf() } else { root.join(path) }));
+        }
+
+        let glob = GlobBuilder::new(&pattern.replace('\\', "/"))
+            .literal_separator(true)
+            .backslash_escape(false)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|error| Error::InvalidPattern(entry.to_string(), error))?;
+
+        // The paths matching the pattern are all within the directory named by its leading literal components.
+        let literal = |component: &Component<'_>| !is_glob(&component.as_os_str().to_string_lossy());
+        let mut base = if path.is_absolute() { PathBuf::new() } else { root.to_path_buf() };
+        base.extend(path.components().take_while(literal));
+
+        let specificity = base.components().count() + path.components().skip_while(literal).filter(literal).count();
+        let relative_to = if path.is_absolute() { None } else { Some(root.to_path_buf()) };
+
+        Ok(Self { base, pattern: Some((glob.compile_matcher(), relative_to)), specificity })
+    }
+
+    /// Returns the directory containing all the paths matched by this source path.
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// Returns the number of literal components of this source path, the most specific of the source paths
+    /// matching a file taking precedence over the others.
+    pub fn specificity(&self) -> usize {
+        self.specificity
+    }
+
+    /// Checks if the given absolute path is matched by this source path, either itself, or as part of a
+    /// matched directory.
+    pub fn matches(&self, path: &Path) -> bool {
+        if !path.starts_with(&self.base) {
+            return false;
+        }
+
+        let Some((matcher, relative_to)) = &self.pattern else {
+            return true;
+        };
+
+        path.ancestors().take_while(|ancestor| ancestor.starts_with(&self.base)).any(|ancestor| {
+            let candidate = match relative_to {
+                Some(root) => ancestor.strip_prefix(root).unwrap_or(ancestor),
+                None => ancestor,
+            };
+
+            matcher.is_match(candidate.to_string_lossy().replace('\\', "/"))
+        })
+    }
+}
+
+/// Checks if the given entry of the configuration is a glob pattern, rather than a path.
+fn is_glob(entry: &str) -> bool {
+    entry.contains(['*', '?', '[', '{'])
+}
+
+/// Resolves the given paths relative to the root, and canonicalizes them, keeping the glob patterns as they are,
+/// once they are checked to be valid.
+fn normalize_paths(root: &Path, paths: &[PathBuf], case_insensitive: bool) -> Result<Vec<PathBuf>, Error> {
+    paths
+        .iter()
+        .map(|p| {
+            if is_glob(&p.to_string_lossy()) {
+                SourcePath::new(root, &p.to_string_lossy(), case_insensitive)?;
+
+                return Ok(p.clone());
+            }
+
+            let path = if p.is_absolute() { p.clone() } else { root.join(p) };
+
+            path.canonicalize().map_err(|e| Error::CanonicalizingPath(p.clone(), e))
+        })
+        .collect()
+}
+
+fn default_use_builtin_stubs() -> bool {
+    true
+}
+
+fn default_respect_gitignore() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_glob_patterns_are_rejected() {
+        let configuration = SourceConfiguration::from_root(CURRENT_DIR.clone());
+        let invalid = [
+            SourceConfiguration { paths: vec!["src/[Entity".into()], ..configuration.clone() },
+            SourceConfiguration { includes: vec!["{vendor,lib".into()], ..configuration.clone() },
+            SourceConfiguration { excludes: vec!["**/generated/[!".to_string()], ..configuration.clone() },
+        ];
+
+        for (mut configuration, expected) in invalid.into_iter().zip(["src/[Entity", "{vendor,lib", "**/generated/[!"])
+        {
+            let error = configuration.normalize().unwrap_err();
+
+            assert!(matches!(&error, Error::InvalidPattern(pattern, _) if pattern == expected));
+            assert!(error.to_string().starts_with(&format!("invalid pattern `{}`: ", expected)));
+        }
+    }
+
+    #[test]
+    fn test_source_paths_match_the_paths_relative_to_the_root() {
+        let root = Path::new("/project");
+        let pattern = SourcePath::new(root, "modules/*/src", false).unwrap();
+
+        assert_eq!(pattern.base(), Path::new("/project/modules"));
+        assert!(pattern.matches(Path::new("/project/modules/Billing/src/Invoice.php")));
+        assert!(!pattern.matches(Path::new("/project/modules/Billing/lib/src/Invoice.php")));
+        assert!(!pattern.matches(Path::new("/elsewhere/modules/Billing/src/Invoice.php")));
+
+        let pattern = SourcePath::new(root, "src/{Entity,Model}/[A-Z]?*.php", false).unwrap();
+        assert!(pattern.matches(Path::new("/project/src/Model/User.php")));
+        assert!(!pattern.matches(Path::new("/project/src/Model/user.php")));
+        assert!(!pattern.matches(Path::new("/project/src/Service/User.php")));
+
+        let path = SourcePath::new(root, "src/Entity", false).unwrap();
+        assert!(path.matches(Path::new("/project/src/Entity/User.php")));
+        assert!(path.specificity() > SourcePath::new(root, "src/**", false).unwrap().specificity());
+    }
+}
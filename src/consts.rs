@@ -29,6 +29,16 @@ pub const ENVIRONMENT_PREFIX: &str = "MAGO";
 /// The name of the configuration file for mago.
 pub const CONFIGURATION_FILE: &str = "mago";
 
+/// The name of the baseline file used when no baseline is configured.
+pub const BASELINE_FILE: &str = "mago-baseline.toml";
+
+/// The name of the files listing the paths to skip when scanning the user defined sources, in the syntax of
+/// `.gitignore` files.
+pub const IGNORE_FILE: &str = ".magoignore";
+
+/// The directory of the cache used when no cache directory is configured, relative to the source root.
+pub const CACHE_DIR: &str = ".mago/cache";
+
 /// The minimum stack size for each thread.
 pub const MINIMUM_STACK_SIZE: usize = 8 * 1024 * 1024;
 
@@ -54,3 +64,6 @@ pub static CURRENT_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
 });
 
 include!(concat!(env!("OUT_DIR"), "/stubs_map.rs"));
+
+/// The reflections of the PHP stubs, for each version of PHP, encoded by the build script.
+pub static PHP_STUB_REFLECTIONS: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/stubs.bin"));
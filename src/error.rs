@@ -1,3 +1,4 @@
+use mago_reflector::stubs::StubsError;
 use mago_reporting::error::ReportingError;
 use mago_source::error::SourceError;
 
@@ -5,15 +6,27 @@ use mago_source::error::SourceError;
 pub enum Error {
     Source(SourceError),
     Reporting(ReportingError),
+    Stubs(StubsError),
     BuildingRuntime(std::io::Error),
-    Walking(async_walkdir::Error),
+    Walking(ignore::Error),
     BuildingConfiguration(config::ConfigError),
     DeserializingToml(toml::de::Error),
     SerializingToml(toml::ser::Error),
     CanonicalizingPath(std::path::PathBuf, std::io::Error),
+    InvalidPattern(String, globset::Error),
     Join(tokio::task::JoinError),
     Json(serde_json::Error),
     SelfUpdate(self_update::errors::Error),
+    Baseline(std::path::PathBuf, std::io::Error),
+    Backup(std::path::PathBuf, std::io::Error),
+    Cache(std::path::PathBuf, std::io::Error),
+    Reflection(std::path::PathBuf, String),
+    UnknownRule(String, Vec<String>),
+    Git(String),
+    Prompting(std::io::Error),
+    ReadingStdin(std::io::Error),
+    WritingStdout(std::io::Error),
+    Watching(notify::Error),
 }
 
 impl std::fmt::Display for Error {
@@ -21,15 +34,32 @@ impl std::fmt::Display for Error {
         match self {
             Self::Source(error) => write!(f, "{}", error),
             Self::Reporting(error) => write!(f, "{}", error),
+            Self::Stubs(error) => write!(f, "{}", error),
             Self::Walking(error) => write!(f, "{}", error),
             Self::BuildingRuntime(error) => write!(f, "{}", error),
             Self::BuildingConfiguration(error) => write!(f, "{}", error),
             Self::DeserializingToml(error) => write!(f, "{}", error),
             Self::SerializingToml(error) => write!(f, "{}", error),
             Self::CanonicalizingPath(_, error) => write!(f, "{}", error),
+            Self::InvalidPattern(pattern, error) => write!(f, "invalid pattern `{}`: {}", pattern, error.kind()),
             Self::Join(error) => write!(f, "{}", error),
             Self::Json(error) => write!(f, "{}", error),
             Self::SelfUpdate(error) => write!(f, "{}", error),
+            Self::Baseline(path, error) => write!(f, "failed to access baseline `{}`: {}", path.display(), error),
+            Self::Backup(path, error) => write!(f, "failed to write backup `{}`: {}", path.display(), error),
+            Self::Cache(path, error) => write!(f, "failed to access cache `{}`: {}", path.display(), error),
+            Self::Reflection(path, error) => write!(f, "failed to access reflection `{}`: {}", path.display(), error),
+            Self::Git(message) => write!(f, "{}", message),
+            Self::Prompting(error) => write!(f, "failed to prompt for input: {}", error),
+            Self::ReadingStdin(error) => write!(f, "failed to read from stdin: {}", error),
+            Self::WritingStdout(error) => write!(f, "failed to write to stdout: {}", error),
+            Self::Watching(error) => write!(f, "failed to watch for changes: {}", error),
+            Self::UnknownRule(rule, suggestions) if suggestions.is_empty() => write!(f, "unknown rule `{}`", rule),
+            Self::UnknownRule(rule, suggestions) => {
+                let suggestions = suggestions.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ");
+
+                write!(f, "unknown rule `{}`, did you mean {}?", rule, suggestions)
+            }
         }
     }
 }
@@ -39,15 +69,27 @@ impl std::error::Error for Error {
         match self {
             Self::Source(error) => Some(error),
             Self::Reporting(error) => Some(error),
+            Self::Stubs(error) => Some(error),
             Self::Walking(error) => Some(error),
             Self::BuildingConfiguration(error) => Some(error),
             Self::BuildingRuntime(error) => Some(error),
             Self::DeserializingToml(error) => Some(error),
             Self::SerializingToml(error) => Some(error),
             Self::CanonicalizingPath(_, error) => Some(error),
+            Self::InvalidPattern(_, error) => Some(error),
             Self::Join(error) => Some(error),
             Self::Json(error) => Some(error),
             Self::SelfUpdate(error) => Some(error),
+            Self::Baseline(_, error) => Some(error),
+            Self::Backup(_, error) => Some(error),
+            Self::Cache(_, error) => Some(error),
+            Self::Reflection(_, _) => None,
+            Self::UnknownRule(_, _) => None,
+            Self::Git(_) => None,
+            Self::Prompting(error) => Some(error),
+            Self::ReadingStdin(error) => Some(error),
+            Self::WritingStdout(error) => Some(error),
+            Self::Watching(error) => Some(error),
         }
     }
 }
@@ -64,8 +106,8 @@ impl From<ReportingError> for Error {
     }
 }
 
-impl From<async_walkdir::Error> for Error {
-    fn from(error: async_walkdir::Error) -> Self {
+impl From<ignore::Error> for Error {
+    fn from(error: ignore::Error) -> Self {
         Self::Walking(error)
     }
 }
@@ -105,3 +147,9 @@ impl From<self_update::errors::Error> for Error {
         Self::SelfUpdate(error)
     }
 }
+
+impl From<StubsError> for Error {
+    fn from(error: StubsError) -> Self {
+        Self::Stubs(error)
+    }
+}
@@ -0,0 +1,321 @@
+//! The JSON format of the reflection of a codebase, written by `mago reflect`, and read back with
+//! `--import-reflection`, so that external tools can read the codebase as mago sees it, and so that the
+//! external sources do not need to be reflected again by later runs.
+//!
+//! The document is an object with the following fields:
+//!
+//! - `format`: always `"mago-codebase"`.
+//! - `version`: the version of the format, incremented whenever its layout changes.
+//! - `mago_version`: the version of mago which wrote the document, whose reflections may differ from those
+//!   of other versions, so that a document is only imported by the version which wrote it.
+//! - `php_version`: the version of PHP for which the codebase was reflected, such as `"8.3"`.
+//! - `class_likes`: the classes, interfaces, enums, and traits, along with their members, and inheritance.
+//! - `function_likes`: the functions, closures, and arrow functions.
+//! - `constants`: the constants, whether declared with `const`, or `define()`.
+//! - `class_like_aliases`: the aliases of class-likes, declared with `class_alias()`.
+//!
+//! Each reflection is written as its structure in the `mago-reflection` crate, with the interned strings, such as
+//! the names, written as the strings they represent. Locations are spans, from a start to an end position, each of
+//! which is a pair of the source, itself a pair of the name of the file and of whether it is user-defined, and of
+//! an offset in bytes.
+//!
+//! The same codebase is always written the same way: the reflections are sorted by their names, and by their
+//! locations, the keys of objects are sorted, and so are the arrays of the fields holding sets.
+
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use mago_interner::ThreadedInterner;
+use mago_php_version::PHPVersion;
+use mago_reflection::class_like::alias::ClassLikeAliasReflection;
+use mago_reflection::class_like::ClassLikeReflection;
+use mago_reflection::constant::ConstantReflection;
+use mago_reflection::function_like::FunctionLikeReflection;
+use mago_reflection::CodebaseReflection;
+use mago_span::Span;
+
+use crate::consts::VERSION;
+use crate::error::Error;
+
+/// The value of the `format` field of the documents.
+const FORMAT: &str = "mago-codebase";
+
+/// The version of the format, incremented whenever the layout of the documents changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// The fields of the reflections holding sets, whose arrays are sorted, as the order of sets is unspecified.
+const SET_FIELDS: [&str; 10] = [
+    "direct_implemented_interfaces",
+    "all_implemented_interfaces",
+    "all_extended_classes",
+    "direct_extended_interfaces",
+    "all_extended_interfaces",
+    "require_implementations",
+    "require_extensions",
+    "children",
+    "used_traits",
+    "overriden_members",
+];
+
+/// The fields identifying a document, read before the reflections, so that the documents written in another
+/// version of the format are reported as such, instead of failing to be read.
+#[derive(Debug, Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+    mago_version: String,
+    php_version: PHPVersion,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Document {
+    format: String,
+    version: u32,
+    mago_version: String,
+    php_version: PHPVersion,
+    class_likes: Vec<ClassLikeReflection>,
+    function_likes: Vec<FunctionLikeReflection>,
+    constants: Vec<ConstantReflection>,
+    class_like_aliases: Vec<ClassLikeAliasReflection>,
+}
+
+/// Writes the reflection of the given codebase to the file at the given path, creating missing directories.
+pub fn export(
+    interner: &ThreadedInterner,
+    codebase: &CodebaseReflection,
+    php_version: PHPVersion,
+    path: &Path,
+) -> Result<(), Error> {
+    let document = encode(interner, codebase, php_version).map_err(|error| Error::Reflection(path.into(), error))?;
+
+    if let Some(directory) = path.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+        std::fs::create_dir_all(directory).map_err(|error| Error::Reflection(path.into(), error.to_string()))?;
+    }
+
+    std::fs::write(path, document).map_err(|error| Error::Reflection(path.into(), error.to_string()))
+}
+
+/// Reads the reflection written to the file at the given path, for the given version of PHP.
+///
+/// Only the declarations of the external sources are imported, as those of the user-defined sources are
+/// reflected again, along with the changes made to them since the reflection was written.
+pub fn import(interner: &ThreadedInterner, path: &Path, php_version: PHPVersion) -> Result<CodebaseReflection, Error> {
+    let document = std::fs::read_to_string(path).map_err(|error| Error::Reflection(path.into(), error.to_string()))?;
+
+    decode(interner, &document, php_version).map_err(|error| Error::Reflection(path.into(), error))
+}
+
+/// Encodes the reflection of the given codebase as a document.
+fn encode(
+    interner: &ThreadedInterner,
+    codebase: &CodebaseReflection,
+    php_version: PHPVersion,
+) -> Result<String, String> {
+    let key = |name: String, span: Span| (name, interner.lookup(&span.start.source.0).to_string(), span.start.offset);
+
+    let mut class_likes = codebase.class_like_reflections.values().cloned().collect::<Vec<_>>();
+    class_likes.sort_by_cached_key(|class_like| key(class_like.name.get_key(interner), class_like.span));
+    let mut function_likes = codebase.function_like_reflections.values().cloned().collect::<Vec<_>>();
+    function_likes.sort_by_cached_key(|function_like| key(function_like.name.get_key(interner), function_like.span));
+    let mut constants = codebase.constant_reflections.values().cloned().collect::<Vec<_>>();
+    constants.sort_by_cached_key(|constant| key(interner.lookup(&constant.name.value).to_string(), constant.item_span));
+    let mut class_like_aliases = codebase.class_like_aliases.values().cloned().collect::<Vec<_>>();
+    class_like_aliases.sort_by_cached_key(|alias| key(interner.lookup(&alias.name.value).to_string(), alias.span));
+
+    let document = Document {
+        format: FORMAT.to_string(),
+        version: FORMAT_VERSION,
+        mago_version: VERSION.to_string(),
+        php_version,
+        class_likes,
+        function_likes,
+        constants,
+        class_like_aliases,
+    };
+
+    // The keys of the objects of a value are sorted, unlike those of the maps of the reflections.
+    let mut value =
+        interner.with_resolved_serialization(|| serde_json::to_value(&document)).map_err(|error| error.to_string())?;
+    sort_sets(&mut value);
+
+    serde_json::to_string_pretty(&value).map_err(|error| error.to_string())
+}
+
+/// Decodes the declarations of the external sources from the given document, which must have been written
+/// for the given version of PHP.
+fn decode(interner: &ThreadedInterner, document: &str, php_version: PHPVersion) -> Result<CodebaseReflection, String> {
+    let header: Header = serde_json::from_str(document).map_err(|error| error.to_string())?;
+    if header.format != FORMAT || header.version != FORMAT_VERSION || header.mago_version != VERSION {
+        return Err(format!(
+            "the reflection was written in version {} of the `{}` format by mago {}, expected version {} of the `{}` format by mago {}; write it again with `mago reflect`",
+            header.version, header.format, header.mago_version, FORMAT_VERSION, FORMAT, VERSION
+        ));
+    }
+
+    if header.php_version != php_version {
+        return Err(format!(
+            "the reflection was written for PHP {}, but PHP {} is targeted; write it again with `mago reflect`",
+            header.php_version, php_version
+        ));
+    }
+
+    let document: Document =
+        interner.with_resolved_deserialization(|| serde_json::from_str(document)).map_err(|error| error.to_string())?;
+
+    let mut codebase = CodebaseReflection::new();
+    for class_like in document.class_likes {
+        if !class_like.span.start.source.is_user_defined() {
+            codebase.register_class_like(interner, class_like);
+        }
+    }
+
+    for function_like in document.function_likes {
+        if !function_like.span.start.source.is_user_defined() {
+            codebase.register_function_like(interner, function_like);
+        }
+    }
+
+    for constant in document.constants {
+        if !constant.item_span.start.source.is_user_defined() {
+            codebase.register_constant(interner, constant);
+        }
+    }
+
+    for alias in document.class_like_aliases {
+        if !alias.span.start.source.is_user_defined() {
+            codebase.register_class_like_alias(interner, alias);
+        }
+    }
+
+    Ok(codebase)
+}
+
+/// Sorts the arrays of the fields holding sets within the given value, and within the objects of the
+/// `overriden_members` field, which map each member to the set of class-likes it overrides.
+fn sort_sets(value: &mut Value) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(sort_sets),
+        Value::Object(fields) => {
+            for (field, value) in fields.iter_mut() {
+                if SET_FIELDS.contains(&field.as_str()) {
+                    match value {
+                        Value::Array(values) => sort_values(values),
+                        Value::Object(sets) => sets.values_mut().for_each(|set| {
+                            if let Value::Array(values) = set {
+                                sort_values(values);
+                            }
+                        }),
+                        _ => {}
+                    }
+                }
+
+                sort_sets(value);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn sort_values(values: &mut [Value]) {
+    values.sort_by_cached_key(Value::to_string);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mago_parser::parse_source;
+    use mago_source::SourceManager;
+
+    fn codebase(
+        interner: &ThreadedInterner,
+        manager: &SourceManager,
+        sources: &[(&str, &str, bool)],
+    ) -> CodebaseReflection {
+        let mut codebase = CodebaseReflection::new();
+        for (name, content, user_defined) in sources {
+            let source_id = manager.insert_content(name.to_string(), content.to_string(), *user_defined);
+            let source = manager.load(&source_id).unwrap();
+            let (program, _) = parse_source(interner, &source);
+            let names = mago_names::Names::resolve(interner, &program);
+
+            codebase =
+                mago_reflector::merge(interner, codebase, mago_reflector::reflect(interner, &source, &program, &names));
+        }
+
+        mago_reflector::populate(interner, &mut codebase);
+
+        codebase
+    }
+
+    #[test]
+    fn test_reflection_is_written_deterministically() {
+        let sources = [
+            ("vendor/lib.php", "<?php interface A {} interface B {} interface C {} class Base implements C, A, B { public function run(int $a): ?string { return null; } } const LIB = 1;", false),
+            ("src/app.php", "<?php final class App extends Base { public function run(int $a): string { return ''; } }", true),
+        ];
+
+        let interner = ThreadedInterner::new();
+        let first =
+            encode(&interner, &codebase(&interner, &SourceManager::new(interner.clone()), &sources), PHPVersion::PHP83)
+                .unwrap();
+
+        // The strings are interned in another order, and the maps are filled in another order.
+        let interner = ThreadedInterner::new();
+        interner.intern("run");
+        interner.intern("C");
+        let mut reversed = sources;
+        reversed.reverse();
+        let second = encode(
+            &interner,
+            &codebase(&interner, &SourceManager::new(interner.clone()), &reversed),
+            PHPVersion::PHP83,
+        )
+        .unwrap();
+
+        assert_eq!(first, second);
+
+        let document: Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(document["format"], "mago-codebase");
+        assert_eq!(document["php_version"], "8.3");
+        assert_eq!(document["class_likes"].as_array().unwrap().len(), 5);
+        assert_eq!(document["constants"][0]["name"]["value"], "LIB");
+
+        let base = &document["class_likes"][3];
+        assert_eq!(base["name"]["Class"]["value"], "Base");
+        // The names in sets are sorted by their serialized form, in which their spans come first.
+        let interfaces = base["inheritance"]["all_implemented_interfaces"].as_array().unwrap();
+        assert_eq!(
+            interfaces.iter().map(|interface| interface["value"].as_str().unwrap()).collect::<Vec<_>>(),
+            ["C", "A", "B"]
+        );
+    }
+
+    #[test]
+    fn test_external_declarations_are_imported() {
+        let interner = ThreadedInterner::new();
+        let sources = [
+            ("vendor/lib.php", "<?php class Base {} function helper(string $value): int { return 1; }", false),
+            ("src/app.php", "<?php class App extends Base {}", true),
+        ];
+        let document =
+            encode(&interner, &codebase(&interner, &SourceManager::new(interner.clone()), &sources), PHPVersion::PHP83)
+                .unwrap();
+
+        let interner = ThreadedInterner::new();
+        let imported = decode(&interner, &document, PHPVersion::PHP83).unwrap();
+
+        assert!(imported.class_exists(&interner, &interner.intern("Base")));
+        assert!(!imported.class_exists(&interner, &interner.intern("App")));
+        let helper = imported.get_function(&interner, &interner.intern("helper")).unwrap();
+        assert_eq!(interner.lookup(&helper.parameters[0].name), "$value");
+        assert_eq!(interner.lookup(&helper.span.start.source.0), "vendor/lib.php");
+
+        let error = decode(&interner, &document, PHPVersion::PHP84).unwrap_err();
+        assert!(error.contains("PHP 8.3"));
+        assert!(decode(&interner, &document.replace("\"version\": 1", "\"version\": 0"), PHPVersion::PHP83).is_err());
+    }
+}
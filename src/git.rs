@@ -0,0 +1,122 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::error::Error;
+
+/// Returns the files that were added, copied, modified, or renamed compared to the given reference,
+/// along with the untracked files that are not ignored.
+///
+/// # Arguments
+///
+/// * `directory` - A directory inside the git working tree.
+/// * `reference` - The git reference to compare the working tree against, e.g. `HEAD` or `origin/main`.
+///
+/// # Returns
+///
+/// The canonical paths of the changed files that still exist, or an error if the directory is not
+/// part of a git repository, or if git fails.
+pub fn changed_files(directory: &Path, reference: &str) -> Result<Vec<PathBuf>, Error> {
+    let toplevel = git(directory, &["rev-parse", "--show-toplevel"])
+        .map_err(|_| Error::Git(format!("`{}` is not inside a git repository", directory.display())))?;
+    let toplevel = PathBuf::from(toplevel.trim());
+
+    let modified = git(&toplevel, &["diff", "--name-only", "--diff-filter=ACMR", reference, "--"])?;
+    let untracked = git(&toplevel, &["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+
+    let mut files: Vec<PathBuf> = modified
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| toplevel.join(line).canonicalize().ok())
+        .filter(|path| path.is_file())
+        .collect();
+
+    files.sort();
+    files.dedup();
+
+    Ok(files)
+}
+
+/// Runs git with the given arguments in the given directory, returning its standard output.
+fn git(directory: &Path, arguments: &[&str]) -> Result<String, Error> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(arguments)
+        .output()
+        .map_err(|error| Error::Git(format!("failed to run git: {}", error)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        return Err(Error::Git(format!("`git {}` failed: {}", arguments.join(" "), stderr.trim())));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repository(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("mago-git-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+
+        directory.canonicalize().unwrap()
+    }
+
+    fn write(directory: &Path, path: &str, content: &str) {
+        let path = directory.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_changed_files_include_modified_and_untracked_files() {
+        let directory = repository("changed");
+        for (path, content) in [
+            (".gitignore", "ignored.php\n"),
+            ("src/Modified.php", "<?php"),
+            ("src/Unchanged.php", "<?php"),
+            ("src/Deleted.php", "<?php"),
+        ] {
+            write(&directory, path, content);
+        }
+
+        git(&directory, &["init", "--quiet"]).unwrap();
+        git(&directory, &["add", "--all"]).unwrap();
+        git(
+            &directory,
+            &["-c", "user.name=mago", "-c", "user.email=mago@localhost", "commit", "--quiet", "-m", "init"],
+        )
+        .unwrap();
+
+        write(&directory, "src/Modified.php", "<?php echo 1;");
+        write(&directory, "src/New.php", "<?php");
+        write(&directory, "ignored.php", "<?php");
+        std::fs::remove_file(directory.join("src/Deleted.php")).unwrap();
+
+        assert_eq!(
+            changed_files(&directory.join("src"), "HEAD").unwrap(),
+            vec![directory.join("src/Modified.php"), directory.join("src/New.php")]
+        );
+
+        let error = changed_files(&directory, "not-a-reference").unwrap_err();
+        assert!(matches!(error, Error::Git(message) if message.contains("not-a-reference")));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_changed_files_outside_of_a_repository_are_rejected() {
+        let directory = repository("outside");
+        let error = changed_files(&directory, "HEAD").unwrap_err();
+
+        assert!(matches!(error, Error::Git(message) if message.contains("is not inside a git repository")));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
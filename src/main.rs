@@ -5,26 +5,48 @@ use tokio::runtime::Builder;
 
 use mago_feedback::initialize_logger;
 use mago_feedback::LevelFilter;
+use mago_reporting::reporter::ReportingTarget;
 
+use crate::commands::MagoArguments;
 use crate::commands::MagoCommand;
 use crate::config::Configuration;
 use crate::error::Error;
 
+mod baseline;
+mod cache;
+mod color;
 mod commands;
+mod composer;
 mod config;
 mod consts;
 mod error;
+mod export;
+mod git;
 mod macros;
 mod reflection;
 mod source;
 mod utils;
 
 pub fn main() -> Result<ExitCode, Error> {
+    let arguments = MagoArguments::parse();
+    let color = arguments.color;
+
     // Set up the logger.
-    initialize_logger(if cfg!(debug_assertions) { LevelFilter::DEBUG } else { LevelFilter::INFO }, "MAGO_LOG");
+    initialize_logger(
+        if cfg!(debug_assertions) { LevelFilter::DEBUG } else { LevelFilter::INFO },
+        "MAGO_LOG",
+        color.use_colors(ReportingTarget::Stderr),
+    );
 
     // Load the configuration.
-    let configuration = Configuration::load()?;
+    let mut configuration = Configuration::load()?;
+    if let Some(php_version) = arguments.php_version {
+        configuration.php_version = php_version;
+    }
+
+    if arguments.no_cache {
+        configuration.cache.enabled = false;
+    }
 
     // Create the runtime.
     let runtime = if configuration.threads <= 1 {
@@ -38,11 +60,16 @@ pub fn main() -> Result<ExitCode, Error> {
             .map_err(Error::BuildingRuntime)?
     };
 
-    match MagoCommand::parse() {
-        MagoCommand::Lint(cmd) => runtime.block_on(commands::lint::execute(cmd, configuration)),
-        MagoCommand::Fix(cmd) => runtime.block_on(commands::fix::execute(cmd, configuration)),
-        MagoCommand::Format(cmd) => runtime.block_on(commands::format::execute(cmd, configuration)),
-        MagoCommand::Ast(cmd) => runtime.block_on(commands::ast::execute(cmd)),
+    match arguments.command {
+        MagoCommand::Lint(cmd) => runtime.block_on(commands::lint::execute(cmd, configuration, color)),
+        MagoCommand::Fix(cmd) => runtime.block_on(commands::fix::execute(cmd, configuration, color)),
+        MagoCommand::Format(cmd) => runtime.block_on(commands::format::execute(cmd, configuration, color)),
+        MagoCommand::Ast(cmd) => runtime.block_on(commands::ast::execute(cmd, color)),
+        MagoCommand::Tokens(cmd) => runtime.block_on(commands::tokens::execute(cmd, color)),
         MagoCommand::SelfUpdate(cmd) => commands::self_update::execute(cmd),
+        MagoCommand::Cache(cmd) => commands::cache::execute(cmd, configuration),
+        MagoCommand::Find(cmd) => runtime.block_on(commands::find::execute(cmd, configuration, color)),
+        MagoCommand::Reflect(cmd) => runtime.block_on(commands::reflect::execute(cmd, configuration)),
+        MagoCommand::References(cmd) => runtime.block_on(commands::references::execute(cmd, configuration, color)),
     }
 }
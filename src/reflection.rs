@@ -1,21 +1,86 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use ahash::HashSet;
+
+use crate::cache::ReflectionCache;
+use crate::config::source::SourceConfiguration;
+use crate::consts::PHP_STUBS;
+use crate::consts::PHP_STUB_REFLECTIONS;
 use crate::error::Error;
+use crate::export;
 use mago_interner::ThreadedInterner;
 use mago_names::Names;
-use mago_parser::parse_source;
+use mago_parser::parse_source_with_settings;
+use mago_parser::settings::ParserSettings;
+use mago_php_version::PHPVersion;
 use mago_reflection::CodebaseReflection;
 use mago_source::SourceIdentifier;
 use mago_source::SourceManager;
 
+/// How the reflection of the external sources, such as the dependencies, and the stubs, is obtained.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalReflection {
+    /// The external sources are not reflected, disabling the rules relying on their reflection.
+    Skip,
+    /// The external sources are reflected, unless their reflections are cached.
+    Reflect,
+    /// The reflection written by `mago reflect` to the given file is imported, instead of reflecting the
+    /// external sources.
+    Import(PathBuf),
+}
+
+impl ExternalReflection {
+    /// Creates the way the external sources are reflected, importing their reflection from the given file, if any.
+    pub fn new(reflect: bool, import: Option<&Path>) -> Self {
+        match import {
+            _ if !reflect => Self::Skip,
+            Some(path) => Self::Import(path.to_path_buf()),
+            None => Self::Reflect,
+        }
+    }
+
+    /// Determines whether the external sources are available, either reflected, or imported.
+    pub fn is_available(&self) -> bool {
+        !matches!(self, Self::Skip)
+    }
+
+    /// Returns the reflection of the external sources, which is empty if they are skipped.
+    pub async fn load(
+        &self,
+        interner: &ThreadedInterner,
+        manager: &SourceManager,
+        configuration: &SourceConfiguration,
+        php_version: PHPVersion,
+        cache: &ReflectionCache,
+    ) -> Result<CodebaseReflection, Error> {
+        match self {
+            Self::Skip => Ok(CodebaseReflection::new()),
+            Self::Reflect => reflect_all_external_sources(interner, manager, configuration, php_version, cache).await,
+            Self::Import(path) => export::import(interner, path, php_version),
+        }
+    }
+}
+
 /// Creates a reflection of all external sources managed by the `SourceManager`.
 ///
 /// This function processes all external sources concurrently.
 ///
-/// Each source is parsed, and reflected to generate a comprehensive `CodebaseReflection`.
+/// Each source is parsed, and reflected to generate a comprehensive `CodebaseReflection`, as it applies to
+/// the targeted version of PHP, so that the stubs declaring elements for some versions only are honored.
+///
+/// The bundled stubs, when used, are not parsed: their reflections, generated at build time, are loaded
+/// instead, before merging the reflections of the other sources.
+///
+/// The reflections of the other sources are loaded from the cache when their content did not change.
 ///
 /// # Arguments
 ///
 /// - `interner`: A `ThreadedInterner` instance used for symbol interning across multiple threads.
 /// - `manager`: A `SourceManager` that provides access to external sources.
+/// - `configuration`: The configuration of the sources, such as whether the bundled stubs are used.
+/// - `php_version`: The targeted version of PHP.
+/// - `cache`: The cache of the reflections of the sources.
 ///
 /// # Returns
 ///
@@ -27,24 +92,90 @@ use mago_source::SourceManager;
 pub async fn reflect_all_external_sources(
     interner: &ThreadedInterner,
     manager: &SourceManager,
+    configuration: &SourceConfiguration,
+    php_version: PHPVersion,
+    cache: &ReflectionCache,
+) -> Result<CodebaseReflection, Error> {
+    let settings = configuration.parser_settings();
+
+    let mut combined_reflection = CodebaseReflection::new();
+    let mut bundled_stubs = HashSet::default();
+    if configuration.uses_builtin_stubs() {
+        let stubs = mago_reflector::stubs::decode(interner, PHP_STUB_REFLECTIONS)?;
+
+        combined_reflection = mago_reflector::stubs::select(interner, stubs, php_version);
+        bundled_stubs.extend(PHP_STUBS.iter().map(|(stub, _)| *stub));
+    }
+
+    // Collect all external source identifiers managed by the SourceManager, except for the bundled stubs.
+    let source_ids = manager
+        .external_source_ids()
+        .filter(|source_id| !bundled_stubs.contains(interner.lookup(&source_id.0)))
+        .collect::<Vec<_>>();
+
+    reflect_sources(interner, manager, cache, source_ids, settings, php_version, combined_reflection).await
+}
+
+/// Creates a reflection of all user-defined sources managed by the `SourceManager`.
+///
+/// This function processes all user-defined sources concurrently, loading their reflections from the cache
+/// when their content did not change.
+///
+/// # Arguments
+///
+/// - `interner`: A `ThreadedInterner` instance used for symbol interning across multiple threads.
+/// - `manager`: A `SourceManager` that provides access to user-defined sources.
+/// - `configuration`: The configuration of the sources.
+/// - `php_version`: The targeted version of PHP.
+/// - `cache`: The cache of the reflections of the sources.
+///
+/// # Returns
+///
+/// Returns a `CodebaseReflection` representing the combined reflection of all user-defined sources.
+///
+/// # Errors
+///
+/// - Returns an `Error` if any source cannot be loaded.
+pub async fn reflect_all_user_defined_sources(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    configuration: &SourceConfiguration,
+    php_version: PHPVersion,
+    cache: &ReflectionCache,
 ) -> Result<CodebaseReflection, Error> {
-    // Collect all external source identifiers managed by the SourceManager.
-    let source_ids = manager.external_source_ids().collect::<Vec<_>>();
-    let total_sources = source_ids.len();
+    let source_ids = manager.user_defined_source_ids().collect();
+    let settings = configuration.parser_settings();
+
+    reflect_sources(interner, manager, cache, source_ids, settings, php_version, CodebaseReflection::new()).await
+}
+
+/// Reflects the given sources concurrently, merging their reflections into the given one.
+async fn reflect_sources(
+    interner: &ThreadedInterner,
+    manager: &SourceManager,
+    cache: &ReflectionCache,
+    mut source_ids: Vec<SourceIdentifier>,
+    settings: ParserSettings,
+    php_version: PHPVersion,
+    mut combined_reflection: CodebaseReflection,
+) -> Result<CodebaseReflection, Error> {
+    // The reflections are merged in the order of the names of their sources, so that the same declaration
+    // is kept among duplicates, no matter the order in which the sources were discovered.
+    source_ids.sort_by_cached_key(|source_id| interner.lookup(&source_id.0).to_string());
 
     // Create a vector to hold the async tasks for reflecting each source.
-    let mut reflection_tasks = Vec::with_capacity(total_sources);
+    let mut reflection_tasks = Vec::with_capacity(source_ids.len());
     for source_id in source_ids {
         reflection_tasks.push(tokio::spawn({
             let interner = interner.clone();
             let manager = manager.clone();
+            let cache = cache.clone();
 
-            async move { reflect_single_source(&interner, &manager, &source_id) }
+            async move { reflect_single_source(&interner, &manager, &cache, &source_id, settings, php_version) }
         }));
     }
 
     // Combine individual reflections into a unified `CodebaseReflection`.
-    let mut combined_reflection = CodebaseReflection::new();
     for task in reflection_tasks {
         let source_reflection = task.await??; // Await task completion and handle errors.
         combined_reflection = mago_reflector::merge(interner, combined_reflection, source_reflection);
@@ -56,13 +187,16 @@ pub async fn reflect_all_external_sources(
 /// Reflects a single source into a `CodebaseReflection`.
 ///
 /// This function loads the specified source, parses its content, resolves its names, and performs
-/// reflection to generate a `CodebaseReflection` for the given source.
+/// reflection to generate a `CodebaseReflection` for the given source, unless its reflection is cached.
 ///
 /// # Arguments
 ///
 /// - `interner`: A `ThreadedInterner` instance used for symbol interning.
 /// - `manager`: A `SourceManager` to load the source code.
+/// - `cache`: The cache of the reflections of the sources.
 /// - `source_id`: The identifier of the source to reflect.
+/// - `settings`: The settings used to parse the source.
+/// - `php_version`: The targeted version of PHP.
 ///
 /// # Returns
 ///
@@ -74,17 +208,136 @@ pub async fn reflect_all_external_sources(
 fn reflect_single_source(
     interner: &ThreadedInterner,
     manager: &SourceManager,
+    cache: &ReflectionCache,
     source_id: &SourceIdentifier,
+    settings: ParserSettings,
+    php_version: PHPVersion,
 ) -> Result<CodebaseReflection, Error> {
     // Load the source code using the SourceManager.
     let source = manager.load(source_id)?;
 
-    // Parse the source code into an intermediate representation (program).
-    let (program, _) = parse_source(interner, &source);
+    Ok(cache.get_or_reflect(interner, &source, php_version, settings, || {
+        // Parse the source code into an intermediate representation (program).
+        let (program, _) = parse_source_with_settings(interner, &source, settings);
+
+        // Resolve names and symbols within the program.
+        let names = Names::resolve(interner, &program);
+
+        // Reflect the source into a `CodebaseReflection`.
+        mago_reflector::reflect_for_version(interner, &source, &program, &names, php_version)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::cache::CacheConfiguration;
 
-    // Resolve names and symbols within the program.
-    let names = Names::resolve(interner, &program);
+    fn parameters(codebase: &CodebaseReflection, interner: &ThreadedInterner, function: &str) -> Vec<String> {
+        let function = codebase.get_function(interner, &interner.intern(function)).unwrap();
+
+        function.parameters.iter().map(|parameter| interner.lookup(&parameter.name).to_string()).collect()
+    }
 
-    // Reflect the source into a `CodebaseReflection`.
-    Ok(mago_reflector::reflect(interner, &source, &program, &names))
+    #[test]
+    fn test_bundled_stubs_are_selected_for_the_php_version() {
+        let interner = ThreadedInterner::new();
+        let stubs = mago_reflector::stubs::decode(&interner, PHP_STUB_REFLECTIONS).unwrap();
+
+        let codebase = mago_reflector::stubs::select(&interner, stubs.clone(), PHPVersion::PHP82);
+        assert_eq!(parameters(&codebase, &interner, "strrchr"), vec!["$haystack", "$needle"]);
+
+        let codebase = mago_reflector::stubs::select(&interner, stubs, PHPVersion::PHP83);
+        assert_eq!(parameters(&codebase, &interner, "strrchr"), vec!["$haystack", "$needle", "$before_needle"]);
+
+        // The declarations refer to the bundled stubs, which are loaded under the same names.
+        let strlen = codebase.get_function(&interner, &interner.intern("strlen")).unwrap();
+        let source = interner.lookup(&strlen.span.start.source.0);
+        assert!(strlen.span.start.source.is_external());
+        assert!(PHP_STUBS.iter().any(|(stub, _)| *stub == source));
+    }
+
+    #[test]
+    fn test_external_sources_are_reflected_for_the_php_version() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content(
+            "stubs/example.php".to_string(),
+            r#"<?php
+                use JetBrains\PhpStorm\Internal\PhpStormStubsElementAvailable;
+
+                function example(int $a, #[PhpStormStubsElementAvailable(from: '8.1')] int $b) {}
+
+                #[PhpStormStubsElementAvailable(to: '8.0')]
+                function legacy(string $a) {}
+
+                #[PhpStormStubsElementAvailable('8.1')]
+                function legacy(string $a, string $b) {}
+            "#
+            .to_string(),
+            false,
+        );
+
+        let settings = ParserSettings::default();
+        let cache = ReflectionCache::new(&CacheConfiguration { enabled: false, ..Default::default() });
+        let codebase =
+            reflect_single_source(&interner, &manager, &cache, &source, settings, PHPVersion::PHP80).unwrap();
+        assert_eq!(parameters(&codebase, &interner, "example"), vec!["$a"]);
+        assert_eq!(parameters(&codebase, &interner, "legacy"), vec!["$a"]);
+
+        let codebase =
+            reflect_single_source(&interner, &manager, &cache, &source, settings, PHPVersion::PHP81).unwrap();
+        assert_eq!(parameters(&codebase, &interner, "example"), vec!["$a", "$b"]);
+        assert_eq!(parameters(&codebase, &interner, "legacy"), vec!["$a", "$b"]);
+    }
+
+    #[test]
+    fn test_dynamically_declared_symbols_are_reflected() {
+        let interner = ThreadedInterner::new();
+        let manager = SourceManager::new(interner.clone());
+        let source = manager.insert_content(
+            "bootstrap.php".to_string(),
+            r#"<?php
+                namespace App;
+
+                class Kernel {}
+
+                define('APP_ROOT', __DIR__);
+                \define('App\APP_ENV', 'prod');
+                class_alias(Kernel::class, 'LegacyKernel');
+
+                if (!defined('APP_DEBUG')) {
+                    define('APP_DEBUG', false);
+                    class_alias('LegacyKernel', 'App\OldKernel');
+                }
+
+                define($name, 1);
+                class_alias($original, 'Unknown');
+                class_alias('Missing', 'Dangling');
+            "#
+            .to_string(),
+            false,
+        );
+
+        let settings = ParserSettings::default();
+        let cache = ReflectionCache::new(&CacheConfiguration { enabled: false, ..Default::default() });
+        let mut codebase =
+            reflect_single_source(&interner, &manager, &cache, &source, settings, PHPVersion::PHP84).unwrap();
+        mago_reflector::populate(&interner, &mut codebase);
+
+        assert!(codebase.constant_exists(&interner, &interner.intern("APP_ROOT")));
+        assert!(codebase.constant_exists(&interner, &interner.intern("App\\APP_ENV")));
+        let debug = codebase.get_constant(&interner, &interner.intern("APP_DEBUG")).unwrap();
+        assert!(debug.is_conditionally_declared);
+        assert_eq!(codebase.constant_reflections.len(), 3);
+
+        let kernel = codebase.get_named_class_like(&interner, &interner.intern("App\\Kernel")).unwrap();
+        for alias in ["LegacyKernel", "app\\oldkernel"] {
+            assert_eq!(codebase.get_named_class_like(&interner, &interner.intern(alias)), Some(kernel));
+        }
+
+        assert!(codebase.get_named_class_like(&interner, &interner.intern("Unknown")).is_none());
+        assert!(codebase.get_named_class_like(&interner, &interner.intern("Dangling")).is_none());
+    }
 }
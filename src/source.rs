@@ -2,14 +2,17 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use ahash::HashSet;
-use async_walkdir::Filtering;
-use async_walkdir::WalkDir;
-use futures::StreamExt;
+use ignore::WalkBuilder;
 
 use mago_interner::ThreadedInterner;
+use mago_source::SourceIdentifier;
 use mago_source::SourceManager;
 
+use crate::composer;
 use crate::config::source::SourceConfiguration;
+use crate::config::source::SourcePath;
+use crate::consts::CURRENT_DIR;
+use crate::consts::IGNORE_FILE;
 use crate::consts::PHP_STUBS;
 use crate::error::Error;
 
@@ -20,7 +23,11 @@ use crate::error::Error;
 ///
 /// * `interner` - The interner to use for string interning.
 /// * `configuration` - The configuration to use for loading the sources.
-/// * `include_stubs` - Whether to include stubs in the source manager.
+/// * `include_stubs` - Whether to include stubs in the source manager, either the bundled ones, or those of
+///   the configured stubs directory.
+/// * `selection` - Absolute paths of the files and directories to analyze, or an empty slice to
+///   analyze all user defined sources. The other sources are still loaded, but not as user defined,
+///   so that they remain available for reflection.
 ///
 /// # Returns
 ///
@@ -30,109 +37,272 @@ pub async fn load(
     interner: &ThreadedInterner,
     configuration: &SourceConfiguration,
     include_stubs: bool,
+    selection: &[PathBuf],
 ) -> Result<SourceManager, Error> {
-    let SourceConfiguration { root, paths, includes, excludes, extensions } = configuration;
+    load_sources(interner, configuration, include_stubs, selection, false).await
+}
 
-    let mut starting_paths = Vec::new();
+/// Load the source manager with a single user defined source, that is not backed by a file.
+///
+/// The project sources are loaded as external sources, so that they remain available for reflection,
+/// except for the one with the same name as the virtual source, which it replaces.
+///
+/// # Arguments
+///
+/// * `interner` - The interner to use for string interning.
+/// * `configuration` - The configuration to use for loading the sources.
+/// * `name` - The name of the virtual source, relative to the source root.
+/// * `content` - The content of the virtual source.
+///
+/// # Returns
+///
+/// The new source manager, along with the identifier of the virtual source.
+pub async fn load_virtual(
+    interner: &ThreadedInterner,
+    configuration: &SourceConfiguration,
+    name: String,
+    content: String,
+) -> Result<(SourceManager, SourceIdentifier), Error> {
+    let manager = load_sources(interner, configuration, true, &[], true).await?;
+    manager.remove(&SourceIdentifier(interner.intern(&name), false));
 
-    if paths.is_empty() {
-        starting_paths.push((root.clone(), true));
-    } else {
-        for source in paths {
-            starting_paths.push((source.clone(), true));
-        }
-    }
+    let source = manager.insert_content(name, content, true);
 
-    for include in includes {
-        starting_paths.push((include.clone(), false));
-    }
+    Ok((manager, source))
+}
 
-    if paths.is_empty() && includes.is_empty() {
-        starting_paths.push((root.clone(), true));
-    }
+async fn load_sources(
+    interner: &ThreadedInterner,
+    configuration: &SourceConfiguration,
+    include_stubs: bool,
+    selection: &[PathBuf],
+    all_external: bool,
+) -> Result<SourceManager, Error> {
+    let SourceConfiguration { root, extensions, stubs, .. } = configuration;
 
-    let excludes_set: HashSet<Exclusion> = excludes
-        .iter()
-        .map(|exclude| {
-            // if it contains a wildcard, treat it as a pattern
-            if exclude.contains('*') {
-                Exclusion::Pattern(exclude.clone())
-            } else {
-                let path = Path::new(exclude);
+    let mut sources = Sources::new(configuration);
 
-                if path.is_absolute() {
-                    Exclusion::Path(path.to_path_buf())
-                } else {
-                    Exclusion::Path(root.join(path))
-                }
-            }
-        })
-        .collect();
+    // Selected paths outside of the configured sources are scanned on their own.
+    let mut explicit_selection = vec![];
+    for selected in selection {
+        if sources.overlaps(selected) {
+            explicit_selection.push(selected);
+        } else {
+            tracing::warn!(
+                "`{}` is outside of the configured source paths, project-level reflection may be incomplete.",
+                selected.display()
+            );
 
-    let extensions: HashSet<&String> = extensions.iter().collect();
+            sources.paths.push(SourcePath::path(selected.clone()));
+        }
+    }
 
+    let extensions: HashSet<&String> = extensions.iter().collect();
+    let ignore_files = IgnoreFiles::of(configuration);
     let manager = SourceManager::new(interner.clone());
-    for (path, user_defined) in starting_paths.into_iter() {
-        let mut entries = WalkDir::new(path)
-            // filter out .git directories
-            .filter(|entry| async move {
-                if entry.path().starts_with(".") {
-                    Filtering::IgnoreDir
-                } else {
-                    Filtering::Continue
-                }
-            });
+    let mut collected = HashSet::default();
+    let mut discovered = 0;
+    for (path, user_defined, is_discovered) in sources.starting_paths() {
+        let ignore_files = if user_defined { ignore_files } else { IgnoreFiles::None };
 
-        // Check for errors after processing all entries in the current path
-        while let Some(entry) = entries.next().await {
-            let path = entry?.path();
-            if !path.is_file() {
+        for path in collect_files(path, ignore_files).await? {
+            // Skip the files that are not part of the sources, or that are user defined, but were not found
+            // when scanning the user defined sources, as they are ignored.
+            let Some(is_user_defined) =
+                sources.classify(&path).filter(|is_user_defined| user_defined || !is_user_defined)
+            else {
                 continue;
-            }
+            };
 
-            // Skip user-defined sources if they are included in the `includes` list.
-            if user_defined && includes.iter().any(|include| path.starts_with(include)) {
+            // Skip excluded files and directories, and files that do not have an accepted extension.
+            if sources.is_excluded(&path) || !is_accepted_file(&path, &extensions) || !collected.insert(path.clone()) {
                 continue;
             }
 
-            // Skip excluded files and directories.
-            if is_excluded(&path, &excludes_set) {
-                continue;
+            let name = source_name(root, &path);
+            let selected =
+                !all_external && (selection.is_empty() || selection.iter().any(|selected| path.starts_with(selected)));
+
+            if is_discovered && !is_user_defined {
+                discovered += 1;
             }
 
-            // Skip files that do not have an accepted extension.
-            if !is_accepted_file(&path, &extensions) {
+            manager.insert_path(name, path, is_user_defined && selected);
+        }
+    }
+
+    if discovered > 0 {
+        tracing::debug!("discovered {} external files autoloaded by the installed composer packages", discovered);
+    }
+
+    // The selected paths that are ignored are still analyzed, as they were given explicitly.
+    for selected in explicit_selection {
+        let mut ignored = 0;
+        for path in collect_files(selected.clone(), ignore_files).await? {
+            if sources.classify(&path) != Some(true)
+                || sources.is_excluded(&path)
+                || !is_accepted_file(&path, &extensions)
+                || !collected.insert(path.clone())
+            {
                 continue;
             }
 
-            let name = match path.strip_prefix(root) {
-                Ok(rel_path) => rel_path.display().to_string(),
-                Err(_) => path.display().to_string(),
-            };
+            manager.insert_path(source_name(root, &path), path, !all_external);
+            ignored += 1;
+        }
 
-            manager.insert_path(name, path.clone(), user_defined);
+        if ignored > 0 {
+            tracing::info!(
+                "Analyzing {} file(s) of `{}` ignored by the ignore files, as the path was given explicitly.",
+                ignored,
+                selected.display()
+            );
         }
     }
 
     if include_stubs {
-        for (stub, content) in PHP_STUBS {
-            manager.insert_content(stub.to_owned(), content.to_owned(), false);
+        if let Some(stubs) = stubs {
+            for path in collect_files(stubs.clone(), IgnoreFiles::None).await? {
+                if is_accepted_file(&path, &extensions) {
+                    manager.insert_path(source_name(root, &path), path, false);
+                }
+            }
+        } else if configuration.use_builtin_stubs {
+            // The bundled stubs are reflected at build time, but their content is still needed to report
+            // the issues found in them, or referring to them.
+            for (stub, content) in PHP_STUBS {
+                manager.insert_content(stub.to_owned(), content.to_owned(), false);
+            }
         }
     }
 
     Ok(manager)
 }
 
-fn is_excluded(path: &Path, excludes: &HashSet<Exclusion>) -> bool {
-    for exclusion in excludes {
-        return match exclusion {
-            Exclusion::Path(p) if path.starts_with(p) => true,
-            Exclusion::Pattern(p) if glob_match::glob_match(p, path.to_string_lossy().as_ref()) => true,
-            _ => continue,
-        };
+/// Returns the names, and paths of the user defined source files, as they would be loaded by [`load`].
+///
+/// Only the user defined sources are scanned, making this suitable for repeatedly checking for changes.
+pub async fn user_defined_files(
+    configuration: &SourceConfiguration,
+    selection: &[PathBuf],
+) -> Result<Vec<(String, PathBuf)>, Error> {
+    let SourceConfiguration { root, extensions, .. } = configuration;
+
+    let mut sources = Sources::new(configuration);
+    for selected in selection {
+        if !sources.overlaps(selected) {
+            sources.paths.push(SourcePath::path(selected.clone()));
+        }
+    }
+
+    let extensions: HashSet<&String> = extensions.iter().collect();
+    let ignore_files = IgnoreFiles::of(configuration);
+
+    let starting_paths = sources.paths.iter().map(|path| path.base().to_path_buf()).collect::<Vec<_>>();
+
+    let mut files = vec![];
+    let mut collected = HashSet::default();
+    // The selected paths are collected on their own as well, so that they are included even when ignored.
+    for path in starting_paths.into_iter().chain(selection.iter().cloned()) {
+        for path in collect_files(path, ignore_files).await? {
+            if sources.classify(&path) != Some(true)
+                || sources.is_excluded(&path)
+                || !is_accepted_file(&path, &extensions)
+                || collected.contains(&path)
+            {
+                continue;
+            }
+
+            if selection.is_empty() || selection.iter().any(|selected| path.starts_with(selected)) {
+                collected.insert(path.clone());
+                files.push((source_name(root, &path), path));
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Resolves the given paths, relative to the current directory, into the canonical paths used to select sources.
+///
+/// Returns the first path that does not exist as an error.
+pub fn canonicalize_selection(paths: &[PathBuf]) -> Result<Vec<PathBuf>, &Path> {
+    paths.iter().map(|path| CURRENT_DIR.join(path).canonicalize().map_err(|_| path.as_path())).collect()
+}
+
+/// Returns whether the given absolute path is part of the user defined sources of the configuration.
+///
+/// Exclusions and extensions are not taken into account.
+pub fn is_user_defined_path(configuration: &SourceConfiguration, path: &Path) -> bool {
+    Sources::new(configuration).classify(path) == Some(true)
+}
+
+/// Returns whether the given absolute path is excluded from the user defined sources of the configuration,
+/// either because it is outside of the configured sources, or because it matches one of the exclusions.
+///
+/// Unlike [`is_user_defined_path`], the path does not need to exist.
+pub fn is_excluded_path(configuration: &SourceConfiguration, path: &Path) -> bool {
+    let sources = Sources::new(configuration);
+
+    sources.classify(path) != Some(true) || sources.is_excluded(path)
+}
+
+/// Resolves the given path, relative to the current directory, into an absolute path.
+///
+/// The path is canonicalized when it exists, or when its parent directory does, so that it can be
+/// compared with the configured paths, which are canonical.
+pub fn resolve_path(path: &Path) -> PathBuf {
+    let path = CURRENT_DIR.join(path);
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
     }
 
-    false
+    match (path.parent().map(Path::canonicalize), path.file_name()) {
+        (Some(Ok(parent)), Some(name)) => parent.join(name),
+        _ => path,
+    }
+}
+
+/// Returns the files found in the given directory, recursively, or the given path itself if it is a file.
+///
+/// The files ignored by the given ignore files are skipped, along with the `.git` directories.
+async fn collect_files(path: PathBuf, ignore_files: IgnoreFiles) -> Result<Vec<PathBuf>, Error> {
+    if path.is_file() {
+        return Ok(vec![path]);
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let mut builder = WalkBuilder::new(path);
+        builder
+            .standard_filters(false)
+            .parents(ignore_files != IgnoreFiles::None)
+            .git_ignore(ignore_files == IgnoreFiles::All)
+            .git_exclude(ignore_files == IgnoreFiles::All)
+            .filter_entry(|entry| entry.file_name() != ".git");
+
+        if ignore_files != IgnoreFiles::None {
+            builder.add_custom_ignore_filename(IGNORE_FILE);
+        }
+
+        let mut files = vec![];
+        for entry in builder.build() {
+            let path = entry?.into_path();
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+
+        Ok(files)
+    })
+    .await?
+}
+
+/// Returns the name of the source at the given path, relative to the root when possible.
+pub fn source_name(root: &Path, path: &Path) -> String {
+    match path.strip_prefix(root) {
+        Ok(rel_path) => rel_path.display().to_string(),
+        Err(_) => path.display().to_string(),
+    }
 }
 
 fn is_accepted_file(path: &Path, extensions: &HashSet<&String>) -> bool {
@@ -143,8 +313,303 @@ fn is_accepted_file(path: &Path, extensions: &HashSet<&String>) -> bool {
     }
 }
 
-#[derive(Debug, Hash, Eq, PartialEq)]
-enum Exclusion {
-    Path(PathBuf),
-    Pattern(String),
+/// The ignore files that are respected when collecting the files of a directory.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum IgnoreFiles {
+    /// No file is ignored, as for the external sources, and the stubs.
+    None,
+    /// The files ignored by the `.magoignore` files are skipped.
+    Mago,
+    /// The files ignored by the `.gitignore` files of the repository, and by the `.magoignore` files, are skipped.
+    All,
+}
+
+impl IgnoreFiles {
+    /// Returns the ignore files respected for the user defined sources of the given configuration.
+    fn of(configuration: &SourceConfiguration) -> Self {
+        if configuration.respect_gitignore {
+            IgnoreFiles::All
+        } else {
+            IgnoreFiles::Mago
+        }
+    }
+}
+
+/// The paths of the sources of a configuration, deciding which of the scanned files are user defined.
+#[derive(Debug)]
+struct Sources {
+    /// The paths of the user defined sources.
+    paths: Vec<SourcePath>,
+    /// The paths of the non-user defined sources: the configured includes, followed by the paths autoloaded by
+    /// the composer packages installed for the project, which are not already included.
+    includes: Vec<SourcePath>,
+    /// The number of configured includes, the other ones being discovered from the composer packages.
+    configured_includes: usize,
+    excludes: Vec<SourcePath>,
+    stubs: Option<PathBuf>,
+}
+
+impl Sources {
+    fn new(configuration: &SourceConfiguration) -> Self {
+        let mut includes = configuration.include_paths();
+        let configured_includes = includes.len();
+        for path in composer::discover_autoload_paths(&configuration.root, configuration.include_dev_dependencies) {
+            if !includes.iter().any(|include| include.matches(&path)) {
+                includes.push(SourcePath::path(path));
+            }
+        }
+
+        Self {
+            paths: configuration.source_paths(),
+            includes,
+            configured_includes,
+            excludes: configuration.exclude_paths(),
+            stubs: configuration.stubs.clone(),
+        }
+    }
+
+    /// Returns the directories to scan for the sources, whether they contain user defined sources, and whether
+    /// they were discovered from the composer packages.
+    fn starting_paths(&self) -> Vec<(PathBuf, bool, bool)> {
+        let paths = self.paths.iter().map(|path| (path.base().to_path_buf(), true, false));
+        let includes = self
+            .includes
+            .iter()
+            .enumerate()
+            .map(|(index, include)| (include.base().to_path_buf(), false, index >= self.configured_includes));
+
+        paths.chain(includes).filter(|(path, _, _)| path.exists()).collect()
+    }
+
+    /// Checks if the given selected path contains, or is contained in, the user defined sources.
+    fn overlaps(&self, selected: &Path) -> bool {
+        self.paths.iter().any(|path| path.matches(selected) || path.base().starts_with(selected))
+    }
+
+    /// Returns whether the given absolute path is a user defined source, or a non-user defined one, or `None` if
+    /// it is not part of the sources, regardless of the exclusions.
+    ///
+    /// When the path is matched by several paths, and includes, the most specific one wins, and the last one
+    /// among equally specific ones, the includes being listed after the paths. The stubs are never part of the
+    /// sources, as they are loaded separately.
+    fn classify(&self, path: &Path) -> Option<bool> {
+        if self.stubs.as_ref().is_some_and(|stubs| path.starts_with(stubs)) {
+            return None;
+        }
+
+        let paths = self.paths.iter().map(|source| (source, true));
+        let includes = self.includes.iter().map(|include| (include, false));
+
+        paths
+            .chain(includes)
+            .enumerate()
+            .filter(|(_, (source, _))| source.matches(path))
+            .max_by_key(|(index, (source, _))| (source.specificity(), *index))
+            .map(|(_, (_, user_defined))| user_defined)
+    }
+
+    /// Checks if the given absolute path is excluded, which takes precedence over both the paths, and the includes.
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.excludes.iter().any(|exclude| exclude.matches(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("mago-source-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+
+        // The `.gitignore` files are only respected within a repository.
+        std::fs::create_dir_all(directory.join(".git")).unwrap();
+        for (path, content) in files {
+            let path = directory.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        directory.canonicalize().unwrap()
+    }
+
+    fn user_defined_sources(configuration: &SourceConfiguration, selection: &[PathBuf]) -> Vec<String> {
+        sources(configuration, selection).0
+    }
+
+    /// Returns the sorted names of the user defined, and of the external sources.
+    fn sources(configuration: &SourceConfiguration, selection: &[PathBuf]) -> (Vec<String>, Vec<String>) {
+        let interner = ThreadedInterner::new();
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let manager = runtime.block_on(load(&interner, configuration, false, selection)).unwrap();
+
+        let names = |sources: Vec<SourceIdentifier>| {
+            let mut names: Vec<_> = sources.iter().map(|source| interner.lookup(&source.0).to_string()).collect();
+            names.sort();
+            names
+        };
+
+        (names(manager.user_defined_source_ids().collect()), names(manager.external_source_ids().collect()))
+    }
+
+    const FILES: &[(&str, &str)] = &[
+        (".gitignore", "build/\n*.generated.php\n!keep.generated.php\n"),
+        (".magoignore", "src/Fixtures/\n"),
+        ("build/cache.php", "<?php"),
+        ("src/Foo.php", "<?php"),
+        ("src/Foo.generated.php", "<?php"),
+        ("src/keep.generated.php", "<?php"),
+        ("src/Fixtures/Broken.php", "<?php"),
+        ("src/Legacy/.gitignore", "Old.php\n"),
+        ("src/Legacy/Old.php", "<?php"),
+        ("src/Legacy/New.php", "<?php"),
+        ("src/Legacy/Nested/.magoignore", "*\n!Kept.php\n"),
+        ("src/Legacy/Nested/Kept.php", "<?php"),
+        ("src/Legacy/Nested/Skipped.php", "<?php"),
+    ];
+
+    #[test]
+    fn test_ignored_files_are_skipped() {
+        let root = directory("ignored", FILES);
+        let configuration = SourceConfiguration::from_root(root);
+
+        assert_eq!(
+            user_defined_sources(&configuration, &[]),
+            vec!["src/Foo.php", "src/Legacy/Nested/Kept.php", "src/Legacy/New.php", "src/keep.generated.php"]
+        );
+    }
+
+    #[test]
+    fn test_gitignore_files_can_be_disregarded() {
+        let root = directory("disregarded", FILES);
+        let configuration = SourceConfiguration { respect_gitignore: false, ..SourceConfiguration::from_root(root) };
+
+        assert_eq!(
+            user_defined_sources(&configuration, &[]),
+            vec![
+                "build/cache.php",
+                "src/Foo.generated.php",
+                "src/Foo.php",
+                "src/Legacy/Nested/Kept.php",
+                "src/Legacy/New.php",
+                "src/Legacy/Old.php",
+                "src/keep.generated.php",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_selected_paths_are_analyzed_even_when_ignored() {
+        let root = directory("selected", FILES);
+        let configuration = SourceConfiguration::from_root(root.clone());
+        let selection = vec![root.join("build"), root.join("src/Legacy/Old.php"), root.join("src/Legacy/Nested")];
+
+        assert_eq!(
+            user_defined_sources(&configuration, &selection),
+            vec!["build/cache.php", "src/Legacy/Nested/Kept.php", "src/Legacy/Old.php"]
+        );
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let files = runtime.block_on(user_defined_files(&configuration, &selection)).unwrap();
+        let mut names: Vec<_> = files.into_iter().map(|(name, _)| name).collect();
+        names.sort();
+
+        assert_eq!(names, vec!["build/cache.php", "src/Legacy/Nested/Kept.php", "src/Legacy/Old.php"]);
+    }
+
+    #[test]
+    fn test_unselected_sources_are_loaded_as_external() {
+        let root =
+            directory("selection", &[("src/Foo.php", "<?php"), ("src/Bar/Baz.php", "<?php"), ("lib/Qux.php", "<?php")]);
+        let configuration =
+            SourceConfiguration { paths: vec!["src".into()], ..SourceConfiguration::from_root(root.clone()) };
+
+        let (user_defined, external) = sources(&configuration, &[root.join("src/Bar")]);
+        assert_eq!(user_defined, vec!["src/Bar/Baz.php"]);
+        assert_eq!(external, vec!["src/Foo.php"]);
+
+        // Selected paths outside of the configured source paths are analyzed as well.
+        let (user_defined, external) = sources(&configuration, &[root.join("src/Foo.php"), root.join("lib")]);
+        assert_eq!(user_defined, vec!["lib/Qux.php", "src/Foo.php"]);
+        assert_eq!(external, vec!["src/Bar/Baz.php"]);
+    }
+
+    #[test]
+    fn test_selections_of_missing_paths_are_rejected() {
+        let root = directory("canonicalized", &[("src/Foo.php", "<?php")]);
+        let missing = root.join("src/Missing.php");
+
+        assert_eq!(canonicalize_selection(&[root.join("src/../src/Foo.php")]).unwrap(), vec![root.join("src/Foo.php")]);
+        assert_eq!(canonicalize_selection(&[root.join("src"), missing.clone()]).unwrap_err(), missing.as_path());
+    }
+
+    #[test]
+    fn test_changed_paths_are_classified_against_the_configured_sources() {
+        let root = directory("classified", &[("src/Foo.php", "<?php"), ("vendor/Bar.php", "<?php")]);
+        let configuration = SourceConfiguration {
+            paths: vec!["src".into()],
+            excludes: vec!["src/Generated".to_string()],
+            ..SourceConfiguration::from_root(root.clone())
+        };
+
+        assert!(is_user_defined_path(&configuration, &root.join("src/Foo.php")));
+        assert!(!is_user_defined_path(&configuration, &root.join("vendor/Bar.php")));
+
+        assert!(!is_excluded_path(&configuration, &root.join("src/Foo.php")));
+        assert!(is_excluded_path(&configuration, &root.join("src/Generated/Proxy.php")));
+        assert!(is_excluded_path(&configuration, &root.join("vendor/Bar.php")));
+    }
+
+    const PATTERN_FILES: &[(&str, &str)] = &[
+        ("src/App/Entity/User.php", "<?php"),
+        ("src/App/Entity/generated/UserProxy.php", "<?php"),
+        ("src/App/Service/Mailer.php", "<?php"),
+        ("src/Entity/Post.php", "<?php"),
+        ("src/Entity/post.blade.php", "<?php"),
+        ("modules/Billing/src/Invoice.php", "<?php"),
+        ("modules/Billing/tests/InvoiceTest.php", "<?php"),
+        ("modules/Legacy/src/Order.php", "<?php"),
+        ("modules/Legacy/src/generated/OrderProxy.php", "<?php"),
+    ];
+
+    #[test]
+    fn test_source_paths_can_be_glob_patterns() {
+        let root = directory("patterns", PATTERN_FILES);
+        let configuration = SourceConfiguration {
+            paths: vec!["src/**/Entity".into(), "modules/*/src".into()],
+            includes: vec!["modules/Legacy/src".into()],
+            excludes: vec!["**/generated/**".to_string(), "**/*.blade.php".to_string()],
+            ..SourceConfiguration::from_root(root)
+        };
+
+        let (user_defined, external) = sources(&configuration, &[]);
+
+        assert_eq!(
+            user_defined,
+            vec!["modules/Billing/src/Invoice.php", "src/App/Entity/User.php", "src/Entity/Post.php"]
+        );
+        // The include is more specific than the pattern of the paths, and the exclusions apply to it as well.
+        assert_eq!(external, vec!["modules/Legacy/src/Order.php"]);
+    }
+
+    #[test]
+    fn test_glob_patterns_are_case_sensitive_unless_configured_otherwise() {
+        let root = directory("case", PATTERN_FILES);
+        let configuration =
+            SourceConfiguration { paths: vec!["src/**/entity".into()], ..SourceConfiguration::from_root(root) };
+
+        assert!(user_defined_sources(&configuration, &[]).is_empty());
+
+        let configuration = SourceConfiguration { case_insensitive: true, ..configuration };
+
+        assert_eq!(
+            user_defined_sources(&configuration, &[]),
+            vec![
+                "src/App/Entity/User.php",
+                "src/App/Entity/generated/UserProxy.php",
+                "src/Entity/Post.php",
+                "src/Entity/post.blade.php",
+            ]
+        );
+    }
 }
@@ -9,7 +9,7 @@ use crate::error::Error;
 /// Applies changes to the source file.
 ///
 /// If `dry_run` is `true`, it compares the original and modified content,
-/// displays a diff with context around changes, and does not write to disk.
+/// displays a unified diff with context around changes, and does not write to disk.
 ///
 /// If `dry_run` is `false`, it writes the formatted content to the source manager.
 ///
@@ -20,6 +20,7 @@ use crate::error::Error;
 /// * `source` - Reference to the `Source` being processed.
 /// * `changed_code` - The formatted content as a `String`.
 /// * `dry_run` - Boolean flag indicating whether to perform a dry run.
+/// * `use_colors` - Whether to use colors when displaying the diff.
 ///
 /// # Returns
 ///
@@ -30,6 +31,7 @@ pub fn apply_changes(
     source: &Source,
     changed_code: String,
     dry_run: bool,
+    use_colors: bool,
 ) -> Result<bool, Error> {
     let original_content = interner.lookup(&source.content);
     if original_content == changed_code {
@@ -38,13 +40,10 @@ pub fn apply_changes(
 
     if dry_run {
         let source_name = interner.lookup(&source.identifier.0);
-        let patch = diffy::create_patch(original_content, changed_code.as_str());
+        let diff = unified_diff(source_name, original_content, &changed_code, use_colors);
 
         mago_feedback::progress::GLOBAL_PROGRESS_MANAGER.suspend(|| {
-            let formatter = PatchFormatter::new().with_color();
-
-            println!("diff of '{}':", source_name);
-            println!("{}", formatter.fmt_patch(&patch));
+            print!("{}", diff);
         });
     } else {
         source_manager.write(source.identifier, changed_code)?;
@@ -52,3 +51,46 @@ pub fn apply_changes(
 
     Ok(true)
 }
+
+/// Returns the unified diff between the original and the changed content of a source.
+///
+/// The diff headers use the `a/` and `b/` prefixes used by git, so that they can be applied with `git apply`.
+///
+/// # Arguments
+///
+/// * `source_name` - The name of the source, used in the diff headers.
+/// * `original` - The original content of the source.
+/// * `changed` - The changed content of the source.
+/// * `use_colors` - Whether to use colors in the diff.
+pub fn unified_diff(source_name: &str, original: &str, changed: &str, use_colors: bool) -> String {
+    let patch = diffy::create_patch(original, changed);
+    let formatter = if use_colors { PatchFormatter::new().with_color() } else { PatchFormatter::new() };
+
+    let diff = formatter.fmt_patch(&patch).to_string();
+
+    diff.replacen("--- original\n+++ modified\n", &format!("--- a/{}\n+++ b/{}\n", source_name, source_name), 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_uses_git_headers() {
+        let original = "<?php\n\necho 1;\necho 2;\n";
+        let changed = "<?php\n\necho 1;\necho 3;\n";
+
+        let diff = unified_diff("src/a.php", original, changed, false);
+
+        assert_eq!(diff, "--- a/src/a.php\n+++ b/src/a.php\n@@ -1,4 +1,4 @@\n <?php\n\n echo 1;\n-echo 2;\n+echo 3;\n");
+        assert_eq!(diffy::apply(original, &diffy::Patch::from_str(&diff).unwrap()).unwrap(), changed);
+    }
+
+    #[test]
+    fn test_colored_unified_diff_uses_git_headers() {
+        let diff = unified_diff("src/a.php", "<?php echo 1;\n", "<?php echo 2;\n", true);
+
+        assert!(diff.contains("--- a/src/a.php\n+++ b/src/a.php\n"));
+        assert!(diff.contains('\u{1b}'));
+    }
+}